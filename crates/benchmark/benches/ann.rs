@@ -0,0 +1,144 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! ANN search latency of an in-process usearch index, without the vector-store around it, as a
+//! baseline for the latencies measured against a cluster.
+
+mod profiler;
+
+use crate::profiler::Flamegraph;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use std::hint::black_box;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+use usearch::Index;
+use usearch::IndexOptions;
+use usearch::MetricKind;
+
+const ENV_INDEX_SIZE: &str = "BENCHES_INDEX_SIZE";
+const ENV_CONCURRENCY: &str = "BENCHES_CONCURRENCY";
+
+const DIMENSIONS: [usize; 3] = [128, 768, 1536];
+const LIMITS: [usize; 3] = [1, 10, 100];
+/// Query vectors used in turn, so the searches do not hit the same part of the graph.
+const QUERIES: usize = 1000;
+
+fn env_or(key: &str, default: usize) -> usize {
+    std::env::var(key)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(default)
+}
+
+fn index_size() -> usize {
+    env_or(ENV_INDEX_SIZE, 20_000)
+}
+
+fn concurrency() -> usize {
+    env_or(
+        ENV_CONCURRENCY,
+        thread::available_parallelism().map_or(1, |n| n.get()),
+    )
+}
+
+fn random_vectors(count: usize, dimensions: usize) -> Vec<Vec<f32>> {
+    (0..count)
+        .map(|_| {
+            (0..dimensions)
+                .map(|_| rand::random_range(-1.0..1.0))
+                .collect()
+        })
+        .collect()
+}
+
+/// Builds an index of random vectors, adding them from all threads.
+fn build_index(dimensions: usize) -> Index {
+    let size = index_size();
+    let threads = concurrency();
+    let index = Index::new(&IndexOptions {
+        dimensions,
+        metric: MetricKind::L2sq,
+        ..Default::default()
+    })
+    .unwrap();
+    index.reserve_capacity_and_threads(size, threads).unwrap();
+    let vectors = random_vectors(size, dimensions);
+    thread::scope(|scope| {
+        for worker in 0..threads {
+            let index = &index;
+            let vectors = &vectors;
+            scope.spawn(move || {
+                for key in (worker..size).step_by(threads) {
+                    index.add(key as u64, &vectors[key]).unwrap();
+                }
+            });
+        }
+    });
+    index
+}
+
+/// Runs `iters` searches on `concurrency` threads and returns the time it took.
+fn search_concurrently(
+    index: &Index,
+    queries: &[Vec<f32>],
+    limit: usize,
+    concurrency: usize,
+    iters: u64,
+) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for worker in 0..concurrency {
+            scope.spawn(move || {
+                for it in (worker as u64..iters).step_by(concurrency) {
+                    let query = &queries[it as usize % queries.len()];
+                    black_box(index.search(query, limit).unwrap());
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn search(c: &mut Criterion) {
+    let concurrency = concurrency();
+    for dimensions in DIMENSIONS {
+        let index = build_index(dimensions);
+        let queries = random_vectors(QUERIES, dimensions);
+
+        let mut group = c.benchmark_group(format!("ann-{dimensions}"));
+        group.throughput(Throughput::Elements(1));
+        for limit in LIMITS {
+            group.bench_with_input(BenchmarkId::new("search", limit), &limit, |b, &limit| {
+                let mut it = 0;
+                b.iter(|| {
+                    it = (it + 1) % queries.len();
+                    index.search(&queries[it], limit).unwrap()
+                });
+            });
+            group.bench_with_input(
+                BenchmarkId::new(format!("search-concurrent-{concurrency}"), limit),
+                &limit,
+                |b, &limit| {
+                    b.iter_custom(|iters| {
+                        search_concurrently(&index, &queries, limit, concurrency, iters)
+                    });
+                },
+            );
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().with_profiler(Flamegraph::default());
+    targets = search
+);
+criterion_main!(benches);
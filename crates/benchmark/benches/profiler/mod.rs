@@ -0,0 +1,47 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use criterion::profiler::Profiler;
+use pprof::ProfilerGuard;
+use pprof::ProfilerGuardBuilder;
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+
+const FREQUENCY: i32 = 1000;
+
+/// Writes `flamegraph.svg` of every benchmark run with `--profile-time <SECONDS>` into its
+/// criterion directory, e.g. `target/criterion/ann-128/search/10/profile/`.
+#[derive(Default)]
+pub(crate) struct Flamegraph {
+    guard: Option<ProfilerGuard<'static>>,
+}
+
+impl Profiler for Flamegraph {
+    fn start_profiling(&mut self, _benchmark_id: &str, _benchmark_dir: &Path) {
+        self.guard = Some(
+            ProfilerGuardBuilder::default()
+                .frequency(FREQUENCY)
+                .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+                .build()
+                .expect("failed to start profiling"),
+        );
+    }
+
+    fn stop_profiling(&mut self, _benchmark_id: &str, benchmark_dir: &Path) {
+        let Some(guard) = self.guard.take() else {
+            return;
+        };
+        fs::create_dir_all(benchmark_dir).expect("failed to create the profile directory");
+        let file = File::create(benchmark_dir.join("flamegraph.svg"))
+            .expect("failed to create the flamegraph");
+        guard
+            .report()
+            .build()
+            .expect("failed to build the profile report")
+            .flamegraph(file)
+            .expect("failed to write the flamegraph");
+    }
+}
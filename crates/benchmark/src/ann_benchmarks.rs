@@ -0,0 +1,110 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Writes results in the layout of ann-benchmarks (https://github.com/erikbern/ann-benchmarks).
+//! ann-benchmarks stores a run as an HDF5 file with attributes and `times`, `neighbors` and
+//! `distances` datasets; here the same attributes and datasets are written as a JSON file, at
+//! `<dir>/<dataset>/<count>/<algo>/<name>.json`, to be converted or compared with published
+//! results without a native HDF5 library.
+
+use crate::sweep::SearchResult;
+use serde::Serialize;
+use std::fs;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::time::Duration;
+use tracing::info;
+
+pub(crate) struct Run<'a> {
+    pub(crate) algo: &'static str,
+    pub(crate) name: String,
+    pub(crate) dataset: &'a str,
+    pub(crate) distance: &'static str,
+    pub(crate) count: usize,
+    pub(crate) build_time: Duration,
+    /// Memory used by the index in bytes.
+    pub(crate) index_size: usize,
+    pub(crate) results: &'a [SearchResult],
+}
+
+#[derive(Serialize)]
+struct Attrs<'a> {
+    algo: &'a str,
+    batch_mode: bool,
+    best_search_time: f64,
+    build_time: f64,
+    candidates: f64,
+    count: usize,
+    dataset: &'a str,
+    distance: &'a str,
+    expect_extra: bool,
+    /// In kB, as ann-benchmarks reports it.
+    index_size: usize,
+    name: &'a str,
+    run_count: usize,
+}
+
+#[derive(Serialize)]
+struct Output<'a> {
+    attrs: Attrs<'a>,
+    times: Vec<f64>,
+    neighbors: Vec<&'a [u64]>,
+    distances: Vec<&'a [f32]>,
+}
+
+pub(crate) fn write(dir: &Path, run: &Run) {
+    let times: Vec<_> = run
+        .results
+        .iter()
+        .map(|result| result.time.as_secs_f64())
+        .collect();
+    let queries = run.results.len().max(1) as f64;
+    let output = Output {
+        attrs: Attrs {
+            algo: run.algo,
+            batch_mode: false,
+            best_search_time: times.iter().sum::<f64>() / queries,
+            build_time: run.build_time.as_secs_f64(),
+            candidates: run
+                .results
+                .iter()
+                .map(|result| result.keys.len())
+                .sum::<usize>() as f64
+                / queries,
+            count: run.count,
+            dataset: run.dataset,
+            distance: run.distance,
+            expect_extra: false,
+            index_size: run.index_size / 1024,
+            name: &run.name,
+            run_count: 1,
+        },
+        times,
+        neighbors: run
+            .results
+            .iter()
+            .map(|result| result.keys.as_slice())
+            .collect(),
+        distances: run
+            .results
+            .iter()
+            .map(|result| result.distances.as_slice())
+            .collect(),
+    };
+
+    let dir = dir
+        .join(run.dataset)
+        .join(run.count.to_string())
+        .join(run.algo);
+    fs::create_dir_all(&dir).expect("failed to create the ann-benchmarks directory");
+    let path = dir.join(format!("{}.json", run.name));
+    serde_json::to_writer(
+        BufWriter::new(File::create(&path).expect("failed to create an ann-benchmarks file")),
+        &output,
+    )
+    .expect("failed to write an ann-benchmarks file");
+    info!("Written ann-benchmarks results to {path:?}");
+}
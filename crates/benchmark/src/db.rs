@@ -23,7 +23,7 @@ use tokio::time;
 use tracing::error;
 use tracing::info;
 
-const VECTOR_ID: &str = "vector_id";
+pub(crate) const VECTOR_ID: &str = "vector_id";
 const VECTOR: &str = "vector";
 const BUCKET: &str = "bucket";
 
@@ -0,0 +1,330 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Runs the whole flow against a live ScyllaDB and vector-store deployment: populates a table
+//! from a dataset, builds an index and then measures query latency and ingestion lag while new
+//! rows are written, for a summary report used to sign off a release.
+
+use crate::SearchMeasure;
+use crate::db;
+use crate::db::Scylla;
+use crate::vs;
+use dataset::Data;
+use httpapi::ColumnName;
+use httpapi::IndexName;
+use httpapi::KeyspaceName;
+use httpclient::HttpClient;
+use std::fmt::Write as _;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::fs;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+use tokio::time;
+use tokio::time::Instant;
+use tracing::info;
+
+/// Ids of the rows written during the measurement start here, above the ids of datasets.
+const FIRST_WRITTEN_ID: i64 = 1 << 40;
+const LAG_PROBE_INTERVAL: Duration = Duration::from_secs(1);
+const LAG_POLL_INTERVAL: Duration = Duration::from_millis(10);
+const LAG_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub(crate) struct Options {
+    pub(crate) scylla: SocketAddr,
+    pub(crate) user: Option<String>,
+    pub(crate) passwd_path: Option<PathBuf>,
+    pub(crate) keyspace: String,
+    pub(crate) table: String,
+    pub(crate) index: String,
+    pub(crate) index_options: String,
+    pub(crate) rf: usize,
+    pub(crate) upload_concurrency: usize,
+    pub(crate) search_concurrency: usize,
+    pub(crate) limit: usize,
+    /// Rows written per second during the measurement.
+    pub(crate) write_rate: u32,
+    pub(crate) duration: Duration,
+    pub(crate) report: Option<PathBuf>,
+}
+
+/// Times from writing a row until vector-store returns it.
+struct Lags {
+    lags: Vec<Duration>,
+    timeouts: usize,
+}
+
+impl Lags {
+    fn percentile(&self, percentile: usize) -> Duration {
+        if self.lags.is_empty() {
+            return Duration::ZERO;
+        }
+        self.lags[(self.lags.len() - 1) * percentile / 100]
+    }
+}
+
+pub(crate) async fn run(clients: Vec<HttpClient>, dataset: Data, options: Options) {
+    assert!(!clients.is_empty());
+    let dimension = dataset.dimension().await;
+    let scylla = connect(&options).await;
+
+    info!("Populating table {}.{}", options.keyspace, options.table);
+    let start = Instant::now();
+    scylla
+        .create_table(&options.keyspace, &options.table, dimension, options.rf)
+        .await;
+    scylla
+        .upload_vectors(
+            &options.keyspace,
+            &options.table,
+            dataset.buckets(),
+            dataset.vector_stream().await,
+            options.upload_concurrency,
+        )
+        .await;
+    let populate = start.elapsed();
+    info!("Populating took {populate:.2?}");
+
+    info!("Building index {}.{}", options.keyspace, options.index);
+    let start = Instant::now();
+    scylla
+        .create_index(
+            &options.keyspace,
+            &options.table,
+            &options.index,
+            false,
+            &options.index_options,
+        )
+        .await;
+    vs::wait_for_indexes_ready(&options.keyspace, &options.index, &clients).await;
+    let indexing = start.elapsed();
+    info!("Indexing took {indexing:.2?}");
+
+    // The search statement is prepared when connecting, so it needs the table to exist.
+    let scylla = connect(&options).await;
+    let queries = Arc::new(dataset.queries(None, options.limit).await);
+    let stop = Instant::now() + options.duration;
+
+    // Rows are written through a channel consumed by the usual upload, which keeps at most
+    // `upload_concurrency` writes in flight.
+    let (tx, rx) = mpsc::channel(options.upload_concurrency);
+    let upload = tokio::spawn({
+        let scylla = scylla.clone();
+        let keyspace = options.keyspace.clone();
+        let table = options.table.clone();
+        let concurrency = options.upload_concurrency;
+        async move {
+            scylla
+                .upload_vectors(&keyspace, &table, Default::default(), rx, concurrency)
+                .await
+        }
+    });
+
+    info!(
+        "Measuring for {:?} with {} rows/s written",
+        options.duration, options.write_rate
+    );
+    let start = Instant::now();
+    let writer = tokio::spawn({
+        let tx = tx.clone();
+        let write_rate = options.write_rate;
+        async move {
+            let mut interval = time::interval(Duration::from_secs(1) / write_rate);
+            let mut written = 0;
+            while interval.tick().await < stop {
+                let id = FIRST_WRITTEN_ID + 1 + written;
+                if tx.send((id, random_vector(dimension))).await.is_err() {
+                    break;
+                }
+                written += 1;
+            }
+            written
+        }
+    });
+    let prober = tokio::spawn({
+        let tx = tx.clone();
+        let client = clients[0].clone();
+        let keyspace = options.keyspace.clone().into();
+        let index = options.index.clone().into();
+        async move { probe_lags(tx, client, keyspace, index, dimension, stop).await }
+    });
+    drop(tx);
+
+    let mut searches = JoinSet::new();
+    for _ in 0..options.search_concurrency {
+        let scylla = scylla.clone();
+        let queries = Arc::clone(&queries);
+        searches.spawn(async move {
+            let mut measure = SearchMeasure::new();
+            while Instant::now() < stop {
+                let query = crate::random(&queries);
+                let start = Instant::now();
+                let recall = scylla.search(None, query).await;
+                measure.record(start.elapsed(), recall);
+            }
+            measure
+        });
+    }
+    let mut search = SearchMeasure::new();
+    while let Some(measure) = searches.join_next().await {
+        search.append(&measure.unwrap());
+    }
+    let written = writer.await.unwrap();
+    let lags = prober.await.unwrap();
+    upload.await.unwrap();
+    let duration = start.elapsed();
+
+    let report = report(Report {
+        options: &options,
+        populate,
+        indexing,
+        duration,
+        written,
+        lags: &lags,
+        search: &search,
+    });
+    report.lines().for_each(|line| info!("{line}"));
+    if let Some(path) = &options.report {
+        fs::write(path, &report)
+            .await
+            .expect("failed to write the report");
+        info!("Report written to {path:?}");
+    }
+}
+
+async fn connect(options: &Options) -> Scylla {
+    Scylla::new(
+        options.scylla,
+        options.user.clone(),
+        options.passwd_path.clone(),
+        &options.keyspace,
+        &options.table,
+    )
+    .await
+}
+
+fn random_vector(dimension: usize) -> Box<[f32]> {
+    (0..dimension)
+        .map(|_| rand::random_range(-1.0..1.0))
+        .collect()
+}
+
+/// Writes a row every [`LAG_PROBE_INTERVAL`] and measures how long it takes until an ANN query
+/// for its exact vector returns it.
+async fn probe_lags(
+    tx: mpsc::Sender<(i64, Box<[f32]>)>,
+    client: HttpClient,
+    keyspace: KeyspaceName,
+    index: IndexName,
+    dimension: usize,
+    stop: Instant,
+) -> Lags {
+    let column: ColumnName = db::VECTOR_ID.into();
+    let mut lags = Lags {
+        lags: Vec::new(),
+        timeouts: 0,
+    };
+    let mut interval = time::interval(LAG_PROBE_INTERVAL);
+    let mut id = FIRST_WRITTEN_ID;
+    while interval.tick().await < stop {
+        // Probes take ids below the rows of the writer.
+        id -= 1;
+        let vector = random_vector(dimension);
+        let start = Instant::now();
+        if tx.send((id, vector.clone())).await.is_err() {
+            break;
+        }
+        loop {
+            let (keys, _, _) = client
+                .ann(
+                    &keyspace,
+                    &index,
+                    vector.to_vec().into(),
+                    None,
+                    NonZeroUsize::MIN.into(),
+                )
+                .await;
+            if keys
+                .get(&column)
+                .is_some_and(|ids| ids.iter().any(|found| found.as_i64() == Some(id)))
+            {
+                lags.lags.push(start.elapsed());
+                break;
+            }
+            if start.elapsed() > LAG_TIMEOUT {
+                lags.timeouts += 1;
+                break;
+            }
+            time::sleep(LAG_POLL_INTERVAL).await;
+        }
+    }
+    lags.lags.sort();
+    lags
+}
+
+struct Report<'a> {
+    options: &'a Options,
+    populate: Duration,
+    indexing: Duration,
+    duration: Duration,
+    written: i64,
+    lags: &'a Lags,
+    search: &'a SearchMeasure,
+}
+
+/// Formats the summary as markdown.
+fn report(report: Report) -> String {
+    let Report {
+        options,
+        populate,
+        indexing,
+        duration,
+        written,
+        lags,
+        search,
+    } = report;
+    let secs = duration.as_secs_f64();
+    let mut out = String::new();
+    writeln!(out, "# End-to-end benchmark").unwrap();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "Index {}.{} with options {}, measured for {duration:.1?}.",
+        options.keyspace, options.index, options.index_options
+    )
+    .unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "| metric | value |").unwrap();
+    writeln!(out, "|---|---|").unwrap();
+    let mut row = |metric: &str, value: String| writeln!(out, "| {metric} | {value} |").unwrap();
+    row("populate time", format!("{populate:.1?}"));
+    row("index build time", format!("{indexing:.1?}"));
+    row("rows written/s", format!("{:.1}", written as f64 / secs));
+    row("ingestion lag probes", lags.lags.len().to_string());
+    row("ingestion lag P50", format!("{:.1?}", lags.percentile(50)));
+    row("ingestion lag P99", format!("{:.1?}", lags.percentile(99)));
+    row("ingestion lag max", format!("{:.1?}", lags.percentile(100)));
+    row("ingestion lag timeouts", lags.timeouts.to_string());
+    row("queries", search.count.to_string());
+    row("QPS", format!("{:.1}", search.count as f64 / secs));
+    for percentile in [50, 90, 99] {
+        row(
+            &format!("latency P{percentile}"),
+            format!("{:.1?}", search.histogram.percentile(percentile as f64)),
+        );
+    }
+    row("latency max", format!("{:.1?}", search.latency_max));
+    row(
+        "recall avg",
+        format!(
+            "{:.1}",
+            search.recall_sum * 100.0 / search.count.max(1) as f64
+        ),
+    );
+    out
+}
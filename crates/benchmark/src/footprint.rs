@@ -0,0 +1,108 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Reports the memory used per vector by in-process usearch indexes built from a dataset, for
+//! every combination of quantization and connectivity.
+//!
+//! usearch reports only the total memory of an index, so it is split into:
+//! - vectors: the size of a stored vector,
+//! - graph: the rest of the serialized index, i.e. keys, levels and neighbor lists of the nodes,
+//! - key map: the memory not in the serialized index, i.e. the key lookup table and the
+//!   allocation overhead.
+
+use crate::quantization::Quantization;
+use dataset::Data;
+use itertools::Itertools;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::thread;
+use tracing::info;
+use usearch::Index;
+use usearch::IndexOptions;
+
+const CSV_HEADER: &str =
+    "quantization,connectivity,rows,total_bytes,vector_bytes,graph_bytes,key_map_bytes";
+
+/// Bytes per vector.
+struct Footprint {
+    total: f64,
+    vectors: f64,
+    graph: f64,
+    key_map: f64,
+}
+
+impl Footprint {
+    fn new(index: &Index, vector_bytes: usize) -> Self {
+        let rows = index.size().max(1) as f64;
+        let memory = index.memory_usage() as f64;
+        let serialized = index.serialized_length() as f64;
+        Self {
+            total: memory / rows,
+            vectors: vector_bytes as f64,
+            graph: serialized / rows - vector_bytes as f64,
+            key_map: (memory - serialized) / rows,
+        }
+    }
+}
+
+/// Builds an index of every combination of `quantizations` and `connectivities` from at most
+/// `rows` vectors of the dataset and writes one CSV row per index to `output`.
+pub(crate) async fn run(
+    dataset: Data,
+    quantizations: Vec<Quantization>,
+    connectivities: Vec<usize>,
+    rows: Option<usize>,
+    threads: usize,
+    output: &Path,
+) {
+    let dimensions = dataset.dimension().await;
+    let mut stream = dataset.vector_stream().await;
+    let mut vectors = Vec::new();
+    while rows.is_none_or(|rows| vectors.len() < rows)
+        && let Some(vector) = stream.recv().await
+    {
+        vectors.push(vector);
+    }
+    info!("Loaded {} vectors", vectors.len());
+
+    let mut csv = BufWriter::new(File::create(output).expect("failed to create the output file"));
+    writeln!(csv, "{CSV_HEADER}").unwrap();
+    for (quantization, connectivity) in quantizations.into_iter().cartesian_product(connectivities)
+    {
+        let index = Index::new(&IndexOptions {
+            connectivity,
+            ..quantization.index_options(dimensions)
+        })
+        .expect("failed to create an index");
+        index
+            .reserve_capacity_and_threads(vectors.len(), threads)
+            .unwrap();
+        thread::scope(|scope| {
+            for chunk in vectors.chunks(vectors.len().div_ceil(threads).max(1)) {
+                let index = &index;
+                scope.spawn(move || {
+                    for (key, vector) in chunk {
+                        quantization.add(index, *key as u64, vector);
+                    }
+                });
+            }
+        });
+
+        let footprint = Footprint::new(&index, quantization.vector_bytes(dimensions));
+        let row = format!(
+            "{quantization:?},{connectivity},{},{:.1},{:.1},{:.1},{:.1}",
+            index.size(),
+            footprint.total,
+            footprint.vectors,
+            footprint.graph,
+            footprint.key_map
+        );
+        info!("{row}");
+        writeln!(csv, "{row}").unwrap();
+    }
+    csv.flush().unwrap();
+}
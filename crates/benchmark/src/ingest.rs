@@ -0,0 +1,199 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Measures how fast in-process usearch indexes are built from a dataset file, for every
+//! combination of quantization and number of threads, to size the hardware for backfills.
+
+use crate::quantization::Quantization;
+use dataset::Data;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::RwLock;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+use sysinfo::Pid;
+use sysinfo::ProcessRefreshKind;
+use sysinfo::ProcessesToUpdate;
+use sysinfo::System;
+use tokio::sync::mpsc;
+use tracing::info;
+use usearch::Index;
+use usearch::IndexOptions;
+
+/// Capacity added to the index every time it is full, as the number of rows is not known upfront.
+const RESERVE_INCREMENT: usize = 1_000_000;
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+const CSV_HEADER: &str =
+    "quantization,threads,rows,secs,rows_per_sec,cpu_cores,index_bytes,peak_rss_bytes";
+
+struct Measure {
+    quantization: Quantization,
+    threads: usize,
+    rows: usize,
+    duration: Duration,
+    cpu: Duration,
+    index_bytes: usize,
+    peak_rss: u64,
+}
+
+impl Measure {
+    fn csv(&self) -> String {
+        let secs = self.duration.as_secs_f64();
+        format!(
+            "{:?},{},{},{:.3},{:.1},{:.2},{},{}",
+            self.quantization,
+            self.threads,
+            self.rows,
+            secs,
+            self.rows as f64 / secs,
+            self.cpu.as_secs_f64() / secs,
+            self.index_bytes,
+            self.peak_rss
+        )
+    }
+}
+
+/// Cpu time and memory of this process.
+struct Process {
+    pid: Pid,
+    system: System,
+}
+
+impl Process {
+    fn new() -> Self {
+        Self {
+            pid: sysinfo::get_current_pid().expect("failed to get the pid"),
+            system: System::new(),
+        }
+    }
+
+    /// Returns the cpu time used so far and the resident memory.
+    fn sample(&mut self) -> (Duration, u64) {
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[self.pid]),
+            true,
+            ProcessRefreshKind::nothing().with_cpu().with_memory(),
+        );
+        let process = self
+            .system
+            .process(self.pid)
+            .expect("failed to get process");
+        (
+            Duration::from_millis(process.accumulated_cpu_time()),
+            process.memory(),
+        )
+    }
+}
+
+/// Builds an index of every combination of `quantizations` and `threads` and writes one CSV row
+/// per index to `output`. `rows` limits the number of rows read from the dataset.
+pub(crate) async fn run(
+    dataset: Data,
+    quantizations: Vec<Quantization>,
+    threads: Vec<usize>,
+    rows: Option<usize>,
+    output: &Path,
+) {
+    let dimensions = dataset.dimension().await;
+    let mut csv = BufWriter::new(File::create(output).expect("failed to create the output file"));
+    writeln!(csv, "{CSV_HEADER}").unwrap();
+    for quantization in quantizations {
+        for &threads in &threads {
+            let stream = dataset.vector_stream().await;
+            let options = quantization.index_options(dimensions);
+            let measure = tokio::task::spawn_blocking(move || {
+                ingest(stream, &options, quantization, threads, rows)
+            })
+            .await
+            .unwrap();
+            info!("{}", measure.csv());
+            writeln!(csv, "{}", measure.csv()).unwrap();
+        }
+    }
+    csv.flush().unwrap();
+}
+
+fn ingest(
+    stream: mpsc::Receiver<(i64, Box<[f32]>)>,
+    options: &IndexOptions,
+    quantization: Quantization,
+    threads: usize,
+    rows: Option<usize>,
+) -> Measure {
+    let index = Index::new(options).expect("failed to create an index");
+    // usearch does not allow adding vectors while the capacity is reserved. The rows are counted
+    // when taken from the stream, so the capacity is reserved before any of them is added.
+    let reserve = RwLock::new(());
+    let stream = Mutex::new((stream, 0));
+    let done = AtomicBool::new(false);
+
+    let mut process = Process::new();
+    let (cpu_start, mut peak_rss) = process.sample();
+    let start = Instant::now();
+    index
+        .reserve_capacity_and_threads(rows.unwrap_or(RESERVE_INCREMENT), threads)
+        .unwrap();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    loop {
+                        let (key, vector) = {
+                            let mut stream = stream.lock().unwrap();
+                            if rows.is_some_and(|rows| stream.1 >= rows) {
+                                break;
+                            }
+                            let Some(row) = stream.0.blocking_recv() else {
+                                break;
+                            };
+                            stream.1 += 1;
+                            if stream.1 > index.capacity() {
+                                let _lock = reserve.write().unwrap();
+                                index
+                                    .reserve_capacity_and_threads(
+                                        index.capacity() + RESERVE_INCREMENT,
+                                        threads,
+                                    )
+                                    .unwrap();
+                            }
+                            row
+                        };
+                        let _lock = reserve.read().unwrap();
+                        quantization.add(&index, key as u64, &vector);
+                    }
+                })
+            })
+            .collect();
+        scope.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                peak_rss = peak_rss.max(process.sample().1);
+                thread::sleep(SAMPLE_INTERVAL);
+            }
+        });
+        workers
+            .into_iter()
+            .for_each(|worker| worker.join().unwrap());
+        done.store(true, Ordering::Relaxed);
+    });
+    let duration = start.elapsed();
+    let (cpu_end, rss) = process.sample();
+
+    Measure {
+        quantization,
+        threads,
+        rows: index.size(),
+        duration,
+        cpu: cpu_end.saturating_sub(cpu_start),
+        index_bytes: index.memory_usage(),
+        peak_rss: peak_rss.max(rss),
+    }
+}
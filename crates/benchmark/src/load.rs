@@ -0,0 +1,291 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Drives running vector-store nodes with ANN queries over HTTP, for capacity planning.
+//!
+//! In the closed loop `concurrency` workers send a query as soon as the previous one finished.
+//! In the open loop queries arrive at a fixed rate regardless of the responses, and the latency
+//! is measured from the planned arrival, so queueing in an overloaded node is not hidden.
+
+use crate::SearchMeasure;
+use httpapi::IndexName;
+use httpapi::KeyspaceName;
+use httpapi::Limit;
+use httpclient::HttpClient;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tokio::time;
+use tokio::time::Instant;
+use tracing::info;
+
+/// An index to query, parsed from `keyspace.index`.
+#[derive(Clone)]
+pub(crate) struct Index {
+    keyspace: KeyspaceName,
+    index: IndexName,
+}
+
+pub(crate) fn parse_index(value: &str) -> Result<Index, String> {
+    let (keyspace, index) = value
+        .split_once('.')
+        .ok_or_else(|| format!("expected keyspace.index, got {value}"))?;
+    Ok(Index {
+        keyspace: keyspace.to_string().into(),
+        index: index.to_string().into(),
+    })
+}
+
+/// A limit of queries with its weight in the mix, parsed from `limit[:weight]`.
+#[derive(Clone, Copy)]
+pub(crate) struct WeightedLimit {
+    limit: NonZeroUsize,
+    weight: f64,
+}
+
+pub(crate) fn parse_weighted_limit(value: &str) -> Result<WeightedLimit, String> {
+    let (limit, weight) = value.split_once(':').unwrap_or((value, "1"));
+    Ok(WeightedLimit {
+        limit: limit
+            .parse()
+            .map_err(|err| format!("invalid limit {limit}: {err}"))?,
+        weight: weight
+            .parse()
+            .map_err(|err| format!("invalid weight {weight}: {err}"))?,
+    })
+}
+
+/// Samples indexes of `weights` with probabilities proportional to them.
+struct Weighted {
+    cdf: Vec<f64>,
+}
+
+impl Weighted {
+    fn new(weights: impl IntoIterator<Item = f64>) -> Self {
+        let mut sum = 0.0;
+        let cdf = weights
+            .into_iter()
+            .map(|weight| {
+                sum += weight;
+                sum
+            })
+            .collect();
+        Self { cdf }
+    }
+
+    /// Zipfian weights of `count` items with the exponent `s`; 0 selects items uniformly.
+    fn zipf(count: usize, s: f64) -> Self {
+        Self::new((1..=count).map(|rank| 1.0 / (rank as f64).powf(s)))
+    }
+
+    fn sample(&self) -> usize {
+        let total = self.cdf.last().copied().unwrap_or_default();
+        let point = rand::random_range(0.0..total);
+        self.cdf
+            .partition_point(|&sum| sum <= point)
+            .min(self.cdf.len() - 1)
+    }
+}
+
+pub(crate) struct Options {
+    pub(crate) indexes: Vec<Index>,
+    pub(crate) zipf: f64,
+    pub(crate) limits: Vec<WeightedLimit>,
+    pub(crate) dimensions: usize,
+    pub(crate) concurrency: usize,
+    /// Queries per second of the open loop; the closed loop is used when not set.
+    pub(crate) rate: Option<u32>,
+    pub(crate) duration: Duration,
+}
+
+struct Generator {
+    clients: Vec<HttpClient>,
+    next_client: AtomicUsize,
+    indexes: Vec<Index>,
+    index_weights: Weighted,
+    limits: Vec<Limit>,
+    limit_weights: Weighted,
+    dimensions: usize,
+}
+
+impl Generator {
+    /// Sends a random query to the index `idx` and returns whether it succeeded.
+    async fn query(&self, idx: usize) -> bool {
+        let client =
+            &self.clients[self.next_client.fetch_add(1, Ordering::Relaxed) % self.clients.len()];
+        let index = &self.indexes[idx];
+        let limit = self.limits[self.limit_weights.sample()];
+        let vector: Vec<f32> = (0..self.dimensions)
+            .map(|_| rand::random_range(-1.0..1.0))
+            .collect();
+        let response = client
+            .post_ann(&index.keyspace, &index.index, vector.into(), None, limit)
+            .await;
+        response.status().is_success()
+    }
+}
+
+#[derive(Clone)]
+struct Measures {
+    per_index: Vec<SearchMeasure>,
+    errors: Vec<usize>,
+}
+
+impl Measures {
+    fn new(indexes: usize) -> Self {
+        Self {
+            per_index: vec![SearchMeasure::without_recall(); indexes],
+            errors: vec![0; indexes],
+        }
+    }
+
+    fn record(&mut self, latency: Duration, (idx, ok): (usize, bool)) {
+        if ok {
+            self.per_index[idx].record_without_recall(latency);
+        } else {
+            self.errors[idx] += 1;
+        }
+    }
+
+    fn append(&mut self, other: &Self) {
+        for (a, b) in self.per_index.iter_mut().zip(other.per_index.iter()) {
+            a.append(b);
+        }
+        for (a, b) in self.errors.iter_mut().zip(other.errors.iter()) {
+            *a += b;
+        }
+    }
+}
+
+pub(crate) async fn run(clients: Vec<HttpClient>, options: Options) {
+    assert!(!clients.is_empty());
+    assert!(!options.indexes.is_empty());
+    assert!(!options.limits.is_empty());
+    let generator = Arc::new(Generator {
+        clients,
+        next_client: AtomicUsize::new(0),
+        index_weights: Weighted::zipf(options.indexes.len(), options.zipf),
+        indexes: options.indexes,
+        limits: options
+            .limits
+            .iter()
+            .map(|limit| limit.limit.into())
+            .collect(),
+        limit_weights: Weighted::new(options.limits.iter().map(|limit| limit.weight)),
+        dimensions: options.dimensions,
+    });
+
+    let start = Instant::now();
+    let stop = start + options.duration;
+    let measures = match options.rate {
+        None => {
+            info!(
+                "Starting closed loop with concurrency {}",
+                options.concurrency
+            );
+            closed_loop(&generator, options.concurrency, stop).await
+        }
+        Some(rate) => {
+            info!(
+                "Starting open loop with {rate} queries/s and at most {} in flight",
+                options.concurrency
+            );
+            open_loop(&generator, rate, options.concurrency, stop).await
+        }
+    };
+    let duration = start.elapsed();
+
+    let mut overall = SearchMeasure::without_recall();
+    measures
+        .per_index
+        .iter()
+        .for_each(|measure| overall.append(measure));
+    overall.log(duration, None);
+    info!("errors: {}", measures.errors.iter().sum::<usize>());
+    if generator.indexes.len() > 1 {
+        for (idx, index) in generator.indexes.iter().enumerate() {
+            let label = format!("{}.{}", index.keyspace, index.index);
+            measures.per_index[idx].log(duration, Some(&label));
+            info!("errors for {label}: {}", measures.errors[idx]);
+        }
+    }
+}
+
+async fn closed_loop(generator: &Arc<Generator>, concurrency: usize, stop: Instant) -> Measures {
+    let indexes = generator.indexes.len();
+    let mut workers = JoinSet::new();
+    for _ in 0..concurrency {
+        let generator = Arc::clone(generator);
+        workers.spawn(async move {
+            let mut measures = Measures::new(indexes);
+            while Instant::now() < stop {
+                let start = Instant::now();
+                let result = query(&generator).await;
+                measures.record(start.elapsed(), result);
+            }
+            measures
+        });
+    }
+    let mut measures = Measures::new(indexes);
+    while let Some(worker) = workers.join_next().await {
+        measures.append(&worker.unwrap());
+    }
+    measures
+}
+
+async fn open_loop(
+    generator: &Arc<Generator>,
+    rate: u32,
+    max_in_flight: usize,
+    stop: Instant,
+) -> Measures {
+    let indexes = generator.indexes.len();
+    let in_flight = Arc::new(Semaphore::new(max_in_flight));
+    let mut interval = time::interval(Duration::from_secs(1) / rate);
+    let mut queries = JoinSet::new();
+    let mut measures = Measures::new(indexes);
+    let mut dropped = 0;
+    loop {
+        let planned = interval.tick().await;
+        if planned >= stop {
+            break;
+        }
+        let Ok(permit) = Arc::clone(&in_flight).try_acquire_owned() else {
+            dropped += 1;
+            continue;
+        };
+        let generator = Arc::clone(generator);
+        queries.spawn(async move {
+            let result = query(&generator).await;
+            drop(permit);
+            (planned.elapsed(), result)
+        });
+        while let Some(query) = queries.try_join_next() {
+            let (latency, result) = query.unwrap();
+            measures.record(latency, result);
+        }
+    }
+    while let Some(query) = queries.join_next().await {
+        let (latency, result) = query.unwrap();
+        measures.record(latency, result);
+    }
+    info!("queries not sent because {max_in_flight} were in flight: {dropped}");
+    measures
+}
+
+/// Sends a query to a random index in its own task, so a failed connection is counted as an
+/// error of the query. Returns the index and whether the query succeeded.
+async fn query(generator: &Arc<Generator>) -> (usize, bool) {
+    let idx = generator.index_weights.sample();
+    let generator = Arc::clone(generator);
+    let ok = tokio::spawn(async move { generator.query(idx).await })
+        .await
+        .unwrap_or(false);
+    (idx, ok)
+}
@@ -3,21 +3,31 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
-mod data;
+mod ann_benchmarks;
 mod db;
+mod end_to_end;
+mod footprint;
+mod ingest;
+mod load;
+mod quantization;
+mod sweep;
 mod vs;
 
-use crate::data::Query;
 use crate::db::Scylla;
+use crate::quantization::Quantization;
 use clap::Parser;
 use clap::Subcommand;
+use dataset::Query;
 use futures::future;
 use itertools::Itertools;
+use pprof::ProfilerGuardBuilder;
 use std::cmp;
+use std::fs::File;
 use std::net::SocketAddr;
 use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::thread;
 use std::time::Duration;
 use std::time::SystemTime;
 use tokio::sync::Notify;
@@ -32,10 +42,15 @@ use tracing_subscriber::prelude::*;
 const KEYSPACE: &str = "vsb_keyspace";
 const TABLE: &str = "vsb_table";
 const INDEX: &str = "vsb_index";
+const PROFILER_FREQUENCY: i32 = 1000;
 
 #[derive(Parser)]
 #[clap(version)]
 struct Args {
+    /// Profile the command and write a flamegraph to this SVG file
+    #[clap(long, global = true)]
+    flamegraph: Option<PathBuf>,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -207,6 +222,173 @@ enum Command {
         #[clap(long)]
         from: Option<humantime::Timestamp>,
     },
+
+    /// Measure recall and QPS of in-process indexes built from the dataset for every combination
+    /// of the index options, and write them as CSV
+    Sweep {
+        #[clap(long)]
+        data_dir: PathBuf,
+
+        #[clap(long, value_parser = clap::value_parser!(u32).range(1..=10_000))]
+        limit: u32,
+
+        #[clap(long, value_enum, default_value = "euclidean")]
+        similarity: sweep::Similarity,
+
+        #[clap(long, value_delimiter = ',', default_value = "16")]
+        connectivity: Vec<usize>,
+
+        #[clap(long, value_delimiter = ',', default_value = "128")]
+        expansion_add: Vec<usize>,
+
+        #[clap(long, value_delimiter = ',', default_value = "16,32,64,128,256")]
+        expansion_search: Vec<usize>,
+
+        #[clap(long)]
+        threads: Option<NonZeroUsize>,
+
+        #[clap(long)]
+        output: PathBuf,
+
+        /// Also write every run in the ann-benchmarks format to this directory
+        #[clap(long)]
+        ann_benchmarks: Option<PathBuf>,
+    },
+
+    /// Measure rows/s, cpu and memory of building in-process indexes from the dataset for every
+    /// combination of quantization and number of threads, and write them as CSV
+    BuildThroughput {
+        #[clap(long)]
+        data_dir: PathBuf,
+
+        #[clap(long, value_enum, value_delimiter = ',', default_value = "f32")]
+        quantization: Vec<Quantization>,
+
+        #[clap(long, value_delimiter = ',', required = true)]
+        threads: Vec<NonZeroUsize>,
+
+        /// Read at most this number of rows from the dataset
+        #[clap(long)]
+        rows: Option<NonZeroUsize>,
+
+        #[clap(long)]
+        output: PathBuf,
+    },
+
+    /// Populate a table, build an index and measure query latency and ingestion lag under write
+    /// load against a live cluster, and write a summary report
+    EndToEnd {
+        #[clap(long)]
+        data_dir: PathBuf,
+
+        #[clap(long)]
+        scylla: SocketAddr,
+
+        #[clap(long)]
+        user: Option<String>,
+
+        #[clap(long)]
+        passwd_path: Option<PathBuf>,
+
+        #[clap(long)]
+        rf: usize,
+
+        #[clap(long, default_value = KEYSPACE)]
+        keyspace: String,
+
+        #[clap(long, default_value = TABLE)]
+        table: String,
+
+        #[clap(long, default_value = INDEX)]
+        index: String,
+
+        #[clap(long, required = true)]
+        vector_store: Vec<SocketAddr>,
+
+        #[clap(long, default_value = "{}")]
+        options: String,
+
+        #[clap(long, value_parser = clap::value_parser!(u32).range(1..=1_000_000))]
+        upload_concurrency: u32,
+
+        #[clap(long, value_parser = clap::value_parser!(u32).range(1..=1_000_000))]
+        search_concurrency: u32,
+
+        #[clap(long, value_parser = clap::value_parser!(u32).range(1..=10_000))]
+        limit: u32,
+
+        /// Rows written per second while measuring
+        #[clap(long, value_parser = clap::value_parser!(u32).range(1..=1_000_000))]
+        write_rate: u32,
+
+        #[clap(long)]
+        duration: humantime::Duration,
+
+        /// Write the summary as markdown to this file
+        #[clap(long)]
+        report: Option<PathBuf>,
+    },
+
+    /// Send ANN queries to vector-store nodes in a closed loop, or in an open loop at a fixed
+    /// rate, and measure their latency
+    Load {
+        #[clap(long, required = true)]
+        vector_store: Vec<SocketAddr>,
+
+        /// Index to query as keyspace.index, can be repeated
+        #[clap(long, required = true, value_parser = load::parse_index)]
+        index: Vec<load::Index>,
+
+        /// Exponent of the zipfian distribution of queries over the indexes, 0 for uniform
+        #[clap(long, default_value_t = 0.0)]
+        zipf: f64,
+
+        /// Limit of queries as limit[:weight], can be repeated for a mix of limits
+        #[clap(long, required = true, value_parser = load::parse_weighted_limit)]
+        limit: Vec<load::WeightedLimit>,
+
+        #[clap(long)]
+        dimensions: NonZeroUsize,
+
+        /// Workers of the closed loop or maximum queries in flight of the open loop
+        #[clap(long, value_parser = clap::value_parser!(u32).range(1..=1_000_000))]
+        concurrency: u32,
+
+        /// Queries per second; runs an open loop when set
+        #[clap(long, value_parser = clap::value_parser!(u32).range(1..=1_000_000))]
+        rate: Option<u32>,
+
+        #[clap(long)]
+        duration: humantime::Duration,
+    },
+
+    /// Measure memory per vector of in-process indexes built from the dataset for every
+    /// combination of quantization and connectivity, and write it as CSV
+    MemoryFootprint {
+        #[clap(long)]
+        data_dir: PathBuf,
+
+        #[clap(
+            long,
+            value_enum,
+            value_delimiter = ',',
+            default_value = "f32,f16,bf16,i8,b1"
+        )]
+        quantization: Vec<Quantization>,
+
+        #[clap(long, value_delimiter = ',', default_value = "16")]
+        connectivity: Vec<usize>,
+
+        /// Read at most this number of rows from the dataset
+        #[clap(long)]
+        rows: Option<NonZeroUsize>,
+
+        #[clap(long)]
+        threads: Option<NonZeroUsize>,
+
+        #[clap(long)]
+        output: PathBuf,
+    },
 }
 
 #[tokio::main]
@@ -220,9 +402,18 @@ async fn main() {
         .with(fmt::layer().with_target(false))
         .init();
 
-    match Args::parse().command {
+    let args = Args::parse();
+    let profiler = args.flamegraph.as_ref().map(|_| {
+        ProfilerGuardBuilder::default()
+            .frequency(PROFILER_FREQUENCY)
+            .blocklist(&["libc", "libgcc", "pthread", "vdso"])
+            .build()
+            .expect("failed to start profiling")
+    });
+
+    match args.command {
         Command::BuildBuckets { data_dir } => {
-            data::build_and_write_buckets(data_dir).await;
+            dataset::build_and_write_buckets(data_dir).await;
         }
 
         Command::BuildTable {
@@ -236,7 +427,7 @@ async fn main() {
             table,
             concurrency,
         } => {
-            let dataset = data::new(data_dir).await;
+            let dataset = dataset::new(data_dir).await;
             let dimension = dataset.dimension().await;
             let data_multiplicity = data_multiplicity.unwrap_or(1);
             let scylla = Scylla::new(scylla, user, passwd_path, &keyspace, &table).await;
@@ -327,7 +518,7 @@ async fn main() {
             from,
             delay,
         } => {
-            let dataset = data::new(data_dir).await;
+            let dataset = dataset::new(data_dir).await;
             let queries = Arc::new(dataset.queries(bucket, limit as usize).await);
             let notify = Arc::new(Notify::new());
             let scylla = Scylla::new(scylla, user, passwd_path, &keyspace, &table).await;
@@ -393,7 +584,7 @@ async fn main() {
             concurrency,
             from,
         } => {
-            let dataset = data::new(data_dir).await;
+            let dataset = dataset::new(data_dir).await;
             let keyspace = Arc::new(keyspace.into());
             let index = Arc::new(index.into());
             let queries = Arc::new(dataset.queries(None, limit as usize).await);
@@ -474,7 +665,165 @@ async fn main() {
                     });
             }
         }
+
+        Command::Sweep {
+            data_dir,
+            limit,
+            similarity,
+            connectivity,
+            expansion_add,
+            expansion_search,
+            threads,
+            output,
+            ann_benchmarks,
+        } => {
+            let dataset_name = data_dir
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let dataset = dataset::new(data_dir).await;
+            let queries = dataset.queries(None, limit as usize).await;
+            let threads = threads
+                .or_else(|| thread::available_parallelism().ok())
+                .map_or(1, NonZeroUsize::get);
+            let (duration, _) = measure_duration(sweep::run(
+                dataset,
+                &dataset_name,
+                queries,
+                sweep::Options {
+                    similarity,
+                    connectivity,
+                    expansion_add,
+                    expansion_search,
+                    threads,
+                    ann_benchmarks,
+                },
+                &output,
+            ))
+            .await;
+            info!("Sweep took {duration:.2?}, results written to {output:?}");
+        }
+
+        Command::BuildThroughput {
+            data_dir,
+            quantization,
+            threads,
+            rows,
+            output,
+        } => {
+            let dataset = dataset::new(data_dir).await;
+            let (duration, _) = measure_duration(ingest::run(
+                dataset,
+                quantization,
+                threads.into_iter().map(NonZeroUsize::get).collect(),
+                rows.map(NonZeroUsize::get),
+                &output,
+            ))
+            .await;
+            info!("Build throughput took {duration:.2?}, results written to {output:?}");
+        }
+
+        Command::EndToEnd {
+            data_dir,
+            scylla,
+            user,
+            passwd_path,
+            rf,
+            keyspace,
+            table,
+            index,
+            vector_store,
+            options,
+            upload_concurrency,
+            search_concurrency,
+            limit,
+            write_rate,
+            duration,
+            report,
+        } => {
+            end_to_end::run(
+                vs::new_http_clients(vector_store),
+                dataset::new(data_dir).await,
+                end_to_end::Options {
+                    scylla,
+                    user,
+                    passwd_path,
+                    keyspace,
+                    table,
+                    index,
+                    index_options: options,
+                    rf,
+                    upload_concurrency: upload_concurrency as usize,
+                    search_concurrency: search_concurrency as usize,
+                    limit: limit as usize,
+                    write_rate,
+                    duration: duration.into(),
+                    report,
+                },
+            )
+            .await;
+        }
+
+        Command::Load {
+            vector_store,
+            index,
+            zipf,
+            limit,
+            dimensions,
+            concurrency,
+            rate,
+            duration,
+        } => {
+            load::run(
+                vs::new_http_clients(vector_store),
+                load::Options {
+                    indexes: index,
+                    zipf,
+                    limits: limit,
+                    dimensions: dimensions.get(),
+                    concurrency: concurrency as usize,
+                    rate,
+                    duration: duration.into(),
+                },
+            )
+            .await;
+        }
+
+        Command::MemoryFootprint {
+            data_dir,
+            quantization,
+            connectivity,
+            rows,
+            threads,
+            output,
+        } => {
+            let dataset = dataset::new(data_dir).await;
+            let threads = threads
+                .or_else(|| thread::available_parallelism().ok())
+                .map_or(1, NonZeroUsize::get);
+            let (duration, _) = measure_duration(footprint::run(
+                dataset,
+                quantization,
+                connectivity,
+                rows.map(NonZeroUsize::get),
+                threads,
+                &output,
+            ))
+            .await;
+            info!("Memory footprint took {duration:.2?}, results written to {output:?}");
+        }
     };
+
+    if let (Some(profiler), Some(path)) = (profiler, args.flamegraph) {
+        let file = File::create(&path).expect("failed to create the flamegraph");
+        profiler
+            .report()
+            .build()
+            .expect("failed to build the profile report")
+            .flamegraph(file)
+            .expect("failed to write the flamegraph");
+        info!("Flamegraph written to {path:?}");
+    }
 }
 
 async fn measure_duration<T>(f: impl Future<Output = T>) -> (Duration, T) {
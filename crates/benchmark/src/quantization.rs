@@ -0,0 +1,74 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use clap::ValueEnum;
+use usearch::Index;
+use usearch::IndexOptions;
+use usearch::MetricKind;
+use usearch::ScalarKind;
+use usearch::b1x8;
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub(crate) enum Quantization {
+    F32,
+    F16,
+    Bf16,
+    I8,
+    B1,
+}
+
+impl Quantization {
+    /// Index options for vectors of `dimensions`, with the hamming metric for B1 as vector-store
+    /// does.
+    pub(crate) fn index_options(self, dimensions: usize) -> IndexOptions {
+        IndexOptions {
+            dimensions,
+            quantization: match self {
+                Self::F32 => ScalarKind::F32,
+                Self::F16 => ScalarKind::F16,
+                Self::Bf16 => ScalarKind::BF16,
+                Self::I8 => ScalarKind::I8,
+                Self::B1 => ScalarKind::B1,
+            },
+            metric: match self {
+                Self::B1 => MetricKind::Hamming,
+                _ => MetricKind::L2sq,
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Size of a stored vector of `dimensions`.
+    pub(crate) fn vector_bytes(self, dimensions: usize) -> usize {
+        match self {
+            Self::F32 => dimensions * 4,
+            Self::F16 | Self::Bf16 => dimensions * 2,
+            Self::I8 => dimensions,
+            Self::B1 => dimensions.div_ceil(8),
+        }
+    }
+
+    /// Adds a vector, packing it into bits for B1 as vector-store does.
+    pub(crate) fn add(self, index: &Index, key: u64, vector: &[f32]) {
+        match self {
+            Self::B1 => {
+                let bits: Vec<_> = vector
+                    .chunks(8)
+                    .map(|chunk| {
+                        b1x8(
+                            chunk
+                                .iter()
+                                .enumerate()
+                                .filter(|(_, value)| **value > 0.0)
+                                .fold(0, |byte, (bit, _)| byte | (1 << bit)),
+                        )
+                    })
+                    .collect();
+                index.add(key, &bits).unwrap();
+            }
+            _ => index.add(key, vector).unwrap(),
+        }
+    }
+}
@@ -0,0 +1,243 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Builds in-process usearch indexes from a dataset for every combination of the index options
+//! and measures recall and QPS of each, to choose the options empirically.
+
+use crate::ann_benchmarks;
+use clap::ValueEnum;
+use dataset::Data;
+use dataset::Query;
+use itertools::Itertools;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+use tracing::info;
+use usearch::Index;
+use usearch::IndexOptions;
+use usearch::MetricKind;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub(crate) enum Similarity {
+    Euclidean,
+    Cosine,
+    DotProduct,
+}
+
+impl Similarity {
+    fn metric(self) -> MetricKind {
+        match self {
+            Self::Euclidean => MetricKind::L2sq,
+            Self::Cosine => MetricKind::Cos,
+            Self::DotProduct => MetricKind::IP,
+        }
+    }
+
+    /// The name of the distance in ann-benchmarks.
+    fn distance(self) -> &'static str {
+        match self {
+            Self::Euclidean => "euclidean",
+            Self::Cosine => "angular",
+            Self::DotProduct => "ip",
+        }
+    }
+}
+
+pub(crate) struct Options {
+    pub(crate) similarity: Similarity,
+    pub(crate) connectivity: Vec<usize>,
+    pub(crate) expansion_add: Vec<usize>,
+    pub(crate) expansion_search: Vec<usize>,
+    pub(crate) threads: usize,
+    /// Also write every run in the ann-benchmarks format to this directory.
+    pub(crate) ann_benchmarks: Option<PathBuf>,
+}
+
+struct Measure {
+    connectivity: usize,
+    expansion_add: usize,
+    expansion_search: usize,
+    build: Duration,
+    recall: f64,
+    qps: f64,
+}
+
+const CSV_HEADER: &str = "connectivity,expansion_add,expansion_search,build_secs,recall,qps";
+
+impl Measure {
+    fn csv(&self) -> String {
+        format!(
+            "{},{},{},{:.3},{:.4},{:.1}",
+            self.connectivity,
+            self.expansion_add,
+            self.expansion_search,
+            self.build.as_secs_f64(),
+            self.recall,
+            self.qps
+        )
+    }
+}
+
+/// Runs the sweep and writes one CSV row per combination of the options to `output`.
+pub(crate) async fn run(
+    dataset: Data,
+    dataset_name: &str,
+    queries: Vec<Query>,
+    options: Options,
+    output: &Path,
+) {
+    let dimensions = dataset.dimension().await;
+    let mut stream = dataset.vector_stream().await;
+    let mut vectors = Vec::new();
+    while let Some(vector) = stream.recv().await {
+        vectors.push(vector);
+    }
+    info!("Loaded {} vectors", vectors.len());
+
+    let mut csv = BufWriter::new(File::create(output).expect("failed to create the output file"));
+    writeln!(csv, "{CSV_HEADER}").unwrap();
+    for (connectivity, expansion_add) in options
+        .connectivity
+        .iter()
+        .cartesian_product(options.expansion_add.iter())
+    {
+        let (build, index) = build_index(
+            &vectors,
+            &IndexOptions {
+                dimensions,
+                connectivity: *connectivity,
+                expansion_add: *expansion_add,
+                metric: options.similarity.metric(),
+                ..Default::default()
+            },
+            options.threads,
+        );
+        info!(
+            "Built index with connectivity {connectivity} and expansion_add {expansion_add} in {build:.2?}"
+        );
+        for expansion_search in &options.expansion_search {
+            index.change_expansion_search(*expansion_search);
+            let (duration, mut results) = search(&index, &queries, options.threads);
+            let recall = queries
+                .iter()
+                .zip(results.iter())
+                .map(|(query, result)| {
+                    let found = result
+                        .keys
+                        .iter()
+                        .filter(|key| query.neighbors.contains(&(**key as i64)))
+                        .count();
+                    found as f64 / query.neighbors.len() as f64
+                })
+                .sum::<f64>()
+                / queries.len() as f64;
+            if let Some(dir) = &options.ann_benchmarks {
+                if let Similarity::Euclidean = options.similarity {
+                    // usearch returns squared distances for L2sq
+                    results
+                        .iter_mut()
+                        .flat_map(|result| result.distances.iter_mut())
+                        .for_each(|distance| *distance = distance.sqrt());
+                }
+                ann_benchmarks::write(
+                    dir,
+                    &ann_benchmarks::Run {
+                        algo: "usearch",
+                        name: format!(
+                            "usearch(connectivity={connectivity},expansion_add={expansion_add},expansion_search={expansion_search})"
+                        ),
+                        dataset: dataset_name,
+                        distance: options.similarity.distance(),
+                        count: queries
+                            .iter()
+                            .map(|query| query.neighbors.len())
+                            .max()
+                            .unwrap_or_default(),
+                        build_time: build,
+                        index_size: index.memory_usage(),
+                        results: &results,
+                    },
+                );
+            }
+            let measure = Measure {
+                connectivity: *connectivity,
+                expansion_add: *expansion_add,
+                expansion_search: *expansion_search,
+                build,
+                recall,
+                qps: queries.len() as f64 / duration.as_secs_f64(),
+            };
+            info!("{}", measure.csv());
+            writeln!(csv, "{}", measure.csv()).unwrap();
+        }
+    }
+    csv.flush().unwrap();
+}
+
+fn build_index(
+    vectors: &[(i64, Box<[f32]>)],
+    options: &IndexOptions,
+    threads: usize,
+) -> (Duration, Index) {
+    let start = Instant::now();
+    let index = Index::new(options).expect("failed to create an index");
+    index
+        .reserve_capacity_and_threads(vectors.len(), threads)
+        .unwrap();
+    thread::scope(|scope| {
+        for chunk in vectors.chunks(vectors.len().div_ceil(threads).max(1)) {
+            let index = &index;
+            scope.spawn(move || {
+                for (key, vector) in chunk {
+                    index.add(*key as u64, vector).unwrap();
+                }
+            });
+        }
+    });
+    (start.elapsed(), index)
+}
+
+/// The result of a single query.
+pub(crate) struct SearchResult {
+    pub(crate) time: Duration,
+    pub(crate) keys: Vec<u64>,
+    pub(crate) distances: Vec<f32>,
+}
+
+/// Runs every query once and returns the time it took and the results in the order of queries.
+fn search(index: &Index, queries: &[Query], threads: usize) -> (Duration, Vec<SearchResult>) {
+    let start = Instant::now();
+    let results = thread::scope(|scope| {
+        queries
+            .chunks(queries.len().div_ceil(threads).max(1))
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|query| {
+                            let start = Instant::now();
+                            let matches =
+                                index.search(&query.query, query.neighbors.len()).unwrap();
+                            SearchResult {
+                                time: start.elapsed(),
+                                keys: matches.keys,
+                                distances: matches.distances,
+                            }
+                        })
+                        .collect_vec()
+                })
+            })
+            .collect_vec()
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    (start.elapsed(), results)
+}
@@ -3,7 +3,7 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
-use crate::data::Query;
+use crate::Query;
 use futures::StreamExt;
 use futures::stream;
 use futures::stream::BoxStream;
@@ -0,0 +1,191 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! The texmex format used by SIFT1M and GIST1M: every vector is stored as its dimension (i32)
+//! followed by its coordinates (f32 in fvecs, i32 in ivecs), all little endian.
+
+use crate::Query;
+use futures::StreamExt;
+use futures::stream;
+use futures::stream::BoxStream;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::mem;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::fs::File;
+use tokio::io::AsyncReadExt;
+use tokio::io::BufReader;
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+
+#[derive(Deserialize)]
+pub(crate) struct Config {
+    base_fvecs: PathBuf,
+    query_fvecs: PathBuf,
+    groundtruth_ivecs: PathBuf,
+}
+
+#[derive(Debug, PartialEq)]
+struct Header {
+    count: usize,
+    dimension: usize,
+}
+
+impl Header {
+    /// Every vector in a file has the same dimension, so the count follows from the file size.
+    async fn header(path: &Path) -> Self {
+        let mut file = File::open(path).await.unwrap();
+        let dimension = file.read_u32_le().await.unwrap() as usize;
+        let len = fs::metadata(path).await.unwrap().len() as usize;
+        let record = mem::size_of::<u32>() * (dimension + 1);
+        assert_eq!(len % record, 0, "{path:?} is not a valid vecs file");
+        Self {
+            count: len / record,
+            dimension,
+        }
+    }
+}
+
+async fn read_dimension(reader: &mut BufReader<File>, expected: usize) {
+    let dimension = reader.read_u32_le().await.unwrap() as usize;
+    assert_eq!(dimension, expected, "vectors have different dimensions");
+}
+
+pub(crate) async fn dimension(path: Arc<PathBuf>, config: Arc<Config>) -> usize {
+    Header::header(&path.join(&config.base_fvecs))
+        .await
+        .dimension
+}
+
+pub(crate) async fn ids_stream(path: Arc<PathBuf>, config: Arc<Config>) -> BoxStream<'static, i64> {
+    let header = Header::header(&path.join(&config.base_fvecs)).await;
+    stream::iter(0..header.count as i64).boxed()
+}
+
+pub(crate) async fn vector_stream(
+    path: Arc<PathBuf>,
+    config: Arc<Config>,
+) -> mpsc::Receiver<(i64, Box<[f32]>)> {
+    let header = Header::header(&path.join(&config.base_fvecs)).await;
+
+    let mut base_fvecs = BufReader::new(File::open(path.join(&config.base_fvecs)).await.unwrap());
+
+    let workers = Handle::current().metrics().num_workers();
+    const OVERLOAD_FACTOR: usize = 3;
+    let (tx, rx) = mpsc::channel(workers * OVERLOAD_FACTOR);
+    tokio::spawn(async move {
+        for id in 0..header.count as i64 {
+            read_dimension(&mut base_fvecs, header.dimension).await;
+            let mut vector = Vec::with_capacity(header.dimension);
+            for _ in 0..header.dimension {
+                vector.push(base_fvecs.read_f32_le().await.unwrap());
+            }
+            if tx.send((id, vector.into_boxed_slice())).await.is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+pub(crate) async fn queries(
+    path: Arc<PathBuf>,
+    config: Arc<Config>,
+    id_ok: impl Fn(i64) -> bool,
+    limit: usize,
+) -> Vec<Query> {
+    let header_fvecs = Header::header(&path.join(&config.query_fvecs)).await;
+    let header_ivecs = Header::header(&path.join(&config.groundtruth_ivecs)).await;
+    assert_eq!(header_fvecs.count, header_ivecs.count);
+
+    let mut fvecs = BufReader::new(File::open(path.join(&config.query_fvecs)).await.unwrap());
+    let mut ivecs = BufReader::new(
+        File::open(path.join(&config.groundtruth_ivecs))
+            .await
+            .unwrap(),
+    );
+
+    let mut queries = Vec::with_capacity(header_fvecs.count);
+    for _ in 0..header_fvecs.count {
+        read_dimension(&mut fvecs, header_fvecs.dimension).await;
+        let mut query = Vec::with_capacity(header_fvecs.dimension);
+        for _ in 0..header_fvecs.dimension {
+            query.push(fvecs.read_f32_le().await.unwrap());
+        }
+
+        read_dimension(&mut ivecs, header_ivecs.dimension).await;
+        let mut neighbors = HashSet::with_capacity(limit);
+        for _ in 0..header_ivecs.dimension {
+            let id = ivecs.read_i32_le().await.unwrap() as i64;
+            if neighbors.len() < limit && id_ok(id) {
+                neighbors.insert(id);
+            }
+        }
+
+        if !neighbors.is_empty() {
+            queries.push(Query { query, neighbors });
+        }
+    }
+    queries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+
+    async fn write_vecs(path: &Path, vectors: &[&[u32]]) {
+        let mut file = File::create(path).await.unwrap();
+        for vector in vectors {
+            file.write_u32_le(vector.len() as u32).await.unwrap();
+            for value in *vector {
+                file.write_u32_le(*value).await.unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_vectors_and_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = Arc::new(dir.path().to_path_buf());
+        let config = Arc::new(Config {
+            base_fvecs: "base.fvecs".into(),
+            query_fvecs: "query.fvecs".into(),
+            groundtruth_ivecs: "groundtruth.ivecs".into(),
+        });
+        let bits = |values: &[f32]| values.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+        write_vecs(
+            &path.join("base.fvecs"),
+            &[&bits(&[1.0, 2.0]), &bits(&[3.0, 4.0]), &bits(&[5.0, 6.0])],
+        )
+        .await;
+        write_vecs(&path.join("query.fvecs"), &[&bits(&[1.5, 2.5])]).await;
+        write_vecs(&path.join("groundtruth.ivecs"), &[&[0, 1, 2]]).await;
+
+        assert_eq!(dimension(Arc::clone(&path), Arc::clone(&config)).await, 2);
+
+        let mut rx = vector_stream(Arc::clone(&path), Arc::clone(&config)).await;
+        let mut vectors = Vec::new();
+        while let Some(vector) = rx.recv().await {
+            vectors.push(vector);
+        }
+        assert_eq!(
+            vectors,
+            vec![
+                (0, vec![1.0, 2.0].into_boxed_slice()),
+                (1, vec![3.0, 4.0].into_boxed_slice()),
+                (2, vec![5.0, 6.0].into_boxed_slice()),
+            ]
+        );
+
+        let queries = queries(path, config, |id| id != 0, 1).await;
+        assert_eq!(queries.len(), 1);
+        assert_eq!(queries[0].query, vec![1.5, 2.5]);
+        assert_eq!(queries[0].neighbors, HashSet::from([1]));
+    }
+}
@@ -3,7 +3,12 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
+//! Datasets of vectors with query vectors and their ground truth neighbours, shared by the
+//! benchmark and the validator. The format of a dataset directory is described by its
+//! `dataset.toml`; a directory without it is read as VectorDBBench parquet files.
+
 mod fbin;
+mod fvecs;
 mod parquet;
 
 use futures::StreamExt;
@@ -25,12 +30,12 @@ use tracing::info;
 const DATASET_FILENAME: &str = "dataset.toml";
 const BUCKETS_FILENAME: &str = "buckets.bin";
 
-pub(crate) struct Query {
-    pub(crate) query: Vec<f32>,
-    pub(crate) neighbors: HashSet<i64>,
+pub struct Query {
+    pub query: Vec<f32>,
+    pub neighbors: HashSet<i64>,
 }
 
-pub(crate) struct Data {
+pub struct Data {
     path: Arc<PathBuf>,
     format: Format,
     buckets: Arc<BTreeMap<i64, u8>>,
@@ -39,10 +44,11 @@ pub(crate) struct Data {
 enum Format {
     Parquet(Arc<parquet::Config>),
     Fbin(Arc<fbin::Config>),
+    Fvecs(Arc<fvecs::Config>),
 }
 
 impl Data {
-    pub(crate) async fn dimension(&self) -> usize {
+    pub async fn dimension(&self) -> usize {
         let dim = match &self.format {
             Format::Parquet(config) => {
                 parquet::dimension(Arc::clone(&self.path), Arc::clone(config)).await
@@ -50,12 +56,15 @@ impl Data {
             Format::Fbin(config) => {
                 fbin::dimension(Arc::clone(&self.path), Arc::clone(config)).await
             }
+            Format::Fvecs(config) => {
+                fvecs::dimension(Arc::clone(&self.path), Arc::clone(config)).await
+            }
         };
         info!("Found dimension {dim} for dataset at {:?}", self.path);
         dim
     }
 
-    pub(crate) async fn queries(&self, bucket: Option<u8>, limit: usize) -> Vec<Query> {
+    pub async fn queries(&self, bucket: Option<u8>, limit: usize) -> Vec<Query> {
         let buckets = Arc::clone(&self.buckets);
         let id_ok = move |id| {
             let Some(bucket) = &bucket else {
@@ -70,10 +79,13 @@ impl Data {
             Format::Fbin(config) => {
                 fbin::queries(Arc::clone(&self.path), Arc::clone(config), id_ok, limit).await
             }
+            Format::Fvecs(config) => {
+                fvecs::queries(Arc::clone(&self.path), Arc::clone(config), id_ok, limit).await
+            }
         }
     }
 
-    pub(crate) async fn vector_stream(&self) -> mpsc::Receiver<(i64, Box<[f32]>)> {
+    pub async fn vector_stream(&self) -> mpsc::Receiver<(i64, Box<[f32]>)> {
         match &self.format {
             Format::Parquet(config) => {
                 parquet::vector_stream(Arc::clone(&self.path), Arc::clone(config)).await
@@ -81,10 +93,13 @@ impl Data {
             Format::Fbin(config) => {
                 fbin::vector_stream(Arc::clone(&self.path), Arc::clone(config)).await
             }
+            Format::Fvecs(config) => {
+                fvecs::vector_stream(Arc::clone(&self.path), Arc::clone(config)).await
+            }
         }
     }
 
-    pub(crate) fn buckets(&self) -> Arc<BTreeMap<i64, u8>> {
+    pub fn buckets(&self) -> Arc<BTreeMap<i64, u8>> {
         Arc::clone(&self.buckets)
     }
 }
@@ -93,6 +108,7 @@ impl Data {
 struct Config {
     parquet: Option<parquet::Config>,
     fbin: Option<fbin::Config>,
+    fvecs: Option<fvecs::Config>,
 }
 
 async fn format(path: &Path) -> Format {
@@ -109,6 +125,9 @@ async fn format(path: &Path) -> Format {
     if let Some(config) = config.fbin {
         return Format::Fbin(Arc::new(config));
     }
+    if let Some(config) = config.fvecs {
+        return Format::Fvecs(Arc::new(config));
+    }
     info!("Not found format type in {DATASET_FILENAME} in {path:?}. Using default parquet format.");
     Format::Parquet(Arc::new(parquet::Config::default()))
 }
@@ -117,6 +136,7 @@ async fn build_buckets(path: Arc<PathBuf>, format: &Format) -> BTreeMap<i64, u8>
     let stream = match &format {
         Format::Parquet(config) => parquet::ids_stream(Arc::clone(&path), Arc::clone(config)).await,
         Format::Fbin(config) => fbin::ids_stream(Arc::clone(&path), Arc::clone(config)).await,
+        Format::Fvecs(config) => fvecs::ids_stream(Arc::clone(&path), Arc::clone(config)).await,
     };
     info!("Building buckets for dataset at {path:?}...");
     let mut buckets = stream
@@ -159,7 +179,7 @@ async fn write_buckets(path: &Path, buckets: BTreeMap<i64, u8>) {
     }
 }
 
-pub(crate) async fn build_and_write_buckets(data_dir: PathBuf) {
+pub async fn build_and_write_buckets(data_dir: PathBuf) {
     let data_dir = Arc::new(data_dir);
     let format = format(&data_dir).await;
     let buckets = build_buckets(Arc::clone(&data_dir), &format).await;
@@ -184,7 +204,7 @@ async fn read_buckets(path: &Path) -> BTreeMap<i64, u8> {
     buckets
 }
 
-pub(crate) async fn new(data_dir: PathBuf) -> Data {
+pub async fn new(data_dir: PathBuf) -> Data {
     Data {
         format: format(&data_dir).await,
         buckets: Arc::new(read_buckets(&data_dir).await),
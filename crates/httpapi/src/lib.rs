@@ -100,6 +100,26 @@ pub struct IndexInfo {
     pub index_type: IndexType,
 }
 
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// The version of the definition of a vector index served by a node.
+pub struct IndexVersionInfo {
+    pub keyspace: KeyspaceName,
+    pub index: IndexName,
+    /// The id of the index definition, changed when the index is recreated.
+    pub version: String,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// The versions of a vector index served by the nodes of the cluster.
+pub struct ClusterIndexVersions {
+    pub keyspace: KeyspaceName,
+    pub index: IndexName,
+    /// The served version by the id of the node.
+    pub versions: HashMap<String, String>,
+    /// Whether the nodes serve different versions of the index.
+    pub divergent: bool,
+}
+
 impl IndexInfo {
     pub fn new(keyspace: &str, index: &str) -> Self {
         IndexInfo {
@@ -133,6 +153,74 @@ impl IndexInfo {
 /// A name of the vector index in a db.
 pub struct IndexName(String);
 
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Estimated memory in bytes used by the parts of an index.
+pub struct IndexMemoryUsage {
+    /// Primary keys of the indexed rows and the maps of them to the internal ids.
+    pub key_maps: u64,
+    /// Vectors and the graph of their neighbours.
+    pub graph: u64,
+    /// Rows read from the database and not applied to the index yet.
+    pub pending_queues: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Internals of the HNSW graphs of a vector index.
+pub struct IndexGraphStats {
+    /// Vectors in the index.
+    pub vectors: u64,
+    /// Slots reserved for vectors.
+    pub capacity: u64,
+    /// Slots of removed vectors which are not reused by added vectors yet.
+    pub deleted_slots: u64,
+    /// Deleted slots to all slots holding vectors or deleted ones.
+    pub deleted_slot_ratio: f64,
+    /// Partitions of a local index, each with its own graph. A global index has one.
+    pub partitions: u64,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Searches of an index served by the node since it started.
+pub struct IndexSearchStats {
+    pub count: u64,
+    /// Total time of the searches in seconds.
+    pub seconds: f64,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Drift of vectors of an index from a reference of the first vectors of their kind seen by the node.
+pub struct VectorDriftStats {
+    /// Vectors seen since the index was created on the node.
+    pub vectors: u64,
+    /// Shift of the mean norm of recent vectors, in standard deviations of the reference norms.
+    pub norm_drift: f64,
+    /// Cosine distance between the mean vectors of the reference and of recent vectors.
+    pub direction_drift: f64,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Drift of the ingested and query vectors of an index. Each is reported once the node has seen
+/// enough vectors of its kind for a reference.
+pub struct IndexDriftStats {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ingested: Option<VectorDriftStats>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub queried: Option<VectorDriftStats>,
+}
+
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+pub struct IndexStatsResponse {
+    pub memory: IndexMemoryUsage,
+    /// Only for vector indexes built by usearch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub graph: Option<IndexGraphStats>,
+    pub searches: IndexSearchStats,
+    /// Only for vector indexes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drift: Option<IndexDriftStats>,
+}
+
 #[derive(ToEnumSchema, serde::Deserialize, serde::Serialize, PartialEq, Debug)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 /// Operational status of the vector index.
@@ -143,12 +231,32 @@ pub enum IndexStatus {
     Bootstrapping,
     /// The index has completed the initial table scan. It is now monitoring the database for changes.
     Serving,
+    /// The index is not built: its parameters exceed the limits of the node, or building it failed.
+    Failed,
+}
+
+/// Progress of the initial full scan of an index.
+#[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+pub struct IndexBuildProgress {
+    /// Scanned part of the token ring of the table in percent.
+    pub percent: f64,
+    /// Rows of the table read by the scan so far.
+    pub processed_rows: u64,
+    /// Rows of the table estimated from the scanned part, unknown at the start of the scan.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_rows: Option<u64>,
 }
 
 #[derive(Debug, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
 pub struct IndexStatusResponse {
     pub status: IndexStatus,
     pub count: usize,
+    /// Why the index failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Only while the index is bootstrapping.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progress: Option<IndexBuildProgress>,
 }
 
 #[derive(Debug, PartialEq, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
@@ -156,6 +264,7 @@ pub struct IndexStatusResponse {
 pub enum IndexNotReadyReason {
     NodeBootstrapping,
     IndexBuilding { message: String },
+    IndexCatchingUp { message: String },
 }
 
 /// The 503 response shared by the ANN and BM25 search endpoints when the index
@@ -165,7 +274,8 @@ pub enum IndexNotReadyReason {
     description = "Service Unavailable. The index is not ready to serve requests. \
 The body is a JSON object with a 'reason' field: \
 'NODE_BOOTSTRAPPING' when the node has not yet finished its startup sequence; \
-'INDEX_BUILDING' (with a 'message' field) when the node is healthy but this index is still being constructed.",
+'INDEX_BUILDING' (with a 'message' field) when the node is healthy but this index is still being constructed; \
+'INDEX_CATCHING_UP' (with a 'message' field) when the index has not read the writes up to 'min_write_timestamp' in time.",
     content_type = "application/json"
 )]
 pub struct IndexNotReadyResponse(#[allow(dead_code)] IndexNotReadyReason);
@@ -312,6 +422,101 @@ pub struct PostIndexAnnRequest {
     pub filter: Option<PostIndexAnnFilter>,
     #[serde(default)]
     pub limit: Limit,
+    /// Write timestamp in microseconds since the UNIX epoch. The search waits until the index has
+    /// read the changes of the table written up to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_write_timestamp: Option<u64>,
+    /// How long to wait for `min_write_timestamp` in milliseconds. Defaults to 5000.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_wait_timeout_ms: Option<u64>,
+    /// How many times more candidates are searched in a quantized index and rescored by the exact
+    /// distances of their full precision vectors, read from the table. Ignored by indexes which
+    /// are not quantized.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<u32>, minimum = 1)]
+    pub oversampling: Option<NonZeroUsize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerank: Option<Rerank>,
+    /// Columns stored by the index, as its filtering and payload columns, returned for each result
+    /// in `payload` of the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<Vec<ColumnName>>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Request body for a batch of ANN searches, run in parallel.
+pub struct PostIndexAnnBatchRequest {
+    pub queries: Vec<PostIndexAnnBatchQuery>,
+    /// The limit of the queries which do not set their own.
+    #[serde(default)]
+    pub limit: Limit,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// A query of a batch of ANN searches.
+pub struct PostIndexAnnBatchQuery {
+    pub vector: Vector,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<Limit>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Response for a batch of ANN searches, with the results of the queries in their order.
+pub struct PostIndexAnnBatchResponse {
+    pub results: Vec<PostIndexAnnResponse>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Request body for searching all vectors within a distance of a vector.
+pub struct PostIndexRangeRequest {
+    pub vector: Vector,
+    /// The greatest distance of a result from the vector, in the distance of the similarity
+    /// function of the index.
+    pub max_distance: f32,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Request body for ANN search by text, which is embedded by the embedding provider of the server.
+pub struct PostIndexAnnTextRequest {
+    /// The text to embed and search for.
+    pub text: String,
+    pub filter: Option<PostIndexAnnFilter>,
+    #[serde(default)]
+    pub limit: Limit,
+    /// Write timestamp in microseconds since the UNIX epoch. The search waits until the index has
+    /// read the changes of the table written up to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_write_timestamp: Option<u64>,
+    /// How long to wait for `min_write_timestamp` in milliseconds. Defaults to 5000.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub write_wait_timeout_ms: Option<u64>,
+    /// How many times more candidates are searched in a quantized index and rescored by the exact
+    /// distances of their full precision vectors, read from the table. Ignored by indexes which
+    /// are not quantized.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<u32>, minimum = 1)]
+    pub oversampling: Option<NonZeroUsize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rerank: Option<Rerank>,
+    /// Columns stored by the index, as its filtering and payload columns, returned for each result
+    /// in `payload` of the response.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<Vec<ColumnName>>,
+}
+
+#[derive(Clone, serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Reranking of the candidates found by the ANN search by a cross-encoder model configured on the
+/// server, which scores the text of each candidate against the query.
+pub struct Rerank {
+    /// The query the candidates are scored against.
+    pub query: String,
+    /// The text column of the table read for each candidate.
+    pub column: ColumnName,
+    /// How many times more candidates than `limit` are searched and reranked. Defaults to the
+    /// setting of the server.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<u32>, minimum = 1)]
+    pub oversampling: Option<NonZeroUsize>,
 }
 
 #[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
@@ -319,6 +524,9 @@ pub struct PostIndexAnnResponse {
     pub primary_keys: HashMap<ColumnName, Vec<Value>>,
     pub distances: Vec<Distance>,
     pub similarity_scores: Vec<SimilarityScore>,
+    /// Values of the requested payload columns, in the order of the results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<HashMap<ColumnName, Vec<Value>>>,
 }
 
 #[derive(Copy, Clone, Debug, serde::Deserialize, derive_more::From, utoipa::ToSchema)]
@@ -367,6 +575,7 @@ mod tests {
                 SimilarityScore::from(f32::NEG_INFINITY),
                 SimilarityScore::from(0.5),
             ],
+            payload: None,
         })
         .unwrap();
 
@@ -417,3 +626,36 @@ pub struct PostIndexBm25Response {
     pub primary_keys: HashMap<ColumnName, Vec<Value>>,
     pub scores: Vec<f32>,
 }
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Request body for finding near-duplicate vectors of an index.
+pub struct PostIndexDuplicatesRequest {
+    /// The greatest distance between two vectors which are near-duplicates, in the distance of the
+    /// similarity function of the index.
+    pub max_distance: f32,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Response for finding near-duplicate vectors of an index.
+pub struct PostIndexDuplicatesResponse {
+    /// Clusters of rows with near-duplicate vectors, the largest first. Each cluster holds the
+    /// primary keys of its rows by column.
+    pub clusters: Vec<HashMap<ColumnName, Vec<Value>>>,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Request body for clustering the vectors of an index by k-means.
+pub struct PostIndexKMeansRequest {
+    /// The number of clusters.
+    #[schema(value_type = u64, minimum = 1)]
+    pub k: NonZeroUsize,
+}
+
+#[derive(serde::Deserialize, serde::Serialize, utoipa::ToSchema)]
+/// Response for clustering the vectors of an index by k-means.
+pub struct PostIndexKMeansResponse {
+    /// The centroids of the clusters. There are fewer than `k` if the index has fewer vectors.
+    pub centroids: Vec<Vec<f32>>,
+    /// The number of vectors of each cluster, in the order of the centroids.
+    pub sizes: Vec<u64>,
+}
@@ -0,0 +1,180 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::HttpClient;
+use crate::WaitOptions;
+use httpapi::ClusterIndexVersions;
+use httpapi::ColumnName;
+use httpapi::Distance;
+use httpapi::IndexInfo;
+use httpapi::IndexName;
+use httpapi::IndexStatsResponse;
+use httpapi::IndexStatus;
+use httpapi::IndexStatusResponse;
+use httpapi::IndexVersionInfo;
+use httpapi::InfoResponse;
+use httpapi::KeyspaceName;
+use httpapi::Limit;
+use httpapi::NodeStatus;
+use httpapi::PostIndexAnnFilter;
+use httpapi::SimilarityScore;
+use httpapi::Vector;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use tokio::runtime::Runtime;
+
+/// A client for callers without an async runtime. Every call blocks the current thread until
+/// the response is received, so it must not be used from within an async runtime.
+#[derive(Debug)]
+pub struct BlockingHttpClient {
+    client: HttpClient,
+    runtime: Runtime,
+}
+
+impl BlockingHttpClient {
+    pub fn new(addr: SocketAddr) -> anyhow::Result<Self> {
+        Self::with_client(HttpClient::builder(addr).build()?)
+    }
+
+    /// Wraps a client configured by [`HttpClient::builder`].
+    pub fn with_client(client: HttpClient) -> anyhow::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self { client, runtime })
+    }
+
+    pub fn url(&self) -> &str {
+        self.client.url()
+    }
+
+    pub fn indexes(&self) -> Vec<IndexInfo> {
+        self.runtime.block_on(self.client.indexes())
+    }
+
+    pub fn ann(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        vector: Vector,
+        filter: Option<PostIndexAnnFilter>,
+        limit: Limit,
+    ) -> (
+        HashMap<ColumnName, Vec<Value>>,
+        Vec<Distance>,
+        Vec<SimilarityScore>,
+    ) {
+        self.runtime.block_on(
+            self.client
+                .ann(keyspace_name, index_name, vector, filter, limit),
+        )
+    }
+
+    pub fn bm25(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        query: String,
+        limit: Limit,
+    ) -> (HashMap<ColumnName, Vec<Value>>, Vec<f32>) {
+        self.runtime
+            .block_on(self.client.bm25(keyspace_name, index_name, query, limit))
+    }
+
+    pub fn index_status(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+    ) -> anyhow::Result<IndexStatusResponse> {
+        self.runtime
+            .block_on(self.client.index_status(keyspace_name, index_name))
+    }
+
+    pub fn index_stats(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+    ) -> anyhow::Result<IndexStatsResponse> {
+        self.runtime
+            .block_on(self.client.index_stats(keyspace_name, index_name))
+    }
+
+    pub fn index_versions(&self) -> anyhow::Result<Vec<IndexVersionInfo>> {
+        self.runtime.block_on(self.client.index_versions())
+    }
+
+    pub fn cluster_index_versions(&self) -> anyhow::Result<Option<Vec<ClusterIndexVersions>>> {
+        self.runtime.block_on(self.client.cluster_index_versions())
+    }
+
+    pub fn info(&self) -> InfoResponse {
+        self.runtime.block_on(self.client.info())
+    }
+
+    pub fn status(&self) -> anyhow::Result<NodeStatus> {
+        self.runtime.block_on(self.client.status())
+    }
+
+    pub fn wait_for_status(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        status: IndexStatus,
+        wait: WaitOptions,
+    ) -> anyhow::Result<IndexStatusResponse> {
+        self.runtime.block_on(
+            self.client
+                .wait_for_status(keyspace_name, index_name, status, wait),
+        )
+    }
+
+    pub fn wait_for_serving(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        wait: WaitOptions,
+    ) -> anyhow::Result<IndexStatusResponse> {
+        self.runtime.block_on(
+            self.client
+                .wait_for_serving(keyspace_name, index_name, wait),
+        )
+    }
+
+    pub fn wait_for_index_count(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        count: usize,
+        wait: WaitOptions,
+    ) -> anyhow::Result<IndexStatusResponse> {
+        self.runtime.block_on(self.client.wait_for_index_count(
+            keyspace_name,
+            index_name,
+            count,
+            wait,
+        ))
+    }
+
+    pub fn get_metrics_text(&self) -> String {
+        self.runtime.block_on(self.client.get_metrics_text())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+
+    #[test]
+    fn requests_fail_without_server() {
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let client = BlockingHttpClient::new(addr).unwrap();
+        assert!(client.status().is_err());
+    }
+}
@@ -0,0 +1,126 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::HttpClient;
+use crate::RetryPolicy;
+use crate::retry::RetryBudget;
+use reqwest::header::HeaderMap;
+use reqwest::header::HeaderName;
+use reqwest::header::HeaderValue;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// A builder of an [`HttpClient`] with custom connection settings.
+#[derive(Debug)]
+pub struct HttpClientBuilder {
+    addr: SocketAddr,
+    base_path: String,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    headers: HeaderMap,
+    retry: RetryPolicy,
+}
+
+impl HttpClientBuilder {
+    pub(crate) fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            base_path: String::new(),
+            connect_timeout: None,
+            timeout: None,
+            pool_max_idle_per_host: None,
+            headers: HeaderMap::new(),
+            retry: RetryPolicy::NONE,
+        }
+    }
+
+    /// Sets the path the API is served under, e.g. by a reverse proxy.
+    pub fn base_path(mut self, base_path: impl Into<String>) -> Self {
+        self.base_path = base_path.into().trim_end_matches('/').to_string();
+        if !self.base_path.is_empty() && !self.base_path.starts_with('/') {
+            self.base_path.insert(0, '/');
+        }
+        self
+    }
+
+    /// Sets the timeout of establishing a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the timeout of a whole request, from connecting until the response body is read.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept open to the server.
+    pub fn pool_max_idle(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Adds a header sent with every request.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Sets how idempotent requests are retried. Requests are not retried by default.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Builds the client. Fails only if the underlying http client cannot be initialized.
+    pub fn build(self) -> anyhow::Result<HttpClient> {
+        let mut client = reqwest::Client::builder().default_headers(self.headers);
+        if let Some(timeout) = self.connect_timeout {
+            client = client.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            client = client.timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            client = client.pool_max_idle_per_host(max);
+        }
+        let url = format!("http://{}{}", self.addr, self.base_path);
+        Ok(HttpClient {
+            client: client.build()?,
+            url_api: format!("{url}/api/v1"),
+            url_internals_api: format!("{url}/api/internals"),
+            url,
+            retry: self.retry,
+            retry_budget: Arc::new(RetryBudget::new(&self.retry)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_include_base_path() {
+        let addr = SocketAddr::from(([127, 0, 0, 1], 6080));
+        let client = HttpClient::builder(addr).build().unwrap();
+        assert_eq!(client.url(), "http://127.0.0.1:6080/api/v1");
+
+        for base_path in ["/vs", "vs", "/vs/"] {
+            let client = HttpClient::builder(addr)
+                .base_path(base_path)
+                .build()
+                .unwrap();
+            assert_eq!(client.url(), "http://127.0.0.1:6080/vs/api/v1");
+            assert_eq!(
+                client.url_internals_api,
+                "http://127.0.0.1:6080/vs/api/internals"
+            );
+        }
+    }
+}
@@ -0,0 +1,119 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use httpapi::IndexNotReadyReason;
+use reqwest::Response;
+use reqwest::StatusCode;
+
+/// An error response of the vector store.
+///
+/// The kind of the error is derived from the status code and, where the server reports details,
+/// from the body of the response.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ServerError {
+    #[error("index not found: {0}")]
+    IndexNotFound(String),
+
+    #[error("wrong embedding dimension: expected {expected}, got {actual}")]
+    WrongDimension { expected: usize, actual: usize },
+
+    #[error("bad request: {0}")]
+    BadRequest(String),
+
+    #[error("unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("index not ready: {0:?}")]
+    NotReady(IndexNotReadyReason),
+
+    #[error("overloaded: {0}")]
+    Overloaded(String),
+
+    #[error("HTTP {status}: {body}")]
+    Other { status: StatusCode, body: String },
+}
+
+impl ServerError {
+    /// Reads the error from an unsuccessful response.
+    pub async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        Self::new(status, body)
+    }
+
+    fn new(status: StatusCode, body: String) -> Self {
+        match status {
+            StatusCode::NOT_FOUND => Self::IndexNotFound(body),
+            StatusCode::BAD_REQUEST => match wrong_dimension(&body) {
+                Some((expected, actual)) => Self::WrongDimension { expected, actual },
+                None => Self::BadRequest(body),
+            },
+            StatusCode::UNAUTHORIZED => Self::Unauthorized(body),
+            StatusCode::FORBIDDEN => Self::Forbidden(body),
+            StatusCode::SERVICE_UNAVAILABLE => match serde_json::from_str(&body) {
+                Ok(reason) => Self::NotReady(reason),
+                Err(_) => Self::Overloaded(body),
+            },
+            StatusCode::TOO_MANY_REQUESTS => Self::Overloaded(body),
+            status => Self::Other { status, body },
+        }
+    }
+}
+
+/// Parses "Wrong embedding dimension: expected {expected}, got {actual}".
+fn wrong_dimension(body: &str) -> Option<(usize, usize)> {
+    let (expected, actual) = body
+        .strip_prefix("Wrong embedding dimension: expected ")?
+        .split_once(", got ")?;
+    Some((expected.parse().ok()?, actual.trim().parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn errors_are_classified_by_status_and_body() {
+        assert_eq!(
+            ServerError::new(StatusCode::NOT_FOUND, "missing index: ks.idx".to_string()),
+            ServerError::IndexNotFound("missing index: ks.idx".to_string())
+        );
+        assert_eq!(
+            ServerError::new(
+                StatusCode::BAD_REQUEST,
+                "Wrong embedding dimension: expected 3, got 2".to_string()
+            ),
+            ServerError::WrongDimension {
+                expected: 3,
+                actual: 2
+            }
+        );
+        assert_eq!(
+            ServerError::new(StatusCode::BAD_REQUEST, "bad filter".to_string()),
+            ServerError::BadRequest("bad filter".to_string())
+        );
+        assert_eq!(
+            ServerError::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                r#"{"reason":"NODE_BOOTSTRAPPING"}"#.to_string()
+            ),
+            ServerError::NotReady(IndexNotReadyReason::NodeBootstrapping)
+        );
+        assert_eq!(
+            ServerError::new(StatusCode::SERVICE_UNAVAILABLE, "busy".to_string()),
+            ServerError::Overloaded("busy".to_string())
+        );
+        assert_eq!(
+            ServerError::new(StatusCode::INTERNAL_SERVER_ERROR, "oops".to_string()),
+            ServerError::Other {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                body: "oops".to_string()
+            }
+        );
+    }
+}
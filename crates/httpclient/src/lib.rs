@@ -3,42 +3,103 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
+mod blocking;
+mod builder;
+mod error;
+mod retry;
+mod wait;
+
+use httpapi::ClusterIndexVersions;
 use httpapi::ColumnName;
 use httpapi::Distance;
 use httpapi::IndexInfo;
 use httpapi::IndexName;
+use httpapi::IndexStatsResponse;
 use httpapi::IndexStatusResponse;
+use httpapi::IndexVersionInfo;
 use httpapi::InfoResponse;
 use httpapi::KeyspaceName;
 use httpapi::Limit;
 use httpapi::NodeStatus;
+use httpapi::PostIndexAnnBatchQuery;
+use httpapi::PostIndexAnnBatchRequest;
+use httpapi::PostIndexAnnBatchResponse;
 use httpapi::PostIndexAnnFilter;
 use httpapi::PostIndexAnnRequest;
 use httpapi::PostIndexAnnResponse;
 use httpapi::PostIndexBm25Request;
 use httpapi::PostIndexBm25Response;
+use httpapi::PostIndexDuplicatesRequest;
+use httpapi::PostIndexDuplicatesResponse;
+use httpapi::PostIndexKMeansRequest;
+use httpapi::PostIndexKMeansResponse;
+use httpapi::PostIndexRangeRequest;
 use httpapi::SimilarityScore;
 use httpapi::Vector;
-use reqwest::Client;
+use reqwest::RequestBuilder;
+use reqwest::Response;
+use reqwest::StatusCode;
+use retry::RetryBudget;
 use serde::Serialize;
+use serde::de::DeserializeOwned;
 use serde_json::Value;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+pub use blocking::BlockingHttpClient;
+pub use builder::HttpClientBuilder;
+pub use error::ServerError;
+pub use retry::RetryPolicy;
+pub use wait::WaitOptions;
 
 #[derive(Debug, Clone)]
 pub struct HttpClient {
-    client: Client,
+    client: reqwest::Client,
+    url: String,
     url_api: String,
     url_internals_api: String,
+    retry: RetryPolicy,
+    retry_budget: Arc<RetryBudget>,
 }
 
 impl HttpClient {
     pub fn new(addr: SocketAddr) -> Self {
-        Self {
-            url_api: format!("http://{addr}/api/v1"),
-            url_internals_api: format!("http://{addr}/api/internals"),
-            client: Client::new(),
+        Self::builder(addr)
+            .build()
+            .expect("HttpClient::new: failed to build an http client")
+    }
+
+    pub fn builder(addr: SocketAddr) -> HttpClientBuilder {
+        HttpClientBuilder::new(addr)
+    }
+
+    /// Retries idempotent requests (listing indexes, status and ANN queries) according to
+    /// `policy`. Requests are not retried by default.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_budget = Arc::new(RetryBudget::new(&policy));
+        self.retry = policy;
+        self
+    }
+
+    async fn send_idempotent(
+        &self,
+        request: impl Fn() -> RequestBuilder,
+    ) -> reqwest::Result<Response> {
+        self.retry_budget.deposit(&self.retry);
+        let mut retry = 0;
+        loop {
+            let result = request().send().await;
+            if !retry::is_retryable(&result)
+                || retry >= self.retry.max_retries
+                || !self.retry_budget.withdraw()
+            {
+                return result;
+            }
+            tokio::time::sleep(self.retry.backoff(retry)).await;
+            retry += 1;
         }
     }
 
@@ -47,9 +108,7 @@ impl HttpClient {
     }
 
     pub async fn indexes(&self) -> Vec<IndexInfo> {
-        self.client
-            .get(format!("{}/indexes", self.url_api))
-            .send()
+        self.send_idempotent(|| self.client.get(format!("{}/indexes", self.url_api)))
             .await
             .unwrap()
             .json()
@@ -90,6 +149,11 @@ impl HttpClient {
             vector,
             filter,
             limit,
+            min_write_timestamp: None,
+            write_wait_timeout_ms: None,
+            oversampling: None,
+            rerank: None,
+            payload: None,
         };
         self.post_ann_data(keyspace_name, index_name, &request)
             .await
@@ -101,15 +165,69 @@ impl HttpClient {
         index_name: &IndexName,
         data: &T,
     ) -> reqwest::Response {
-        self.client
-            .post(format!(
-                "{}/indexes/{}/{}/ann",
-                self.url_api, keyspace_name, index_name
-            ))
-            .json(data)
-            .send()
-            .await
-            .unwrap()
+        self.send_idempotent(|| {
+            self.client
+                .post(format!(
+                    "{}/indexes/{}/{}/ann",
+                    self.url_api, keyspace_name, index_name
+                ))
+                .json(data)
+        })
+        .await
+        .unwrap()
+    }
+
+    /// Searches the vectors of a batch in parallel, each up to its limit or the shared `limit`.
+    pub async fn ann_batch(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        queries: Vec<(Vector, Option<Limit>)>,
+        limit: Limit,
+    ) -> anyhow::Result<PostIndexAnnBatchResponse> {
+        let request = PostIndexAnnBatchRequest {
+            queries: queries
+                .into_iter()
+                .map(|(vector, limit)| PostIndexAnnBatchQuery { vector, limit })
+                .collect(),
+            limit,
+        };
+        let response = self
+            .send_idempotent(|| {
+                self.client
+                    .post(format!(
+                        "{}/indexes/{}/{}/ann/batch",
+                        self.url_api, keyspace_name, index_name
+                    ))
+                    .json(&request)
+            })
+            .await?;
+        json_or_error(response).await
+    }
+
+    /// Searches all vectors within `max_distance` of the vector.
+    pub async fn range(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        vector: Vector,
+        max_distance: f32,
+    ) -> anyhow::Result<PostIndexAnnResponse> {
+        let request = PostIndexRangeRequest {
+            vector,
+            max_distance,
+        };
+        let response = self
+            .send_idempotent(|| {
+                self.client
+                    .post(format!(
+                        "{}/indexes/{}/{}/range",
+                        self.url_api, keyspace_name, index_name
+                    ))
+                    .json(&request)
+            })
+            .await?;
+        json_or_error(response).await
     }
 
     pub async fn bm25(
@@ -153,27 +271,104 @@ impl HttpClient {
         index_name: &IndexName,
     ) -> anyhow::Result<IndexStatusResponse> {
         let response = self
-            .client
-            .get(format!(
-                "{}/indexes/{}/{}/status",
-                self.url_api, keyspace_name, index_name
-            ))
-            .send()
+            .send_idempotent(|| {
+                self.client.get(format!(
+                    "{}/indexes/{}/{}/status",
+                    self.url_api, keyspace_name, index_name
+                ))
+            })
             .await?;
+        json_or_error(response).await
+    }
 
-        if response.status().is_success() {
-            Ok(response.json::<IndexStatusResponse>().await?)
-        } else {
-            let status = response.status();
-            let error_text = response.text().await?;
-            Err(anyhow::anyhow!("HTTP {status}: {error_text}"))
+    pub async fn index_stats(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+    ) -> anyhow::Result<IndexStatsResponse> {
+        let response = self
+            .send_idempotent(|| {
+                self.client.get(format!(
+                    "{}/indexes/{}/{}/stats",
+                    self.url_api, keyspace_name, index_name
+                ))
+            })
+            .await?;
+        json_or_error(response).await
+    }
+
+    /// Returns clusters of near-duplicate vectors of an index. It searches the whole index.
+    pub async fn duplicates(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        max_distance: f32,
+    ) -> anyhow::Result<PostIndexDuplicatesResponse> {
+        let request = PostIndexDuplicatesRequest { max_distance };
+        let response = self
+            .send_idempotent(|| {
+                self.client
+                    .post(format!(
+                        "{}/indexes/{}/{}/duplicates",
+                        self.url_api, keyspace_name, index_name
+                    ))
+                    .json(&request)
+            })
+            .await?;
+        json_or_error(response).await
+    }
+
+    /// Returns up to `k` centroids of the vectors of an index with the sizes of their clusters.
+    pub async fn kmeans(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        k: NonZeroUsize,
+    ) -> anyhow::Result<PostIndexKMeansResponse> {
+        let request = PostIndexKMeansRequest { k };
+        let response = self
+            .send_idempotent(|| {
+                self.client
+                    .post(format!(
+                        "{}/indexes/{}/{}/kmeans",
+                        self.url_api, keyspace_name, index_name
+                    ))
+                    .json(&request)
+            })
+            .await?;
+        json_or_error(response).await
+    }
+
+    /// Returns the versions of the indexes served by the node.
+    pub async fn index_versions(&self) -> anyhow::Result<Vec<IndexVersionInfo>> {
+        let response = self
+            .send_idempotent(|| {
+                self.client
+                    .get(format!("{}/indexes/versions", self.url_api))
+            })
+            .await?;
+        json_or_error(response).await
+    }
+
+    /// Returns the versions of the indexes served by the nodes of the cluster, or `None` when
+    /// clustering is not configured on the node.
+    pub async fn cluster_index_versions(
+        &self,
+    ) -> anyhow::Result<Option<Vec<ClusterIndexVersions>>> {
+        let response = self
+            .send_idempotent(|| {
+                self.client
+                    .get(format!("{}/cluster/index-versions", self.url_api))
+            })
+            .await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
         }
+        json_or_error(response).await.map(Some)
     }
 
     pub async fn info(&self) -> InfoResponse {
-        self.client
-            .get(format!("{}/info", self.url_api))
-            .send()
+        self.send_idempotent(|| self.client.get(format!("{}/info", self.url_api)))
             .await
             .unwrap()
             .json()
@@ -183,14 +378,32 @@ impl HttpClient {
 
     pub async fn status(&self) -> anyhow::Result<NodeStatus> {
         Ok(self
-            .client
-            .get(format!("{}/status", self.url_api))
-            .send()
+            .send_idempotent(|| self.client.get(format!("{}/status", self.url_api)))
             .await?
             .json()
             .await?)
     }
 
+    /// Status code of the liveness probe.
+    pub async fn healthz(&self) -> anyhow::Result<StatusCode> {
+        Ok(self
+            .client
+            .get(format!("{}/healthz", self.url))
+            .send()
+            .await?
+            .status())
+    }
+
+    /// Status code of the readiness probe.
+    pub async fn readyz(&self) -> anyhow::Result<StatusCode> {
+        Ok(self
+            .client
+            .get(format!("{}/readyz", self.url))
+            .send()
+            .await?
+            .status())
+    }
+
     pub async fn internals_counters(&self) -> anyhow::Result<BTreeMap<String, u64>> {
         Ok(self
             .client
@@ -229,10 +442,7 @@ impl HttpClient {
 
     pub async fn get_metrics_text(&self) -> String {
         self.client
-            .get(format!(
-                "{}/metrics",
-                self.url_api.trim_end_matches("/api/v1")
-            ))
+            .get(format!("{}/metrics", self.url))
             .send()
             .await
             .unwrap()
@@ -241,3 +451,11 @@ impl HttpClient {
             .unwrap()
     }
 }
+
+async fn json_or_error<T: DeserializeOwned>(response: Response) -> anyhow::Result<T> {
+    if response.status().is_success() {
+        Ok(response.json().await?)
+    } else {
+        Err(ServerError::from_response(response).await.into())
+    }
+}
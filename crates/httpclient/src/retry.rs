@@ -0,0 +1,152 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use reqwest::StatusCode;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// How idempotent requests are retried after a transient failure: a connection error, a timeout,
+/// or a 502, 503 or 504 response.
+///
+/// The delay before the n-th retry is drawn at random up to `initial_backoff * 2^n`, capped by
+/// `max_backoff`. Retries are also limited by a budget shared by all requests of a client, so a
+/// failing server is not flooded with retries.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries of a single request.
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Retries earned by each request.
+    pub budget_ratio: f64,
+    /// Retries available when no request has been made yet, and the most the budget can hold.
+    pub budget_max: u32,
+}
+
+impl RetryPolicy {
+    /// A policy which never retries.
+    pub const NONE: Self = Self {
+        max_retries: 0,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+        budget_ratio: 0.0,
+        budget_max: 0,
+    };
+
+    /// A policy retrying a request up to `max_retries` times, with delays from 100ms up to 5s and
+    /// a budget of one retry per five requests.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(5),
+            budget_ratio: 0.2,
+            budget_max: 10 * max_retries,
+        }
+    }
+
+    pub(crate) fn backoff(&self, retry: u32) -> Duration {
+        let max = self
+            .initial_backoff
+            .saturating_mul(2_u32.saturating_pow(retry))
+            .min(self.max_backoff);
+        max.mul_f64(rand::random_range(0.0..=1.0))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+pub(crate) fn is_retryable(result: &reqwest::Result<reqwest::Response>) -> bool {
+    match result {
+        Ok(response) => matches!(
+            response.status(),
+            StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE | StatusCode::GATEWAY_TIMEOUT
+        ),
+        Err(err) => err.is_connect() || err.is_timeout(),
+    }
+}
+
+/// Retries left to a client. Every request adds `budget_ratio` and every retry takes one.
+#[derive(Debug)]
+pub(crate) struct RetryBudget {
+    tokens: Mutex<f64>,
+}
+
+impl RetryBudget {
+    pub(crate) fn new(policy: &RetryPolicy) -> Self {
+        Self {
+            tokens: Mutex::new(policy.budget_max as f64),
+        }
+    }
+
+    pub(crate) fn deposit(&self, policy: &RetryPolicy) {
+        let mut tokens = self.tokens.lock().unwrap();
+        *tokens = (*tokens + policy.budget_ratio).min(policy.budget_max as f64);
+    }
+
+    pub(crate) fn withdraw(&self) -> bool {
+        let mut tokens = self.tokens.lock().unwrap();
+        if *tokens < 1.0 {
+            return false;
+        }
+        *tokens -= 1.0;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_up_to_max() {
+        let policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+            ..RetryPolicy::new(10)
+        };
+        for _ in 0..100 {
+            assert!(policy.backoff(0) <= Duration::from_millis(100));
+            assert!(policy.backoff(2) <= Duration::from_millis(400));
+            assert!(policy.backoff(9) <= Duration::from_secs(1));
+            assert!(policy.backoff(u32::MAX) <= Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn budget_is_earned_by_requests() {
+        let policy = RetryPolicy {
+            budget_ratio: 0.5,
+            budget_max: 2,
+            ..RetryPolicy::new(3)
+        };
+        let budget = RetryBudget::new(&policy);
+
+        assert!(budget.withdraw());
+        assert!(budget.withdraw());
+        assert!(!budget.withdraw());
+
+        budget.deposit(&policy);
+        assert!(!budget.withdraw());
+        budget.deposit(&policy);
+        assert!(budget.withdraw());
+
+        (0..10).for_each(|_| budget.deposit(&policy));
+        assert!(budget.withdraw());
+        assert!(budget.withdraw());
+        assert!(!budget.withdraw());
+    }
+
+    #[test]
+    fn no_retries_by_default() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.max_retries, 0);
+        assert!(!RetryBudget::new(&policy).withdraw());
+    }
+}
@@ -0,0 +1,176 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::HttpClient;
+use anyhow::anyhow;
+use anyhow::ensure;
+use httpapi::IndexName;
+use httpapi::IndexStatus;
+use httpapi::IndexStatusResponse;
+use httpapi::KeyspaceName;
+use std::time::Duration;
+use tokio::time;
+
+/// How long to wait for a condition and how often to check it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WaitOptions {
+    pub timeout: Duration,
+    pub interval: Duration,
+}
+
+impl WaitOptions {
+    /// Waits up to `timeout`, checking every 100ms.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            interval: Duration::from_millis(100),
+        }
+    }
+
+    pub fn with_interval(self, interval: Duration) -> Self {
+        Self { interval, ..self }
+    }
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(60))
+    }
+}
+
+/// Calls `check` every `wait.interval` until it succeeds. On timeout returns the last error of
+/// `check`, which should explain why the condition is not met yet.
+async fn wait_until<T>(
+    wait: WaitOptions,
+    what: String,
+    mut check: impl AsyncFnMut() -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    let mut last_err = None;
+    let result = time::timeout(wait.timeout, async {
+        loop {
+            match check().await {
+                Ok(value) => return value,
+                Err(err) => last_err = Some(err),
+            }
+            time::sleep(wait.interval).await;
+        }
+    })
+    .await;
+    result.map_err(|_| match last_err {
+        Some(err) => err.context(format!("timeout waiting for {what}")),
+        None => anyhow!("timeout waiting for {what}"),
+    })
+}
+
+impl HttpClient {
+    /// Waits until the index reports `status`.
+    pub async fn wait_for_status(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        status: IndexStatus,
+        wait: WaitOptions,
+    ) -> anyhow::Result<IndexStatusResponse> {
+        wait_until(
+            wait,
+            format!(
+                "index {keyspace_name}/{index_name} to be {status:?} at {}",
+                self.url()
+            ),
+            async || {
+                let response = self.index_status(keyspace_name, index_name).await?;
+                ensure!(
+                    response.status == status,
+                    "index {keyspace_name}/{index_name} is {:?}",
+                    response.status
+                );
+                Ok(response)
+            },
+        )
+        .await
+    }
+
+    /// Waits until the index is serving queries.
+    pub async fn wait_for_serving(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        wait: WaitOptions,
+    ) -> anyhow::Result<IndexStatusResponse> {
+        self.wait_for_status(keyspace_name, index_name, IndexStatus::Serving, wait)
+            .await
+    }
+
+    /// Waits until the index is serving queries and holds `count` vectors.
+    pub async fn wait_for_index_count(
+        &self,
+        keyspace_name: &KeyspaceName,
+        index_name: &IndexName,
+        count: usize,
+        wait: WaitOptions,
+    ) -> anyhow::Result<IndexStatusResponse> {
+        wait_until(
+            wait,
+            format!(
+                "index {keyspace_name}/{index_name} to hold {count} vectors at {}",
+                self.url()
+            ),
+            async || {
+                let response = self.index_status(keyspace_name, index_name).await?;
+                ensure!(
+                    response.status == IndexStatus::Serving && response.count == count,
+                    "index {keyspace_name}/{index_name} is {:?} with {} vectors",
+                    response.status,
+                    response.count
+                );
+                Ok(response)
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn wait_until_reports_last_error_on_timeout() {
+        let mut checks = 0;
+        let err = wait_until(
+            WaitOptions::new(Duration::from_millis(50)).with_interval(Duration::from_millis(10)),
+            "nothing".to_string(),
+            async || -> anyhow::Result<()> {
+                checks += 1;
+                Err(anyhow!("check {checks} failed"))
+            },
+        )
+        .await
+        .unwrap_err();
+        assert!(checks > 1);
+        assert_eq!(err.to_string(), "timeout waiting for nothing");
+        assert_eq!(
+            err.root_cause().to_string(),
+            format!("check {checks} failed")
+        );
+    }
+
+    #[tokio::test]
+    async fn wait_until_returns_first_success() {
+        let mut checks = 0;
+        let value = wait_until(
+            WaitOptions::default().with_interval(Duration::from_millis(1)),
+            "third check".to_string(),
+            async || {
+                checks += 1;
+                ensure!(checks == 3, "check {checks} failed");
+                Ok(checks)
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(value, 3);
+    }
+}
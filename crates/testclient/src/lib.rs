@@ -59,6 +59,11 @@ impl TestClient {
                 vector,
                 filter,
                 limit,
+                min_write_timestamp: None,
+                write_wait_timeout_ms: None,
+                oversampling: None,
+                rerank: None,
+                payload: None,
             })
             .await
     }
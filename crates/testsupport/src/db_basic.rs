@@ -18,6 +18,7 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::Sender;
+use tokio::sync::watch;
 use uuid::Uuid;
 use vector_store::AsyncInProgress;
 use vector_store::ColumnName;
@@ -46,9 +47,9 @@ use vector_store::db_index::DbIndex;
 use vector_store::node_state::Event;
 use vector_store::node_state::NodeState;
 
-pub(crate) type RxIndexedRow = mpsc::Receiver<(DbIndexedRow, AsyncInProgress)>;
-pub(crate) type TxIndexedRow = mpsc::Sender<(DbIndexedRow, AsyncInProgress)>;
-pub(crate) type ScanFn = Box<dyn FnOnce(TxIndexedRow) -> BoxFuture<'static, ()> + Send + Sync>;
+pub type RxIndexedRow = mpsc::Receiver<(DbIndexedRow, AsyncInProgress)>;
+pub type TxIndexedRow = mpsc::Sender<(DbIndexedRow, AsyncInProgress)>;
+pub type ScanFn = Box<dyn FnOnce(TxIndexedRow) -> BoxFuture<'static, ()> + Send + Sync>;
 
 fn make_scan_fn(rows: impl Iterator<Item = DbIndexedRow> + Send + Sync + 'static) -> ScanFn {
     Box::new(move |tx| {
@@ -68,11 +69,11 @@ fn make_scan_fn(rows: impl Iterator<Item = DbIndexedRow> + Send + Sync + 'static
     })
 }
 
-pub(crate) fn pending_scan_fn() -> ScanFn {
+pub fn pending_scan_fn() -> ScanFn {
     Box::new(|_tx| std::future::pending::<()>().boxed())
 }
 
-pub(crate) fn scan_fn_vectors<I>(items: I) -> ScanFn
+pub fn scan_fn_vectors<I>(items: I) -> ScanFn
 where
     I: IntoIterator<
         Item = (
@@ -106,7 +107,7 @@ where
     )
 }
 
-pub(crate) fn scan_fn_documents<I>(items: I) -> ScanFn
+pub fn scan_fn_documents<I>(items: I) -> ScanFn
 where
     I: IntoIterator<Item = (PrimaryKey, Option<String>, Timestamp)>,
     I::IntoIter: Send + Sync + 'static,
@@ -126,9 +127,9 @@ where
 }
 
 #[derive(Clone, derive_more::Debug)]
-pub(crate) struct DbBasic(#[debug(skip)] Arc<RwLock<DbMock>>);
+pub struct DbBasic(#[debug(skip)] Arc<RwLock<DbMock>>);
 
-pub(crate) fn new(node_state: Sender<NodeState>) -> (mpsc::Sender<Db>, DbBasic) {
+pub fn new(node_state: Sender<NodeState>) -> (mpsc::Sender<Db>, DbBasic) {
     let (tx, mut rx) = mpsc::channel(10);
     let db = DbBasic::new();
     tokio::spawn({
@@ -142,11 +143,11 @@ pub(crate) fn new(node_state: Sender<NodeState>) -> (mpsc::Sender<Db>, DbBasic)
     (tx, db)
 }
 
-pub(crate) struct Table {
-    pub(crate) primary_keys: NonemptyArc<ColumnName>,
-    pub(crate) partition_key_count: usize,
-    pub(crate) columns: Arc<HashMap<ColumnName, NativeType>>,
-    pub(crate) dimensions: HashMap<ColumnName, Dimensions>,
+pub struct Table {
+    pub primary_keys: NonemptyArc<ColumnName>,
+    pub partition_key_count: usize,
+    pub columns: Arc<HashMap<ColumnName, NativeType>>,
+    pub dimensions: HashMap<ColumnName, Dimensions>,
 }
 
 struct Index {
@@ -184,8 +185,14 @@ impl DbMock {
     }
 }
 
+impl Default for DbBasic {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl DbBasic {
-    pub(crate) fn new() -> Self {
+    pub fn new() -> Self {
         Self(Arc::new(RwLock::new(DbMock {
             schema_version: CqlTimeuuid::from(Uuid::new_v4()),
             keyspaces: HashMap::new(),
@@ -195,7 +202,7 @@ impl DbBasic {
         })))
     }
 
-    pub(crate) fn add_table(
+    pub fn add_table(
         &self,
         keyspace_name: KeyspaceName,
         table_name: TableName,
@@ -216,7 +223,7 @@ impl DbBasic {
         Ok(())
     }
 
-    pub(crate) fn add_vector_column(
+    pub fn add_vector_column(
         &self,
         keyspace_name: KeyspaceName,
         table_name: TableName,
@@ -236,7 +243,7 @@ impl DbBasic {
         Ok(())
     }
 
-    pub(crate) fn add_index(
+    pub fn add_index(
         &self,
         metadata: IndexMetadata,
         fullscan_fn: Option<ScanFn>,
@@ -273,7 +280,7 @@ impl DbBasic {
         Ok(())
     }
 
-    pub(crate) fn del_index(
+    pub fn del_index(
         &self,
         keyspace_name: &KeyspaceName,
         index_name: &IndexName,
@@ -291,15 +298,15 @@ impl DbBasic {
         Ok(())
     }
 
-    pub(crate) fn set_next_get_db_index_failed(&self) {
+    pub fn set_next_get_db_index_failed(&self) {
         self.0.write().unwrap().next_get_db_index_failed = true;
     }
 
-    pub(crate) fn set_next_full_scan_progress(&self, progress: Progress) {
+    pub fn set_next_full_scan_progress(&self, progress: Progress) {
         self.0.write().unwrap().next_full_scan_progress = Some(progress);
     }
 
-    pub(crate) fn simulate_endless_get_indexes_processing(&self) {
+    pub fn simulate_endless_get_indexes_processing(&self) {
         self.0
             .write()
             .unwrap()
@@ -347,6 +354,11 @@ fn process_db(db: &DbBasic, msg: Db, node_state: Sender<NodeState>) {
                                     IndexKind::Vs(_) => DbIndexKind::VectorSearch,
                                     IndexKind::Fts(_) => DbIndexKind::FullTextSearch,
                                 },
+                                embedding_model: index
+                                    .metadata
+                                    .vs()
+                                    .and_then(|options| options.embedding_model.clone()),
+                                ttl: index.metadata.kind.ttl(),
                             })
                     })
                     .collect()))
@@ -421,10 +433,15 @@ fn process_db(db: &DbBasic, msg: Db, node_state: Sender<NodeState>) {
             .send(true)
             .map_err(|_| anyhow!("Db::IsValidIndex: unable to send response"))
             .unwrap(),
+
+        Db::GetTexts { tx, .. } => tx
+            .send(Err(anyhow!("DbBasic does not store values of rows")))
+            .map_err(|_| anyhow!("Db::GetTexts: unable to send response"))
+            .unwrap(),
     }
 }
 
-pub(crate) fn new_db_index(
+pub fn new_db_index(
     mut db: DbBasic,
     metadata: IndexMetadata,
     node_state: Sender<NodeState>,
@@ -601,6 +618,17 @@ async fn spawn_process_db_index(
                 })
                 .map_err(|_| anyhow!("DbIndex::GetTargetColumn: unable to send response"))
                 .unwrap(),
+
+            // Rows are sent to the index as soon as they are written.
+            DbIndex::CdcProgress { tx } => tx
+                .send(watch::channel(Timestamp::MAX).1)
+                .map_err(|_| anyhow!("DbIndex::CdcProgress: unable to send response"))
+                .unwrap(),
+
+            DbIndex::GetVectors { tx, .. } => tx
+                .send(Err(anyhow!("DbBasic does not store values of rows")))
+                .map_err(|_| anyhow!("DbIndex::GetVectors: unable to send response"))
+                .unwrap(),
         }
     });
 }
@@ -0,0 +1,152 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! In-memory mocks of ScyllaDB and OpenSearch together with [`TestVectorStore`], which runs
+//! the vector-store service against them for integration tests.
+
+pub mod db_basic;
+pub mod mock_opensearch;
+
+use crate::db_basic::DbBasic;
+use crate::db_basic::ScanFn;
+use crate::db_basic::Table;
+use crate::mock_opensearch::TestOpenSearchServer;
+use anyhow::bail;
+use httpclient::HttpClient;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time;
+use vector_store::Config;
+use vector_store::EngineHandle;
+use vector_store::IndexKey;
+use vector_store::IndexMetadata;
+use vector_store::VectorStore;
+use vector_store::VectorStoreBuilder;
+use vector_store::node_state::IndexStatus;
+
+const WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+const WAIT_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Returns a config which binds the HTTP server to a random local port.
+pub fn test_config() -> Config {
+    Config {
+        vector_store_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+        ..Default::default()
+    }
+}
+
+pub struct TestVectorStoreBuilder {
+    config: Config,
+    opensearch: bool,
+}
+
+impl TestVectorStoreBuilder {
+    /// Replaces the default [`test_config`].
+    pub fn config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Uses a mock OpenSearch server as the vector index backend instead of usearch.
+    pub fn opensearch(mut self) -> Self {
+        self.opensearch = true;
+        self
+    }
+
+    pub async fn build(self) -> anyhow::Result<TestVectorStore> {
+        let node_state = vector_store::new_node_state().await;
+        let (db_actor, db) = db_basic::new(node_state.clone());
+        let mut builder = VectorStoreBuilder::new(self.config)
+            .node_state(node_state)
+            .db(db_actor);
+
+        let opensearch = if self.opensearch {
+            let server = TestOpenSearchServer::start().await;
+            let (_, config_rx) = watch::channel(Arc::new(builder.config().clone()));
+            builder = builder.index_factory(vector_store::new_index_factory_opensearch(
+                server.base_url(),
+                config_rx,
+            )?);
+            Some(server)
+        } else {
+            None
+        };
+
+        let store = builder.build().await?;
+        let Some(addr) = store.addr().await else {
+            bail!("the vector-store HTTP server is not listening");
+        };
+        Ok(TestVectorStore {
+            client: HttpClient::new(addr),
+            db,
+            store,
+            _opensearch: opensearch,
+        })
+    }
+}
+
+/// The vector-store service running against an in-memory db and, optionally, a mock OpenSearch.
+///
+/// Tables, indexes and rows are fed through [`TestVectorStore::db`], queries are sent through
+/// [`TestVectorStore::client`].
+pub struct TestVectorStore {
+    client: HttpClient,
+    db: DbBasic,
+    store: VectorStore,
+    _opensearch: Option<TestOpenSearchServer>,
+}
+
+impl TestVectorStore {
+    pub fn builder() -> TestVectorStoreBuilder {
+        TestVectorStoreBuilder {
+            config: test_config(),
+            opensearch: false,
+        }
+    }
+
+    /// Starts the service with the default configuration and the usearch backend.
+    pub async fn start() -> anyhow::Result<Self> {
+        Self::builder().build().await
+    }
+
+    pub fn client(&self) -> &HttpClient {
+        &self.client
+    }
+
+    pub fn db(&self) -> &DbBasic {
+        &self.db
+    }
+
+    pub fn engine(&self) -> &EngineHandle {
+        self.store.engine()
+    }
+
+    /// Creates a table and an index over it. Rows from `fullscan` are indexed during the initial
+    /// scan and rows from `cdc` afterwards.
+    pub fn add_index(
+        &self,
+        index: IndexMetadata,
+        table: Table,
+        fullscan: Option<ScanFn>,
+        cdc: Option<ScanFn>,
+    ) -> anyhow::Result<()> {
+        self.db
+            .add_table(index.keyspace_name.clone(), index.table_name.clone(), table)?;
+        self.db.add_index(index, fullscan, cdc)
+    }
+
+    /// Waits until the index is serving queries.
+    pub async fn wait_for_serving(&self, key: &IndexKey) -> anyhow::Result<()> {
+        time::timeout(WAIT_TIMEOUT, async {
+            while self.engine().index_status(key) != Some(IndexStatus::Serving) {
+                time::sleep(WAIT_INTERVAL).await;
+            }
+        })
+        .await
+        .map_err(|_| anyhow::anyhow!("timeout waiting for index {key} to be serving"))
+    }
+}
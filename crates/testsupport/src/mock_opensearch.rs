@@ -14,6 +14,7 @@ use axum::routing::post;
 use axum::routing::put;
 use serde_json::Value;
 use serde_json::json;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -32,6 +33,8 @@ const RESERVE_INCREMENT: usize = 1000000;
 #[derive(Clone, Default)]
 struct MockServerState {
     indices: Arc<RwLock<HashMap<String, Arc<RwLock<Index>>>>>,
+    // Column values of documents per index, used by filters.
+    columns: Arc<RwLock<HashMap<String, HashMap<u64, Value>>>>,
 }
 
 pub struct TestOpenSearchServer {
@@ -131,6 +134,13 @@ async fn add_document(
             .collect::<Vec<_>>();
         let res = index_lock.read().unwrap().add(id, &vector);
         if res.is_ok() {
+            state
+                .columns
+                .write()
+                .unwrap()
+                .entry(index)
+                .or_default()
+                .insert(id, payload["columns"].clone());
             return (StatusCode::OK, Json(json!({ "result": "created" })));
         } else {
             error!(
@@ -185,7 +195,20 @@ async fn search(
             .map(|v| v.as_f64().unwrap() as f32)
             .collect::<Vec<_>>();
         let limit = vector["k"].as_u64().unwrap_or(10) as usize;
-        let results = index_lock.read().unwrap().search(&embedding, limit);
+        let results = if vector["filter"].is_null() {
+            index_lock.read().unwrap().search(&embedding, limit)
+        } else {
+            let columns = state.columns.read().unwrap();
+            let columns = columns.get(&index);
+            index_lock
+                .read()
+                .unwrap()
+                .filtered_search(&embedding, limit, |id| {
+                    columns
+                        .and_then(|columns| columns.get(&id))
+                        .is_some_and(|columns| matches(&vector["filter"], columns))
+                })
+        };
         if results.is_err() {
             return {
                 (
@@ -227,6 +250,9 @@ async fn remove_document(
     let indices = state.indices.read().unwrap();
     if let Some(index_lock) = indices.get(&index) {
         let res = index_lock.read().unwrap().remove(id);
+        if let Some(columns) = state.columns.write().unwrap().get_mut(&index) {
+            columns.remove(&id);
+        }
         if res.is_err() {
             return (
                 StatusCode::INTERNAL_SERVER_ERROR,
@@ -242,3 +268,61 @@ async fn remove_document(
         Json(json!({ "error": "index not found" })),
     )
 }
+
+/// Evaluates the subset of the query DSL used by filtered ANN against the column values of a
+/// document.
+fn matches(filter: &Value, columns: &Value) -> bool {
+    if let Some(term) = filter["term"].as_object() {
+        return term
+            .iter()
+            .all(|(field, value)| column(columns, field) == Some(value));
+    }
+    if let Some(terms) = filter["terms"].as_object() {
+        return terms.iter().all(|(field, values)| {
+            let value = column(columns, field);
+            values
+                .as_array()
+                .is_some_and(|values| values.iter().any(|other| Some(other) == value))
+        });
+    }
+    if let Some(range) = filter["range"].as_object() {
+        return range.iter().all(|(field, bounds)| {
+            let Some(value) = column(columns, field) else {
+                return false;
+            };
+            bounds.as_object().is_some_and(|bounds| {
+                bounds.iter().all(|(op, bound)| {
+                    compare(value, bound).is_some_and(|ord| match op.as_str() {
+                        "lt" => ord.is_lt(),
+                        "lte" => ord.is_le(),
+                        "gt" => ord.is_gt(),
+                        "gte" => ord.is_ge(),
+                        _ => false,
+                    })
+                })
+            })
+        });
+    }
+    let query = &filter["bool"];
+    let all = query["filter"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .all(|filter| matches(filter, columns));
+    let any = query["should"]
+        .as_array()
+        .is_none_or(|should| should.iter().any(|filter| matches(filter, columns)));
+    all && any
+}
+
+fn column<'a>(columns: &'a Value, field: &str) -> Option<&'a Value> {
+    columns.get(field.strip_prefix("columns.")?)
+}
+
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::Number(lhs), Value::Number(rhs)) => lhs.as_f64()?.partial_cmp(&rhs.as_f64()?),
+        (Value::String(lhs), Value::String(rhs)) => Some(lhs.cmp(rhs)),
+        _ => None,
+    }
+}
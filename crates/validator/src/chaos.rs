@@ -0,0 +1,219 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::TestActors;
+use crate::common::*;
+use e2etest_firewall::FirewallExt;
+use e2etest_vector_store_cluster::VectorStoreClusterExt;
+use httpclient::WaitOptions;
+use scylla::client::session::Session;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tap::Pipe;
+use tokio::time;
+use tracing::info;
+
+/// Faults injected during a single test.
+const FAULT_ROUNDS: usize = 5;
+/// How long a fault lasts before the node is brought back.
+const FAULT_DURATION: Duration = Duration::from_secs(5);
+/// How long the cluster runs without faults between two rounds.
+const RECOVERY_DURATION: Duration = Duration::from_secs(10);
+/// The share of failed ANN queries tolerated while faults are injected. Queries fail while no
+/// vector-store node is up.
+const MAX_READ_ERROR_RATIO: f64 = 0.5;
+/// The share of failed writes tolerated. Faults do not touch the path from the client to
+/// ScyllaDB, so writes should not fail.
+const MAX_WRITE_ERROR_RATIO: f64 = 0.01;
+/// How long the indexes may take to converge after the last fault.
+const CONVERGENCE_TIMEOUT: Duration = Duration::from_secs(120);
+
+e2etest::group!(
+    name = chaos,
+    fixtures = (Fixture),
+    parent = crate::validator
+);
+
+struct Fixture {
+    actors: Arc<TestActors>,
+}
+
+impl e2etest::Fixture for Fixture {
+    async fn setup(setup: &mut impl e2etest::Setup) -> Self {
+        setup.setup::<TestActors>().await;
+        let actors = setup.get::<TestActors>().await.unwrap();
+        init_with_proxy(&actors).await;
+        Self { actors }
+    }
+
+    async fn teardown(self) {
+        cleanup(&self.actors).await;
+    }
+}
+
+#[derive(Debug)]
+enum Fault {
+    /// Cuts the vector-store nodes off a ScyllaDB node by dropping the traffic of its proxy.
+    IsolateDbNode(Ipv4Addr),
+    /// Stops all vector-store nodes and starts them again.
+    RestartVectorStore,
+}
+
+impl Fault {
+    fn random(actors: &TestActors) -> Self {
+        let proxies = get_default_db_proxy_ips(actors);
+        let choice = rand::random_range(0..=proxies.len());
+        proxies
+            .get(choice)
+            .map_or(Self::RestartVectorStore, |&ip| Self::IsolateDbNode(ip))
+    }
+
+    async fn inject(&self, actors: &TestActors) {
+        match self {
+            Self::IsolateDbNode(ip) => actors.firewall.drop_traffic(vec![*ip]).await,
+            Self::RestartVectorStore => actors.vs.stop().await,
+        }
+    }
+
+    async fn heal(&self, actors: &TestActors) {
+        match self {
+            Self::IsolateDbNode(_) => actors.firewall.turn_off_rules().await,
+            Self::RestartVectorStore => {
+                actors
+                    .vs
+                    .start(get_proxy_vs_node_configs(actors).pipe(|mut nodes| {
+                        let translation_map = get_proxy_translation_map(actors);
+                        for node in nodes.iter_mut() {
+                            node.envs.insert(
+                                "VECTOR_STORE_CQL_URI_TRANSLATION_MAP".to_string(),
+                                serde_json::to_string(&translation_map).unwrap(),
+                            );
+                        }
+                        nodes
+                    }))
+                    .await;
+                assert!(actors.vs.wait_for_ready().await);
+            }
+        }
+    }
+}
+
+async fn count_rows(session: &Session, table: &TableName) -> usize {
+    get_query_results(format!("SELECT COUNT(*) FROM {table}"), session)
+        .await
+        .first_row::<(i64,)>()
+        .expect("failed to count rows")
+        .0 as usize
+}
+
+#[e2etest::test(group = chaos)]
+async fn ann_survives_random_faults(actors: Arc<TestActors>) {
+    info!("started");
+
+    let (session, clients) = prepare_connection_no_tls(&actors).await;
+    let keyspace = create_keyspace(&session).await;
+    let table = create_table(&session, "pk INT PRIMARY KEY, v VECTOR<FLOAT, 3>", None).await;
+    let index = create_index(CreateIndexQuery::new(&session, &clients, &table, "v")).await;
+    for client in &clients {
+        wait_for_index(client, &index).await;
+    }
+
+    info!("Starting insert and ANN load");
+    let stop = Arc::new(AtomicBool::new(false));
+    let writes = Arc::new(LoadStats::default());
+    let reads = Arc::new(LoadStats::default());
+    let insert = session
+        .prepare(format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"))
+        .await
+        .expect("failed to prepare an insert");
+    let writer = spawn_load(
+        Arc::clone(&stop),
+        Arc::clone(&writes),
+        Duration::from_millis(10),
+        {
+            let session = Arc::clone(&session);
+            move |id| {
+                let session = Arc::clone(&session);
+                let insert = insert.clone();
+                async move {
+                    session
+                        .execute_unpaged(&insert, (id, vec![id as f32, 1.0, 1.0]))
+                        .await
+                        .is_ok()
+                }
+            }
+        },
+    );
+    let reader = spawn_load(
+        Arc::clone(&stop),
+        Arc::clone(&reads),
+        Duration::from_millis(50),
+        {
+            let session = Arc::clone(&session);
+            let query = format!("SELECT pk FROM {table} ORDER BY v ANN OF [1.0, 1.0, 1.0] LIMIT 5");
+            move |_| {
+                let session = Arc::clone(&session);
+                let query = query.clone();
+                async move { session.query_unpaged(query, ()).await.is_ok() }
+            }
+        },
+    );
+
+    for round in 0..FAULT_ROUNDS {
+        let fault = Fault::random(&actors);
+        info!("Round {round}: injecting {fault:?}");
+        fault.inject(&actors).await;
+        time::sleep(FAULT_DURATION).await;
+        info!("Round {round}: healing {fault:?}");
+        fault.heal(&actors).await;
+        time::sleep(RECOVERY_DURATION).await;
+    }
+
+    info!("Stopping the load");
+    stop.store(true, Ordering::Relaxed);
+    writer.await.unwrap();
+    reader.await.unwrap();
+    info!(
+        "Writes: {} ok, {} failed; reads: {} ok, {} failed",
+        writes.ok.load(Ordering::Relaxed),
+        writes.failed.load(Ordering::Relaxed),
+        reads.ok.load(Ordering::Relaxed),
+        reads.failed.load(Ordering::Relaxed),
+    );
+    assert!(
+        writes.error_ratio() <= MAX_WRITE_ERROR_RATIO,
+        "too many failed writes: {}",
+        writes.error_ratio()
+    );
+    assert!(
+        reads.error_ratio() <= MAX_READ_ERROR_RATIO,
+        "too many failed ANN queries: {}",
+        reads.error_ratio()
+    );
+
+    info!("Waiting for the indexes to converge");
+    let count = count_rows(&session, &table).await;
+    for client in &clients {
+        client
+            .wait_for_index_count(
+                &index.keyspace,
+                &index.index,
+                count,
+                WaitOptions::new(CONVERGENCE_TIMEOUT).with_interval(Duration::from_secs(1)),
+            )
+            .await
+            .unwrap_or_else(|err| panic!("{err:#}"));
+    }
+
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+
+    info!("finished");
+}
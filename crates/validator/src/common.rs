@@ -15,10 +15,10 @@ use e2etest_vector_store_cluster::VectorStoreClusterExt;
 use e2etest_vector_store_cluster::VectorStoreNodeConfig;
 use httpapi::IndexInfo;
 use httpapi::IndexName;
-use httpapi::IndexStatus;
 use httpapi::IndexStatusResponse;
 use httpapi::KeyspaceName;
 use httpclient::HttpClient;
+use httpclient::WaitOptions;
 use itertools::Itertools;
 use scylla::client::session::Session;
 use scylla::client::session::TlsContext;
@@ -28,12 +28,15 @@ use scylla::statement::Statement;
 use std::collections::HashMap;
 use std::iter;
 use std::net::Ipv4Addr;
+use std::net::SocketAddrV4;
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 use tap::Pipe;
+use tokio::task::JoinHandle;
 use tokio::time;
 use tracing::info;
 
@@ -90,6 +93,14 @@ pub fn get_default_vs_ips(actors: &TestActors) -> Vec<Ipv4Addr> {
     ]
 }
 
+#[framed]
+pub fn get_default_vs_addrs(actors: &TestActors) -> Vec<SocketAddrV4> {
+    get_default_vs_ips(actors)
+        .into_iter()
+        .map(|ip| SocketAddrV4::new(ip, VS_PORT))
+        .collect()
+}
+
 #[framed]
 pub fn get_default_db_ips(actors: &TestActors) -> Vec<Ipv4Addr> {
     get_default_db_ips_for_subnet(&actors.services_subnet)
@@ -488,20 +499,14 @@ where
 
 #[framed]
 pub async fn wait_for_index(client: &HttpClient, index: &IndexInfo) -> IndexStatusResponse {
-    wait_for_value(
-        || async {
-            match client.index_status(&index.keyspace, &index.index).await {
-                Ok(resp) if resp.status == IndexStatus::Serving => Some(resp),
-                _ => None,
-            }
-        },
-        format!(
-            "Waiting for index to be SERVING at {url}",
-            url = client.url()
-        ),
-        Duration::from_secs(60),
-    )
-    .await
+    info!(
+        "Waiting for index to be SERVING at {url}",
+        url = client.url()
+    );
+    client
+        .wait_for_serving(&index.keyspace, &index.index, WaitOptions::default())
+        .await
+        .unwrap_or_else(|err| panic!("{err:#}"))
 }
 
 #[framed]
@@ -531,28 +536,67 @@ pub async fn wait_for_index_count(
     expected_count: usize,
 ) {
     for client in clients {
-        wait_for(
-            || async {
-                client
-                    .index_status(&index.keyspace, &index.index)
-                    .await
-                    .is_ok_and(|resp| {
-                        resp.status == httpapi::IndexStatus::Serving && resp.count == expected_count
-                    })
-            },
-            format!(
-                "index '{}/{}' to report count {} at {}",
-                index.keyspace,
-                index.index,
+        info!(
+            "Waiting for: index '{}/{}' to report count {} at {}",
+            index.keyspace,
+            index.index,
+            expected_count,
+            client.url()
+        );
+        client
+            .wait_for_index_count(
+                &index.keyspace,
+                &index.index,
                 expected_count,
-                client.url()
-            ),
-            Duration::from_secs(60),
-        )
-        .await;
+                WaitOptions::default(),
+            )
+            .await
+            .unwrap_or_else(|err| panic!("{err:#}"));
+    }
+}
+
+/// Counts the outcomes of the requests of a load.
+#[derive(Default)]
+pub struct LoadStats {
+    pub ok: AtomicUsize,
+    pub failed: AtomicUsize,
+}
+
+impl LoadStats {
+    pub fn record(&self, ok: bool) {
+        let counter = if ok { &self.ok } else { &self.failed };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn error_ratio(&self) -> f64 {
+        let ok = self.ok.load(Ordering::Relaxed);
+        let failed = self.failed.load(Ordering::Relaxed);
+        failed as f64 / (ok + failed).max(1) as f64
     }
 }
 
+/// Runs `query` until `stop` is set. The query gets its sequence number and returns whether it
+/// succeeded.
+pub fn spawn_load<F, Fut>(
+    stop: Arc<AtomicBool>,
+    stats: Arc<LoadStats>,
+    interval: Duration,
+    query: F,
+) -> JoinHandle<()>
+where
+    F: Fn(i32) -> Fut + Send + 'static,
+    Fut: Future<Output = bool> + Send,
+{
+    tokio::spawn(async move {
+        let mut id = 0;
+        while !stop.load(Ordering::Relaxed) {
+            stats.record(query(id).await);
+            id += 1;
+            time::sleep(interval).await;
+        }
+    })
+}
+
 #[framed]
 pub async fn get_query_results(query: impl Into<String>, session: &Session) -> QueryRowsResult {
     let mut stmt = Statement::new(query);
@@ -777,3 +821,33 @@ impl<'a> CreateIndexQuery<'a> {
         self
     }
 }
+
+/// Formats a vector as a CQL literal.
+pub fn format_vector(vector: &[f32]) -> String {
+    format!("[{}]", vector.iter().join(", "))
+}
+
+/// A deterministic generator of vectors with coordinates in [-1, 1), so datasets are the same in
+/// every run.
+pub struct VectorGenerator(u64);
+
+impl VectorGenerator {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// SplitMix64.
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    pub fn next_vector(&mut self, dimensions: usize) -> Vec<f32> {
+        (0..dimensions)
+            .map(|_| (self.next_u64() >> 40) as f32 / (1 << 23) as f32 - 1.0)
+            .collect()
+    }
+}
@@ -0,0 +1,150 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! ScyllaDB finds the vector-store nodes by their names in the test DNS. These tests change
+//! what the names resolve to while the cluster runs.
+
+use crate::TestActors;
+use crate::common::*;
+use e2etest_dns::DnsExt;
+use httpapi::KeyspaceName;
+use scylla::client::session::Session;
+use std::net::Ipv4Addr;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+const DATASET_SIZE: i32 = 100;
+/// An address in the services subnet where nothing listens.
+const UNREACHABLE_OCTET: u8 = 250;
+/// How long ScyllaDB may take to notice that a name resolves to another address.
+const RESOLVE_TIMEOUT: Duration = Duration::from_secs(60);
+
+e2etest::group!(name = dns, fixtures = (Fixture), parent = crate::validator);
+
+struct Fixture {
+    actors: Arc<TestActors>,
+}
+
+impl e2etest::Fixture for Fixture {
+    async fn setup(setup: &mut impl e2etest::Setup) -> Self {
+        setup.setup::<TestActors>().await;
+        let actors = setup.get::<TestActors>().await.unwrap();
+        init(&actors).await;
+        Self { actors }
+    }
+
+    async fn teardown(self) {
+        cleanup(&self.actors).await;
+    }
+}
+
+async fn ann_works(session: &Session, table: &TableName) -> bool {
+    session
+        .query_unpaged(
+            format!("SELECT pk FROM {table} ORDER BY v ANN OF [1.0, 1.0, 1.0] LIMIT 5"),
+            (),
+        )
+        .await
+        .is_ok()
+}
+
+async fn resolve_vs_names_to(actors: &TestActors, ips: &[Ipv4Addr]) {
+    for (name, ip) in VS_NAMES.iter().zip(ips) {
+        actors.dns.upsert(name.to_string(), *ip).await;
+    }
+}
+
+async fn prepare_table(actors: &TestActors) -> (Arc<Session>, KeyspaceName, TableName) {
+    let (session, clients) = prepare_connection(actors).await;
+    let keyspace = create_keyspace(&session).await;
+    let table = create_table(&session, "pk INT PRIMARY KEY, v VECTOR<FLOAT, 3>", None).await;
+    for pk in 0..DATASET_SIZE {
+        session
+            .query_unpaged(
+                format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"),
+                (pk, vec![pk as f32, 1.0, 1.0]),
+            )
+            .await
+            .expect("failed to insert data");
+    }
+    let index = create_index(CreateIndexQuery::new(&session, &clients, &table, "v")).await;
+    for client in &clients {
+        wait_for_index(client, &index).await;
+    }
+    (session, keyspace, table)
+}
+
+#[e2etest::test(group = dns)]
+async fn ann_works_with_wrong_vs_addresses(actors: Arc<TestActors>) {
+    info!("started");
+
+    let (session, keyspace, table) = prepare_table(&actors).await;
+    let vs_ips = get_default_vs_ips(&actors);
+
+    info!("Resolving the first vector-store name to an unreachable address");
+    let unreachable = actors.services_subnet.ip(UNREACHABLE_OCTET);
+    resolve_vs_names_to(&actors, &[unreachable]).await;
+    wait_for(
+        || ann_works(&session, &table),
+        "ANN queries to fail over to the other vector-store nodes",
+        RESOLVE_TIMEOUT,
+    )
+    .await;
+
+    info!("Resolving all vector-store names to the address of the second node");
+    resolve_vs_names_to(&actors, &[vs_ips[1]; 3]).await;
+    wait_for(
+        || ann_works(&session, &table),
+        "ANN queries to be served by the second vector-store node",
+        RESOLVE_TIMEOUT,
+    )
+    .await;
+
+    init_dns(&actors).await;
+    for _ in 0..10 {
+        assert!(ann_works(&session, &table).await);
+    }
+
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+
+    info!("finished");
+}
+
+#[e2etest::test(group = dns)]
+async fn ann_recovers_when_vs_names_resolve_again(actors: Arc<TestActors>) {
+    info!("started");
+
+    let (session, keyspace, table) = prepare_table(&actors).await;
+
+    info!("Resolving all vector-store names to an unreachable address");
+    let unreachable = actors.services_subnet.ip(UNREACHABLE_OCTET);
+    resolve_vs_names_to(&actors, &[unreachable; 3]).await;
+    wait_for(
+        || async { !ann_works(&session, &table).await },
+        "ANN queries to fail without reachable vector-store nodes",
+        RESOLVE_TIMEOUT,
+    )
+    .await;
+
+    info!("Restoring the vector-store names");
+    init_dns(&actors).await;
+    wait_for(
+        || ann_works(&session, &table),
+        "ANN queries to recover after the names resolve again",
+        RESOLVE_TIMEOUT,
+    )
+    .await;
+
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+
+    info!("finished");
+}
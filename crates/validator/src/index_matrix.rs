@@ -0,0 +1,187 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Creates an index for every combination of quantization, similarity function and key schema
+//! and checks that it is discovered, indexes the whole table and answers ANN queries.
+
+use crate::TestActors;
+use crate::common::*;
+use crate::recall::Similarity;
+use scylla::value::Row;
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::info;
+
+const DIMENSIONS: usize = 8;
+const DATASET_SIZE: usize = 100;
+const K: usize = 10;
+
+const QUANTIZATIONS: [&str; 5] = ["f32", "f16", "bf16", "i8", "b1"];
+
+e2etest::group!(
+    name = index_matrix,
+    fixtures = (Fixture),
+    parent = crate::validator
+);
+
+struct Fixture {
+    actors: Arc<TestActors>,
+}
+
+impl e2etest::Fixture for Fixture {
+    async fn setup(setup: &mut impl e2etest::Setup) -> Self {
+        setup.setup::<TestActors>().await;
+        let actors = setup.get::<TestActors>().await.unwrap();
+        init(&actors).await;
+        Self { actors }
+    }
+
+    async fn teardown(self) {
+        cleanup(&self.actors).await;
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum KeySchema {
+    PartitionKey,
+    CompositePartitionKey,
+    ClusteringKey,
+}
+
+const KEY_SCHEMAS: [KeySchema; 3] = [
+    KeySchema::PartitionKey,
+    KeySchema::CompositePartitionKey,
+    KeySchema::ClusteringKey,
+];
+
+impl KeySchema {
+    fn columns(self) -> String {
+        let keys = match self {
+            Self::PartitionKey => "pk INT PRIMARY KEY",
+            Self::CompositePartitionKey => "pk1 INT, pk2 INT, PRIMARY KEY ((pk1, pk2))",
+            Self::ClusteringKey => "pk INT, ck INT, PRIMARY KEY (pk, ck)",
+        };
+        format!("{keys}, v VECTOR<FLOAT, {DIMENSIONS}>")
+    }
+
+    fn key_columns(self) -> &'static str {
+        match self {
+            Self::PartitionKey => "pk",
+            Self::CompositePartitionKey => "pk1, pk2",
+            Self::ClusteringKey => "pk, ck",
+        }
+    }
+
+    /// The key of the row with the given id, as CQL literals.
+    fn key_values(self, id: usize) -> String {
+        match self {
+            Self::PartitionKey => id.to_string(),
+            Self::CompositePartitionKey | Self::ClusteringKey => {
+                format!("{}, {}", id / 10, id % 10)
+            }
+        }
+    }
+}
+
+async fn run_matrix_test(actors: &TestActors, similarity: Similarity) {
+    let (session, clients) = prepare_connection(actors).await;
+    let keyspace = create_keyspace(&session).await;
+
+    let mut generator = VectorGenerator::new(7);
+    let dataset: Vec<_> = (0..DATASET_SIZE)
+        .map(|_| generator.next_vector(DIMENSIONS))
+        .collect();
+    let query = format_vector(&generator.next_vector(DIMENSIONS));
+
+    for schema in KEY_SCHEMAS {
+        let table = create_table(&session, &schema.columns(), None).await;
+        for (id, vector) in dataset.iter().enumerate() {
+            session
+                .query_unpaged(
+                    format!(
+                        "INSERT INTO {table} ({}, v) VALUES ({}, ?)",
+                        schema.key_columns(),
+                        schema.key_values(id)
+                    ),
+                    (vector,),
+                )
+                .await
+                .expect("failed to insert data");
+        }
+
+        for quantization in QUANTIZATIONS {
+            info!("{quantization} {similarity:?} {schema:?}");
+            let index = create_index(
+                CreateIndexQuery::new(&session, &clients, &table, "v").options([
+                    ("similarity_function", similarity.option()),
+                    ("quantization", quantization),
+                ]),
+            )
+            .await;
+            for client in &clients {
+                let status = wait_for_index(client, &index).await;
+                assert_eq!(
+                    status.count,
+                    DATASET_SIZE,
+                    "{quantization} {similarity:?} {schema:?}: wrong count at {}",
+                    client.url()
+                );
+            }
+
+            let rows = get_query_results(
+                format!(
+                    "SELECT {} FROM {table} ORDER BY v ANN OF {query} LIMIT {K}",
+                    schema.key_columns()
+                ),
+                &session,
+            )
+            .await
+            .rows::<Row>()
+            .expect("failed to get rows")
+            .map(|row| format!("{:?}", row.expect("failed to get row").columns))
+            .collect::<HashSet<_>>();
+            assert_eq!(
+                rows.len(),
+                K,
+                "{quantization} {similarity:?} {schema:?}: ANN returned {} distinct rows",
+                rows.len()
+            );
+
+            session
+                .query_unpaged(format!("DROP INDEX {}", index.index), ())
+                .await
+                .expect("failed to drop an index");
+            for client in &clients {
+                wait_for_no_index(client, &index).await;
+            }
+        }
+    }
+
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+}
+
+#[e2etest::test(group = index_matrix)]
+async fn matrix_euclidean(actors: Arc<TestActors>) {
+    info!("started");
+    run_matrix_test(&actors, Similarity::Euclidean).await;
+    info!("finished");
+}
+
+#[e2etest::test(group = index_matrix)]
+async fn matrix_cosine(actors: Arc<TestActors>) {
+    info!("started");
+    run_matrix_test(&actors, Similarity::Cosine).await;
+    info!("finished");
+}
+
+#[e2etest::test(group = index_matrix)]
+async fn matrix_dot_product(actors: Arc<TestActors>) {
+    info!("started");
+    run_matrix_test(&actors, Similarity::DotProduct).await;
+    info!("finished");
+}
@@ -8,6 +8,7 @@ use crate::common::*;
 use httpapi::IndexName;
 use httpapi::IndexStatus;
 use httpapi::KeyspaceName;
+use httpclient::ServerError;
 use std::sync::Arc;
 use tracing::info;
 
@@ -90,8 +91,10 @@ async fn status_returns_404_for_non_existent_index(actors: Arc<TestActors>) {
     let index_name = IndexName::from("non_existent_index".to_string());
     for client in &clients {
         let index_status = client.index_status(&keyspace_name, &index_name).await;
-        assert!(index_status.is_err());
-        assert!(index_status.err().unwrap().to_string().contains("404"));
+        assert!(matches!(
+            index_status.unwrap_err().downcast_ref::<ServerError>(),
+            Some(ServerError::IndexNotFound(_))
+        ));
     }
 
     info!("finished");
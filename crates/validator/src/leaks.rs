@@ -0,0 +1,104 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::TestActors;
+use crate::common::*;
+use crate::resources::Limits;
+use crate::resources::Monitor;
+use httpclient::HttpClient;
+use scylla::client::session::Session;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+const ROUNDS: usize = 20;
+const DATASET_SIZE: i32 = 100;
+const SAMPLE_INTERVAL: Duration = Duration::from_secs(1);
+/// Every index is dropped at the end of a round, so the usage should come back to the level
+/// after the warm-up round.
+const LIMITS: Limits = Limits {
+    rss_growth: 1.5,
+    fd_growth: 50,
+};
+
+e2etest::group!(
+    name = leaks,
+    fixtures = (Fixture),
+    parent = crate::validator
+);
+
+struct Fixture {
+    actors: Arc<TestActors>,
+}
+
+impl e2etest::Fixture for Fixture {
+    async fn setup(setup: &mut impl e2etest::Setup) -> Self {
+        setup.setup::<TestActors>().await;
+        let actors = setup.get::<TestActors>().await.unwrap();
+        init(&actors).await;
+        Self { actors }
+    }
+
+    async fn teardown(self) {
+        cleanup(&self.actors).await;
+    }
+}
+
+/// Creates a table with an index, queries it and drops it.
+async fn index_round(session: &Session, clients: &[HttpClient]) {
+    let table = create_table(session, "pk INT PRIMARY KEY, v VECTOR<FLOAT, 3>", None).await;
+    for pk in 0..DATASET_SIZE {
+        session
+            .query_unpaged(
+                format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"),
+                (pk, vec![pk as f32, 1.0, 1.0]),
+            )
+            .await
+            .expect("failed to insert data");
+    }
+    let index = create_index(CreateIndexQuery::new(session, clients, &table, "v")).await;
+    for client in clients {
+        wait_for_index(client, &index).await;
+    }
+    for _ in 0..10 {
+        get_query_results(
+            format!("SELECT pk FROM {table} ORDER BY v ANN OF [1.0, 1.0, 1.0] LIMIT 5"),
+            session,
+        )
+        .await;
+    }
+    session
+        .query_unpaged(format!("DROP TABLE {table}"), ())
+        .await
+        .expect("failed to drop a table");
+    for client in clients {
+        wait_for_no_index(client, &index).await;
+    }
+}
+
+#[e2etest::test(group = leaks)]
+async fn index_lifecycle_does_not_leak(actors: Arc<TestActors>) {
+    info!("started");
+
+    let (session, clients) = prepare_connection(&actors).await;
+    let keyspace = create_keyspace(&session).await;
+
+    info!("Warming up");
+    index_round(&session, &clients).await;
+
+    let monitor = Monitor::start(get_default_vs_addrs(&actors), SAMPLE_INTERVAL);
+    for round in 0..ROUNDS {
+        info!("Round {round}");
+        index_round(&session, &clients).await;
+    }
+    monitor.assert_bounded(LIMITS).await;
+
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+
+    info!("finished");
+}
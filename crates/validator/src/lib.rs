@@ -7,26 +7,43 @@ mod alternator;
 mod ann;
 mod auth;
 mod cdc;
+mod chaos;
 mod coexisting_indexes;
 mod common;
 mod connection_timeout;
 mod crud;
 mod db_timeout;
+mod dns;
 mod filtering;
 mod fts;
 mod full_scan;
 mod high_availability;
 mod index_create;
+mod index_matrix;
 mod index_status;
+mod leaks;
+mod many_indexes;
+mod netem;
+mod network;
+mod partition;
 mod quantization_and_rescoring;
+mod recall;
 mod reconnect;
+mod report;
+mod resources;
 mod routing;
+mod runner;
+mod runtime;
 mod serde;
 mod similarity_functions;
+mod soak;
+mod split_brain;
 mod tls_reload;
+mod upgrade;
 
 use clap::Parser;
 use clap::Subcommand;
+use clap::ValueEnum;
 use e2etest::Config;
 use e2etest_dns::Dns;
 use e2etest_dns::DnsExt;
@@ -38,10 +55,14 @@ use e2etest_tls::Tls;
 use e2etest_vector_store_cluster::VectorStoreCluster;
 use e2etest_vector_store_cluster::VectorStoreClusterExt;
 use std::env;
+use std::ffi::OsString;
 use std::net::Ipv4Addr;
+use std::num::NonZeroUsize;
+use std::path::Path;
 use std::path::PathBuf;
 use std::process::ExitCode;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::runtime::Handle;
 use tokio::sync::mpsc;
 use tokio::time;
@@ -99,6 +120,47 @@ struct RunArgs {
     #[arg(long, default_value = "false")]
     duplicate_errors: bool,
 
+    /// How long the soak test runs its load, in seconds. The soak test is skipped when zero.
+    #[arg(long, default_value = "0", value_name = "SECONDS")]
+    soak_duration: u64,
+
+    /// Path to the Vector Store executable the rolling upgrade test upgrades from. The rolling
+    /// upgrade test is skipped when not set.
+    #[arg(long, value_name = "PATH")]
+    upgrade_from: Option<PathBuf>,
+
+    /// Path to a dataset directory for the dataset recall test, in a format supported by the
+    /// benchmark. The dataset recall test is skipped when not set.
+    #[arg(long, value_name = "PATH")]
+    dataset: Option<PathBuf>,
+
+    /// The similarity function the ground truth of the dataset was computed with.
+    #[arg(long, value_enum, default_value = "euclidean")]
+    dataset_similarity: recall::Similarity,
+
+    /// Where the ScyllaDB and Vector Store nodes run.
+    #[arg(long, value_enum, default_value = "local")]
+    runtime: runtime::RuntimeKind,
+
+    /// Image of the containers the nodes run in with the docker or podman runtime. The
+    /// executables are mounted into the containers, unless they are paths in the image.
+    #[arg(long, value_name = "IMAGE")]
+    runtime_image: Option<String>,
+
+    /// Path of a JUnit XML report of the test results to write. With a report every test runs in
+    /// a child validator process of its own, whose output is captured into the report.
+    #[arg(long, value_name = "PATH")]
+    junit_report: Option<PathBuf>,
+
+    /// Path of a JSON report of the test results to write, like the JUnit XML report.
+    #[arg(long, value_name = "PATH")]
+    json_report: Option<PathBuf>,
+
+    /// Number of tests to run in parallel. Every parallel test runs in a child validator process
+    /// in a network namespace of its own, which needs root privileges and the local runtime.
+    #[arg(short, long, default_value = "1", value_name = "COUNT")]
+    jobs: NonZeroUsize,
+
     /// Path to the ScyllaDB executable.
     #[arg(value_name = "PATH")]
     scylla: PathBuf,
@@ -118,7 +180,66 @@ struct RunArgs {
     filters: Vec<String>,
 }
 
-fn init(args: RunArgs) -> Config {
+impl RunArgs {
+    /// Whether the tests run in child validator processes.
+    fn runs_tests_apart(&self) -> bool {
+        self.junit_report.is_some() || self.json_report.is_some() || self.jobs.get() > 1
+    }
+
+    /// Returns the arguments of a child validator process running the tests selected by the
+    /// filter in the tmp directory, with colors disabled for the captured output.
+    fn child_args(&self, filter: &str, tmpdir: &Path) -> Vec<OsString> {
+        let mut args: Vec<OsString> = vec![
+            "run".into(),
+            "--dns-ip".into(),
+            self.dns_ip.to_string().into(),
+            "--base-ip".into(),
+            self.base_ip.to_string().into(),
+            "--scylla-default-conf".into(),
+            self.scylla_default_conf.clone().into(),
+            "--tmpdir".into(),
+            tmpdir.into(),
+            "--disable-colors".into(),
+            "--soak-duration".into(),
+            self.soak_duration.to_string().into(),
+            "--dataset-similarity".into(),
+            value_name(self.dataset_similarity).into(),
+            "--runtime".into(),
+            value_name(self.runtime).into(),
+        ];
+        if self.verbose {
+            args.push("--verbose".into());
+        }
+        if self.duplicate_errors {
+            args.push("--duplicate-errors".into());
+        }
+        if let Some(path) = &self.upgrade_from {
+            args.extend(["--upgrade-from".into(), path.clone().into()]);
+        }
+        if let Some(path) = &self.dataset {
+            args.extend(["--dataset".into(), path.clone().into()]);
+        }
+        if let Some(image) = &self.runtime_image {
+            args.extend(["--runtime-image".into(), image.into()]);
+        }
+        args.extend([
+            self.scylla.clone().into(),
+            self.vector_store.clone().into(),
+            filter.into(),
+        ]);
+        args
+    }
+}
+
+fn value_name(value: impl ValueEnum) -> String {
+    value
+        .to_possible_value()
+        .expect("argument values are not skipped")
+        .get_name()
+        .to_string()
+}
+
+fn init_tracing(args: &RunArgs) {
     let ansi = !args.disable_colors;
     let rust_log = if args.verbose {
         "info"
@@ -154,12 +275,16 @@ fn init(args: RunArgs) -> Config {
                 .with_writer(std::io::stdout),
         )
         .init();
+}
 
+fn init(args: RunArgs) -> Config {
+    init_tracing(&args);
+    let timeout = common::DEFAULT_TEST_TIMEOUT + Duration::from_secs(args.soak_duration);
     args.filters
         .iter()
         .fold(Config::default(), |acc, filter| acc.with_filter(filter))
         .with_permanent_fixture(args)
-        .with_default_timeout(common::DEFAULT_TEST_TIMEOUT)
+        .with_default_timeout(timeout)
 }
 
 fn validate_different_subnet(dns_ip: Ipv4Addr, base_ip: Ipv4Addr) {
@@ -183,6 +308,10 @@ pub async fn run() -> ExitCode {
             });
             return ExitCode::SUCCESS;
         }
+        Command::Run(args) if args.runs_tests_apart() => {
+            init_tracing(&args);
+            return runner::run(args, root.test_names()).await;
+        }
         Command::Run(args) => e2etest::run(init(args), root).await,
     };
 
@@ -251,9 +380,14 @@ struct TestActors {
     pub(crate) tls: mpsc::Sender<Tls>,
     pub(crate) dns: mpsc::Sender<Dns>,
     pub(crate) firewall: mpsc::Sender<Firewall>,
+    pub(crate) netem: mpsc::Sender<netem::Netem>,
+    pub(crate) partition: mpsc::Sender<partition::Partition>,
     pub(crate) db: mpsc::Sender<ScyllaCluster>,
     pub(crate) vs: mpsc::Sender<VectorStoreCluster>,
     pub(crate) db_proxy: mpsc::Sender<ScyllaProxyCluster>,
+    pub(crate) soak_duration: Duration,
+    pub(crate) upgrade: Option<upgrade::Binaries>,
+    pub(crate) dataset: Option<recall::Dataset>,
 }
 
 impl e2etest::Fixture for TestActors {
@@ -262,19 +396,26 @@ impl e2etest::Fixture for TestActors {
 
         validate_different_subnet(args.dns_ip, args.base_ip);
 
+        let runtime = runtime::new(&args);
         let services_subnet = Arc::new(ServicesSubnet::new(args.base_ip));
         let tls = e2etest_tls::new(&common::get_default_db_ips_for_subnet(&services_subnet)).await;
         let dns = e2etest_dns::new(args.dns_ip).await;
         let firewall = e2etest_firewall::new().await;
+        let netem = netem::new().await;
+        let partition = partition::new().await;
         let db = e2etest_scylla_cluster::new(
-            args.scylla.clone(),
+            runtime
+                .executable(&args.scylla)
+                .expect("prepare the ScyllaDB executable"),
             args.scylla_default_conf.clone(),
             args.tmpdir.clone(),
             args.verbose,
         )
         .await;
         let vs = e2etest_vector_store_cluster::new(
-            args.vector_store.clone(),
+            runtime
+                .executable(&args.vector_store)
+                .expect("prepare the Vector Store executable"),
             args.verbose,
             args.disable_colors,
             args.tmpdir.clone(),
@@ -297,9 +438,20 @@ impl e2etest::Fixture for TestActors {
             tls,
             dns,
             firewall,
+            netem,
+            partition,
             db,
             vs,
             db_proxy,
+            soak_duration: Duration::from_secs(args.soak_duration),
+            upgrade: args
+                .upgrade_from
+                .clone()
+                .map(|old| upgrade::Binaries::new(old, &args, runtime.as_ref())),
+            dataset: args.dataset.clone().map(|path| recall::Dataset {
+                path,
+                similarity: args.dataset_similarity,
+            }),
         }
     }
     async fn teardown(self) {}
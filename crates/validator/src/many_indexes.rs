@@ -0,0 +1,239 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::TestActors;
+use crate::common::*;
+use httpapi::IndexName;
+use httpapi::IndexStatus;
+use httpapi::KeyspaceName;
+use httpclient::HttpClient;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use tokio::task::JoinSet;
+use tokio::time;
+use tracing::info;
+
+const KEYSPACES: usize = 20;
+const TABLES_PER_KEYSPACE: usize = 10;
+/// Keys of a table start at its number times this, so results of different indexes never share
+/// a key.
+const KEY_RANGE: i32 = 1000;
+const DISCOVERY_TIMEOUT: Duration = Duration::from_secs(120);
+const SERVING_TIMEOUT: Duration = Duration::from_secs(300);
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+e2etest::group!(
+    name = many_indexes,
+    fixtures = (Fixture),
+    parent = crate::validator
+);
+
+struct Fixture {
+    actors: Arc<TestActors>,
+}
+
+impl e2etest::Fixture for Fixture {
+    async fn setup(setup: &mut impl e2etest::Setup) -> Self {
+        setup.setup::<TestActors>().await;
+        let actors = setup.get::<TestActors>().await.unwrap();
+        init(&actors).await;
+        Self { actors }
+    }
+
+    async fn teardown(self) {
+        cleanup(&self.actors).await;
+    }
+}
+
+struct Table {
+    keyspace: KeyspaceName,
+    table: TableName,
+    index: IndexName,
+    first_key: i32,
+    rows: usize,
+}
+
+impl Table {
+    fn name(&self) -> String {
+        format!("{}.{}", self.keyspace, self.table)
+    }
+}
+
+/// The order in which an index goes through its statuses.
+fn rank(status: &IndexStatus) -> usize {
+    match status {
+        IndexStatus::Initializing => 0,
+        IndexStatus::Bootstrapping => 1,
+        IndexStatus::Serving => 2,
+        IndexStatus::Failed => 3,
+    }
+}
+
+/// Polls the status of every index until all of them are serving, and checks that no index
+/// goes back to an earlier status on the way.
+async fn wait_for_serving_in_order(client: &HttpClient, tables: &[Table]) {
+    let deadline = Instant::now() + SERVING_TIMEOUT;
+    let serving = Some(rank(&IndexStatus::Serving));
+    let mut ranks = vec![None; tables.len()];
+    while ranks.iter().any(|last| *last != serving) {
+        assert!(
+            Instant::now() < deadline,
+            "indexes at {} are not serving in time",
+            client.url()
+        );
+        for (table, last) in tables.iter().zip(ranks.iter_mut()) {
+            let Ok(status) = client.index_status(&table.keyspace, &table.index).await else {
+                continue;
+            };
+            assert_ne!(
+                status.status,
+                IndexStatus::Failed,
+                "index {} failed at {}",
+                table.index,
+                client.url()
+            );
+            let rank = rank(&status.status);
+            assert!(
+                last.is_none_or(|last| last <= rank),
+                "index {} went back to {:?} at {}",
+                table.index,
+                status.status,
+                client.url()
+            );
+            *last = Some(rank);
+        }
+        time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+#[e2etest::test(group = many_indexes)]
+async fn hundreds_of_indexes_in_many_keyspaces(actors: Arc<TestActors>) {
+    info!("started");
+
+    let (session, clients) = prepare_connection(&actors).await;
+
+    info!("Creating {KEYSPACES} keyspaces with {TABLES_PER_KEYSPACE} tables each");
+    let mut keyspaces = Vec::new();
+    let mut tables = Vec::new();
+    for _ in 0..KEYSPACES {
+        let keyspace = create_keyspace(&session).await;
+        keyspaces.push(keyspace.clone());
+        for _ in 0..TABLES_PER_KEYSPACE {
+            let number = tables.len();
+            let table = Table {
+                keyspace: keyspace.clone(),
+                table: create_table(&session, "pk INT PRIMARY KEY, v VECTOR<FLOAT, 3>", None).await,
+                index: unique_index_name(),
+                first_key: number as i32 * KEY_RANGE,
+                rows: number % 5 + 1,
+            };
+            for pk in table.first_key..table.first_key + table.rows as i32 {
+                session
+                    .query_unpaged(
+                        format!("INSERT INTO {} (pk, v) VALUES (?, ?)", table.name()),
+                        (pk, vec![pk as f32, 1.0, 1.0]),
+                    )
+                    .await
+                    .expect("failed to insert data");
+            }
+            tables.push(table);
+        }
+    }
+
+    info!("Creating {} indexes at once", tables.len());
+    let start = Instant::now();
+    let mut tasks = JoinSet::new();
+    for table in &tables {
+        let session = Arc::clone(&session);
+        let query = format!(
+            "CREATE CUSTOM INDEX {} ON {}(v) USING 'vector_index'",
+            table.index,
+            table.name()
+        );
+        tasks.spawn(async move {
+            session
+                .query_unpaged(query, ())
+                .await
+                .expect("failed to create an index");
+        });
+    }
+    tasks.join_all().await;
+
+    for client in &clients {
+        wait_for(
+            || async {
+                let indexes = client.indexes().await;
+                tables
+                    .iter()
+                    .all(|table| indexes.iter().any(|index| index.index == table.index))
+            },
+            format!("all indexes to be discovered at {}", client.url()),
+            DISCOVERY_TIMEOUT,
+        )
+        .await;
+        info!(
+            "All indexes discovered at {} after {:?}",
+            client.url(),
+            start.elapsed()
+        );
+    }
+
+    for client in &clients {
+        wait_for_serving_in_order(client, &tables).await;
+        info!(
+            "All indexes serving at {} after {:?}",
+            client.url(),
+            start.elapsed()
+        );
+    }
+
+    info!("Checking that the indexes do not interfere");
+    for client in &clients {
+        for table in &tables {
+            let status = client
+                .index_status(&table.keyspace, &table.index)
+                .await
+                .expect("failed to get index status");
+            assert_eq!(
+                status.count,
+                table.rows,
+                "index {} has a wrong count at {}",
+                table.index,
+                client.url()
+            );
+        }
+    }
+    for table in &tables {
+        let keys = table.first_key..table.first_key + table.rows as i32;
+        get_query_results(
+            format!(
+                "SELECT pk FROM {} ORDER BY v ANN OF [1.0, 1.0, 1.0] LIMIT 10",
+                table.name()
+            ),
+            &session,
+        )
+        .await
+        .rows::<(i32,)>()
+        .expect("failed to get rows")
+        .map(|row| row.expect("failed to get row").0)
+        .for_each(|pk| {
+            assert!(
+                keys.contains(&pk),
+                "index {} returned the key {pk} of another table",
+                table.index
+            )
+        });
+    }
+
+    for keyspace in keyspaces {
+        session
+            .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+            .await
+            .expect("failed to drop a keyspace");
+    }
+
+    info!("finished");
+}
@@ -0,0 +1,159 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Degrades the network on the loopback interface with tc/netem: packets sent to selected
+//! addresses are delayed or lost, while the rest of the traffic is not touched.
+
+use std::net::Ipv4Addr;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tracing::debug;
+use tracing::info;
+
+const DEVICE: &str = "lo";
+
+/// The band of the root prio qdisc which is not used by the default priomap, so only the
+/// filtered traffic goes through the netem qdisc attached to it.
+const DEGRADED_BAND: &str = "1:4";
+
+/// How packets to the degraded addresses are impaired.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct Impairment {
+    pub(crate) delay: Duration,
+    /// The delay varies randomly by up to this value.
+    pub(crate) jitter: Duration,
+    /// The share of lost packets, from 0 to 1.
+    pub(crate) loss: f64,
+}
+
+impl Impairment {
+    fn netem_params(&self) -> String {
+        format!(
+            "delay {}us {}us loss {}%",
+            self.delay.as_micros(),
+            self.jitter.as_micros(),
+            self.loss * 100.0
+        )
+    }
+}
+
+pub(crate) enum Netem {
+    Degrade {
+        ips: Vec<Ipv4Addr>,
+        impairment: Impairment,
+        tx: oneshot::Sender<()>,
+    },
+    Restore {
+        tx: oneshot::Sender<()>,
+    },
+}
+
+pub(crate) trait NetemExt {
+    /// Impairs the traffic sent to `ips`, replacing previous impairments.
+    async fn degrade(&self, ips: Vec<Ipv4Addr>, impairment: Impairment);
+
+    /// Removes all impairments.
+    async fn restore(&self);
+}
+
+impl NetemExt for mpsc::Sender<Netem> {
+    async fn degrade(&self, ips: Vec<Ipv4Addr>, impairment: Impairment) {
+        let (tx, rx) = oneshot::channel();
+        self.send(Netem::Degrade {
+            ips,
+            impairment,
+            tx,
+        })
+        .await
+        .expect("NetemExt::degrade: internal actor should receive request");
+        rx.await
+            .expect("NetemExt::degrade: failed to receive a response");
+    }
+
+    async fn restore(&self) {
+        let (tx, rx) = oneshot::channel();
+        self.send(Netem::Restore { tx })
+            .await
+            .expect("NetemExt::restore: internal actor should receive request");
+        rx.await
+            .expect("NetemExt::restore: failed to receive a response");
+    }
+}
+
+pub(crate) async fn new() -> mpsc::Sender<Netem> {
+    let (tx, mut rx) = mpsc::channel(10);
+
+    // Remove leftovers of a previous run which did not finish cleanly.
+    remove_qdiscs().await;
+
+    tokio::spawn(async move {
+        debug!("starting");
+        while let Some(msg) = rx.recv().await {
+            match msg {
+                Netem::Degrade {
+                    ips,
+                    impairment,
+                    tx,
+                } => {
+                    info!("Degrading traffic to {ips:?}: {impairment:?}");
+                    remove_qdiscs().await;
+                    add_qdiscs(&ips, &impairment).await;
+                    _ = tx.send(());
+                }
+                Netem::Restore { tx } => {
+                    info!("Restoring traffic");
+                    remove_qdiscs().await;
+                    _ = tx.send(());
+                }
+            }
+        }
+        remove_qdiscs().await;
+        debug!("finished");
+    });
+
+    tx
+}
+
+async fn add_qdiscs(ips: &[Ipv4Addr], impairment: &Impairment) {
+    tc(&format!(
+        "qdisc add dev {DEVICE} root handle 1: prio bands 4"
+    ))
+    .await;
+    tc(&format!(
+        "qdisc add dev {DEVICE} parent {DEGRADED_BAND} netem {}",
+        impairment.netem_params()
+    ))
+    .await;
+    for ip in ips {
+        tc(&format!(
+            "filter add dev {DEVICE} parent 1: protocol ip prio 1 u32 \
+            match ip dst {ip}/32 flowid {DEGRADED_BAND}"
+        ))
+        .await;
+    }
+}
+
+async fn remove_qdiscs() {
+    // Fails when there is no root qdisc, which is fine.
+    _ = Command::new("tc")
+        .args(["qdisc", "del", "dev", DEVICE, "root"])
+        .output()
+        .await;
+}
+
+async fn tc(command: &str) {
+    let output = Command::new("tc")
+        .args(command.split_whitespace())
+        .output()
+        .await
+        .expect("failed to run tc");
+    assert!(
+        output.status.success(),
+        "tc {command} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
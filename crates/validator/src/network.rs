@@ -0,0 +1,146 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::TestActors;
+use crate::common::*;
+use crate::netem::Impairment;
+use crate::netem::NetemExt;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use tracing::info;
+
+const DATASET_SIZE: i32 = 100;
+
+e2etest::group!(
+    name = network,
+    fixtures = (Fixture),
+    parent = crate::validator
+);
+
+struct Fixture {
+    actors: Arc<TestActors>,
+}
+
+impl e2etest::Fixture for Fixture {
+    async fn setup(setup: &mut impl e2etest::Setup) -> Self {
+        setup.setup::<TestActors>().await;
+        let actors = setup.get::<TestActors>().await.unwrap();
+        init(&actors).await;
+        Self { actors }
+    }
+
+    async fn teardown(self) {
+        self.actors.netem.restore().await;
+        cleanup(&self.actors).await;
+    }
+}
+
+#[e2etest::test(group = network)]
+async fn ann_works_with_slow_vector_store(actors: Arc<TestActors>) {
+    info!("started");
+
+    let (session, clients) = prepare_connection(&actors).await;
+    let keyspace = create_keyspace(&session).await;
+    let table = create_table(&session, "pk INT PRIMARY KEY, v VECTOR<FLOAT, 3>", None).await;
+    for pk in 0..DATASET_SIZE {
+        session
+            .query_unpaged(
+                format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"),
+                (pk, vec![pk as f32, 1.0, 1.0]),
+            )
+            .await
+            .expect("failed to insert data");
+    }
+    let index = create_index(CreateIndexQuery::new(&session, &clients, &table, "v")).await;
+    for client in &clients {
+        wait_for_index(client, &index).await;
+    }
+
+    let delay = Duration::from_millis(200);
+    info!("Delaying and losing packets sent to the vector-store nodes");
+    actors
+        .netem
+        .degrade(
+            get_default_vs_ips(&actors),
+            Impairment {
+                delay,
+                jitter: Duration::from_millis(50),
+                loss: 0.01,
+            },
+        )
+        .await;
+
+    for _ in 0..10 {
+        let start = Instant::now();
+        let rows = get_query_results(
+            format!("SELECT pk FROM {table} ORDER BY v ANN OF [1.0, 1.0, 1.0] LIMIT 5"),
+            &session,
+        )
+        .await
+        .rows::<(i32,)>()
+        .expect("failed to get rows")
+        .count();
+        assert_eq!(rows, 5);
+        assert!(
+            start.elapsed() >= delay / 2,
+            "ANN query was not delayed: {:?}",
+            start.elapsed()
+        );
+    }
+
+    actors.netem.restore().await;
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+
+    info!("finished");
+}
+
+#[e2etest::test(group = network)]
+async fn index_is_built_with_slow_db(actors: Arc<TestActors>) {
+    info!("started");
+
+    let (session, clients) = prepare_connection(&actors).await;
+    let keyspace = create_keyspace(&session).await;
+    let table = create_table(&session, "pk INT PRIMARY KEY, v VECTOR<FLOAT, 3>", None).await;
+    for pk in 0..DATASET_SIZE {
+        session
+            .query_unpaged(
+                format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"),
+                (pk, vec![pk as f32, 1.0, 1.0]),
+            )
+            .await
+            .expect("failed to insert data");
+    }
+
+    info!("Delaying and losing packets sent to the ScyllaDB nodes");
+    actors
+        .netem
+        .degrade(
+            get_default_db_ips(&actors),
+            Impairment {
+                delay: Duration::from_millis(50),
+                jitter: Duration::from_millis(20),
+                loss: 0.02,
+            },
+        )
+        .await;
+
+    let index = create_index(CreateIndexQuery::new(&session, &clients, &table, "v")).await;
+    for client in &clients {
+        let status = wait_for_index(client, &index).await;
+        assert_eq!(status.count, DATASET_SIZE as usize);
+    }
+
+    actors.netem.restore().await;
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+
+    info!("finished");
+}
@@ -0,0 +1,142 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Drops traffic in one direction with iptables. Unlike the firewall actor, which cuts an
+//! address off completely, a rule only drops packets from a source to a destination, optionally
+//! only to a single port.
+
+use std::net::Ipv4Addr;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tracing::debug;
+use tracing::info;
+
+/// A dedicated chain, so the rules do not clash with the ones of the firewall actor.
+const CHAIN: &str = "VALIDATOR_PARTITION";
+
+/// Packets dropped by a partition.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Rule {
+    /// Packets from any address are dropped when not set.
+    pub(crate) from: Option<Ipv4Addr>,
+    pub(crate) to: Ipv4Addr,
+    /// Only packets to this TCP port are dropped when set. Connections from `to` keep working
+    /// then, as their replies are sent from the port.
+    pub(crate) port: Option<u16>,
+}
+
+impl Rule {
+    fn iptables_match(&self) -> String {
+        let from = self
+            .from
+            .map(|ip| format!("-s {ip}/32 "))
+            .unwrap_or_default();
+        let port = self
+            .port
+            .map(|port| format!(" -p tcp --dport {port}"))
+            .unwrap_or_default();
+        format!("{from}-d {}/32{port}", self.to)
+    }
+}
+
+pub(crate) enum Partition {
+    Drop {
+        rules: Vec<Rule>,
+        tx: oneshot::Sender<()>,
+    },
+    Heal {
+        tx: oneshot::Sender<()>,
+    },
+}
+
+pub(crate) trait PartitionExt {
+    /// Drops the packets matching `rules`, replacing previous rules.
+    async fn drop_packets(&self, rules: Vec<Rule>);
+
+    /// Removes all rules.
+    async fn heal(&self);
+}
+
+impl PartitionExt for mpsc::Sender<Partition> {
+    async fn drop_packets(&self, rules: Vec<Rule>) {
+        let (tx, rx) = oneshot::channel();
+        self.send(Partition::Drop { rules, tx })
+            .await
+            .expect("PartitionExt::drop_packets: internal actor should receive request");
+        rx.await
+            .expect("PartitionExt::drop_packets: failed to receive a response");
+    }
+
+    async fn heal(&self) {
+        let (tx, rx) = oneshot::channel();
+        self.send(Partition::Heal { tx })
+            .await
+            .expect("PartitionExt::heal: internal actor should receive request");
+        rx.await
+            .expect("PartitionExt::heal: failed to receive a response");
+    }
+}
+
+pub(crate) async fn new() -> mpsc::Sender<Partition> {
+    let (tx, mut rx) = mpsc::channel(10);
+
+    // Remove leftovers of a previous run which did not finish cleanly.
+    remove_chain().await;
+    iptables(&format!("-N {CHAIN}")).await;
+    iptables(&format!("-I OUTPUT -j {CHAIN}")).await;
+
+    tokio::spawn(async move {
+        debug!("starting");
+        while let Some(msg) = rx.recv().await {
+            match msg {
+                Partition::Drop { rules, tx } => {
+                    info!("Dropping packets: {rules:?}");
+                    iptables(&format!("-F {CHAIN}")).await;
+                    for rule in rules {
+                        iptables(&format!("-A {CHAIN} {} -j DROP", rule.iptables_match())).await;
+                    }
+                    _ = tx.send(());
+                }
+                Partition::Heal { tx } => {
+                    info!("Healing the partition");
+                    iptables(&format!("-F {CHAIN}")).await;
+                    _ = tx.send(());
+                }
+            }
+        }
+        remove_chain().await;
+        debug!("finished");
+    });
+
+    tx
+}
+
+async fn remove_chain() {
+    // Each step fails when the chain does not exist, which is fine.
+    for command in [
+        format!("-D OUTPUT -j {CHAIN}"),
+        format!("-F {CHAIN}"),
+        format!("-X {CHAIN}"),
+    ] {
+        _ = Command::new("iptables")
+            .args(command.split_whitespace())
+            .output()
+            .await;
+    }
+}
+
+async fn iptables(command: &str) {
+    let output = Command::new("iptables")
+        .args(command.split_whitespace())
+        .output()
+        .await
+        .expect("failed to run iptables");
+    assert!(
+        output.status.success(),
+        "iptables {command} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,330 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::TestActors;
+use crate::common::*;
+use httpclient::WaitOptions;
+use itertools::Itertools;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+use tokio::task::JoinSet;
+use tracing::info;
+
+const DIMENSIONS: usize = 32;
+const DATASET_SIZE: usize = 1000;
+const QUERIES: usize = 20;
+const K: usize = 10;
+/// Queries of the dataset used by the dataset recall test.
+const DATASET_QUERIES: usize = 100;
+/// Concurrent inserts while loading the dataset.
+const LOAD_CONCURRENCY: usize = 64;
+const MIN_DATASET_RECALL: f64 = 0.9;
+const DATASET_BUILD_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+e2etest::group!(
+    name = recall,
+    fixtures = (Fixture),
+    parent = crate::validator
+);
+
+struct Fixture {
+    actors: Arc<TestActors>,
+}
+
+impl e2etest::Fixture for Fixture {
+    async fn setup(setup: &mut impl e2etest::Setup) -> Self {
+        setup.setup::<TestActors>().await;
+        let actors = setup.get::<TestActors>().await.unwrap();
+        init(&actors).await;
+        Self { actors }
+    }
+
+    async fn teardown(self) {
+        cleanup(&self.actors).await;
+    }
+}
+
+/// An index configuration and the recall@K it must reach.
+struct Case {
+    quantization: &'static str,
+    rescoring: bool,
+    min_recall: f64,
+}
+
+const CASES: [Case; 4] = [
+    Case {
+        quantization: "f32",
+        rescoring: false,
+        min_recall: 0.95,
+    },
+    Case {
+        quantization: "f16",
+        rescoring: false,
+        min_recall: 0.95,
+    },
+    Case {
+        quantization: "bf16",
+        rescoring: false,
+        min_recall: 0.9,
+    },
+    Case {
+        quantization: "i8",
+        rescoring: true,
+        min_recall: 0.9,
+    },
+];
+
+#[derive(Clone, Copy, Debug, clap::ValueEnum)]
+pub(crate) enum Similarity {
+    Euclidean,
+    Cosine,
+    DotProduct,
+}
+
+impl Similarity {
+    pub(crate) fn option(self) -> &'static str {
+        match self {
+            Self::Euclidean => "EUCLIDEAN",
+            Self::Cosine => "COSINE",
+            Self::DotProduct => "DOT_PRODUCT",
+        }
+    }
+
+    /// The higher the score, the closer the vectors.
+    fn score(self, a: &[f32], b: &[f32]) -> f32 {
+        let dot = || a.iter().zip(b).map(|(a, b)| a * b).sum::<f32>();
+        let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        match self {
+            Self::Euclidean => -a.iter().zip(b).map(|(a, b)| (a - b) * (a - b)).sum::<f32>(),
+            Self::Cosine => dot() / (norm(a) * norm(b)),
+            Self::DotProduct => dot(),
+        }
+    }
+}
+
+/// A dataset with ground truth neighbours computed with `similarity`.
+#[derive(Clone)]
+pub(crate) struct Dataset {
+    pub(crate) path: PathBuf,
+    pub(crate) similarity: Similarity,
+}
+
+fn exact_neighbors(similarity: Similarity, dataset: &[Vec<f32>], query: &[f32]) -> HashSet<i32> {
+    (0..dataset.len())
+        .sorted_by(|&a, &b| {
+            similarity
+                .score(query, &dataset[b])
+                .total_cmp(&similarity.score(query, &dataset[a]))
+        })
+        .take(K)
+        .map(|pk| pk as i32)
+        .collect()
+}
+
+async fn run_recall_test(actors: &TestActors, similarity: Similarity) {
+    let (session, clients) = prepare_connection(actors).await;
+    let keyspace = create_keyspace(&session).await;
+
+    let mut generator = VectorGenerator::new(42);
+    let dataset = (0..DATASET_SIZE)
+        .map(|_| generator.next_vector(DIMENSIONS))
+        .collect_vec();
+    let queries = (0..QUERIES)
+        .map(|_| generator.next_vector(DIMENSIONS))
+        .collect_vec();
+    let ground_truth = queries
+        .iter()
+        .map(|query| exact_neighbors(similarity, &dataset, query))
+        .collect_vec();
+
+    for case in &CASES {
+        let table = create_table(
+            &session,
+            &format!("pk INT PRIMARY KEY, v VECTOR<FLOAT, {DIMENSIONS}>"),
+            None,
+        )
+        .await;
+        let insert = session
+            .prepare(format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"))
+            .await
+            .expect("failed to prepare an insert");
+        for (pk, vector) in dataset.iter().enumerate() {
+            session
+                .execute_unpaged(&insert, (pk as i32, vector))
+                .await
+                .expect("failed to insert data");
+        }
+
+        let rescoring = case.rescoring.to_string();
+        let index = create_index(
+            CreateIndexQuery::new(&session, &clients, &table, "v").options([
+                ("similarity_function", similarity.option()),
+                ("quantization", case.quantization),
+                ("rescoring", rescoring.as_str()),
+                ("oversampling", "2.0"),
+            ]),
+        )
+        .await;
+        for client in &clients {
+            wait_for_index(client, &index).await;
+        }
+
+        let mut found = 0;
+        for (query, expected) in queries.iter().zip(&ground_truth) {
+            let results = get_query_results(
+                format!(
+                    "SELECT pk FROM {table} ORDER BY v ANN OF {} LIMIT {K}",
+                    format_vector(query)
+                ),
+                &session,
+            )
+            .await;
+            found += results
+                .rows::<(i32,)>()
+                .expect("failed to get rows")
+                .map(|row| row.expect("failed to get row").0)
+                .filter(|pk| expected.contains(pk))
+                .count();
+        }
+        let recall = found as f64 / (QUERIES * K) as f64;
+        info!(
+            "{similarity:?} with {} quantization: recall@{K} = {recall}",
+            case.quantization
+        );
+        assert!(
+            recall >= case.min_recall,
+            "recall@{K} of {similarity:?} with {} quantization is {recall}, expected at least {}",
+            case.quantization,
+            case.min_recall
+        );
+    }
+
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+}
+
+#[e2etest::test(group = recall)]
+async fn recall_euclidean(actors: Arc<TestActors>) {
+    info!("started");
+    run_recall_test(&actors, Similarity::Euclidean).await;
+    info!("finished");
+}
+
+#[e2etest::test(group = recall)]
+async fn recall_cosine(actors: Arc<TestActors>) {
+    info!("started");
+    run_recall_test(&actors, Similarity::Cosine).await;
+    info!("finished");
+}
+
+#[e2etest::test(group = recall)]
+async fn recall_dot_product(actors: Arc<TestActors>) {
+    info!("started");
+    run_recall_test(&actors, Similarity::DotProduct).await;
+    info!("finished");
+}
+
+#[e2etest::test(group = recall)]
+async fn recall_dataset(actors: Arc<TestActors>) {
+    info!("started");
+
+    let Some(Dataset { path, similarity }) = actors.dataset.clone() else {
+        info!("skipped, the dataset is not set");
+        return;
+    };
+
+    let data = dataset::new(path).await;
+    let dimensions = data.dimension().await;
+    let (session, clients) = prepare_connection(&actors).await;
+    let keyspace = create_keyspace(&session).await;
+    let table = create_table(
+        &session,
+        &format!("pk BIGINT PRIMARY KEY, v VECTOR<FLOAT, {dimensions}>"),
+        None,
+    )
+    .await;
+
+    info!("Loading the dataset");
+    let insert = session
+        .prepare(format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"))
+        .await
+        .expect("failed to prepare an insert");
+    let vectors = Arc::new(Mutex::new(data.vector_stream().await));
+    let mut tasks = JoinSet::new();
+    for _ in 0..LOAD_CONCURRENCY {
+        let session = Arc::clone(&session);
+        let insert = insert.clone();
+        let vectors = Arc::clone(&vectors);
+        tasks.spawn(async move {
+            loop {
+                let Some((pk, vector)) = vectors.lock().await.recv().await else {
+                    break;
+                };
+                session
+                    .execute_unpaged(&insert, (pk, vector.into_vec()))
+                    .await
+                    .expect("failed to insert data");
+            }
+        });
+    }
+    tasks.join_all().await;
+
+    info!("Building the index");
+    let index = create_index(
+        CreateIndexQuery::new(&session, &clients, &table, "v")
+            .options([("similarity_function", similarity.option())]),
+    )
+    .await;
+    for client in &clients {
+        client
+            .wait_for_serving(
+                &index.keyspace,
+                &index.index,
+                WaitOptions::new(DATASET_BUILD_TIMEOUT).with_interval(Duration::from_secs(5)),
+            )
+            .await
+            .unwrap_or_else(|err| panic!("{err:#}"));
+    }
+
+    let queries = data.queries(None, K).await;
+    let queries = &queries[..queries.len().min(DATASET_QUERIES)];
+    let mut found = 0;
+    let mut expected = 0;
+    for query in queries {
+        let results = get_query_results(
+            format!(
+                "SELECT pk FROM {table} ORDER BY v ANN OF {} LIMIT {K}",
+                format_vector(&query.query)
+            ),
+            &session,
+        )
+        .await;
+        expected += query.neighbors.len();
+        found += results
+            .rows::<(i64,)>()
+            .expect("failed to get rows")
+            .map(|row| row.expect("failed to get row").0)
+            .filter(|pk| query.neighbors.contains(pk))
+            .count();
+    }
+    let recall = found as f64 / expected.max(1) as f64;
+    info!("{similarity:?} on the dataset: recall@{K} = {recall}");
+    assert!(
+        recall >= MIN_DATASET_RECALL,
+        "recall@{K} of {similarity:?} on the dataset is {recall}, expected at least {MIN_DATASET_RECALL}"
+    );
+
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+
+    info!("finished");
+}
@@ -0,0 +1,109 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Machine-readable reports of the test results, in JUnit XML and JSON, so that CI systems and
+//! dashboards can track the tests without parsing the logs.
+
+use itertools::Itertools;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+/// The result of a single test.
+pub(crate) struct TestReport {
+    pub(crate) group: String,
+    pub(crate) test: String,
+    pub(crate) passed: bool,
+    pub(crate) duration: Duration,
+    /// The output of the test, stdout followed by stderr.
+    pub(crate) log: String,
+}
+
+pub(crate) fn write_junit(path: &Path, reports: &[TestReport]) -> io::Result<()> {
+    let failures = reports.iter().filter(|report| !report.passed).count();
+    let time: Duration = reports.iter().map(|report| report.duration).sum();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    _ = writeln!(
+        xml,
+        r#"<testsuites name="{}" tests="{}" failures="{failures}" time="{:.3}">"#,
+        env!("CARGO_PKG_NAME"),
+        reports.len(),
+        time.as_secs_f64()
+    );
+    for (group, reports) in &reports.iter().chunk_by(|report| &report.group) {
+        let reports: Vec<_> = reports.collect();
+        let failures = reports.iter().filter(|report| !report.passed).count();
+        let time: Duration = reports.iter().map(|report| report.duration).sum();
+        _ = writeln!(
+            xml,
+            r#"  <testsuite name="{}" tests="{}" failures="{failures}" time="{:.3}">"#,
+            escaped(group),
+            reports.len(),
+            time.as_secs_f64()
+        );
+        for report in reports {
+            _ = writeln!(
+                xml,
+                r#"    <testcase classname="{}" name="{}" time="{:.3}">"#,
+                escaped(&report.group),
+                escaped(&report.test),
+                report.duration.as_secs_f64()
+            );
+            if !report.passed {
+                _ = writeln!(xml, r#"      <failure message="test failed"/>"#);
+            }
+            _ = writeln!(
+                xml,
+                "      <system-out>{}</system-out>",
+                escaped(&report.log)
+            );
+            _ = writeln!(xml, "    </testcase>");
+        }
+        _ = writeln!(xml, "  </testsuite>");
+    }
+    _ = writeln!(xml, "</testsuites>");
+    fs::write(path, xml)
+}
+
+pub(crate) fn write_json(path: &Path, reports: &[TestReport]) -> io::Result<()> {
+    let tests: Vec<_> = reports
+        .iter()
+        .map(|report| {
+            serde_json::json!({
+                "group": report.group,
+                "test": report.test,
+                "status": if report.passed { "passed" } else { "failed" },
+                "duration_secs": report.duration.as_secs_f64(),
+                "log": report.log,
+            })
+        })
+        .collect();
+    let passed = reports.iter().filter(|report| report.passed).count();
+    let json = serde_json::json!({
+        "passed": passed,
+        "failed": reports.len() - passed,
+        "tests": tests,
+    });
+    fs::write(path, serde_json::to_string_pretty(&json)?)
+}
+
+/// Escapes the text for XML, dropping control characters XML does not allow.
+fn escaped(text: &str) -> String {
+    text.chars()
+        .filter(|&char| !char.is_control() || matches!(char, '\t' | '\n' | '\r'))
+        .fold(String::with_capacity(text.len()), |mut escaped, char| {
+            match char {
+                '&' => escaped.push_str("&amp;"),
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '"' => escaped.push_str("&quot;"),
+                '\'' => escaped.push_str("&apos;"),
+                char => escaped.push(char),
+            }
+            escaped
+        })
+}
@@ -0,0 +1,181 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Samples the resident memory and the open file descriptors of the vector-store processes
+//! during a test, to catch leaks. A process is found by the socket listening on its HTTP
+//! address, so it is found again after a restart.
+
+use std::net::Ipv4Addr;
+use std::net::SocketAddrV4;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::fs;
+use tokio::task::JoinHandle;
+use tokio::time;
+use tracing::info;
+
+/// The state of a TCP socket listening for connections in /proc/net/tcp.
+const TCP_LISTEN: &str = "0A";
+
+/// Resources used by a process.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Usage {
+    /// Resident memory in bytes.
+    pub(crate) rss: u64,
+    pub(crate) fds: usize,
+}
+
+/// How much the usage of a process may grow between the first and the last sample.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Limits {
+    pub(crate) rss_growth: f64,
+    pub(crate) fd_growth: usize,
+}
+
+/// Samples the usage of the processes listening on `addrs` until stopped.
+pub(crate) struct Monitor {
+    stop: Arc<AtomicBool>,
+    samples: Arc<Mutex<Vec<Vec<Usage>>>>,
+    task: JoinHandle<()>,
+}
+
+impl Monitor {
+    pub(crate) fn start(addrs: Vec<SocketAddrV4>, interval: Duration) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let samples = Arc::new(Mutex::new(vec![Vec::new(); addrs.len()]));
+        let task = tokio::spawn({
+            let stop = Arc::clone(&stop);
+            let samples = Arc::clone(&samples);
+            async move {
+                let mut pids = vec![None; addrs.len()];
+                while !stop.load(Ordering::Relaxed) {
+                    for (node, addr) in addrs.iter().enumerate() {
+                        if let Some(usage) = sample(&mut pids[node], *addr).await {
+                            samples.lock().unwrap()[node].push(usage);
+                        }
+                    }
+                    time::sleep(interval).await;
+                }
+            }
+        });
+        Self {
+            stop,
+            samples,
+            task,
+        }
+    }
+
+    /// Stops sampling and returns the samples of every process.
+    async fn stop(self) -> Vec<Vec<Usage>> {
+        self.stop.store(true, Ordering::Relaxed);
+        self.task.await.unwrap();
+        Arc::into_inner(self.samples).unwrap().into_inner().unwrap()
+    }
+
+    /// Stops sampling and panics when the usage of a process grew above `limits`.
+    pub(crate) async fn assert_bounded(self, limits: Limits) {
+        for (node, samples) in self.stop().await.iter().enumerate() {
+            let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+                panic!("no resource samples of node {node}");
+            };
+            info!(
+                "node {node}: {} samples from {first:?} to {last:?}",
+                samples.len()
+            );
+            assert!(
+                last.rss as f64 <= first.rss as f64 * limits.rss_growth,
+                "resident memory of node {node} grew from {} to {} bytes",
+                first.rss,
+                last.rss
+            );
+            assert!(
+                last.fds <= first.fds + limits.fd_growth,
+                "open file descriptors of node {node} grew from {} to {}",
+                first.fds,
+                last.fds
+            );
+        }
+    }
+}
+
+/// Samples the process listening on `addr`, looking it up again when it is gone.
+async fn sample(pid: &mut Option<u32>, addr: SocketAddrV4) -> Option<Usage> {
+    if let Some(pid) = *pid
+        && let Some(usage) = usage(pid).await
+    {
+        return Some(usage);
+    }
+    *pid = listener_pid(addr).await;
+    usage((*pid)?).await
+}
+
+async fn usage(pid: u32) -> Option<Usage> {
+    let status = fs::read_to_string(format!("/proc/{pid}/status"))
+        .await
+        .ok()?;
+    let rss_kb = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    let mut fds = 0;
+    let mut entries = fs::read_dir(format!("/proc/{pid}/fd")).await.ok()?;
+    while entries.next_entry().await.ok()?.is_some() {
+        fds += 1;
+    }
+    Some(Usage {
+        rss: rss_kb * 1024,
+        fds,
+    })
+}
+
+/// Parses an address from /proc/net/tcp, where the IP is printed as a native endian integer.
+fn parse_addr(addr: &str) -> Option<SocketAddrV4> {
+    let (ip, port) = addr.split_once(':')?;
+    let ip = u32::from_str_radix(ip, 16).ok()?;
+    let port = u16::from_str_radix(port, 16).ok()?;
+    Some(SocketAddrV4::new(Ipv4Addr::from(ip.to_ne_bytes()), port))
+}
+
+async fn listener_pid(addr: SocketAddrV4) -> Option<u32> {
+    let tcp = fs::read_to_string("/proc/net/tcp").await.ok()?;
+    let inode = tcp.lines().skip(1).find_map(|line| {
+        let fields: Vec<_> = line.split_whitespace().collect();
+        let local = parse_addr(fields.get(1)?)?;
+        let listening = fields.get(3)? == &TCP_LISTEN;
+        let matches =
+            local.port() == addr.port() && (local.ip() == addr.ip() || local.ip().is_unspecified());
+        if !(listening && matches) {
+            return None;
+        }
+        fields.get(9).map(|inode| inode.to_string())
+    })?;
+    let socket = format!("socket:[{inode}]");
+
+    let mut procs = fs::read_dir("/proc").await.ok()?;
+    while let Ok(Some(entry)) = procs.next_entry().await {
+        let Some(pid) = entry.file_name().to_str().and_then(|pid| pid.parse().ok()) else {
+            continue;
+        };
+        let Ok(mut fds) = fs::read_dir(entry.path().join("fd")).await else {
+            continue;
+        };
+        while let Ok(Some(fd)) = fds.next_entry().await {
+            if fs::read_link(fd.path())
+                .await
+                .is_ok_and(|link| link.as_os_str() == socket.as_str())
+            {
+                return Some(pid);
+            }
+        }
+    }
+    None
+}
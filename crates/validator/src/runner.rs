@@ -0,0 +1,175 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Runs every selected test in a child validator process of its own, so that the status, the
+//! duration and the output of each test are captured for the reports, and independent tests run
+//! in parallel jobs.
+//!
+//! Parallel jobs are isolated from each other: every child runs in a network namespace of its
+//! own, with its own loopback subnets, DNS server, firewall and netem rules and ScyllaDB cluster,
+//! so the keyspaces of the tests do not meet either, and every job has its own tmp directory.
+
+use crate::RunArgs;
+use crate::format_failed_names;
+use crate::report;
+use crate::report::TestReport;
+use crate::runtime::RuntimeKind;
+use std::collections::VecDeque;
+use std::env;
+use std::io;
+use std::process::ExitCode;
+use std::process::Output;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Instant;
+use tokio::fs;
+use tokio::process::Command;
+use tokio::task::JoinSet;
+use tracing::error;
+use tracing::info;
+
+pub(crate) async fn run(args: RunArgs, names: Vec<String>) -> ExitCode {
+    if args.jobs.get() > 1 && args.runtime != RuntimeKind::Local {
+        error!("parallel jobs are isolated by network namespaces, which need the local runtime");
+        return ExitCode::FAILURE;
+    }
+    let tests: VecDeque<_> = names
+        .iter()
+        .filter_map(|name| {
+            let (group, test) = name.rsplit_once("::")?;
+            let group = group.rsplit("::").next().unwrap_or(group);
+            (args.filters.is_empty()
+                || args
+                    .filters
+                    .iter()
+                    .any(|filter| selects(filter, group, test)))
+            .then(|| (group.to_string(), test.to_string()))
+        })
+        .enumerate()
+        .collect();
+
+    let args = Arc::new(args);
+    let tests = Arc::new(Mutex::new(tests));
+    let mut jobs = JoinSet::new();
+    for job in 0..args.jobs.get() {
+        let args = Arc::clone(&args);
+        let tests = Arc::clone(&tests);
+        jobs.spawn(async move {
+            let mut reports = Vec::new();
+            loop {
+                let next = tests.lock().unwrap().pop_front();
+                let Some((position, (group, test))) = next else {
+                    break;
+                };
+                reports.push((position, run_test(&args, job, &group, &test).await));
+            }
+            reports
+        });
+    }
+    let mut reports: Vec<_> = jobs.join_all().await.into_iter().flatten().collect();
+    reports.sort_by_key(|(position, _)| *position);
+    let reports: Vec<_> = reports.into_iter().map(|(_, report)| report).collect();
+
+    if let Some(path) = &args.junit_report
+        && let Err(err) = report::write_junit(path, &reports)
+    {
+        error!("unable to write the JUnit report to {path:?}: {err}");
+        return ExitCode::FAILURE;
+    }
+    if let Some(path) = &args.json_report
+        && let Err(err) = report::write_json(path, &reports)
+    {
+        error!("unable to write the JSON report to {path:?}: {err}");
+        return ExitCode::FAILURE;
+    }
+
+    let failed_names: Vec<_> = reports
+        .iter()
+        .filter(|report| !report.passed)
+        .map(|report| format!("{}::{}", report.group, report.test))
+        .collect();
+    if !failed_names.is_empty() {
+        error!("{}", format_failed_names(&failed_names));
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Runs the test in a child validator process and prints its output when it is done.
+async fn run_test(args: &RunArgs, job: usize, group: &str, test: &str) -> TestReport {
+    info!("running {group}::{test} in job {job}");
+    let started = Instant::now();
+    let output = output(args, job, &format!(r#""{group}"::"{test}""#)).await;
+    let duration = started.elapsed();
+    let (passed, log) = match output {
+        Ok(output) => (
+            output.status.success(),
+            String::from_utf8_lossy(&output.stdout).into_owned()
+                + &String::from_utf8_lossy(&output.stderr),
+        ),
+        Err(err) => (false, format!("unable to run the test: {err}\n")),
+    };
+    print!("{log}");
+    info!(
+        "{group}::{test} {} in {duration:?}",
+        if passed { "passed" } else { "failed" }
+    );
+    TestReport {
+        group: group.to_string(),
+        test: test.to_string(),
+        passed,
+        duration,
+        log,
+    }
+}
+
+/// Runs a child validator process, in a network namespace of its own if jobs run in parallel.
+async fn output(args: &RunArgs, job: usize, filter: &str) -> io::Result<Output> {
+    let validator = env::current_exe()?;
+    let parallel = args.jobs.get() > 1;
+    let tmpdir = if parallel {
+        args.tmpdir.join(format!("job-{job}"))
+    } else {
+        args.tmpdir.clone()
+    };
+    fs::create_dir_all(&tmpdir).await?;
+    let mut command = if parallel {
+        let mut command = Command::new("unshare");
+        command
+            .args([
+                "--net",
+                "--",
+                "sh",
+                "-c",
+                r#"ip link set lo up && exec "$0" "$@""#,
+            ])
+            .arg(validator);
+        command
+    } else {
+        Command::new(validator)
+    };
+    command
+        .args(args.child_args(filter, &tmpdir))
+        .kill_on_drop(true)
+        .output()
+        .await
+}
+
+/// Returns whether the filter selects the test, with the syntax of the `FILTER` arguments.
+fn selects(filter: &str, group: &str, test: &str) -> bool {
+    let matches = |pattern: &str, name: &str| match pattern
+        .strip_prefix('"')
+        .and_then(|pattern| pattern.strip_suffix('"'))
+    {
+        Some(exact) => exact == name,
+        None => name.contains(pattern),
+    };
+    match filter.split_once("::") {
+        Some((group_filter, test_filter)) => {
+            matches(group_filter, group) && matches(test_filter, test)
+        }
+        None => matches(filter, group) || matches(filter, test),
+    }
+}
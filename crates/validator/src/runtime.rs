@@ -0,0 +1,146 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Runtimes the ScyllaDB and Vector Store nodes run in. The node-management actors start the
+//! executables they are given, so a runtime hands them the executable to start: the binary itself
+//! for local processes, or a wrapper script running the binary in a container of an image.
+
+use crate::RunArgs;
+use std::fs;
+use std::io;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub(crate) enum RuntimeKind {
+    /// Local processes, which need no privileges.
+    Local,
+    /// Docker containers.
+    Docker,
+    /// Podman containers, which can run rootless.
+    Podman,
+}
+
+pub(crate) trait Runtime: Send + Sync {
+    /// Returns the executable the node-management actors start in place of `executable`.
+    fn executable(&self, executable: &Path) -> io::Result<PathBuf>;
+}
+
+/// Runs the nodes as local processes.
+struct Local;
+
+impl Runtime for Local {
+    fn executable(&self, executable: &Path) -> io::Result<PathBuf> {
+        Ok(executable.to_path_buf())
+    }
+}
+
+/// Runs the nodes in containers of an image on the host network, with the paths the nodes use
+/// mounted at the same places. Executables which do not exist on the host are looked up in the
+/// image.
+struct Container {
+    engine: &'static str,
+    image: String,
+    tmpdir: PathBuf,
+    volumes: Vec<PathBuf>,
+    /// Number of wrapper scripts, which tells apart executables of the same name.
+    wrappers: AtomicUsize,
+}
+
+impl Runtime for Container {
+    fn executable(&self, executable: &Path) -> io::Result<PathBuf> {
+        let executable = &executable
+            .canonicalize()
+            .unwrap_or_else(|_| executable.to_path_buf());
+        let name = executable
+            .file_name()
+            .ok_or_else(|| io::Error::other(format!("{executable:?} is not an executable")))?;
+        let wrapper = self.tmpdir.join(format!(
+            "{}-{}-{}",
+            self.engine,
+            self.wrappers.fetch_add(1, Ordering::Relaxed),
+            name.to_string_lossy()
+        ));
+        fs::write(&wrapper, self.wrapper(executable))?;
+        fs::set_permissions(&wrapper, fs::Permissions::from_mode(0o755))?;
+        Ok(wrapper)
+    }
+}
+
+impl Container {
+    /// Returns the script running the executable in a container. The actors stop the nodes by
+    /// signals, so the script forwards them to the container, and removes the container when the
+    /// script itself is killed.
+    fn wrapper(&self, executable: &Path) -> String {
+        let volumes: String = self
+            .volumes
+            .iter()
+            .map(PathBuf::as_path)
+            .chain(executable.exists().then_some(executable))
+            .map(|path| {
+                let path = quoted(&path.to_string_lossy());
+                format!(" --volume={path}:{path}")
+            })
+            .collect();
+        let engine = self.engine;
+        let tmpdir = quoted(&self.tmpdir.to_string_lossy());
+        let image = quoted(&self.image);
+        let executable = quoted(&executable.to_string_lossy());
+        format!(
+            r#"#!/bin/sh
+cid={tmpdir}/{engine}-cid.$$
+env_file={tmpdir}/{engine}-env.$$
+env | grep -v -e '^PATH=' -e '^HOME=' -e '^HOSTNAME=' > "$env_file"
+{engine} run --rm --init --network=host --cidfile="$cid" --env-file="$env_file" \
+    --workdir="$PWD"{volumes} --entrypoint={executable} {image} "$@" &
+child=$!
+(
+    while kill -0 $$ 2>/dev/null; do sleep 1; done
+    [ -f "$cid" ] && {engine} rm --force "$(cat "$cid")"
+) >/dev/null 2>&1 &
+trap '[ -f "$cid" ] && {engine} kill --signal=TERM "$(cat "$cid")" >/dev/null 2>&1' TERM INT
+while :; do
+    wait $child
+    status=$?
+    kill -0 $child 2>/dev/null || break
+done
+rm -f "$cid" "$env_file"
+exit $status
+"#
+        )
+    }
+}
+
+/// Returns the runtime selected by the arguments.
+pub(crate) fn new(args: &RunArgs) -> Arc<dyn Runtime> {
+    let engine = match args.runtime {
+        RuntimeKind::Local => return Arc::new(Local),
+        RuntimeKind::Docker => "docker",
+        RuntimeKind::Podman => "podman",
+    };
+    let image = args
+        .runtime_image
+        .clone()
+        .expect("--runtime-image is required to run the nodes in containers");
+    Arc::new(Container {
+        engine,
+        image,
+        tmpdir: args.tmpdir.clone(),
+        volumes: [&args.tmpdir, &args.scylla_default_conf]
+            .into_iter()
+            .filter_map(|path| path.canonicalize().ok())
+            .collect(),
+        wrappers: AtomicUsize::new(0),
+    })
+}
+
+/// Quotes the value for the shell.
+fn quoted(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
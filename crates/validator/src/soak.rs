@@ -0,0 +1,267 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::TestActors;
+use crate::common::*;
+use crate::resources::Limits;
+use crate::resources::Monitor;
+use httpapi::IndexInfo;
+use httpclient::HttpClient;
+use httpclient::WaitOptions;
+use scylla::client::session::Session;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+use tokio::task::JoinSet;
+use tokio::time;
+use tracing::info;
+
+const DIMENSIONS: usize = 64;
+const DATASET_SIZE: usize = 1_000_000;
+/// Concurrent inserts while loading the dataset.
+const LOAD_CONCURRENCY: usize = 64;
+/// Share of the churn writes which delete a row instead of upserting it.
+const DELETE_RATIO: f64 = 0.2;
+const BUILD_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+const REPORT_INTERVAL: Duration = Duration::from_secs(60);
+/// How much the memory used by an index may grow between the first and the last report.
+const MAX_MEMORY_GROWTH: f64 = 1.5;
+/// How much the p99 latency of ANN queries may grow between the first and the last report.
+const MAX_LATENCY_GROWTH: f64 = 3.0;
+const MAX_QUERY_ERROR_RATIO: f64 = 0.01;
+/// How much the vector-store processes may grow during the load.
+const PROCESS_LIMITS: Limits = Limits {
+    rss_growth: 1.5,
+    fd_growth: 100,
+};
+
+e2etest::group!(name = soak, fixtures = (Fixture), parent = crate::validator);
+
+struct Fixture {
+    actors: Arc<TestActors>,
+}
+
+impl e2etest::Fixture for Fixture {
+    async fn setup(setup: &mut impl e2etest::Setup) -> Self {
+        setup.setup::<TestActors>().await;
+        let actors = setup.get::<TestActors>().await.unwrap();
+        init(&actors).await;
+        Self { actors }
+    }
+
+    async fn teardown(self) {
+        cleanup(&self.actors).await;
+    }
+}
+
+/// The state of the cluster at the end of a report interval.
+#[derive(Debug)]
+struct Report {
+    p50: Duration,
+    p99: Duration,
+    /// Memory used by the index on each node.
+    memory: Vec<u64>,
+}
+
+fn percentile(sorted: &[Duration], percentile: usize) -> Duration {
+    sorted
+        .get((sorted.len() * percentile / 100).min(sorted.len().saturating_sub(1)))
+        .copied()
+        .unwrap_or_default()
+}
+
+async fn load_dataset(session: &Arc<Session>, table: &TableName) {
+    let insert = session
+        .prepare(format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"))
+        .await
+        .expect("failed to prepare an insert");
+    let mut tasks = JoinSet::new();
+    for worker in 0..LOAD_CONCURRENCY {
+        let session = Arc::clone(session);
+        let insert = insert.clone();
+        tasks.spawn(async move {
+            let mut generator = VectorGenerator::new(worker as u64);
+            for pk in (worker..DATASET_SIZE).step_by(LOAD_CONCURRENCY) {
+                session
+                    .execute_unpaged(&insert, (pk as i32, generator.next_vector(DIMENSIONS)))
+                    .await
+                    .expect("failed to insert data");
+            }
+        });
+    }
+    tasks.join_all().await;
+}
+
+/// Upserts and deletes random rows until `stop` is set.
+async fn churn(session: Arc<Session>, table: TableName, stop: Arc<AtomicBool>) {
+    let insert = session
+        .prepare(format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"))
+        .await
+        .expect("failed to prepare an insert");
+    let delete = session
+        .prepare(format!("DELETE FROM {table} WHERE pk = ?"))
+        .await
+        .expect("failed to prepare a delete");
+    let mut generator = VectorGenerator::new(u64::MAX);
+    while !stop.load(Ordering::Relaxed) {
+        let pk = (generator.next_u64() % DATASET_SIZE as u64) as i32;
+        let result = if rand::random_bool(DELETE_RATIO) {
+            session.execute_unpaged(&delete, (pk,)).await
+        } else {
+            session
+                .execute_unpaged(&insert, (pk, generator.next_vector(DIMENSIONS)))
+                .await
+        };
+        if let Err(err) = result {
+            info!("churn write failed: {err}");
+        }
+    }
+}
+
+/// Runs ANN queries until `stop` is set, recording their latencies.
+async fn query(
+    session: Arc<Session>,
+    table: TableName,
+    stop: Arc<AtomicBool>,
+    latencies: Arc<Mutex<Vec<Duration>>>,
+    errors: Arc<AtomicUsize>,
+) {
+    let mut generator = VectorGenerator::new(u64::MAX / 2);
+    while !stop.load(Ordering::Relaxed) {
+        let query = format!(
+            "SELECT pk FROM {table} ORDER BY v ANN OF {} LIMIT 10",
+            format_vector(&generator.next_vector(DIMENSIONS))
+        );
+        let start = Instant::now();
+        match session.query_unpaged(query, ()).await {
+            Ok(_) => latencies.lock().unwrap().push(start.elapsed()),
+            Err(_) => {
+                errors.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
+async fn report(
+    clients: &[HttpClient],
+    index: &IndexInfo,
+    latencies: &Mutex<Vec<Duration>>,
+) -> Report {
+    let mut latencies = std::mem::take(&mut *latencies.lock().unwrap());
+    latencies.sort();
+    let mut memory = Vec::with_capacity(clients.len());
+    for client in clients {
+        let stats = client
+            .index_stats(&index.keyspace, &index.index)
+            .await
+            .expect("failed to get index stats");
+        memory.push(stats.memory.total);
+    }
+    let report = Report {
+        p50: percentile(&latencies, 50),
+        p99: percentile(&latencies, 99),
+        memory,
+    };
+    info!("{} queries: {report:?}", latencies.len());
+    report
+}
+
+#[e2etest::test(group = soak)]
+async fn sustained_load_does_not_degrade(actors: Arc<TestActors>) {
+    info!("started");
+
+    if actors.soak_duration.is_zero() {
+        info!("skipped, the soak duration is not set");
+        return;
+    }
+
+    let (session, clients) = prepare_connection(&actors).await;
+    let keyspace = create_keyspace(&session).await;
+    let table = create_table(
+        &session,
+        &format!("pk INT PRIMARY KEY, v VECTOR<FLOAT, {DIMENSIONS}>"),
+        None,
+    )
+    .await;
+
+    info!("Loading {DATASET_SIZE} vectors");
+    load_dataset(&session, &table).await;
+
+    info!("Building the index");
+    let index = create_index(CreateIndexQuery::new(&session, &clients, &table, "v")).await;
+    for client in &clients {
+        client
+            .wait_for_serving(
+                &index.keyspace,
+                &index.index,
+                WaitOptions::new(BUILD_TIMEOUT).with_interval(Duration::from_secs(5)),
+            )
+            .await
+            .unwrap_or_else(|err| panic!("{err:#}"));
+    }
+
+    info!("Running the load for {:?}", actors.soak_duration);
+    let stop = Arc::new(AtomicBool::new(false));
+    let latencies = Arc::new(Mutex::new(Vec::new()));
+    let errors = Arc::new(AtomicUsize::new(0));
+    let churn_task = tokio::spawn(churn(
+        Arc::clone(&session),
+        table.clone(),
+        Arc::clone(&stop),
+    ));
+    let query_task = tokio::spawn(query(
+        Arc::clone(&session),
+        table.clone(),
+        Arc::clone(&stop),
+        Arc::clone(&latencies),
+        Arc::clone(&errors),
+    ));
+
+    let monitor = Monitor::start(get_default_vs_addrs(&actors), REPORT_INTERVAL);
+    let deadline = Instant::now() + actors.soak_duration;
+    let mut reports = Vec::new();
+    let mut queries = 0;
+    while Instant::now() < deadline {
+        time::sleep(REPORT_INTERVAL.min(deadline.saturating_duration_since(Instant::now()))).await;
+        queries += latencies.lock().unwrap().len();
+        reports.push(report(&clients, &index, &latencies).await);
+    }
+
+    stop.store(true, Ordering::Relaxed);
+    churn_task.await.unwrap();
+    query_task.await.unwrap();
+
+    let errors = errors.load(Ordering::Relaxed);
+    assert!(
+        errors as f64 <= (queries + errors) as f64 * MAX_QUERY_ERROR_RATIO,
+        "{errors} of {} ANN queries failed",
+        queries + errors
+    );
+    let (first, last) = (reports.first().unwrap(), reports.last().unwrap());
+    for (node, (&before, &after)) in first.memory.iter().zip(&last.memory).enumerate() {
+        assert!(
+            after as f64 <= before as f64 * MAX_MEMORY_GROWTH,
+            "memory of the index on node {node} grew from {before} to {after} bytes"
+        );
+    }
+    assert!(
+        last.p99.as_secs_f64() <= first.p99.as_secs_f64() * MAX_LATENCY_GROWTH,
+        "p99 latency of ANN queries grew from {:?} to {:?}",
+        first.p99,
+        last.p99
+    );
+    monitor.assert_bounded(PROCESS_LIMITS).await;
+
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+
+    info!("finished");
+}
@@ -0,0 +1,148 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::TestActors;
+use crate::common::*;
+use crate::partition::PartitionExt;
+use crate::partition::Rule;
+use httpapi::IndexInfo;
+use httpclient::HttpClient;
+use scylla::client::session::Session;
+use std::sync::Arc;
+use tracing::info;
+
+const DATASET_SIZE: i32 = 100;
+
+e2etest::group!(
+    name = split_brain,
+    fixtures = (Fixture),
+    parent = crate::validator
+);
+
+struct Fixture {
+    actors: Arc<TestActors>,
+}
+
+impl e2etest::Fixture for Fixture {
+    async fn setup(setup: &mut impl e2etest::Setup) -> Self {
+        setup.setup::<TestActors>().await;
+        let actors = setup.get::<TestActors>().await.unwrap();
+        // The vector-store nodes connect to ScyllaDB through the proxies and the test connects
+        // directly, so rules for the proxy addresses only affect the vector-store nodes.
+        init_with_proxy(&actors).await;
+        Self { actors }
+    }
+
+    async fn teardown(self) {
+        self.actors.partition.heal().await;
+        cleanup(&self.actors).await;
+    }
+}
+
+async fn insert_rows(session: &Session, table: &TableName, pks: impl Iterator<Item = i32>) {
+    for pk in pks {
+        session
+            .query_unpaged(
+                format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"),
+                (pk, vec![pk as f32, 1.0, 1.0]),
+            )
+            .await
+            .expect("failed to insert data");
+    }
+}
+
+async fn assert_ann_works(session: &Session, table: &TableName) {
+    for _ in 0..10 {
+        let rows = get_query_results(
+            format!("SELECT pk FROM {table} ORDER BY v ANN OF [1.0, 1.0, 1.0] LIMIT 5"),
+            session,
+        )
+        .await
+        .rows::<(i32,)>()
+        .expect("failed to get rows")
+        .count();
+        assert_eq!(rows, 5);
+    }
+}
+
+async fn prepare_index(session: &Session, clients: &[HttpClient], table: &TableName) -> IndexInfo {
+    insert_rows(session, table, 0..DATASET_SIZE).await;
+    let index = create_index(CreateIndexQuery::new(session, clients, table, "v")).await;
+    for client in clients {
+        wait_for_index(client, &index).await;
+    }
+    index
+}
+
+#[e2etest::test(group = split_brain)]
+async fn vector_store_cut_off_from_db_node(actors: Arc<TestActors>) {
+    info!("started");
+
+    let (session, clients) = prepare_connection_no_tls(&actors).await;
+    let keyspace = create_keyspace(&session).await;
+    let table = create_table(&session, "pk INT PRIMARY KEY, v VECTOR<FLOAT, 3>", None).await;
+    let index = prepare_index(&session, &clients, &table).await;
+
+    info!("Dropping CQL connections from the vector-store nodes to the first ScyllaDB node");
+    actors
+        .partition
+        .drop_packets(vec![Rule {
+            from: None,
+            to: get_default_db_proxy_ips(&actors)[0],
+            port: Some(DB_PORT),
+        }])
+        .await;
+
+    // The first ScyllaDB node still reaches the vector-store nodes, and the vector-store nodes
+    // read the changes through the other ScyllaDB nodes.
+    insert_rows(&session, &table, DATASET_SIZE..2 * DATASET_SIZE).await;
+    wait_for_index_count(&clients, &index, 2 * DATASET_SIZE as usize).await;
+    assert_ann_works(&session, &table).await;
+
+    actors.partition.heal().await;
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+
+    info!("finished");
+}
+
+#[e2etest::test(group = split_brain)]
+async fn db_cut_off_from_vector_store_node(actors: Arc<TestActors>) {
+    info!("started");
+
+    let (session, clients) = prepare_connection_no_tls(&actors).await;
+    let keyspace = create_keyspace(&session).await;
+    let table = create_table(&session, "pk INT PRIMARY KEY, v VECTOR<FLOAT, 3>", None).await;
+    let index = prepare_index(&session, &clients, &table).await;
+
+    info!("Dropping HTTP requests to the first vector-store node");
+    actors
+        .partition
+        .drop_packets(vec![Rule {
+            from: None,
+            to: get_default_vs_ips(&actors)[0],
+            port: Some(VS_PORT),
+        }])
+        .await;
+
+    // ScyllaDB fails over to the other vector-store nodes, while the cut off node keeps reading
+    // the changes from ScyllaDB.
+    insert_rows(&session, &table, DATASET_SIZE..2 * DATASET_SIZE).await;
+    wait_for_index_count(&clients[1..], &index, 2 * DATASET_SIZE as usize).await;
+    assert_ann_works(&session, &table).await;
+
+    info!("Healing the partition");
+    actors.partition.heal().await;
+    wait_for_index_count(&clients[..1], &index, 2 * DATASET_SIZE as usize).await;
+
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+
+    info!("finished");
+}
@@ -0,0 +1,230 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::RunArgs;
+use crate::TestActors;
+use crate::common::*;
+use crate::runtime::Runtime;
+use e2etest_scylla_cluster::ScyllaClusterExt;
+use e2etest_vector_store_cluster::VectorStoreCluster;
+use e2etest_vector_store_cluster::VectorStoreClusterExt;
+use e2etest_vector_store_cluster::VectorStoreNodeConfig;
+use httpapi::IndexInfo;
+use httpclient::HttpClient;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time;
+use tracing::info;
+
+const DIMENSIONS: usize = 8;
+const DATASET_SIZE: usize = 500;
+const K: usize = 10;
+/// How long the upgraded node serves next to the old ones before the next node is upgraded.
+const SETTLE_DURATION: Duration = Duration::from_secs(5);
+
+e2etest::group!(
+    name = upgrade,
+    fixtures = (Fixture),
+    parent = crate::validator
+);
+
+struct Fixture {
+    actors: Arc<TestActors>,
+}
+
+impl e2etest::Fixture for Fixture {
+    async fn setup(setup: &mut impl e2etest::Setup) -> Self {
+        setup.setup::<TestActors>().await;
+        let actors = setup.get::<TestActors>().await.unwrap();
+        // The vector-store nodes are started by the test, each with its own binary.
+        init_dns(&actors).await;
+        actors
+            .db
+            .start(get_default_scylla_node_configs(&actors).await)
+            .await;
+        assert!(actors.db.wait_for_ready().await);
+        Self { actors }
+    }
+
+    async fn teardown(self) {
+        cleanup(&self.actors).await;
+    }
+}
+
+/// The vector-store executables a rolling upgrade goes between.
+#[derive(Clone)]
+pub(crate) struct Binaries {
+    old: PathBuf,
+    new: PathBuf,
+    verbose: bool,
+    disable_colors: bool,
+    tmpdir: PathBuf,
+}
+
+impl Binaries {
+    pub(crate) fn new(old: PathBuf, args: &RunArgs, runtime: &dyn Runtime) -> Self {
+        Self {
+            old: runtime
+                .executable(&old)
+                .expect("prepare the old Vector Store executable"),
+            new: runtime
+                .executable(&args.vector_store)
+                .expect("prepare the Vector Store executable"),
+            verbose: args.verbose,
+            disable_colors: args.disable_colors,
+            tmpdir: args.tmpdir.clone(),
+        }
+    }
+
+    /// Starts a single vector-store node. Every node gets its own cluster actor, so it can be
+    /// replaced while the other nodes keep running.
+    async fn start_node(
+        &self,
+        binary: &Path,
+        config: VectorStoreNodeConfig,
+    ) -> mpsc::Sender<VectorStoreCluster> {
+        let node = e2etest_vector_store_cluster::new(
+            binary.to_path_buf(),
+            self.verbose,
+            self.disable_colors,
+            self.tmpdir.clone(),
+        )
+        .await;
+        info!(
+            "Starting vector-store {} at {}",
+            node.version().await,
+            config.vs_ip
+        );
+        node.start(vec![config]).await;
+        assert!(node.wait_for_ready().await);
+        node
+    }
+}
+
+/// The primary keys of the nearest neighbours of `query` as returned by the node.
+async fn neighbors(client: &HttpClient, index: &IndexInfo, query: &[f32]) -> Vec<i64> {
+    let (primary_keys, _, _) = client
+        .ann(
+            &index.keyspace,
+            &index.index,
+            query.to_vec().into(),
+            None,
+            NonZeroUsize::new(K).unwrap().into(),
+        )
+        .await;
+    let mut pks: Vec<_> = primary_keys
+        .get(&"pk".into())
+        .expect("pk column")
+        .iter()
+        .map(|pk| pk.as_i64().expect("pk is not an integer"))
+        .collect();
+    pks.sort();
+    pks
+}
+
+#[e2etest::test(group = upgrade)]
+async fn rolling_upgrade_keeps_serving(actors: Arc<TestActors>) {
+    info!("started");
+
+    let Some(binaries) = actors.upgrade.clone() else {
+        info!("skipped, the binary to upgrade from is not set");
+        return;
+    };
+
+    let mut nodes = Vec::new();
+    for config in get_default_vs_node_configs(&actors).await {
+        nodes.push(binaries.start_node(&binaries.old, config).await);
+    }
+
+    let (session, clients) = prepare_connection(&actors).await;
+    let keyspace = create_keyspace(&session).await;
+    let table = create_table(
+        &session,
+        &format!("pk INT PRIMARY KEY, v VECTOR<FLOAT, {DIMENSIONS}>"),
+        None,
+    )
+    .await;
+    let insert = session
+        .prepare(format!("INSERT INTO {table} (pk, v) VALUES (?, ?)"))
+        .await
+        .expect("failed to prepare an insert");
+    let mut generator = VectorGenerator::new(42);
+    for pk in 0..DATASET_SIZE {
+        session
+            .execute_unpaged(&insert, (pk as i32, generator.next_vector(DIMENSIONS)))
+            .await
+            .expect("failed to insert data");
+    }
+    let index = create_index(CreateIndexQuery::new(&session, &clients, &table, "v")).await;
+    for client in &clients {
+        wait_for_index(client, &index).await;
+    }
+    let query = generator.next_vector(DIMENSIONS);
+    let expected = neighbors(&clients[0], &index, &query).await;
+
+    info!("Starting ANN load");
+    let stop = Arc::new(AtomicBool::new(false));
+    let reads = Arc::new(LoadStats::default());
+    let reader = spawn_load(
+        Arc::clone(&stop),
+        Arc::clone(&reads),
+        Duration::from_millis(50),
+        {
+            let session = Arc::clone(&session);
+            let query = format!(
+                "SELECT pk FROM {table} ORDER BY v ANN OF {} LIMIT {K}",
+                format_vector(&query)
+            );
+            move |_| {
+                let session = Arc::clone(&session);
+                let query = query.clone();
+                async move { session.query_unpaged(query, ()).await.is_ok() }
+            }
+        },
+    );
+
+    let configs = get_default_vs_node_configs(&actors).await;
+    for (node, (config, client)) in nodes.iter_mut().zip(configs.into_iter().zip(&clients)) {
+        info!("Upgrading the node at {}", client.url());
+        node.stop().await;
+        *node = binaries.start_node(&binaries.new, config).await;
+        wait_for_index_count(std::slice::from_ref(client), &index, DATASET_SIZE).await;
+        for client in &clients {
+            assert_eq!(
+                neighbors(client, &index, &query).await,
+                expected,
+                "the node at {} returns different neighbours",
+                client.url()
+            );
+        }
+        time::sleep(SETTLE_DURATION).await;
+    }
+
+    info!("Stopping the load");
+    stop.store(true, Ordering::Relaxed);
+    reader.await.unwrap();
+    let failed = reads.failed.load(Ordering::Relaxed);
+    info!(
+        "Reads: {} ok, {failed} failed",
+        reads.ok.load(Ordering::Relaxed)
+    );
+    assert_eq!(failed, 0, "ANN queries failed during the upgrade");
+
+    session
+        .query_unpaged(format!("DROP KEYSPACE {keyspace}"), ())
+        .await
+        .expect("failed to drop a keyspace");
+    for node in nodes {
+        node.stop().await;
+    }
+
+    info!("finished");
+}
@@ -0,0 +1,234 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Microbenchmarks of primary keys and of the map between keys and their ids, to evaluate
+//! changes of their memory layout.
+//!
+//! The `key` group compares [`PrimaryKey`] with the `Vec<CqlValue>` it replaced. The `key-map`
+//! group fills a map with `BENCHES_KEYS` keys (10M by default) and measures inserting and looking
+//! up keys in it, for a map storing the keys in both directions and for the layout of the table,
+//! which stores the keys once in a vector indexed by id and maps only their hashes to ids.
+
+mod profiler;
+
+use crate::profiler::Flamegraph;
+use criterion::BatchSize;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use scylla::value::CqlValue;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::BuildHasher;
+use std::hash::RandomState;
+use std::hint::black_box;
+use std::time::Duration;
+use std::time::Instant;
+use vector_store::PrimaryKey;
+
+const ENV_KEYS: &str = "BENCHES_KEYS";
+
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+fn keys_count() -> usize {
+    dotenvy::var(ENV_KEYS)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(10_000_000)
+}
+
+/// Values of a key with a bigint partition key and a text clustering key.
+fn values(id: u64) -> Vec<CqlValue> {
+    vec![
+        CqlValue::BigInt(id as i64),
+        CqlValue::Text(format!("ck-{}", id % 1000)),
+    ]
+}
+
+fn key(c: &mut Criterion) {
+    let mut group = c.benchmark_group("key");
+    group.throughput(Throughput::Elements(1));
+
+    group.bench_function("encode/primary-key", |b| {
+        b.iter_batched(
+            || values(42),
+            |values| PrimaryKey::from_iter(values),
+            BatchSize::SmallInput,
+        )
+    });
+    group.bench_function("encode/vec", |b| {
+        b.iter_batched(
+            || values(42),
+            |values| values.into_boxed_slice(),
+            BatchSize::SmallInput,
+        )
+    });
+
+    let primary_key = PrimaryKey::from_iter(values(42));
+    let vec = values(42);
+    group.bench_function("decode/primary-key", |b| {
+        b.iter(|| {
+            (0..primary_key.len())
+                .map(|idx| primary_key.get(idx).unwrap())
+                .count()
+        })
+    });
+    group.bench_function("decode/vec", |b| b.iter(|| vec.to_vec()));
+
+    let hasher = RandomState::new();
+    group.bench_function("hash/primary-key", |b| {
+        b.iter(|| hasher.hash_one(black_box(&primary_key)))
+    });
+    // CqlValue is not Hash, so keys were hashed by their debug representation.
+    group.bench_function("hash/vec", |b| {
+        b.iter(|| hasher.hash_one(format!("{:?}", black_box(&vec))))
+    });
+
+    let other_key = PrimaryKey::from_iter(values(42));
+    let other_vec = values(42);
+    group.bench_function("eq/primary-key", |b| {
+        b.iter(|| black_box(&primary_key) == black_box(&other_key))
+    });
+    group.bench_function("eq/vec", |b| {
+        b.iter(|| black_box(&vec) == black_box(&other_vec))
+    });
+
+    group.finish();
+}
+
+trait KeyMap {
+    fn insert(&mut self, key: PrimaryKey) -> u64;
+    fn get(&self, key: &PrimaryKey) -> Option<u64>;
+    fn key(&self, id: u64) -> Option<&PrimaryKey>;
+}
+
+/// Keys stored in both directions.
+#[derive(Default)]
+struct BiMap {
+    ids: HashMap<PrimaryKey, u64>,
+    keys: HashMap<u64, PrimaryKey>,
+}
+
+impl KeyMap for BiMap {
+    fn insert(&mut self, key: PrimaryKey) -> u64 {
+        let id = self.keys.len() as u64;
+        self.ids.insert(key.clone(), id);
+        self.keys.insert(id, key);
+        id
+    }
+
+    fn get(&self, key: &PrimaryKey) -> Option<u64> {
+        self.ids.get(key).copied()
+    }
+
+    fn key(&self, id: u64) -> Option<&PrimaryKey> {
+        self.keys.get(&id)
+    }
+}
+
+/// Keys stored once and indexed by their hash, as in the table.
+#[derive(Default)]
+struct HashIndex {
+    hasher: RandomState,
+    ids: HashMap<u64, u64>,
+    collisions: HashMap<u64, Vec<u64>>,
+    keys: Vec<PrimaryKey>,
+}
+
+impl KeyMap for HashIndex {
+    fn insert(&mut self, key: PrimaryKey) -> u64 {
+        let id = self.keys.len() as u64;
+        let hash = self.hasher.hash_one(&key);
+        match self.ids.entry(hash) {
+            Entry::Vacant(entry) => {
+                entry.insert(id);
+            }
+            Entry::Occupied(_) => self.collisions.entry(hash).or_default().push(id),
+        }
+        self.keys.push(key);
+        id
+    }
+
+    fn get(&self, key: &PrimaryKey) -> Option<u64> {
+        let hash = self.hasher.hash_one(key);
+        let is_key_of = |id: &u64| self.keys[*id as usize] == *key;
+        let first = self.ids.get(&hash)?;
+        if is_key_of(first) {
+            return Some(*first);
+        }
+        self.collisions.get(&hash)?.iter().copied().find(is_key_of)
+    }
+
+    fn key(&self, id: u64) -> Option<&PrimaryKey> {
+        self.keys.get(id as usize)
+    }
+}
+
+fn bench_key_map<M: KeyMap + Default>(c: &mut Criterion, name: &str) {
+    let count = keys_count();
+    let mut map = M::default();
+    for id in 0..count as u64 {
+        map.insert(PrimaryKey::from_iter(values(id)));
+    }
+    let mut next = count as u64;
+
+    let mut group = c.benchmark_group("key-map");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function(BenchmarkId::new(format!("{name}/insert"), count), |b| {
+        b.iter_custom(|iters| {
+            let keys: Vec<_> = (next..next + iters)
+                .map(|id| PrimaryKey::from_iter(values(id)))
+                .collect();
+            next += iters;
+            let start = Instant::now();
+            for key in keys {
+                black_box(map.insert(key));
+            }
+            start.elapsed()
+        })
+    });
+    group.bench_function(BenchmarkId::new(format!("{name}/get"), count), |b| {
+        b.iter_custom(|iters| {
+            let keys: Vec<_> = (0..iters)
+                .map(|_| PrimaryKey::from_iter(values(rand::random_range(0..count as u64))))
+                .collect();
+            let start = Instant::now();
+            for key in &keys {
+                black_box(map.get(key).unwrap());
+            }
+            start.elapsed()
+        })
+    });
+    group.bench_function(BenchmarkId::new(format!("{name}/key"), count), |b| {
+        b.iter_custom(|iters| {
+            let ids: Vec<_> = (0..iters)
+                .map(|_| rand::random_range(0..count as u64))
+                .collect();
+            let start = Instant::now();
+            for id in ids {
+                black_box(map.key(id).unwrap());
+            }
+            start.elapsed()
+        })
+    });
+    group.finish();
+}
+
+fn key_map(c: &mut Criterion) {
+    bench_key_map::<BiMap>(c, "bimap");
+    bench_key_map::<HashIndex>(c, "hash-index");
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default()
+        .measurement_time(Duration::from_secs(10))
+        .with_profiler(Flamegraph::default());
+    targets = key, key_map
+);
+criterion_main!(benches);
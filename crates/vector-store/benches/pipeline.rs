@@ -3,17 +3,13 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
-#[path = "../tests/integration/db_basic.rs"]
-#[allow(dead_code)]
-mod db_basic;
+mod profiler;
 
+use crate::profiler::Flamegraph;
 use axum::http::StatusCode;
 use criterion::BenchmarkId;
 use criterion::Criterion;
 use criterion::criterion_group;
-use db_basic::DbBasic;
-use db_basic::ScanFn;
-use db_basic::Table;
 use futures::FutureExt;
 use futures::StreamExt;
 use futures::stream;
@@ -34,6 +30,10 @@ use std::time::Duration;
 use std::time::Instant;
 use tap::Pipe;
 use testclient::TestClient;
+use testsupport::db_basic;
+use testsupport::db_basic::DbBasic;
+use testsupport::db_basic::ScanFn;
+use testsupport::db_basic::Table;
 use tokio::runtime::Runtime;
 use tokio::sync::Notify;
 use tokio::sync::mpsc;
@@ -131,6 +131,8 @@ fn default_index_metadata(dimensions: usize) -> IndexMetadata {
             expansion_search: ExpansionSearch::default(),
             space_type: SpaceType::Euclidean,
             quantization: Quantization::default(),
+            embedding_model: None,
+            ttl: None,
         }),
     }
 }
@@ -197,7 +199,13 @@ async fn run_vector_store(
     let index_factory = vector_store::new_index_factory_usearch(config.clone()).unwrap();
 
     let addr = config.borrow().vector_store_addr;
-    let (http_tx, http_rx) = watch::channel(Some(Arc::new(HttpServerConfig { addr, tls: None })));
+    let (http_tx, http_rx) = watch::channel(Some(Arc::new(HttpServerConfig {
+        addr,
+        extra_addrs: Vec::new(),
+        tls: None,
+        tuning: Default::default(),
+        auth: None,
+    })));
     let (_mtls_tx, mtls_http_rx) = watch::channel(None);
     let receivers = ConfigReceivers {
         config,
@@ -1166,13 +1174,15 @@ where
 }
 
 criterion_group!(
-    benches,
-    fullscan_add,
-    search,
-    cdc_add,
-    cdc_update,
-    search_while_updating,
-    search_while_inserting,
+    name = benches;
+    config = Criterion::default().with_profiler(Flamegraph::default());
+    targets =
+        fullscan_add,
+        search,
+        cdc_add,
+        cdc_update,
+        search_while_updating,
+        search_while_inserting,
 );
 
 #[hotpath::main]
@@ -0,0 +1,118 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! CPU spent on the protocol of a single ANN query, for several embedding sizes: the client
+//! encodes the request, the server decodes it and encodes the response, and the client decodes
+//! the response. `http-json` is the current HTTP API; `raw-binary` copies little endian values
+//! without any framing and is the lower bound of a binary RPC protocol. Searching itself is
+//! measured by the `pipeline` benches.
+
+mod profiler;
+
+use crate::profiler::Flamegraph;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use httpapi::PostIndexAnnRequest;
+use httpapi::PostIndexAnnResponse;
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::num::NonZeroUsize;
+
+#[global_allocator]
+static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
+
+const DIMENSIONS: [usize; 4] = [128, 768, 1536, 3072];
+const LIMIT: usize = 10;
+
+fn embedding(dimensions: usize) -> Vec<f32> {
+    (0..dimensions).map(|it| it as f32 / 7.0).collect()
+}
+
+fn http_json(dimensions: usize) -> usize {
+    let request = serde_json::to_vec(&PostIndexAnnRequest {
+        vector: embedding(dimensions).into(),
+        filter: None,
+        limit: NonZeroUsize::new(LIMIT).unwrap().into(),
+        min_write_timestamp: None,
+        write_wait_timeout_ms: None,
+        oversampling: None,
+        rerank: None,
+        payload: None,
+    })
+    .unwrap();
+
+    let request: PostIndexAnnRequest = serde_json::from_slice(&request).unwrap();
+    let response = serde_json::to_vec(&PostIndexAnnResponse {
+        primary_keys: HashMap::from([("id".into(), (0..LIMIT as i64).map(Into::into).collect())]),
+        distances: (0..LIMIT).map(|it| (it as f32).into()).collect(),
+        similarity_scores: (0..LIMIT).map(|it| (it as f32).into()).collect(),
+        payload: None,
+    })
+    .unwrap();
+
+    let response: PostIndexAnnResponse = serde_json::from_slice(&response).unwrap();
+    request.vector.as_ref().len() + response.distances.len()
+}
+
+fn raw_binary(dimensions: usize) -> usize {
+    let mut request = Vec::with_capacity(8 + dimensions * 4);
+    request.extend_from_slice(&(LIMIT as u32).to_le_bytes());
+    request.extend_from_slice(&(dimensions as u32).to_le_bytes());
+    embedding(dimensions)
+        .into_iter()
+        .for_each(|value| request.extend_from_slice(&value.to_le_bytes()));
+
+    let limit = u32::from_le_bytes(request[0..4].try_into().unwrap()) as usize;
+    let vector: Vec<f32> = request[8..]
+        .chunks_exact(4)
+        .map(|value| f32::from_le_bytes(value.try_into().unwrap()))
+        .collect();
+    let mut response = Vec::with_capacity(limit * 16);
+    for it in 0..limit {
+        response.extend_from_slice(&(it as i64).to_le_bytes());
+        response.extend_from_slice(&(it as f32).to_le_bytes());
+        response.extend_from_slice(&(it as f32).to_le_bytes());
+    }
+
+    let results: Vec<(i64, f32, f32)> = response
+        .chunks_exact(16)
+        .map(|result| {
+            (
+                i64::from_le_bytes(result[0..8].try_into().unwrap()),
+                f32::from_le_bytes(result[8..12].try_into().unwrap()),
+                f32::from_le_bytes(result[12..16].try_into().unwrap()),
+            )
+        })
+        .collect();
+    vector.len() + results.len()
+}
+
+fn protocol(c: &mut Criterion) {
+    let mut group = c.benchmark_group("protocol");
+    group.throughput(Throughput::Elements(1));
+    for dimensions in DIMENSIONS {
+        group.bench_with_input(
+            BenchmarkId::new("http-json", dimensions),
+            &dimensions,
+            |b, &dimensions| b.iter(|| http_json(black_box(dimensions))),
+        );
+        group.bench_with_input(
+            BenchmarkId::new("raw-binary", dimensions),
+            &dimensions,
+            |b, &dimensions| b.iter(|| raw_binary(black_box(dimensions))),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().with_profiler(Flamegraph::default());
+    targets = protocol
+);
+criterion_main!(benches);
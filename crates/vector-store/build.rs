@@ -0,0 +1,16 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+fn main() {
+    // The gRPC API needs `protoc` to generate its messages, so it is compiled only on request.
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/vector_store.proto");
+        tonic_prost_build::configure()
+            .build_client(false)
+            .compile_protos(&["proto/vector_store.proto"], &["proto"])
+            .expect("unable to compile the gRPC API, is protoc installed?");
+    }
+}
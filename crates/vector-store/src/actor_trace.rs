@@ -0,0 +1,163 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Tracing of messages processed by internal actors.
+//!
+//! Tracing is disabled by default. It is switched at runtime by the `actor_tracing` config
+//! option (`VECTOR_STORE_ACTOR_TRACING`) or by [`set_enabled`]. Every traced message is logged
+//! at the trace level with the `actor_trace` target and passed to the hook set by [`set_hook`].
+
+use crate::Config;
+use crate::IndexKey;
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+use tokio::sync::watch;
+use tracing::info;
+use tracing::trace;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static HOOK: RwLock<Option<ActorTraceHook>> = RwLock::new(None);
+
+/// A message processed by an actor.
+#[derive(Clone, Debug)]
+pub struct ActorMessage {
+    /// Name of the actor, e.g. `engine` or `usearch`.
+    pub actor: &'static str,
+    /// Name of the message variant.
+    pub message: &'static str,
+    /// The index the actor works on, if any.
+    pub index: Option<IndexKey>,
+    /// Number of messages waiting in the actor queue when the message was received.
+    pub queue_len: usize,
+    /// Time the message waited inside the actor before processing started, for actors which
+    /// queue received messages internally.
+    pub queue_wait: Option<Duration>,
+    /// Time spent processing the message.
+    pub processing: Duration,
+}
+
+/// A callback receiving every traced message. The hook is global for the process.
+pub type ActorTraceHook = Arc<dyn Fn(&ActorMessage) + Send + Sync>;
+
+pub fn set_enabled(enabled: bool) {
+    if ENABLED.swap(enabled, Ordering::Relaxed) != enabled {
+        info!(
+            "actor tracing {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Sets the hook called for every traced message. The hook is called on the actor task, so it
+/// must return quickly.
+pub fn set_hook(hook: Option<ActorTraceHook>) {
+    *HOOK.write().unwrap() = hook;
+}
+
+/// Keeps the runtime switch in sync with the config.
+pub(crate) fn watch_config(mut config_rx: watch::Receiver<Arc<Config>>) {
+    set_enabled(config_rx.borrow_and_update().actor_tracing);
+    tokio::spawn(async move {
+        while config_rx.changed().await.is_ok() {
+            set_enabled(config_rx.borrow_and_update().actor_tracing);
+        }
+    });
+}
+
+/// Measures processing of a single message. The message is reported when the guard is dropped.
+pub(crate) struct MessageGuard {
+    actor: &'static str,
+    message: &'static str,
+    index: Option<IndexKey>,
+    queue_len: usize,
+    queue_wait: Option<Duration>,
+    start: Instant,
+}
+
+impl MessageGuard {
+    pub(crate) fn queue_wait(mut self, received: Instant) -> Self {
+        self.queue_wait = Some(self.start.saturating_duration_since(received));
+        self
+    }
+}
+
+/// Starts tracing a message. Returns `None` when tracing is disabled.
+pub(crate) fn start(
+    actor: &'static str,
+    message: &'static str,
+    index: Option<&IndexKey>,
+    queue_len: usize,
+) -> Option<MessageGuard> {
+    is_enabled().then(|| MessageGuard {
+        actor,
+        message,
+        index: index.cloned(),
+        queue_len,
+        queue_wait: None,
+        start: Instant::now(),
+    })
+}
+
+impl Drop for MessageGuard {
+    fn drop(&mut self) {
+        let msg = ActorMessage {
+            actor: self.actor,
+            message: self.message,
+            index: self.index.take(),
+            queue_len: self.queue_len,
+            queue_wait: self.queue_wait,
+            processing: self.start.elapsed(),
+        };
+        trace!(
+            target: "actor_trace",
+            actor = msg.actor,
+            message = msg.message,
+            index = ?msg.index,
+            queue_len = msg.queue_len,
+            queue_wait = ?msg.queue_wait,
+            processing = ?msg.processing,
+        );
+        if let Some(hook) = HOOK.read().unwrap().as_ref() {
+            hook(&msg);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn reports_messages_only_when_enabled() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        set_hook(Some(Arc::new({
+            let seen = Arc::clone(&seen);
+            move |msg: &ActorMessage| {
+                if msg.actor == "test" {
+                    seen.lock().unwrap().push(msg.message);
+                }
+            }
+        })));
+
+        set_enabled(false);
+        assert!(start("test", "Disabled", None, 0).is_none());
+
+        set_enabled(true);
+        drop(start("test", "Enabled", None, 3));
+        set_enabled(false);
+        set_hook(None);
+
+        assert_eq!(*seen.lock().unwrap(), vec!["Enabled"]);
+    }
+}
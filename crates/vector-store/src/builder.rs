@@ -0,0 +1,507 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::Config;
+use crate::ConfigManager;
+use crate::ConfigReceivers;
+use crate::Credentials;
+use crate::EngineHandle;
+use crate::HttpServer;
+use crate::HttpServerExt;
+use crate::VsIndexFactory;
+use crate::db::Db;
+use crate::internals::Internals;
+use crate::metrics::Metrics;
+use crate::node_state::Event;
+use crate::node_state::NodeState;
+use crate::node_state::NodeStateExt;
+use anyhow::bail;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::watch;
+
+type Env = Box<dyn Fn(&str) -> anyhow::Result<String> + Send + Sync>;
+
+/// A builder for a running vector-store service.
+///
+/// The builder collects the configuration and the optional custom components (index factory,
+/// db actor, ...) and starts the service with [`VectorStoreBuilder::build`]. Components which
+/// are not provided are created from the configuration the same way as the `vector-store`
+/// binary does it.
+pub struct VectorStoreBuilder {
+    config: Config,
+    receivers: Option<ConfigReceivers>,
+    env: Option<Env>,
+    index_factory: Option<Box<dyn VsIndexFactory + Send + Sync>>,
+    node_state: Option<Sender<NodeState>>,
+    db: Option<Sender<Db>>,
+    internals: Option<Sender<Internals>>,
+    metrics: Option<Arc<Metrics>>,
+}
+
+impl std::fmt::Debug for VectorStoreBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VectorStoreBuilder")
+            .field("config", &self.config)
+            .field("custom_config_receivers", &self.receivers.is_some())
+            .field("reload_from_env", &self.env.is_some())
+            .field("custom_index_factory", &self.index_factory.is_some())
+            .field("custom_db", &self.db.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for VectorStoreBuilder {
+    fn default() -> Self {
+        Self::new(Config::default())
+    }
+}
+
+impl VectorStoreBuilder {
+    /// Creates a builder with the given initial configuration.
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            receivers: None,
+            env: None,
+            index_factory: None,
+            node_state: None,
+            db: None,
+            internals: None,
+            metrics: None,
+        }
+    }
+
+    /// Returns the configuration collected so far.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Sets the address of the main HTTP server.
+    pub fn addr(mut self, addr: SocketAddr) -> Self {
+        self.config.vector_store_addr = addr;
+        self
+    }
+
+    /// Adds an address the main HTTP server listens on in addition to [`VectorStoreBuilder::addr`],
+    /// e.g. an IPv6 address next to an IPv4 one.
+    pub fn extra_addr(mut self, addr: SocketAddr) -> Self {
+        self.config.vector_store_extra_addrs.push(addr);
+        self
+    }
+
+    /// Sets the address of the mTLS HTTP server. It is used only when
+    /// [`VectorStoreBuilder::mtls_ca_cert`] is set.
+    pub fn mtls_addr(mut self, addr: SocketAddr) -> Self {
+        self.config.mtls_addr = addr;
+        self
+    }
+
+    /// Sets the address of the gRPC server, which needs the `grpc` feature.
+    pub fn grpc_addr(mut self, addr: SocketAddr) -> Self {
+        self.config.grpc_addr = Some(addr);
+        self
+    }
+
+    /// Sets the URI of the ScyllaDB node used for discovering indexes and reading data.
+    pub fn scylladb_uri(mut self, uri: impl Into<String>) -> Self {
+        self.config.scylladb_uri = uri.into();
+        self
+    }
+
+    /// Sets the number of threads for the tokio runtime. It is used by [`crate::block_on`], the
+    /// runtime must be created before calling [`VectorStoreBuilder::build`].
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.config.threads = Some(threads);
+        self
+    }
+
+    /// Enables TLS for the main HTTP server.
+    pub fn tls(mut self, cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        self.config.tls_cert_path = Some(cert_path.into());
+        self.config.tls_key_path = Some(key_path.into());
+        self
+    }
+
+    /// Enables the mTLS HTTP server which verifies clients using the given CA certificate.
+    /// Requires [`VectorStoreBuilder::tls`].
+    pub fn mtls_ca_cert(mut self, ca_cert_path: impl Into<PathBuf>) -> Self {
+        self.config.mtls_ca_cert_path = Some(ca_cert_path.into());
+        self
+    }
+
+    /// Sets credentials used to authenticate to ScyllaDB.
+    pub fn credentials(mut self, credentials: Credentials) -> Self {
+        self.config.credentials = Some(credentials);
+        self
+    }
+
+    /// Uses already created configuration channels instead of creating a [`ConfigManager`] from
+    /// the builder configuration. The builder configuration is ignored in that case.
+    pub fn config_receivers(mut self, receivers: ConfigReceivers) -> Self {
+        self.receivers = Some(receivers);
+        self
+    }
+
+    /// Reloads the configuration from the environment on SIGHUP.
+    pub fn reload_from_env(
+        mut self,
+        env: impl Fn(&str) -> anyhow::Result<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.env = Some(Box::new(env));
+        self
+    }
+
+    /// Uses a custom vector index factory instead of the one selected by the configuration.
+    pub fn index_factory(mut self, index_factory: Box<dyn VsIndexFactory + Send + Sync>) -> Self {
+        self.index_factory = Some(index_factory);
+        self
+    }
+
+    /// Uses a custom node state actor.
+    pub fn node_state(mut self, node_state: Sender<NodeState>) -> Self {
+        self.node_state = Some(node_state);
+        self
+    }
+
+    /// Uses a custom db actor instead of connecting to ScyllaDB. See [`crate::db::new_from_source`]
+    /// for serving the actor from a [`crate::db::DbSource`].
+    pub fn db(mut self, db: Sender<Db>) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Uses a custom internals actor.
+    pub fn internals(mut self, internals: Sender<Internals>) -> Self {
+        self.internals = Some(internals);
+        self
+    }
+
+    /// Uses custom metrics.
+    pub fn metrics(mut self, metrics: Arc<Metrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Validates the builder configuration. It is called by [`VectorStoreBuilder::build`].
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.receivers.is_some() {
+            return Ok(());
+        }
+        let config = &self.config;
+        config.validate()?;
+        if config.opensearch_addr.is_some() && config.use_diskann && self.index_factory.is_none() {
+            bail!("OpenSearch and DiskANN index backends cannot be enabled together");
+        }
+        if cfg!(not(feature = "opensearch"))
+            && config.opensearch_addr.is_some()
+            && self.index_factory.is_none()
+        {
+            bail!("OpenSearch index backend is not compiled in, enable the `opensearch` feature");
+        }
+        if config.faiss_index.is_some()
+            && (config.opensearch_addr.is_some() || config.use_diskann)
+            && self.index_factory.is_none()
+        {
+            bail!("FAISS index backend cannot be enabled together with OpenSearch or DiskANN");
+        }
+        if cfg!(not(feature = "faiss"))
+            && config.faiss_index.is_some()
+            && self.index_factory.is_none()
+        {
+            bail!("FAISS index backend is not compiled in, enable the `faiss` feature");
+        }
+        if cfg!(not(feature = "grpc")) && config.grpc_addr.is_some() {
+            bail!("gRPC API is not compiled in, enable the `grpc` feature");
+        }
+        if config.scylladb_uri.is_empty() && self.db.is_none() {
+            bail!("ScyllaDB URI must not be empty");
+        }
+        Ok(())
+    }
+
+    /// Validates the configuration, starts all actors and HTTP servers and returns a handle to
+    /// the running service. Must be called from within a tokio runtime.
+    pub async fn build(self) -> anyhow::Result<VectorStore> {
+        let started = self.start_engine().await?;
+        crate::systemd::start(
+            started.engine.node_state().clone(),
+            started.receivers.config.borrow().systemd_ready_status,
+        );
+        let consul = started.receivers.config.borrow().consul.clone();
+        let use_tls = started.receivers.config.borrow().tls_cert_path.is_some();
+        let grpc_addr = started.receivers.config.borrow().grpc_addr;
+        let grpc =
+            crate::run_grpc_server(&started.engine, started.metrics.clone(), grpc_addr).await?;
+        let (http, mtls) = crate::run_http_servers(
+            &started.engine,
+            started.internals,
+            started.metrics,
+            started.receivers.http,
+            started.receivers.mtls_http,
+        )
+        .await?;
+        if let Some(consul) = consul {
+            crate::consul::start(
+                consul,
+                started.engine.node_state().clone(),
+                http.address().await,
+                use_tls,
+            );
+        }
+        Ok(VectorStore {
+            http,
+            mtls,
+            grpc,
+            engine: started.engine,
+        })
+    }
+
+    /// Validates the configuration and starts only the db, engine and monitor actors, without
+    /// binding the HTTP listeners. It is intended for embedding vector-store into another
+    /// service. Must be called from within a tokio runtime.
+    pub async fn build_engine(self) -> anyhow::Result<EngineHandle> {
+        Ok(self.start_engine().await?.engine)
+    }
+
+    async fn start_engine(self) -> anyhow::Result<StartedEngine> {
+        self.validate()?;
+
+        let receivers = match self.receivers {
+            Some(receivers) => receivers,
+            None => {
+                let (config_manager, receivers) = ConfigManager::new(self.config).await?;
+                if let Some(env) = self.env {
+                    config_manager.start(env);
+                }
+                receivers
+            }
+        };
+
+        let config_rx = receivers.config.clone();
+        let index_factory = match self.index_factory {
+            Some(index_factory) => index_factory,
+            None => default_index_factory(&config_rx)?,
+        };
+
+        let node_state = match self.node_state {
+            Some(node_state) => node_state,
+            None => crate::new_node_state().await,
+        };
+        let internals = self.internals.unwrap_or_else(crate::new_internals);
+        let metrics = self.metrics.unwrap_or_else(crate::new_metrics);
+        let db = match self.db {
+            Some(db) => db,
+            None => {
+                crate::new_db(
+                    node_state.clone(),
+                    internals.clone(),
+                    config_rx.clone(),
+                    metrics.clone(),
+                )
+                .await?
+            }
+        };
+
+        let engine = crate::new_engine(
+            node_state,
+            db,
+            internals.clone(),
+            index_factory,
+            config_rx,
+            metrics.clone(),
+        )
+        .await?;
+
+        Ok(StartedEngine {
+            engine,
+            internals,
+            metrics,
+            receivers,
+        })
+    }
+}
+
+/// Creates the index factory selected by the configuration.
+fn default_index_factory(
+    config_rx: &watch::Receiver<Arc<Config>>,
+) -> anyhow::Result<Box<dyn VsIndexFactory + Send + Sync>> {
+    let factory = backend_index_factory(config_rx)?;
+    let Some(max_rows) = config_rx.borrow().flat_index_max_rows else {
+        return Ok(factory);
+    };
+    tracing::info!("Using flat indexes up to {max_rows} rows");
+    Ok(crate::new_index_factory_flat(
+        factory,
+        max_rows,
+        config_rx.clone(),
+    ))
+}
+
+/// Creates the factory of the index backend selected by the configuration.
+fn backend_index_factory(
+    config_rx: &watch::Receiver<Arc<Config>>,
+) -> anyhow::Result<Box<dyn VsIndexFactory + Send + Sync>> {
+    #[cfg(feature = "opensearch")]
+    if let Some(addr) = config_rx.borrow().opensearch_addr.clone() {
+        tracing::info!("Using OpenSearch index factory at {addr}");
+        return crate::new_index_factory_opensearch(addr, config_rx.clone());
+    }
+    #[cfg(feature = "faiss")]
+    if let Some(description) = config_rx.borrow().faiss_index.clone() {
+        tracing::info!("Using FAISS index factory with {description}");
+        return crate::new_index_factory_faiss(description);
+    }
+    if config_rx.borrow().use_diskann {
+        tracing::info!("Using DiskANN index factory");
+        return crate::new_index_factory_diskann(config_rx.clone());
+    }
+    tracing::info!("Using Usearch index factory");
+    crate::new_index_factory_usearch(config_rx.clone())
+}
+
+struct StartedEngine {
+    engine: EngineHandle,
+    internals: Sender<Internals>,
+    metrics: Arc<Metrics>,
+    receivers: ConfigReceivers,
+}
+
+/// A handle to the running vector-store service created by [`VectorStoreBuilder`].
+///
+/// The service is stopped when the handle and all clones of its actor senders are dropped.
+pub struct VectorStore {
+    http: Sender<HttpServer>,
+    mtls: Sender<HttpServer>,
+    grpc: Option<SocketAddr>,
+    engine: EngineHandle,
+}
+
+impl std::fmt::Debug for VectorStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VectorStore").finish_non_exhaustive()
+    }
+}
+
+impl VectorStore {
+    /// Returns the main HTTP server actor.
+    pub fn http_server(&self) -> &Sender<HttpServer> {
+        &self.http
+    }
+
+    /// Returns the mTLS HTTP server actor.
+    pub fn mtls_server(&self) -> &Sender<HttpServer> {
+        &self.mtls
+    }
+
+    /// Returns the handle to the engine serving the HTTP servers.
+    pub fn engine(&self) -> &EngineHandle {
+        &self.engine
+    }
+
+    /// Returns the address the main HTTP server is listening on.
+    pub async fn addr(&self) -> Option<SocketAddr> {
+        *self.http.address().await.borrow()
+    }
+
+    /// Returns all addresses the main HTTP server is listening on.
+    pub async fn addrs(&self) -> Vec<SocketAddr> {
+        self.http.addresses().await.borrow().clone()
+    }
+
+    /// Returns the address the mTLS HTTP server is listening on, if it is enabled.
+    pub async fn mtls_addr(&self) -> Option<SocketAddr> {
+        *self.mtls.address().await.borrow()
+    }
+
+    /// Returns the address the gRPC server is listening on, if it is enabled.
+    pub fn grpc_addr(&self) -> Option<SocketAddr> {
+        self.grpc
+    }
+
+    /// Marks the node as shutting down, so that `/readyz` stops reporting it ready, and announces
+    /// to the cluster peers that the node leaves.
+    pub async fn start_shutdown(&self) {
+        self.engine
+            .node_state()
+            .send_event(Event::ShutdownStarted)
+            .await;
+        self.engine.gossip().leave().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_default_config() {
+        assert!(VectorStoreBuilder::default().validate().is_ok());
+    }
+
+    #[test]
+    fn validate_tls_requires_cert_and_key() {
+        let mut builder = VectorStoreBuilder::default();
+        builder.config.tls_cert_path = Some("cert.pem".into());
+        assert!(builder.validate().is_err());
+
+        let mut builder = VectorStoreBuilder::default();
+        builder.config.tls_key_path = Some("key.pem".into());
+        assert!(builder.validate().is_err());
+
+        let builder = VectorStoreBuilder::default().tls("cert.pem", "key.pem");
+        assert!(builder.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_mtls_requires_tls_and_separate_address() {
+        let builder = VectorStoreBuilder::default().mtls_ca_cert("ca.pem");
+        assert!(builder.validate().is_err());
+
+        let addr: SocketAddr = "127.0.0.1:6080".parse().unwrap();
+        let builder = VectorStoreBuilder::default()
+            .tls("cert.pem", "key.pem")
+            .mtls_ca_cert("ca.pem")
+            .addr(addr)
+            .mtls_addr(addr);
+        assert!(builder.validate().is_err());
+
+        let builder = VectorStoreBuilder::default()
+            .tls("cert.pem", "key.pem")
+            .mtls_ca_cert("ca.pem");
+        assert!(builder.validate().is_ok());
+    }
+
+    #[test]
+    #[cfg(not(feature = "opensearch"))]
+    fn validate_opensearch_not_compiled_in() {
+        let mut builder = VectorStoreBuilder::default();
+        builder.config.opensearch_addr = Some("http://127.0.0.1:9200".into());
+        assert!(builder.validate().is_err());
+    }
+
+    #[test]
+    fn validate_exclusive_index_backends() {
+        let mut builder = VectorStoreBuilder::default();
+        builder.config.opensearch_addr = Some("http://127.0.0.1:9200".into());
+        builder.config.use_diskann = true;
+        assert!(builder.validate().is_err());
+
+        let mut builder = VectorStoreBuilder::default();
+        builder.config.faiss_index = Some("HNSW32".into());
+        builder.config.use_diskann = true;
+        assert!(builder.validate().is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "faiss"))]
+    fn validate_faiss_not_compiled_in() {
+        let mut builder = VectorStoreBuilder::default();
+        builder.config.faiss_index = Some("HNSW32".into());
+        assert!(builder.validate().is_err());
+    }
+}
@@ -0,0 +1,1169 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Distribution of indexes across a fleet of vector-store nodes.
+//!
+//! All nodes of a cluster share the same membership list and pick the owner of an index with
+//! rendezvous hashing: every node gets a weight for the index and the heaviest one wins. The nodes
+//! agree on the owners without coordination, and adding a node moves only the indexes it wins.
+//!
+//! With a replication factor R the R heaviest nodes serve the index. The heaviest one is the
+//! primary replica. Indexes live only in memory and cannot be serialized, so there is nothing a
+//! replica could copy from the primary: every replica scans the table and reads CDC on its own.
+//!
+//! The configured nodes are seeds. Every probe interval a node increments its heartbeat and
+//! exchanges the members it knows with a random peer at [`GOSSIP_PATH`], keeping the newer state
+//! of every member. A node started later learns the cluster from its seeds and the cluster learns
+//! about it the same way. A node shutting down announces that it leaves to all its peers, which
+//! forget it after [`LEFT_RETENTION_ROUNDS`] probe intervals.
+//!
+//! The nodes send the peer token with all requests to each other. Gossip is accepted only with
+//! the peer token, so a node without one neither sends nor accepts gossip and its cluster is made
+//! of the configured nodes only.
+//!
+//! Each node probes the status endpoint of its peers. A peer failing several probes in a row is
+//! left out of the membership until it answers again, so its indexes move to the remaining nodes
+//! and come back when it rejoins. A node which crashed without leaving stays known and is probed
+//! until it comes back.
+
+use crate::Config;
+use crate::IndexKey;
+use crate::http_auth;
+use crate::serde_duration;
+use anyhow::bail;
+use futures::future;
+use secrecy::SecretString;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::btree_map::Entry;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+use thiserror::Error;
+use tokio::sync::watch;
+use tokio::time;
+use tokio::time::Interval;
+use tokio::time::MissedTickBehavior;
+use tracing::Instrument;
+use tracing::debug;
+use tracing::debug_span;
+use tracing::info;
+use tracing::warn;
+
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    derive_more::AsRef,
+    derive_more::Display,
+    derive_more::From,
+)]
+#[serde(transparent)]
+#[from(String, &str)]
+/// Identifier of a vector-store node in a cluster.
+pub struct NodeId(String);
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClusterNode {
+    pub id: NodeId,
+    /// Base URL of the node HTTP API, e.g. `http://10.0.0.1:6080`.
+    pub url: String,
+    /// Availability zone of the node.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>,
+}
+
+/// Clustering mode settings. Without them the node builds every discovered index.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClusterConfig {
+    /// Identifier of this node, which must be listed in `nodes`.
+    pub node_id: NodeId,
+    pub nodes: Vec<ClusterNode>,
+    /// Number of nodes serving each index. Replicas do not copy indexes from each other: each one
+    /// scans the table and reads CDC on its own, so the load on ScyllaDB grows with the factor.
+    #[serde(default = "default_replication_factor")]
+    pub replication_factor: NonZeroUsize,
+    /// How often the peers are probed.
+    #[serde(default = "default_probe_interval", with = "serde_duration")]
+    pub probe_interval: Duration,
+    /// Number of failed probes in a row after which a peer is considered down.
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: NonZeroUsize,
+    /// How long a replica may take to answer a forwarded query before the next one is tried.
+    #[serde(default = "default_forward_timeout", with = "serde_duration")]
+    pub forward_timeout: Duration,
+}
+
+fn default_replication_factor() -> NonZeroUsize {
+    NonZeroUsize::MIN
+}
+
+pub(crate) fn default_probe_interval() -> Duration {
+    Duration::from_secs(1)
+}
+
+pub(crate) fn default_failure_threshold() -> NonZeroUsize {
+    NonZeroUsize::new(3).unwrap()
+}
+
+pub(crate) fn default_forward_timeout() -> Duration {
+    Duration::from_secs(5)
+}
+
+impl ClusterConfig {
+    pub(crate) fn validate(&self) -> anyhow::Result<()> {
+        let mut ids = HashSet::new();
+        for node in &self.nodes {
+            if !ids.insert(&node.id) {
+                bail!("cluster node {} is listed more than once", node.id);
+            }
+            if !node.url.starts_with("http://") && !node.url.starts_with("https://") {
+                bail!(
+                    "cluster node {} URL {} is not an HTTP URL",
+                    node.id,
+                    node.url
+                );
+            }
+        }
+        if self.replication_factor.get() > self.nodes.len() {
+            bail!(
+                "cluster replication factor {} is greater than the number of nodes {}",
+                self.replication_factor,
+                self.nodes.len()
+            );
+        }
+        if !ids.contains(&self.node_id) {
+            bail!(
+                "cluster node id {} is not in the cluster nodes",
+                self.node_id
+            );
+        }
+        if self.probe_interval.is_zero() {
+            bail!("cluster probe interval must be greater than zero");
+        }
+        if self.forward_timeout.is_zero() {
+            bail!("cluster forward timeout must be greater than zero");
+        }
+        Ok(())
+    }
+}
+
+/// The nodes of the cluster as seen by the local node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Membership {
+    local: NodeId,
+    nodes: Vec<ClusterNode>,
+    replication_factor: NonZeroUsize,
+}
+
+impl Membership {
+    pub fn new(
+        local: NodeId,
+        mut nodes: Vec<ClusterNode>,
+        replication_factor: NonZeroUsize,
+    ) -> Self {
+        nodes.sort_by(|a, b| a.id.cmp(&b.id));
+        Self {
+            local,
+            nodes,
+            replication_factor,
+        }
+    }
+
+    pub fn local(&self) -> &NodeId {
+        &self.local
+    }
+
+    pub fn nodes(&self) -> &[ClusterNode] {
+        &self.nodes
+    }
+
+    /// Returns the nodes ordered by their preference to own the index.
+    pub fn ranked(&self, key: &IndexKey) -> Vec<&ClusterNode> {
+        let mut nodes: Vec<_> = self.nodes.iter().collect();
+        nodes.sort_by_cached_key(|node| std::cmp::Reverse(weight(&node.id, key)));
+        nodes
+    }
+
+    /// Returns the nodes serving the index, the primary replica first.
+    pub fn replicas(&self, key: &IndexKey) -> Vec<&ClusterNode> {
+        let mut nodes = self.ranked(key);
+        nodes.truncate(self.replication_factor.get());
+        nodes
+    }
+
+    /// Returns the replicas of the index in the zone of the local node first, otherwise in the
+    /// order of [`Self::replicas`].
+    pub fn preferred_replicas(&self, key: &IndexKey) -> Vec<&ClusterNode> {
+        let mut nodes = self.replicas(key);
+        if let Some(zone) = self.zone() {
+            nodes.sort_by_key(|node| node.zone.as_deref() != Some(zone));
+        }
+        nodes
+    }
+
+    /// Availability zone of the local node.
+    pub fn zone(&self) -> Option<&str> {
+        self.nodes
+            .iter()
+            .find(|node| node.id == self.local)
+            .and_then(|node| node.zone.as_deref())
+    }
+
+    pub fn primary(&self, key: &IndexKey) -> Option<&ClusterNode> {
+        self.nodes.iter().max_by_key(|node| weight(&node.id, key))
+    }
+
+    /// Checks if the local node is one of the replicas of the index.
+    pub fn owns(&self, key: &IndexKey) -> bool {
+        self.replicas(key).iter().any(|node| node.id == self.local)
+    }
+}
+
+/// The endpoint exchanging the known members between nodes.
+pub(crate) const GOSSIP_PATH: &str = "/api/v1/cluster/gossip";
+
+/// How long a leaving node waits for each peer to take the announcement.
+const LEAVE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Number of probe intervals a member which left is remembered, so that the older states of it
+/// still gossiped by the peers do not bring it back.
+const LEFT_RETENTION_ROUNDS: u32 = 60;
+
+/// Why a gossip exchange is refused.
+#[derive(Debug, Error)]
+pub(crate) enum GossipError {
+    #[error("clustering is not configured")]
+    Disabled,
+    #[error("gossip requires the peer token")]
+    Unauthorized,
+}
+
+/// A node of the cluster with the version of its state, as exchanged by gossip.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Member {
+    pub(crate) node: ClusterNode,
+    /// Start time of the node in milliseconds, so that a restarted node replaces its previous
+    /// state. Zero for seeds not heard from yet.
+    pub(crate) generation: u64,
+    /// Incremented by the node itself on every gossip round.
+    pub(crate) heartbeat: u64,
+    /// Set by the node when it shuts down.
+    #[serde(default)]
+    pub(crate) left: bool,
+}
+
+impl Member {
+    fn version(&self) -> (u64, u64) {
+        (self.generation, self.heartbeat)
+    }
+}
+
+/// Members of the cluster known by the local node.
+struct Members {
+    local: NodeId,
+    members: BTreeMap<NodeId, Member>,
+    /// When the members which left were first seen leaving.
+    left_since: HashMap<NodeId, Instant>,
+    peer_token: Option<SecretString>,
+}
+
+impl Members {
+    fn new(cluster: &ClusterConfig) -> Self {
+        let generation = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |since| since.as_millis() as u64);
+        let mut members = Self {
+            local: cluster.node_id.clone(),
+            members: BTreeMap::new(),
+            left_since: HashMap::new(),
+            peer_token: None,
+        };
+        for node in &cluster.nodes {
+            members.members.insert(
+                node.id.clone(),
+                Member {
+                    node: node.clone(),
+                    generation: if node.id == cluster.node_id {
+                        generation
+                    } else {
+                        0
+                    },
+                    heartbeat: 0,
+                    left: false,
+                },
+            );
+        }
+        members
+    }
+
+    /// Adds new seeds and updates the local node. Nodes removed from the configuration stay
+    /// known until they leave.
+    fn configure(&mut self, cluster: &ClusterConfig) {
+        for node in &cluster.nodes {
+            match self.members.entry(node.id.clone()) {
+                Entry::Occupied(mut entry) => {
+                    if node.id == self.local && entry.get().node != *node {
+                        let member = entry.get_mut();
+                        member.node = node.clone();
+                        member.heartbeat += 1;
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(Member {
+                        node: node.clone(),
+                        generation: 0,
+                        heartbeat: 0,
+                        left: false,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Keeps the newer state of every member. Only the local node changes its own state.
+    fn merge(&mut self, members: Vec<Member>) {
+        let now = Instant::now();
+        for member in members {
+            if member.node.id == self.local {
+                continue;
+            }
+            let id = member.node.id.clone();
+            let left = member.left;
+            match self.members.entry(id.clone()) {
+                Entry::Occupied(mut entry) => {
+                    if member.version() <= entry.get().version() {
+                        continue;
+                    }
+                    if left && !entry.get().left {
+                        info!("cluster node {id} left");
+                    }
+                    entry.insert(member);
+                }
+                Entry::Vacant(entry) => {
+                    if !left {
+                        info!("cluster node {id} joined");
+                    }
+                    entry.insert(member);
+                }
+            }
+            if left {
+                self.left_since.entry(id).or_insert(now);
+            } else {
+                self.left_since.remove(&id);
+            }
+        }
+    }
+
+    /// Forgets the members which left at least `retention` before `now`.
+    fn expire(&mut self, now: Instant, retention: Duration) {
+        self.left_since.retain(|id, since| {
+            if now.saturating_duration_since(*since) < retention {
+                return true;
+            }
+            debug!("forgetting cluster node {id} which left");
+            self.members.remove(id);
+            false
+        });
+    }
+
+    fn local_mut(&mut self) -> &mut Member {
+        self.members
+            .get_mut(&self.local)
+            .expect("the local node should be a member")
+    }
+
+    fn list(&self) -> Vec<Member> {
+        self.members.values().cloned().collect()
+    }
+
+    /// Returns the peers which did not leave.
+    fn peers(&self) -> Vec<ClusterNode> {
+        self.members
+            .values()
+            .filter(|member| member.node.id != self.local && !member.left)
+            .map(|member| member.node.clone())
+            .collect()
+    }
+}
+
+/// The members known by the local node, shared by the cluster task and the gossip endpoint.
+#[derive(Clone, Default)]
+pub(crate) struct Gossip(Arc<Mutex<Option<Members>>>);
+
+impl Gossip {
+    /// Merges the members known by a peer, authenticated by the bearer `token`, and returns the
+    /// members known by the local node.
+    pub(crate) fn exchange(
+        &self,
+        token: Option<&str>,
+        members: Vec<Member>,
+    ) -> Result<Vec<Member>, GossipError> {
+        let mut guard = self.0.lock().unwrap();
+        let local = guard.as_mut().ok_or(GossipError::Disabled)?;
+        let is_peer = local
+            .peer_token
+            .as_ref()
+            .zip(token)
+            .is_some_and(|(peer_token, token)| http_auth::is_token(peer_token, token));
+        if !is_peer {
+            return Err(GossipError::Unauthorized);
+        }
+        local.merge(members);
+        Ok(local.list())
+    }
+
+    /// Announces to all peers that the local node leaves the cluster.
+    pub(crate) async fn leave(&self) {
+        let mut peer_token = None;
+        let Some((members, peers)) = self.update(|local| {
+            let member = local.local_mut();
+            member.left = true;
+            member.heartbeat += 1;
+            peer_token = local.peer_token.clone();
+        }) else {
+            return;
+        };
+        let Some(peer_token) = peer_token else {
+            return;
+        };
+        info!("leaving the cluster");
+        let client = reqwest::Client::new();
+        future::join_all(
+            peers
+                .iter()
+                .map(|peer| exchange(&client, peer, &members, LEAVE_TIMEOUT, &peer_token)),
+        )
+        .await;
+    }
+
+    fn configure(&self, config: &Config) {
+        let mut guard = self.0.lock().unwrap();
+        match (&config.cluster, guard.as_mut()) {
+            (None, _) => *guard = None,
+            (Some(cluster), Some(local)) if local.local == cluster.node_id => {
+                local.configure(cluster)
+            }
+            (Some(cluster), _) => *guard = Some(Members::new(cluster)),
+        }
+        if let Some(cluster) = &config.cluster
+            && cluster.replication_factor.get() > 1
+        {
+            info!(
+                "each of the {} replicas of an index scans ScyllaDB on its own",
+                cluster.replication_factor
+            );
+        }
+        if let Some(local) = guard.as_mut() {
+            local.peer_token = peer_token(config).cloned();
+            if local.peer_token.is_none() {
+                warn!("cluster gossip is disabled without a peer token");
+            }
+        }
+    }
+
+    /// Applies the change to the local members and returns all members and the peers.
+    fn update(&self, change: impl FnOnce(&mut Members)) -> Option<(Vec<Member>, Vec<ClusterNode>)> {
+        let mut guard = self.0.lock().unwrap();
+        let local = guard.as_mut()?;
+        change(local);
+        Some((local.list(), local.peers()))
+    }
+
+    fn merge(&self, members: Vec<Member>) {
+        if let Some(local) = self.0.lock().unwrap().as_mut() {
+            local.merge(members);
+        }
+    }
+
+    fn members(&self) -> Vec<Member> {
+        self.0
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(Members::list)
+            .unwrap_or_default()
+    }
+}
+
+async fn exchange(
+    client: &reqwest::Client,
+    peer: &ClusterNode,
+    members: &[Member],
+    timeout: Duration,
+    peer_token: &SecretString,
+) -> reqwest::Result<Vec<Member>> {
+    http_auth::to_peer(
+        client.post(format!("{}{GOSSIP_PATH}", peer.url)),
+        Some(peer_token),
+    )
+    .timeout(timeout)
+    .json(members)
+    .send()
+    .await?
+    .error_for_status()?
+    .json()
+    .await
+}
+
+/// A weight of the node for the index, stable across processes and releases.
+fn weight(node: &NodeId, key: &IndexKey) -> u64 {
+    // FNV-1a followed by the splitmix64 finalizer to spread similar inputs.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    let bytes = node.0.bytes().chain([0xff]).chain(key.as_ref().bytes());
+    for byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash ^= hash >> 30;
+    hash = hash.wrapping_mul(0xbf58476d1ce4e5b9);
+    hash ^= hash >> 27;
+    hash = hash.wrapping_mul(0x94d049bb133111eb);
+    hash ^ (hash >> 31)
+}
+
+/// Tracks the liveness of the peers by probing their status endpoint.
+struct FailureDetector {
+    client: reqwest::Client,
+    failures: HashMap<NodeId, usize>,
+}
+
+impl FailureDetector {
+    fn new() -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            failures: HashMap::new(),
+        }
+    }
+
+    /// Probes the peers once. Peers are considered alive until they fail enough probes.
+    async fn probe(
+        &mut self,
+        peers: &[ClusterNode],
+        cluster: &ClusterConfig,
+        peer_token: Option<&SecretString>,
+    ) {
+        let client = &self.client;
+        let probes = peers.iter().map(|node| async move {
+            let result = http_auth::to_peer(
+                client.get(format!("{}/api/v1/status", node.url)),
+                peer_token,
+            )
+            .timeout(cluster.probe_interval)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+            (node, result)
+        });
+        for (node, result) in future::join_all(probes).await {
+            let failures = self.failures.entry(node.id.clone()).or_default();
+            match result {
+                Ok(_) => {
+                    if *failures >= cluster.failure_threshold.get() {
+                        info!("cluster node {} is up", node.id);
+                    }
+                    *failures = 0;
+                }
+                Err(err) => {
+                    *failures += 1;
+                    if *failures == cluster.failure_threshold.get() {
+                        warn!("cluster node {} is down: {err}", node.id);
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_alive(&self, node: &NodeId, cluster: &ClusterConfig) -> bool {
+        self.failures.get(node).copied().unwrap_or_default() < cluster.failure_threshold.get()
+    }
+}
+
+/// Probes the peers and exchanges the members with one of them, preferably a live one.
+async fn round(
+    detector: &mut FailureDetector,
+    gossip: &Gossip,
+    cluster: &ClusterConfig,
+    peer_token: Option<&SecretString>,
+) {
+    let retention = cluster.probe_interval * LEFT_RETENTION_ROUNDS;
+    let Some((members, peers)) = gossip.update(|local| {
+        local.local_mut().heartbeat += 1;
+        local.expire(Instant::now(), retention);
+    }) else {
+        return;
+    };
+    let alive: Vec<_> = peers
+        .iter()
+        .filter(|peer| detector.is_alive(&peer.id, cluster))
+        .collect();
+    let candidates = if alive.is_empty() {
+        peers.iter().collect()
+    } else {
+        alive
+    };
+    let target =
+        (!candidates.is_empty()).then(|| candidates[rand::random_range(0..candidates.len())]);
+    let client = detector.client.clone();
+    let exchanged = async {
+        let peer = target?;
+        // The peers refuse gossip without the peer token.
+        let peer_token = peer_token?;
+        exchange(&client, peer, &members, cluster.probe_interval, peer_token)
+            .await
+            .inspect_err(|err| debug!("unable to gossip with cluster node {}: {err}", peer.id))
+            .ok()
+    };
+    let ((), exchanged) = tokio::join!(detector.probe(&peers, cluster, peer_token), exchanged);
+    if let Some(members) = exchanged {
+        gossip.merge(members);
+    }
+}
+
+fn membership(
+    config: &Config,
+    detector: &FailureDetector,
+    gossip: &Gossip,
+) -> Option<Arc<Membership>> {
+    config.cluster.as_ref().map(|cluster| {
+        let nodes = gossip
+            .members()
+            .into_iter()
+            .filter(|member| {
+                member.node.id == cluster.node_id
+                    || (!member.left && detector.is_alive(&member.node.id, cluster))
+            })
+            .map(|member| member.node)
+            .collect();
+        Arc::new(Membership::new(
+            cluster.node_id.clone(),
+            nodes,
+            cluster.replication_factor,
+        ))
+    })
+}
+
+/// Returns the token the node authenticates with to its peers.
+fn peer_token(config: &Config) -> Option<&SecretString> {
+    config.auth.as_ref()?.peer_token.as_ref()
+}
+
+fn probe_interval(config: &Config) -> Option<Interval> {
+    config.cluster.as_ref().map(|cluster| {
+        let mut interval = time::interval(cluster.probe_interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        interval
+    })
+}
+
+async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => future::pending().await,
+    }
+}
+
+fn publish(
+    tx: &watch::Sender<Option<Arc<Membership>>>,
+    config: &Config,
+    detector: &FailureDetector,
+    gossip: &Gossip,
+) {
+    let membership = membership(config, detector, gossip);
+    tx.send_if_modified(|current| {
+        if *current == membership {
+            return false;
+        }
+        info!("cluster membership changed to {membership:?}");
+        *current = membership;
+        true
+    });
+}
+
+/// Publishes the cluster membership, or `None` when clustering is disabled. The returned
+/// [`Gossip`] serves the gossip endpoint.
+pub(crate) fn new(
+    mut config_rx: watch::Receiver<Arc<Config>>,
+) -> (watch::Receiver<Option<Arc<Membership>>>, Gossip) {
+    let mut detector = FailureDetector::new();
+    let mut config = config_rx.borrow_and_update().clone();
+    let mut interval = probe_interval(&config);
+    let gossip = Gossip::default();
+    gossip.configure(&config);
+    let (tx, rx) = watch::channel(membership(&config, &detector, &gossip));
+    tokio::spawn({
+        let gossip = gossip.clone();
+        async move {
+            loop {
+                tokio::select! {
+                    changed = config_rx.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                        let new_config = config_rx.borrow_and_update().clone();
+                        if new_config.cluster != config.cluster {
+                            interval = probe_interval(&new_config);
+                        }
+                        config = new_config;
+                        gossip.configure(&config);
+                        publish(&tx, &config, &detector, &gossip);
+                    }
+                    _ = tick(&mut interval) => {
+                        if let Some(cluster) = &config.cluster {
+                            round(&mut detector, &gossip, cluster, peer_token(&config)).await;
+                        }
+                        publish(&tx, &config, &detector, &gossip);
+                    }
+                    _ = tx.closed() => break,
+                }
+            }
+        }
+        .instrument(debug_span!("cluster"))
+    });
+    (rx, gossip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AuthConfig;
+    use crate::IndexName;
+    use crate::KeyspaceName;
+    use axum::Json;
+    use axum::Router;
+    use axum::http::HeaderMap;
+    use axum::http::StatusCode;
+    use axum::routing::get;
+    use axum::routing::post;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+    use tokio::net::TcpListener;
+
+    fn nodes(count: usize) -> Vec<ClusterNode> {
+        (0..count)
+            .map(|i| ClusterNode {
+                id: format!("node{i}").into(),
+                url: format!("http://10.0.0.{i}:6080"),
+                zone: None,
+            })
+            .collect()
+    }
+
+    fn keys(count: usize) -> Vec<IndexKey> {
+        (0..count)
+            .map(|i| {
+                IndexKey::new(
+                    &KeyspaceName("ks".to_string()),
+                    &IndexName(format!("idx{i}")),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn all_nodes_agree_on_a_single_owner() {
+        let nodes = nodes(3);
+        let memberships: Vec<_> = nodes
+            .iter()
+            .map(|node| {
+                Membership::new(
+                    node.id.clone(),
+                    nodes.iter().rev().cloned().collect(),
+                    NonZeroUsize::MIN,
+                )
+            })
+            .collect();
+
+        for key in keys(100) {
+            let owners = memberships.iter().filter(|m| m.owns(&key)).count();
+            assert_eq!(owners, 1, "index {key} should have exactly one owner");
+            assert_eq!(
+                memberships[0].primary(&key),
+                memberships[0].ranked(&key).first().copied()
+            );
+        }
+    }
+
+    #[test]
+    fn each_index_is_served_by_replication_factor_nodes() {
+        let nodes = nodes(5);
+        let replication_factor = NonZeroUsize::new(3).unwrap();
+        let memberships: Vec<_> = nodes
+            .iter()
+            .map(|node| Membership::new(node.id.clone(), nodes.clone(), replication_factor))
+            .collect();
+
+        for key in keys(100) {
+            let owners: Vec<_> = memberships
+                .iter()
+                .filter(|m| m.owns(&key))
+                .map(|m| m.local().clone())
+                .collect();
+            assert_eq!(owners.len(), 3);
+            let replicas = memberships[0].replicas(&key);
+            assert_eq!(replicas[0], memberships[0].primary(&key).unwrap());
+            assert!(replicas.iter().all(|node| owners.contains(&node.id)));
+        }
+    }
+
+    #[test]
+    fn indexes_are_spread_and_adding_a_node_moves_only_its_indexes() {
+        let before = Membership::new("node0".into(), nodes(3), NonZeroUsize::MIN);
+        let after = Membership::new("node0".into(), nodes(4), NonZeroUsize::MIN);
+        let keys = keys(1000);
+
+        let mut counts = HashMap::new();
+        for key in &keys {
+            *counts.entry(&before.primary(key).unwrap().id).or_insert(0) += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        assert!(counts.values().all(|&count| count > 250));
+
+        for key in &keys {
+            let new_owner = &after.primary(key).unwrap().id;
+            if new_owner != &before.primary(key).unwrap().id {
+                assert_eq!(new_owner.as_ref(), "node3");
+            }
+        }
+    }
+
+    #[test]
+    fn replicas_in_the_local_zone_are_preferred() {
+        let mut nodes = nodes(4);
+        for (i, node) in nodes.iter_mut().enumerate() {
+            node.zone = Some(if i % 2 == 0 { "zone-a" } else { "zone-b" }.to_string());
+        }
+        let replication_factor = NonZeroUsize::new(3).unwrap();
+        let zoned = Membership::new("node0".into(), nodes.clone(), replication_factor);
+        for node in &mut nodes {
+            node.zone = None;
+        }
+        let unzoned = Membership::new("node0".into(), nodes, replication_factor);
+
+        assert_eq!(zoned.zone(), Some("zone-a"));
+        assert_eq!(unzoned.zone(), None);
+        for key in keys(100) {
+            let preferred = zoned.preferred_replicas(&key);
+            let same_zone = preferred
+                .iter()
+                .take_while(|node| node.zone.as_deref() == Some("zone-a"))
+                .count();
+            assert!(
+                preferred[same_zone..]
+                    .iter()
+                    .all(|node| node.zone.as_deref() == Some("zone-b"))
+            );
+            let ids = |nodes: Vec<&ClusterNode>| -> HashSet<_> {
+                nodes.into_iter().map(|node| node.id.clone()).collect()
+            };
+            assert_eq!(ids(preferred), ids(zoned.replicas(&key)));
+            assert_eq!(unzoned.preferred_replicas(&key), unzoned.replicas(&key));
+        }
+    }
+
+    #[test]
+    fn validate_cluster_config() {
+        let config = ClusterConfig {
+            node_id: "node1".into(),
+            nodes: nodes(2),
+            replication_factor: NonZeroUsize::new(2).unwrap(),
+            probe_interval: default_probe_interval(),
+            failure_threshold: default_failure_threshold(),
+            forward_timeout: default_forward_timeout(),
+        };
+        assert!(config.validate().is_ok());
+
+        let no_probe_interval = ClusterConfig {
+            probe_interval: Duration::ZERO,
+            ..config.clone()
+        };
+        assert!(no_probe_interval.validate().is_err());
+
+        let no_forward_timeout = ClusterConfig {
+            forward_timeout: Duration::ZERO,
+            ..config.clone()
+        };
+        assert!(no_forward_timeout.validate().is_err());
+
+        let too_many_replicas = ClusterConfig {
+            replication_factor: NonZeroUsize::new(3).unwrap(),
+            ..config.clone()
+        };
+        assert!(too_many_replicas.validate().is_err());
+
+        let unknown = ClusterConfig {
+            node_id: "node5".into(),
+            ..config.clone()
+        };
+        assert!(unknown.validate().is_err());
+
+        let mut duplicated = config.clone();
+        duplicated.nodes.push(duplicated.nodes[0].clone());
+        assert!(duplicated.validate().is_err());
+
+        let mut bad_url = config;
+        bad_url.nodes[0].url = "10.0.0.1:6080".to_string();
+        assert!(bad_url.validate().is_err());
+    }
+
+    fn cluster_config(node_id: &str, nodes: Vec<ClusterNode>) -> ClusterConfig {
+        ClusterConfig {
+            node_id: node_id.into(),
+            nodes,
+            replication_factor: NonZeroUsize::MIN,
+            probe_interval: Duration::from_millis(10),
+            failure_threshold: NonZeroUsize::new(2).unwrap(),
+            forward_timeout: default_forward_timeout(),
+        }
+    }
+
+    fn member(id: &str, generation: u64, heartbeat: u64, left: bool) -> Member {
+        Member {
+            node: ClusterNode {
+                id: id.into(),
+                url: format!("http://{id}:6080"),
+                zone: None,
+            },
+            generation,
+            heartbeat,
+            left,
+        }
+    }
+
+    #[test]
+    fn newer_member_states_replace_older_ones() {
+        let mut members = Members::new(&cluster_config("node0", nodes(2)));
+        let local = members.members[&NodeId::from("node0")].clone();
+
+        members.merge(vec![
+            member("node0", u64::MAX, 1, true),
+            member("node1", 5, 2, false),
+            member("node2", 5, 1, false),
+        ]);
+        assert_eq!(members.members[&NodeId::from("node0")], local);
+        assert_eq!(
+            members.members[&NodeId::from("node1")],
+            member("node1", 5, 2, false)
+        );
+        assert_eq!(
+            members.members[&NodeId::from("node2")],
+            member("node2", 5, 1, false)
+        );
+
+        members.merge(vec![
+            member("node1", 5, 1, true),
+            member("node2", 5, 2, true),
+        ]);
+        assert!(!members.members[&NodeId::from("node1")].left);
+        assert!(members.members[&NodeId::from("node2")].left);
+        let peers: Vec<_> = members.peers().into_iter().map(|node| node.id).collect();
+        assert_eq!(peers, vec![NodeId::from("node1")]);
+
+        members.merge(vec![member("node2", 6, 0, false)]);
+        assert_eq!(members.peers().len(), 2);
+    }
+
+    #[test]
+    fn members_which_left_are_forgotten_after_the_retention() {
+        let mut members = Members::new(&cluster_config("node0", nodes(1)));
+        let retention = Duration::from_secs(60);
+        members.merge(vec![
+            member("node1", 5, 1, false),
+            member("node2", 5, 1, true),
+        ]);
+
+        members.expire(Instant::now(), retention);
+        members.merge(vec![member("node2", 5, 0, false)]);
+        assert!(members.members[&NodeId::from("node2")].left);
+
+        members.expire(Instant::now() + retention, retention);
+        let ids: Vec<_> = members
+            .members
+            .keys()
+            .map(|id| id.as_ref().as_str())
+            .collect();
+        assert_eq!(ids, vec!["node0", "node1"]);
+
+        members.merge(vec![member("node1", 5, 2, true)]);
+        members.merge(vec![member("node1", 6, 0, false)]);
+        members.expire(Instant::now() + retention, retention);
+        assert!(members.members.contains_key(&NodeId::from("node1")));
+    }
+
+    #[test]
+    fn gossip_is_accepted_only_with_the_peer_token() {
+        let gossip = Gossip::default();
+        assert!(matches!(
+            gossip.exchange(Some("peer"), Vec::new()),
+            Err(GossipError::Disabled)
+        ));
+
+        let mut config = Config {
+            cluster: Some(cluster_config("node0", nodes(1))),
+            ..Default::default()
+        };
+        gossip.configure(&config);
+        assert!(matches!(
+            gossip.exchange(Some("peer"), vec![member("node1", 5, 1, false)]),
+            Err(GossipError::Unauthorized)
+        ));
+
+        config.auth = Some(AuthConfig {
+            tokens: vec!["client".into()],
+            peer_token: Some("peer".into()),
+            ..Default::default()
+        });
+        gossip.configure(&config);
+        for token in [None, Some("client"), Some("other")] {
+            assert!(matches!(
+                gossip.exchange(token, vec![member("node1", 5, 1, false)]),
+                Err(GossipError::Unauthorized)
+            ));
+        }
+        assert_eq!(gossip.members().len(), 1);
+
+        let members = gossip
+            .exchange(Some("peer"), vec![member("node1", 5, 1, false)])
+            .unwrap();
+        assert_eq!(members.len(), 2);
+    }
+
+    async fn wait_for_nodes(rx: &mut watch::Receiver<Option<Arc<Membership>>>, ids: &[&str]) {
+        time::timeout(
+            Duration::from_secs(5),
+            rx.wait_for(|membership| {
+                let nodes = membership.as_ref().unwrap().nodes();
+                nodes
+                    .iter()
+                    .map(|node| node.id.as_ref().as_str())
+                    .eq(ids.iter().copied())
+            }),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn peers_failing_probes_are_left_out_until_they_answer() {
+        let up = Arc::new(AtomicBool::new(true));
+        let router = Router::new().route(
+            "/api/v1/status",
+            get({
+                let up = Arc::clone(&up);
+                move || {
+                    let status = if up.load(Ordering::Relaxed) {
+                        StatusCode::OK
+                    } else {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    };
+                    async move { status }
+                }
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let mut nodes = nodes(3);
+        nodes[1].url = format!("http://{addr}");
+        nodes[2].url = "http://127.0.0.1:1".to_string();
+        let config = Config {
+            cluster: Some(ClusterConfig {
+                node_id: "node0".into(),
+                nodes,
+                replication_factor: NonZeroUsize::MIN,
+                probe_interval: Duration::from_millis(10),
+                failure_threshold: NonZeroUsize::new(2).unwrap(),
+                forward_timeout: default_forward_timeout(),
+            }),
+            ..Default::default()
+        };
+        let (_config_tx, config_rx) = watch::channel(Arc::new(config));
+        let (mut membership_rx, _gossip) = new(config_rx);
+        assert_eq!(membership_rx.borrow().as_ref().unwrap().nodes().len(), 3);
+
+        wait_for_nodes(&mut membership_rx, &["node0", "node1"]).await;
+
+        up.store(false, Ordering::Relaxed);
+        wait_for_nodes(&mut membership_rx, &["node0"]).await;
+
+        up.store(true, Ordering::Relaxed);
+        wait_for_nodes(&mut membership_rx, &["node0", "node1"]).await;
+    }
+
+    #[tokio::test]
+    async fn nodes_learn_joining_and_leaving_peers_by_gossip() {
+        let listeners = [
+            TcpListener::bind("127.0.0.1:0").await.unwrap(),
+            TcpListener::bind("127.0.0.1:0").await.unwrap(),
+        ];
+        let nodes: Vec<_> = listeners
+            .iter()
+            .enumerate()
+            .map(|(i, listener)| ClusterNode {
+                id: format!("node{i}").into(),
+                url: format!("http://{}", listener.local_addr().unwrap()),
+                zone: None,
+            })
+            .collect();
+
+        // node0 knows only itself and learns about node1 when node1 joins with node0 as a seed.
+        let configs = [
+            cluster_config("node0", vec![nodes[0].clone()]),
+            cluster_config("node1", nodes.clone()),
+        ];
+        let mut started = Vec::new();
+        for (listener, cluster) in listeners.into_iter().zip(configs) {
+            let config = Config {
+                cluster: Some(cluster),
+                auth: Some(AuthConfig {
+                    peer_token: Some("peer".into()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            let (config_tx, config_rx) = watch::channel(Arc::new(config));
+            let (membership_rx, gossip) = new(config_rx);
+            let router = Router::new()
+                .route("/api/v1/status", get(|| async { StatusCode::OK }))
+                .route(
+                    GOSSIP_PATH,
+                    post({
+                        let gossip = gossip.clone();
+                        move |headers: HeaderMap, Json(members): Json<Vec<Member>>| {
+                            let members = gossip
+                                .exchange(http_auth::bearer(&headers), members)
+                                .unwrap();
+                            async move { Json(members) }
+                        }
+                    }),
+                );
+            tokio::spawn(async move { axum::serve(listener, router).await });
+            started.push((config_tx, membership_rx, gossip));
+        }
+
+        wait_for_nodes(&mut started[0].1, &["node0", "node1"]).await;
+        wait_for_nodes(&mut started[1].1, &["node0", "node1"]).await;
+
+        started[1].2.leave().await;
+        wait_for_nodes(&mut started[0].1, &["node0"]).await;
+        assert_eq!(started[1].1.borrow().as_ref().unwrap().nodes().len(), 2);
+    }
+}
@@ -3,18 +3,36 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
+use crate::AuthConfig;
+use crate::ClusterConfig;
+use crate::ClusterNode;
 use crate::Config;
+use crate::ConsulConfig;
 use crate::Credentials;
 use crate::DiskannAlpha;
+use crate::EmbeddingConfig;
+use crate::EmbeddingProvider;
+use crate::HttpTuning;
+use crate::IndexLimits;
+use crate::KeyspacePartition;
+use crate::RerankConfig;
+use crate::RerankProvider;
+use crate::cluster;
+use crate::embedding;
 use crate::file_monitor::TlsFilesMonitor;
+use crate::http_auth::Authenticator;
+use crate::rerank;
 use crate::tls;
 use crate::tls::TlsServerConfig;
 use anyhow::anyhow;
 use anyhow::bail;
 use itertools::Itertools;
 use secrecy::ExposeSecret;
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::net::ToSocketAddrs;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::watch;
@@ -41,16 +59,33 @@ fn tls_file_check_interval(env: &impl Fn(&str) -> anyhow::Result<String>) -> Dur
 #[derive(Clone, PartialEq)]
 pub struct HttpServerConfig {
     pub addr: SocketAddr,
+    /// Addresses the server listens on in addition to `addr`.
+    pub extra_addrs: Vec<SocketAddr>,
     pub tls: Option<TlsServerConfig>,
+    pub tuning: HttpTuning,
+    /// Authentication of the requests, which are not authenticated when not set.
+    pub auth: Option<Authenticator>,
 }
 
 impl HttpServerConfig {
+    pub(crate) fn addrs(&self) -> impl Iterator<Item = SocketAddr> {
+        std::iter::once(self.addr).chain(self.extra_addrs.iter().copied())
+    }
+
     pub(crate) fn protocol_label(&self) -> &'static str {
         match &self.tls {
             None => "HTTP",
             Some(_) => "HTTPS",
         }
     }
+
+    /// Distinguishes the servers in metrics.
+    pub(crate) fn server_label(&self) -> &'static str {
+        match &self.tls {
+            Some(tls) if tls.is_mtls() => "mtls",
+            _ => "main",
+        }
+    }
 }
 
 pub struct ConfigReceivers {
@@ -66,7 +101,10 @@ async fn derive_http_config(config: &Config) -> anyhow::Result<HttpServerConfig>
     };
     Ok(HttpServerConfig {
         addr: config.vector_store_addr,
+        extra_addrs: config.vector_store_extra_addrs.clone(),
         tls,
+        tuning: config.http_tuning.clone(),
+        auth: config.auth.as_ref().map(Authenticator::new).transpose()?,
     })
 }
 
@@ -104,7 +142,10 @@ async fn derive_mtls_http_config(config: &Config) -> anyhow::Result<Option<HttpS
     let tls = Some(TlsServerConfig::new_mtls(&identity, &ca_bundle)?);
     Ok(Some(HttpServerConfig {
         addr: config.mtls_addr,
+        extra_addrs: config.mtls_extra_addrs.clone(),
         tls,
+        tuning: config.http_tuning.clone(),
+        auth: None,
     }))
 }
 
@@ -311,6 +352,8 @@ where
     const USERNAME_ENV: &str = "VECTOR_STORE_SCYLLADB_USERNAME";
     const PASS_FILE_ENV: &str = "VECTOR_STORE_SCYLLADB_PASSWORD_FILE";
     const CERT_FILE_ENV: &str = "VECTOR_STORE_SCYLLADB_CERTIFICATE_FILE";
+    const CLIENT_CERT_FILE_ENV: &str = "VECTOR_STORE_SCYLLADB_CLIENT_CERTIFICATE_FILE";
+    const CLIENT_KEY_FILE_ENV: &str = "VECTOR_STORE_SCYLLADB_CLIENT_KEY_FILE";
     // Check for certificate file
     let certificate_path = match env(CERT_FILE_ENV) {
         Ok(val) => {
@@ -320,6 +363,24 @@ where
         Err(_) => None,
     };
 
+    // Check for a client certificate and key, sent over TLS only
+    let (client_certificate_path, client_key_path) = match (
+        env(CLIENT_CERT_FILE_ENV),
+        env(CLIENT_KEY_FILE_ENV),
+    ) {
+        (Ok(cert), Ok(key)) => (
+            Some(std::path::PathBuf::from(cert)),
+            Some(std::path::PathBuf::from(key)),
+        ),
+        (Err(_), Err(_)) => (None, None),
+        _ => bail!(
+            "credentials: {CLIENT_CERT_FILE_ENV} and {CLIENT_KEY_FILE_ENV} env must be set together"
+        ),
+    };
+    if client_certificate_path.is_some() && certificate_path.is_none() {
+        bail!("credentials: {CERT_FILE_ENV} env required when {CLIENT_CERT_FILE_ENV} is set");
+    }
+
     // Check for username/password authentication
     let username = match env(USERNAME_ENV) {
         Ok(val) => {
@@ -368,9 +429,352 @@ where
         username,
         password,
         certificate_path,
+        client_certificate_path,
+        client_key_path,
+    }))
+}
+
+fn http_tuning(env: &impl Fn(&str) -> anyhow::Result<String>) -> anyhow::Result<HttpTuning> {
+    let bool_env = |name: &str, default: bool| -> anyhow::Result<bool> {
+        env(name).map_or(Ok(default), |v| {
+            v.trim()
+                .parse()
+                .map_err(|_| anyhow!("Unable to parse {name} env (true/false)"))
+        })
+    };
+    let duration_env = |name: &str| -> anyhow::Result<Option<Duration>> {
+        env(name)
+            .ok()
+            .map(|v| {
+                v.parse::<humantime::Duration>()
+                    .map(Into::into)
+                    .map_err(|err| anyhow!("Unable to parse {name} env (duration): {err}"))
+            })
+            .transpose()
+    };
+    Ok(HttpTuning {
+        http2: bool_env("VECTOR_STORE_HTTP2", true)?,
+        keep_alive: bool_env("VECTOR_STORE_HTTP_KEEP_ALIVE", true)?,
+        header_read_timeout: duration_env("VECTOR_STORE_HTTP_HEADER_READ_TIMEOUT")?,
+        http2_keep_alive_interval: duration_env("VECTOR_STORE_HTTP2_KEEP_ALIVE_INTERVAL")?,
+        http2_keep_alive_timeout: duration_env("VECTOR_STORE_HTTP2_KEEP_ALIVE_TIMEOUT")?,
+        http2_max_concurrent_streams: positive_env(
+            env,
+            "VECTOR_STORE_HTTP2_MAX_CONCURRENT_STREAMS",
+        )?,
+        max_connections: positive_env(env, "VECTOR_STORE_HTTP_MAX_CONNECTIONS")?,
+        max_concurrent_requests: positive_env(env, "VECTOR_STORE_HTTP_MAX_CONCURRENT_REQUESTS")?,
+    })
+}
+
+fn index_limits(env: &impl Fn(&str) -> anyhow::Result<String>) -> anyhow::Result<IndexLimits> {
+    Ok(IndexLimits {
+        max_dimensions: positive_env(env, "VECTOR_STORE_INDEX_MAX_DIMENSIONS")?,
+        max_connectivity: positive_env(env, "VECTOR_STORE_INDEX_MAX_CONNECTIVITY")?,
+        max_expansion_add: positive_env(env, "VECTOR_STORE_INDEX_MAX_EXPANSION_ADD")?,
+        max_expansion_search: positive_env(env, "VECTOR_STORE_INDEX_MAX_EXPANSION_SEARCH")?,
+    })
+}
+
+fn positive_env<T: FromStr>(
+    env: &impl Fn(&str) -> anyhow::Result<String>,
+    name: &str,
+) -> anyhow::Result<Option<T>> {
+    env(name)
+        .ok()
+        .map(|v| {
+            v.trim()
+                .parse()
+                .map_err(|_| anyhow!("Unable to parse {name} env (positive integer)"))
+        })
+        .transpose()
+}
+
+/// Parses a comma separated list of listen addresses into the first one and the rest.
+fn listen_addrs(name: &str, value: &str) -> anyhow::Result<(SocketAddr, Vec<SocketAddr>)> {
+    let mut addrs = value.split(',').map(|addr| {
+        addr.trim()
+            .to_socket_addrs()
+            .map_err(|err| anyhow!("Unable to parse {name} env (host:port[,host:port...]): {err}"))?
+            .next()
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unable to parse {name} env (host:port[,host:port...]): no addresses resolved"
+                )
+            })
+    });
+    let first = addrs.next().expect("split returns at least one item")?;
+    Ok((first, addrs.collect::<anyhow::Result<_>>()?))
+}
+
+/// Parses the clustering mode settings, which are set together or not at all.
+fn cluster(env: &impl Fn(&str) -> anyhow::Result<String>) -> anyhow::Result<Option<ClusterConfig>> {
+    let (node_id, nodes) = match (
+        env("VECTOR_STORE_CLUSTER_NODE_ID"),
+        env("VECTOR_STORE_CLUSTER_NODES"),
+    ) {
+        (Ok(node_id), Ok(nodes)) => (node_id, nodes),
+        (Err(_), Err(_)) => return Ok(None),
+        _ => bail!(
+            "Both VECTOR_STORE_CLUSTER_NODE_ID and VECTOR_STORE_CLUSTER_NODES must be set together"
+        ),
+    };
+    let mut nodes: Vec<ClusterNode> = nodes
+        .split(',')
+        .map(|node| {
+            let (id, url) = node.trim().split_once('=').ok_or_else(|| {
+                anyhow!("Unable to parse VECTOR_STORE_CLUSTER_NODES env (id=url[,id=url...])")
+            })?;
+            Ok(ClusterNode {
+                id: id.trim().into(),
+                url: url.trim().trim_end_matches('/').to_string(),
+                zone: None,
+            })
+        })
+        .collect::<anyhow::Result<_>>()?;
+    if let Ok(zones) = env("VECTOR_STORE_CLUSTER_ZONES") {
+        for zone in zones.split(',') {
+            let (id, zone) = zone.trim().split_once('=').ok_or_else(|| {
+                anyhow!("Unable to parse VECTOR_STORE_CLUSTER_ZONES env (id=zone[,id=zone...])")
+            })?;
+            let id = id.trim();
+            let Some(node) = nodes.iter_mut().find(|node| node.id.as_ref() == id) else {
+                bail!(
+                    "VECTOR_STORE_CLUSTER_ZONES lists node {id} missing in VECTOR_STORE_CLUSTER_NODES"
+                );
+            };
+            node.zone = Some(zone.trim().to_string());
+        }
+    }
+    let probe_interval =
+        env("VECTOR_STORE_CLUSTER_PROBE_INTERVAL")
+            .ok()
+            .map(|v| {
+                v.parse::<humantime::Duration>().map(Into::into).map_err(|err| {
+                anyhow!("Unable to parse VECTOR_STORE_CLUSTER_PROBE_INTERVAL env (duration): {err}")
+            })
+            })
+            .transpose()?;
+    let forward_timeout = env("VECTOR_STORE_CLUSTER_FORWARD_TIMEOUT")
+        .ok()
+        .map(|v| {
+            v.parse::<humantime::Duration>()
+                .map(Into::into)
+                .map_err(|err| {
+                    anyhow!(
+                        "Unable to parse VECTOR_STORE_CLUSTER_FORWARD_TIMEOUT env (duration): {err}"
+                    )
+                })
+        })
+        .transpose()?;
+    Ok(Some(ClusterConfig {
+        node_id: node_id.trim().into(),
+        nodes,
+        replication_factor: positive_env(env, "VECTOR_STORE_CLUSTER_REPLICATION_FACTOR")?
+            .unwrap_or(NonZeroUsize::MIN),
+        probe_interval: probe_interval.unwrap_or_else(cluster::default_probe_interval),
+        failure_threshold: positive_env(env, "VECTOR_STORE_CLUSTER_FAILURE_THRESHOLD")?
+            .unwrap_or_else(cluster::default_failure_threshold),
+        forward_timeout: forward_timeout.unwrap_or_else(cluster::default_forward_timeout),
+    }))
+}
+
+fn consul(env: &impl Fn(&str) -> anyhow::Result<String>) -> Option<ConsulConfig> {
+    let uri = env("VECTOR_STORE_CONSUL_URI").ok()?;
+    Some(ConsulConfig {
+        uri: uri.trim().to_string(),
+        service_name: env("VECTOR_STORE_CONSUL_SERVICE_NAME").map_or_else(
+            |_| "vector-store".to_string(),
+            |name| name.trim().to_string(),
+        ),
+        service_address: env("VECTOR_STORE_CONSUL_SERVICE_ADDRESS")
+            .ok()
+            .map(|address| address.trim().to_string()),
+    })
+}
+
+/// Parses the embedding provider settings, enabled by VECTOR_STORE_EMBEDDING_PROVIDER.
+async fn embedding(
+    env: &impl Fn(&str) -> anyhow::Result<String>,
+) -> anyhow::Result<Option<EmbeddingConfig>> {
+    let Ok(provider) = env("VECTOR_STORE_EMBEDDING_PROVIDER") else {
+        return Ok(None);
+    };
+    let provider: EmbeddingProvider = provider.trim().parse()?;
+    let uri = env("VECTOR_STORE_EMBEDDING_URI").map_or_else(
+        |_| provider.default_uri().map(ToString::to_string),
+        |uri| Some(uri.trim().trim_end_matches('/').to_string()),
+    );
+    let index_models =
+        env("VECTOR_STORE_EMBEDDING_INDEX_MODELS").map_or(Ok(HashMap::new()), |models| {
+            models
+                .split(',')
+                .map(|model| {
+                    let (index, model) = model.trim().split_once('=').ok_or_else(|| {
+                        anyhow!(
+                            "Unable to parse VECTOR_STORE_EMBEDDING_INDEX_MODELS env \
+                            (keyspace.index=model[,keyspace.index=model...])"
+                        )
+                    })?;
+                    Ok((index.trim().to_string(), model.trim().to_string()))
+                })
+                .collect::<anyhow::Result<_>>()
+        })?;
+    let api_key = match env("VECTOR_STORE_EMBEDDING_API_KEY_FILE") {
+        Ok(path) => Some(secrecy::SecretString::new(
+            tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|err| anyhow!("embedding: failed to read API key file: {err}"))?
+                .trim()
+                .into(),
+        )),
+        Err(_) => None,
+    };
+    let cache_size = env("VECTOR_STORE_EMBEDDING_CACHE_SIZE")
+        .ok()
+        .map(|v| {
+            v.trim().parse().map_err(|_| {
+                anyhow!("Unable to parse VECTOR_STORE_EMBEDDING_CACHE_SIZE env (usize)")
+            })
+        })
+        .transpose()?;
+    let cache_ttl = env("VECTOR_STORE_EMBEDDING_CACHE_TTL")
+        .ok()
+        .map(|v| {
+            v.parse::<humantime::Duration>()
+                .map(Into::into)
+                .map_err(|err| {
+                    anyhow!(
+                        "Unable to parse VECTOR_STORE_EMBEDDING_CACHE_TTL env (duration): {err}"
+                    )
+                })
+        })
+        .transpose()?;
+    let timeout = env("VECTOR_STORE_EMBEDDING_TIMEOUT")
+        .ok()
+        .map(|v| {
+            v.parse::<humantime::Duration>()
+                .map(Into::into)
+                .map_err(|err| {
+                    anyhow!("Unable to parse VECTOR_STORE_EMBEDDING_TIMEOUT env (duration): {err}")
+                })
+        })
+        .transpose()?;
+    Ok(Some(EmbeddingConfig {
+        provider,
+        uri,
+        model: env("VECTOR_STORE_EMBEDDING_MODEL")
+            .ok()
+            .map(|model| model.trim().to_string()),
+        index_models,
+        api_key,
+        cache_size: cache_size.unwrap_or_else(embedding::default_cache_size),
+        cache_ttl: cache_ttl.unwrap_or_else(embedding::default_cache_ttl),
+        rate_limit: positive_env(env, "VECTOR_STORE_EMBEDDING_RATE_LIMIT")?,
+        timeout: timeout.unwrap_or_else(embedding::default_timeout),
+    }))
+}
+
+async fn rerank(
+    env: &impl Fn(&str) -> anyhow::Result<String>,
+) -> anyhow::Result<Option<RerankConfig>> {
+    let Ok(provider) = env("VECTOR_STORE_RERANK_PROVIDER") else {
+        return Ok(None);
+    };
+    let provider: RerankProvider = provider.trim().parse()?;
+    let uri = env("VECTOR_STORE_RERANK_URI").map_or_else(
+        |_| provider.default_uri().map(ToString::to_string),
+        |uri| Some(uri.trim().trim_end_matches('/').to_string()),
+    );
+    let model = env("VECTOR_STORE_RERANK_MODEL")
+        .map_err(|_| anyhow!("VECTOR_STORE_RERANK_MODEL env is required for reranking"))?
+        .trim()
+        .to_string();
+    let api_key = match env("VECTOR_STORE_RERANK_API_KEY_FILE") {
+        Ok(path) => Some(secrecy::SecretString::new(
+            tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|err| anyhow!("rerank: failed to read API key file: {err}"))?
+                .trim()
+                .into(),
+        )),
+        Err(_) => None,
+    };
+    let timeout = env("VECTOR_STORE_RERANK_TIMEOUT")
+        .ok()
+        .map(|v| {
+            v.parse::<humantime::Duration>()
+                .map(Into::into)
+                .map_err(|err| {
+                    anyhow!("Unable to parse VECTOR_STORE_RERANK_TIMEOUT env (duration): {err}")
+                })
+        })
+        .transpose()?;
+    Ok(Some(RerankConfig {
+        provider,
+        uri,
+        model,
+        api_key,
+        oversampling: positive_env(env, "VECTOR_STORE_RERANK_OVERSAMPLING")?
+            .unwrap_or_else(rerank::default_oversampling),
+        timeout: timeout.unwrap_or_else(rerank::default_timeout),
     }))
 }
 
+async fn auth(env: &impl Fn(&str) -> anyhow::Result<String>) -> anyhow::Result<Option<AuthConfig>> {
+    let tokens = match env("VECTOR_STORE_AUTH_TOKENS_FILE") {
+        Ok(path) => tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|err| anyhow!("auth: failed to read tokens file: {err}"))?
+            .lines()
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+            .map(secrecy::SecretString::from)
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    let jwt_key = match env("VECTOR_STORE_AUTH_JWT_KEY_FILE") {
+        Ok(path) => Some(secrecy::SecretString::new(
+            tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|err| anyhow!("auth: failed to read JWT key file: {err}"))?
+                .trim()
+                .into(),
+        )),
+        Err(_) => None,
+    };
+    let peer_token = match env("VECTOR_STORE_AUTH_PEER_TOKEN_FILE") {
+        Ok(path) => Some(secrecy::SecretString::new(
+            tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|err| anyhow!("auth: failed to read peer token file: {err}"))?
+                .trim()
+                .into(),
+        )),
+        Err(_) => None,
+    };
+    if tokens.is_empty() && jwt_key.is_none() && peer_token.is_none() {
+        return Ok(None);
+    }
+    let jwt_algorithm = env("VECTOR_STORE_AUTH_JWT_ALGORITHM")
+        .ok()
+        .map(|algorithm| {
+            algorithm.trim().parse().map_err(|err| {
+                anyhow!("Unable to parse VECTOR_STORE_AUTH_JWT_ALGORITHM env: {err}")
+            })
+        })
+        .transpose()?
+        .unwrap_or_default();
+    let auth = AuthConfig {
+        tokens,
+        jwt_key,
+        jwt_algorithm,
+        peer_token,
+    };
+    Authenticator::new(&auth)?;
+    Ok(Some(auth))
+}
+
 pub async fn load_config(env: impl Fn(&str) -> anyhow::Result<String>) -> anyhow::Result<Config> {
     let mut config = Config::default();
 
@@ -386,17 +790,9 @@ pub async fn load_config(env: impl Fn(&str) -> anyhow::Result<String>) -> anyhow
         config.disable_colors = disable_colors;
     }
 
-    if let Some(vector_store_addr) = env("VECTOR_STORE_URI")
-        .ok()
-        .map(|v| {
-            v.to_socket_addrs()
-                .map_err(|_| anyhow!("Unable to parse VECTOR_STORE_URI env (host:port)"))?
-                .next()
-                .ok_or(anyhow!("Unable to parse VECTOR_STORE_URI env (host:port)"))
-        })
-        .transpose()?
-    {
-        config.vector_store_addr = vector_store_addr;
+    if let Ok(uri) = env("VECTOR_STORE_URI") {
+        (config.vector_store_addr, config.vector_store_extra_addrs) =
+            listen_addrs("VECTOR_STORE_URI", &uri)?;
     }
 
     if let Ok(scylladb_uri) = env("VECTOR_STORE_SCYLLADB_URI") {
@@ -431,6 +827,16 @@ pub async fn load_config(env: impl Fn(&str) -> anyhow::Result<String>) -> anyhow
         config.opensearch_addr = Some(opensearch_addr);
     }
 
+    if let Ok(opensearch_engine) = env("VECTOR_STORE_OPENSEARCH_ENGINE") {
+        config.opensearch_engine = Some(opensearch_engine);
+    }
+
+    if let Ok(faiss_index) = env("VECTOR_STORE_FAISS_INDEX") {
+        config.faiss_index = Some(faiss_index);
+    }
+
+    config.flat_index_max_rows = positive_env(&env, "VECTOR_STORE_FLAT_INDEX_MAX_ROWS")?;
+
     config.usearch_simulator = env("VECTOR_STORE_USEARCH_SIMULATOR")
         .ok()
         .map(|v| v.split(':').map(|s| s.parse::<humantime::Duration>()).map_ok(|v| v.into()).collect::<Result<Vec<_>, _>>().map_err(|err| {
@@ -474,6 +880,14 @@ pub async fn load_config(env: impl Fn(&str) -> anyhow::Result<String>) -> anyhow
         config.fulltext_indexes = fulltext_indexes;
     }
 
+    config.actor_tracing = env("VECTOR_STORE_ACTOR_TRACING")
+        .unwrap_or("false".into())
+        .trim()
+        .parse()
+        .or(Err(anyhow!(
+            "Unable to parse VECTOR_STORE_ACTOR_TRACING env (true/false)"
+        )))?;
+
     config.credentials = credentials(&env).await?;
 
     // Load TLS configuration
@@ -484,23 +898,39 @@ pub async fn load_config(env: impl Fn(&str) -> anyhow::Result<String>) -> anyhow
         .ok()
         .map(std::path::PathBuf::from);
 
-    config.mtls_addr = env("VECTOR_STORE_MTLS_URI")
+    if let Ok(uri) = env("VECTOR_STORE_MTLS_URI") {
+        (config.mtls_addr, config.mtls_extra_addrs) = listen_addrs("VECTOR_STORE_MTLS_URI", &uri)?;
+    }
+
+    config.grpc_addr = env("VECTOR_STORE_GRPC_URI")
         .ok()
-        .map(|v| {
-            v.to_socket_addrs()
-                .map_err(|err| anyhow!("Unable to parse VECTOR_STORE_MTLS_URI env (host:port): {err}"))?
-                .next()
-                .ok_or_else(|| {
-                    anyhow!("Unable to parse VECTOR_STORE_MTLS_URI env (host:port): no addresses resolved")
-                })
+        .map(|uri| {
+            uri.trim().parse().map_err(|err| {
+                anyhow!("Unable to parse VECTOR_STORE_GRPC_URI env (host:port): {err}")
+            })
         })
-        .transpose()?
-        .unwrap_or(config.mtls_addr);
+        .transpose()?;
 
     config.mtls_ca_cert_path = env("VECTOR_STORE_MTLS_CA_CERT_PATH")
         .ok()
         .map(std::path::PathBuf::from);
 
+    config.key_storage_dir = env("VECTOR_STORE_KEY_STORAGE_DIR")
+        .ok()
+        .map(std::path::PathBuf::from);
+
+    config.key_interning = env("VECTOR_STORE_KEY_INTERNING")
+        .unwrap_or("false".into())
+        .trim()
+        .parse()
+        .or(Err(anyhow!(
+            "Unable to parse VECTOR_STORE_KEY_INTERNING env (true/false)"
+        )))?;
+
+    config.vector_spill_dir = env("VECTOR_STORE_VECTOR_SPILL_DIR")
+        .ok()
+        .map(std::path::PathBuf::from);
+
     config.cql_connection_timeout = env("VECTOR_STORE_CQL_CONNECTION_TIMEOUT")
         .ok()
         .map(|v| v.parse::<humantime::Duration>())
@@ -549,6 +979,17 @@ pub async fn load_config(env: impl Fn(&str) -> anyhow::Result<String>) -> anyhow
         .transpose()?
         .map(|v| v.into());
 
+    if let Ok(modes) = env("VECTOR_STORE_CDC_MODES") {
+        config.cdc_modes = serde_json::from_str(&modes)
+            .map_err(|err| anyhow!("Unable to parse VECTOR_STORE_CDC_MODES env: {err}"))?;
+    }
+
+    config.token_ownership = env("VECTOR_STORE_TOKEN_RANGES")
+        .ok()
+        .map(|ranges| ranges.parse())
+        .transpose()
+        .map_err(|err| anyhow!("Unable to parse VECTOR_STORE_TOKEN_RANGES env: {err}"))?;
+
     config.monitor_indexes_interval = env("VECTOR_STORE_MONITOR_INDEXES_INTERVAL")
         .ok()
         .map(|v| v.parse::<humantime::Duration>())
@@ -561,11 +1002,78 @@ pub async fn load_config(env: impl Fn(&str) -> anyhow::Result<String>) -> anyhow
         .transpose()?
         .map(|v| v.into());
 
+    config.shutdown_delay = env("VECTOR_STORE_SHUTDOWN_DELAY")
+        .ok()
+        .map(|v| v.parse::<humantime::Duration>())
+        .transpose()?
+        .map(|v| v.into());
+
     config.cql_uri_translation_map = env("VECTOR_STORE_CQL_URI_TRANSLATION_MAP")
         .ok()
         .map(|v| serde_json::from_str(&v))
         .transpose()?;
 
+    if let Some(channel_size_per_worker) = env("VECTOR_STORE_CHANNEL_SIZE_PER_WORKER")
+        .ok()
+        .map(|v| {
+            v.trim().parse().map_err(|_| {
+                anyhow!(
+                    "Unable to parse VECTOR_STORE_CHANNEL_SIZE_PER_WORKER env (positive integer)"
+                )
+            })
+        })
+        .transpose()?
+    {
+        config.channel_size_per_worker = channel_size_per_worker;
+    }
+
+    if let Some(fts_commit_interval) = env("VECTOR_STORE_FTS_COMMIT_INTERVAL")
+        .ok()
+        .map(|v| v.parse::<humantime::Duration>())
+        .transpose()?
+    {
+        config.fts_commit_interval = fts_commit_interval.into();
+    }
+
+    if let Some(fts_max_uncommitted_docs) = env("VECTOR_STORE_FTS_MAX_UNCOMMITTED_DOCS")
+        .ok()
+        .map(|v| {
+            v.trim().parse().map_err(|_| {
+                anyhow!(
+                    "Unable to parse VECTOR_STORE_FTS_MAX_UNCOMMITTED_DOCS env (positive integer)"
+                )
+            })
+        })
+        .transpose()?
+    {
+        config.fts_max_uncommitted_docs = fts_max_uncommitted_docs;
+    }
+
+    config.http_tuning = http_tuning(&env)?;
+    config.index_limits = index_limits(&env)?;
+    config.cluster = cluster(&env)?;
+    config.consul = consul(&env);
+    config.embedding = embedding(&env).await?;
+    config.rerank = rerank(&env).await?;
+    config.auth = auth(&env).await?;
+
+    if let Ok(status) = env("VECTOR_STORE_SYSTEMD_READY_STATUS") {
+        config.systemd_ready_status = status.trim().parse().map_err(|err| {
+            anyhow!("Unable to parse VECTOR_STORE_SYSTEMD_READY_STATUS env: {err}")
+        })?;
+    }
+
+    if let Ok(partitions) = env("VECTOR_STORE_KEYSPACE_PARTITIONS") {
+        let partitions: HashMap<String, KeyspacePartition> = serde_json::from_str(&partitions)
+            .map_err(|err| {
+                anyhow!("Unable to parse VECTOR_STORE_KEYSPACE_PARTITIONS env: {err}")
+            })?;
+        config.keyspace_partitions = partitions
+            .into_iter()
+            .map(|(keyspace, partition)| (keyspace.into(), partition))
+            .collect();
+    }
+
     // Validate that both cert and key are provided together, or neither
     match (&tls_cert_path, &tls_key_path) {
         (Some(_), Some(_)) | (None, None) => {
@@ -579,15 +1087,23 @@ pub async fn load_config(env: impl Fn(&str) -> anyhow::Result<String>) -> anyhow
         }
     }
 
+    config.validate()?;
+
     Ok(config)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::CdcMode;
+    use crate::IndexKey;
+    use crate::TokenOwnership;
+    use crate::node_state::NodeStatus;
     use secrecy::ExposeSecret;
     use std::collections::HashMap;
     use std::io::Write;
+    use std::num::NonZeroU32;
+    use std::num::NonZeroUsize;
     use tempfile::NamedTempFile;
 
     const USERNAME: &str = "test_user";
@@ -740,6 +1256,61 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn credentials_with_client_certificate() {
+        let env = mock_env(HashMap::from([
+            (
+                "VECTOR_STORE_SCYLLADB_CERTIFICATE_FILE",
+                "/path/to/ca.pem".into(),
+            ),
+            (
+                "VECTOR_STORE_SCYLLADB_CLIENT_CERTIFICATE_FILE",
+                "/path/to/client.pem".into(),
+            ),
+            (
+                "VECTOR_STORE_SCYLLADB_CLIENT_KEY_FILE",
+                "/path/to/client.key".into(),
+            ),
+        ]));
+
+        let creds = credentials(&env).await.unwrap().unwrap();
+
+        assert_eq!(
+            creds.client_certificate_path,
+            Some(std::path::PathBuf::from("/path/to/client.pem"))
+        );
+        assert_eq!(
+            creds.client_key_path,
+            Some(std::path::PathBuf::from("/path/to/client.key"))
+        );
+
+        // The key is missing.
+        let env = mock_env(HashMap::from([
+            (
+                "VECTOR_STORE_SCYLLADB_CERTIFICATE_FILE",
+                "/path/to/ca.pem".into(),
+            ),
+            (
+                "VECTOR_STORE_SCYLLADB_CLIENT_CERTIFICATE_FILE",
+                "/path/to/client.pem".into(),
+            ),
+        ]));
+        assert!(credentials(&env).await.is_err());
+
+        // TLS is not enabled.
+        let env = mock_env(HashMap::from([
+            (
+                "VECTOR_STORE_SCYLLADB_CLIENT_CERTIFICATE_FILE",
+                "/path/to/client.pem".into(),
+            ),
+            (
+                "VECTOR_STORE_SCYLLADB_CLIENT_KEY_FILE",
+                "/path/to/client.key".into(),
+            ),
+        ]));
+        assert!(credentials(&env).await.is_err());
+    }
+
     #[tokio::test]
     async fn config_manager_reload_notifies_watchers() {
         let (config_manager, receivers) = ConfigManager::new(Config::default()).await.unwrap();
@@ -777,47 +1348,468 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn config_manager_multiple_watchers() {
-        let initial_config = Config::default();
-        let (config_manager, receivers) = ConfigManager::new(initial_config).await.unwrap();
-
-        // Clone receivers for multiple watchers
-        let mut rx1 = receivers.config.clone();
-        let mut rx2 = receivers.config.clone();
-        let mut rx3 = receivers.config;
-
-        // Spawn tasks to wait for changes
-        let task1 = tokio::spawn(async move {
-            rx1.changed().await.unwrap();
-            rx1.borrow().vector_store_addr.to_string()
-        });
-        let task2 = tokio::spawn(async move {
-            rx2.changed().await.unwrap();
-            rx2.borrow().vector_store_addr.to_string()
-        });
-        let task3 = tokio::spawn(async move {
-            rx3.changed().await.unwrap();
-            rx3.borrow().vector_store_addr.to_string()
-        });
+    async fn load_config_http_tuning() {
+        let config = load_config(mock_env(HashMap::new())).await.unwrap();
+        assert_eq!(config.http_tuning, HttpTuning::default());
 
-        // Give tasks time to start waiting
-        tokio::time::sleep(Duration::from_millis(10)).await;
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_HTTP2", "false".into()),
+            ("VECTOR_STORE_HTTP_KEEP_ALIVE", "false".into()),
+            ("VECTOR_STORE_HTTP_HEADER_READ_TIMEOUT", "5s".into()),
+            ("VECTOR_STORE_HTTP2_KEEP_ALIVE_INTERVAL", "10s".into()),
+            ("VECTOR_STORE_HTTP2_KEEP_ALIVE_TIMEOUT", "2s".into()),
+            ("VECTOR_STORE_HTTP2_MAX_CONCURRENT_STREAMS", "1000".into()),
+            ("VECTOR_STORE_HTTP_MAX_CONNECTIONS", "10000".into()),
+            ("VECTOR_STORE_HTTP_MAX_CONCURRENT_REQUESTS", "512".into()),
+        ]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(
+            config.http_tuning,
+            HttpTuning {
+                http2: false,
+                keep_alive: false,
+                header_read_timeout: Some(Duration::from_secs(5)),
+                http2_keep_alive_interval: Some(Duration::from_secs(10)),
+                http2_keep_alive_timeout: Some(Duration::from_secs(2)),
+                http2_max_concurrent_streams: std::num::NonZeroU32::new(1000),
+                max_connections: NonZeroUsize::new(10_000),
+                max_concurrent_requests: NonZeroUsize::new(512),
+            }
+        );
 
-        // Reload config
         let env = mock_env(HashMap::from([(
-            "VECTOR_STORE_URI",
-            "192.168.1.100:8080".into(),
+            "VECTOR_STORE_HTTP2_MAX_CONCURRENT_STREAMS",
+            "0".into(),
         )]));
-        config_manager.reload_config(env).await.unwrap();
-
-        // Verify all watchers received the update
-        let addr1 = task1.await.unwrap();
-        let addr2 = task2.await.unwrap();
-        let addr3 = task3.await.unwrap();
+        assert!(load_config(env).await.is_err());
 
-        assert_eq!(addr1, "192.168.1.100:8080");
-        assert_eq!(addr2, "192.168.1.100:8080");
-        assert_eq!(addr3, "192.168.1.100:8080");
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_HTTP_MAX_CONNECTIONS",
+            "0".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_index_limits() {
+        let config = load_config(mock_env(HashMap::new())).await.unwrap();
+        assert_eq!(config.index_limits, IndexLimits::default());
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_INDEX_MAX_DIMENSIONS", "4096".into()),
+            ("VECTOR_STORE_INDEX_MAX_CONNECTIVITY", "64".into()),
+            ("VECTOR_STORE_INDEX_MAX_EXPANSION_ADD", "512".into()),
+            ("VECTOR_STORE_INDEX_MAX_EXPANSION_SEARCH", "1024".into()),
+        ]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(
+            config.index_limits,
+            IndexLimits {
+                max_dimensions: NonZeroUsize::new(4096),
+                max_connectivity: NonZeroUsize::new(64),
+                max_expansion_add: NonZeroUsize::new(512),
+                max_expansion_search: NonZeroUsize::new(1024),
+            }
+        );
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_INDEX_MAX_DIMENSIONS",
+            "-1".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_consul() {
+        let config = load_config(mock_env(HashMap::new())).await.unwrap();
+        assert_eq!(config.consul, None);
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_CONSUL_URI",
+            "http://127.0.0.1:8500".into(),
+        )]));
+        assert_eq!(
+            load_config(env).await.unwrap().consul,
+            Some(ConsulConfig {
+                uri: "http://127.0.0.1:8500".to_string(),
+                service_name: "vector-store".to_string(),
+                service_address: None,
+            })
+        );
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_CONSUL_URI", "http://127.0.0.1:8500".into()),
+            ("VECTOR_STORE_CONSUL_SERVICE_NAME", "vs".into()),
+            ("VECTOR_STORE_CONSUL_SERVICE_ADDRESS", "10.0.0.1".into()),
+        ]));
+        let consul = load_config(env).await.unwrap().consul.unwrap();
+        assert_eq!(consul.service_name, "vs");
+        assert_eq!(consul.service_address, Some("10.0.0.1".to_string()));
+    }
+
+    #[tokio::test]
+    async fn load_config_embedding() {
+        let config = load_config(mock_env(HashMap::new())).await.unwrap();
+        assert!(config.embedding.is_none());
+
+        let key = pass_file("sk-test");
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_EMBEDDING_PROVIDER", "openai".into()),
+            (
+                "VECTOR_STORE_EMBEDDING_MODEL",
+                "text-embedding-3-small".into(),
+            ),
+            ("VECTOR_STORE_EMBEDDING_API_KEY_FILE", path(&key)),
+        ]));
+        let embedding = load_config(env).await.unwrap().embedding.unwrap();
+        assert_eq!(embedding.provider, EmbeddingProvider::OpenAi);
+        assert_eq!(embedding.uri.as_deref(), Some("https://api.openai.com/v1"));
+        assert_eq!(embedding.model.as_deref(), Some("text-embedding-3-small"));
+        assert!(embedding.index_models.is_empty());
+        assert_eq!(embedding.api_key.unwrap().expose_secret(), "sk-test");
+        assert_eq!(embedding.cache_size, 10_000);
+        assert_eq!(embedding.cache_ttl, Duration::from_secs(3600));
+        assert_eq!(embedding.rate_limit, None);
+        assert_eq!(embedding.timeout, Duration::from_secs(10));
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_EMBEDDING_PROVIDER", "bedrock".into()),
+            (
+                "VECTOR_STORE_EMBEDDING_MODEL",
+                "amazon.titan-embed-text-v2:0".into(),
+            ),
+            (
+                "VECTOR_STORE_EMBEDDING_URI",
+                "https://bedrock-runtime.us-east-1.amazonaws.com/".into(),
+            ),
+            ("VECTOR_STORE_EMBEDDING_CACHE_SIZE", "0".into()),
+            ("VECTOR_STORE_EMBEDDING_CACHE_TTL", "5m".into()),
+            ("VECTOR_STORE_EMBEDDING_RATE_LIMIT", "50".into()),
+            ("VECTOR_STORE_EMBEDDING_TIMEOUT", "2s".into()),
+        ]));
+        let embedding = load_config(env).await.unwrap().embedding.unwrap();
+        assert_eq!(
+            embedding.uri.as_deref(),
+            Some("https://bedrock-runtime.us-east-1.amazonaws.com")
+        );
+        assert!(embedding.api_key.is_none());
+        assert_eq!(embedding.cache_size, 0);
+        assert_eq!(embedding.cache_ttl, Duration::from_secs(300));
+        assert_eq!(embedding.rate_limit, NonZeroU32::new(50));
+        assert_eq!(embedding.timeout, Duration::from_secs(2));
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_EMBEDDING_PROVIDER", "bedrock".into()),
+            (
+                "VECTOR_STORE_EMBEDDING_MODEL",
+                "amazon.titan-embed-text-v2:0".into(),
+            ),
+        ]));
+        assert!(load_config(env).await.is_err());
+
+        // Indexes of text columns declare their own models.
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_EMBEDDING_PROVIDER",
+            "cohere".into(),
+        )]));
+        let embedding = load_config(env).await.unwrap().embedding.unwrap();
+        assert_eq!(embedding.model, None);
+        assert!(embedding.index_models.is_empty());
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_EMBEDDING_PROVIDER", "cohere".into()),
+            (
+                "VECTOR_STORE_EMBEDDING_INDEX_MODELS",
+                "ks.a=embed-english-v3.0, ks.b=embed-multilingual-v3.0".into(),
+            ),
+        ]));
+        let embedding = load_config(env).await.unwrap().embedding.unwrap();
+        assert_eq!(embedding.model, None);
+        assert_eq!(
+            embedding.index_models,
+            HashMap::from([
+                ("ks.a".to_string(), "embed-english-v3.0".to_string()),
+                ("ks.b".to_string(), "embed-multilingual-v3.0".to_string()),
+            ])
+        );
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_EMBEDDING_PROVIDER", "cohere".into()),
+            ("VECTOR_STORE_EMBEDDING_INDEX_MODELS", "ks.a".into()),
+        ]));
+        assert!(load_config(env).await.is_err());
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_EMBEDDING_PROVIDER", "onnx".into()),
+            (
+                "VECTOR_STORE_EMBEDDING_MODEL",
+                "/models/all-MiniLM-L6-v2".into(),
+            ),
+        ]));
+        let embedding = load_config(env)
+            .await
+            .map(|config| config.embedding.unwrap());
+        if cfg!(feature = "onnx") {
+            assert_eq!(embedding.unwrap().uri, None);
+        } else {
+            assert!(embedding.is_err());
+        }
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_EMBEDDING_PROVIDER", "unknown".into()),
+            ("VECTOR_STORE_EMBEDDING_MODEL", "model".into()),
+        ]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_rerank() {
+        let config = load_config(mock_env(HashMap::new())).await.unwrap();
+        assert!(config.rerank.is_none());
+
+        let key = pass_file("co-test");
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_RERANK_PROVIDER", "cohere".into()),
+            ("VECTOR_STORE_RERANK_MODEL", "rerank-v3.5".into()),
+            ("VECTOR_STORE_RERANK_API_KEY_FILE", path(&key)),
+        ]));
+        let rerank = load_config(env).await.unwrap().rerank.unwrap();
+        assert_eq!(rerank.provider, RerankProvider::Cohere);
+        assert_eq!(rerank.uri.as_deref(), Some("https://api.cohere.com/v2"));
+        assert_eq!(rerank.model, "rerank-v3.5");
+        assert_eq!(rerank.api_key.unwrap().expose_secret(), "co-test");
+        assert_eq!(rerank.oversampling, NonZeroUsize::new(4).unwrap());
+        assert_eq!(rerank.timeout, Duration::from_secs(10));
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_RERANK_PROVIDER", "cohere".into()),
+            ("VECTOR_STORE_RERANK_MODEL", "jina-reranker-v2".into()),
+            ("VECTOR_STORE_RERANK_URI", "https://api.jina.ai/v1/".into()),
+            ("VECTOR_STORE_RERANK_OVERSAMPLING", "10".into()),
+            ("VECTOR_STORE_RERANK_TIMEOUT", "2s".into()),
+        ]));
+        let rerank = load_config(env).await.unwrap().rerank.unwrap();
+        assert_eq!(rerank.uri.as_deref(), Some("https://api.jina.ai/v1"));
+        assert!(rerank.api_key.is_none());
+        assert_eq!(rerank.oversampling, NonZeroUsize::new(10).unwrap());
+        assert_eq!(rerank.timeout, Duration::from_secs(2));
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_RERANK_PROVIDER",
+            "cohere".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_RERANK_PROVIDER", "cohere".into()),
+            ("VECTOR_STORE_RERANK_MODEL", "rerank-v3.5".into()),
+            ("VECTOR_STORE_RERANK_OVERSAMPLING", "0".into()),
+        ]));
+        assert!(load_config(env).await.is_err());
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_RERANK_PROVIDER", "onnx".into()),
+            (
+                "VECTOR_STORE_RERANK_MODEL",
+                "/models/ms-marco-MiniLM-L-6-v2".into(),
+            ),
+        ]));
+        let rerank = load_config(env).await.map(|config| config.rerank.unwrap());
+        if cfg!(feature = "onnx") {
+            assert_eq!(rerank.unwrap().uri, None);
+        } else {
+            assert!(rerank.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn load_config_cluster() {
+        let config = load_config(mock_env(HashMap::new())).await.unwrap();
+        assert_eq!(config.cluster, None);
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_CLUSTER_NODE_ID", "vs2".into()),
+            (
+                "VECTOR_STORE_CLUSTER_NODES",
+                "vs1=http://10.0.0.1:6080, vs2=https://10.0.0.2:6080/".into(),
+            ),
+        ]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(
+            config.cluster,
+            Some(ClusterConfig {
+                node_id: "vs2".into(),
+                nodes: vec![
+                    ClusterNode {
+                        id: "vs1".into(),
+                        url: "http://10.0.0.1:6080".to_string(),
+                        zone: None,
+                    },
+                    ClusterNode {
+                        id: "vs2".into(),
+                        url: "https://10.0.0.2:6080".to_string(),
+                        zone: None,
+                    },
+                ],
+                replication_factor: NonZeroUsize::MIN,
+                probe_interval: Duration::from_secs(1),
+                failure_threshold: NonZeroUsize::new(3).unwrap(),
+                forward_timeout: Duration::from_secs(5),
+            })
+        );
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_CLUSTER_NODE_ID", "vs1".into()),
+            (
+                "VECTOR_STORE_CLUSTER_NODES",
+                "vs1=http://10.0.0.1:6080".into(),
+            ),
+            ("VECTOR_STORE_CLUSTER_PROBE_INTERVAL", "250ms".into()),
+            ("VECTOR_STORE_CLUSTER_FAILURE_THRESHOLD", "5".into()),
+            ("VECTOR_STORE_CLUSTER_FORWARD_TIMEOUT", "2s".into()),
+        ]));
+        let cluster = load_config(env).await.unwrap().cluster.unwrap();
+        assert_eq!(cluster.probe_interval, Duration::from_millis(250));
+        assert_eq!(cluster.failure_threshold, NonZeroUsize::new(5).unwrap());
+        assert_eq!(cluster.forward_timeout, Duration::from_secs(2));
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_CLUSTER_NODE_ID", "vs1".into()),
+            (
+                "VECTOR_STORE_CLUSTER_NODES",
+                "vs1=http://10.0.0.1:6080,vs2=http://10.0.0.2:6080".into(),
+            ),
+            (
+                "VECTOR_STORE_CLUSTER_ZONES",
+                "vs2=us-east-1b, vs1=us-east-1a".into(),
+            ),
+        ]));
+        let cluster = load_config(env).await.unwrap().cluster.unwrap();
+        assert_eq!(cluster.nodes[0].zone.as_deref(), Some("us-east-1a"));
+        assert_eq!(cluster.nodes[1].zone.as_deref(), Some("us-east-1b"));
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_CLUSTER_NODE_ID", "vs1".into()),
+            (
+                "VECTOR_STORE_CLUSTER_NODES",
+                "vs1=http://10.0.0.1:6080".into(),
+            ),
+            ("VECTOR_STORE_CLUSTER_ZONES", "vs3=us-east-1c".into()),
+        ]));
+        assert!(load_config(env).await.is_err());
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_CLUSTER_NODE_ID", "vs1".into()),
+            (
+                "VECTOR_STORE_CLUSTER_NODES",
+                "vs1=http://10.0.0.1:6080".into(),
+            ),
+            ("VECTOR_STORE_CLUSTER_REPLICATION_FACTOR", "2".into()),
+        ]));
+        assert!(load_config(env).await.is_err());
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_CLUSTER_NODE_ID",
+            "vs1".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_CLUSTER_NODE_ID", "vs3".into()),
+            (
+                "VECTOR_STORE_CLUSTER_NODES",
+                "vs1=http://10.0.0.1:6080".into(),
+            ),
+        ]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_multiple_listen_addresses() {
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_URI", "127.0.0.1:6080, [::1]:6080".into()),
+            ("VECTOR_STORE_MTLS_URI", "127.0.0.1:6081".into()),
+        ]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.vector_store_addr.to_string(), "127.0.0.1:6080");
+        assert_eq!(
+            config.vector_store_extra_addrs,
+            vec!["[::1]:6080".parse::<SocketAddr>().unwrap()]
+        );
+        assert!(config.mtls_extra_addrs.is_empty());
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_URI",
+            "127.0.0.1:6080,127.0.0.1:6080".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_URI",
+            "127.0.0.1:6080,".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn config_manager_multiple_watchers() {
+        let initial_config = Config::default();
+        let (config_manager, receivers) = ConfigManager::new(initial_config).await.unwrap();
+
+        // Clone receivers for multiple watchers
+        let mut rx1 = receivers.config.clone();
+        let mut rx2 = receivers.config.clone();
+        let mut rx3 = receivers.config;
+
+        // Spawn tasks to wait for changes
+        let task1 = tokio::spawn(async move {
+            rx1.changed().await.unwrap();
+            rx1.borrow().vector_store_addr.to_string()
+        });
+        let task2 = tokio::spawn(async move {
+            rx2.changed().await.unwrap();
+            rx2.borrow().vector_store_addr.to_string()
+        });
+        let task3 = tokio::spawn(async move {
+            rx3.changed().await.unwrap();
+            rx3.borrow().vector_store_addr.to_string()
+        });
+
+        // Give tasks time to start waiting
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        // Reload config
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_URI",
+            "192.168.1.100:8080".into(),
+        )]));
+        config_manager.reload_config(env).await.unwrap();
+
+        // Verify all watchers received the update
+        let addr1 = task1.await.unwrap();
+        let addr2 = task2.await.unwrap();
+        let addr3 = task3.await.unwrap();
+
+        assert_eq!(addr1, "192.168.1.100:8080");
+        assert_eq!(addr2, "192.168.1.100:8080");
+        assert_eq!(addr3, "192.168.1.100:8080");
+    }
+
+    #[tokio::test]
+    async fn load_config_grpc_addr() {
+        let env = mock_env(HashMap::new());
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.grpc_addr, None);
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_GRPC_URI",
+            "127.0.0.1:6082".into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.grpc_addr, Some("127.0.0.1:6082".parse().unwrap()));
+
+        let env = mock_env(HashMap::from([("VECTOR_STORE_GRPC_URI", "6082".into())]));
+        assert!(load_config(env).await.is_err());
     }
 
     #[tokio::test]
@@ -851,6 +1843,23 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn load_config_vector_spill_dir() {
+        let env = mock_env(HashMap::new());
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.vector_spill_dir, None);
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_VECTOR_SPILL_DIR",
+            "/var/lib/vector-store".into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(
+            config.vector_spill_dir,
+            Some(std::path::PathBuf::from("/var/lib/vector-store"))
+        );
+    }
+
     #[tokio::test]
     async fn load_config_cql_connection_timeout() {
         let env = mock_env(HashMap::new());
@@ -882,6 +1891,137 @@ mod tests {
         assert!(!config.fulltext_indexes);
     }
 
+    #[tokio::test]
+    async fn load_config_actor_tracing() {
+        let config = load_config(mock_env(HashMap::new())).await.unwrap();
+        assert!(!config.actor_tracing);
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_ACTOR_TRACING",
+            "true".into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert!(config.actor_tracing);
+    }
+
+    #[tokio::test]
+    async fn load_config_keyspace_partitions() {
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_KEYSPACE_PARTITIONS",
+            r#"{"tenant_a": {"workers": 2, "memory_limit": 1048576}, "tenant_b": {}}"#.into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.keyspace_partitions.len(), 2);
+        assert_eq!(
+            config.keyspace_partitions[&"tenant_a".into()],
+            KeyspacePartition {
+                workers: NonZeroUsize::new(2),
+                memory_limit: Some(1048576),
+            }
+        );
+        assert_eq!(
+            config.keyspace_partitions[&"tenant_b".into()],
+            KeyspacePartition::default()
+        );
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_KEYSPACE_PARTITIONS",
+            r#"{"tenant_a": {"threads": 2}}"#.into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_tunables() {
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_CHANNEL_SIZE_PER_WORKER", "8".into()),
+            ("VECTOR_STORE_FTS_COMMIT_INTERVAL", "500ms".into()),
+            ("VECTOR_STORE_FTS_MAX_UNCOMMITTED_DOCS", "100".into()),
+        ]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.channel_size_per_worker.get(), 8);
+        assert_eq!(config.fts_commit_interval, Duration::from_millis(500));
+        assert_eq!(config.fts_max_uncommitted_docs.get(), 100);
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_CHANNEL_SIZE_PER_WORKER",
+            "0".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_systemd_ready_status() {
+        let config = load_config(mock_env(HashMap::new())).await.unwrap();
+        assert_eq!(config.systemd_ready_status, NodeStatus::Serving);
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_SYSTEMD_READY_STATUS",
+            "discovering-indexes".into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.systemd_ready_status, NodeStatus::DiscoveringIndexes);
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_SYSTEMD_READY_STATUS",
+            "ready".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_rejects_out_of_range_values() {
+        for (key, value) in [
+            ("VECTOR_STORE_MEMORY_LIMIT", "0"),
+            ("VECTOR_STORE_MONITOR_INDEXES_INTERVAL", "0s"),
+            ("VECTOR_STORE_FTS_COMMIT_INTERVAL", "0s"),
+            ("VECTOR_STORE_USEARCH_SIMULATOR", "1ms:1ms:1ms:1ms"),
+            ("VECTOR_STORE_MTLS_CA_CERT_PATH", "ca.pem"),
+        ] {
+            let env = mock_env(HashMap::from([(key, value.into())]));
+            assert!(load_config(env).await.is_err(), "{key}={value} accepted");
+        }
+    }
+
+    #[test]
+    fn config_serde_roundtrip() {
+        let config = Config {
+            memory_usage_check_interval: Some(Duration::from_millis(100)),
+            usearch_simulator: Some(vec![Duration::from_micros(5), Duration::ZERO]),
+            diskann_alpha: Some(DiskannAlpha::new(1.2).unwrap()),
+            keyspace_partitions: HashMap::from([(
+                "tenant".into(),
+                KeyspacePartition {
+                    workers: NonZeroUsize::new(2),
+                    memory_limit: None,
+                },
+            )]),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&config).unwrap();
+        assert_eq!(json["memory_usage_check_interval"], "100ms");
+        assert_eq!(json["fts_commit_interval"], "3s");
+
+        let parsed: Config = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            parsed.memory_usage_check_interval,
+            config.memory_usage_check_interval
+        );
+        assert_eq!(parsed.usearch_simulator, config.usearch_simulator);
+        assert_eq!(parsed.diskann_alpha, config.diskann_alpha);
+        assert_eq!(parsed.keyspace_partitions, config.keyspace_partitions);
+
+        let parsed: Config = serde_json::from_str(r#"{"threads": 4}"#).unwrap();
+        assert_eq!(parsed.threads, Some(4));
+        assert_eq!(
+            parsed.vector_store_addr,
+            Config::default().vector_store_addr
+        );
+
+        assert!(serde_json::from_str::<Config>(r#"{"diskann_alpha": -1.0}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r#"{"unknown": 1}"#).is_err());
+    }
+
     #[tokio::test]
     async fn load_config_fulltext_indexes_invalid_value_errors() {
         let env = mock_env(HashMap::from([(
@@ -898,6 +2038,178 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn load_config_opensearch_engine() {
+        let env = mock_env(HashMap::new());
+        let config = load_config(env).await.unwrap();
+        assert!(config.opensearch_engine.is_none());
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_OPENSEARCH_ENGINE",
+            "lucene".into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.opensearch_engine.as_deref(), Some("lucene"));
+    }
+
+    #[tokio::test]
+    async fn load_config_faiss_index() {
+        let env = mock_env(HashMap::new());
+        let config = load_config(env).await.unwrap();
+        assert!(config.faiss_index.is_none());
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_FAISS_INDEX",
+            "IVF1024,Flat".into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.faiss_index.as_deref(), Some("IVF1024,Flat"));
+    }
+
+    #[tokio::test]
+    async fn load_config_flat_index_max_rows() {
+        let env = mock_env(HashMap::new());
+        let config = load_config(env).await.unwrap();
+        assert!(config.flat_index_max_rows.is_none());
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_FLAT_INDEX_MAX_ROWS",
+            "10000".into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.flat_index_max_rows, NonZeroUsize::new(10000));
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_FLAT_INDEX_MAX_ROWS",
+            "0".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_cdc_modes() {
+        let env = mock_env(HashMap::new());
+        let config = load_config(env).await.unwrap();
+        assert!(config.cdc_modes.is_empty());
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_CDC_MODES",
+            r#"{"ks.fast": "fine", "ks.safe": "wide"}"#.into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        let index = |name: &str| IndexKey::new(&"ks".into(), &name.into());
+        assert_eq!(config.cdc_modes[&index("fast")], CdcMode::Fine);
+        assert_eq!(config.cdc_modes[&index("safe")], CdcMode::Wide);
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_CDC_MODES",
+            r#"{"ks.idx": "none"}"#.into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_CDC_MODES",
+            r#"{"idx": "fine"}"#.into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_auth() {
+        let config = load_config(mock_env(HashMap::new())).await.unwrap();
+        assert!(config.auth.is_none());
+
+        let tokens = pass_file("first\n\n  second  ");
+        let key = pass_file("secret");
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_AUTH_TOKENS_FILE", path(&tokens)),
+            ("VECTOR_STORE_AUTH_JWT_KEY_FILE", path(&key)),
+            ("VECTOR_STORE_AUTH_JWT_ALGORITHM", "HS512".into()),
+        ]));
+        let auth = load_config(env).await.unwrap().auth.unwrap();
+        assert_eq!(
+            auth.tokens
+                .iter()
+                .map(|token| token.expose_secret())
+                .collect_vec(),
+            ["first", "second"]
+        );
+        assert_eq!(auth.jwt_key.unwrap().expose_secret(), "secret");
+        assert_eq!(auth.jwt_algorithm, jsonwebtoken::Algorithm::HS512);
+        assert!(auth.peer_token.is_none());
+
+        let peer_token = pass_file("peer");
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_AUTH_PEER_TOKEN_FILE",
+            path(&peer_token),
+        )]));
+        let auth = load_config(env).await.unwrap().auth.unwrap();
+        assert!(auth.tokens.is_empty());
+        assert_eq!(auth.peer_token.unwrap().expose_secret(), "peer");
+
+        let env = mock_env(HashMap::from([
+            ("VECTOR_STORE_AUTH_JWT_KEY_FILE", path(&key)),
+            ("VECTOR_STORE_AUTH_JWT_ALGORITHM", "RS256".into()),
+        ]));
+        assert!(load_config(env).await.is_err());
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_AUTH_TOKENS_FILE",
+            "/nonexistent".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_shutdown_delay() {
+        let env = mock_env(HashMap::new());
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.shutdown_delay, None);
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_SHUTDOWN_DELAY",
+            "5s".into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.shutdown_delay, Some(Duration::from_secs(5)));
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_SHUTDOWN_DELAY",
+            "soon".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn load_config_token_ranges() {
+        let env = mock_env(HashMap::new());
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.token_ownership, None);
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_TOKEN_RANGES",
+            "local".into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(config.token_ownership, Some(TokenOwnership::Local));
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_TOKEN_RANGES",
+            "0:100,-100:-1".into(),
+        )]));
+        let config = load_config(env).await.unwrap();
+        assert_eq!(
+            config.token_ownership,
+            Some(TokenOwnership::Ranges("-100:100".parse().unwrap()))
+        );
+
+        let env = mock_env(HashMap::from([(
+            "VECTOR_STORE_TOKEN_RANGES",
+            "100:0".into(),
+        )]));
+        assert!(load_config(env).await.is_err());
+    }
+
     #[tokio::test]
     async fn load_config_diskann() {
         let env = mock_env(HashMap::new());
@@ -0,0 +1,283 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Registration of the node in the Consul service catalog.
+//!
+//! The main HTTP server is registered at the Consul agent with a TTL check. The check is kept
+//! passing while the node is serving and warning while it is still bootstrapping. When the node
+//! stops, the check expires and the agent removes the service after a while. The service is
+//! registered again when the agent loses it or the server moves to another address.
+
+use crate::node_state::NodeState;
+use crate::node_state::NodeStateExt;
+use crate::node_state::NodeStatus;
+use anyhow::bail;
+use serde_json::json;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::sync::mpsc::Sender;
+use tokio::sync::watch;
+use tokio::time;
+use tracing::Instrument;
+use tracing::debug_span;
+use tracing::info;
+use tracing::warn;
+
+/// How often the check is updated. The check expires after three missed updates.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+const DEREGISTER_CRITICAL_AFTER: &str = "1m";
+
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConsulConfig {
+    /// URL of the Consul agent HTTP API, e.g. `http://127.0.0.1:8500`.
+    pub uri: String,
+    pub service_name: String,
+    /// Address advertised for the service. The agent node address is used if not set and the
+    /// server listens on an unspecified address.
+    pub service_address: Option<String>,
+}
+
+impl ConsulConfig {
+    pub(crate) fn validate(&self) -> anyhow::Result<()> {
+        if !self.uri.starts_with("http://") && !self.uri.starts_with("https://") {
+            bail!("Consul agent URL {} is not an HTTP URL", self.uri);
+        }
+        if self.service_name.is_empty() {
+            bail!("Consul service name is empty");
+        }
+        Ok(())
+    }
+}
+
+struct Service {
+    id: String,
+    check_id: String,
+    body: serde_json::Value,
+}
+
+impl Service {
+    fn new(config: &ConsulConfig, addr: SocketAddr, use_tls: bool, ttl: Duration) -> Self {
+        let id = format!("{}-{}", config.service_name, addr.port());
+        let check_id = format!("{id}-status");
+        let address = config
+            .service_address
+            .clone()
+            .or_else(|| (!addr.ip().is_unspecified()).then(|| addr.ip().to_string()));
+        let body = json!({
+            "ID": id,
+            "Name": config.service_name,
+            "Address": address.unwrap_or_default(),
+            "Port": addr.port(),
+            "Meta": {
+                "scheme": if use_tls { "https" } else { "http" },
+            },
+            "Check": {
+                "CheckID": check_id,
+                "Name": "vector-store status",
+                "TTL": humantime::format_duration(ttl).to_string(),
+                "DeregisterCriticalServiceAfter": DEREGISTER_CRITICAL_AFTER,
+            },
+        });
+        Self { id, check_id, body }
+    }
+}
+
+struct Agent {
+    client: reqwest::Client,
+    uri: String,
+}
+
+impl Agent {
+    async fn register(&self, service: &Service) -> reqwest::Result<()> {
+        self.client
+            .put(format!("{}/v1/agent/service/register", self.uri))
+            .json(&service.body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    async fn update_check(&self, service: &Service, status: NodeStatus) -> reqwest::Result<()> {
+        let check_status = if status == NodeStatus::Serving {
+            "passing"
+        } else {
+            "warning"
+        };
+        self.client
+            .put(format!(
+                "{}/v1/agent/check/update/{}",
+                self.uri, service.check_id
+            ))
+            .json(&json!({
+                "Status": check_status,
+                "Output": format!("{status:?}"),
+            }))
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+}
+
+/// Starts registering the main HTTP server listening on the published address.
+pub(crate) fn start(
+    config: ConsulConfig,
+    node_state: Sender<NodeState>,
+    addr_rx: watch::Receiver<Option<SocketAddr>>,
+    use_tls: bool,
+) {
+    let agent = Agent {
+        client: reqwest::Client::new(),
+        uri: config.uri.trim_end_matches('/').to_string(),
+    };
+    tokio::spawn(
+        run(agent, config, node_state, addr_rx, use_tls, UPDATE_INTERVAL)
+            .instrument(debug_span!("consul")),
+    );
+}
+
+async fn run(
+    agent: Agent,
+    config: ConsulConfig,
+    node_state: Sender<NodeState>,
+    mut addr_rx: watch::Receiver<Option<SocketAddr>>,
+    use_tls: bool,
+    update_interval: Duration,
+) {
+    let ttl = update_interval * 3;
+    let mut registered = None;
+    let mut interval = time::interval(update_interval);
+
+    while !node_state.is_closed() {
+        tokio::select! {
+            _ = interval.tick() => {}
+            changed = addr_rx.changed() => {
+                if changed.is_err() {
+                    break;
+                }
+                registered = None;
+            }
+        }
+
+        let Some(addr) = *addr_rx.borrow_and_update() else {
+            continue;
+        };
+        let service = match registered.take() {
+            Some(service) => service,
+            None => {
+                let service = Service::new(&config, addr, use_tls, ttl);
+                if let Err(err) = agent.register(&service).await {
+                    warn!("unable to register service {} in Consul: {err}", service.id);
+                    continue;
+                }
+                info!("registered service {} in Consul", service.id);
+                service
+            }
+        };
+        match agent
+            .update_check(&service, node_state.get_status().await)
+            .await
+        {
+            Ok(()) => registered = Some(service),
+            Err(err) => warn!("unable to update Consul check {}: {err}", service.check_id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node_state::Event;
+    use axum::Json;
+    use axum::Router;
+    use axum::extract::Path;
+    use axum::routing::put;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    /// Spawns a fake Consul agent sending the received requests to the channel.
+    async fn spawn_agent() -> (String, mpsc::UnboundedReceiver<(String, serde_json::Value)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let router = Router::new()
+            .route(
+                "/v1/agent/service/register",
+                put({
+                    let tx = tx.clone();
+                    move |Json(body): Json<serde_json::Value>| {
+                        tx.send(("register".to_string(), body)).unwrap();
+                        async {}
+                    }
+                }),
+            )
+            .route(
+                "/v1/agent/check/update/{id}",
+                put(
+                    move |Path(id): Path<String>, Json(body): Json<serde_json::Value>| {
+                        tx.send((id, body)).unwrap();
+                        async {}
+                    },
+                ),
+            );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        (format!("http://{addr}"), rx)
+    }
+
+    async fn recv(
+        rx: &mut mpsc::UnboundedReceiver<(String, serde_json::Value)>,
+    ) -> (String, serde_json::Value) {
+        time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn service_is_registered_and_check_follows_node_status() {
+        let (uri, mut requests) = spawn_agent().await;
+        let node_state = crate::new_node_state().await;
+        let (_addr_tx, addr_rx) = watch::channel(Some("0.0.0.0:6080".parse().unwrap()));
+        let config = ConsulConfig {
+            uri: uri.clone(),
+            service_name: "vector-store".to_string(),
+            service_address: None,
+        };
+        let agent = Agent {
+            client: reqwest::Client::new(),
+            uri,
+        };
+        tokio::spawn(run(
+            agent,
+            config,
+            node_state.clone(),
+            addr_rx,
+            true,
+            Duration::from_millis(10),
+        ));
+
+        let (kind, body) = recv(&mut requests).await;
+        assert_eq!(kind, "register");
+        assert_eq!(body["ID"], "vector-store-6080");
+        assert_eq!(body["Address"], "");
+        assert_eq!(body["Port"], 6080);
+        assert_eq!(body["Meta"]["scheme"], "https");
+        assert_eq!(body["Check"]["TTL"], "30ms");
+
+        let (check_id, body) = recv(&mut requests).await;
+        assert_eq!(check_id, "vector-store-6080-status");
+        assert_eq!(body["Status"], "warning");
+
+        node_state.send_event(Event::ConnectingToDb).await;
+        node_state.send_event(Event::ConnectedToDb).await;
+        node_state.send_event(Event::DiscoveringIndexes).await;
+        node_state
+            .send_event(Event::IndexesDiscovered(Default::default()))
+            .await;
+        while recv(&mut requests).await.1["Status"] != "passing" {}
+    }
+}
@@ -18,12 +18,15 @@ use crate::ExpansionSearch;
 use crate::IndexMetadata;
 use crate::IndexName;
 use crate::IndexVersion;
+use crate::KeyspaceIdentifier;
 use crate::KeyspaceName;
 use crate::Metrics;
 use crate::NonemptyArc;
 use crate::NonemptyIteratorExt;
+use crate::PrimaryKey;
 use crate::Quantization;
 use crate::SpaceType;
+use crate::TableIdentifier;
 use crate::TableName;
 use crate::db_index;
 use crate::db_index::DbIndex;
@@ -37,11 +40,14 @@ use crate::perf;
 use anyhow::Context;
 use anyhow::anyhow;
 use anyhow::bail;
+use async_trait::async_trait;
+use futures::StreamExt;
 use futures::TryStreamExt;
 use regex::Regex;
 use rustls::ClientConfig;
 use rustls::RootCertStore;
 use rustls::pki_types::CertificateDer;
+use rustls::pki_types::PrivateKeyDer;
 use rustls_pki_types::pem::PemObject;
 use scylla::client::session::Session;
 use scylla::client::session::TlsContext;
@@ -53,7 +59,9 @@ use scylla::statement::prepared::PreparedStatement;
 use scylla::value::CqlTimeuuid;
 use secrecy::ExposeSecret;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 use tap::Pipe;
 use tokio::sync::Notify;
@@ -70,15 +78,15 @@ use tracing::trace;
 use tracing::warn;
 use uuid::Uuid;
 
-type GetDbIndexR = anyhow::Result<(
+pub type GetDbIndexR = anyhow::Result<(
     mpsc::Sender<DbIndex>,
     mpsc::Receiver<(DbIndexedRow, AsyncInProgress)>,
 )>;
-pub(crate) type LatestSchemaVersionR = anyhow::Result<Option<CqlTimeuuid>>;
-type GetIndexesR = anyhow::Result<Vec<DbCustomIndex>>;
-type GetIndexVersionR = anyhow::Result<Option<IndexVersion>>;
-type GetIndexTargetTypeR = anyhow::Result<Option<Dimensions>>;
-type GetIndexParamsR = anyhow::Result<
+pub type LatestSchemaVersionR = anyhow::Result<Option<CqlTimeuuid>>;
+pub type GetIndexesR = anyhow::Result<Vec<DbCustomIndex>>;
+pub type GetIndexVersionR = anyhow::Result<Option<IndexVersion>>;
+pub type GetIndexTargetTypeR = anyhow::Result<Option<Dimensions>>;
+pub type GetIndexParamsR = anyhow::Result<
     Option<(
         Connectivity,
         ExpansionAdd,
@@ -87,23 +95,32 @@ type GetIndexParamsR = anyhow::Result<
         Quantization,
     )>,
 >;
-type IsValidIndexR = bool;
+pub type IsValidIndexR = bool;
+pub type GetTextsR = anyhow::Result<Vec<Option<String>>>;
 
 const RECONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+/// Rows read concurrently by a single [`Db::GetTexts`].
+const GET_TEXTS_CONCURRENCY: usize = 16;
 
+/// Messages handled by the db actor, which provides the schema and the data of indexed tables.
+///
+/// The actor is usually backed by ScyllaDB (see [`crate::new_db`]), but any [`DbSource`] can be
+/// served with [`new_from_source`].
 pub enum Db {
+    /// Returns the per-index actor and a stream of indexed rows. The stream starts with a full
+    /// scan of the table and continues with later changes.
     GetDbIndex {
         metadata: IndexMetadata,
         tx: oneshot::Sender<GetDbIndexR>,
     },
 
+    /// Returns the version of the latest schema change, if it is known.
     LatestSchemaVersion {
         tx: oneshot::Sender<LatestSchemaVersionR>,
     },
 
-    GetIndexes {
-        tx: oneshot::Sender<GetIndexesR>,
-    },
+    /// Returns all custom indexes which should be served.
+    GetIndexes { tx: oneshot::Sender<GetIndexesR> },
 
     GetIndexVersion {
         keyspace: KeyspaceName,
@@ -138,6 +155,17 @@ pub enum Db {
         metadata: IndexMetadata,
         tx: oneshot::Sender<IsValidIndexR>,
     },
+
+    /// Returns the values of a text column of the rows with the primary keys, in their order. The
+    /// value of a missing row is `None`.
+    GetTexts {
+        keyspace: KeyspaceName,
+        table: TableName,
+        column: ColumnName,
+        primary_key_columns: NonemptyArc<ColumnName>,
+        primary_keys: Vec<PrimaryKey>,
+        tx: oneshot::Sender<GetTextsR>,
+    },
 }
 
 pub(crate) trait DbExt {
@@ -170,6 +198,15 @@ pub(crate) trait DbExt {
     ) -> GetIndexParamsR;
 
     async fn is_valid_index(&self, metadata: IndexMetadata) -> IsValidIndexR;
+
+    async fn get_texts(
+        &self,
+        keyspace: KeyspaceName,
+        table: TableName,
+        column: ColumnName,
+        primary_key_columns: NonemptyArc<ColumnName>,
+        primary_keys: Vec<PrimaryKey>,
+    ) -> GetTextsR;
 }
 
 impl DbExt for mpsc::Sender<Db> {
@@ -252,6 +289,173 @@ impl DbExt for mpsc::Sender<Db> {
         rx.await
             .expect("DbExt::is_valid_index: internal actor should send response")
     }
+
+    async fn get_texts(
+        &self,
+        keyspace: KeyspaceName,
+        table: TableName,
+        column: ColumnName,
+        primary_key_columns: NonemptyArc<ColumnName>,
+        primary_keys: Vec<PrimaryKey>,
+    ) -> GetTextsR {
+        let (tx, rx) = oneshot::channel();
+        self.send(Db::GetTexts {
+            keyspace,
+            table,
+            column,
+            primary_key_columns,
+            primary_keys,
+            tx,
+        })
+        .await?;
+        rx.await?
+    }
+}
+
+/// A custom data source backing the vector-store instead of ScyllaDB.
+///
+/// Use [`new_from_source`] to serve the [`Db`] actor protocol from an implementation. Per-index
+/// actors returned by [`DbSource::get_db_index`] can be served with
+/// [`crate::db_index::new_from_source`].
+#[async_trait]
+pub trait DbSource: Send + Sync + 'static {
+    async fn get_db_index(&self, metadata: IndexMetadata) -> GetDbIndexR;
+
+    async fn latest_schema_version(&self) -> LatestSchemaVersionR;
+
+    async fn get_indexes(&self) -> GetIndexesR;
+
+    async fn get_index_version(
+        &self,
+        keyspace: KeyspaceName,
+        table: TableName,
+        index: IndexName,
+    ) -> GetIndexVersionR;
+
+    async fn get_index_target_type(
+        &self,
+        keyspace: KeyspaceName,
+        table: TableName,
+        target_column: ColumnName,
+        index: IndexName,
+    ) -> GetIndexTargetTypeR;
+
+    async fn get_index_params(
+        &self,
+        keyspace: KeyspaceName,
+        table: TableName,
+        index: IndexName,
+    ) -> GetIndexParamsR;
+
+    async fn is_valid_index(&self, metadata: IndexMetadata) -> IsValidIndexR;
+
+    /// Sources without rows to read fail the reranking of search results.
+    async fn get_texts(
+        &self,
+        _keyspace: KeyspaceName,
+        _table: TableName,
+        _column: ColumnName,
+        _primary_key_columns: NonemptyArc<ColumnName>,
+        _primary_keys: Vec<PrimaryKey>,
+    ) -> GetTextsR {
+        bail!("the data source does not support reading rows")
+    }
+}
+
+/// Starts a [`Db`] actor answering requests from the given source. Requests are processed
+/// concurrently, as they are for ScyllaDB.
+pub fn new_from_source(source: impl DbSource) -> mpsc::Sender<Db> {
+    let source = Arc::new(source);
+    let (tx, mut rx) = mpsc::channel(perf::channel_size().into());
+    tokio::spawn(
+        async move {
+            while let Some(msg) = rx.recv().await {
+                tokio::spawn(
+                    process_source(Arc::clone(&source), msg)
+                        .instrument(error_span!("db-source-process")),
+                );
+            }
+        }
+        .instrument(error_span!("db-source")),
+    );
+    tx
+}
+
+async fn process_source(source: Arc<impl DbSource>, msg: Db) {
+    match msg {
+        Db::GetDbIndex { metadata, tx } => tx
+            .send(source.get_db_index(metadata).await)
+            .unwrap_or_else(|_| trace!("process_source: Db::GetDbIndex: unable to send response")),
+
+        Db::LatestSchemaVersion { tx } => tx
+            .send(source.latest_schema_version().await)
+            .unwrap_or_else(|_| {
+                trace!("process_source: Db::LatestSchemaVersion: unable to send response")
+            }),
+
+        Db::GetIndexes { tx } => tx
+            .send(source.get_indexes().await)
+            .unwrap_or_else(|_| trace!("process_source: Db::GetIndexes: unable to send response")),
+
+        Db::GetIndexVersion {
+            keyspace,
+            table,
+            index,
+            tx,
+        } => tx
+            .send(source.get_index_version(keyspace, table, index).await)
+            .unwrap_or_else(|_| {
+                trace!("process_source: Db::GetIndexVersion: unable to send response")
+            }),
+
+        Db::GetIndexTargetType {
+            keyspace,
+            table,
+            target_column,
+            index,
+            tx,
+        } => tx
+            .send(
+                source
+                    .get_index_target_type(keyspace, table, target_column, index)
+                    .await,
+            )
+            .unwrap_or_else(|_| {
+                trace!("process_source: Db::GetIndexTargetType: unable to send response")
+            }),
+
+        Db::GetIndexParams {
+            keyspace,
+            table,
+            index,
+            tx,
+        } => tx
+            .send(source.get_index_params(keyspace, table, index).await)
+            .unwrap_or_else(|_| {
+                trace!("process_source: Db::GetIndexParams: unable to send response")
+            }),
+
+        Db::IsValidIndex { metadata, tx } => tx
+            .send(source.is_valid_index(metadata).await)
+            .unwrap_or_else(|_| {
+                trace!("process_source: Db::IsValidIndex: unable to send response")
+            }),
+
+        Db::GetTexts {
+            keyspace,
+            table,
+            column,
+            primary_key_columns,
+            primary_keys,
+            tx,
+        } => tx
+            .send(
+                source
+                    .get_texts(keyspace, table, column, primary_key_columns, primary_keys)
+                    .await,
+            )
+            .unwrap_or_else(|_| trace!("process_source: Db::GetTexts: unable to send response")),
+    }
 }
 
 pub(crate) async fn new(
@@ -388,6 +592,9 @@ fn respond_with_error(msg: Db, error: anyhow::Error) {
         Db::IsValidIndex { tx, .. } => {
             let _ = tx.send(false);
         }
+        Db::GetTexts { tx, .. } => {
+            let _ = tx.send(Err(error));
+        }
     }
 }
 
@@ -452,6 +659,21 @@ async fn process(
         Db::IsValidIndex { metadata, tx } => tx
             .send(statements.is_valid_index(metadata).await)
             .unwrap_or_else(|_| trace!("process: Db::IsValidIndex: unable to send response")),
+
+        Db::GetTexts {
+            keyspace,
+            table,
+            column,
+            primary_key_columns,
+            primary_keys,
+            tx,
+        } => tx
+            .send(
+                statements
+                    .get_texts(keyspace, table, column, primary_key_columns, primary_keys)
+                    .await,
+            )
+            .unwrap_or_else(|_| trace!("process: Db::GetTexts: unable to send response")),
     }
 }
 
@@ -493,6 +715,8 @@ fn credentials_changed(
         (Some(old_creds), Some(new_creds)) => {
             old_creds.username != new_creds.username
                 || old_creds.certificate_path != new_creds.certificate_path
+                || old_creds.client_certificate_path != new_creds.client_certificate_path
+                || old_creds.client_key_path != new_creds.client_key_path
                 || match (&old_creds.password, &new_creds.password) {
                     (None, None) => false,
                     (Some(_), None) | (None, Some(_)) => true,
@@ -512,9 +736,17 @@ struct Statements {
     st_get_indexes: PreparedStatement,
     st_get_index_target_type: PreparedStatement,
     st_get_index_options: PreparedStatement,
+    /// Statements reading the texts of a column by primary keys, prepared on first use.
+    st_get_texts: Mutex<HashMap<(KeyspaceName, TableName, ColumnName), GetTextsStatement>>,
     re_get_index_target_type: Regex,
 }
 
+/// A statement reading the texts of a column, with the primary key columns it is bound by.
+struct GetTextsStatement {
+    primary_key_columns: NonemptyArc<ColumnName>,
+    statement: PreparedStatement,
+}
+
 async fn create_session(
     config: Arc<Config>,
     node_state: &Sender<NodeState>,
@@ -559,6 +791,8 @@ async fn create_session(
         username,
         password,
         certificate_path,
+        client_certificate_path,
+        client_key_path,
     }) = &config.credentials
     {
         // Configure username/password authentication if provided
@@ -581,9 +815,27 @@ async fn create_session(
             let mut root_store = RootCertStore::empty();
             root_store.add_parsable_certificates(ca_der);
 
-            let client_cfg = ClientConfig::builder()
-                .with_root_certificates(root_store)
-                .with_no_client_auth();
+            let client_cfg = ClientConfig::builder().with_root_certificates(root_store);
+            let client_cfg = match (client_certificate_path, client_key_path) {
+                (Some(client_cert_path), Some(client_key_path)) => {
+                    let cert_pem = tokio::fs::read(client_cert_path).await.with_context(|| {
+                        format!("Failed to read client certificate file at {client_cert_path:?}")
+                    })?;
+                    let cert_chain = CertificateDer::pem_slice_iter(&cert_pem)
+                        .collect::<Result<Vec<_>, _>>()
+                        .context("Failed to parse client certificate PEM")?;
+                    let key_pem = tokio::fs::read(client_key_path).await.with_context(|| {
+                        format!("Failed to read client key file at {client_key_path:?}")
+                    })?;
+                    let key = PrivateKeyDer::from_pem_slice(&key_pem)
+                        .context("Failed to parse client key PEM")?;
+                    debug!("TLS client certificate from {client_cert_path:?}");
+                    client_cfg
+                        .with_client_auth_cert(cert_chain, key)
+                        .context("Invalid client certificate or key")?
+                }
+                _ => client_cfg.with_no_client_auth(),
+            };
 
             let tls_context = TlsContext::from(Arc::new(client_cfg));
             builder = builder.tls_context(Some(tls_context));
@@ -674,6 +926,8 @@ impl Statements {
                 .await
                 .context("ST_GET_INDEX_OPTIONS")?,
 
+            st_get_texts: Mutex::new(HashMap::new()),
+
             re_get_index_target_type: Regex::new(Self::RE_GET_INDEX_TARGET_TYPE)
                 .context("RE_GET_INDEX_TARGET_TYPE")?,
 
@@ -760,9 +1014,15 @@ impl Statements {
                         .ok_or_else(|| {
                             anyhow!("table {table_name} does not exist").context(InvalidMetadata)
                         })?;
+                    let embedding_model = options.remove("embedding_model");
+                    let payload_columns = options.remove("payload_columns");
+                    let ttl = parse_ttl_option(options.remove("ttl")).inspect_err(|err| {
+                        warn!("Skipping index {index_name} due to invalid ttl option: {err}");
+                    });
                     Ok(options.remove("target").and_then(|target| {
+                        let ttl = ttl.ok()?;
                         let kind = db_index_kind_from_options(&mut options)?;
-                        from_target_option(table, target, kind)
+                        from_target_option(table, target, kind, embedding_model.is_some())
                             .map(
                                 |(partitioning, target_column, filtering_columns)| DbCustomIndex {
                                     keyspace: keyspace_name.into(),
@@ -771,8 +1031,13 @@ impl Statements {
                                     target_columns: NonemptyArc::new([target_column])
                                         .expect("target column should be non-empty"),
                                     partitioning,
-                                    filtering_columns,
+                                    filtering_columns: with_payload_columns(
+                                        filtering_columns,
+                                        payload_columns.as_deref(),
+                                    ),
                                     kind,
+                                    embedding_model,
+                                    ttl,
                                 },
                             )
                             .inspect_err(|err| {
@@ -866,6 +1131,85 @@ impl Statements {
         }))
     }
 
+    async fn get_texts(
+        &self,
+        keyspace: KeyspaceName,
+        table: TableName,
+        column: ColumnName,
+        primary_key_columns: NonemptyArc<ColumnName>,
+        primary_keys: Vec<PrimaryKey>,
+    ) -> GetTextsR {
+        let session = self
+            .session_rx
+            .borrow()
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No active session"))?;
+        let statement = self
+            .get_texts_statement(&session, keyspace, table, column, primary_key_columns)
+            .await?;
+        futures::stream::iter(primary_keys)
+            .map(|primary_key| {
+                let session = &session;
+                let statement = &statement;
+                async move {
+                    let values: Vec<_> = (0..primary_key.len())
+                        .filter_map(|idx| primary_key.get(idx))
+                        .collect();
+                    let text = session
+                        .execute_unpaged(statement, values)
+                        .await?
+                        .into_rows_result()?
+                        .maybe_first_row::<(Option<String>,)>()?
+                        .and_then(|(text,)| text);
+                    anyhow::Ok(text)
+                }
+            })
+            .buffered(GET_TEXTS_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
+    /// Returns the cached statement of [`get_texts`](Self::get_texts), preparing it again when
+    /// the primary key columns of the table changed since, i.e. the table was recreated.
+    async fn get_texts_statement(
+        &self,
+        session: &Session,
+        keyspace: KeyspaceName,
+        table: TableName,
+        column: ColumnName,
+        primary_key_columns: NonemptyArc<ColumnName>,
+    ) -> anyhow::Result<PreparedStatement> {
+        let key = (keyspace, table, column);
+        if let Some(cached) = self.st_get_texts.lock().unwrap().get(&key)
+            && cached.primary_key_columns == primary_key_columns
+        {
+            return Ok(cached.statement.clone());
+        }
+        let (keyspace, table, column) = &key;
+        let query = db_index_backend::request_query(
+            &KeyspaceIdentifier::from(keyspace),
+            &TableIdentifier::from(table),
+            [column],
+            primary_key_columns.iter(),
+        );
+        let statement = session
+            .prepare(query)
+            .await
+            .context("get_texts")?
+            .pipe(|mut stmt| {
+                stmt.set_is_idempotent(true);
+                stmt
+            });
+        self.st_get_texts.lock().unwrap().insert(
+            key,
+            GetTextsStatement {
+                primary_key_columns,
+                statement: statement.clone(),
+            },
+        );
+        Ok(statement)
+    }
+
     async fn get_index_params(
         &self,
         keyspace: KeyspaceName,
@@ -1040,17 +1384,51 @@ fn db_index_kind_from_options(options: &mut BTreeMap<String, String>) -> Option<
     }
 }
 
+/// Parses the `ttl` option of an index, a number of seconds as the TTLs of ScyllaDB.
+fn parse_ttl_option(value: Option<String>) -> anyhow::Result<Option<Duration>> {
+    value
+        .map(|value| match value.trim().parse::<u64>() {
+            Ok(seconds) if seconds > 0 => Ok(Duration::from_secs(seconds)),
+            _ => Err(anyhow!("{value} is not a positive number of seconds")),
+        })
+        .transpose()
+}
+
+/// Adds the columns of the `payload_columns` option, a comma separated list, to the filtering
+/// columns of the target option. The table stores the values of all of them.
+fn with_payload_columns(
+    filtering_columns: Arc<[ColumnName]>,
+    payload_columns: Option<&str>,
+) -> Arc<[ColumnName]> {
+    let Some(payload_columns) = payload_columns else {
+        return filtering_columns;
+    };
+    let mut columns = filtering_columns.to_vec();
+    payload_columns
+        .split(',')
+        .map(str::trim)
+        .filter(|column| !column.is_empty())
+        .map(ColumnName::from)
+        .for_each(|column| {
+            if !columns.contains(&column) {
+                columns.push(column);
+            }
+        });
+    columns.into()
+}
+
 fn from_target_option(
     table: &Table,
     value: String,
     kind: DbIndexKind,
+    embedded: bool,
 ) -> anyhow::Result<(DbIndexPartitioning, ColumnName, Arc<[ColumnName]>)> {
     let Some(target) = parse_target_option(table, &value)? else {
         // Global index with a single target column
         return Ok((DbIndexPartitioning::Global, value.into(), Arc::new([])));
     };
 
-    validate_target_column(table, &target.target_column, kind)?;
+    validate_target_column(table, &target.target_column, kind, embedded)?;
 
     let partitioning = if target.partition_key_columns.is_empty() {
         DbIndexPartitioning::Global
@@ -1089,19 +1467,32 @@ fn validate_target_column(
     table: &Table,
     target_name: &str,
     kind: DbIndexKind,
+    embedded: bool,
 ) -> anyhow::Result<()> {
     let column = table.columns.get(target_name).ok_or_else(|| {
         anyhow!("invalid target option: column {target_name} does not exist in a table")
     })?;
-    validate_column_type_for_kind(target_name, &column.typ, kind)
+    validate_column_type_for_kind(target_name, &column.typ, kind, embedded)
 }
 
+/// Texts of a column with an embedding model are indexed by their embeddings.
 fn validate_column_type_for_kind(
     target_name: &str,
     column_type: &ColumnType,
     kind: DbIndexKind,
+    embedded: bool,
 ) -> anyhow::Result<()> {
     match kind {
+        DbIndexKind::VectorSearch if embedded => {
+            if !matches!(
+                column_type,
+                ColumnType::Native(NativeType::Text) | ColumnType::Native(NativeType::Ascii)
+            ) {
+                bail!(
+                    "invalid target option: column {target_name} to embed is not a text column in a table"
+                );
+            }
+        }
         DbIndexKind::VectorSearch => {
             if !matches!(column_type, ColumnType::Vector { .. }) {
                 bail!(
@@ -1126,9 +1517,17 @@ fn validate_column_type_for_kind(
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
+    use crate::IndexKind;
+    use crate::IndexOptionsVs;
+    use crate::Progress;
+    use crate::db_index::DbIndexExt;
+    use crate::db_index::DbIndexSource;
+    use crate::db_index::GetPrimaryKeyColumnsR;
+    use crate::db_index::GetTableColumnsR;
     use mockall::automock;
     use std::collections::HashMap;
     use std::net::SocketAddr;
+    use std::num::NonZeroUsize;
     use tracing::debug;
 
     #[automock]
@@ -1179,6 +1578,16 @@ pub(crate) mod tests {
             metadata: IndexMetadata,
             tx: oneshot::Sender<IsValidIndexR>,
         ) -> impl Future<Output = ()> + Send + 'static;
+
+        fn get_texts(
+            &self,
+            keyspace: KeyspaceName,
+            table: TableName,
+            column: ColumnName,
+            primary_key_columns: NonemptyArc<ColumnName>,
+            primary_keys: Vec<PrimaryKey>,
+            tx: oneshot::Sender<GetTextsR>,
+        ) -> impl Future<Output = ()> + Send + 'static;
     }
 
     pub(crate) fn new(sim: impl SimDb + Send + 'static) -> mpsc::Sender<Db> {
@@ -1226,6 +1635,25 @@ pub(crate) mod tests {
                         } => sim.get_index_params(keyspace, table, index, tx).await,
 
                         Db::IsValidIndex { metadata, tx } => sim.is_valid_index(metadata, tx).await,
+
+                        Db::GetTexts {
+                            keyspace,
+                            table,
+                            column,
+                            primary_key_columns,
+                            primary_keys,
+                            tx,
+                        } => {
+                            sim.get_texts(
+                                keyspace,
+                                table,
+                                column,
+                                primary_key_columns,
+                                primary_keys,
+                                tx,
+                            )
+                            .await
+                        }
                     }
                 }
 
@@ -1279,6 +1707,30 @@ pub(crate) mod tests {
         assert!(!options.contains_key("class_name"));
     }
 
+    #[test]
+    fn ttl_option_is_seconds() {
+        assert_eq!(parse_ttl_option(None).unwrap(), None);
+        assert_eq!(
+            parse_ttl_option(Some("86400".to_string())).unwrap(),
+            Some(Duration::from_secs(86400))
+        );
+        assert!(parse_ttl_option(Some("0".to_string())).is_err());
+        assert!(parse_ttl_option(Some("1d".to_string())).is_err());
+    }
+
+    #[test]
+    fn payload_columns_are_added_to_filtering_columns() {
+        let filtering_columns: Arc<[ColumnName]> = Arc::new(["a".into(), "b".into()]);
+        assert_eq!(
+            with_payload_columns(Arc::clone(&filtering_columns), None),
+            filtering_columns
+        );
+        assert_eq!(
+            with_payload_columns(Arc::clone(&filtering_columns), Some(" b, c ,,d")),
+            Arc::from(["a".into(), "b".into(), "c".into(), "d".into()])
+        );
+    }
+
     #[test]
     fn db_index_kind_from_options_absent() {
         let mut options = BTreeMap::new();
@@ -1300,13 +1752,17 @@ pub(crate) mod tests {
             typ: Box::new(ColumnType::Native(NativeType::Float)),
             dimensions: 128,
         };
-        assert!(validate_column_type_for_kind("emb", &col_type, DbIndexKind::VectorSearch).is_ok());
+        assert!(
+            validate_column_type_for_kind("emb", &col_type, DbIndexKind::VectorSearch, false)
+                .is_ok()
+        );
     }
 
     #[test]
     fn validate_vector_search_rejects_non_vector_column() {
         let col_type = ColumnType::Native(NativeType::Text);
-        let result = validate_column_type_for_kind("col", &col_type, DbIndexKind::VectorSearch);
+        let result =
+            validate_column_type_for_kind("col", &col_type, DbIndexKind::VectorSearch, false);
         assert!(result.is_err());
         assert!(
             result
@@ -1316,11 +1772,33 @@ pub(crate) mod tests {
         );
     }
 
+    #[test]
+    fn validate_vector_search_with_embedding_model_accepts_text_column() {
+        let col_type = ColumnType::Native(NativeType::Text);
+        assert!(
+            validate_column_type_for_kind("doc", &col_type, DbIndexKind::VectorSearch, true)
+                .is_ok()
+        );
+        let col_type = ColumnType::Vector {
+            typ: Box::new(ColumnType::Native(NativeType::Float)),
+            dimensions: 3,
+        };
+        let result =
+            validate_column_type_for_kind("col", &col_type, DbIndexKind::VectorSearch, true);
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("not a text column")
+        );
+    }
+
     #[test]
     fn validate_fts_accepts_text_column() {
         let col_type = ColumnType::Native(NativeType::Text);
         assert!(
-            validate_column_type_for_kind("doc", &col_type, DbIndexKind::FullTextSearch).is_ok()
+            validate_column_type_for_kind("doc", &col_type, DbIndexKind::FullTextSearch, false)
+                .is_ok()
         );
     }
 
@@ -1328,14 +1806,16 @@ pub(crate) mod tests {
     fn validate_fts_accepts_ascii_column() {
         let col_type = ColumnType::Native(NativeType::Ascii);
         assert!(
-            validate_column_type_for_kind("doc", &col_type, DbIndexKind::FullTextSearch).is_ok()
+            validate_column_type_for_kind("doc", &col_type, DbIndexKind::FullTextSearch, false)
+                .is_ok()
         );
     }
 
     #[test]
     fn validate_fts_rejects_non_text_column() {
         let col_type = ColumnType::Native(NativeType::Int);
-        let result = validate_column_type_for_kind("col", &col_type, DbIndexKind::FullTextSearch);
+        let result =
+            validate_column_type_for_kind("col", &col_type, DbIndexKind::FullTextSearch, false);
         assert!(result.is_err());
         assert!(
             result
@@ -1351,7 +1831,8 @@ pub(crate) mod tests {
             typ: Box::new(ColumnType::Native(NativeType::Float)),
             dimensions: 3,
         };
-        let result = validate_column_type_for_kind("col", &col_type, DbIndexKind::FullTextSearch);
+        let result =
+            validate_column_type_for_kind("col", &col_type, DbIndexKind::FullTextSearch, false);
         assert!(result.is_err());
         assert!(
             result
@@ -1360,4 +1841,115 @@ pub(crate) mod tests {
                 .contains("not a text column")
         );
     }
+
+    struct TestDbIndexSource;
+
+    #[async_trait]
+    impl DbIndexSource for TestDbIndexSource {
+        async fn get_primary_key_columns(&self) -> GetPrimaryKeyColumnsR {
+            NonemptyArc::new(["pk", "ck"]).unwrap()
+        }
+
+        async fn get_partition_key_count(&self) -> usize {
+            1
+        }
+
+        async fn get_table_columns(&self) -> GetTableColumnsR {
+            Arc::new(HashMap::from([("ck".into(), NativeType::Int)]))
+        }
+
+        async fn full_scan_progress(&self) -> Progress {
+            Progress::Done
+        }
+    }
+
+    struct TestDbSource;
+
+    #[async_trait]
+    impl DbSource for TestDbSource {
+        async fn get_db_index(&self, _: IndexMetadata) -> GetDbIndexR {
+            let (_, rx) = mpsc::channel(1);
+            Ok((crate::db_index::new_from_source(TestDbIndexSource), rx))
+        }
+
+        async fn latest_schema_version(&self) -> LatestSchemaVersionR {
+            Ok(None)
+        }
+
+        async fn get_indexes(&self) -> GetIndexesR {
+            Ok(Vec::new())
+        }
+
+        async fn get_index_version(
+            &self,
+            _: KeyspaceName,
+            _: TableName,
+            _: IndexName,
+        ) -> GetIndexVersionR {
+            Ok(None)
+        }
+
+        async fn get_index_target_type(
+            &self,
+            _: KeyspaceName,
+            _: TableName,
+            _: ColumnName,
+            _: IndexName,
+        ) -> GetIndexTargetTypeR {
+            Ok(Some(NonZeroUsize::new(3).unwrap().into()))
+        }
+
+        async fn get_index_params(
+            &self,
+            _: KeyspaceName,
+            _: TableName,
+            _: IndexName,
+        ) -> GetIndexParamsR {
+            Ok(None)
+        }
+
+        async fn is_valid_index(&self, _: IndexMetadata) -> IsValidIndexR {
+            true
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_requests_from_custom_source() {
+        let db = new_from_source(TestDbSource);
+        let metadata = IndexMetadata {
+            keyspace_name: "ks".into(),
+            index_name: "idx".into(),
+            table_name: "tbl".into(),
+            target_columns: NonemptyArc::new(["embedding"]).unwrap(),
+            partitioning: DbIndexPartitioning::Global,
+            filtering_columns: Arc::new([]),
+            version: Uuid::new_v4().into(),
+            kind: IndexKind::Vs(IndexOptionsVs {
+                dimensions: NonZeroUsize::new(3).unwrap().into(),
+                connectivity: Default::default(),
+                expansion_add: Default::default(),
+                expansion_search: Default::default(),
+                space_type: Default::default(),
+                quantization: Default::default(),
+                embedding_model: None,
+                ttl: None,
+            }),
+        };
+
+        assert!(db.get_indexes().await.unwrap().is_empty());
+        assert!(db.latest_schema_version().await.unwrap().is_none());
+        assert_eq!(
+            db.get_index_target_type("ks".into(), "tbl".into(), "embedding".into(), "idx".into())
+                .await
+                .unwrap(),
+            Some(NonZeroUsize::new(3).unwrap().into())
+        );
+        assert!(db.is_valid_index(metadata.clone()).await);
+
+        let (db_index, _) = db.get_db_index(metadata).await.unwrap();
+        assert_eq!(db_index.get_partition_key_count().await, 1);
+        assert_eq!(db_index.get_primary_key_columns().await.len().get(), 2);
+        assert_eq!(db_index.full_scan_progress().await, Progress::Done);
+        assert!(db_index.get_vectors(Vec::new()).await.is_err());
+    }
 }
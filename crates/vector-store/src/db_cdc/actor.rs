@@ -10,6 +10,7 @@ use crate::IndexMetadata;
 use crate::IndexName;
 use crate::KeyspaceName;
 use crate::Metrics;
+use crate::Timestamp;
 use crate::db_cdc::READER_FINE;
 use crate::db_cdc::READER_WIDE;
 use crate::db_cdc::checkpoint_saver::MetricsCheckpointSaver;
@@ -115,6 +116,7 @@ pub(crate) fn new(
     tx_embeddings: mpsc::Sender<(DbIndexedRow, AsyncInProgress)>,
     semaphore: Arc<Semaphore>,
     config: CdcReaderConfig,
+    progress: Arc<watch::Sender<Timestamp>>,
 ) -> mpsc::Sender<DbCdc> {
     let (tx, mut rx) = mpsc::channel::<DbCdc>(perf::channel_size().into());
 
@@ -129,6 +131,7 @@ pub(crate) fn new(
         metadata.keyspace_name.clone(),
         metadata.index_name.clone(),
         semaphore,
+        progress,
     );
     let name = reader.name;
     let actor_key = metadata.key();
@@ -211,6 +214,7 @@ struct CdcReaderState {
     metrics: Arc<Metrics>,
     keyspace: KeyspaceName,
     index_name: IndexName,
+    progress: Arc<watch::Sender<Timestamp>>,
 }
 
 impl CdcReaderState {
@@ -221,6 +225,7 @@ impl CdcReaderState {
         keyspace: KeyspaceName,
         index_name: IndexName,
         semaphore: Arc<Semaphore>,
+        progress: Arc<watch::Sender<Timestamp>>,
     ) -> Self {
         let state = Self {
             reader: None,
@@ -234,6 +239,7 @@ impl CdcReaderState {
             metrics,
             keyspace,
             index_name,
+            progress,
         };
         state.set_reader_metric_down();
         state
@@ -299,6 +305,7 @@ impl CdcReaderState {
             Arc::clone(&self.semaphore),
             Arc::clone(&self.metrics),
             self.name,
+            Arc::clone(&self.progress),
         )
         .await
         {
@@ -445,6 +452,7 @@ async fn create_cdc_reader(
     semaphore: Arc<Semaphore>,
     metrics: Arc<Metrics>,
     reader_name: &str,
+    progress: Arc<watch::Sender<Timestamp>>,
 ) -> anyhow::Result<(
     scylla_cdc::log_reader::CDCLogReader,
     impl std::future::Future<Output = anyhow::Result<()>>,
@@ -470,6 +478,7 @@ async fn create_cdc_reader(
         metadata.keyspace_name.as_ref().to_string(),
         metadata.index_name.as_ref().to_string(),
         reader_name.to_string(),
+        progress,
     ));
 
     CDCLogReaderBuilder::new()
@@ -481,6 +490,8 @@ async fn create_cdc_reader(
         .safety_interval(params.safety_interval)
         .sleep_interval(params.sleep_interval)
         .should_save_progress(true)
+        // Checkpoints publish the progress used by queries waiting for their writes.
+        .pause_between_saves(params.sleep_interval)
         .checkpoint_saver(checkpoint_saver)
         .build()
         .await
@@ -552,6 +563,7 @@ mod tests {
             "ks".into(),
             "idx".into(),
             Semaphore::new(1).into(),
+            Arc::new(watch::channel(Timestamp::MIN).0),
         )
     }
 
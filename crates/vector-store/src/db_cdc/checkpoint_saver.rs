@@ -4,6 +4,7 @@
  */
 
 use crate::Metrics;
+use crate::Timestamp;
 use async_trait::async_trait;
 use scylla_cdc::cdc_types::GenerationTimestamp;
 use scylla_cdc::cdc_types::StreamID;
@@ -14,6 +15,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::Duration;
+use tokio::sync::watch;
 
 /// Updates the `cdc_last_processed_timestamp_seconds` gauge instead of persisting to storage.
 /// Reports the minimum timestamp across all streams.
@@ -23,6 +25,8 @@ pub(super) struct MetricsCheckpointSaver {
     index_name: String,
     reader_name: String,
     state: Mutex<StreamProgress>,
+    /// The timestamp up to which any reader of the index has read the changes, never moving back.
+    progress: Arc<watch::Sender<Timestamp>>,
 }
 
 /// Tracks progress across all streams to compute the reader-wide minimum.
@@ -63,6 +67,7 @@ impl MetricsCheckpointSaver {
         keyspace: String,
         index_name: String,
         reader_name: String,
+        progress: Arc<watch::Sender<Timestamp>>,
     ) -> Self {
         Self {
             metrics,
@@ -70,6 +75,7 @@ impl MetricsCheckpointSaver {
             index_name,
             reader_name,
             state: Mutex::new(StreamProgress::default()),
+            progress,
         }
     }
 
@@ -80,6 +86,15 @@ impl MetricsCheckpointSaver {
                 .cdc_last_processed_timestamp_seconds
                 .with_label_values(&[&self.keyspace, &self.index_name, &self.reader_name])
                 .set(progress.as_secs_f64());
+            let timestamp =
+                Timestamp::from_micros(u64::try_from(progress.as_micros()).unwrap_or(u64::MAX));
+            self.progress.send_if_modified(|current| {
+                let advanced = timestamp > *current;
+                if advanced {
+                    *current = timestamp;
+                }
+                advanced
+            });
         }
     }
 
@@ -140,6 +155,7 @@ mod tests {
             "ks".to_string(),
             "idx".to_string(),
             READER_WIDE.to_string(),
+            Arc::new(watch::channel(Timestamp::MIN).0),
         );
 
         saver.record_stream_progress(stream_id(1), Duration::from_secs(1_700_000_100));
@@ -174,6 +190,7 @@ mod tests {
             "ks".to_string(),
             "idx".to_string(),
             READER_FINE.to_string(),
+            Arc::new(watch::channel(Timestamp::MIN).0),
         );
 
         saver.record_stream_progress(stream_id(1), Duration::from_secs(1_700_000_000));
@@ -200,6 +217,7 @@ mod tests {
             "ks".to_string(),
             "idx".to_string(),
             READER_WIDE.to_string(),
+            Arc::new(watch::channel(Timestamp::MIN).0),
         );
 
         // Two streams at the same timestamp.
@@ -229,6 +247,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn checkpoint_savers_publish_the_furthest_progress_of_the_index() {
+        let metrics = Arc::new(Metrics::new());
+        let (progress_tx, progress_rx) = watch::channel(Timestamp::MIN);
+        let progress_tx = Arc::new(progress_tx);
+        let new_saver = |reader_name: &str| {
+            MetricsCheckpointSaver::new(
+                Arc::clone(&metrics),
+                "ks".to_string(),
+                "idx".to_string(),
+                reader_name.to_string(),
+                Arc::clone(&progress_tx),
+            )
+        };
+        let wide = new_saver(READER_WIDE);
+        let fine = new_saver(READER_FINE);
+
+        fine.record_stream_progress(stream_id(1), Duration::from_secs(1_700_000_100));
+        fine.record_stream_progress(stream_id(2), Duration::from_secs(1_700_000_050));
+        assert_eq!(
+            *progress_rx.borrow(),
+            Timestamp::from_seconds(1_700_000_050)
+        );
+
+        // The wide reader lags behind, which must not move the progress back.
+        wide.record_stream_progress(stream_id(1), Duration::from_secs(1_700_000_000));
+        assert_eq!(
+            *progress_rx.borrow(),
+            Timestamp::from_seconds(1_700_000_050)
+        );
+
+        fine.record_stream_progress(stream_id(2), Duration::from_secs(1_700_000_200));
+        assert_eq!(
+            *progress_rx.borrow(),
+            Timestamp::from_seconds(1_700_000_100)
+        );
+    }
+
     #[tokio::test]
     async fn checkpoint_saver_never_loads_progress() {
         let saver = MetricsCheckpointSaver::new(
@@ -236,6 +292,7 @@ mod tests {
             "ks".to_string(),
             "idx".to_string(),
             READER_WIDE.to_string(),
+            Arc::new(watch::channel(Timestamp::MIN).0),
         );
 
         assert!(saver.load_last_generation().await.unwrap().is_none());
@@ -11,4 +11,5 @@ pub(crate) const READER_WIDE: &str = "wide";
 pub(crate) const READER_FINE: &str = "fine";
 
 pub(crate) use actor::CdcReaderConfig;
+pub(crate) use actor::DbCdc;
 pub(crate) use actor::new;
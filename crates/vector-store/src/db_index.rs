@@ -21,9 +21,12 @@ use crate::PrimaryKey;
 use crate::Progress;
 use crate::TableIdentifier;
 use crate::Timestamp;
+use crate::TokenOwnership;
+use crate::TokenRanges;
 use crate::Vector;
 use crate::db_cdc;
 use crate::db_cdc::CdcReaderConfig;
+use crate::db_cdc::DbCdc;
 use crate::db_index_backend;
 use crate::internals::Internals;
 use crate::invariant_key::InvariantKey;
@@ -32,9 +35,12 @@ use crate::node_state::NodeState;
 use crate::node_state::NodeStateExt;
 use crate::perf;
 use crate::timestamp::Timestamped;
+use crate::token_ranges::TOKEN_MAX;
+use crate::token_ranges::TOKEN_MIN;
 use anyhow::Context;
 use anyhow::anyhow;
 use anyhow::bail;
+use async_trait::async_trait;
 use futures::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
@@ -50,7 +56,9 @@ use scylla::value::CqlValue;
 use scylla::value::Row;
 use scylla_cdc::CqlIdentifier;
 use std::collections::HashMap;
+use std::future;
 use std::iter;
+use std::net::SocketAddr;
 use std::num::NonZeroUsize;
 use std::pin::Pin;
 use std::sync::Arc;
@@ -71,14 +79,17 @@ use tracing::info;
 use tracing::trace;
 use tracing::warn;
 
-type GetPrimaryKeyColumnsR = NonemptyArc<ColumnName>;
-type GetTableColumnsR = Arc<HashMap<ColumnName, NativeType>>;
+pub type GetPrimaryKeyColumnsR = NonemptyArc<ColumnName>;
+pub type GetTableColumnsR = Arc<HashMap<ColumnName, NativeType>>;
+pub type GetVectorsR = anyhow::Result<Vec<Option<Vector>>>;
 type RangeScanResult =
     anyhow::Result<Pin<Box<dyn Stream<Item = DbIndexedRow> + std::marker::Send>>, anyhow::Error>;
 
 const START_RETRY_TIMEOUT: Duration = Duration::from_millis(100);
 const RETRY_TIMEOUT_LIMIT: Duration = Duration::from_secs(16);
 const INCREASE_RATE: u32 = 2;
+/// Concurrent reads of the vectors of rows.
+const GET_VECTORS_CONCURRENCY: usize = 16;
 
 impl From<u64> for Percentage {
     fn from(value: u64) -> Self {
@@ -96,6 +107,8 @@ impl From<u64> for Progress {
     }
 }
 
+/// Messages handled by a per-index db actor. The actor is returned by [`crate::db::Db::GetDbIndex`]
+/// together with the stream of indexed rows.
 pub enum DbIndex {
     GetPrimaryKeyColumns {
         tx: oneshot::Sender<GetPrimaryKeyColumnsR>,
@@ -109,6 +122,15 @@ pub enum DbIndex {
     FullScanProgress {
         tx: oneshot::Sender<Progress>,
     },
+    /// Timestamp up to which the changes of the table have been read.
+    CdcProgress {
+        tx: oneshot::Sender<watch::Receiver<Timestamp>>,
+    },
+    /// Full precision vectors of the rows, in the order of the keys.
+    GetVectors {
+        primary_keys: Vec<PrimaryKey>,
+        tx: oneshot::Sender<GetVectorsR>,
+    },
 }
 
 pub(crate) trait DbIndexExt {
@@ -116,6 +138,8 @@ pub(crate) trait DbIndexExt {
     async fn get_partition_key_count(&self) -> usize;
     async fn get_table_columns(&self) -> GetTableColumnsR;
     async fn full_scan_progress(&self) -> Progress;
+    async fn cdc_progress(&self) -> watch::Receiver<Timestamp>;
+    async fn get_vectors(&self, primary_keys: Vec<PrimaryKey>) -> GetVectorsR;
 }
 
 impl DbIndexExt for mpsc::Sender<DbIndex> {
@@ -150,6 +174,97 @@ impl DbIndexExt for mpsc::Sender<DbIndex> {
             .expect("internal actor should receive request");
         rx.await.expect("internal actor should send response")
     }
+
+    async fn cdc_progress(&self) -> watch::Receiver<Timestamp> {
+        let (tx, rx) = oneshot::channel();
+        self.send(DbIndex::CdcProgress { tx })
+            .await
+            .expect("internal actor should receive request");
+        rx.await.expect("internal actor should send response")
+    }
+
+    async fn get_vectors(&self, primary_keys: Vec<PrimaryKey>) -> GetVectorsR {
+        let (tx, rx) = oneshot::channel();
+        self.send(DbIndex::GetVectors { primary_keys, tx }).await?;
+        rx.await?
+    }
+}
+
+/// A table backing a single index, implemented by custom data sources.
+///
+/// Use [`new_from_source`] to serve the [`DbIndex`] actor protocol from an implementation.
+#[async_trait]
+pub trait DbIndexSource: Send + Sync + 'static {
+    /// Primary key columns of the table, partition key columns first.
+    async fn get_primary_key_columns(&self) -> GetPrimaryKeyColumnsR;
+
+    /// Number of leading primary key columns that form the partition key.
+    async fn get_partition_key_count(&self) -> usize;
+
+    /// Types of the table columns which can be used for filtering.
+    async fn get_table_columns(&self) -> GetTableColumnsR;
+
+    /// Progress of the initial scan of the table.
+    async fn full_scan_progress(&self) -> Progress;
+
+    /// Timestamp up to which the changes of the table have been read. By default all changes are
+    /// considered read.
+    async fn cdc_progress(&self) -> watch::Receiver<Timestamp> {
+        watch::channel(Timestamp::MAX).1
+    }
+
+    /// Sources without rows to read fail the rescoring of search results.
+    async fn get_vectors(&self, _primary_keys: Vec<PrimaryKey>) -> GetVectorsR {
+        bail!("the data source does not support reading rows")
+    }
+}
+
+/// Starts a [`DbIndex`] actor answering requests from the given source.
+pub fn new_from_source(source: impl DbIndexSource) -> mpsc::Sender<DbIndex> {
+    let (tx, mut rx) = mpsc::channel(perf::channel_size().into());
+    tokio::spawn(
+        async move {
+            while let Some(msg) = rx.recv().await {
+                match msg {
+                    DbIndex::GetPrimaryKeyColumns { tx } => tx
+                        .send(source.get_primary_key_columns().await)
+                        .unwrap_or_else(|_| {
+                            trace!("DbIndex::GetPrimaryKeyColumns: unable to send response")
+                        }),
+
+                    DbIndex::GetPartitionKeyCount { tx } => tx
+                        .send(source.get_partition_key_count().await)
+                        .unwrap_or_else(|_| {
+                            trace!("DbIndex::GetPartitionKeyCount: unable to send response")
+                        }),
+
+                    DbIndex::GetTableColumns { tx } => tx
+                        .send(source.get_table_columns().await)
+                        .unwrap_or_else(|_| {
+                            trace!("DbIndex::GetTableColumns: unable to send response")
+                        }),
+
+                    DbIndex::FullScanProgress { tx } => tx
+                        .send(source.full_scan_progress().await)
+                        .unwrap_or_else(|_| {
+                            trace!("DbIndex::FullScanProgress: unable to send response")
+                        }),
+
+                    DbIndex::CdcProgress { tx } => {
+                        tx.send(source.cdc_progress().await).unwrap_or_else(|_| {
+                            trace!("DbIndex::CdcProgress: unable to send response")
+                        })
+                    }
+
+                    DbIndex::GetVectors { primary_keys, tx } => tx
+                        .send(source.get_vectors(primary_keys).await)
+                        .unwrap_or_else(|_| trace!("DbIndex::GetVectors: unable to send response")),
+                }
+            }
+        }
+        .instrument(error_span!("db-index-source")),
+    );
+    tx
 }
 
 pub(crate) async fn new(
@@ -179,33 +294,40 @@ pub(crate) async fn new(
         }
     }
 
-    let statements = Arc::new(Statements::new(statements_session_rx, metadata.clone()).await?);
+    let config = config_rx.borrow().clone();
+    let statements =
+        Arc::new(Statements::new(statements_session_rx, metadata.clone(), &config).await?);
 
     let semaphore = Arc::new(Semaphore::new(concurrency_limit()));
-
-    // Create wide-framed CDC actor
-    let cdc_wide = db_cdc::new(
-        config_rx.clone(),
-        session_rx.clone(),
-        metadata.clone(),
-        internals.clone(),
-        metrics.clone(),
-        tx_embeddings.clone(),
-        Arc::clone(&semaphore),
-        CdcReaderConfig::Wide,
-    );
-
-    // Create fine-grained CDC actor
-    let cdc_fine = db_cdc::new(
-        config_rx,
-        session_rx.clone(),
-        metadata.clone(),
-        internals,
-        metrics,
-        tx_embeddings.clone(),
-        semaphore,
-        CdcReaderConfig::Fine,
-    );
+    let (cdc_progress_tx, cdc_progress) = watch::channel(Timestamp::MIN);
+    let cdc_progress_tx = Arc::new(cdc_progress_tx);
+
+    let cdc_mode = config.cdc_modes.get(&key).copied().unwrap_or_default();
+    let tx_cdc = if statements.token_filter.is_some() {
+        filter_cdc(Arc::clone(&statements), tx_embeddings.clone())
+    } else {
+        tx_embeddings.clone()
+    };
+    let cdc_reader = |config| {
+        db_cdc::new(
+            config_rx.clone(),
+            session_rx.clone(),
+            metadata.clone(),
+            internals.clone(),
+            metrics.clone(),
+            tx_cdc.clone(),
+            Arc::clone(&semaphore),
+            config,
+            Arc::clone(&cdc_progress_tx),
+        )
+    };
+    // Create wide-framed and fine-grained CDC actors selected for the index
+    let cdc_wide = cdc_mode
+        .reads_wide()
+        .then(|| cdc_reader(CdcReaderConfig::Wide));
+    let cdc_fine = cdc_mode
+        .reads_fine()
+        .then(|| cdc_reader(CdcReaderConfig::Fine));
 
     // Signal from the main db_index task to the CDC monitor task that
     // this db_index is shutting down. Without it, the monitor would keep
@@ -219,8 +341,8 @@ pub(crate) async fn new(
         let db_index_stopped = Arc::clone(&db_index_stopped);
         async move {
             tokio::select! {
-                _ = cdc_wide.closed() => cdc_error_notify.notify_one(),
-                _ = cdc_fine.closed() => cdc_error_notify.notify_one(),
+                _ = closed(cdc_wide.as_ref()) => cdc_error_notify.notify_one(),
+                _ = closed(cdc_fine.as_ref()) => cdc_error_notify.notify_one(),
                 _ = db_index_stopped.notified() => {}
             }
         }
@@ -237,10 +359,9 @@ pub(crate) async fn new(
 
             info!("starting full scan on {}", metadata.key());
 
-            let mut initial_scan = Box::pin(statements.initial_scan(
-                tx_embeddings.clone(),
-                completed_scan_length.clone(),
-            ));
+            let mut initial_scan = Box::pin(
+                statements.initial_scan(tx_embeddings.clone(), completed_scan_length.clone()),
+            );
 
             // Initial scan and message processing loop
             loop {
@@ -255,7 +376,12 @@ pub(crate) async fn new(
                     msg = rx_index.recv() => {
                         match msg {
                             Some(msg) => {
-                                tokio::spawn(process(Arc::clone(&statements), msg, completed_scan_length.clone()));
+                                tokio::spawn(process(
+                                    Arc::clone(&statements),
+                                    msg,
+                                    completed_scan_length.clone(),
+                                    cdc_progress.clone(),
+                                ));
                             }
                             None => {
                                 // Index was dropped, stop fullscan.
@@ -274,7 +400,12 @@ pub(crate) async fn new(
 
             // Continue processing messages after scan completes
             while let Some(msg) = rx_index.recv().await {
-                tokio::spawn(process(Arc::clone(&statements), msg, completed_scan_length.clone()));
+                tokio::spawn(process(
+                    Arc::clone(&statements),
+                    msg,
+                    completed_scan_length.clone(),
+                    cdc_progress.clone(),
+                ));
             }
 
             db_index_stopped.notify_one();
@@ -286,7 +417,47 @@ pub(crate) async fn new(
     Ok((tx_index, rx_embeddings))
 }
 
-async fn process(statements: Arc<Statements>, msg: DbIndex, completed_scan_length: Arc<AtomicU64>) {
+/// Forwards the rows read by the CDC readers of the token ranges indexed by the node.
+fn filter_cdc(
+    statements: Arc<Statements>,
+    tx_embeddings: mpsc::Sender<(DbIndexedRow, AsyncInProgress)>,
+) -> mpsc::Sender<(DbIndexedRow, AsyncInProgress)> {
+    let (tx, mut rx) =
+        mpsc::channel::<(DbIndexedRow, AsyncInProgress)>(perf::channel_size().into());
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                row = rx.recv() => {
+                    let Some((row, in_progress)) = row else {
+                        break;
+                    };
+                    if statements.is_indexed(&row.primary_key)
+                        && tx_embeddings.send((row, in_progress)).await.is_err()
+                    {
+                        break;
+                    }
+                }
+                _ = tx_embeddings.closed() => break,
+            }
+        }
+    });
+    tx
+}
+
+/// Waits until the CDC actor is closed, forever for a reader not selected for the index.
+async fn closed(cdc: Option<&mpsc::Sender<DbCdc>>) {
+    match cdc {
+        Some(cdc) => cdc.closed().await,
+        None => future::pending().await,
+    }
+}
+
+async fn process(
+    statements: Arc<Statements>,
+    msg: DbIndex,
+    completed_scan_length: Arc<AtomicU64>,
+    cdc_progress: watch::Receiver<Timestamp>,
+) {
     match msg {
         DbIndex::GetPrimaryKeyColumns { tx } => tx
             .send(statements.get_primary_key_columns())
@@ -309,6 +480,12 @@ async fn process(statements: Arc<Statements>, msg: DbIndex, completed_scan_lengt
                 trace!("process: Db::FullScanProgress: unable to send response");
             }
         }
+        DbIndex::CdcProgress { tx } => tx
+            .send(cdc_progress)
+            .unwrap_or_else(|_| trace!("process: Db::CdcProgress: unable to send response")),
+        DbIndex::GetVectors { primary_keys, tx } => tx
+            .send(statements.get_vectors(primary_keys).await)
+            .unwrap_or_else(|_| trace!("process: Db::GetVectors: unable to send response")),
     }
 }
 
@@ -320,13 +497,84 @@ struct Statements {
     filtering_columns: Arc<[ColumnName]>,
     table_columns: GetTableColumnsR,
     st_range_scan: PreparedStatement,
+    token_filter: Option<TokenFilter>,
     kind: IndexKind,
+    keyspace: KeyspaceIdentifier,
+    table: TableIdentifier,
+}
+
+/// Rows of the token ranges indexed by the node, when it does not index whole tables.
+struct TokenFilter {
+    ranges: TokenRanges,
+    partition_key_count: usize,
+}
+
+impl TokenFilter {
+    async fn new(
+        session: &Session,
+        ownership: &TokenOwnership,
+        config: &Config,
+        partition_key_count: usize,
+    ) -> anyhow::Result<Self> {
+        let ranges = match ownership {
+            TokenOwnership::Ranges(ranges) => ranges.clone(),
+            TokenOwnership::Local => local_token_ranges(session, config).await?,
+        };
+        Ok(Self {
+            ranges,
+            partition_key_count,
+        })
+    }
+
+    /// Whether the row is within the token ranges. Rows whose token cannot be computed are
+    /// skipped.
+    fn contains(&self, primary_key: &PrimaryKey) -> bool {
+        if primary_key.len() < self.partition_key_count {
+            warn!(
+                "skipping a row with {} primary key columns, fewer than {} partition key columns",
+                primary_key.len(),
+                self.partition_key_count
+            );
+            return false;
+        }
+        let token = primary_key.token(self.partition_key_count);
+        self.ranges.contains(token.value())
+    }
+}
+
+/// Primary token ranges of the ScyllaDB node the vector store connects to.
+async fn local_token_ranges(session: &Session, config: &Config) -> anyhow::Result<TokenRanges> {
+    let uri = &config.scylladb_uri;
+    let mut addrs: Vec<SocketAddr> = tokio::net::lookup_host(uri)
+        .await
+        .with_context(|| format!("unable to resolve {uri}"))?
+        .collect();
+    // Nodes are known by their addresses before the translation.
+    if let Some(translation_map) = &config.cql_uri_translation_map {
+        let untranslated = translation_map
+            .iter()
+            .filter(|(_, to)| addrs.contains(to))
+            .map(|(from, _)| *from)
+            .collect_vec();
+        addrs.extend(untranslated);
+    }
+    let ips = addrs.iter().map(SocketAddr::ip).collect_vec();
+    TokenRanges::primary(
+        session
+            .get_cluster_state()
+            .replica_locator()
+            .ring()
+            .iter()
+            .map(|(token, node)| (token.value(), ips.contains(&node.address.ip()))),
+    )
+    .ok_or_else(|| anyhow!("ScyllaDB node at {uri} owns no tokens"))
 }
 
 impl Statements {
     async fn new(
         session_rx: tokio::sync::watch::Receiver<Option<Arc<Session>>>,
         metadata: IndexMetadata,
+        config: &Config,
     ) -> anyhow::Result<Self> {
         let session = session_rx
             .borrow()
@@ -415,6 +663,20 @@ impl Statements {
                 stmt
             });
 
+        let token_filter = match &config.token_ownership {
+            Some(ownership) => {
+                let token_filter =
+                    TokenFilter::new(&session, ownership, config, partition_key_count).await?;
+                info!(
+                    "indexing {} tokens of {}",
+                    token_filter.ranges.tokens(),
+                    metadata.key()
+                );
+                Some(token_filter)
+            }
+            None => None,
+        };
+
         Ok(Self {
             primary_key_columns,
             partition_key_count,
@@ -422,11 +684,21 @@ impl Statements {
             filtering_columns,
             table_columns,
             st_range_scan,
+            token_filter,
             session_rx,
             kind: metadata.kind.clone(),
+            keyspace: keyspace_identifier,
+            table: table_identifier,
         })
     }
 
+    /// Whether the row is indexed by the node.
+    fn is_indexed(&self, primary_key: &PrimaryKey) -> bool {
+        self.token_filter
+            .as_ref()
+            .is_none_or(|filter| filter.contains(primary_key))
+    }
+
     fn get_primary_key_columns(&self) -> GetPrimaryKeyColumnsR {
         self.primary_key_columns.clone()
     }
@@ -435,6 +707,54 @@ impl Statements {
         self.table_columns.clone()
     }
 
+    /// Reads the vectors of the target column of the rows. Rows without a vector, or removed
+    /// since, get none.
+    async fn get_vectors(&self, primary_keys: Vec<PrimaryKey>) -> GetVectorsR {
+        let column = match (self.target_columns.as_slice(), &self.kind) {
+            ([column], IndexKind::Vs(options)) if options.embedding_model.is_none() => column,
+            _ => bail!("only vectors of a single vector column can be read from the table"),
+        };
+        let session = self
+            .session_rx
+            .borrow()
+            .clone()
+            .ok_or_else(|| anyhow!("No active session"))?;
+        let query = db_index_backend::request_query(
+            &self.keyspace,
+            &self.table,
+            [column],
+            self.primary_key_columns.iter(),
+        );
+        let statement = session
+            .prepare(query)
+            .await
+            .context("get_vectors")?
+            .pipe(|mut stmt| {
+                stmt.set_is_idempotent(true);
+                stmt
+            });
+        futures::stream::iter(primary_keys)
+            .map(|primary_key| {
+                let session = &session;
+                let statement = &statement;
+                async move {
+                    let values: Vec<_> = (0..primary_key.len())
+                        .filter_map(|idx| primary_key.get(idx))
+                        .collect();
+                    let value = session
+                        .execute_unpaged(statement, values)
+                        .await?
+                        .into_rows_result()?
+                        .maybe_first_row::<Row>()?
+                        .and_then(|row| row.columns.into_iter().next().flatten());
+                    value.map(Vector::try_from).transpose()
+                }
+            })
+            .buffered(GET_VECTORS_CONCURRENCY)
+            .try_collect()
+            .await
+    }
+
     async fn preform_range_scan(&self, begin: Token, end: Token) -> RangeScanResult {
         let mut range_scan = self.range_scan_stream(begin, end).await;
         let mut retry_timeout = START_RETRY_TIMEOUT;
@@ -474,6 +794,11 @@ impl Statements {
     ) {
         let semaphore_capacity = self.nr_parallel_queries().get();
         let semaphore = Arc::new(Semaphore::new(semaphore_capacity));
+        let scan_tokens = self
+            .token_filter
+            .as_ref()
+            .map_or(u64::MAX, |filter| filter.ranges.tokens());
+        let scanned_tokens = Arc::new(AtomicU64::new(0));
 
         for (begin, end) in self.fullscan_ranges() {
             let permit = Arc::clone(&semaphore).acquire_owned().await.unwrap();
@@ -482,6 +807,7 @@ impl Statements {
             if let Ok(embeddings) = range_scan {
                 let tx = tx.clone();
                 let scan_length = completed_scan_length.clone();
+                let scanned_tokens = Arc::clone(&scanned_tokens);
                 tokio::spawn(async move {
                     let (tx_in_progress, mut rx_in_progress) = mpsc::channel(1);
                     embeddings
@@ -502,8 +828,13 @@ impl Statements {
                     }
 
                     //Safety: end > begin, and the range fits into u64
-                    scan_length.fetch_add(
-                        end.value().abs_diff(begin.value() - 1),
+                    let range_tokens = end.value().abs_diff(begin.value() - 1);
+                    let scanned = scanned_tokens
+                        .fetch_add(range_tokens, std::sync::atomic::Ordering::Relaxed)
+                        + range_tokens;
+                    // The progress is u64::MAX when all tokens indexed by the node are scanned.
+                    scan_length.fetch_max(
+                        (scanned as u128 * u64::MAX as u128 / scan_tokens as u128) as u64,
                         std::sync::atomic::Ordering::Relaxed,
                     );
                     drop(permit);
@@ -554,11 +885,8 @@ impl Statements {
     /// from the token ring, the first item is the lowest possible token, and the last item is the
     /// highest possible token - for support the specific token range after the highest token to
     /// the lowest token. The highest possible token value is not decremented, because it doesn't
-    /// start a new range.
+    /// start a new range. Ranges are limited to the token ranges indexed by the node.
     fn fullscan_ranges(&self) -> impl Iterator<Item = (Token, Token)> {
-        const TOKEN_MAX: i64 = i64::MAX; // the highest possible token value in the ScyllaDB
-        const TOKEN_MIN: i64 = -TOKEN_MAX; // the lowest possible token value in the ScyllaDB
-
         let tokens = iter::once(Token::new(TOKEN_MIN))
             .chain(
                 self.session_rx
@@ -589,6 +917,14 @@ impl Statements {
                     (begin, Token::new(end.value() - 1))
                 }
             })
+            .flat_map(|(begin, end)| match &self.token_filter {
+                Some(filter) => filter
+                    .ranges
+                    .intersect(begin.value(), end.value())
+                    .map(|(begin, end)| (Token::new(begin), Token::new(end)))
+                    .collect_vec(),
+                None => vec![(begin, end)],
+            })
     }
 
     async fn range_scan_stream(
@@ -734,10 +1070,11 @@ fn parse_primary_key(columns: impl IntoIterator<Item = Option<CqlValue>>) -> Opt
 
 fn parse_indexed_value(value: CqlValue, kind: &IndexKind) -> anyhow::Result<DbIndexedValue> {
     match kind {
-        IndexKind::Vs(_) => Vector::try_from(value)
+        IndexKind::Vs(options) if options.embedding_model.is_none() => Vector::try_from(value)
             .map_err(|err| anyhow!("parse_indexed_value: {err}"))
             .map(DbIndexedValue::Vector),
-        IndexKind::Fts(_) => match value {
+        // Texts to embed are indexed as documents until monitor_items embeds them.
+        IndexKind::Vs(_) | IndexKind::Fts(_) => match value {
             CqlValue::Text(s) => Ok(DbIndexedValue::Document(s)),
             CqlValue::Ascii(s) => Ok(DbIndexedValue::Document(s)),
             other => {
@@ -774,11 +1111,32 @@ mod tests {
             expansion_search: ExpansionSearch::default(),
             space_type: SpaceType::default(),
             quantization: Quantization::default(),
+            embedding_model: None,
+            ttl: None,
         })
     }
 
     fn fts_kind() -> IndexKind {
-        IndexKind::Fts(IndexOptionsFts {})
+        IndexKind::Fts(IndexOptionsFts { ttl: None })
+    }
+
+    #[test]
+    fn token_filter_checks_the_token_of_the_partition_key() {
+        let key = PrimaryKey::from([CqlValue::Int(1), CqlValue::Int(2)]);
+        let token = key.token(1).value();
+        let filter = |ranges: Vec<(i64, i64)>| TokenFilter {
+            ranges: ranges.try_into().unwrap(),
+            partition_key_count: 1,
+        };
+
+        assert!(filter(vec![(token, token)]).contains(&key));
+        assert!(!filter(vec![(TOKEN_MIN, token - 1)]).contains(&key));
+
+        let filter = TokenFilter {
+            ranges: vec![(TOKEN_MIN, TOKEN_MAX)].try_into().unwrap(),
+            partition_key_count: 3,
+        };
+        assert!(!filter.contains(&key));
     }
 
     #[test]
@@ -822,6 +1180,22 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_indexed_value_document_for_embedding_model() {
+        let IndexKind::Vs(options) = vs_kind() else {
+            unreachable!()
+        };
+        let kind = IndexKind::Vs(IndexOptionsVs {
+            embedding_model: Some("model".to_string()),
+            ..options
+        });
+        let cql = CqlValue::Text("hello world".to_string());
+        assert_eq!(
+            parse_indexed_value(cql, &kind).unwrap(),
+            DbIndexedValue::Document("hello world".to_string())
+        );
+    }
+
     #[test]
     fn parse_indexed_value_fts_from_text() {
         let cql = CqlValue::Text("hello world".to_string());
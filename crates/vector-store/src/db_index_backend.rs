@@ -115,6 +115,9 @@ pub(crate) fn request_query<'a, 'b>(
 
 /// Retrieves the vector dimensions for the given index, dispatching to the
 /// appropriate strategy based on whether the keyspace is Alternator- or CQL-backed.
+///
+/// A text column embedded by a model has no vector type, so its index declares the
+/// dimensions of the model in the index option `"dimensions"`.
 pub(crate) async fn get_dimensions(
     target_column: &ColumnName,
     session: &Session,
@@ -123,18 +126,19 @@ pub(crate) async fn get_dimensions(
     st_get_index_options: &PreparedStatement,
     location: IndexLocation,
 ) -> anyhow::Result<Option<Dimensions>> {
-    if location.keyspace.is_alternator() {
-        get_dimensions_from_index_options(session, st_get_index_options, location).await
-    } else {
-        get_dimensions_from_column_type(
+    if !location.keyspace.is_alternator()
+        && let Some(dimensions) = get_dimensions_from_column_type(
             target_column,
             session,
             st_get_index_target_type,
             re_get_index_target_type,
-            location,
+            &location,
         )
-        .await
+        .await?
+    {
+        return Ok(Some(dimensions));
     }
+    get_dimensions_from_index_options(session, st_get_index_options, location).await
 }
 
 /// Retrieves the vector dimensions for a CQL-native table by parsing the column type.
@@ -143,12 +147,12 @@ async fn get_dimensions_from_column_type(
     session: &Session,
     st_get_index_target_type: &PreparedStatement,
     re_get_index_target_type: &Regex,
-    location: IndexLocation,
+    location: &IndexLocation,
 ) -> anyhow::Result<Option<Dimensions>> {
     let column_type = session
         .execute_iter(
             st_get_index_target_type.clone(),
-            (location.keyspace, location.table, target_column.clone()),
+            (&location.keyspace, &location.table, target_column),
         )
         .await?
         .rows_stream::<(String,)>()?
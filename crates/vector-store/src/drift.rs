@@ -0,0 +1,192 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Drift of the distribution of the vectors of an index.
+//!
+//! Ingested and query vectors are summarized separately by the mean and the spread of their norms
+//! and by the means of their dimensions. Each summary keeps a reference of the first
+//! [`REFERENCE_SIZE`] vectors and a recent window, a moving average over about the last
+//! [`WINDOW_SIZE`] vectors. A change of the embedding model moves the recent window away from the
+//! reference: its norms by the norm drift, in standard deviations of the reference norms, and its
+//! direction by the direction drift, the cosine distance between the mean vectors.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+
+/// Vectors in the reference of a summary.
+const REFERENCE_SIZE: u64 = 1000;
+/// Vectors weighted by the recent window of a summary.
+const WINDOW_SIZE: u64 = 1000;
+/// The least deviation of the reference norms relative to their mean, as normalized embeddings
+/// have a single norm and their rounding errors must not look like a drift.
+const MIN_RELATIVE_DEVIATION: f64 = 1e-3;
+
+/// Drift of the vectors of one kind from their reference.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Drift {
+    /// Vectors seen since the index was created on the node.
+    pub(crate) vectors: u64,
+    /// Shift of the mean norm, in standard deviations of the reference norms.
+    pub(crate) norm: f64,
+    /// Cosine distance between the mean vectors of the reference and of the recent window.
+    pub(crate) direction: f64,
+}
+
+/// Tracks the drift of the ingested and query vectors of an index. It is shared by the actors and
+/// the routes of the index.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct VectorDrift(Arc<VectorDriftParts>);
+
+#[derive(Debug, Default)]
+struct VectorDriftParts {
+    ingested: Mutex<Summary>,
+    queried: Mutex<Summary>,
+}
+
+impl VectorDrift {
+    pub(crate) fn observe_ingested(&self, vector: &[f32]) {
+        self.0.ingested.lock().unwrap().observe(vector);
+    }
+
+    pub(crate) fn observe_queried(&self, vector: &[f32]) {
+        self.0.queried.lock().unwrap().observe(vector);
+    }
+
+    /// Returns the drift of the ingested vectors, once their reference is complete.
+    pub(crate) fn ingested(&self) -> Option<Drift> {
+        self.0.ingested.lock().unwrap().drift()
+    }
+
+    /// Returns the drift of the query vectors, once their reference is complete.
+    pub(crate) fn queried(&self) -> Option<Drift> {
+        self.0.queried.lock().unwrap().drift()
+    }
+}
+
+#[derive(Debug, Default)]
+struct Moments {
+    norm: f64,
+    norm_squared: f64,
+    dimensions: Vec<f64>,
+}
+
+impl Moments {
+    /// Moves the moments towards the vector by the weight.
+    fn update(&mut self, vector: &[f32], norm: f64, weight: f64) {
+        self.norm += weight * (norm - self.norm);
+        self.norm_squared += weight * (norm * norm - self.norm_squared);
+        self.dimensions
+            .iter_mut()
+            .zip(vector)
+            .for_each(|(mean, &value)| *mean += weight * (value as f64 - *mean));
+    }
+}
+
+#[derive(Debug, Default)]
+struct Summary {
+    vectors: u64,
+    reference: Moments,
+    recent: Moments,
+}
+
+impl Summary {
+    fn observe(&mut self, vector: &[f32]) {
+        if self.vectors == 0 {
+            self.reference.dimensions = vec![0.0; vector.len()];
+            self.recent.dimensions = vec![0.0; vector.len()];
+        }
+        if vector.len() != self.reference.dimensions.len() {
+            return;
+        }
+        self.vectors += 1;
+        let norm = vector
+            .iter()
+            .map(|&value| value as f64 * value as f64)
+            .sum::<f64>()
+            .sqrt();
+        if self.vectors <= REFERENCE_SIZE {
+            self.reference
+                .update(vector, norm, 1.0 / self.vectors as f64);
+        }
+        self.recent.update(
+            vector,
+            norm,
+            1.0 / u64::min(self.vectors, WINDOW_SIZE) as f64,
+        );
+    }
+
+    fn drift(&self) -> Option<Drift> {
+        if self.vectors < REFERENCE_SIZE {
+            return None;
+        }
+        let deviation = (self.reference.norm_squared - self.reference.norm.powi(2))
+            .max(0.0)
+            .sqrt()
+            .max(MIN_RELATIVE_DEVIATION * self.reference.norm);
+        let norm_shift = (self.recent.norm - self.reference.norm).abs();
+        let dot = |lhs: &[f64], rhs: &[f64]| lhs.iter().zip(rhs).map(|(l, r)| l * r).sum::<f64>();
+        let (reference, recent) = (&self.reference.dimensions, &self.recent.dimensions);
+        let norms = (dot(reference, reference) * dot(recent, recent)).sqrt();
+        Some(Drift {
+            vectors: self.vectors,
+            norm: if deviation > 0.0 {
+                norm_shift / deviation
+            } else {
+                0.0
+            },
+            direction: if norms > 0.0 {
+                (1.0 - dot(reference, recent) / norms).clamp(0.0, 2.0)
+            } else {
+                0.0
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_drift_before_reference_is_complete() {
+        let drift = VectorDrift::default();
+        for _ in 1..REFERENCE_SIZE {
+            drift.observe_ingested(&[1.0, 0.0]);
+        }
+        assert_eq!(drift.ingested(), None);
+        drift.observe_ingested(&[1.0, 0.0]);
+        assert_eq!(
+            drift.ingested(),
+            Some(Drift {
+                vectors: REFERENCE_SIZE,
+                norm: 0.0,
+                direction: 0.0,
+            })
+        );
+        assert_eq!(drift.queried(), None);
+    }
+
+    #[test]
+    fn drift_follows_changed_vectors() {
+        let drift = VectorDrift::default();
+        for idx in 0..REFERENCE_SIZE {
+            let norm = if idx % 2 == 0 { 0.9 } else { 1.1 };
+            drift.observe_queried(&[norm, 0.0]);
+        }
+        let stable = drift.queried().unwrap();
+        assert!(stable.norm < 0.01);
+        assert!(stable.direction < 0.01);
+
+        for _ in 0..10 * WINDOW_SIZE {
+            drift.observe_queried(&[0.0, 2.0]);
+        }
+        // Vectors of other dimensions are ignored.
+        drift.observe_queried(&[1.0, 0.0, 0.0]);
+        let drifted = drift.queried().unwrap();
+        assert_eq!(drifted.vectors, 11 * REFERENCE_SIZE);
+        assert!((drifted.norm - 10.0).abs() < 0.1);
+        assert!((drifted.direction - 1.0).abs() < 0.01);
+    }
+}
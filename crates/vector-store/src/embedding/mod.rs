@@ -0,0 +1,774 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Embedding of texts by a model, so clients can search an index with raw text.
+//!
+//! Texts are embedded by the API of OpenAI, Cohere or Amazon Bedrock, or of a service compatible
+//! with one of them, or by a local ONNX model run on the node. Each index may use its own model.
+//! Embeddings of recently queried texts are cached for a configured time, keyed by the model and
+//! the text with its whitespace collapsed, and the requests sent to the provider are limited to a
+//! configured rate, so a burst of queries does not exhaust its quota.
+//!
+//! An index of a text column with an `embedding_model` option stores the embeddings of the texts
+//! of the column by that model, which embeds also the texts of its queries. Texts of rows are not
+//! cached and wait for the rate limit instead of failing, so the index gets all rows.
+
+#[cfg(feature = "onnx")]
+pub(crate) mod onnx;
+
+use crate::IndexKey;
+use crate::Metrics;
+use crate::serde_duration;
+use anyhow::bail;
+use secrecy::ExposeSecret;
+use serde_json::Value;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::time;
+use tokio::time::Instant;
+use tracing::debug;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EmbeddingProvider {
+    /// `POST {uri}/embeddings` of the OpenAI API.
+    OpenAi,
+    /// `POST {uri}/embed` of the Cohere API.
+    Cohere,
+    /// `POST {uri}/model/{model}/invoke` of the Amazon Bedrock runtime API with a Titan model.
+    Bedrock,
+    /// A sentence-transformer model run on the node, where the model is a directory with its
+    /// `model.onnx` and `tokenizer.json`.
+    Onnx,
+}
+
+impl EmbeddingProvider {
+    /// Base URL of the public API of the provider, if there is a single one.
+    pub(crate) fn default_uri(self) -> Option<&'static str> {
+        match self {
+            Self::OpenAi => Some("https://api.openai.com/v1"),
+            Self::Cohere => Some("https://api.cohere.com/v2"),
+            Self::Bedrock | Self::Onnx => None,
+        }
+    }
+
+    fn is_local(self) -> bool {
+        self == Self::Onnx
+    }
+}
+
+impl FromStr for EmbeddingProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        match value {
+            "openai" => Ok(Self::OpenAi),
+            "cohere" => Ok(Self::Cohere),
+            "bedrock" => Ok(Self::Bedrock),
+            "onnx" => Ok(Self::Onnx),
+            _ => bail!("unknown embedding provider {value} (openai, cohere, bedrock, onnx)"),
+        }
+    }
+}
+
+/// Settings of the embedding provider. The API key is never (de)serialized.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmbeddingConfig {
+    pub provider: EmbeddingProvider,
+    /// Base URL of the provider API, e.g. `https://api.openai.com/v1`. Not used by local models.
+    #[serde(default)]
+    pub uri: Option<String>,
+    /// Model of indexes without their own model.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Models of indexes by `keyspace.index`.
+    #[serde(default)]
+    pub index_models: HashMap<String, String>,
+    /// Sent as a bearer token.
+    #[serde(skip)]
+    pub api_key: Option<secrecy::SecretString>,
+    /// Number of cached embeddings; 0 disables the cache.
+    #[serde(default = "default_cache_size")]
+    pub cache_size: usize,
+    /// Time after which a cached embedding is computed again.
+    #[serde(default = "default_cache_ttl", with = "serde_duration")]
+    pub cache_ttl: Duration,
+    /// Requests per second sent to the provider; not limited if not set.
+    #[serde(default)]
+    pub rate_limit: Option<NonZeroU32>,
+    #[serde(default = "default_timeout", with = "serde_duration")]
+    pub timeout: Duration,
+}
+
+pub(crate) fn default_cache_size() -> usize {
+    10_000
+}
+
+pub(crate) fn default_cache_ttl() -> Duration {
+    Duration::from_secs(3600)
+}
+
+pub(crate) fn default_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
+impl EmbeddingConfig {
+    pub(crate) fn validate(&self) -> anyhow::Result<()> {
+        if self.provider.is_local() {
+            if cfg!(not(feature = "onnx")) {
+                bail!("ONNX embedding models are not compiled in, enable the `onnx` feature");
+            }
+        } else {
+            let Some(uri) = &self.uri else {
+                bail!(
+                    "embedding provider URL is required for the {:?} provider",
+                    self.provider
+                );
+            };
+            if !uri.starts_with("http://") && !uri.starts_with("https://") {
+                bail!("embedding provider URL {uri} is not an HTTP URL");
+            }
+        }
+        if self
+            .model
+            .iter()
+            .chain(self.index_models.values())
+            .any(String::is_empty)
+        {
+            bail!("embedding model is empty");
+        }
+        Ok(())
+    }
+
+    /// Returns the model embedding the texts of queries for the index.
+    pub(crate) fn model(&self, index: &IndexKey) -> Option<&str> {
+        self.index_models
+            .get(index.as_ref())
+            .or(self.model.as_ref())
+            .map(String::as_str)
+    }
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    #[error("rate limit of the embedding provider exceeded")]
+    RateLimited,
+
+    #[error("embedding provider error: {0}")]
+    Provider(String),
+
+    #[error("embedding model error: {0}")]
+    Model(String),
+
+    #[error("no embedding model is configured for index {0}")]
+    NoModel(IndexKey),
+}
+
+/// What the embedded text is, as some providers embed queries and documents differently.
+#[derive(Clone, Copy)]
+enum Input {
+    Query,
+    Document,
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Provider(err.to_string())
+    }
+}
+
+/// Model and normalized text of a cached embedding.
+type CacheKey = (String, String);
+
+/// Collapses runs of whitespace into single spaces, so texts differing only by spacing share
+/// their embedding.
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+struct CacheEntry {
+    embedding: Arc<[f32]>,
+    used: u64,
+    expires: Instant,
+}
+
+/// Embeddings of the most recently used texts.
+struct Cache {
+    capacity: usize,
+    ttl: Duration,
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// Keys by the time of their last use.
+    used: BTreeMap<u64, CacheKey>,
+    now: u64,
+}
+
+impl Cache {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: HashMap::new(),
+            used: BTreeMap::new(),
+            now: 0,
+        }
+    }
+
+    fn get(&mut self, key: &CacheKey, now: Instant) -> Option<Arc<[f32]>> {
+        let entry = self.entries.get_mut(key)?;
+        let key = self
+            .used
+            .remove(&entry.used)
+            .expect("cached key should be in use order");
+        if entry.expires <= now {
+            self.entries.remove(&key);
+            return None;
+        }
+        self.now += 1;
+        entry.used = self.now;
+        self.used.insert(self.now, key);
+        Some(Arc::clone(&entry.embedding))
+    }
+
+    fn insert(&mut self, key: CacheKey, embedding: Arc<[f32]>, now: Instant) {
+        if self.capacity == 0 || self.entries.contains_key(&key) {
+            return;
+        }
+        if self.entries.len() >= self.capacity
+            && let Some((_, oldest)) = self.used.pop_first()
+        {
+            self.entries.remove(&oldest);
+        }
+        self.now += 1;
+        self.used.insert(self.now, key.clone());
+        self.entries.insert(
+            key,
+            CacheEntry {
+                embedding,
+                used: self.now,
+                expires: now + self.ttl,
+            },
+        );
+    }
+}
+
+/// Delays of retrying a text of a row after the provider rate limited it.
+const RETRY_DELAY_MIN: Duration = Duration::from_millis(100);
+const RETRY_DELAY_MAX: Duration = Duration::from_secs(30);
+
+/// Token bucket allowing bursts of at most one second of requests.
+struct RateLimiter {
+    rate: f64,
+    tokens: f64,
+    last: Instant,
+}
+
+impl RateLimiter {
+    fn new(rate: NonZeroU32, now: Instant) -> Self {
+        let rate = rate.get() as f64;
+        Self {
+            rate,
+            tokens: rate,
+            last: now,
+        }
+    }
+
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.last).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.last = now;
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+
+    /// Time after which a token is available.
+    fn wait_time(&self) -> Duration {
+        Duration::from_secs_f64((1.0 - self.tokens).max(0.0) / self.rate)
+    }
+}
+
+enum Backend {
+    Http(reqwest::Client),
+    #[cfg(feature = "onnx")]
+    Onnx(onnx::Models),
+}
+
+#[derive(Clone)]
+pub(crate) struct Embedder {
+    config: Arc<EmbeddingConfig>,
+    backend: Arc<Backend>,
+    cache: Arc<Mutex<Cache>>,
+    limiter: Option<Arc<Mutex<RateLimiter>>>,
+    metrics: Arc<Metrics>,
+}
+
+impl Embedder {
+    pub(crate) fn new(config: EmbeddingConfig, metrics: Arc<Metrics>) -> Self {
+        let backend = match config.provider {
+            #[cfg(feature = "onnx")]
+            EmbeddingProvider::Onnx => Backend::Onnx(onnx::Models::default()),
+            _ => Backend::Http(
+                reqwest::Client::builder()
+                    .timeout(config.timeout)
+                    .build()
+                    .expect("Embedder::new: HTTP client should be created"),
+            ),
+        };
+        Self {
+            cache: Arc::new(Mutex::new(Cache::new(config.cache_size, config.cache_ttl))),
+            limiter: config
+                .rate_limit
+                .map(|rate| Arc::new(Mutex::new(RateLimiter::new(rate, Instant::now())))),
+            config: Arc::new(config),
+            backend: Arc::new(backend),
+            metrics,
+        }
+    }
+
+    /// Returns the embedding of the text by the model of the index, from the cache or from the
+    /// model.
+    pub(crate) async fn embed(&self, index: &IndexKey, text: &str) -> Result<Arc<[f32]>, Error> {
+        let model = self
+            .config
+            .model(index)
+            .ok_or_else(|| Error::NoModel(index.clone()))?;
+        self.embed_query(model, text).await
+    }
+
+    /// Returns the embedding of the text of a query by the model, from the cache or from the
+    /// model.
+    pub(crate) async fn embed_query(&self, model: &str, text: &str) -> Result<Arc<[f32]>, Error> {
+        let key = (model.to_string(), normalize(text));
+        let cached = self.cache.lock().unwrap().get(&key, Instant::now());
+        if let Some(embedding) = cached {
+            self.metrics
+                .embedding_cache_hits_total
+                .with_label_values(&[model])
+                .inc();
+            return Ok(embedding);
+        }
+        self.metrics
+            .embedding_cache_misses_total
+            .with_label_values(&[model])
+            .inc();
+        if let Some(limiter) = &self.limiter
+            && !limiter.lock().unwrap().try_acquire(Instant::now())
+        {
+            return Err(Error::RateLimited);
+        }
+        let embedding: Arc<[f32]> = self.compute(model, &key.1, Input::Query).await?.into();
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(key, Arc::clone(&embedding), Instant::now());
+        Ok(embedding)
+    }
+
+    /// Returns the embedding of the text of a row by the model. Waits for the rate limit, and
+    /// retries while the provider rejects requests over its own limit.
+    pub(crate) async fn embed_document(&self, model: &str, text: &str) -> Result<Vec<f32>, Error> {
+        let mut delay = RETRY_DELAY_MIN;
+        loop {
+            self.acquire().await;
+            match self.compute(model, text, Input::Document).await {
+                Err(Error::RateLimited) => {
+                    debug!(
+                        "embedding provider rate limited a text of a row, retrying in {delay:?}"
+                    );
+                    time::sleep(delay).await;
+                    delay = (delay * 2).min(RETRY_DELAY_MAX);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Returns the embedder of the texts of rows of an index by its model.
+    pub(crate) fn for_model(&self, model: String) -> IndexEmbedder {
+        IndexEmbedder {
+            embedder: self.clone(),
+            model,
+        }
+    }
+
+    async fn acquire(&self) {
+        let Some(limiter) = &self.limiter else {
+            return;
+        };
+        loop {
+            let wait = {
+                let mut limiter = limiter.lock().unwrap();
+                if limiter.try_acquire(Instant::now()) {
+                    return;
+                }
+                limiter.wait_time()
+            };
+            time::sleep(wait).await;
+        }
+    }
+
+    async fn compute(&self, model: &str, text: &str, input: Input) -> Result<Vec<f32>, Error> {
+        match self.backend.as_ref() {
+            Backend::Http(client) => self.request(client, model, text, input).await,
+            #[cfg(feature = "onnx")]
+            Backend::Onnx(models) => models.embed(model, text).await,
+        }
+    }
+
+    async fn request(
+        &self,
+        client: &reqwest::Client,
+        model: &str,
+        text: &str,
+        input: Input,
+    ) -> Result<Vec<f32>, Error> {
+        let EmbeddingConfig {
+            provider,
+            uri,
+            api_key,
+            ..
+        } = self.config.as_ref();
+        let uri = uri.as_deref().unwrap_or_default();
+        let (url, body) = match provider {
+            EmbeddingProvider::OpenAi => (
+                format!("{uri}/embeddings"),
+                json!({ "model": model, "input": text }),
+            ),
+            EmbeddingProvider::Cohere => (
+                format!("{uri}/embed"),
+                json!({
+                    "model": model,
+                    "texts": [text],
+                    "input_type": match input {
+                        Input::Query => "search_query",
+                        Input::Document => "search_document",
+                    },
+                    "embedding_types": ["float"],
+                }),
+            ),
+            EmbeddingProvider::Bedrock => (
+                format!("{uri}/model/{model}/invoke"),
+                json!({ "inputText": text }),
+            ),
+            EmbeddingProvider::Onnx => {
+                return Err(Error::Model(
+                    "ONNX embedding models are not compiled in".to_string(),
+                ));
+            }
+        };
+        let mut request = client.post(url).json(&body);
+        if let Some(api_key) = api_key {
+            request = request.bearer_auth(api_key.expose_secret());
+        }
+        let response = request.send().await?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(Error::RateLimited);
+        }
+        let response: Value = response.error_for_status()?.json().await?;
+        parse_embedding(*provider, &response)
+            .ok_or_else(|| Error::Provider("response without an embedding".to_string()))
+    }
+}
+
+/// Embeds the texts of rows of an index of a text column.
+#[derive(Clone)]
+pub(crate) struct IndexEmbedder {
+    embedder: Embedder,
+    model: String,
+}
+
+impl IndexEmbedder {
+    pub(crate) async fn embed(&self, text: &str) -> Result<Vec<f32>, Error> {
+        self.embedder.embed_document(&self.model, text).await
+    }
+}
+
+fn parse_embedding(provider: EmbeddingProvider, response: &Value) -> Option<Vec<f32>> {
+    let embedding = match provider {
+        EmbeddingProvider::OpenAi => &response["data"][0]["embedding"],
+        // Version 2 of the API groups embeddings by their type.
+        EmbeddingProvider::Cohere => match &response["embeddings"] {
+            Value::Object(types) => &types.get("float")?[0],
+            embeddings => &embeddings[0],
+        },
+        EmbeddingProvider::Bedrock => &response["embedding"],
+        EmbeddingProvider::Onnx => return None,
+    };
+    embedding
+        .as_array()?
+        .iter()
+        .map(|value| value.as_f64().map(|value| value as f32))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Json;
+    use axum::Router;
+    use axum::http::HeaderMap;
+    use axum::routing::post;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use tokio::net::TcpListener;
+
+    fn config(uri: String) -> EmbeddingConfig {
+        EmbeddingConfig {
+            provider: EmbeddingProvider::OpenAi,
+            uri: Some(uri),
+            model: Some("small".to_string()),
+            index_models: HashMap::from([("ks.large".to_string(), "large-model".to_string())]),
+            api_key: Some(secrecy::SecretString::new("secret".into())),
+            cache_size: default_cache_size(),
+            cache_ttl: default_cache_ttl(),
+            rate_limit: None,
+            timeout: default_timeout(),
+        }
+    }
+
+    /// Spawns a fake OpenAI API embedding a text as the lengths of the text and of the model and
+    /// counting the requests.
+    async fn spawn_provider() -> (String, Arc<AtomicUsize>) {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let router = Router::new().route(
+            "/embeddings",
+            post({
+                let requests = Arc::clone(&requests);
+                move |headers: HeaderMap, Json(body): Json<Value>| {
+                    requests.fetch_add(1, Ordering::Relaxed);
+                    assert_eq!(headers["authorization"], "Bearer secret");
+                    let text = body["input"].as_str().unwrap().len();
+                    let model = body["model"].as_str().unwrap().len();
+                    async move { Json(json!({ "data": [{ "embedding": [text, model] }] })) }
+                }
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        (format!("http://{addr}"), requests)
+    }
+
+    #[tokio::test]
+    async fn embeddings_are_cached() {
+        let (uri, requests) = spawn_provider().await;
+        let metrics = Arc::new(Metrics::new());
+        let embedder = Embedder::new(config(uri), Arc::clone(&metrics));
+        let index = IndexKey::new(&"ks".to_string().into(), &"idx".to_string().into());
+
+        assert_eq!(
+            embedder.embed(&index, "abc").await.unwrap().as_ref(),
+            [3.0, 5.0]
+        );
+        assert_eq!(
+            embedder.embed(&index, "abc").await.unwrap().as_ref(),
+            [3.0, 5.0]
+        );
+        assert_eq!(requests.load(Ordering::Relaxed), 1);
+
+        assert_eq!(
+            embedder.embed(&index, "abcd").await.unwrap().as_ref(),
+            [4.0, 5.0]
+        );
+        assert_eq!(requests.load(Ordering::Relaxed), 2);
+
+        // Texts differing by whitespace only are embedded once.
+        assert_eq!(
+            embedder
+                .embed(&index, "  ab \n cd ")
+                .await
+                .unwrap()
+                .as_ref(),
+            [5.0, 5.0]
+        );
+        assert_eq!(
+            embedder.embed(&index, "ab cd").await.unwrap().as_ref(),
+            [5.0, 5.0]
+        );
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
+
+        let count = |counter: &prometheus::CounterVec| counter.with_label_values(&["small"]).get();
+        assert_eq!(count(&metrics.embedding_cache_hits_total), 2.0);
+        assert_eq!(count(&metrics.embedding_cache_misses_total), 3.0);
+    }
+
+    #[tokio::test]
+    async fn indexes_use_their_own_models() {
+        let (uri, requests) = spawn_provider().await;
+        let embedder = Embedder::new(config(uri.clone()), Arc::new(Metrics::new()));
+        let index = IndexKey::new(&"ks".to_string().into(), &"idx".to_string().into());
+        let large = IndexKey::new(&"ks".to_string().into(), &"large".to_string().into());
+
+        assert_eq!(
+            embedder.embed(&index, "abc").await.unwrap().as_ref(),
+            [3.0, 5.0]
+        );
+        assert_eq!(
+            embedder.embed(&large, "abc").await.unwrap().as_ref(),
+            [3.0, 11.0]
+        );
+        assert_eq!(requests.load(Ordering::Relaxed), 2);
+
+        let embedder = Embedder::new(
+            EmbeddingConfig {
+                model: None,
+                ..config(uri)
+            },
+            Arc::new(Metrics::new()),
+        );
+        assert!(matches!(
+            embedder.embed(&index, "abc").await,
+            Err(Error::NoModel(_))
+        ));
+        assert!(embedder.embed(&large, "abc").await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn requests_over_rate_limit_are_rejected() {
+        let (uri, requests) = spawn_provider().await;
+        let embedder = Embedder::new(
+            EmbeddingConfig {
+                cache_size: 0,
+                rate_limit: NonZeroU32::new(2),
+                ..config(uri)
+            },
+            Arc::new(Metrics::new()),
+        );
+
+        let index = IndexKey::new(&"ks".to_string().into(), &"idx".to_string().into());
+        embedder.embed(&index, "a").await.unwrap();
+        embedder.embed(&index, "a").await.unwrap();
+        assert!(matches!(
+            embedder.embed(&index, "a").await,
+            Err(Error::RateLimited)
+        ));
+        assert_eq!(requests.load(Ordering::Relaxed), 2);
+    }
+
+    #[tokio::test]
+    async fn texts_of_rows_wait_for_rate_limit() {
+        let (uri, requests) = spawn_provider().await;
+        let embedder = Embedder::new(
+            EmbeddingConfig {
+                rate_limit: NonZeroU32::new(2),
+                ..config(uri)
+            },
+            Arc::new(Metrics::new()),
+        );
+
+        let start = std::time::Instant::now();
+        let index = embedder.for_model("model".to_string());
+        for _ in 0..3 {
+            assert_eq!(index.embed("abc").await.unwrap(), [3.0, 5.0]);
+        }
+        // Texts of rows are not cached, and the third one waits for a token.
+        assert_eq!(requests.load(Ordering::Relaxed), 3);
+        assert!(start.elapsed() >= Duration::from_millis(400));
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used() {
+        let key = |text: &str| ("model".to_string(), text.to_string());
+        let now = Instant::now();
+        let mut cache = Cache::new(2, default_cache_ttl());
+        cache.insert(key("a"), [1.0].into(), now);
+        cache.insert(key("b"), [2.0].into(), now);
+        assert!(cache.get(&key("a"), now).is_some());
+        cache.insert(key("c"), [3.0].into(), now);
+
+        assert!(cache.get(&key("b"), now).is_none());
+        assert_eq!(cache.get(&key("a"), now).unwrap().as_ref(), [1.0]);
+        assert_eq!(cache.get(&key("c"), now).unwrap().as_ref(), [3.0]);
+    }
+
+    #[test]
+    fn cache_expires_entries() {
+        let key = |text: &str| ("model".to_string(), text.to_string());
+        let now = Instant::now();
+        let mut cache = Cache::new(2, Duration::from_secs(10));
+        cache.insert(key("a"), [1.0].into(), now);
+        cache.insert(key("b"), [2.0].into(), now + Duration::from_secs(5));
+
+        let later = now + Duration::from_secs(10);
+        assert!(cache.get(&key("a"), later).is_none());
+        assert_eq!(cache.get(&key("b"), later).unwrap().as_ref(), [2.0]);
+        assert_eq!(cache.entries.len(), 1);
+        assert_eq!(cache.used.len(), 1);
+
+        // The expired entry is computed and cached again.
+        cache.insert(key("a"), [3.0].into(), later);
+        assert_eq!(cache.get(&key("a"), later).unwrap().as_ref(), [3.0]);
+    }
+
+    #[test]
+    fn texts_are_normalized() {
+        assert_eq!(normalize("  hello \t\n world  "), "hello world");
+        assert_eq!(normalize("hello world"), "hello world");
+        assert_eq!(normalize(""), "");
+    }
+
+    #[test]
+    fn rate_limiter_refills() {
+        let now = Instant::now();
+        let mut limiter = RateLimiter::new(NonZeroU32::new(2).unwrap(), now);
+        assert!(limiter.try_acquire(now));
+        assert!(limiter.try_acquire(now));
+        assert!(!limiter.try_acquire(now));
+        assert_eq!(limiter.wait_time(), Duration::from_millis(500));
+        assert!(limiter.try_acquire(now + Duration::from_millis(500)));
+        assert!(!limiter.try_acquire(now + Duration::from_millis(500)));
+        // The bucket holds at most one second of requests.
+        let later = now + Duration::from_secs(10);
+        assert!(limiter.try_acquire(later));
+        assert!(limiter.try_acquire(later));
+        assert!(!limiter.try_acquire(later));
+    }
+
+    #[test]
+    fn embeddings_are_parsed_from_provider_responses() {
+        assert_eq!(
+            parse_embedding(
+                EmbeddingProvider::OpenAi,
+                &json!({ "data": [{ "embedding": [0.5, 1.0] }] })
+            ),
+            Some(vec![0.5, 1.0])
+        );
+        assert_eq!(
+            parse_embedding(
+                EmbeddingProvider::Cohere,
+                &json!({ "embeddings": { "float": [[0.5, 1.0]] } })
+            ),
+            Some(vec![0.5, 1.0])
+        );
+        assert_eq!(
+            parse_embedding(
+                EmbeddingProvider::Cohere,
+                &json!({ "embeddings": [[0.5, 1.0]] })
+            ),
+            Some(vec![0.5, 1.0])
+        );
+        assert_eq!(
+            parse_embedding(
+                EmbeddingProvider::Bedrock,
+                &json!({ "embedding": [0.5, 1.0], "inputTextTokenCount": 2 })
+            ),
+            Some(vec![0.5, 1.0])
+        );
+        assert_eq!(
+            parse_embedding(EmbeddingProvider::OpenAi, &json!({ "data": [] })),
+            None
+        );
+    }
+}
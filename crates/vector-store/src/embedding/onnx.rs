@@ -0,0 +1,212 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Sentence-transformer models run on the node by ONNX Runtime.
+//!
+//! A model is a directory with the exported `model.onnx` and the `tokenizer.json` of its
+//! tokenizer. Models are loaded on the first query that needs them. The embedding of a text is the
+//! mean of the token embeddings of the first output, normalized to unit length, as by the default
+//! pooling of sentence-transformers. A cross-encoder scores a pair of texts by the last logit of
+//! its first output.
+
+use super::Error;
+use anyhow::anyhow;
+use ort::session::Session;
+use ort::value::Tensor;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tokenizers::Encoding;
+use tokenizers::Tokenizer;
+use tokio::sync::OnceCell;
+use tokio::task;
+use tracing::info;
+
+const MODEL_FILE: &str = "model.onnx";
+const TOKENIZER_FILE: &str = "tokenizer.json";
+
+struct Model {
+    session: Mutex<Session>,
+    tokenizer: Tokenizer,
+    /// BERT models take also the segments of tokens.
+    token_type_ids: bool,
+}
+
+impl Model {
+    fn load(dir: &Path) -> anyhow::Result<Self> {
+        let tokenizer = Tokenizer::from_file(dir.join(TOKENIZER_FILE))
+            .map_err(|err| anyhow!("failed to load the tokenizer: {err}"))?;
+        let session = Session::builder()?.commit_from_file(dir.join(MODEL_FILE))?;
+        let token_type_ids = session
+            .inputs
+            .iter()
+            .any(|input| input.name == "token_type_ids");
+        Ok(Self {
+            session: Mutex::new(session),
+            tokenizer,
+            token_type_ids,
+        })
+    }
+
+    fn embed(&self, text: &str) -> anyhow::Result<Vec<f32>> {
+        let encoding = self
+            .tokenizer
+            .encode(text, true)
+            .map_err(|err| anyhow!("failed to tokenize the text: {err}"))?;
+        self.run(&encoding, |shape, tokens| {
+            let dimensions = *shape
+                .last()
+                .ok_or_else(|| anyhow!("the model output has no dimensions"))?
+                as usize;
+            Ok(mean_pool(tokens, encoding.get_attention_mask(), dimensions))
+        })
+    }
+
+    fn score(&self, query: &str, document: &str) -> anyhow::Result<f32> {
+        let encoding = self
+            .tokenizer
+            .encode((query, document), true)
+            .map_err(|err| anyhow!("failed to tokenize the texts: {err}"))?;
+        self.run(&encoding, |_, logits| {
+            logits
+                .last()
+                .copied()
+                .ok_or_else(|| anyhow!("the model output is empty"))
+        })
+    }
+
+    /// Runs the model and passes the shape and the values of its first output to `output`.
+    fn run<T>(
+        &self,
+        encoding: &Encoding,
+        output: impl FnOnce(&[i64], &[f32]) -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        let tensor = |values: &[u32]| {
+            Tensor::from_array((
+                [1, values.len()],
+                values.iter().map(|&value| value as i64).collect::<Vec<_>>(),
+            ))
+        };
+        let mut inputs = ort::inputs![
+            "input_ids" => tensor(encoding.get_ids())?,
+            "attention_mask" => tensor(encoding.get_attention_mask())?,
+        ];
+        if self.token_type_ids {
+            inputs.push((
+                "token_type_ids".into(),
+                tensor(encoding.get_type_ids())?.into(),
+            ));
+        }
+        let mut session = self.session.lock().unwrap();
+        let outputs = session.run(inputs)?;
+        let (shape, values) = outputs[0].try_extract_tensor::<f32>()?;
+        output(&shape[..], values)
+    }
+}
+
+/// Averages the embeddings of the tokens not masked out and normalizes the result.
+fn mean_pool(tokens: &[f32], attention_mask: &[u32], dimensions: usize) -> Vec<f32> {
+    let mut embedding = vec![0.0; dimensions];
+    let mut count = 0.0;
+    for (token, _) in tokens
+        .chunks_exact(dimensions)
+        .zip(attention_mask)
+        .filter(|(_, mask)| **mask != 0)
+    {
+        embedding
+            .iter_mut()
+            .zip(token)
+            .for_each(|(sum, value)| *sum += value);
+        count += 1.0;
+    }
+    embedding
+        .iter_mut()
+        .for_each(|value| *value /= f32::max(count, 1.0));
+    let norm = embedding
+        .iter()
+        .map(|value| value * value)
+        .sum::<f32>()
+        .sqrt();
+    if norm > 0.0 {
+        embedding.iter_mut().for_each(|value| *value /= norm);
+    }
+    embedding
+}
+
+/// Models by their directories.
+#[derive(Default)]
+pub(crate) struct Models {
+    models: Mutex<HashMap<String, Arc<OnceCell<Arc<Model>>>>>,
+}
+
+impl Models {
+    pub(crate) async fn embed(&self, model: &str, text: &str) -> Result<Vec<f32>, Error> {
+        let loaded = self.load(model).await?;
+        let text = text.to_string();
+        // Inference keeps a core busy, so it does not run on the async runtime.
+        task::spawn_blocking(move || loaded.embed(&text))
+            .await
+            .map_err(|err| Error::Model(err.to_string()))?
+            .map_err(|err| Error::Model(format!("ONNX model {model} failed: {err}")))
+    }
+
+    /// Returns the relevance scores of the documents to the query by a cross-encoder model.
+    pub(crate) async fn score(
+        &self,
+        model: &str,
+        query: &str,
+        documents: Vec<String>,
+    ) -> Result<Vec<f32>, Error> {
+        let loaded = self.load(model).await?;
+        let query = query.to_string();
+        task::spawn_blocking(move || {
+            documents
+                .iter()
+                .map(|document| loaded.score(&query, document))
+                .collect::<anyhow::Result<Vec<_>>>()
+        })
+        .await
+        .map_err(|err| Error::Model(err.to_string()))?
+        .map_err(|err| Error::Model(format!("ONNX model {model} failed: {err}")))
+    }
+
+    async fn load(&self, model: &str) -> Result<Arc<Model>, Error> {
+        let cell = Arc::clone(
+            self.models
+                .lock()
+                .unwrap()
+                .entry(model.to_string())
+                .or_default(),
+        );
+        let loaded = cell
+            .get_or_try_init(|| {
+                let dir = Path::new(model).to_path_buf();
+                async move {
+                    info!("loading ONNX model {dir:?}");
+                    task::spawn_blocking(move || Model::load(&dir))
+                        .await?
+                        .map(Arc::new)
+                }
+            })
+            .await
+            .map_err(|err: anyhow::Error| {
+                Error::Model(format!("failed to load ONNX model {model}: {err}"))
+            })?;
+        Ok(Arc::clone(loaded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mean_pool_skips_masked_tokens_and_normalizes() {
+        let tokens = [1.0, 2.0, 5.0, 6.0, 100.0, 100.0];
+        assert_eq!(mean_pool(&tokens, &[1, 1, 0], 2), vec![0.6, 0.8]);
+        assert_eq!(mean_pool(&tokens, &[0, 0, 0], 2), vec![0.0, 0.0]);
+    }
+}
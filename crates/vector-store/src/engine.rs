@@ -5,32 +5,51 @@
 
 use crate::Config;
 use crate::DbIndexPartitioning;
+use crate::IndexError;
+use crate::IndexHandle;
 use crate::IndexKey;
 use crate::IndexKind;
 use crate::IndexMetadata;
 use crate::Internals;
+use crate::KeyspaceName;
+use crate::Membership;
 use crate::Metrics;
+use crate::Progress;
+use crate::actor_trace;
+use crate::cluster::Gossip;
 use crate::db::Db;
 use crate::db::DbExt;
 use crate::db_index::DbIndex;
 use crate::db_index::DbIndexExt;
+use crate::drift::VectorDrift;
+use crate::embedding::Embedder;
 use crate::fts_index::FtsIndex;
 use crate::fts_index::FtsIndexFactory;
 use crate::indexes::Indexes;
+use crate::invariant_key::KeyArena;
 use crate::memory;
 use crate::memory::Memory;
 use crate::monitor_indexes;
 use crate::monitor_items;
+use crate::monitor_items::ItemsOptions;
+use crate::monitor_items::MonitorItemsExt;
+use crate::node_state::Event;
+use crate::node_state::IndexStatus;
+use crate::node_state::LifecycleEvent;
 use crate::node_state::NodeState;
 use crate::node_state::NodeStateExt;
+use crate::node_state::NodeStatus;
 use crate::perf;
+use crate::rerank::Reranker;
 use crate::table::Table;
 use crate::vs_index::VsIndex;
 use crate::vs_index::factory::VsIndexConfiguration;
 use crate::vs_index::factory::VsIndexFactory;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::RwLock;
 use std::time::Duration;
+use tokio::sync::broadcast;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::oneshot;
@@ -68,6 +87,18 @@ pub(crate) enum Engine {
     },
 }
 
+impl Engine {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            Engine::GetVsIndexKeys { .. } => "GetVsIndexKeys",
+            Engine::AddIndex { .. } => "AddIndex",
+            Engine::DelIndex { .. } => "DelIndex",
+            Engine::GetVsIndex { .. } => "GetVsIndex",
+            Engine::GetFtsIndex { .. } => "GetFtsIndex",
+        }
+    }
+}
+
 pub(crate) trait EngineExt {
     async fn get_vs_index_keys(&self) -> GetVsIndexKeysR;
     async fn add_index(&self, metadata: IndexMetadata) -> AddIndexR;
@@ -120,74 +151,179 @@ impl EngineExt for mpsc::Sender<Engine> {
     }
 }
 
+/// A handle to the running engine together with the db and monitor actors, usable without the
+/// HTTP servers. It is created by [`crate::new_engine`] or [`crate::VectorStoreBuilder`].
+#[derive(Clone)]
+pub struct EngineHandle {
+    pub(crate) engine: mpsc::Sender<Engine>,
+    pub(crate) indexes: Arc<RwLock<Indexes>>,
+    pub(crate) node_state: Sender<NodeState>,
+    pub(crate) index_engine_version: String,
+    pub(crate) membership: watch::Receiver<Option<Arc<Membership>>>,
+    pub(crate) gossip: Gossip,
+    pub(crate) embedder: Option<Embedder>,
+    pub(crate) reranker: Option<Reranker>,
+    pub(crate) config_rx: watch::Receiver<Arc<Config>>,
+}
+
+impl std::fmt::Debug for EngineHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EngineHandle")
+            .field("index_engine_version", &self.index_engine_version)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EngineHandle {
+    pub(crate) fn engine(&self) -> &mpsc::Sender<Engine> {
+        &self.engine
+    }
+
+    pub(crate) fn indexes(&self) -> &Arc<RwLock<Indexes>> {
+        &self.indexes
+    }
+
+    /// Returns the cluster membership seen by the node, `None` when clustering is disabled.
+    pub fn membership(&self) -> &watch::Receiver<Option<Arc<Membership>>> {
+        &self.membership
+    }
+
+    pub(crate) fn gossip(&self) -> &Gossip {
+        &self.gossip
+    }
+
+    /// Returns the node state actor used by the engine.
+    pub fn node_state(&self) -> &Sender<NodeState> {
+        &self.node_state
+    }
+
+    /// Returns the name and the version of the vector index backend.
+    pub fn index_engine_version(&self) -> &str {
+        &self.index_engine_version
+    }
+
+    /// Returns the current status of the node.
+    pub async fn status(&self) -> NodeStatus {
+        self.node_state.get_status().await
+    }
+
+    /// Subscribes to lifecycle events of the node and its indexes. Only events published after
+    /// the subscription are received.
+    pub async fn subscribe(&self) -> broadcast::Receiver<LifecycleEvent> {
+        self.node_state.subscribe().await
+    }
+
+    /// Returns keys of all indexes (vector and full-text) managed by the engine.
+    pub fn index_keys(&self) -> Vec<IndexKey> {
+        let indexes = self.indexes.read().unwrap();
+        indexes
+            .iter_vs()
+            .map(|(key, _)| key.clone())
+            .chain(indexes.iter_fts().map(|(key, _)| key.clone()))
+            .collect()
+    }
+
+    /// Returns a handle for in-process queries to the vector index.
+    pub fn index(&self, key: &IndexKey) -> Result<IndexHandle, IndexError> {
+        if self.indexes.read().unwrap().get_vs(key).is_none() {
+            return Err(IndexError::NotFound(key.clone()));
+        }
+        Ok(IndexHandle::new(key.clone(), Arc::clone(&self.indexes)))
+    }
+
+    /// Returns the status of the index or `None` if the index is not managed by the engine.
+    pub fn index_status(&self, key: &IndexKey) -> Option<IndexStatus> {
+        let indexes = self.indexes.read().unwrap();
+        indexes
+            .get_vs(key)
+            .map(|entry| entry.status())
+            .or_else(|| indexes.get_fts(key).map(|entry| entry.status()))
+    }
+}
+
 pub(crate) struct IndexFactories {
     pub(crate) vs: Box<dyn VsIndexFactory + Send + Sync>,
     pub(crate) fts: Box<dyn FtsIndexFactory + Send + Sync>,
 }
 
+/// Actors and shared state the engine creates its indexes with.
+pub(crate) struct EngineDeps {
+    pub(crate) db: mpsc::Sender<Db>,
+    pub(crate) index_factories: IndexFactories,
+    pub(crate) embedder: Option<Embedder>,
+    pub(crate) node_state: Sender<NodeState>,
+    pub(crate) metrics: Arc<Metrics>,
+    pub(crate) indexes: Arc<RwLock<Indexes>>,
+    pub(crate) internals: Sender<Internals>,
+}
+
 pub(crate) async fn new(
-    db: mpsc::Sender<Db>,
-    index_factories: IndexFactories,
-    node_state: Sender<NodeState>,
-    metrics: Arc<Metrics>,
-    indexes: Arc<RwLock<Indexes>>,
-    internals: Sender<Internals>,
+    deps: EngineDeps,
     config_rx: watch::Receiver<Arc<Config>>,
+    membership_rx: watch::Receiver<Option<Arc<Membership>>>,
 ) -> anyhow::Result<mpsc::Sender<Engine>> {
     let (tx, mut rx) = mpsc::channel(perf::channel_size().into());
 
     let monitor_actor = monitor_indexes::new(
-        db.clone(),
+        deps.db.clone(),
         tx.downgrade(),
-        node_state.clone(),
+        deps.node_state.clone(),
         config_rx.clone(),
+        membership_rx,
     )
     .await?;
     let check_interval = config_rx
         .borrow()
         .engine_status_update_interval
         .unwrap_or(Duration::from_secs(1));
-    let memory_actor = memory::new(internals, config_rx);
+    actor_trace::watch_config(config_rx.clone());
+    let memory_actor = memory::new(deps.internals.clone(), config_rx.clone());
 
     tokio::spawn(
         async move {
             debug!("starting");
 
             let mut interval = time::interval(check_interval);
+            let mut keyspace_memory = HashMap::new();
             loop {
                 tokio::select! {
                     msg = rx.recv() => {
                         let Some(msg) = msg else {
                             break;
                         };
+                        let _trace = actor_trace::start("engine", msg.name(), None, rx.len());
                         match msg {
-                            Engine::GetVsIndexKeys { tx } => get_vs_index_keys(tx, &indexes).await,
+                            Engine::GetVsIndexKeys { tx } => {
+                                get_vs_index_keys(tx, &deps.indexes).await
+                            }
 
                             Engine::AddIndex { metadata, tx } => {
-                                add_index(
-                                    metadata,
-                                    tx,
-                                    &db,
-                                    &index_factories,
-                                    &indexes,
-                                    metrics.clone(),
-                                    memory_actor.clone(),
-                                )
-                                .await
+                                let memory = memory_for_keyspace(
+                                    &metadata.keyspace_name,
+                                    &memory_actor,
+                                    &mut keyspace_memory,
+                                    &config_rx,
+                                );
+                                let config = config_rx.borrow().clone();
+                                add_index(metadata, tx, &deps, memory, &config).await
                             }
 
-                            Engine::DelIndex { key } => del_index(key, &indexes, &metrics).await,
+                            Engine::DelIndex { key } => {
+                                del_index(key, &deps.node_state, &deps.indexes, &deps.metrics).await
+                            }
 
-                            Engine::GetVsIndex { key, tx } => get_vs_index(key, tx, &indexes).await,
+                            Engine::GetVsIndex { key, tx } => {
+                                get_vs_index(key, tx, &deps.indexes).await
+                            }
 
                             Engine::GetFtsIndex { key, tx } => {
-                                get_fts_index(key, tx, &indexes).await
+                                get_fts_index(key, tx, &deps.indexes).await
                             }
 
                         }
                     }
 
-                    _ = interval.tick() => update_indexes(&node_state, &indexes).await,
+                    _ = interval.tick() => update_indexes(&deps.node_state, &deps.indexes).await,
                 }
             }
             drop(monitor_actor);
@@ -200,6 +336,31 @@ pub(crate) async fn new(
     Ok(tx)
 }
 
+/// Returns the memory actor for indexes of the keyspace. Keyspaces with a configured memory limit
+/// get a dedicated actor, which is kept for the lifetime of the engine.
+fn memory_for_keyspace(
+    keyspace: &KeyspaceName,
+    node: &Sender<Memory>,
+    keyspace_memory: &mut HashMap<KeyspaceName, Sender<Memory>>,
+    config_rx: &watch::Receiver<Arc<Config>>,
+) -> Sender<Memory> {
+    let limited = config_rx
+        .borrow()
+        .keyspace_partitions
+        .get(keyspace)
+        .is_some_and(|partition| partition.memory_limit.is_some());
+    if !limited && !keyspace_memory.contains_key(keyspace) {
+        return node.clone();
+    }
+    keyspace_memory
+        .entry(keyspace.clone())
+        .or_insert_with(|| {
+            info!("creating a memory partition for keyspace {keyspace}");
+            memory::new_keyspace(keyspace.clone(), node.clone(), config_rx.clone())
+        })
+        .clone()
+}
+
 async fn get_vs_index_keys(tx: oneshot::Sender<GetVsIndexKeysR>, indexes: &RwLock<Indexes>) {
     let keys = indexes
         .read()
@@ -214,12 +375,19 @@ async fn get_vs_index_keys(tx: oneshot::Sender<GetVsIndexKeysR>, indexes: &RwLoc
 async fn add_index(
     metadata: IndexMetadata,
     tx: oneshot::Sender<AddIndexR>,
-    db: &mpsc::Sender<Db>,
-    index_factories: &IndexFactories,
-    indexes: &RwLock<Indexes>,
-    metrics: Arc<Metrics>,
+    deps: &EngineDeps,
     memory: Sender<Memory>,
+    config: &Config,
 ) {
+    let EngineDeps {
+        db,
+        index_factories,
+        embedder,
+        node_state,
+        metrics,
+        indexes,
+        ..
+    } = deps;
     let key = metadata.key();
     if indexes.read().unwrap().contains_key(&key) {
         trace!("add_index: trying to replace index with key {key}");
@@ -234,8 +402,7 @@ async fn add_index(
         Ok((db_index, embeddings_stream)) => (db_index, embeddings_stream),
         Err(err) => {
             debug!("unable to create a db monitoring task for an index {key}: {err}");
-            tx.send(Err(err))
-                .unwrap_or_else(|_| trace!("add_index: unable to send response"));
+            send_add_index_result(tx, node_state, key, Err(err)).await;
             return;
         }
     };
@@ -247,6 +414,28 @@ async fn add_index(
         DbIndexPartitioning::Local(partition_key_columns) => Some(partition_key_columns.clone()),
         DbIndexPartitioning::Global => None,
     };
+    let key_arena = match config
+        .key_storage_dir
+        .as_deref()
+        .map(KeyArena::mapped)
+        .transpose()
+    {
+        Ok(key_arena) => key_arena.unwrap_or_default(),
+        Err(err) => {
+            let err = anyhow::anyhow!("unable to create a file for primary keys: {err}");
+            debug!("unable to create a table cache for an index {key}: {err}");
+            send_add_index_result(tx, node_state, key, Err(err)).await;
+            return;
+        }
+    };
+    let key_arena = if config.key_interning {
+        key_arena.with_interning(metrics.key_interning_saved_bytes.with_label_values(&[
+            metadata.keyspace_name.as_ref(),
+            metadata.index_name.as_ref(),
+        ]))
+    } else {
+        key_arena
+    };
     let table = match Table::new(
         key.clone(),
         primary_key_columns.clone(),
@@ -256,23 +445,23 @@ async fn add_index(
         Arc::clone(&metadata.filtering_columns),
         table_columns,
     ) {
-        Ok(table) => Arc::new(RwLock::new(table)),
+        Ok(table) => Arc::new(RwLock::new(table.with_key_arena(key_arena))),
         Err(err) => {
             debug!("unable to create a table cache for an index {key}: {err}");
-            tx.send(Err(err))
-                .unwrap_or_else(|_| trace!("add_index: unable to send response"));
+            send_add_index_result(tx, node_state, key, Err(err)).await;
             return;
         }
     };
 
     let ctx = AddIndexContext {
-        key,
+        key: key.clone(),
         table,
         embeddings_stream,
-        metrics,
+        metrics: Arc::clone(metrics),
         db_index,
         indexes,
         index_factories,
+        embedder: embedder.as_ref(),
         memory,
         metadata,
     };
@@ -283,16 +472,25 @@ async fn add_index(
         add_index_fts(ctx).await
     };
 
-    match result {
-        Ok(()) => {
-            tx.send(Ok(()))
-                .unwrap_or_else(|_| trace!("add_index: unable to send response"));
-        }
-        Err(err) => {
-            tx.send(Err(err))
-                .unwrap_or_else(|_| trace!("add_index: unable to send response"));
-        }
-    }
+    send_add_index_result(tx, node_state, key, result).await;
+}
+
+async fn send_add_index_result(
+    tx: oneshot::Sender<AddIndexR>,
+    node_state: &Sender<NodeState>,
+    key: IndexKey,
+    result: AddIndexR,
+) {
+    let event = match &result {
+        Ok(()) => Event::IndexAdded(key),
+        Err(err) => Event::IndexFailed {
+            key,
+            error: err.to_string(),
+        },
+    };
+    node_state.send_event(event).await;
+    tx.send(result)
+        .unwrap_or_else(|_| trace!("add_index: unable to send response"));
 }
 
 struct AddIndexContext<'a> {
@@ -303,6 +501,7 @@ struct AddIndexContext<'a> {
     db_index: mpsc::Sender<DbIndex>,
     indexes: &'a RwLock<Indexes>,
     index_factories: &'a IndexFactories,
+    embedder: Option<&'a Embedder>,
     memory: Sender<Memory>,
     metadata: IndexMetadata,
 }
@@ -312,37 +511,67 @@ async fn add_index_vs(ctx: AddIndexContext<'_>) -> anyhow::Result<()> {
         .metadata
         .vs()
         .ok_or_else(|| anyhow::anyhow!("add_index_vs must be called with a vector-search index"))?;
-    let vs_sender = ctx.index_factories.vs.create_index(
-        VsIndexConfiguration {
-            key: ctx.key.clone(),
-            dimensions: options.dimensions,
-            connectivity: options.connectivity,
-            expansion_add: options.expansion_add,
-            expansion_search: options.expansion_search,
-            space_type: options.space_type,
-            quantization: options.quantization,
-        },
-        Arc::clone(&ctx.table),
-        ctx.memory,
-    )?;
+    let embedder = match (&options.embedding_model, ctx.embedder) {
+        (None, _) => None,
+        (Some(model), Some(embedder)) => Some(embedder.for_model(model.clone())),
+        (Some(model), None) => {
+            anyhow::bail!(
+                "the index embeds texts by the model {model}, \
+                but no embedding provider is configured"
+            );
+        }
+    };
+    let index_memory = ctx.table.read().unwrap().memory().clone();
+    let drift = VectorDrift::default();
+    let vs_sender = ctx
+        .index_factories
+        .vs
+        .create_index(
+            VsIndexConfiguration {
+                key: ctx.key.clone(),
+                dimensions: options.dimensions,
+                connectivity: options.connectivity,
+                expansion_add: options.expansion_add,
+                expansion_search: options.expansion_search,
+                space_type: options.space_type,
+                quantization: options.quantization,
+            },
+            Arc::clone(&ctx.table),
+            ctx.memory,
+        )
+        .await?;
 
     let monitor_actor = monitor_items::new(
         ctx.key.clone(),
-        ctx.table,
+        Arc::clone(&ctx.table),
         ctx.embeddings_stream,
         vs_sender.clone(),
         ctx.metrics,
+        ItemsOptions {
+            memory: index_memory.clone(),
+            drift: drift.clone(),
+            embedder,
+            ttl: options.ttl,
+        },
     )
     .await?;
 
-    let entry =
-        crate::indexes::VsIndexEntry::new(vs_sender, monitor_actor, ctx.db_index, ctx.metadata)
-            .await?;
+    let entry = crate::indexes::VsIndexEntry::new(
+        vs_sender,
+        monitor_actor,
+        ctx.db_index,
+        index_memory,
+        drift,
+        ctx.table,
+        ctx.metadata,
+    )
+    .await?;
     ctx.indexes.write().unwrap().insert_vs(ctx.key, entry);
     Ok(())
 }
 
 async fn add_index_fts(ctx: AddIndexContext<'_>) -> anyhow::Result<()> {
+    let index_memory = ctx.table.read().unwrap().memory().clone();
     let fts_sender =
         ctx.index_factories
             .fts
@@ -350,22 +579,41 @@ async fn add_index_fts(ctx: AddIndexContext<'_>) -> anyhow::Result<()> {
 
     let monitor_actor = monitor_items::new(
         ctx.key.clone(),
-        ctx.table,
+        Arc::clone(&ctx.table),
         ctx.embeddings_stream,
         fts_sender.clone(),
         ctx.metrics,
+        ItemsOptions {
+            memory: index_memory.clone(),
+            ttl: ctx.metadata.kind.ttl(),
+            ..Default::default()
+        },
     )
     .await?;
 
-    let entry = crate::indexes::FtsIndexEntry::new(fts_sender, monitor_actor, ctx.db_index).await;
+    let entry = crate::indexes::FtsIndexEntry::new(
+        fts_sender,
+        monitor_actor,
+        ctx.db_index,
+        index_memory,
+        ctx.table,
+    )
+    .await;
     ctx.indexes.write().unwrap().insert_fts(ctx.key, entry);
     Ok(())
 }
 
-async fn del_index(key: IndexKey, indexes: &RwLock<Indexes>, metrics: &Metrics) {
-    if indexes.write().unwrap().remove(&key) {
+async fn del_index(
+    key: IndexKey,
+    node_state: &Sender<NodeState>,
+    indexes: &RwLock<Indexes>,
+    metrics: &Metrics,
+) {
+    let removed = indexes.write().unwrap().remove(&key);
+    if removed {
         info!("removed the index {key}");
         metrics.remove_index_labels(key.keyspace().as_ref(), key.index().as_ref());
+        node_state.send_event(Event::IndexRemoved(key)).await;
     }
 }
 
@@ -402,6 +650,7 @@ async fn update_indexes(node_state: &Sender<NodeState>, indexes: &RwLock<Indexes
                 (
                     key.clone(),
                     entry.db_index(),
+                    entry.monitor().clone(),
                     entry.progress(),
                     entry.status(),
                 )
@@ -410,6 +659,7 @@ async fn update_indexes(node_state: &Sender<NodeState>, indexes: &RwLock<Indexes
                 (
                     key.clone(),
                     entry.db_index(),
+                    entry.monitor().clone(),
                     entry.progress(),
                     entry.status(),
                 )
@@ -417,7 +667,7 @@ async fn update_indexes(node_state: &Sender<NodeState>, indexes: &RwLock<Indexes
             .collect()
     };
 
-    for (key, db_index, progress, status) in actual_indexes.into_iter() {
+    for (key, db_index, monitor, progress, status) in actual_indexes.into_iter() {
         let Some(new_status) = node_state
             .get_index_status(key.keyspace().as_ref(), key.index().as_ref())
             .await
@@ -425,6 +675,18 @@ async fn update_indexes(node_state: &Sender<NodeState>, indexes: &RwLock<Indexes
             continue;
         };
         let new_progress = db_index.full_scan_progress().await;
+        if new_status == IndexStatus::FullScanning
+            && let Progress::InProgress(percent) = new_progress
+            && let Ok(rows) = monitor.scan_rows(new_progress).await
+        {
+            node_state
+                .send_event(Event::FullScanProgress {
+                    key: key.clone(),
+                    percent,
+                    rows,
+                })
+                .await;
+        }
         if new_progress != progress || new_status != status {
             let mut indexes = indexes.write().unwrap();
             if let Some(entry) = indexes.get_vs_mut(&key) {
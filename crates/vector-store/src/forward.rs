@@ -0,0 +1,328 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Forwarding of queries for indexes served by other nodes of the cluster.
+//!
+//! In clustered mode a node builds only the indexes it owns, so a query may arrive at a node
+//! without the index. Such a query is proxied to the replicas of the index in the order of their
+//! preference, the ones in the zone of the local node first. A forwarded query carries
+//! [`FORWARDED_HEADER`] and is never forwarded again, so nodes with different views of the
+//! membership do not bounce it between each other. A replica which does not answer within the
+//! forward timeout of the cluster is skipped like an unreachable one.
+
+use crate::IndexKey;
+use crate::Membership;
+use crate::http_auth;
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::http::header;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use secrecy::SecretString;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::debug;
+use tracing::warn;
+
+/// Set on forwarded queries to the id of the forwarding node.
+pub(crate) const FORWARDED_HEADER: &str = "x-vector-store-forwarded-by";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Clone)]
+pub(crate) struct Forwarder {
+    client: reqwest::Client,
+    membership: watch::Receiver<Option<Arc<Membership>>>,
+    timeout: Duration,
+    peer_token: Option<SecretString>,
+}
+
+impl Forwarder {
+    pub(crate) fn new(
+        membership: watch::Receiver<Option<Arc<Membership>>>,
+        timeout: Duration,
+        peer_token: Option<SecretString>,
+    ) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .expect("Forwarder::new: HTTP client should be created");
+        Self {
+            client,
+            membership,
+            timeout,
+            peer_token,
+        }
+    }
+
+    /// Forwards the query for the index to its replicas. Returns `None` when the query should be
+    /// answered locally: clustering is disabled, the local node is a replica of the index or the
+    /// query was already forwarded.
+    pub(crate) async fn forward(
+        &self,
+        key: &IndexKey,
+        path: &str,
+        headers: &HeaderMap,
+        body: &(impl serde::Serialize + Sync),
+    ) -> Option<Response> {
+        if headers.contains_key(FORWARDED_HEADER) {
+            return None;
+        }
+        let membership = self.membership.borrow().clone()?;
+        if membership.owns(key) {
+            return None;
+        }
+
+        let mut last_response = None;
+        for node in membership.preferred_replicas(key) {
+            let url = format!("{}{path}", node.url);
+            debug!("forwarding query for index {key} to {}", node.id);
+            let request = http_auth::to_peer(self.client.post(&url), self.peer_token.as_ref())
+                .header(FORWARDED_HEADER, membership.local().as_ref())
+                .timeout(self.timeout)
+                .json(body);
+            let response = match request.send().await {
+                Ok(response) => response,
+                Err(err) => {
+                    warn!(
+                        "unable to forward query for index {key} to {}: {err}",
+                        node.id
+                    );
+                    continue;
+                }
+            };
+            let response = match into_response(response).await {
+                Ok(response) => response,
+                Err(err) => {
+                    warn!(
+                        "unable to read response of {} for index {key}: {err}",
+                        node.id
+                    );
+                    continue;
+                }
+            };
+            // The replica may still be building the index while another one is serving it.
+            if response.status() != StatusCode::SERVICE_UNAVAILABLE {
+                return Some(response);
+            }
+            last_response = Some(response);
+        }
+        Some(last_response.unwrap_or_else(|| {
+            let msg = format!("unable to forward query for index {key} to its replicas");
+            (StatusCode::BAD_GATEWAY, msg).into_response()
+        }))
+    }
+}
+
+async fn into_response(response: reqwest::Response) -> reqwest::Result<Response> {
+    let status = response.status();
+    let content_type = response.headers().get(header::CONTENT_TYPE).cloned();
+    let body = response.bytes().await?;
+    let mut response = (status, body).into_response();
+    if let Some(content_type) = content_type {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_TYPE, content_type);
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AuthConfig;
+    use crate::ClusterNode;
+    use crate::IndexName;
+    use crate::KeyspaceName;
+    use crate::cluster;
+    use crate::http_auth::Authenticator;
+    use axum::Json;
+    use axum::Router;
+    use axum::routing::post;
+    use std::num::NonZeroUsize;
+    use tokio::net::TcpListener;
+    use tokio::time;
+
+    const UNREACHABLE: &str = "http://127.0.0.1:1";
+
+    /// Spawns a node answering queries with the status and the id of the forwarding node.
+    async fn spawn_node(status: StatusCode) -> String {
+        spawn_authenticating_node(status, None).await
+    }
+
+    async fn spawn_authenticating_node(status: StatusCode, auth: Option<Authenticator>) -> String {
+        let router = Router::new().route(
+            "/query",
+            post(
+                move |headers: HeaderMap, Json(body): Json<String>| async move {
+                    let forwarded_by = headers[FORWARDED_HEADER].to_str().unwrap().to_string();
+                    (status, Json(format!("{body} from {forwarded_by}")))
+                },
+            ),
+        );
+        let router = http_auth::require_auth(router, auth);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        format!("http://{addr}")
+    }
+
+    /// Spawns a node accepting connections without ever answering on them.
+    async fn spawn_silent_node() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((connection, _)) = listener.accept().await {
+                connections.push(connection);
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    fn forwarder(local: &str, urls: Vec<String>) -> Forwarder {
+        forwarder_with_token(local, urls, None)
+    }
+
+    fn forwarder_with_token(
+        local: &str,
+        urls: Vec<String>,
+        peer_token: Option<SecretString>,
+    ) -> Forwarder {
+        let replication_factor = NonZeroUsize::new(urls.len()).unwrap();
+        let nodes = urls
+            .into_iter()
+            .enumerate()
+            .map(|(i, url)| ClusterNode {
+                id: format!("node{i}").into(),
+                url,
+                zone: None,
+            })
+            .collect();
+        let membership = Membership::new(local.into(), nodes, replication_factor);
+        Forwarder::new(
+            watch::channel(Some(Arc::new(membership))).1,
+            cluster::default_forward_timeout(),
+            peer_token,
+        )
+    }
+
+    fn key() -> IndexKey {
+        IndexKey::new(
+            &KeyspaceName("ks".to_string()),
+            &IndexName("idx".to_string()),
+        )
+    }
+
+    async fn forward(forwarder: &Forwarder, headers: &HeaderMap) -> Option<(StatusCode, String)> {
+        let response = forwarder
+            .forward(&key(), "/query", headers, &"query")
+            .await?;
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        Some((status, String::from_utf8(body.to_vec()).unwrap()))
+    }
+
+    #[tokio::test]
+    async fn query_is_forwarded_to_a_serving_replica() {
+        let forwarder = forwarder(
+            "local",
+            vec![
+                UNREACHABLE.to_string(),
+                spawn_node(StatusCode::SERVICE_UNAVAILABLE).await,
+                spawn_node(StatusCode::OK).await,
+            ],
+        );
+
+        assert_eq!(
+            forward(&forwarder, &HeaderMap::new()).await,
+            Some((StatusCode::OK, "\"query from local\"".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn replica_which_does_not_answer_is_skipped_after_the_timeout() {
+        let silent = spawn_silent_node().await;
+        let serving = spawn_node(StatusCode::OK).await;
+        // Replicas are asked in the order of their rank, so the silent one has to rank first.
+        let mut urls = vec![silent.clone(), serving];
+        let first = forwarder("local", urls.clone())
+            .membership
+            .borrow()
+            .as_ref()
+            .unwrap()
+            .preferred_replicas(&key())[0]
+            .url
+            .clone();
+        if first != silent {
+            urls.reverse();
+        }
+        let forwarder = Forwarder {
+            timeout: Duration::from_millis(100),
+            ..forwarder("local", urls)
+        };
+
+        let forwarded = time::timeout(
+            Duration::from_secs(5),
+            forward(&forwarder, &HeaderMap::new()),
+        )
+        .await
+        .expect("the query should not wait for the silent replica");
+        assert_eq!(
+            forwarded,
+            Some((StatusCode::OK, "\"query from local\"".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn query_is_forwarded_with_the_peer_token() {
+        let auth = Authenticator::new(&AuthConfig {
+            peer_token: Some("peer".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        let node = spawn_authenticating_node(StatusCode::OK, Some(auth)).await;
+
+        let (status, _) = forward(&forwarder("local", vec![node.clone()]), &HeaderMap::new())
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let forwarder = forwarder_with_token("local", vec![node], Some("peer".into()));
+        assert_eq!(
+            forward(&forwarder, &HeaderMap::new()).await,
+            Some((StatusCode::OK, "\"query from local\"".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn unreachable_replicas_are_reported_as_bad_gateway() {
+        let forwarder = forwarder("local", vec![UNREACHABLE.to_string()]);
+
+        let (status, _) = forward(&forwarder, &HeaderMap::new()).await.unwrap();
+        assert_eq!(status, StatusCode::BAD_GATEWAY);
+    }
+
+    #[tokio::test]
+    async fn query_is_answered_locally() {
+        let without_cluster = Forwarder::new(
+            watch::channel(None).1,
+            cluster::default_forward_timeout(),
+            None,
+        );
+        assert!(forward(&without_cluster, &HeaderMap::new()).await.is_none());
+
+        let replica = forwarder("node0", vec![spawn_node(StatusCode::OK).await]);
+        assert!(forward(&replica, &HeaderMap::new()).await.is_none());
+
+        let not_replica = forwarder("local", vec![spawn_node(StatusCode::OK).await]);
+        let mut headers = HeaderMap::new();
+        headers.insert(FORWARDED_HEADER, "node1".parse().unwrap());
+        assert!(forward(&not_replica, &headers).await.is_none());
+    }
+}
@@ -49,6 +49,18 @@ pub(crate) enum FtsIndex {
     },
 }
 
+impl FtsIndex {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            FtsIndex::AddDocument { .. } => "AddDocument",
+            FtsIndex::RemoveDocument { .. } => "RemoveDocument",
+            FtsIndex::Count { .. } => "Count",
+            FtsIndex::Search { .. } => "Search",
+            FtsIndex::Stats { .. } => "Stats",
+        }
+    }
+}
+
 pub(crate) trait FtsIndexExt {
     async fn add_document(
         &self,
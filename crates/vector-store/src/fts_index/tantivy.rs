@@ -36,6 +36,7 @@ use tracing::error;
 use crate::AsyncInProgress;
 use crate::IndexKey;
 use crate::Limit;
+use crate::actor_trace;
 use crate::fts_index::factory::FtsIndexFactory;
 use crate::memory::Allocate;
 use crate::memory::Memory;
@@ -56,12 +57,16 @@ use super::actor::FtsStatsR;
 
 pub(crate) struct TantivyIndexFactory {
     worker: async_channel::Sender<Worker>,
+    commit_interval: Duration,
+    max_uncommitted_docs: usize,
 }
 
 impl TantivyIndexFactory {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(commit_interval: Duration, max_uncommitted_docs: usize) -> Self {
         Self {
             worker: worker::new(),
+            commit_interval,
+            max_uncommitted_docs,
         }
     }
 }
@@ -78,8 +83,8 @@ impl FtsIndexFactory for TantivyIndexFactory {
             table,
             self.worker.clone(),
             memory,
-            COMMIT_INTERVAL,
-            MAX_UNCOMMITTED_THRESHOLD,
+            self.commit_interval,
+            self.max_uncommitted_docs,
         )
     }
 }
@@ -133,8 +138,6 @@ struct IndexState {
 }
 
 const TOKENIZER_NAME: &str = "standard";
-const COMMIT_INTERVAL: Duration = Duration::from_secs(3);
-const MAX_UNCOMMITTED_THRESHOLD: usize = 10_000;
 
 impl IndexState {
     fn new() -> anyhow::Result<Self> {
@@ -396,6 +399,7 @@ pub(crate) fn new(
                     let Some(msg) = msg else {
                         break;
                     };
+                    let _trace = actor_trace::start("tantivy", msg.name(), Some(&key), rx.len());
                     match msg {
                         FtsIndex::AddDocument {
                             primary_id,
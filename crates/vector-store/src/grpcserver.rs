@@ -0,0 +1,355 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! The gRPC API, serving the operations of [`httproutes`](crate::httproutes) for listing the
+//! indexes, counting them, searching them and the status of the node. It shares the engine and
+//! the indexes with the HTTP servers. Queries for indexes not served by the node are not
+//! forwarded to their owners, unlike over HTTP.
+
+use crate::IndexKey;
+use crate::KeyspaceName;
+use crate::Progress;
+use crate::Quantization;
+use crate::SimilarityScore;
+use crate::engine::Engine;
+use crate::engine::EngineExt;
+use crate::engine::EngineHandle;
+use crate::fts_index::FtsIndexExt;
+use crate::httproutes;
+use crate::indexes;
+use crate::indexes::Indexes;
+use crate::metrics::Metrics;
+use crate::node_state::NodeState;
+use crate::node_state::NodeStateExt;
+use crate::vs_index;
+use crate::vs_index::VsIndexExt;
+use prost_types::ListValue;
+use prost_types::Struct;
+use prost_types::value::Kind;
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::RwLock;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc::Sender;
+use tonic::Request;
+use tonic::Response;
+use tonic::Status;
+use tonic::transport::Server;
+use tonic::transport::server::TcpIncoming;
+use tracing::debug;
+
+mod proto {
+    tonic::include_proto!("vector_store.v1");
+}
+
+use proto::vector_store_server::VectorStore;
+use proto::vector_store_server::VectorStoreServer;
+
+/// Integers above it are not exact as a protobuf number, so they are sent as strings.
+const MAX_EXACT_INTEGER: u64 = 1 << f64::MANTISSA_DIGITS;
+
+struct Service {
+    engine: Sender<Engine>,
+    indexes: Arc<RwLock<Indexes>>,
+    metrics: Arc<Metrics>,
+    node_state: Sender<NodeState>,
+}
+
+/// Starts serving the gRPC API on the address and returns the address it listens on. The server
+/// stops together with the engine.
+pub(crate) async fn start(
+    engine: &EngineHandle,
+    metrics: Arc<Metrics>,
+    addr: SocketAddr,
+) -> anyhow::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr).await?;
+    let addr = listener.local_addr()?;
+    let service = Service {
+        engine: engine.engine().clone(),
+        indexes: Arc::clone(engine.indexes()),
+        metrics,
+        node_state: engine.node_state().clone(),
+    };
+    let stopped = engine.engine().clone();
+    tokio::spawn(async move {
+        tracing::info!("gRPC server listening on {addr}");
+        if let Err(err) = Server::builder()
+            .add_service(VectorStoreServer::new(service))
+            .serve_with_incoming_shutdown(TcpIncoming::from(listener), stopped.closed())
+            .await
+        {
+            tracing::warn!("gRPC server completed with error: {err}");
+        }
+    });
+    Ok(addr)
+}
+
+fn index_key(keyspace: String, index: String) -> IndexKey {
+    IndexKey::new(&KeyspaceName::from(keyspace), &index.into())
+}
+
+impl From<Quantization> for proto::DataType {
+    fn from(quantization: Quantization) -> Self {
+        match quantization {
+            Quantization::F32 => proto::DataType::F32,
+            Quantization::F16 => proto::DataType::F16,
+            Quantization::BF16 => proto::DataType::Bf16,
+            Quantization::I8 => proto::DataType::I8,
+            Quantization::B1 => proto::DataType::B1,
+        }
+    }
+}
+
+impl From<crate::node_state::NodeStatus> for proto::NodeStatus {
+    fn from(status: crate::node_state::NodeStatus) -> Self {
+        match status {
+            crate::node_state::NodeStatus::Initializing => proto::NodeStatus::Initializing,
+            crate::node_state::NodeStatus::ConnectingToDb => proto::NodeStatus::ConnectingToDb,
+            crate::node_state::NodeStatus::IndexingEmbeddings => proto::NodeStatus::Bootstrapping,
+            crate::node_state::NodeStatus::DiscoveringIndexes => proto::NodeStatus::Bootstrapping,
+            crate::node_state::NodeStatus::Serving => proto::NodeStatus::Serving,
+        }
+    }
+}
+
+/// Converts a value of a primary key encoded for the HTTP API.
+fn to_proto_value(value: Value) -> prost_types::Value {
+    let kind = match value {
+        Value::Null => Kind::NullValue(0),
+        Value::Bool(value) => Kind::BoolValue(value),
+        Value::Number(value) => match (value.as_u64(), value.as_i64()) {
+            (Some(unsigned), _) if unsigned > MAX_EXACT_INTEGER => {
+                Kind::StringValue(value.to_string())
+            }
+            (_, Some(signed)) if signed.unsigned_abs() > MAX_EXACT_INTEGER => {
+                Kind::StringValue(value.to_string())
+            }
+            _ => Kind::NumberValue(value.as_f64().unwrap_or_default()),
+        },
+        Value::String(value) => Kind::StringValue(value),
+        Value::Array(values) => Kind::ListValue(ListValue {
+            values: values.into_iter().map(to_proto_value).collect(),
+        }),
+        Value::Object(fields) => Kind::StructValue(Struct {
+            fields: fields
+                .into_iter()
+                .map(|(name, value)| (name, to_proto_value(value)))
+                .collect(),
+        }),
+    };
+    prost_types::Value { kind: Some(kind) }
+}
+
+fn to_proto_primary_key(
+    primary_key_columns: usize,
+    primary_key: &crate::PrimaryKey,
+) -> anyhow::Result<proto::PrimaryKey> {
+    anyhow::ensure!(
+        primary_key.len() == primary_key_columns,
+        "wrong size of a primary key: {primary_key_columns}, {}",
+        primary_key.len()
+    );
+    let values = (0..primary_key.len())
+        .map(|idx| {
+            let value = primary_key
+                .get_ref(idx)
+                .expect("primary key index out of bounds after length check");
+            httproutes::try_to_json(value).map(to_proto_value)
+        })
+        .collect::<anyhow::Result<_>>()?;
+    Ok(proto::PrimaryKey { values })
+}
+
+#[tonic::async_trait]
+impl VectorStore for Service {
+    async fn list_indexes(
+        &self,
+        _: Request<proto::ListIndexesRequest>,
+    ) -> Result<Response<proto::ListIndexesResponse>, Status> {
+        let vs_indexes = self.engine.get_vs_index_keys().await;
+        let fts_guard = self.indexes.read().unwrap();
+
+        let indexes = vs_indexes
+            .iter()
+            .map(|(key, vs)| proto::IndexInfo {
+                keyspace: key.keyspace().to_string(),
+                index: key.index().to_string(),
+                index_type: proto::IndexType::Vector.into(),
+                data_type: Some(proto::DataType::from(vs.quantization).into()),
+            })
+            .chain(fts_guard.iter_fts().map(|(key, _)| proto::IndexInfo {
+                keyspace: key.keyspace().to_string(),
+                index: key.index().to_string(),
+                index_type: proto::IndexType::Fulltext.into(),
+                data_type: None,
+            }))
+            .collect();
+        Ok(Response::new(proto::ListIndexesResponse { indexes }))
+    }
+
+    async fn count(
+        &self,
+        request: Request<proto::CountRequest>,
+    ) -> Result<Response<proto::CountResponse>, Status> {
+        let request = request.into_inner();
+        let index_key = index_key(request.keyspace, request.index);
+
+        let (vs, fts) = {
+            let indexes = self.indexes.read().unwrap();
+            (
+                indexes
+                    .get_vs(&index_key)
+                    .map(|entry| entry.index().clone()),
+                indexes
+                    .get_fts(&index_key)
+                    .map(|entry| entry.index().clone()),
+            )
+        };
+        let count = match (vs, fts) {
+            (Some(index), _) => index.count(index_key).await,
+            (None, Some(index)) => index.count(index_key).await,
+            (None, None) => {
+                return Err(Status::not_found(format!("missing index: {index_key}")));
+            }
+        };
+        let count = count.map_err(|err| {
+            let msg = format!("index.count request error: {err}");
+            debug!("grpc count: {msg}");
+            Status::internal(msg)
+        })?;
+        Ok(Response::new(proto::CountResponse {
+            count: count as u64,
+        }))
+    }
+
+    async fn ann(
+        &self,
+        request: Request<proto::AnnRequest>,
+    ) -> Result<Response<proto::AnnResponse>, Status> {
+        let request = request.into_inner();
+        let limit = NonZeroUsize::new(request.limit.unwrap_or(1) as usize)
+            .ok_or_else(|| Status::invalid_argument("limit must be positive"))?;
+        let index_key = index_key(request.keyspace, request.index);
+
+        let timer = self
+            .metrics
+            .latency
+            .with_label_values(&[index_key.keyspace().as_ref(), index_key.index().as_ref()])
+            .start_timer();
+
+        let best_index_state = self
+            .indexes
+            .read()
+            .unwrap()
+            .best_index(&index_key, &[], &[]);
+        let (routed_key, index, primary_key_columns) = match best_index_state {
+            indexes::BestIndexState::Serving {
+                key,
+                index,
+                primary_key_columns,
+                ..
+            } => (key, index, primary_key_columns),
+            indexes::BestIndexState::NoGlobalIndex => {
+                return Err(Status::failed_precondition(format!(
+                    "Global ANN query is not supported when only a local \
+                    vector index is available for {index_key}"
+                )));
+            }
+            indexes::BestIndexState::NotServing(Progress::InProgress(percentage)) => {
+                return Err(Status::unavailable(format!(
+                    "Index {index_key} is not available yet as it is still being constructed, \
+                    progress: {:.3}%",
+                    percentage.get()
+                )));
+            }
+            indexes::BestIndexState::NotServing(Progress::Done) => {
+                return Err(Status::internal(format!(
+                    "Index {index_key} is not serving, but full scan did finish."
+                )));
+            }
+            indexes::BestIndexState::NotFound => {
+                return Err(Status::not_found(format!("missing index: {index_key}")));
+            }
+        };
+
+        if let Some(entry) = self.indexes.read().unwrap().get_vs(&routed_key)
+            && entry.options().dimensions.0.get() == request.vector.len()
+        {
+            entry.drift().observe_queried(&request.vector);
+        }
+
+        let search_result = index
+            .ann(routed_key, request.vector.into(), limit.into())
+            .await;
+        timer.observe_duration();
+
+        let (primary_keys, distances) =
+            search_result.map_err(|err| match err.downcast_ref::<vs_index::Error>() {
+                Some(err) => Status::invalid_argument(err.to_string()),
+                None => {
+                    let msg = format!("index.ann request error: {err}");
+                    debug!("grpc ann: {msg}");
+                    Status::internal(msg)
+                }
+            })?;
+        let primary_keys = primary_keys
+            .iter()
+            .map(|primary_key| to_proto_primary_key(primary_key_columns.len(), primary_key))
+            .collect::<anyhow::Result<_>>()
+            .map_err(|err| Status::internal(err.to_string()))?;
+        Ok(Response::new(proto::AnnResponse {
+            primary_key_columns: primary_key_columns
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            primary_keys,
+            similarity_scores: distances
+                .iter()
+                .copied()
+                .map(|distance| SimilarityScore::from(distance).into())
+                .collect(),
+            distances: distances.into_iter().map(f32::from).collect(),
+        }))
+    }
+
+    async fn status(
+        &self,
+        _: Request<proto::StatusRequest>,
+    ) -> Result<Response<proto::StatusResponse>, Status> {
+        let status = proto::NodeStatus::from(self.node_state.get_status().await);
+        Ok(Response::new(proto::StatusResponse {
+            status: status.into(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primary_key_values_conversion() {
+        let number = |value: f64| Some(Kind::NumberValue(value));
+        let string = |value: &str| Some(Kind::StringValue(value.to_string()));
+
+        assert_eq!(to_proto_value(Value::from(7)).kind, number(7.0));
+        assert_eq!(to_proto_value(Value::from(-1.5)).kind, number(-1.5));
+        assert_eq!(to_proto_value(Value::from("key")).kind, string("key"));
+        assert_eq!(
+            to_proto_value(Value::from(MAX_EXACT_INTEGER)).kind,
+            number(MAX_EXACT_INTEGER as f64)
+        );
+        assert_eq!(
+            to_proto_value(Value::from(i64::MIN)).kind,
+            string("-9223372036854775808")
+        );
+        assert_eq!(
+            to_proto_value(Value::from(u64::MAX)).kind,
+            string("18446744073709551615")
+        );
+    }
+}
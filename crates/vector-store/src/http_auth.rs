@@ -0,0 +1,410 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Authentication of the requests of the HTTP API by bearer tokens.
+//!
+//! A request is accepted when its `Authorization` header carries one of the static tokens of the
+//! node, or a JSON Web Token signed by the configured key. Other requests are answered with
+//! `401 Unauthorized` and a JSON body, except the requests of the routes used by monitoring.
+//!
+//! The nodes of a cluster authenticate to each other with the peer token, which every node sends
+//! with its probes, gossip, forwarded queries and version requests. The peer token is accepted
+//! only by the routes the peers call, and the routes between the nodes, the gossip and the
+//! forwarded queries, accept only the peer token. The status and the index versions are called by
+//! both the clients and the peers.
+
+use crate::cluster;
+use crate::forward;
+use crate::index_versions;
+use anyhow::Context;
+use axum::Json;
+use axum::Router;
+use axum::extract::Request;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::http::header;
+use axum::middleware;
+use axum::middleware::Next;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use jsonwebtoken::Algorithm;
+use jsonwebtoken::DecodingKey;
+use jsonwebtoken::Validation;
+use secrecy::ExposeSecret;
+use secrecy::SecretString;
+use std::sync::Arc;
+
+/// Routes served without authentication.
+const EXEMPT_PATHS: [&str; 3] = ["/metrics", "/healthz", "/readyz"];
+
+/// Routes called only by the peers.
+const PEER_PATHS: [&str; 1] = [cluster::GOSSIP_PATH];
+
+/// Routes called by both the clients and the peers, which probe the status of each other and
+/// collect the versions of their indexes.
+const SHARED_PATHS: [&str; 2] = ["/api/v1/status", index_versions::PATH];
+
+/// Who a request is authenticated as.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Caller {
+    Client,
+    Peer,
+}
+
+/// Settings of the authentication. Secrets are never (de)serialized.
+#[derive(Clone, Debug, Default)]
+pub struct AuthConfig {
+    /// Static bearer tokens.
+    pub tokens: Vec<SecretString>,
+    /// Key validating the signatures of JSON Web Tokens: the secret of the HMAC algorithms, or
+    /// the PEM encoded public key of the others.
+    pub jwt_key: Option<SecretString>,
+    /// Algorithm of the signatures of JSON Web Tokens.
+    pub jwt_algorithm: Algorithm,
+    /// Bearer token shared by the nodes of a cluster, sent with the requests to the peers.
+    pub peer_token: Option<SecretString>,
+}
+
+impl PartialEq for AuthConfig {
+    fn eq(&self, other: &Self) -> bool {
+        let secret = |secret: &SecretString| secret.expose_secret().to_string();
+        self.tokens
+            .iter()
+            .map(secret)
+            .eq(other.tokens.iter().map(secret))
+            && self.jwt_key.as_ref().map(secret) == other.jwt_key.as_ref().map(secret)
+            && self.jwt_algorithm == other.jwt_algorithm
+            && self.peer_token.as_ref().map(secret) == other.peer_token.as_ref().map(secret)
+    }
+}
+
+/// Validates the bearer tokens of requests.
+#[derive(Clone)]
+pub struct Authenticator(Arc<Inner>);
+
+struct Inner {
+    config: AuthConfig,
+    jwt: Option<(DecodingKey, Validation)>,
+}
+
+impl PartialEq for Authenticator {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.config == other.0.config
+    }
+}
+
+impl Authenticator {
+    pub(crate) fn new(config: &AuthConfig) -> anyhow::Result<Self> {
+        let jwt = config
+            .jwt_key
+            .as_ref()
+            .map(|key| {
+                let key = key.expose_secret().as_bytes();
+                let key = match config.jwt_algorithm {
+                    Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                        DecodingKey::from_secret(key)
+                    }
+                    Algorithm::RS256
+                    | Algorithm::RS384
+                    | Algorithm::RS512
+                    | Algorithm::PS256
+                    | Algorithm::PS384
+                    | Algorithm::PS512 => DecodingKey::from_rsa_pem(key)?,
+                    Algorithm::ES256 | Algorithm::ES384 => DecodingKey::from_ec_pem(key)?,
+                    Algorithm::EdDSA => DecodingKey::from_ed_pem(key)?,
+                };
+                anyhow::Ok((key, Validation::new(config.jwt_algorithm)))
+            })
+            .transpose()
+            .context("invalid JWT validation key")?;
+        Ok(Self(Arc::new(Inner {
+            config: config.clone(),
+            jwt,
+        })))
+    }
+
+    /// Returns the token the node sends to its peers.
+    pub(crate) fn peer_token(&self) -> Option<&SecretString> {
+        self.0.config.peer_token.as_ref()
+    }
+
+    fn caller(&self, token: &str) -> Option<Caller> {
+        let config = &self.0.config;
+        if config
+            .peer_token
+            .as_ref()
+            .is_some_and(|valid| is_token(valid, token))
+        {
+            return Some(Caller::Peer);
+        }
+        let is_client = config.tokens.iter().any(|valid| is_token(valid, token))
+            || self.0.jwt.as_ref().is_some_and(|(key, validation)| {
+                jsonwebtoken::decode::<serde_json::Value>(token, key, validation).is_ok()
+            });
+        is_client.then_some(Caller::Client)
+    }
+}
+
+/// Authenticates a request to a peer with the peer token, if any.
+pub(crate) fn to_peer(
+    request: reqwest::RequestBuilder,
+    peer_token: Option<&SecretString>,
+) -> reqwest::RequestBuilder {
+    match peer_token {
+        Some(token) => request.bearer_auth(token.expose_secret()),
+        None => request,
+    }
+}
+
+/// Returns the bearer token of the request, if any.
+pub(crate) fn bearer(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split_once(' '))
+        .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+        .map(|(_, token)| token.trim())
+}
+
+/// Checks if the token is the valid one.
+pub(crate) fn is_token(valid: &SecretString, token: &str) -> bool {
+    constant_time_eq(valid.expose_secret().as_bytes(), token.as_bytes())
+}
+
+/// Compares the tokens in a time independent of the position of the first difference.
+fn constant_time_eq(lhs: &[u8], rhs: &[u8]) -> bool {
+    lhs.len() == rhs.len() && lhs.iter().zip(rhs).fold(0, |acc, (l, r)| acc | (l ^ r)) == 0
+}
+
+#[derive(serde::Serialize)]
+struct ErrorBody {
+    error: &'static str,
+    message: &'static str,
+}
+
+fn unauthorized(message: &'static str) -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        Json(ErrorBody {
+            error: "UNAUTHORIZED",
+            message,
+        }),
+    )
+        .into_response()
+}
+
+async fn authenticate(State(auth): State<Authenticator>, request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    if EXEMPT_PATHS.contains(&path) {
+        return next.run(request).await;
+    }
+    let Some(token) = bearer(request.headers()) else {
+        return unauthorized("missing bearer token");
+    };
+    let Some(caller) = auth.caller(token) else {
+        return unauthorized("invalid bearer token");
+    };
+    let between_peers =
+        PEER_PATHS.contains(&path) || request.headers().contains_key(forward::FORWARDED_HEADER);
+    let accepted = match caller {
+        Caller::Peer => between_peers || SHARED_PATHS.contains(&path),
+        Caller::Client => !between_peers,
+    };
+    if !accepted {
+        return unauthorized("bearer token not accepted by this route");
+    }
+    next.run(request).await
+}
+
+/// Requires authentication for all routes of the router but the exempt ones.
+pub(crate) fn require_auth(router: Router, auth: Option<Authenticator>) -> Router {
+    match auth {
+        Some(auth) => router.layer(middleware::from_fn_with_state(auth, authenticate)),
+        None => router,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderName;
+    use axum::http::HeaderValue;
+    use axum::routing::get;
+    use axum::routing::post;
+    use axum_test::TestServer;
+    use jsonwebtoken::EncodingKey;
+    use jsonwebtoken::Header;
+
+    fn server(config: AuthConfig) -> TestServer {
+        let router = Router::new()
+            .route("/api/v1/status", get(|| async { "SERVING" }))
+            .route("/api/v1/indexes", get(|| async { "[]" }))
+            .route(cluster::GOSSIP_PATH, post(|| async { "[]" }))
+            .route("/metrics", get(|| async { "" }));
+        TestServer::new(require_auth(
+            router,
+            Some(Authenticator::new(&config).unwrap()),
+        ))
+    }
+
+    fn jwt(secret: &[u8], exp: u64) -> String {
+        jsonwebtoken::encode(
+            &Header::default(),
+            &serde_json::json!({ "sub": "client", "exp": exp }),
+            &EncodingKey::from_secret(secret),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn static_tokens_are_accepted() {
+        let server = server(AuthConfig {
+            tokens: vec!["first".into(), "second".into()],
+            ..Default::default()
+        });
+
+        let response = server
+            .get("/api/v1/status")
+            .authorization_bearer("second")
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .get("/api/v1/status")
+            .authorization_bearer("third")
+            .await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            response.json::<serde_json::Value>(),
+            serde_json::json!({
+                "error": "UNAUTHORIZED",
+                "message": "invalid bearer token"
+            })
+        );
+
+        let response = server.get("/api/v1/status").await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.header(header::WWW_AUTHENTICATE), "Bearer");
+
+        let response = server.get("/metrics").await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn signed_tokens_are_accepted() {
+        let server = server(AuthConfig {
+            jwt_key: Some("secret".into()),
+            ..Default::default()
+        });
+        let exp = jsonwebtoken::get_current_timestamp() + 60;
+
+        let response = server
+            .get("/api/v1/status")
+            .authorization_bearer(jwt(b"secret", exp))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .get("/api/v1/status")
+            .authorization_bearer(jwt(b"other", exp))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+        let response = server
+            .get("/api/v1/status")
+            .authorization_bearer(jwt(b"secret", exp - 3600))
+            .await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    }
+
+    fn forwarded() -> (HeaderName, HeaderValue) {
+        (
+            HeaderName::from_static(forward::FORWARDED_HEADER),
+            HeaderValue::from_static("node1"),
+        )
+    }
+
+    #[tokio::test]
+    async fn peer_token_is_accepted_only_by_the_routes_called_by_peers() {
+        let server = server(AuthConfig {
+            tokens: vec!["client".into()],
+            peer_token: Some("peer".into()),
+            ..Default::default()
+        });
+
+        let response = server
+            .get("/api/v1/status")
+            .authorization_bearer("peer")
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .post(cluster::GOSSIP_PATH)
+            .authorization_bearer("peer")
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let (name, value) = forwarded();
+        let response = server
+            .get("/api/v1/indexes")
+            .authorization_bearer("peer")
+            .add_header(name, value)
+            .await;
+        assert_eq!(response.status_code(), StatusCode::OK);
+
+        let response = server
+            .get("/api/v1/indexes")
+            .authorization_bearer("peer")
+            .await;
+        assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn client_tokens_are_refused_by_the_routes_between_peers() {
+        let server = server(AuthConfig {
+            tokens: vec!["client".into()],
+            jwt_key: Some("secret".into()),
+            peer_token: Some("peer".into()),
+            ..Default::default()
+        });
+        let jwt = jwt(b"secret", jsonwebtoken::get_current_timestamp() + 60);
+
+        for token in ["client", jwt.as_str()] {
+            let response = server
+                .get("/api/v1/indexes")
+                .authorization_bearer(token)
+                .await;
+            assert_eq!(response.status_code(), StatusCode::OK);
+
+            let response = server
+                .post(cluster::GOSSIP_PATH)
+                .authorization_bearer(token)
+                .await;
+            assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+
+            let (name, value) = forwarded();
+            let response = server
+                .get("/api/v1/indexes")
+                .authorization_bearer(token)
+                .add_header(name, value)
+                .await;
+            assert_eq!(response.status_code(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[test]
+    fn invalid_public_keys_are_rejected() {
+        assert!(
+            Authenticator::new(&AuthConfig {
+                jwt_key: Some("not a key".into()),
+                jwt_algorithm: Algorithm::RS256,
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+}
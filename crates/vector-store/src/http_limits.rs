@@ -0,0 +1,261 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Limits of accepted connections and concurrent requests of an HTTP server.
+//!
+//! A connection over the limit is closed right after it is accepted, so it does not hold a file
+//! descriptor. A request over the limit is answered with `503 Service Unavailable` without being
+//! queued.
+
+use crate::metrics::Metrics;
+use axum::Router;
+use axum::extract::Request;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::middleware;
+use axum::middleware::Next;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum_server::accept::Accept;
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use prometheus::Counter;
+use prometheus::Gauge;
+use std::io;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncWrite;
+use tokio::io::ReadBuf;
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
+
+/// A permit of a connection or a request, released on drop.
+struct Permit {
+    _permit: Option<OwnedSemaphorePermit>,
+    active: Gauge,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        self.active.dec();
+    }
+}
+
+/// Counts active items of one kind and rejects them over the limit.
+#[derive(Clone)]
+struct Limit {
+    semaphore: Option<Arc<Semaphore>>,
+    active: Gauge,
+    rejected: Counter,
+}
+
+impl Limit {
+    fn new(limit: Option<NonZeroUsize>, active: Gauge, rejected: Counter) -> Self {
+        Self {
+            semaphore: limit.map(|limit| Arc::new(Semaphore::new(limit.get()))),
+            active,
+            rejected,
+        }
+    }
+
+    fn acquire(&self) -> Option<Permit> {
+        let permit = match &self.semaphore {
+            Some(semaphore) => match Arc::clone(semaphore).try_acquire_owned() {
+                Ok(permit) => Some(permit),
+                Err(_) => {
+                    self.rejected.inc();
+                    return None;
+                }
+            },
+            None => None,
+        };
+        self.active.inc();
+        Some(Permit {
+            _permit: permit,
+            active: self.active.clone(),
+        })
+    }
+}
+
+/// The limit of connections of a server, shared by all its listeners.
+#[derive(Clone)]
+pub(crate) struct ConnectionLimit(Limit);
+
+impl ConnectionLimit {
+    pub(crate) fn new(limit: Option<NonZeroUsize>, server: &str, metrics: &Metrics) -> Self {
+        Self(Limit::new(
+            limit,
+            metrics.http_connections.with_label_values(&[server]),
+            metrics
+                .http_connections_rejected_total
+                .with_label_values(&[server]),
+        ))
+    }
+
+    /// Wraps an acceptor of a listener to close connections over the limit.
+    pub(crate) fn acceptor<A>(&self, inner: A) -> ConnectionLimitAcceptor<A> {
+        ConnectionLimitAcceptor {
+            inner,
+            limit: self.0.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ConnectionLimitAcceptor<A> {
+    inner: A,
+    limit: Limit,
+}
+
+impl<A, I, S> Accept<I, S> for ConnectionLimitAcceptor<A>
+where
+    A: Accept<I, S>,
+    A::Future: Send + 'static,
+{
+    type Stream = LimitedStream<A::Stream>;
+    type Service = A::Service;
+    type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let Some(permit) = self.limit.acquire() else {
+            return futures::future::ready(Err(io::Error::other("connection limit reached")))
+                .boxed();
+        };
+        self.inner
+            .accept(stream, service)
+            .map(|result| {
+                let (inner, service) = result?;
+                Ok((
+                    LimitedStream {
+                        inner,
+                        _permit: permit,
+                    },
+                    service,
+                ))
+            })
+            .boxed()
+    }
+}
+
+/// A connection holding its permit until it is closed.
+pub(crate) struct LimitedStream<S> {
+    inner: S,
+    _permit: Permit,
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for LimitedStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for LimitedStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// Adds the concurrent requests limit to all routes of the router.
+pub(crate) fn limit_requests(
+    router: Router,
+    limit: Option<NonZeroUsize>,
+    server: &str,
+    metrics: &Metrics,
+) -> Router {
+    let limit = Limit::new(
+        limit,
+        metrics.http_requests_in_flight.with_label_values(&[server]),
+        metrics
+            .http_requests_rejected_total
+            .with_label_values(&[server]),
+    );
+    router.layer(middleware::from_fn_with_state(limit, request_limit))
+}
+
+async fn request_limit(State(limit): State<Limit>, request: Request, next: Next) -> Response {
+    let Some(_permit) = limit.acquire() else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            "too many concurrent requests",
+        )
+            .into_response();
+    };
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_rejects_over_limit_and_releases_on_drop() {
+        let metrics = Metrics::new();
+        let limit = Limit::new(
+            NonZeroUsize::new(1),
+            metrics.http_connections.with_label_values(&["test"]),
+            metrics
+                .http_connections_rejected_total
+                .with_label_values(&["test"]),
+        );
+
+        let permit = limit.acquire().unwrap();
+        assert_eq!(limit.active.get(), 1.0);
+        assert!(limit.acquire().is_none());
+        assert_eq!(limit.rejected.get(), 1.0);
+
+        drop(permit);
+        assert_eq!(limit.active.get(), 0.0);
+        assert!(limit.acquire().is_some());
+    }
+
+    #[test]
+    fn unlimited_counts_active_items() {
+        let metrics = Metrics::new();
+        let limit = Limit::new(
+            None,
+            metrics.http_requests_in_flight.with_label_values(&["test"]),
+            metrics
+                .http_requests_rejected_total
+                .with_label_values(&["test"]),
+        );
+
+        let permits: Vec<_> = (0..10).map(|_| limit.acquire().unwrap()).collect();
+        assert_eq!(limit.active.get(), 10.0);
+        drop(permits);
+        assert_eq!(limit.active.get(), 0.0);
+    }
+}
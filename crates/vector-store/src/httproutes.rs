@@ -11,19 +11,41 @@ use crate::Progress;
 use crate::Quantization;
 use crate::Restriction;
 use crate::SimilarityScore;
+use crate::Timestamp;
+use crate::cluster;
+use crate::cluster::Gossip;
+use crate::cluster::GossipError;
+use crate::cluster::Member;
+use crate::db_index::DbIndexExt;
 use crate::distance;
+use crate::drift::Drift;
+use crate::embedding;
+use crate::embedding::Embedder;
 use crate::engine::Engine;
 use crate::engine::EngineExt;
+use crate::engine::EngineHandle;
+use crate::forward::Forwarder;
 use crate::fts_index::FtsIndexExt;
+use crate::http_auth;
+use crate::http_auth::Authenticator;
+use crate::index_versions;
+use crate::index_versions::VersionAgreement;
 use crate::indexes;
 use crate::indexes::Indexes;
 use crate::info::Info;
 use crate::internals::Internals;
 use crate::internals::InternalsExt;
+use crate::invariant_key::CqlValueRef;
 use crate::metrics::Metrics;
+use crate::metrics::VECTORS_INGESTED;
+use crate::metrics::VECTORS_QUERIED;
+use crate::node_state::IndexState;
 use crate::node_state::NodeState;
 use crate::node_state::NodeStateExt;
 use crate::perf;
+use crate::rerank;
+use crate::rerank::Reranker;
+use crate::rescore;
 use crate::vector;
 use crate::vs_index;
 use crate::vs_index::VsIndexExt;
@@ -42,6 +64,7 @@ use axum::response;
 use axum::response::IntoResponse;
 use axum::response::Response;
 use axum::routing::get;
+use axum::routing::post;
 use axum::routing::put;
 use axum_server_dual_protocol::Protocol;
 use bigdecimal::BigDecimal;
@@ -67,6 +90,7 @@ use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::RwLock;
+use std::time::Duration;
 use time::Date;
 use time::OffsetDateTime;
 use time::Time;
@@ -79,6 +103,7 @@ use tracing::debug;
 use utoipa::OpenApi;
 use utoipa_axum::router::OpenApiRouter;
 use utoipa_axum::routes;
+#[cfg(feature = "swagger-ui")]
 use utoipa_swagger_ui::SwaggerUi;
 
 #[derive(OpenApi)]
@@ -118,6 +143,9 @@ use utoipa_swagger_ui::SwaggerUi;
 // TODO: modify HTTP API after design
 struct ApiDoc;
 
+/// How long an ANN query waits for the index to read its writes if the timeout is not given.
+const DEFAULT_WRITE_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[derive(Clone)]
 struct RoutesInnerState {
     engine: Sender<Engine>,
@@ -126,37 +154,68 @@ struct RoutesInnerState {
     node_state: Sender<NodeState>,
     internals: Sender<Internals>,
     index_engine_version: String,
+    forwarder: Forwarder,
+    version_agreement: VersionAgreement,
+    gossip: Gossip,
+    embedder: Option<Embedder>,
+    reranker: Option<Reranker>,
     use_tls: bool,
 }
 
 pub(crate) async fn new(
-    indexes: Arc<RwLock<Indexes>>,
-    engine: Sender<Engine>,
+    engine: &EngineHandle,
     metrics: Arc<Metrics>,
-    node_state: Sender<NodeState>,
     internals: Sender<Internals>,
-    index_engine_version: String,
     use_tls: bool,
+    auth: Option<Authenticator>,
 ) -> Router {
+    let peer_token = auth.as_ref().and_then(Authenticator::peer_token).cloned();
+    let membership = engine.membership.clone();
+    let forward_timeout = engine
+        .config_rx
+        .borrow()
+        .cluster
+        .as_ref()
+        .map_or_else(cluster::default_forward_timeout, |cluster| {
+            cluster.forward_timeout
+        });
     let state = RoutesInnerState {
-        engine,
-        indexes,
+        engine: engine.engine.clone(),
+        indexes: Arc::clone(&engine.indexes),
         metrics: metrics.clone(),
-        node_state,
+        node_state: engine.node_state.clone(),
         internals,
-        index_engine_version,
+        index_engine_version: engine.index_engine_version.clone(),
+        forwarder: Forwarder::new(membership.clone(), forward_timeout, peer_token.clone()),
+        version_agreement: VersionAgreement::new(membership, metrics, peer_token),
+        gossip: engine.gossip.clone(),
+        embedder: engine.embedder.clone(),
+        reranker: engine.reranker.clone(),
         use_tls,
     };
     let (router, api) = new_open_api_router();
     let router = router
         .route("/metrics", get(get_metrics))
+        .route("/healthz", get(get_healthz))
+        .route("/readyz", get(get_readyz))
+        .route(cluster::GOSSIP_PATH, post(post_cluster_gossip))
         .nest("/api/internals", new_internals())
-        .with_state(state)
-        .layer(TraceLayer::new_for_http());
+        .with_state(state);
+    let router = with_swagger_ui(router, api);
+
+    http_auth::require_auth(router, auth).layer(TraceLayer::new_for_http())
+}
 
+#[cfg(feature = "swagger-ui")]
+fn with_swagger_ui(router: Router, api: utoipa::openapi::OpenApi) -> Router {
     router.merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", api))
 }
 
+#[cfg(not(feature = "swagger-ui"))]
+fn with_swagger_ui(router: Router, _api: utoipa::openapi::OpenApi) -> Router {
+    router
+}
+
 pub fn api() -> utoipa::openapi::OpenApi {
     new_open_api_router().1
 }
@@ -167,10 +226,18 @@ fn new_open_api_router() -> (Router<RoutesInnerState>, utoipa::openapi::OpenApi)
             OpenApiRouter::new()
                 .routes(routes!(get_indexes))
                 .routes(routes!(get_index_status))
+                .routes(routes!(get_index_stats))
+                .routes(routes!(post_index_duplicates))
+                .routes(routes!(post_index_kmeans))
                 .routes(routes!(post_index_ann))
+                .routes(routes!(post_index_ann_text))
+                .routes(routes!(post_index_ann_batch))
+                .routes(routes!(post_index_range))
                 .routes(routes!(post_index_bm25))
+                .routes(routes!(get_index_versions))
                 .routes(routes!(get_info))
-                .routes(routes!(get_status)),
+                .routes(routes!(get_status))
+                .routes(routes!(get_cluster_index_versions)),
         )
         .split_for_parts()
 }
@@ -211,6 +278,16 @@ impl From<httpapi::IndexName> for crate::IndexName {
     }
 }
 
+impl From<Drift> for httpapi::VectorDriftStats {
+    fn from(drift: Drift) -> Self {
+        Self {
+            vectors: drift.vectors,
+            norm_drift: drift.norm,
+            direction_drift: drift.direction,
+        }
+    }
+}
+
 impl From<Quantization> for DataType {
     fn from(quantization: Quantization) -> Self {
         match quantization {
@@ -300,6 +377,7 @@ impl From<crate::node_state::IndexStatus> for httpapi::IndexStatus {
     tag = "scylla-vector-store-index",
     description = "Retrieves the current operational status and vector count for a specific vector index. \
     The response includes the index's state and the total number of vectors currently indexed (excluding tombstoned or deleted entries). \
+    While the index is bootstrapping, the progress of its initial full scan is included with the rows read so far and the estimated rows of the table. \
     This endpoint enables clients to monitor index readiness and data availability for search operations.",
     params(
         ("keyspace" = httpapi::KeyspaceName, Path, description = "The name of the ScyllaDB keyspace containing the vector index."),
@@ -309,7 +387,8 @@ impl From<crate::node_state::IndexStatus> for httpapi::IndexStatus {
         (
             status = 200,
             description = "Successful operation. Returns the current operational status of the specified index, including its state \
-            and the total number of items currently indexed.",
+            and the total number of items currently indexed. An index whose parameters exceed the limits of the node, or whose building \
+            failed, has the FAILED status with the reason.",
             body = httpapi::IndexStatusResponse,
             content_type = "application/json",
             example = json!({
@@ -344,18 +423,34 @@ async fn get_index_status(
         Fts(Sender<crate::fts_index::FtsIndex>),
     }
 
-    let (index, status) = {
+    let found = {
         let indexes = state.indexes.read().unwrap();
         if let Some(entry) = indexes.get_vs(&index_key) {
-            (IndexSender::Vs(entry.index().clone()), entry.status())
+            Some((IndexSender::Vs(entry.index().clone()), entry.status()))
         } else if let Some(entry) = indexes.get_fts(&index_key) {
-            (IndexSender::Fts(entry.index().clone()), entry.status())
+            Some((IndexSender::Fts(entry.index().clone()), entry.status()))
         } else {
-            let msg = format!("missing index: {keyspace_name}.{index_name}");
-            debug!("get_index_status: {msg}");
-            return (StatusCode::NOT_FOUND, msg).into_response();
+            None
         }
     };
+    let index_state = state.node_state.get_index_state(&index_key).await;
+    let Some((index, status)) = found else {
+        if let Some(IndexState::Failed { error }) = index_state {
+            return (
+                StatusCode::OK,
+                response::Json(httpapi::IndexStatusResponse {
+                    status: httpapi::IndexStatus::Failed,
+                    count: 0,
+                    reason: Some(error),
+                    progress: None,
+                }),
+            )
+                .into_response();
+        }
+        let msg = format!("missing index: {keyspace_name}.{index_name}");
+        debug!("get_index_status: {msg}");
+        return (StatusCode::NOT_FOUND, msg).into_response();
+    };
 
     let count_result = match index {
         IndexSender::Vs(s) => s.count(index_key).await,
@@ -372,12 +467,365 @@ async fn get_index_status(
             response::Json(httpapi::IndexStatusResponse {
                 status: status.into(),
                 count,
+                reason: None,
+                progress: match index_state {
+                    Some(IndexState::FullScanInProgress { percent, rows })
+                        if status == crate::node_state::IndexStatus::FullScanning =>
+                    {
+                        Some(httpapi::IndexBuildProgress {
+                            percent: percent.get(),
+                            processed_rows: rows.processed,
+                            total_rows: rows.total,
+                        })
+                    }
+                    _ => None,
+                },
+            }),
+        )
+            .into_response(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/indexes/{keyspace}/{index}/stats",
+    tag = "scylla-vector-store-index",
+    description = "Returns the estimated memory used by a specific index on this node, split into the primary keys \
+    with their maps to the internal ids, the vectors with their graph, and the rows read from the database which are \
+    not applied to the index yet. The total is checked against the memory limit of the node. \
+    For a vector index built by usearch, the internals of its HNSW graphs are returned too: the vectors, the reserved \
+    slots, and the slots of removed vectors which are not reused yet, which grow the graphs without serving searches. \
+    The searches of the index served by this node are counted with their total time. \
+    For a vector index, the drift of its ingested and query vectors from the first ones seen by this node is returned \
+    once enough of them are seen, to notice a change of the model embedding them.",
+    params(
+        ("keyspace" = httpapi::KeyspaceName, Path, description = "The name of the ScyllaDB keyspace containing the index."),
+        ("index" = httpapi::IndexName, Path, description = "The name of the ScyllaDB index within the specified keyspace.")
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Successful operation. Returns the estimated memory used by the index, in bytes, the internals of its graphs, and its searches.",
+            body = httpapi::IndexStatsResponse,
+            content_type = "application/json",
+            example = json!({
+                "memory": {
+                    "key_maps": 1048576,
+                    "graph": 8388608,
+                    "pending_queues": 4096,
+                    "total": 9441280
+                },
+                "graph": {
+                    "vectors": 9000,
+                    "capacity": 10240,
+                    "deleted_slots": 1000,
+                    "deleted_slot_ratio": 0.1,
+                    "partitions": 1
+                },
+                "searches": {
+                    "count": 1200,
+                    "seconds": 3.6
+                }
+            })
+        ),
+        (
+            status = 404,
+            description = "Index not found. Possible causes: index does not exist, or is not discovered yet.",
+            content_type = "application/json",
+            body = ErrorMessage
+        )
+    )
+)]
+async fn get_index_stats(
+    State(state): State<RoutesInnerState>,
+    Path((keyspace_name, index_name)): Path<(httpapi::KeyspaceName, httpapi::IndexName)>,
+) -> Response {
+    let keyspace_name: crate::KeyspaceName = keyspace_name.into();
+    let index_name: crate::IndexName = index_name.into();
+    let index_key = IndexKey::new(&keyspace_name, &index_name);
+
+    let (usage, vs_index, drift) = {
+        let indexes = state.indexes.read().unwrap();
+        let usage = indexes
+            .get_vs(&index_key)
+            .map(|entry| {
+                (
+                    entry.memory().usage(),
+                    Some(entry.index().clone()),
+                    Some(entry.drift().clone()),
+                )
+            })
+            .or_else(|| {
+                indexes
+                    .get_fts(&index_key)
+                    .map(|entry| (entry.memory().usage(), None, None))
+            });
+        let Some(usage) = usage else {
+            let msg = format!("missing index: {keyspace_name}.{index_name}");
+            debug!("get_index_stats: {msg}");
+            return (StatusCode::NOT_FOUND, msg).into_response();
+        };
+        usage
+    };
+
+    let graph = match vs_index {
+        None => None,
+        Some(index) => index
+            .stats(index_key)
+            .await
+            .inspect_err(|err| debug!("get_index_stats: {err}"))
+            .ok()
+            .map(|stats| {
+                let slots = stats.size + stats.deleted;
+                httpapi::IndexGraphStats {
+                    vectors: stats.size as u64,
+                    capacity: stats.capacity as u64,
+                    deleted_slots: stats.deleted as u64,
+                    deleted_slot_ratio: if slots == 0 {
+                        0.0
+                    } else {
+                        stats.deleted as f64 / slots as f64
+                    },
+                    partitions: stats.partitions as u64,
+                }
+            }),
+    };
+
+    let latency = state
+        .metrics
+        .latency
+        .with_label_values(&[keyspace_name.as_ref(), index_name.as_ref()]);
+
+    (
+        StatusCode::OK,
+        response::Json(httpapi::IndexStatsResponse {
+            memory: httpapi::IndexMemoryUsage {
+                key_maps: usage.key_maps,
+                graph: usage.graph,
+                pending_queues: usage.pending_queues,
+                total: usage.total(),
+            },
+            graph,
+            searches: httpapi::IndexSearchStats {
+                count: latency.get_sample_count(),
+                seconds: latency.get_sample_sum(),
+            },
+            drift: drift.map(|drift| httpapi::IndexDriftStats {
+                ingested: drift.ingested().map(Into::into),
+                queried: drift.queried().map(Into::into),
             }),
+        }),
+    )
+        .into_response()
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/indexes/{keyspace}/{index}/duplicates",
+    tag = "scylla-vector-store-index",
+    description = "Finds clusters of rows whose vectors are near-duplicates in a specific vector index on this node. \
+    Two vectors are near-duplicates if the distance between them is not greater than the given maximum distance, \
+    and a cluster joins all rows linked by near-duplicates. Each vector is compared with its nearest neighbours \
+    found in the index, so the clusters are approximate like the searches. The whole index is searched, \
+    which is as costly as a search for every row, so it is meant for cleaning up datasets.",
+    params(
+        ("keyspace" = httpapi::KeyspaceName, Path, description = "The name of the ScyllaDB keyspace containing the index."),
+        ("index" = httpapi::IndexName, Path, description = "The name of the ScyllaDB index within the specified keyspace.")
+    ),
+    request_body = httpapi::PostIndexDuplicatesRequest,
+    responses(
+        (
+            status = 200,
+            description = "Successful operation. Returns the clusters of near-duplicates, the largest first.",
+            body = httpapi::PostIndexDuplicatesResponse,
+            content_type = "application/json",
+            example = json!({
+                "clusters": [
+                    {
+                        "id": [12, 40, 41]
+                    },
+                    {
+                        "id": [7, 99]
+                    }
+                ]
+            })
+        ),
+        (
+            status = 400,
+            description = "Bad request. Possible causes: malformed input, or a negative maximum distance.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 403,
+            description = "Forbidden. TLS is enabled in the configuration, but the client connected over plain HTTP.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 404,
+            description = "Index not found. Possible causes: index does not exist, is not discovered yet, or is not a vector index.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 500,
+            description = "Error while searching. Possible causes: internal error, or the engine of the index does not find duplicates.",
+            content_type = "application/json",
+            body = ErrorMessage
+        )
+    )
+)]
+async fn post_index_duplicates(
+    State(state): State<RoutesInnerState>,
+    extensions: Extensions,
+    Path((keyspace, index_name)): Path<(httpapi::KeyspaceName, httpapi::IndexName)>,
+    extract::Json(request): extract::Json<httpapi::PostIndexDuplicatesRequest>,
+) -> Response {
+    let keyspace: crate::KeyspaceName = keyspace.into();
+    let index_name: crate::IndexName = index_name.into();
+    if let Some(resp) = check_insecure_tls(state.use_tls, &extensions, "post_index_duplicates") {
+        return resp;
+    }
+
+    if request.max_distance.is_nan() || request.max_distance < 0.0 {
+        let msg = format!(
+            "max_distance must not be negative: {}",
+            request.max_distance
+        );
+        debug!("post_index_duplicates: {msg}");
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
+
+    let index_key = IndexKey::new(&keyspace, &index_name);
+    let (index, primary_key_columns) = {
+        let indexes = state.indexes.read().unwrap();
+        let Some(entry) = indexes.get_vs(&index_key) else {
+            let msg = format!("missing index: {keyspace}.{index_name}");
+            debug!("post_index_duplicates: {msg}");
+            return (StatusCode::NOT_FOUND, msg).into_response();
+        };
+        (entry.index().clone(), entry.primary_key_columns().clone())
+    };
+
+    let clusters = index
+        .duplicates(index_key, request.max_distance)
+        .await
+        .and_then(|clusters| {
+            clusters
+                .iter()
+                .map(|cluster| try_collect_primary_keys(primary_key_columns.as_slice(), cluster))
+                .collect::<anyhow::Result<_>>()
+        });
+
+    match clusters {
+        Err(err) => {
+            let msg = format!("index.duplicates request error: {err}");
+            debug!("post_index_duplicates: {msg}");
+            (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+        }
+        Ok(clusters) => (
+            StatusCode::OK,
+            response::Json(httpapi::PostIndexDuplicatesResponse { clusters }),
         )
             .into_response(),
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/indexes/{keyspace}/{index}/kmeans",
+    tag = "scylla-vector-store-index",
+    description = "Computes k centroids of the vectors of a specific vector index on this node by mini-batch k-means, \
+    with the number of vectors nearest to each of them. The centroids are seeded by k-means++ from a sample of the \
+    vectors and refined by random batches of them, using Euclidean distance whatever the similarity function of the \
+    index. The clusters are counted by a pass over all vectors. It is meant for routing vectors by their clusters \
+    and for exploring the data, and it is as costly as reading every vector of the index.",
+    params(
+        ("keyspace" = httpapi::KeyspaceName, Path, description = "The name of the ScyllaDB keyspace containing the index."),
+        ("index" = httpapi::IndexName, Path, description = "The name of the ScyllaDB index within the specified keyspace.")
+    ),
+    request_body = httpapi::PostIndexKMeansRequest,
+    responses(
+        (
+            status = 200,
+            description = "Successful operation. Returns the centroids and the sizes of their clusters.",
+            body = httpapi::PostIndexKMeansResponse,
+            content_type = "application/json",
+            example = json!({
+                "centroids": [[0.1, 0.9, 0.3], [0.8, 0.2, 0.5]],
+                "sizes": [5120, 3880]
+            })
+        ),
+        (
+            status = 400,
+            description = "Bad request. Possible causes: malformed input, or k equal to zero.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 403,
+            description = "Forbidden. TLS is enabled in the configuration, but the client connected over plain HTTP.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 404,
+            description = "Index not found. Possible causes: index does not exist, is not discovered yet, or is not a vector index.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 500,
+            description = "Error while clustering. Possible causes: internal error, or the engine of the index does not cluster vectors.",
+            content_type = "application/json",
+            body = ErrorMessage
+        )
+    )
+)]
+async fn post_index_kmeans(
+    State(state): State<RoutesInnerState>,
+    extensions: Extensions,
+    Path((keyspace, index_name)): Path<(httpapi::KeyspaceName, httpapi::IndexName)>,
+    extract::Json(request): extract::Json<httpapi::PostIndexKMeansRequest>,
+) -> Response {
+    let keyspace: crate::KeyspaceName = keyspace.into();
+    let index_name: crate::IndexName = index_name.into();
+    if let Some(resp) = check_insecure_tls(state.use_tls, &extensions, "post_index_kmeans") {
+        return resp;
+    }
+
+    let index_key = IndexKey::new(&keyspace, &index_name);
+    let index = {
+        let indexes = state.indexes.read().unwrap();
+        let Some(entry) = indexes.get_vs(&index_key) else {
+            let msg = format!("missing index: {keyspace}.{index_name}");
+            debug!("post_index_kmeans: {msg}");
+            return (StatusCode::NOT_FOUND, msg).into_response();
+        };
+        entry.index().clone()
+    };
+
+    match index.kmeans(index_key, request.k).await {
+        Err(err) => {
+            let msg = format!("index.kmeans request error: {err}");
+            debug!("post_index_kmeans: {msg}");
+            (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+        }
+        Ok(centroids) => {
+            let (centroids, sizes) = centroids
+                .into_iter()
+                .map(|centroid| (centroid.vector.as_slice().to_vec(), centroid.size as u64))
+                .unzip();
+            (
+                StatusCode::OK,
+                response::Json(httpapi::PostIndexKMeansResponse { centroids, sizes }),
+            )
+                .into_response()
+        }
+    }
+}
+
 async fn refresh_index_metrics(
     state: &RoutesInnerState,
     keyspace: KeyspaceName,
@@ -418,6 +866,41 @@ async fn refresh_index_metrics(
     }
 }
 
+fn refresh_memory_metrics(state: &RoutesInnerState) {
+    let indexes = state.indexes.read().unwrap();
+    let memory = indexes
+        .iter_vs()
+        .map(|(key, entry)| (key, entry.memory()))
+        .chain(indexes.iter_fts().map(|(key, entry)| (key, entry.memory())));
+    for (key, memory) in memory {
+        state.metrics.set_index_memory(
+            key.keyspace().as_ref(),
+            key.index().as_ref(),
+            &memory.usage(),
+        );
+    }
+}
+
+fn refresh_drift_metrics(state: &RoutesInnerState) {
+    let indexes = state.indexes.read().unwrap();
+    for (key, entry) in indexes.iter_vs() {
+        for (vectors, drift) in [
+            (VECTORS_INGESTED, entry.drift().ingested()),
+            (VECTORS_QUERIED, entry.drift().queried()),
+        ] {
+            if let Some(drift) = drift {
+                state.metrics.set_index_drift(
+                    key.keyspace().as_ref(),
+                    key.index().as_ref(),
+                    vectors,
+                    drift.norm,
+                    drift.direction,
+                );
+            }
+        }
+    }
+}
+
 async fn get_metrics(
     State(state): State<RoutesInnerState>,
     headers: HeaderMap,
@@ -427,6 +910,10 @@ async fn get_metrics(
         let index_name = IndexName::from(index_name_str);
         refresh_index_metrics(&state, keyspace, index_name).await;
     }
+    refresh_memory_metrics(&state);
+    refresh_drift_metrics(&state);
+    let local_versions = index_versions::local(&state.indexes.read().unwrap());
+    state.version_agreement.collect(local_versions).await;
     let metric_families = state.metrics.registry.gather();
 
     // Decide which encoder and content-type to use
@@ -510,6 +997,10 @@ impl From<distance::Distance> for httpapi::Distance {
 Returns the vectors most similar to the provided vector. \
 The maximum number of results is controlled by the optional 'limit' parameter in the payload. \
 The similarity metric is determined at index creation and cannot be changed per query. \
+If the payload has an 'oversampling' factor and the index is quantized, more candidates are searched and then reordered by the exact distances of their vectors read from the table. \
+If the payload has a 'rerank' object, more candidates are searched and then reordered by the relevance of their texts to its query, \
+as scored by the cross-encoder model configured on the server. \
+If the payload has a 'payload' list of columns stored by the index, their values are returned for each result. \
 If TLS is enabled on the server, clients must connect using a HTTPS protocol.",
     params(
         ("keyspace" = httpapi::KeyspaceName, Path, description = "The name of the ScyllaDB keyspace containing the vector index."),
@@ -524,7 +1015,7 @@ If TLS is enabled on the server, clients must connect using a HTTPS protocol.",
         ),
         (
             status = 400,
-            description = "Bad request. Possible causes: invalid vector size, malformed input, or missing required fields.",
+            description = "Bad request. Possible causes: invalid vector size, a rerank column which is not a text column, a payload column which is not stored by the index, malformed input, or missing required fields.",
             content_type = "application/json",
             body = ErrorMessage
         ),
@@ -540,9 +1031,27 @@ If TLS is enabled on the server, clients must connect using a HTTPS protocol.",
             content_type = "application/json",
             body = ErrorMessage
         ),
+        (
+            status = 429,
+            description = "Too many requests. The rate limit of the rerank provider is exceeded.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
         (
             status = 500,
-            description = "Error while searching vectors. Possible causes: internal error, or search engine issues.",
+            description = "Error while searching vectors. Possible causes: internal error, reading the vectors or the texts of the candidates failed, local rerank model failure, or search engine issues.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 501,
+            description = "Not implemented. Reranking is requested, but no rerank model is configured on the server.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 502,
+            description = "Bad gateway. The rerank provider failed, or the index is served by other nodes of the cluster, but none of them answered the forwarded query.",
             content_type = "application/json",
             body = ErrorMessage
         ),
@@ -556,6 +1065,7 @@ If TLS is enabled on the server, clients must connect using a HTTPS protocol.",
 async fn post_index_ann(
     State(state): State<RoutesInnerState>,
     extensions: Extensions,
+    headers: HeaderMap,
     Path((keyspace, index_name)): Path<(httpapi::KeyspaceName, httpapi::IndexName)>,
     extract::Json(request): extract::Json<httpapi::PostIndexAnnRequest>,
 ) -> Response {
@@ -566,185 +1076,878 @@ async fn post_index_ann(
             return resp;
         }
 
-        // Start timing
-        let timer = state
-            .metrics
-            .latency
-            .with_label_values(&[keyspace.as_ref(), index_name.as_ref()])
-            .start_timer();
+        // Start timing
+        let timer = state
+            .metrics
+            .latency
+            .with_label_values(&[keyspace.as_ref(), index_name.as_ref()])
+            .start_timer();
+
+        let index_key = IndexKey::new(&keyspace, &index_name);
+        let (equality_cols, range_cols) = restriction_columns(&request.filter);
+        let allow_filtering = request.filter.as_ref().is_some_and(|f| f.allow_filtering);
+        let best_index_state =
+            state
+                .indexes
+                .read()
+                .unwrap()
+                .best_index(&index_key, &equality_cols, &range_cols);
+        let (routed_key, index, primary_key_columns, filtering_columns, table_columns) =
+            match best_index_state {
+            indexes::BestIndexState::Serving {
+                key: routed_key,
+                index,
+                needs_filtering,
+                primary_key_columns,
+                filtering_columns,
+                table_columns,
+            } => {
+                if matches!(needs_filtering, indexes::NeedsFiltering::Yes(_)) && !allow_filtering {
+                    timer.observe_duration();
+
+                    let msg = format!(
+                        "Index {keyspace}.{index_name} requires ALLOW FILTERING for this query"
+                    );
+                    debug!("post_index_ann: {msg}");
+                    return (StatusCode::BAD_REQUEST, msg).into_response();
+                }
+                (
+                    routed_key,
+                    index,
+                    primary_key_columns,
+                    filtering_columns,
+                    table_columns,
+                )
+            }
+            indexes::BestIndexState::NoGlobalIndex => {
+                timer.observe_duration();
+
+                let msg = format!(
+                    "Global ANN query is not supported when only a local \
+                    vector index is available for {keyspace}.{index_name}"
+                );
+                debug!("post_index_ann: {msg}");
+                return (StatusCode::BAD_REQUEST, msg).into_response();
+            }
+            indexes::BestIndexState::NotServing(progress) => {
+                timer.observe_duration();
+
+                match progress {
+                    Progress::InProgress(percentage) => {
+                        let reason = index_not_ready_reason(
+                            &state.node_state,
+                            &keyspace,
+                            &index_name,
+                            percentage,
+                        )
+                        .await;
+                        debug!(
+                            "post_index_ann: index {keyspace}.{index_name} not ready: {reason:?}"
+                        );
+                        return (StatusCode::SERVICE_UNAVAILABLE, response::Json(reason))
+                            .into_response();
+                    }
+                    Progress::Done => {
+                        let msg = format!(
+                            "Index {keyspace}.{index_name} is not serving, \
+                            but full scan did finish."
+                        );
+                        debug!("post_index_ann: {msg}");
+                        return (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response();
+                    }
+                }
+            }
+            indexes::BestIndexState::NotFound => {
+                let path = format!("/api/v1/indexes/{keyspace}/{index_name}/ann");
+                if let Some(resp) = state
+                    .forwarder
+                    .forward(&index_key, &path, &headers, &request)
+                    .await
+                {
+                    timer.observe_duration();
+                    return resp;
+                }
+                timer.observe_duration();
+
+                let msg = format!("missing index: {keyspace}.{index_name}");
+                debug!("post_index_ann: {msg}");
+                return (StatusCode::NOT_FOUND, msg).into_response();
+            }
+        };
+
+        let payload_columns = match request.payload {
+            None => None,
+            Some(columns) => {
+                let columns: Vec<crate::ColumnName> =
+                    columns.into_iter().map(Into::into).unique().collect();
+                if let Some(column) = columns
+                    .iter()
+                    .find(|column| !filtering_columns.contains(column))
+                {
+                    timer.observe_duration();
+                    let msg = format!("Payload column {column} is not stored by the index");
+                    debug!("post_index_ann: {msg}");
+                    return (StatusCode::BAD_REQUEST, msg).into_response();
+                }
+                Some(columns)
+            }
+        };
+
+        let limit: NonZeroUsize = request.limit.into();
+        let reranking = match request.rerank {
+            None => None,
+            Some(rerank) => {
+                let Some(reranker) = &state.reranker else {
+                    timer.observe_duration();
+                    let msg = "Reranking requires a rerank model, which is not configured";
+                    debug!("post_index_ann: {msg}");
+                    return (StatusCode::NOT_IMPLEMENTED, msg).into_response();
+                };
+                let column: crate::ColumnName = rerank.column.into();
+                if !matches!(
+                    table_columns.get(&column),
+                    Some(NativeType::Text | NativeType::Ascii)
+                ) {
+                    timer.observe_duration();
+                    let msg = format!("Rerank column {column} is not a text column of the table");
+                    debug!("post_index_ann: {msg}");
+                    return (StatusCode::BAD_REQUEST, msg).into_response();
+                }
+                let Some(table) = state
+                    .indexes
+                    .read()
+                    .unwrap()
+                    .get_vs(&routed_key)
+                    .map(|entry| entry.table().clone())
+                else {
+                    timer.observe_duration();
+                    let msg = format!("missing index: {keyspace}.{index_name}");
+                    debug!("post_index_ann: {msg}");
+                    return (StatusCode::NOT_FOUND, msg).into_response();
+                };
+                let oversampling = rerank
+                    .oversampling
+                    .unwrap_or_else(|| reranker.oversampling());
+                Some((
+                    reranker,
+                    rerank.query,
+                    rerank::TextColumn {
+                        keyspace: routed_key.keyspace(),
+                        table,
+                        column,
+                        primary_key_columns: primary_key_columns.clone(),
+                    },
+                    oversampling,
+                ))
+            }
+        };
+        let search_limit = reranking
+            .as_ref()
+            .map_or(limit, |(_, _, _, oversampling)| {
+                limit.saturating_mul(*oversampling)
+            });
+        let rescoring = request.oversampling.and_then(|oversampling| {
+            let indexes = state.indexes.read().unwrap();
+            let entry = indexes.get_vs(&routed_key)?;
+            let options = entry.options();
+            (options.quantization != Quantization::F32).then(|| {
+                (
+                    entry.db_index(),
+                    vector::Vector::from(request.vector.clone()),
+                    options.space_type,
+                    oversampling,
+                )
+            })
+        });
+        let candidates_limit = rescoring
+            .as_ref()
+            .map_or(search_limit, |(_, _, _, oversampling)| {
+                search_limit.saturating_mul(*oversampling)
+            });
+
+        if let Some(min_write_timestamp) = request.min_write_timestamp {
+            let timeout = request
+                .write_wait_timeout_ms
+                .map_or(DEFAULT_WRITE_WAIT_TIMEOUT, Duration::from_millis);
+            if let Err(reason) =
+                wait_for_writes(&state.indexes, &routed_key, min_write_timestamp, timeout).await
+            {
+                timer.observe_duration();
+                debug!("post_index_ann: index {keyspace}.{index_name} not ready: {reason:?}");
+                return (StatusCode::SERVICE_UNAVAILABLE, response::Json(reason)).into_response();
+            }
+        }
+
+        #[cfg(feature = "slow-test-hooks")]
+        state
+            .internals
+            .increment_counter(format!(
+                "ann-served-request--{}--{}",
+                routed_key.keyspace(),
+                routed_key.index(),
+            ))
+            .await;
+
+        if let Some(entry) = state.indexes.read().unwrap().get_vs(&routed_key)
+            && entry.options().dimensions.0.get() == request.vector.as_ref().len()
+        {
+            entry.drift().observe_queried(request.vector.as_ref());
+        }
+
+        let search_result = if let Some(filter) = request.filter {
+            let filter = match try_from_post_index_ann_filter(
+                filter,
+                filtering_columns.as_slice(),
+                &table_columns,
+            ) {
+                Ok(filter) => filter,
+                Err(err) => {
+                    debug!("post_index_ann: {err}");
+                    return (StatusCode::BAD_REQUEST, err.to_string()).into_response();
+                }
+            };
+            index
+                .filtered_ann(
+                    routed_key.clone(),
+                    request.vector.into(),
+                    filter,
+                    candidates_limit.into(),
+                )
+                .await
+        } else {
+            index
+                .ann(
+                    routed_key.clone(),
+                    request.vector.into(),
+                    candidates_limit.into(),
+                )
+                .await
+        };
+
+        let search_result = match (search_result, rescoring) {
+            (Ok((primary_keys, _)), Some((db_index, query, space_type, _))) => {
+                rescore::rescore(
+                    &db_index,
+                    &query,
+                    space_type,
+                    primary_keys,
+                    search_limit.get(),
+                )
+                .await
+            }
+            (search_result, _) => search_result,
+        };
+
+        let search_result = match (search_result, reranking) {
+            (Ok((primary_keys, distances)), Some((reranker, query, texts, _))) => {
+                match reranker.rerank(&query, texts, &primary_keys).await {
+                    Ok(order) => Ok(rerank::reorder(
+                        primary_keys.into_iter().zip(distances).collect(),
+                        &order,
+                        limit.get(),
+                    )
+                    .into_iter()
+                    .unzip()),
+                    Err(err) => {
+                        timer.observe_duration();
+                        debug!("post_index_ann: {err}");
+                        let status = match err {
+                            rerank::Error::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+                            rerank::Error::Provider(_) => StatusCode::BAD_GATEWAY,
+                            rerank::Error::Model(_) | rerank::Error::Db(_) => {
+                                StatusCode::INTERNAL_SERVER_ERROR
+                            }
+                        };
+                        return (status, err.to_string()).into_response();
+                    }
+                }
+            }
+            (search_result, _) => search_result,
+        };
+
+        // Record duration in Prometheus
+        timer.observe_duration();
+
+        match search_result {
+            Err(err) => match err.downcast_ref::<vs_index::Error>() {
+                Some(err) => (StatusCode::BAD_REQUEST, err.to_string()).into_response(),
+                None => {
+                    let msg = format!("index.ann request error: {err}");
+                    debug!("post_index_ann: {msg}");
+                    (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+                }
+            },
+            Ok((primary_keys, distances)) => {
+                if primary_keys.len() != distances.len() {
+                    let msg = format!(
+                        "wrong size of an ann response: \
+                    number of primary_keys = {}, number of distances = {}",
+                        primary_keys.len(),
+                        distances.len()
+                    );
+                    debug!("post_index_ann: {msg}");
+                    (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+                } else {
+                    let similarity_scores: Vec<httpapi::SimilarityScore> = distances
+                        .iter()
+                        .copied()
+                        .map(SimilarityScore::from)
+                        .map(httpapi::SimilarityScore::from)
+                        .collect();
+
+                    let payload = payload_columns
+                        .map(|columns| {
+                            let values = state
+                                .indexes
+                                .read()
+                                .unwrap()
+                                .get_vs(&routed_key)
+                                .map(|entry| entry.stored_values(&primary_keys, &columns))
+                                .ok_or_else(|| anyhow!("missing index: {routed_key}"))?;
+                            try_collect_primary_keys(&columns, &values)
+                        })
+                        .transpose();
+                    let primary_keys =
+                        try_collect_primary_keys(primary_key_columns.as_slice(), &primary_keys);
+
+                    match primary_keys.and_then(|primary_keys| Ok((primary_keys, payload?))) {
+                        Err(err) => {
+                            debug!("post_index_ann: {err}");
+                            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+                        }
+                        Ok((primary_keys, payload)) => (
+                            StatusCode::OK,
+                            response::Json(httpapi::PostIndexAnnResponse {
+                                primary_keys,
+                                distances: distances.into_iter().map(|d| d.into()).collect(),
+                                similarity_scores,
+                                payload,
+                            }),
+                        )
+                            .into_response(),
+                    }
+                }
+            }
+        }
+    })
+    .await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/indexes/{keyspace}/{index}/ann/text",
+    tag = "scylla-vector-store-index",
+    description = "Performs an Approximate Nearest Neighbor (ANN) search using the specified index for the provided text. \
+The text is embedded by the 'embedding_model' of an index of a text column, or by the embedding model configured on the server for the index, and the search then proceeds as for the 'ann' endpoint. \
+Embeddings of recently queried texts are cached, and requests to the provider are rate limited. \
+If TLS is enabled on the server, clients must connect using a HTTPS protocol.",
+    params(
+        ("keyspace" = httpapi::KeyspaceName, Path, description = "The name of the ScyllaDB keyspace containing the vector index."),
+        ("index" = httpapi::IndexName, Path, description = "The name of the ScyllaDB vector index within the specified keyspace to perform the search on.")
+    ),
+    request_body = httpapi::PostIndexAnnTextRequest,
+    responses(
+        (
+            status = 200,
+            description = "Successful ANN search. Returns a list of primary keys and their corresponding distances and similarity scores for the most similar vectors found.",
+            body = httpapi::PostIndexAnnResponse
+        ),
+        (
+            status = 400,
+            description = "Bad request. Possible causes: the size of the embedding does not match the index, a rerank column which is not a text column, malformed input, or missing required fields.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 403,
+            description = "Forbidden. TLS is enabled in the configuration, but the client connected over plain HTTP.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 404,
+            description = "Index not found. Possible causes: index does not exist, or is not discovered yet.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 429,
+            description = "Too many requests. The rate limit of requests to the embedding provider, or of the rerank provider, is exceeded.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 500,
+            description = "Error while searching vectors. Possible causes: internal error, local embedding or rerank model failure, reading the texts of the candidates failed, or search engine issues.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 501,
+            description = "Not implemented. No embedding model is configured on the server for the index, or reranking is requested, but no rerank model is configured.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 502,
+            description = "Bad gateway. The embedding or rerank provider failed, or the index is served by other nodes of the cluster, but none of them answered the forwarded query.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 503,
+            response = httpapi::IndexNotReadyResponse
+        )
+    )
+)]
+async fn post_index_ann_text(
+    State(state): State<RoutesInnerState>,
+    extensions: Extensions,
+    headers: HeaderMap,
+    Path((keyspace, index_name)): Path<(httpapi::KeyspaceName, httpapi::IndexName)>,
+    extract::Json(request): extract::Json<httpapi::PostIndexAnnTextRequest>,
+) -> Response {
+    if let Some(resp) = check_insecure_tls(state.use_tls, &extensions, "post_index_ann_text") {
+        return resp;
+    }
+    let Some(embedder) = &state.embedder else {
+        let msg = "ANN search by text requires an embedding provider, \
+            which is not configured"
+            .to_string();
+        debug!("post_index_ann_text: {msg}");
+        return (StatusCode::NOT_IMPLEMENTED, msg).into_response();
+    };
+    let index_key = IndexKey::new(&keyspace.clone().into(), &index_name.clone().into());
+    // An index of a text column is searched by the embeddings of its own model.
+    let index_model = state
+        .indexes
+        .read()
+        .unwrap()
+        .get_vs(&index_key)
+        .and_then(|entry| entry.options().embedding_model.clone());
+    let embedding = match &index_model {
+        Some(model) => embedder.embed_query(model, &request.text).await,
+        None => embedder.embed(&index_key, &request.text).await,
+    };
+    let vector = match embedding {
+        Ok(vector) => vector,
+        Err(err) => {
+            debug!("post_index_ann_text: {err}");
+            let status = match err {
+                embedding::Error::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+                embedding::Error::Provider(_) => StatusCode::BAD_GATEWAY,
+                embedding::Error::Model(_) => StatusCode::INTERNAL_SERVER_ERROR,
+                embedding::Error::NoModel(_) => StatusCode::NOT_IMPLEMENTED,
+            };
+            return (status, err.to_string()).into_response();
+        }
+    };
+
+    // The embedded query is served, or forwarded to the replicas, as a query with the vector.
+    let request = httpapi::PostIndexAnnRequest {
+        vector: vector.to_vec().into(),
+        filter: request.filter,
+        limit: request.limit,
+        min_write_timestamp: request.min_write_timestamp,
+        write_wait_timeout_ms: request.write_wait_timeout_ms,
+        oversampling: request.oversampling,
+        rerank: request.rerank,
+        payload: request.payload,
+    };
+    post_index_ann(
+        State(state),
+        extensions,
+        headers,
+        Path((keyspace, index_name)),
+        extract::Json(request),
+    )
+    .await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/indexes/{keyspace}/{index}/ann/batch",
+    tag = "scylla-vector-store-index",
+    description = "Performs a batch of Approximate Nearest Neighbor (ANN) searches using the specified index. \
+Each query has a vector and an optional limit, which defaults to the 'limit' of the batch. \
+The queries are searched in parallel, and the results are returned in the order of the queries, each as the response of the 'ann' endpoint. \
+If TLS is enabled on the server, clients must connect using a HTTPS protocol.",
+    params(
+        ("keyspace" = httpapi::KeyspaceName, Path, description = "The name of the ScyllaDB keyspace containing the vector index."),
+        ("index" = httpapi::IndexName, Path, description = "The name of the ScyllaDB vector index within the specified keyspace to perform the searches on.")
+    ),
+    request_body = httpapi::PostIndexAnnBatchRequest,
+    responses(
+        (
+            status = 200,
+            description = "Successful ANN searches. Returns the primary keys, distances and similarity scores of the most similar vectors found for each query.",
+            body = httpapi::PostIndexAnnBatchResponse
+        ),
+        (
+            status = 400,
+            description = "Bad request. Possible causes: invalid vector size of a query, malformed input, or missing required fields.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 403,
+            description = "Forbidden. TLS is enabled in the configuration, but the client connected over plain HTTP.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 404,
+            description = "Index not found. Possible causes: index does not exist, or is not discovered yet.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 500,
+            description = "Error while searching vectors. Possible causes: internal error, or search engine issues.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 502,
+            description = "Bad gateway. The index is served by other nodes of the cluster, but none of them answered the forwarded queries.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 503,
+            response = httpapi::IndexNotReadyResponse
+        )
+    )
+)]
+async fn post_index_ann_batch(
+    State(state): State<RoutesInnerState>,
+    extensions: Extensions,
+    headers: HeaderMap,
+    Path((keyspace, index_name)): Path<(httpapi::KeyspaceName, httpapi::IndexName)>,
+    extract::Json(request): extract::Json<httpapi::PostIndexAnnBatchRequest>,
+) -> Response {
+    let keyspace: crate::KeyspaceName = keyspace.into();
+    let index_name: crate::IndexName = index_name.into();
+    if let Some(resp) = check_insecure_tls(state.use_tls, &extensions, "post_index_ann_batch") {
+        return resp;
+    }
+
+    let timer = state
+        .metrics
+        .latency
+        .with_label_values(&[keyspace.as_ref(), index_name.as_ref()])
+        .start_timer();
+
+    let index_key = IndexKey::new(&keyspace, &index_name);
+    let best_index_state = state
+        .indexes
+        .read()
+        .unwrap()
+        .best_index(&index_key, &[], &[]);
+    let (routed_key, index, primary_key_columns) = match best_index_state {
+        indexes::BestIndexState::Serving {
+            key,
+            index,
+            primary_key_columns,
+            ..
+        } => (key, index, primary_key_columns),
+        indexes::BestIndexState::NoGlobalIndex => {
+            timer.observe_duration();
+
+            let msg = format!(
+                "Global ANN query is not supported when only a local \
+                vector index is available for {keyspace}.{index_name}"
+            );
+            debug!("post_index_ann_batch: {msg}");
+            return (StatusCode::BAD_REQUEST, msg).into_response();
+        }
+        indexes::BestIndexState::NotServing(Progress::InProgress(percentage)) => {
+            timer.observe_duration();
+
+            let reason =
+                index_not_ready_reason(&state.node_state, &keyspace, &index_name, percentage).await;
+            debug!("post_index_ann_batch: index {keyspace}.{index_name} not ready: {reason:?}");
+            return (StatusCode::SERVICE_UNAVAILABLE, response::Json(reason)).into_response();
+        }
+        indexes::BestIndexState::NotServing(Progress::Done) => {
+            timer.observe_duration();
+
+            let msg =
+                format!("Index {keyspace}.{index_name} is not serving, but full scan did finish.");
+            debug!("post_index_ann_batch: {msg}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response();
+        }
+        indexes::BestIndexState::NotFound => {
+            let path = format!("/api/v1/indexes/{keyspace}/{index_name}/ann/batch");
+            if let Some(resp) = state
+                .forwarder
+                .forward(&index_key, &path, &headers, &request)
+                .await
+            {
+                timer.observe_duration();
+                return resp;
+            }
+            timer.observe_duration();
+
+            let msg = format!("missing index: {keyspace}.{index_name}");
+            debug!("post_index_ann_batch: {msg}");
+            return (StatusCode::NOT_FOUND, msg).into_response();
+        }
+    };
+
+    if let Some(entry) = state.indexes.read().unwrap().get_vs(&routed_key) {
+        request
+            .queries
+            .iter()
+            .map(|query| query.vector.as_ref())
+            .filter(|vector| entry.options().dimensions.0.get() == vector.len())
+            .for_each(|vector| entry.drift().observe_queried(vector));
+    }
+
+    let queries = request
+        .queries
+        .into_iter()
+        .map(|query| {
+            let limit: NonZeroUsize = query.limit.unwrap_or(request.limit).into();
+            (query.vector.into(), limit.into())
+        })
+        .collect();
+    let search_result = index.ann_batch(routed_key, queries).await;
+
+    timer.observe_duration();
+
+    let results = match search_result {
+        Err(err) => {
+            return match err.downcast_ref::<vs_index::Error>() {
+                Some(err) => (StatusCode::BAD_REQUEST, err.to_string()).into_response(),
+                None => {
+                    let msg = format!("index.ann_batch request error: {err}");
+                    debug!("post_index_ann_batch: {msg}");
+                    (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
+                }
+            };
+        }
+        Ok(results) => results,
+    };
+
+    let results = results
+        .into_iter()
+        .map(|(primary_keys, distances)| {
+            Ok(httpapi::PostIndexAnnResponse {
+                primary_keys: try_collect_primary_keys(
+                    primary_key_columns.as_slice(),
+                    &primary_keys,
+                )?,
+                similarity_scores: distances
+                    .iter()
+                    .copied()
+                    .map(SimilarityScore::from)
+                    .map(httpapi::SimilarityScore::from)
+                    .collect(),
+                distances: distances.into_iter().map(Into::into).collect(),
+                payload: None,
+            })
+        })
+        .collect::<anyhow::Result<_>>();
+    match results {
+        Err(err) => {
+            debug!("post_index_ann_batch: {err}");
+            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+        }
+        Ok(results) => (
+            StatusCode::OK,
+            response::Json(httpapi::PostIndexAnnBatchResponse { results }),
+        )
+            .into_response(),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/indexes/{keyspace}/{index}/range",
+    tag = "scylla-vector-store-index",
+    description = "Performs a range search using the specified index. \
+Returns all vectors within the 'max_distance' of the provided vector instead of a fixed number of them, nearest first. \
+The distance is the one of the similarity function of the index, as returned by the 'ann' endpoint. \
+The search is expanded until it reaches beyond the distance, so a large distance makes it as costly as reading the whole index. \
+Like any search of the approximate index, it may miss some vectors within the distance. \
+If TLS is enabled on the server, clients must connect using a HTTPS protocol.",
+    params(
+        ("keyspace" = httpapi::KeyspaceName, Path, description = "The name of the ScyllaDB keyspace containing the vector index."),
+        ("index" = httpapi::IndexName, Path, description = "The name of the ScyllaDB vector index within the specified keyspace to perform the search on.")
+    ),
+    request_body = httpapi::PostIndexRangeRequest,
+    responses(
+        (
+            status = 200,
+            description = "Successful range search. Returns the primary keys, distances and similarity scores of the vectors found within the distance.",
+            body = httpapi::PostIndexAnnResponse
+        ),
+        (
+            status = 400,
+            description = "Bad request. Possible causes: invalid vector size, a maximum distance which is not a number, malformed input, or missing required fields.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 403,
+            description = "Forbidden. TLS is enabled in the configuration, but the client connected over plain HTTP.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 404,
+            description = "Index not found. Possible causes: index does not exist, or is not discovered yet.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 500,
+            description = "Error while searching vectors. Possible causes: internal error, or the engine of the index does not search by range.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 502,
+            description = "Bad gateway. The index is served by other nodes of the cluster, but none of them answered the forwarded query.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+        (
+            status = 503,
+            response = httpapi::IndexNotReadyResponse
+        )
+    )
+)]
+async fn post_index_range(
+    State(state): State<RoutesInnerState>,
+    extensions: Extensions,
+    headers: HeaderMap,
+    Path((keyspace, index_name)): Path<(httpapi::KeyspaceName, httpapi::IndexName)>,
+    extract::Json(request): extract::Json<httpapi::PostIndexRangeRequest>,
+) -> Response {
+    let keyspace: crate::KeyspaceName = keyspace.into();
+    let index_name: crate::IndexName = index_name.into();
+    if let Some(resp) = check_insecure_tls(state.use_tls, &extensions, "post_index_range") {
+        return resp;
+    }
+
+    if request.max_distance.is_nan() {
+        let msg = "max_distance must be a number".to_string();
+        debug!("post_index_range: {msg}");
+        return (StatusCode::BAD_REQUEST, msg).into_response();
+    }
 
-        let index_key = IndexKey::new(&keyspace, &index_name);
-        let (equality_cols, range_cols) = restriction_columns(&request.filter);
-        let allow_filtering = request.filter.as_ref().is_some_and(|f| f.allow_filtering);
-        let best_index_state =
-            state
-                .indexes
-                .read()
-                .unwrap()
-                .best_index(&index_key, &equality_cols, &range_cols);
-        let (routed_key, index, primary_key_columns, filtering_columns, table_columns) =
-            match best_index_state {
-            indexes::BestIndexState::Serving {
-                key: routed_key,
-                index,
-                needs_filtering,
-                primary_key_columns,
-                filtering_columns,
-                table_columns,
-            } => {
-                if matches!(needs_filtering, indexes::NeedsFiltering::Yes(_)) && !allow_filtering {
-                    timer.observe_duration();
+    let timer = state
+        .metrics
+        .latency
+        .with_label_values(&[keyspace.as_ref(), index_name.as_ref()])
+        .start_timer();
 
-                    let msg = format!(
-                        "Index {keyspace}.{index_name} requires ALLOW FILTERING for this query"
-                    );
-                    debug!("post_index_ann: {msg}");
-                    return (StatusCode::BAD_REQUEST, msg).into_response();
-                }
-                (
-                    routed_key,
-                    index,
-                    primary_key_columns,
-                    filtering_columns,
-                    table_columns,
-                )
-            }
-            indexes::BestIndexState::NoGlobalIndex => {
-                timer.observe_duration();
+    let index_key = IndexKey::new(&keyspace, &index_name);
+    let best_index_state = state
+        .indexes
+        .read()
+        .unwrap()
+        .best_index(&index_key, &[], &[]);
+    let (routed_key, index, primary_key_columns) = match best_index_state {
+        indexes::BestIndexState::Serving {
+            key,
+            index,
+            primary_key_columns,
+            ..
+        } => (key, index, primary_key_columns),
+        indexes::BestIndexState::NoGlobalIndex => {
+            timer.observe_duration();
 
-                let msg = format!(
-                    "Global ANN query is not supported when only a local \
-                    vector index is available for {keyspace}.{index_name}"
-                );
-                debug!("post_index_ann: {msg}");
-                return (StatusCode::BAD_REQUEST, msg).into_response();
-            }
-            indexes::BestIndexState::NotServing(progress) => {
-                timer.observe_duration();
+            let msg = format!(
+                "Global range query is not supported when only a local \
+                vector index is available for {keyspace}.{index_name}"
+            );
+            debug!("post_index_range: {msg}");
+            return (StatusCode::BAD_REQUEST, msg).into_response();
+        }
+        indexes::BestIndexState::NotServing(Progress::InProgress(percentage)) => {
+            timer.observe_duration();
 
-                match progress {
-                    Progress::InProgress(percentage) => {
-                        let reason = index_not_ready_reason(
-                            &state.node_state,
-                            &keyspace,
-                            &index_name,
-                            percentage,
-                        )
-                        .await;
-                        debug!(
-                            "post_index_ann: index {keyspace}.{index_name} not ready: {reason:?}"
-                        );
-                        return (StatusCode::SERVICE_UNAVAILABLE, response::Json(reason))
-                            .into_response();
-                    }
-                    Progress::Done => {
-                        let msg = format!(
-                            "Index {keyspace}.{index_name} is not serving, \
-                            but full scan did finish."
-                        );
-                        debug!("post_index_ann: {msg}");
-                        return (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response();
-                    }
-                }
-            }
-            indexes::BestIndexState::NotFound => {
-                timer.observe_duration();
+            let reason =
+                index_not_ready_reason(&state.node_state, &keyspace, &index_name, percentage).await;
+            debug!("post_index_range: index {keyspace}.{index_name} not ready: {reason:?}");
+            return (StatusCode::SERVICE_UNAVAILABLE, response::Json(reason)).into_response();
+        }
+        indexes::BestIndexState::NotServing(Progress::Done) => {
+            timer.observe_duration();
 
-                let msg = format!("missing index: {keyspace}.{index_name}");
-                debug!("post_index_ann: {msg}");
-                return (StatusCode::NOT_FOUND, msg).into_response();
+            let msg =
+                format!("Index {keyspace}.{index_name} is not serving, but full scan did finish.");
+            debug!("post_index_range: {msg}");
+            return (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response();
+        }
+        indexes::BestIndexState::NotFound => {
+            let path = format!("/api/v1/indexes/{keyspace}/{index_name}/range");
+            if let Some(resp) = state
+                .forwarder
+                .forward(&index_key, &path, &headers, &request)
+                .await
+            {
+                timer.observe_duration();
+                return resp;
             }
-        };
+            timer.observe_duration();
 
-        #[cfg(feature = "slow-test-hooks")]
-        state
-            .internals
-            .increment_counter(format!(
-                "ann-served-request--{}--{}",
-                routed_key.keyspace(),
-                routed_key.index(),
-            ))
-            .await;
+            let msg = format!("missing index: {keyspace}.{index_name}");
+            debug!("post_index_range: {msg}");
+            return (StatusCode::NOT_FOUND, msg).into_response();
+        }
+    };
 
-        let search_result = if let Some(filter) = request.filter {
-            let filter = match try_from_post_index_ann_filter(
-                filter,
-                filtering_columns.as_slice(),
-                &table_columns,
-            ) {
-                Ok(filter) => filter,
-                Err(err) => {
-                    debug!("post_index_ann: {err}");
-                    return (StatusCode::BAD_REQUEST, err.to_string()).into_response();
-                }
-            };
-            index
-                .filtered_ann(
-                    routed_key,
-                    request.vector.into(),
-                    filter,
-                    request.limit.into(),
-                )
-                .await
-        } else {
-            index
-                .ann(routed_key, request.vector.into(), request.limit.into())
-                .await
-        };
+    if let Some(entry) = state.indexes.read().unwrap().get_vs(&routed_key)
+        && entry.options().dimensions.0.get() == request.vector.as_ref().len()
+    {
+        entry.drift().observe_queried(request.vector.as_ref());
+    }
 
-        // Record duration in Prometheus
-        timer.observe_duration();
+    let search_result = index
+        .range(routed_key, request.vector.into(), request.max_distance)
+        .await;
 
-        match search_result {
-            Err(err) => match err.downcast_ref::<vs_index::Error>() {
+    timer.observe_duration();
+
+    let (primary_keys, distances) = match search_result {
+        Err(err) => {
+            return match err.downcast_ref::<vs_index::Error>() {
                 Some(err) => (StatusCode::BAD_REQUEST, err.to_string()).into_response(),
                 None => {
-                    let msg = format!("index.ann request error: {err}");
-                    debug!("post_index_ann: {msg}");
+                    let msg = format!("index.range request error: {err}");
+                    debug!("post_index_range: {msg}");
                     (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
                 }
-            },
-            Ok((primary_keys, distances)) => {
-                if primary_keys.len() != distances.len() {
-                    let msg = format!(
-                        "wrong size of an ann response: \
-                    number of primary_keys = {}, number of distances = {}",
-                        primary_keys.len(),
-                        distances.len()
-                    );
-                    debug!("post_index_ann: {msg}");
-                    (StatusCode::INTERNAL_SERVER_ERROR, msg).into_response()
-                } else {
-                    let similarity_scores: Vec<httpapi::SimilarityScore> = distances
-                        .iter()
-                        .copied()
-                        .map(SimilarityScore::from)
-                        .map(httpapi::SimilarityScore::from)
-                        .collect();
-
-                    let primary_keys =
-                        try_collect_primary_keys(primary_key_columns.as_slice(), &primary_keys);
+            };
+        }
+        Ok(results) => results,
+    };
 
-                    match primary_keys {
-                        Err(err) => {
-                            debug!("post_index_ann: {err}");
-                            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
-                        }
-                        Ok(primary_keys) => (
-                            StatusCode::OK,
-                            response::Json(httpapi::PostIndexAnnResponse {
-                                primary_keys,
-                                distances: distances.into_iter().map(|d| d.into()).collect(),
-                                similarity_scores,
-                            }),
-                        )
-                            .into_response(),
-                    }
-                }
-            }
+    match try_collect_primary_keys(primary_key_columns.as_slice(), &primary_keys) {
+        Err(err) => {
+            debug!("post_index_range: {err}");
+            (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
         }
-    })
-    .await
+        Ok(primary_keys) => (
+            StatusCode::OK,
+            response::Json(httpapi::PostIndexAnnResponse {
+                primary_keys,
+                similarity_scores: distances
+                    .iter()
+                    .copied()
+                    .map(SimilarityScore::from)
+                    .map(httpapi::SimilarityScore::from)
+                    .collect(),
+                distances: distances.into_iter().map(Into::into).collect(),
+                payload: None,
+            }),
+        )
+            .into_response(),
+    }
 }
 
 #[utoipa::path(
@@ -1101,7 +2304,7 @@ fn try_collect_primary_keys(
                 })
                 .map_ok(|primary_key| {
                     primary_key
-                        .get(idx_column)
+                        .get_ref(idx_column)
                         .expect("primary key index out of bounds after length check")
                 })
                 .map_ok(try_to_json)
@@ -1112,42 +2315,42 @@ fn try_collect_primary_keys(
         .collect()
 }
 
-fn try_to_json(value: CqlValue) -> anyhow::Result<Value> {
+pub(crate) fn try_to_json(value: CqlValueRef<'_>) -> anyhow::Result<Value> {
     match value {
-        CqlValue::Ascii(value) => Ok(Value::String(value)),
-        CqlValue::Text(value) => Ok(Value::String(value)),
+        CqlValueRef::Ascii(value) => Ok(Value::String(value.to_string())),
+        CqlValueRef::Text(value) => Ok(Value::String(value.to_string())),
 
-        CqlValue::Boolean(value) => Ok(Value::Bool(value)),
+        CqlValueRef::Boolean(value) => Ok(Value::Bool(value)),
 
-        CqlValue::Double(value) => {
+        CqlValueRef::Double(value) => {
             Ok(Value::Number(Number::from_f64(value).ok_or_else(|| {
                 anyhow!("CqlValue::Double should be finite")
             })?))
         }
-        CqlValue::Float(value) => Ok(Value::Number(
+        CqlValueRef::Float(value) => Ok(Value::Number(
             Number::from_f64(value.into())
                 .ok_or_else(|| anyhow!("CqlValue::Float should be finite"))?,
         )),
 
-        CqlValue::Int(value) => Ok(Value::Number(value.into())),
-        CqlValue::BigInt(value) => Ok(Value::Number(value.into())),
-        CqlValue::SmallInt(value) => Ok(Value::Number(value.into())),
-        CqlValue::TinyInt(value) => Ok(Value::Number(value.into())),
+        CqlValueRef::Int(value) => Ok(Value::Number(value.into())),
+        CqlValueRef::BigInt(value) => Ok(Value::Number(value.into())),
+        CqlValueRef::SmallInt(value) => Ok(Value::Number(value.into())),
+        CqlValueRef::TinyInt(value) => Ok(Value::Number(value.into())),
 
-        CqlValue::Uuid(value) => Ok(Value::String(value.into())),
-        CqlValue::Timeuuid(value) => Ok(Value::String((*value.as_ref()).into())),
+        CqlValueRef::Uuid(value) => Ok(Value::String(value.into())),
+        CqlValueRef::Timeuuid(value) => Ok(Value::String((*value.as_ref()).into())),
 
-        CqlValue::Date(value) => Ok(Value::String(
+        CqlValueRef::Date(value) => Ok(Value::String(
             TryInto::<Date>::try_into(value)?.format(&Iso8601::DATE)?,
         )),
-        CqlValue::Time(value) => Ok(Value::String(
+        CqlValueRef::Time(value) => Ok(Value::String(
             TryInto::<Time>::try_into(value)?
                 .format(&Iso8601::TIME)?
                 .strip_prefix("T")
                 .ok_or_else(|| anyhow!("CqlValue::Time: wrong formatting detected"))?
                 .to_string(), // remove 'T' prefix added by time crate
         )),
-        CqlValue::Timestamp(value) => Ok(Value::String(
+        CqlValueRef::Timestamp(value) => Ok(Value::String(
             TryInto::<OffsetDateTime>::try_into(value)?.format({
                 const CONFIG: u128 = Config::DEFAULT
                     .set_time_precision(TimePrecision::Second {
@@ -1158,11 +2361,19 @@ fn try_to_json(value: CqlValue) -> anyhow::Result<Value> {
             })?,
         )),
 
-        CqlValue::Blob(value) => Ok(Value::String(const_hex::encode_prefixed(&value))),
+        CqlValueRef::Blob(value) => Ok(Value::String(const_hex::encode_prefixed(value))),
+
+        CqlValueRef::Varint(value) => Ok(Value::String(
+            BigInt::from_signed_bytes_be(value).to_string(),
+        )),
+
+        CqlValueRef::Decimal { unscaled, scale } => Ok(Value::String(
+            BigDecimal::new(BigInt::from_signed_bytes_be(unscaled), scale.into()).to_string(),
+        )),
 
-        CqlValue::Varint(value) => Ok(Value::String(BigInt::from(value).to_string())),
+        CqlValueRef::Inet(value) => Ok(Value::String(value.to_string())),
 
-        CqlValue::Decimal(value) => Ok(Value::String(BigDecimal::from(value).to_string())),
+        CqlValueRef::Empty => Ok(Value::Null),
 
         _ => unimplemented!(),
     }
@@ -1368,6 +2579,78 @@ async fn get_status(State(state): State<RoutesInnerState>) -> Response {
         .into_response()
 }
 
+/// Liveness probe: the process is up and serving HTTP.
+async fn get_healthz() -> StatusCode {
+    StatusCode::OK
+}
+
+/// Readiness probe: the node is serving and not shutting down.
+async fn get_readyz(State(state): State<RoutesInnerState>) -> StatusCode {
+    if state.node_state.is_ready().await {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+/// Exchanges the members of the cluster with a peer. It is used only between the nodes, so it is
+/// not part of the documented API.
+async fn post_cluster_gossip(
+    State(state): State<RoutesInnerState>,
+    headers: HeaderMap,
+    extract::Json(members): extract::Json<Vec<Member>>,
+) -> Response {
+    match state.gossip.exchange(http_auth::bearer(&headers), members) {
+        Ok(members) => (StatusCode::OK, response::Json(members)).into_response(),
+        Err(err @ GossipError::Disabled) => {
+            (StatusCode::NOT_FOUND, err.to_string()).into_response()
+        }
+        Err(err @ GossipError::Unauthorized) => {
+            (StatusCode::UNAUTHORIZED, err.to_string()).into_response()
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/indexes/versions",
+    tag = "scylla-vector-store-index",
+    description = "Returns the versions of the definitions of the vector indexes served by this node. \
+    A recreated index gets a new version.",
+    responses(
+        (status = 200, description = "Successful operation. Returns the versions of the served vector indexes.", body = [httpapi::IndexVersionInfo]),
+    )
+)]
+async fn get_index_versions(State(state): State<RoutesInnerState>) -> Response {
+    let versions = index_versions::local(&state.indexes.read().unwrap());
+    (StatusCode::OK, response::Json(versions)).into_response()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/cluster/index-versions",
+    tag = "scylla-vector-store-info",
+    description = "Returns the versions of the vector indexes served by the nodes of the cluster, \
+    collected from the nodes answering in time. An index is divergent when its nodes serve different versions, \
+    e.g. when a node is stuck on a stale definition after the index was recreated.",
+    responses(
+        (status = 200, description = "Successful operation. Returns the versions of the vector indexes by the node id.", body = [httpapi::ClusterIndexVersions]),
+        (
+            status = 404,
+            description = "Clustering is not configured on this node.",
+            content_type = "application/json",
+            body = ErrorMessage
+        ),
+    )
+)]
+async fn get_cluster_index_versions(State(state): State<RoutesInnerState>) -> Response {
+    let local_versions = index_versions::local(&state.indexes.read().unwrap());
+    match state.version_agreement.collect(local_versions).await {
+        Some(versions) => (StatusCode::OK, response::Json(versions)).into_response(),
+        None => (StatusCode::NOT_FOUND, "clustering is not configured").into_response(),
+    }
+}
+
 async fn index_not_ready_reason(
     node_state: &Sender<NodeState>,
     keyspace: &crate::KeyspaceName,
@@ -1387,6 +2670,37 @@ async fn index_not_ready_reason(
     }
 }
 
+/// Waits until the index has read the changes of its table written up to the timestamp in
+/// microseconds.
+async fn wait_for_writes(
+    indexes: &RwLock<Indexes>,
+    key: &IndexKey,
+    min_write_timestamp: u64,
+    timeout: Duration,
+) -> Result<(), httpapi::IndexNotReadyReason> {
+    let catching_up = || httpapi::IndexNotReadyReason::IndexCatchingUp {
+        message: format!(
+            "Index {key} has not read the writes up to timestamp {min_write_timestamp} \
+            within {}ms",
+            timeout.as_millis()
+        ),
+    };
+    let Some(db_index) = indexes
+        .read()
+        .unwrap()
+        .get_vs(key)
+        .map(|entry| entry.db_index())
+    else {
+        return Err(catching_up());
+    };
+    let mut progress = db_index.cdc_progress().await;
+    let target = Timestamp::from_micros(min_write_timestamp);
+    match tokio::time::timeout(timeout, progress.wait_for(|progress| *progress >= target)).await {
+        Ok(Ok(_)) => Ok(()),
+        _ => Err(catching_up()),
+    }
+}
+
 fn new_internals() -> Router<RoutesInnerState> {
     Router::new()
         .route(
@@ -1427,6 +2741,11 @@ mod tests {
     use super::*;
     use uuid::Uuid;
 
+    fn to_json(value: CqlValue) -> anyhow::Result<Value> {
+        let key = crate::PrimaryKey::from([value]);
+        try_to_json(key.get_ref(0).unwrap())
+    }
+
     #[test]
     fn try_from_post_index_ann_filter_conversion_ok() {
         let primary_key_columns = vec!["pk".into(), "ck".into()];
@@ -1966,58 +3285,55 @@ mod tests {
     #[test]
     fn try_to_json_conversion() {
         assert_eq!(
-            try_to_json(CqlValue::Ascii("ascii".to_string())).unwrap(),
+            to_json(CqlValue::Ascii("ascii".to_string())).unwrap(),
             Value::String("ascii".to_string())
         );
         assert_eq!(
-            try_to_json(CqlValue::Text("text".to_string())).unwrap(),
+            to_json(CqlValue::Text("text".to_string())).unwrap(),
             Value::String("text".to_string())
         );
 
-        assert_eq!(
-            try_to_json(CqlValue::Boolean(true)).unwrap(),
-            Value::Bool(true)
-        );
+        assert_eq!(to_json(CqlValue::Boolean(true)).unwrap(), Value::Bool(true));
 
         assert_eq!(
-            try_to_json(CqlValue::Double(101.)).unwrap(),
+            to_json(CqlValue::Double(101.)).unwrap(),
             Value::Number(Number::from_f64(101.).unwrap())
         );
         assert_eq!(
-            try_to_json(CqlValue::Float(201.)).unwrap(),
+            to_json(CqlValue::Float(201.)).unwrap(),
             Value::Number(Number::from_f64(201.).unwrap())
         );
 
         assert_eq!(
-            try_to_json(CqlValue::Int(10)).unwrap(),
+            to_json(CqlValue::Int(10)).unwrap(),
             Value::Number(10.into())
         );
         assert_eq!(
-            try_to_json(CqlValue::BigInt(20)).unwrap(),
+            to_json(CqlValue::BigInt(20)).unwrap(),
             Value::Number(20.into())
         );
         assert_eq!(
-            try_to_json(CqlValue::SmallInt(30)).unwrap(),
+            to_json(CqlValue::SmallInt(30)).unwrap(),
             Value::Number(30.into())
         );
         assert_eq!(
-            try_to_json(CqlValue::TinyInt(40)).unwrap(),
+            to_json(CqlValue::TinyInt(40)).unwrap(),
             Value::Number(40.into())
         );
 
         let uuid = Uuid::new_v4();
         assert_eq!(
-            try_to_json(CqlValue::Uuid(uuid)).unwrap(),
+            to_json(CqlValue::Uuid(uuid)).unwrap(),
             Value::String(uuid.into())
         );
         let uuid = Uuid::new_v4();
         assert_eq!(
-            try_to_json(CqlValue::Timeuuid(uuid.into())).unwrap(),
+            to_json(CqlValue::Timeuuid(uuid.into())).unwrap(),
             Value::String(uuid.into())
         );
 
         assert_eq!(
-            try_to_json(CqlValue::Date(
+            to_json(CqlValue::Date(
                 Date::from_calendar_date(2025, time::Month::September, 1)
                     .unwrap()
                     .into()
@@ -2026,11 +3342,11 @@ mod tests {
             Value::String("2025-09-01".to_string())
         );
         assert_eq!(
-            try_to_json(CqlValue::Time(Time::from_hms(12, 10, 10).unwrap().into())).unwrap(),
+            to_json(CqlValue::Time(Time::from_hms(12, 10, 10).unwrap().into())).unwrap(),
             Value::String("12:10:10.000000000".to_string())
         );
         assert_eq!(
-            try_to_json(CqlValue::Timestamp(
+            to_json(CqlValue::Timestamp(
                 OffsetDateTime::from_unix_timestamp(123456789)
                     .unwrap()
                     .into()
@@ -2051,24 +3367,24 @@ mod tests {
                     .unwrap()
             )
         );
-        assert!(try_to_json(CqlValue::Float(f32::NAN)).is_err());
-        assert!(try_to_json(CqlValue::Double(f64::NAN)).is_err());
+        assert!(to_json(CqlValue::Float(f32::NAN)).is_err());
+        assert!(to_json(CqlValue::Double(f64::NAN)).is_err());
 
         assert_eq!(
-            try_to_json(CqlValue::Blob(vec![0xde, 0xad, 0xbe, 0xef])).unwrap(),
+            to_json(CqlValue::Blob(vec![0xde, 0xad, 0xbe, 0xef])).unwrap(),
             Value::String("0xdeadbeef".to_string())
         );
         assert_eq!(
-            try_to_json(CqlValue::Blob(vec![])).unwrap(),
+            to_json(CqlValue::Blob(vec![])).unwrap(),
             Value::String("0x".to_string())
         );
         assert_eq!(
-            try_to_json(CqlValue::Blob(vec![0x00])).unwrap(),
+            to_json(CqlValue::Blob(vec![0x00])).unwrap(),
             Value::String("0x00".to_string())
         );
 
         assert_eq!(
-            try_to_json(CqlValue::Varint(CqlVarint::from(
+            to_json(CqlValue::Varint(CqlVarint::from(
                 "-98765432109876543210987654321098765432109876543210"
                     .parse::<BigInt>()
                     .unwrap()
@@ -2078,7 +3394,7 @@ mod tests {
         );
 
         assert_eq!(
-            try_to_json(CqlValue::Decimal(
+            to_json(CqlValue::Decimal(
                 CqlDecimal::try_from(
                     "-98765432109876543210.123456789"
                         .parse::<BigDecimal>()
@@ -2089,6 +3405,12 @@ mod tests {
             .unwrap(),
             Value::String("-98765432109876543210.123456789".to_string())
         );
+
+        assert_eq!(
+            to_json(CqlValue::Inet("127.0.0.1".parse().unwrap())).unwrap(),
+            Value::String("127.0.0.1".to_string())
+        );
+        assert_eq!(to_json(CqlValue::Empty).unwrap(), Value::Null);
     }
 
     #[test]
@@ -3,21 +3,25 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
+use crate::HttpTuning;
 use crate::config_manager::HttpServerConfig;
-use crate::engine::Engine;
+use crate::engine::EngineHandle;
+use crate::http_limits;
+use crate::http_limits::ConnectionLimit;
 use crate::httproutes;
-use crate::indexes::Indexes;
 use crate::internals::Internals;
 use crate::metrics::Metrics;
-use crate::node_state::NodeState;
 use anyhow::bail;
 use axum::Router;
 use axum_server::Handle;
 use axum_server::accept::NoDelayAcceptor;
 use axum_server::tls_rustls::RustlsConfig;
+use futures::future;
+use hyper_util::rt::TokioExecutor;
+use hyper_util::rt::TokioTimer;
+use hyper_util::server::conn::auto;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use std::sync::RwLock;
 use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::Sender;
@@ -30,12 +34,23 @@ const SPAWN_TIMEOUT: Duration = Duration::from_secs(5);
 
 type ServerTask = JoinHandle<std::io::Result<()>>;
 
+/// A server listening on all addresses of its configuration.
 struct RunningServer {
+    listeners: Vec<Listener>,
+}
+
+impl RunningServer {
+    async fn shutdown(self) {
+        future::join_all(self.listeners.into_iter().map(Listener::shutdown)).await;
+    }
+}
+
+struct Listener {
     handle: Handle<SocketAddr>,
     task: ServerTask,
 }
 
-impl RunningServer {
+impl Listener {
     async fn shutdown(self) {
         const GRACEFUL_SHUTDOWN_DURATION: Duration = Duration::from_secs(10);
         const AWAIT_TASK_TIMEOUT: Duration =
@@ -65,11 +80,17 @@ pub enum HttpServer {
     Address {
         tx: oneshot::Sender<watch::Receiver<Option<SocketAddr>>>,
     },
+    Addresses {
+        tx: oneshot::Sender<watch::Receiver<Vec<SocketAddr>>>,
+    },
 }
 
 pub trait HttpServerExt {
     fn router(&self) -> impl Future<Output = Option<Router>>;
+    /// Returns the first address the server is listening on.
     fn address(&self) -> impl Future<Output = watch::Receiver<Option<SocketAddr>>>;
+    /// Returns all addresses the server is listening on, in the configured order.
+    fn addresses(&self) -> impl Future<Output = watch::Receiver<Vec<SocketAddr>>>;
 }
 
 impl HttpServerExt for mpsc::Sender<HttpServer> {
@@ -82,6 +103,15 @@ impl HttpServerExt for mpsc::Sender<HttpServer> {
             .expect("HttpServerExt::address: internal actor should send response")
     }
 
+    async fn addresses(&self) -> watch::Receiver<Vec<SocketAddr>> {
+        let (tx, rx) = oneshot::channel();
+        self.send(HttpServer::Addresses { tx })
+            .await
+            .expect("HttpServerExt::addresses: internal actor should receive request");
+        rx.await
+            .expect("HttpServerExt::addresses: internal actor should send response")
+    }
+
     async fn router(&self) -> Option<Router> {
         let (tx, rx) = oneshot::channel();
         self.send(HttpServer::Router { tx })
@@ -93,19 +123,29 @@ impl HttpServerExt for mpsc::Sender<HttpServer> {
 }
 
 struct ServerDeps {
-    state: Sender<NodeState>,
-    indexes: Arc<RwLock<Indexes>>,
-    engine: Sender<Engine>,
+    engine: EngineHandle,
     metrics: Arc<Metrics>,
     internals: Sender<Internals>,
-    index_engine_version: String,
+}
+
+/// Publishes the bound addresses of the server.
+struct AddrTx {
+    first: watch::Sender<Option<SocketAddr>>,
+    all: watch::Sender<Vec<SocketAddr>>,
+}
+
+impl AddrTx {
+    fn send(&self, addrs: Vec<SocketAddr>) {
+        self.first.send(addrs.first().copied()).ok();
+        self.all.send(addrs).ok();
+    }
 }
 
 /// Retry spawning a server with exponential backoff
 async fn spawn_server_with_retry(
     config: &HttpServerConfig,
     deps: &ServerDeps,
-) -> anyhow::Result<(RunningServer, SocketAddr, Router)> {
+) -> anyhow::Result<(RunningServer, Vec<SocketAddr>, Router)> {
     let mut retry_delay = Duration::from_millis(50);
     let max_retries = 10;
 
@@ -140,15 +180,14 @@ async fn spawn_server_with_retry(
 async fn enable_server(
     config: &HttpServerConfig,
     deps: &ServerDeps,
-) -> anyhow::Result<(RunningServer, SocketAddr, Router)> {
+) -> anyhow::Result<(RunningServer, Vec<SocketAddr>, Router)> {
     tracing::info!("HTTP server being enabled");
-    let (server, addr, router) = spawn_server_with_retry(config, deps).await?;
+    let (server, addrs, router) = spawn_server_with_retry(config, deps).await?;
     tracing::info!(
-        "{} server started successfully on {}",
+        "{} server started successfully on {addrs:?}",
         config.protocol_label(),
-        addr
     );
-    Ok((server, addr, router))
+    Ok((server, addrs, router))
 }
 
 async fn disable_server(server: Option<RunningServer>) {
@@ -163,7 +202,7 @@ async fn reload_server(
     new_config: &HttpServerConfig,
     current_server: Option<RunningServer>,
     deps: &ServerDeps,
-) -> (Option<RunningServer>, Option<SocketAddr>, Option<Router>) {
+) -> (Option<RunningServer>, Vec<SocketAddr>, Option<Router>) {
     let changes = describe_config_changes(old_config, new_config);
     tracing::info!("HTTP server configuration changed ({changes}), reloading...");
 
@@ -173,20 +212,19 @@ async fn reload_server(
     }
 
     match spawn_server_with_retry(new_config, deps).await {
-        Ok((server, addr, router)) => {
+        Ok((server, addrs, router)) => {
             tracing::info!(
-                "{} server reloaded successfully on {}",
+                "{} server reloaded successfully on {addrs:?}",
                 new_config.protocol_label(),
-                addr
             );
-            (Some(server), Some(addr), Some(router))
+            (Some(server), addrs, Some(router))
         }
         Err(e) => {
             tracing::error!("Failed to reload HTTP server: {e}");
             tracing::error!(
                 "HTTP server is now offline - previous server was shut down but new server failed to start"
             );
-            (None, None, None)
+            (None, Vec::new(), None)
         }
     }
 }
@@ -196,7 +234,7 @@ async fn handle_config_change(
     new_config: &Option<Arc<HttpServerConfig>>,
     current_server: Option<RunningServer>,
     deps: &ServerDeps,
-    addr_tx: &watch::Sender<Option<SocketAddr>>,
+    addr_tx: &AddrTx,
     router: &mut Option<Router>,
 ) -> Option<RunningServer> {
     match (current_config, new_config) {
@@ -204,30 +242,30 @@ async fn handle_config_change(
         (None, None) => current_server,
         // New server enabled: start it
         (None, Some(config)) => match enable_server(config, deps).await {
-            Ok((server, addr, new_router)) => {
-                addr_tx.send(Some(addr)).ok();
+            Ok((server, addrs, new_router)) => {
+                addr_tx.send(addrs);
                 *router = Some(new_router);
                 Some(server)
             }
             Err(e) => {
                 tracing::error!("Failed to start HTTP server: {e}");
-                addr_tx.send(None).ok();
+                addr_tx.send(Vec::new());
                 None
             }
         },
         // Server disabled: stop it
         (Some(_), None) => {
             disable_server(current_server).await;
-            addr_tx.send(None).ok();
+            addr_tx.send(Vec::new());
             *router = None;
             None
         }
         // Config changed: reload server
         (Some(old), Some(new)) => {
             if **old != **new {
-                let (server, addr, new_router) =
+                let (server, addrs, new_router) =
                     reload_server(old, new, current_server, deps).await;
-                addr_tx.send(addr).ok();
+                addr_tx.send(addrs);
                 if let Some(r) = new_router {
                     *router = Some(r);
                 }
@@ -240,35 +278,34 @@ async fn handle_config_change(
 }
 
 pub(crate) async fn new(
-    state: Sender<NodeState>,
-    indexes: Arc<RwLock<Indexes>>,
-    engine: Sender<Engine>,
+    engine: EngineHandle,
     metrics: Arc<Metrics>,
     internals: Sender<Internals>,
-    index_engine_version: String,
     mut config_rx: watch::Receiver<Option<Arc<HttpServerConfig>>>,
 ) -> anyhow::Result<Sender<HttpServer>> {
     // minimal size as channel is used as a lifetime guard
     const CHANNEL_SIZE: usize = 1;
     let (tx, mut rx) = mpsc::channel(CHANNEL_SIZE);
 
-    let (addr_tx, addr_rx) = watch::channel::<Option<SocketAddr>>(None);
+    let (first_addr_tx, addr_rx) = watch::channel(None);
+    let (all_addrs_tx, addrs_rx) = watch::channel(Vec::new());
+    let addr_tx = AddrTx {
+        first: first_addr_tx,
+        all: all_addrs_tx,
+    };
 
     let deps = ServerDeps {
-        state,
-        indexes,
         engine,
         metrics,
         internals,
-        index_engine_version,
     };
 
     let initial_config = config_rx.borrow().clone();
 
     // Start initial server if config is provided
     let (mut current_server, mut router) = if let Some(ref config) = initial_config {
-        let (server, actual_addr, router) = spawn_server_with_retry(config, &deps).await?;
-        addr_tx.send(Some(actual_addr)).ok();
+        let (server, addrs, router) = spawn_server_with_retry(config, &deps).await?;
+        addr_tx.send(addrs);
         (Some(server), Some(router))
     } else {
         tracing::info!("HTTP server disabled by configuration");
@@ -289,6 +326,9 @@ pub(crate) async fn new(
                         HttpServer::Address { tx } => {
                             tx.send(addr_rx.clone()).expect("failed to send response");
                         }
+                        HttpServer::Addresses { tx } => {
+                            tx.send(addrs_rx.clone()).expect("failed to send response");
+                        }
                         HttpServer::Router { tx } => {
                             _ = tx.send(router.clone());
                         }
@@ -317,66 +357,102 @@ pub(crate) async fn new(
             tracing::info!("HTTP server shutting down");
             server.shutdown().await;
         }
-        addr_tx.send(None).ok();
+        addr_tx.send(Vec::new());
     });
 
     Ok(tx)
 }
 
-/// Spawn a new HTTP server instance with the given configuration
-/// Returns the handle and the actual bound address
+/// Spawn a new HTTP server instance with the given configuration, listening on all its addresses
+/// Returns the handle and the actual bound addresses
 async fn spawn_server(
     config: &HttpServerConfig,
     deps: &ServerDeps,
-) -> anyhow::Result<(RunningServer, SocketAddr, Router)> {
-    let protocol = config.protocol_label();
-    let addr = config.addr;
-
-    let handle = Handle::new();
-
+) -> anyhow::Result<(RunningServer, Vec<SocketAddr>, Router)> {
     let router = httproutes::new(
-        Arc::clone(&deps.indexes),
-        deps.engine.clone(),
+        &deps.engine,
         deps.metrics.clone(),
-        deps.state.clone(),
         deps.internals.clone(),
-        deps.index_engine_version.clone(),
         config.tls.is_some(),
+        config.auth.clone(),
     )
     .await;
+    let router = http_limits::limit_requests(
+        router,
+        config.tuning.max_concurrent_requests,
+        config.server_label(),
+        &deps.metrics,
+    );
+    let connection_limit = ConnectionLimit::new(
+        config.tuning.max_connections,
+        config.server_label(),
+        &deps.metrics,
+    );
+
+    let mut server = RunningServer {
+        listeners: Vec::new(),
+    };
+    let mut addrs = Vec::new();
+    for addr in config.addrs() {
+        match spawn_listener(config, addr, router.clone(), connection_limit.clone()).await {
+            Ok((listener, actual_addr)) => {
+                server.listeners.push(listener);
+                addrs.push(actual_addr);
+            }
+            Err(err) => {
+                server.shutdown().await;
+                return Err(err);
+            }
+        }
+    }
+    Ok((server, addrs, router))
+}
+
+async fn spawn_listener(
+    config: &HttpServerConfig,
+    addr: SocketAddr,
+    router: Router,
+    connection_limit: ConnectionLimit,
+) -> anyhow::Result<(Listener, SocketAddr)> {
+    let protocol = config.protocol_label();
+    let handle = Handle::new();
+
     let mut server_task = tokio::spawn({
         let handle = handle.clone();
-        let router = router.clone();
         let tls = config.tls.clone();
+        let tuning = config.tuning.clone();
 
         async move {
             let result = match tls {
                 Some(ref tls_config) if tls_config.is_mtls() => {
                     let rustls_config =
                         RustlsConfig::from_config(Arc::clone(tls_config.server_config()));
-                    axum_server::bind_rustls(addr, rustls_config)
+                    let mut server = axum_server::bind_rustls(addr, rustls_config)
                         .handle(handle)
-                        .serve(router.into_make_service())
-                        .await
+                        .map(|acceptor| connection_limit.acceptor(acceptor));
+                    tune(server.http_builder(), &tuning);
+                    server.serve(router.into_make_service()).await
                 }
                 Some(ref tls_config) => {
                     let rustls_config =
                         RustlsConfig::from_config(Arc::clone(tls_config.server_config()));
-                    axum_server_dual_protocol::bind_dual_protocol(addr, rustls_config)
-                        .handle(handle)
-                        .serve(router.into_make_service())
-                        .await
+                    let mut server =
+                        axum_server_dual_protocol::bind_dual_protocol(addr, rustls_config)
+                            .handle(handle)
+                            .map(|acceptor| connection_limit.acceptor(acceptor));
+                    tune(server.http_builder(), &tuning);
+                    server.serve(router.into_make_service()).await
                 }
                 None => {
-                    axum_server::bind(addr)
+                    let mut server = axum_server::bind(addr)
                         .handle(handle)
-                        .acceptor(NoDelayAcceptor::new())
-                        .serve(router.into_make_service())
-                        .await
+                        .acceptor(connection_limit.acceptor(NoDelayAcceptor::new()));
+                    tune(server.http_builder(), &tuning);
+                    server.serve(router.into_make_service()).await
                 }
             };
             if let Err(ref e) = result {
-                tracing::error!("failed to run {protocol} server: {e}");
+                tracing::error!("failed to run {protocol} server on {addr}: {e}");
             }
             result
         }
@@ -390,7 +466,7 @@ async fn spawn_server(
         }
         result = &mut server_task => {
             match result {
-                Ok(Err(e)) => bail!(e),
+                Ok(Err(e)) => bail!("{protocol} server failed to listen on {addr}: {e}"),
                 Ok(Ok(())) => bail!("{protocol} server exited unexpectedly"),
                 Err(join_err) => bail!("{protocol} server task panicked: {join_err}"),
             }
@@ -403,45 +479,86 @@ async fn spawn_server(
     };
 
     Ok((
-        RunningServer {
+        Listener {
             handle,
             task: server_task,
         },
         actual_addr,
-        router,
     ))
 }
 
+/// Applies the connection settings to the hyper connection builder of a server.
+fn tune(builder: &mut auto::Builder<TokioExecutor>, tuning: &HttpTuning) {
+    if !tuning.http2 {
+        *builder = builder.clone().http1_only();
+    }
+    let mut http1 = builder.http1();
+    http1.timer(TokioTimer::new()).keep_alive(tuning.keep_alive);
+    if let Some(timeout) = tuning.header_read_timeout {
+        http1.header_read_timeout(timeout);
+    }
+    let mut http2 = builder.http2();
+    http2.timer(TokioTimer::new());
+    if let Some(interval) = tuning.http2_keep_alive_interval {
+        http2.keep_alive_interval(interval);
+    }
+    if let Some(timeout) = tuning.http2_keep_alive_timeout {
+        http2.keep_alive_timeout(timeout);
+    }
+    if let Some(streams) = tuning.http2_max_concurrent_streams {
+        http2.max_concurrent_streams(streams.get());
+    }
+}
+
 fn describe_config_changes(old: &HttpServerConfig, new: &HttpServerConfig) -> String {
     let mut changes = Vec::new();
     if old.addr != new.addr {
         changes.push(format!("address {} -> {}", old.addr, new.addr));
     }
+    if old.extra_addrs != new.extra_addrs {
+        changes.push(format!(
+            "extra addresses {:?} -> {:?}",
+            old.extra_addrs, new.extra_addrs
+        ));
+    }
     match (&old.tls, &new.tls) {
         (Some(old_tls), Some(new_tls)) => changes.extend(old_tls.describe_changes(new_tls)),
         (None, Some(_)) => changes.push("TLS enabled".to_string()),
         (Some(_), None) => changes.push("TLS disabled".to_string()),
         (None, None) => {}
     }
+    if old.tuning != new.tuning {
+        changes.push(format!("tuning {:?} -> {:?}", old.tuning, new.tuning));
+    }
     changes.join(", ")
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Config;
+    use crate::cluster::Gossip;
     use crate::indexes::Indexes;
+    use std::sync::RwLock;
 
     fn test_deps() -> ServerDeps {
         let (state_tx, _state_rx) = mpsc::channel(1);
         let (engine_tx, _engine_rx) = mpsc::channel(1);
         let (internals_tx, _internals_rx) = mpsc::channel(1);
         ServerDeps {
-            state: state_tx,
-            indexes: Arc::new(RwLock::new(Indexes::new())),
-            engine: engine_tx,
+            engine: EngineHandle {
+                engine: engine_tx,
+                indexes: Arc::new(RwLock::new(Indexes::new())),
+                node_state: state_tx,
+                index_engine_version: "test".to_string(),
+                membership: watch::channel(None).1,
+                gossip: Gossip::default(),
+                embedder: None,
+                reranker: None,
+                config_rx: watch::channel(Arc::new(Config::default())).1,
+            },
             metrics: Arc::new(Metrics::new()),
             internals: internals_tx,
-            index_engine_version: "test".to_string(),
         }
     }
 
@@ -451,7 +568,10 @@ mod tests {
         let occupied_addr = occupied.local_addr().unwrap();
         let config = HttpServerConfig {
             addr: occupied_addr,
+            extra_addrs: Vec::new(),
             tls: None,
+            tuning: HttpTuning::default(),
+            auth: None,
         };
         let deps = test_deps();
 
@@ -464,13 +584,17 @@ mod tests {
     async fn spawn_server_binds_to_available_port() {
         let config = HttpServerConfig {
             addr: "127.0.0.1:0".parse().unwrap(),
+            extra_addrs: Vec::new(),
             tls: None,
+            tuning: HttpTuning::default(),
+            auth: None,
         };
         let deps = test_deps();
 
-        let (server, addr, _router) = spawn_server(&config, &deps).await.unwrap();
+        let (server, addrs, _router) = spawn_server(&config, &deps).await.unwrap();
 
-        assert_ne!(addr.port(), 0);
+        assert_eq!(addrs.len(), 1);
+        assert_ne!(addrs[0].port(), 0);
 
         server.shutdown().await;
     }
@@ -480,11 +604,20 @@ mod tests {
         let deps = test_deps();
         let config = HttpServerConfig {
             addr: "127.0.0.1:0".parse().unwrap(),
+            extra_addrs: Vec::new(),
             tls: None,
+            tuning: HttpTuning::default(),
+            auth: None,
         };
-        let (server, addr, _router) = spawn_server(&config, &deps).await.unwrap();
+        let (server, addrs, _router) = spawn_server(&config, &deps).await.unwrap();
 
-        let new_config = HttpServerConfig { addr, tls: None };
+        let new_config = HttpServerConfig {
+            addr: addrs[0],
+            extra_addrs: Vec::new(),
+            tls: None,
+            tuning: HttpTuning::default(),
+            auth: None,
+        };
 
         let (new_server, new_addr, _new_router) =
             reload_server(&config, &new_config, Some(server), &deps).await;
@@ -493,8 +626,89 @@ mod tests {
             new_server.is_some(),
             "server should successfully reload on the same port"
         );
-        assert_eq!(new_addr, Some(addr));
+        assert_eq!(new_addr, addrs);
 
         new_server.unwrap().shutdown().await;
     }
+
+    #[tokio::test]
+    async fn spawn_server_listens_on_all_addresses() {
+        let config = HttpServerConfig {
+            addr: "127.0.0.1:0".parse().unwrap(),
+            extra_addrs: vec!["127.0.0.1:0".parse().unwrap()],
+            tls: None,
+            tuning: HttpTuning::default(),
+            auth: None,
+        };
+        let deps = test_deps();
+
+        let (server, addrs, _router) = spawn_server(&config, &deps).await.unwrap();
+
+        assert_eq!(addrs.len(), 2);
+        assert_ne!(addrs[0], addrs[1]);
+        for addr in &addrs {
+            assert!(tokio::net::TcpStream::connect(addr).await.is_ok());
+        }
+
+        server.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn spawn_server_stops_listeners_when_any_address_fails() {
+        let occupied = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let free = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let free_addr = free.local_addr().unwrap();
+        drop(free);
+        let config = HttpServerConfig {
+            addr: free_addr,
+            extra_addrs: vec![occupied.local_addr().unwrap()],
+            tls: None,
+            tuning: HttpTuning::default(),
+            auth: None,
+        };
+        let deps = test_deps();
+
+        assert!(spawn_server(&config, &deps).await.is_err());
+        assert!(std::net::TcpListener::bind(free_addr).is_ok());
+    }
+
+    #[tokio::test]
+    async fn spawn_server_closes_connections_over_limit() {
+        use tokio::io::AsyncReadExt;
+        use tokio::io::AsyncWriteExt;
+
+        let config = HttpServerConfig {
+            addr: "127.0.0.1:0".parse().unwrap(),
+            extra_addrs: Vec::new(),
+            tls: None,
+            tuning: HttpTuning {
+                max_connections: std::num::NonZeroUsize::new(1),
+                ..HttpTuning::default()
+            },
+            auth: None,
+        };
+        let deps = test_deps();
+        let (server, addrs, _router) = spawn_server(&config, &deps).await.unwrap();
+
+        let mut first = tokio::net::TcpStream::connect(addrs[0]).await.unwrap();
+        first
+            .write_all(b"GET /missing HTTP/1.1\r\nhost: localhost\r\n\r\n")
+            .await
+            .unwrap();
+        let mut buf = [0; 1024];
+        assert!(first.read(&mut buf).await.unwrap() > 0);
+
+        let mut second = tokio::net::TcpStream::connect(addrs[0]).await.unwrap();
+        let read = time::timeout(Duration::from_secs(5), second.read(&mut buf)).await;
+        assert!(matches!(read, Ok(Ok(0)) | Ok(Err(_))));
+        assert_eq!(
+            deps.metrics
+                .http_connections_rejected_total
+                .with_label_values(&["main"])
+                .get(),
+            1.0
+        );
+
+        server.shutdown().await;
+    }
 }
@@ -0,0 +1,201 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::DbIndexedOperation;
+use crate::DbIndexedRow;
+use crate::DbIndexedValue;
+use crate::Distance;
+use crate::Filter;
+use crate::IndexKey;
+use crate::Limit;
+use crate::MemoryUsage;
+use crate::NonemptyBox;
+use crate::PrimaryKey;
+use crate::Timestamp;
+use crate::Timestamped;
+use crate::Vector;
+use crate::drift::VectorDrift;
+use crate::indexes::Indexes;
+use crate::memory::IndexMemory;
+use crate::monitor_items::MonitorItems;
+use crate::monitor_items::MonitorItemsExt;
+use crate::node_state::IndexStatus;
+use crate::vs_index::VsIndex;
+use crate::vs_index::VsIndexExt;
+use std::sync::Arc;
+use std::sync::RwLock;
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+#[derive(Debug, Error)]
+pub enum IndexError {
+    #[error("missing index: {0}")]
+    NotFound(IndexKey),
+
+    #[error("index {key} is not serving: {status:?}")]
+    NotServing { key: IndexKey, status: IndexStatus },
+
+    #[error("Wrong embedding dimension: expected {expected}, got {actual}")]
+    WrongEmbeddingDimension { expected: usize, actual: usize },
+
+    #[error(transparent)]
+    Index(#[from] anyhow::Error),
+}
+
+/// An in-process handle to a vector index managed by the engine.
+///
+/// The handle is cheap to clone and resolves the index on every call, so it reports
+/// [`IndexError::NotFound`] after the index is dropped.
+#[derive(Clone)]
+pub struct IndexHandle {
+    key: IndexKey,
+    indexes: Arc<RwLock<Indexes>>,
+}
+
+impl std::fmt::Debug for IndexHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IndexHandle")
+            .field("key", &self.key)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Resolved {
+    index: mpsc::Sender<VsIndex>,
+    monitor: mpsc::Sender<MonitorItems>,
+    memory: IndexMemory,
+    drift: VectorDrift,
+    status: IndexStatus,
+    dimensions: usize,
+}
+
+impl IndexHandle {
+    pub(crate) fn new(key: IndexKey, indexes: Arc<RwLock<Indexes>>) -> Self {
+        Self { key, indexes }
+    }
+
+    pub fn key(&self) -> &IndexKey {
+        &self.key
+    }
+
+    /// Returns the current status of the index.
+    pub fn status(&self) -> Result<IndexStatus, IndexError> {
+        Ok(self.resolve()?.status)
+    }
+
+    /// Returns the estimated memory used by the index.
+    pub fn memory_usage(&self) -> Result<MemoryUsage, IndexError> {
+        Ok(self.resolve()?.memory.usage())
+    }
+
+    /// Returns the nearest neighbours of the embedding, closest first.
+    pub async fn ann(
+        &self,
+        embedding: Vector,
+        limit: Limit,
+    ) -> Result<Vec<(PrimaryKey, Distance)>, IndexError> {
+        let index = self.resolve_serving(&embedding)?;
+        let (primary_keys, distances) = index.ann(self.key.clone(), embedding, limit).await?;
+        Ok(primary_keys.into_iter().zip(distances).collect())
+    }
+
+    /// Returns the nearest neighbours of the embedding which satisfy the filter, closest first.
+    pub async fn filtered_ann(
+        &self,
+        embedding: Vector,
+        filter: Filter,
+        limit: Limit,
+    ) -> Result<Vec<(PrimaryKey, Distance)>, IndexError> {
+        let index = self.resolve_serving(&embedding)?;
+        let (primary_keys, distances) = index
+            .filtered_ann(self.key.clone(), embedding, filter, limit)
+            .await?;
+        Ok(primary_keys.into_iter().zip(distances).collect())
+    }
+
+    /// Returns the number of vectors in the index.
+    pub async fn count(&self) -> Result<usize, IndexError> {
+        let index = self.resolve()?.index;
+        Ok(index.count(self.key.clone()).await?)
+    }
+
+    /// Adds or replaces the embedding of a row. The row is written only to the index, so a later
+    /// change of the same row in the db overrides it.
+    pub async fn add(&self, primary_key: PrimaryKey, embedding: Vector) -> Result<(), IndexError> {
+        let resolved = self.resolve()?;
+        check_dimensions(&embedding, resolved.dimensions)?;
+        let value = Timestamped::new(Timestamp::now(), Some(DbIndexedValue::Vector(embedding)));
+        self.apply_row(
+            resolved.monitor,
+            DbIndexedRow {
+                primary_key,
+                operation: DbIndexedOperation::Upsert(NonemptyBox::new([value]).unwrap()),
+            },
+        )
+        .await
+    }
+
+    /// Removes the embedding of a row from the index.
+    pub async fn remove(&self, primary_key: PrimaryKey) -> Result<(), IndexError> {
+        let monitor = self.resolve()?.monitor;
+        self.apply_row(
+            monitor,
+            DbIndexedRow {
+                primary_key,
+                operation: DbIndexedOperation::Delete(Timestamp::now()),
+            },
+        )
+        .await
+    }
+
+    async fn apply_row(
+        &self,
+        monitor: mpsc::Sender<MonitorItems>,
+        row: DbIndexedRow,
+    ) -> Result<(), IndexError> {
+        monitor
+            .apply_row(row)
+            .await
+            .map_err(|_| IndexError::NotFound(self.key.clone()))
+    }
+
+    fn resolve(&self) -> Result<Resolved, IndexError> {
+        let indexes = self.indexes.read().unwrap();
+        let entry = indexes
+            .get_vs(&self.key)
+            .ok_or_else(|| IndexError::NotFound(self.key.clone()))?;
+        Ok(Resolved {
+            index: entry.index().clone(),
+            monitor: entry.monitor().clone(),
+            memory: entry.memory().clone(),
+            drift: entry.drift().clone(),
+            status: entry.status(),
+            dimensions: entry.options().dimensions.0.get(),
+        })
+    }
+
+    fn resolve_serving(&self, embedding: &Vector) -> Result<mpsc::Sender<VsIndex>, IndexError> {
+        let resolved = self.resolve()?;
+        if resolved.status != IndexStatus::Serving {
+            return Err(IndexError::NotServing {
+                key: self.key.clone(),
+                status: resolved.status,
+            });
+        }
+        check_dimensions(embedding, resolved.dimensions)?;
+        resolved.drift.observe_queried(embedding.as_slice());
+        Ok(resolved.index)
+    }
+}
+
+fn check_dimensions(embedding: &Vector, expected: usize) -> Result<(), IndexError> {
+    if embedding.len() != expected {
+        return Err(IndexError::WrongEmbeddingDimension {
+            expected,
+            actual: embedding.len(),
+        });
+    }
+    Ok(())
+}
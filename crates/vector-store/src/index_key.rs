@@ -12,8 +12,19 @@ use scylla::serialize::writers::CellWriter;
 use scylla::serialize::writers::WrittenCellProof;
 
 #[derive(
-    Clone, Hash, Eq, PartialEq, Debug, PartialOrd, Ord, derive_more::Display, derive_more::AsRef,
+    Clone,
+    Hash,
+    Eq,
+    PartialEq,
+    Debug,
+    PartialOrd,
+    Ord,
+    derive_more::Display,
+    derive_more::AsRef,
+    serde::Serialize,
+    serde::Deserialize,
 )]
+#[serde(transparent)]
 pub struct IndexKey(String);
 
 impl IndexKey {
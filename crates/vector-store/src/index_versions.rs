@@ -0,0 +1,238 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Agreement of the cluster nodes on the versions of the indexes.
+//!
+//! An index recreated in ScyllaDB gets a new version. A node which missed the change, e.g. because
+//! it lost the connection to the database, keeps serving the stale definition. The versions served
+//! by the nodes are collected from their [`PATH`] endpoint and compared.
+
+use crate::Membership;
+use crate::NodeId;
+use crate::http_auth;
+use crate::indexes::Indexes;
+use crate::metrics::Metrics;
+use futures::future;
+use httpapi::ClusterIndexVersions;
+use httpapi::IndexVersionInfo;
+use secrecy::SecretString;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tracing::warn;
+
+/// The endpoint returning the versions of the indexes served by a node.
+pub(crate) const PATH: &str = "/api/v1/indexes/versions";
+
+const TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Returns the versions of the vector indexes served by the local node.
+pub(crate) fn local(indexes: &Indexes) -> Vec<IndexVersionInfo> {
+    let mut versions: Vec<_> = indexes
+        .iter_vs()
+        .map(|(key, entry)| IndexVersionInfo {
+            keyspace: key.keyspace().into(),
+            index: key.index().into(),
+            version: entry.version().to_string(),
+        })
+        .collect();
+    versions.sort_by_key(|info| (info.keyspace.to_string(), info.index.to_string()));
+    versions
+}
+
+#[derive(Clone)]
+pub(crate) struct VersionAgreement {
+    client: reqwest::Client,
+    membership: watch::Receiver<Option<Arc<Membership>>>,
+    metrics: Arc<Metrics>,
+    peer_token: Option<SecretString>,
+}
+
+impl VersionAgreement {
+    pub(crate) fn new(
+        membership: watch::Receiver<Option<Arc<Membership>>>,
+        metrics: Arc<Metrics>,
+        peer_token: Option<SecretString>,
+    ) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            membership,
+            metrics,
+            peer_token,
+        }
+    }
+
+    /// Collects the versions served by the nodes of the cluster and updates the metrics. Returns
+    /// `None` when clustering is disabled. Nodes which do not answer are left out.
+    pub(crate) async fn collect(
+        &self,
+        local: Vec<IndexVersionInfo>,
+    ) -> Option<Vec<ClusterIndexVersions>> {
+        let membership = self.membership.borrow().clone()?;
+        let client = &self.client;
+        let peer_token = self.peer_token.as_ref();
+        let peers = membership
+            .nodes()
+            .iter()
+            .filter(|node| node.id != *membership.local())
+            .map(|node| async move {
+                let result = async {
+                    http_auth::to_peer(client.get(format!("{}{PATH}", node.url)), peer_token)
+                        .timeout(TIMEOUT)
+                        .send()
+                        .await?
+                        .error_for_status()?
+                        .json::<Vec<IndexVersionInfo>>()
+                        .await
+                }
+                .await;
+                (node, result)
+            });
+
+        let mut nodes = vec![(membership.local().clone(), local)];
+        for (node, result) in future::join_all(peers).await {
+            match result {
+                Ok(versions) => nodes.push((node.id.clone(), versions)),
+                Err(err) => warn!(
+                    "unable to get index versions of cluster node {}: {err}",
+                    node.id
+                ),
+            }
+        }
+
+        let versions = merge(nodes);
+        self.metrics.cluster_index_versions.reset();
+        for index in &versions {
+            let distinct: HashSet<_> = index.versions.values().collect();
+            self.metrics
+                .cluster_index_versions
+                .with_label_values(&[index.keyspace.as_ref(), index.index.as_ref()])
+                .set(distinct.len() as f64);
+        }
+        Some(versions)
+    }
+}
+
+fn merge(nodes: Vec<(NodeId, Vec<IndexVersionInfo>)>) -> Vec<ClusterIndexVersions> {
+    let mut indexes: HashMap<_, HashMap<String, String>> = HashMap::new();
+    for (node, versions) in nodes {
+        for info in versions {
+            indexes
+                .entry((info.keyspace, info.index))
+                .or_default()
+                .insert(node.to_string(), info.version);
+        }
+    }
+    let mut indexes: Vec<_> = indexes
+        .into_iter()
+        .map(|((keyspace, index), versions)| {
+            let mut served = versions.values();
+            let first = served.next();
+            let divergent = served.any(|version| Some(version) != first);
+            ClusterIndexVersions {
+                keyspace,
+                index,
+                versions,
+                divergent,
+            }
+        })
+        .collect();
+    indexes.sort_by_key(|index| (index.keyspace.to_string(), index.index.to_string()));
+    indexes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClusterNode;
+    use axum::Json;
+    use axum::Router;
+    use axum::routing::get;
+    use std::num::NonZeroUsize;
+    use tokio::net::TcpListener;
+
+    fn info(index: &str, version: &str) -> IndexVersionInfo {
+        IndexVersionInfo {
+            keyspace: "ks".into(),
+            index: index.into(),
+            version: version.to_string(),
+        }
+    }
+
+    async fn spawn_node(versions: Vec<IndexVersionInfo>) -> String {
+        let router = Router::new().route(
+            PATH,
+            get(move || {
+                let versions = versions.clone();
+                async move { Json(versions) }
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn divergent_versions_are_reported() {
+        let peer = spawn_node(vec![info("idx1", "v1"), info("idx2", "v1")]).await;
+        let nodes = vec![
+            ClusterNode {
+                id: "local".into(),
+                url: "http://127.0.0.1:1".to_string(),
+                zone: None,
+            },
+            ClusterNode {
+                id: "peer".into(),
+                url: peer,
+                zone: None,
+            },
+            ClusterNode {
+                id: "unreachable".into(),
+                url: "http://127.0.0.1:1".to_string(),
+                zone: None,
+            },
+        ];
+        let membership = Membership::new("local".into(), nodes, NonZeroUsize::new(3).unwrap());
+        let metrics = Arc::new(Metrics::new());
+        let agreement = VersionAgreement::new(
+            watch::channel(Some(Arc::new(membership))).1,
+            Arc::clone(&metrics),
+            None,
+        );
+
+        let versions = agreement
+            .collect(vec![info("idx1", "v1"), info("idx2", "v2")])
+            .await
+            .unwrap();
+
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].index, "idx1".into());
+        assert!(!versions[0].divergent);
+        assert_eq!(versions[1].index, "idx2".into());
+        assert!(versions[1].divergent);
+        assert_eq!(versions[1].versions["local"], "v2");
+        assert_eq!(versions[1].versions["peer"], "v1");
+        assert!(!versions[1].versions.contains_key("unreachable"));
+        let gauge = |index: &str| {
+            metrics
+                .cluster_index_versions
+                .with_label_values(&["ks", index])
+                .get()
+        };
+        assert_eq!(gauge("idx1"), 1.0);
+        assert_eq!(gauge("idx2"), 2.0);
+    }
+
+    #[tokio::test]
+    async fn versions_are_not_collected_without_cluster() {
+        let agreement =
+            VersionAgreement::new(watch::channel(None).1, Arc::new(Metrics::new()), None);
+
+        assert!(agreement.collect(vec![info("idx", "v1")]).await.is_none());
+    }
+}
@@ -11,18 +11,24 @@ use crate::IndexVersion;
 use crate::KeyspaceName;
 use crate::NonemptyArc;
 use crate::NonemptyIteratorExt;
+use crate::PrimaryKey;
 use crate::Progress;
 use crate::TableName;
 use crate::db_index::DbIndex;
 use crate::db_index::DbIndexExt;
+use crate::drift::VectorDrift;
 use crate::fts_index::FtsIndex;
+use crate::memory::IndexMemory;
 use crate::monitor_items::MonitorItems;
 use crate::node_state::IndexStatus;
+use crate::table::Table;
 use crate::vs_index::VsIndex;
 use scylla::cluster::metadata::NativeType;
+use scylla::value::CqlValue;
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::sync::Arc;
+use std::sync::RwLock;
 use tokio::sync::mpsc;
 use tracing::debug;
 
@@ -79,8 +85,10 @@ impl From<&IndexMetadata> for RoutingGroupKey {
 
 pub(crate) struct IndexEntry<I, D = ()> {
     index: mpsc::Sender<I>,
-    _monitor: mpsc::Sender<MonitorItems>,
+    monitor: mpsc::Sender<MonitorItems>,
     db_index: mpsc::Sender<DbIndex>,
+    memory: IndexMemory,
+    table: Arc<RwLock<Table>>,
     status: IndexStatus,
     progress: Progress,
     primary_key_columns: NonemptyArc<ColumnName>,
@@ -108,6 +116,7 @@ pub(crate) struct VsIndexData {
     table_columns: Arc<HashMap<ColumnName, NativeType>>,
     version: IndexVersion,
     options: crate::IndexOptionsVs,
+    drift: VectorDrift,
 }
 
 impl<I, D> IndexEntry<I, D> {
@@ -115,10 +124,18 @@ impl<I, D> IndexEntry<I, D> {
         &self.index
     }
 
+    pub(crate) fn monitor(&self) -> &mpsc::Sender<MonitorItems> {
+        &self.monitor
+    }
+
     pub(crate) fn db_index(&self) -> mpsc::Sender<DbIndex> {
         self.db_index.clone()
     }
 
+    pub(crate) fn memory(&self) -> &IndexMemory {
+        &self.memory
+    }
+
     pub(crate) fn progress(&self) -> Progress {
         self.progress
     }
@@ -138,6 +155,27 @@ impl<I, D> IndexEntry<I, D> {
     pub(crate) fn primary_key_columns(&self) -> &NonemptyArc<ColumnName> {
         &self.primary_key_columns
     }
+
+    /// Returns the values of the columns stored by the table for the rows of the primary keys, one
+    /// key of values per row. Values of rows removed in the meantime are empty.
+    pub(crate) fn stored_values(
+        &self,
+        primary_keys: &[PrimaryKey],
+        columns: &[ColumnName],
+    ) -> Vec<PrimaryKey> {
+        let table = self.table.read().unwrap();
+        primary_keys
+            .iter()
+            .map(|primary_key| {
+                table
+                    .stored_values(primary_key, columns)
+                    .map(PrimaryKey::from_iter)
+                    .unwrap_or_else(|| {
+                        PrimaryKey::from_iter(columns.iter().map(|_| CqlValue::Empty))
+                    })
+            })
+            .collect()
+    }
 }
 
 impl VsIndexEntry {
@@ -145,6 +183,9 @@ impl VsIndexEntry {
         index: mpsc::Sender<VsIndex>,
         monitor: mpsc::Sender<MonitorItems>,
         db_index: mpsc::Sender<DbIndex>,
+        memory: IndexMemory,
+        drift: VectorDrift,
+        table: Arc<RwLock<Table>>,
         metadata: IndexMetadata,
     ) -> anyhow::Result<Self> {
         let routing_group = RoutingGroupKey::from(&metadata);
@@ -165,8 +206,10 @@ impl VsIndexEntry {
         let progress = db_index.full_scan_progress().await;
         Ok(Self {
             index,
-            _monitor: monitor,
+            monitor,
             db_index,
+            memory,
+            table,
             status: IndexStatus::Initializing,
             progress,
             primary_key_columns,
@@ -177,6 +220,7 @@ impl VsIndexEntry {
                 table_columns,
                 version: metadata.version,
                 options,
+                drift,
             },
         })
     }
@@ -185,6 +229,18 @@ impl VsIndexEntry {
         &self.data.options
     }
 
+    pub(crate) fn version(&self) -> &IndexVersion {
+        &self.data.version
+    }
+
+    pub(crate) fn drift(&self) -> &VectorDrift {
+        &self.data.drift
+    }
+
+    pub(crate) fn table(&self) -> &TableName {
+        &self.data.routing_group.table
+    }
+
     /// Computes a routing score for an index given the query's restriction columns.
     ///
     /// Returns `None` when the index cannot serve the query at all. This happens
@@ -237,13 +293,17 @@ impl FtsIndexEntry {
         index: mpsc::Sender<FtsIndex>,
         monitor: mpsc::Sender<MonitorItems>,
         db_index: mpsc::Sender<DbIndex>,
+        memory: IndexMemory,
+        table: Arc<RwLock<Table>>,
     ) -> Self {
         let primary_key_columns = db_index.get_primary_key_columns().await;
         let progress = db_index.full_scan_progress().await;
         Self {
             index,
-            _monitor: monitor,
+            monitor,
             db_index,
+            memory,
+            table,
             status: IndexStatus::Initializing,
             progress,
             primary_key_columns,
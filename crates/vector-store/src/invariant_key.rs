@@ -19,10 +19,13 @@
 //! | `Uuid`         | 17 bytes     | 4× smaller             |
 //! | `Text("abc")`  | 8 bytes      | 9× smaller             |
 //!
-//! This matters because [`InvariantKey`] is stored in a [`BiMap`](bimap::BiMap) for **every
+//! This matters because [`InvariantKey`] is stored in the table of **every
 //! indexed row** — potentially millions of entries. For a single `Int` primary key column,
 //! memory per row drops from ~96 bytes to ~24 bytes (4× improvement).
 
+use memmap2::MmapOptions;
+use memmap2::MmapRaw;
+use prometheus::Gauge;
 use scylla::value::Counter;
 use scylla::value::CqlDate;
 use scylla::value::CqlDecimal;
@@ -31,15 +34,23 @@ use scylla::value::CqlTimestamp;
 use scylla::value::CqlTimeuuid;
 use scylla::value::CqlValue;
 use scylla::value::CqlVarint;
+use std::cell::UnsafeCell;
+use std::collections::HashMap;
 use std::fmt;
+use std::fs::File;
 use std::hash::Hash;
 #[cfg(test)]
 use std::hash::Hasher;
+use std::io;
 use std::iter::FusedIterator;
 use std::net::IpAddr;
 use std::net::Ipv4Addr;
 use std::net::Ipv6Addr;
+use std::os::fd::AsRawFd;
+use std::path::Path;
 use std::sync::Arc;
+use std::sync::Weak;
+use tracing::warn;
 use uuid::Uuid;
 
 // Type tag constants for the compact encoding.
@@ -97,15 +108,105 @@ const UUID_SIZE: usize = 16;
 /// `[count: u8][value₀][value₁]…[valueₙ₋₁]`
 ///
 /// Each value is `[tag: u8][data…]` with minimal encoding per type.
-/// Values are decoded on demand via [`get()`](Self::get) or [`iter()`](Self::iter).
+/// Values are decoded on demand via [`get()`](Self::get) or [`iter()`](Self::iter). The
+/// [`get_ref()`](Self::get_ref) and [`iter_ref()`](Self::iter_ref) variants borrow text and
+/// binary data from the buffer instead of allocating.
 ///
 /// Equality and hashing operate directly on the raw bytes, which is both faster
 /// and more correct than the previous `format!("{:?}")` hashing approach.
 ///
-/// The inner buffer is reference-counted via [`Arc`], so cloning is O(1).
-#[derive(Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+/// A buffer of up to [`INLINE_CAPACITY`] bytes is stored inline, which covers keys of a single
+/// `Int`, `BigInt` or `Uuid` column. A longer buffer is reference-counted via [`Arc`], either on
+/// its own or as a slice of a [`KeyArena`] chunk. A key stored by an arena with interning enabled
+/// may share its leading columns with other keys and keep only the rest inline, in which case the
+/// buffer is split in two parts. Either way cloning is O(1).
+#[derive(Clone)]
 pub(crate) struct InvariantKey {
-    data: Arc<[u8]>,
+    data: Data,
+}
+
+/// Size of the largest buffer stored inline, so that [`InvariantKey`] is 24 bytes like a `Vec`.
+const INLINE_CAPACITY: usize = 22;
+
+/// Size of the largest rest of a buffer stored inline after an interned prefix.
+const INTERNED_SUFFIX_CAPACITY: usize = 14;
+
+/// The encoded leading columns shared by interned keys, including the count byte of the keys.
+struct InternedPrefix(Arc<[u8]>);
+
+#[derive(Clone)]
+enum Data {
+    /// The buffer is `bytes[..len]`, the rest is zeroed.
+    Inline {
+        len: u8,
+        bytes: [u8; INLINE_CAPACITY],
+    },
+    Heap(Arc<[u8]>),
+    /// The buffer is a slice of an arena chunk.
+    Arena {
+        chunk: Arc<ArenaChunk>,
+        offset: u32,
+        len: u32,
+    },
+    /// The buffer is the interned prefix followed by `suffix[..len]`.
+    Interned {
+        prefix: Arc<InternedPrefix>,
+        len: u8,
+        suffix: [u8; INTERNED_SUFFIX_CAPACITY],
+    },
+}
+
+impl From<Vec<u8>> for Data {
+    fn from(buf: Vec<u8>) -> Self {
+        if buf.len() <= INLINE_CAPACITY {
+            let mut bytes = [0; INLINE_CAPACITY];
+            bytes[..buf.len()].copy_from_slice(&buf);
+            Data::Inline {
+                len: buf.len() as u8,
+                bytes,
+            }
+        } else {
+            Data::Heap(buf.into())
+        }
+    }
+}
+
+// Equality, hashing and ordering use the encoded bytes only, as the same bytes may be stored
+// inline, on the heap or split after an interned prefix.
+impl PartialEq for InvariantKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for InvariantKey {}
+
+impl Hash for InvariantKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        match self.parts() {
+            (bytes, []) => bytes.hash(state),
+            // Hashers may hash the same bytes written in two parts differently.
+            (head, tail) => [head, tail].concat().hash(state),
+        }
+    }
+}
+
+impl PartialOrd for InvariantKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for InvariantKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        match (self.parts(), other.parts()) {
+            ((lhs, []), (rhs, [])) => lhs.cmp(rhs),
+            ((lhs_head, lhs_tail), (rhs_head, rhs_tail)) => lhs_head
+                .iter()
+                .chain(lhs_tail)
+                .cmp(rhs_head.iter().chain(rhs_tail)),
+        }
+    }
 }
 
 impl InvariantKey {
@@ -168,14 +269,32 @@ impl InvariantKey {
         InvariantKey { data: buf.into() }
     }
 
+    /// The encoded buffer, split in two parts at a value boundary. The second part is empty unless
+    /// the key is interned.
+    fn parts(&self) -> (&[u8], &[u8]) {
+        match &self.data {
+            Data::Inline { len, bytes } => (&bytes[..*len as usize], &[]),
+            Data::Heap(bytes) => (bytes, &[]),
+            // SAFETY: the range was written before the key was created and is never written again.
+            Data::Arena { chunk, offset, len } => {
+                (unsafe { chunk.slice(*offset as usize, *len as usize) }, &[])
+            }
+            Data::Interned {
+                prefix,
+                len,
+                suffix,
+            } => (&prefix.0, &suffix[..*len as usize]),
+        }
+    }
+
     /// Returns the number of values in this key.
     pub(crate) fn len(&self) -> usize {
-        self.data[0] as usize
+        self.parts().0[0] as usize
     }
 
     /// Returns `true` if this key has no values.
     pub(crate) fn is_empty(&self) -> bool {
-        self.data[0] == 0
+        self.len() == 0
     }
 
     /// Decode the value at `index`, returning it as a [`CqlValue`].
@@ -184,16 +303,19 @@ impl InvariantKey {
     /// offset (O(index)), then decodes the value. Since primary keys typically
     /// have 1–3 columns, this is effectively O(1).
     pub(crate) fn get(&self, index: usize) -> Option<CqlValue> {
-        let count = self.data[0] as usize;
-        if index >= count {
+        self.get_ref(index).map(CqlValue::from)
+    }
+
+    /// Decode the value at `index` without copying variable-length data out of the buffer.
+    pub(crate) fn get_ref(&self, index: usize) -> Option<CqlValueRef<'_>> {
+        let mut iter = self.iter_ref();
+        if index >= iter.remaining {
             return None;
         }
-
-        let mut offset = COUNT_SIZE; // skip the count byte
         for _ in 0..index {
-            offset += skip_value(&self.data[offset..]);
+            iter.skip_value();
         }
-        Some(decode_value(&self.data[offset..]).0)
+        iter.next()
     }
 
     /// Compute a hash of the first `n` columns.
@@ -211,7 +333,9 @@ impl InvariantKey {
     /// Panics if `n > self.len()`.
     #[cfg(test)]
     pub(crate) fn hash_prefix<H: Hasher>(&self, state: &mut H, n: usize) {
-        let count = self.data[0] as usize;
+        let (head, tail) = self.parts();
+        let bytes = [head, tail].concat();
+        let count = bytes[0] as usize;
         assert!(
             n <= count,
             "hash_prefix({n}) called on InvariantKey with {count} columns"
@@ -220,19 +344,25 @@ impl InvariantKey {
         // Find the byte range covering exactly the first `n` encoded values.
         let mut offset = COUNT_SIZE; // skip the count byte
         for _ in 0..n {
-            offset += skip_value(&self.data[offset..]);
+            offset += skip_value(&bytes[offset..]);
         }
         // Hash the count and the raw bytes of the first n values.
         (n as u8).hash(state);
-        self.data[COUNT_SIZE..offset].hash(state);
+        bytes[COUNT_SIZE..offset].hash(state);
     }
 
     /// Iterate over all decoded values.
-    pub(crate) fn iter(&self) -> InvariantKeyIter<'_> {
+    pub(crate) fn iter(&self) -> impl ExactSizeIterator<Item = CqlValue> + FusedIterator + '_ {
+        self.iter_ref().map(CqlValue::from)
+    }
+
+    /// Iterate over all values borrowed from the buffer.
+    pub(crate) fn iter_ref(&self) -> InvariantKeyIter<'_> {
+        let (head, tail) = self.parts();
         InvariantKeyIter {
-            data: &self.data,
-            offset: COUNT_SIZE,
-            remaining: self.data[0] as usize,
+            head: &head[COUNT_SIZE..],
+            tail,
+            remaining: head[0] as usize,
         }
     }
 
@@ -259,22 +389,42 @@ impl FromIterator<CqlValue> for InvariantKey {
     }
 }
 
-/// Iterator over the decoded [`CqlValue`]s in an [`InvariantKey`].
+/// Iterator over the [`CqlValueRef`]s in an [`InvariantKey`].
 pub(crate) struct InvariantKeyIter<'a> {
-    data: &'a [u8],
-    offset: usize,
+    /// The undecoded values of the current part of the buffer.
+    head: &'a [u8],
+    /// The second part of the buffer, decoded after `head`.
+    tail: &'a [u8],
     remaining: usize,
 }
 
-impl Iterator for InvariantKeyIter<'_> {
-    type Item = CqlValue;
+impl<'a> InvariantKeyIter<'a> {
+    /// Returns the encoded next value, moving to the second part of the buffer when needed.
+    fn next_data(&mut self) -> &'a [u8] {
+        if self.head.is_empty() {
+            self.head = std::mem::take(&mut self.tail);
+        }
+        self.head
+    }
+
+    /// Skips the next value without decoding it.
+    fn skip_value(&mut self) {
+        let data = self.next_data();
+        self.head = &data[skip_value(data)..];
+        self.remaining -= 1;
+    }
+}
+
+impl<'a> Iterator for InvariantKeyIter<'a> {
+    type Item = CqlValueRef<'a>;
 
-    fn next(&mut self) -> Option<CqlValue> {
+    fn next(&mut self) -> Option<CqlValueRef<'a>> {
         if self.remaining == 0 {
             return None;
         }
-        let (value, consumed) = decode_value(&self.data[self.offset..]);
-        self.offset += consumed;
+        let data = self.next_data();
+        let (value, consumed) = decode_value(data);
+        self.head = &data[consumed..];
         self.remaining -= 1;
         Some(value)
     }
@@ -299,6 +449,474 @@ impl From<Vec<CqlValue>> for InvariantKey {
     }
 }
 
+/// A value decoded from an [`InvariantKey`], borrowing variable-length data from its buffer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum CqlValueRef<'a> {
+    Empty,
+    Boolean(bool),
+    TinyInt(i8),
+    SmallInt(i16),
+    Int(i32),
+    BigInt(i64),
+    Float(f32),
+    Double(f64),
+    Text(&'a str),
+    Ascii(&'a str),
+    Blob(&'a [u8]),
+    /// Signed big-endian two's complement bytes.
+    Varint(&'a [u8]),
+    /// Signed big-endian two's complement bytes of the unscaled value.
+    Decimal {
+        unscaled: &'a [u8],
+        scale: i32,
+    },
+    Uuid(Uuid),
+    Timeuuid(CqlTimeuuid),
+    Date(CqlDate),
+    Time(CqlTime),
+    Timestamp(CqlTimestamp),
+    Inet(IpAddr),
+    Counter(Counter),
+}
+
+impl From<CqlValueRef<'_>> for CqlValue {
+    fn from(value: CqlValueRef<'_>) -> Self {
+        match value {
+            CqlValueRef::Empty => CqlValue::Empty,
+            CqlValueRef::Boolean(v) => CqlValue::Boolean(v),
+            CqlValueRef::TinyInt(v) => CqlValue::TinyInt(v),
+            CqlValueRef::SmallInt(v) => CqlValue::SmallInt(v),
+            CqlValueRef::Int(v) => CqlValue::Int(v),
+            CqlValueRef::BigInt(v) => CqlValue::BigInt(v),
+            CqlValueRef::Float(v) => CqlValue::Float(v),
+            CqlValueRef::Double(v) => CqlValue::Double(v),
+            CqlValueRef::Text(v) => CqlValue::Text(v.to_string()),
+            CqlValueRef::Ascii(v) => CqlValue::Ascii(v.to_string()),
+            CqlValueRef::Blob(v) => CqlValue::Blob(v.to_vec()),
+            CqlValueRef::Varint(v) => CqlValue::Varint(CqlVarint::from_signed_bytes_be(v.to_vec())),
+            CqlValueRef::Decimal { unscaled, scale } => CqlValue::Decimal(
+                CqlDecimal::from_signed_be_bytes_and_exponent(unscaled.to_vec(), scale),
+            ),
+            CqlValueRef::Uuid(v) => CqlValue::Uuid(v),
+            CqlValueRef::Timeuuid(v) => CqlValue::Timeuuid(v),
+            CqlValueRef::Date(v) => CqlValue::Date(v),
+            CqlValueRef::Time(v) => CqlValue::Time(v),
+            CqlValueRef::Timestamp(v) => CqlValue::Timestamp(v),
+            CqlValueRef::Inet(v) => CqlValue::Inet(v),
+            CqlValueRef::Counter(v) => CqlValue::Counter(v),
+        }
+    }
+}
+
+impl CqlValueRef<'_> {
+    /// Appends the value serialized in the CQL binary protocol format, without the length prefix.
+    pub(crate) fn write_cql(&self, buf: &mut Vec<u8>) {
+        match self {
+            CqlValueRef::Empty => {}
+            CqlValueRef::Boolean(v) => buf.push(u8::from(*v)),
+            CqlValueRef::TinyInt(v) => buf.extend_from_slice(&v.to_be_bytes()),
+            CqlValueRef::SmallInt(v) => buf.extend_from_slice(&v.to_be_bytes()),
+            CqlValueRef::Int(v) => buf.extend_from_slice(&v.to_be_bytes()),
+            CqlValueRef::BigInt(v) => buf.extend_from_slice(&v.to_be_bytes()),
+            CqlValueRef::Float(v) => buf.extend_from_slice(&v.to_be_bytes()),
+            CqlValueRef::Double(v) => buf.extend_from_slice(&v.to_be_bytes()),
+            CqlValueRef::Text(v) | CqlValueRef::Ascii(v) => buf.extend_from_slice(v.as_bytes()),
+            CqlValueRef::Blob(v) | CqlValueRef::Varint(v) => buf.extend_from_slice(v),
+            CqlValueRef::Decimal { unscaled, scale } => {
+                buf.extend_from_slice(&scale.to_be_bytes());
+                buf.extend_from_slice(unscaled);
+            }
+            CqlValueRef::Uuid(v) => buf.extend_from_slice(v.as_bytes()),
+            CqlValueRef::Timeuuid(v) => buf.extend_from_slice(v.as_bytes()),
+            CqlValueRef::Date(v) => buf.extend_from_slice(&v.0.to_be_bytes()),
+            CqlValueRef::Time(v) => buf.extend_from_slice(&v.0.to_be_bytes()),
+            CqlValueRef::Timestamp(v) => buf.extend_from_slice(&v.0.to_be_bytes()),
+            CqlValueRef::Inet(IpAddr::V4(v)) => buf.extend_from_slice(&v.octets()),
+            CqlValueRef::Inet(IpAddr::V6(v)) => buf.extend_from_slice(&v.octets()),
+            CqlValueRef::Counter(v) => buf.extend_from_slice(&v.0.to_be_bytes()),
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Arena
+// ---------------------------------------------------------------------------
+
+/// Size of a [`KeyArena`] chunk held in memory.
+const ARENA_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Size of a [`KeyArena`] chunk mapped from a file. It is larger than a chunk in memory to keep
+/// the number of mappings of a large index far below the `vm.max_map_count` limit.
+const ARENA_MAPPED_CHUNK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Buffers longer than this are not stored in a [`KeyArena`], so that a chunk wastes at most a
+/// quarter of its size when it is retired.
+const ARENA_MAX_KEY_SIZE: usize = ARENA_CHUNK_SIZE / 4;
+
+/// A [`KeyArena`] starts a new chunk only if at least this percentage of the keys stored in its
+/// earlier chunks are still alive. Otherwise keys get their own allocations, so that a few
+/// surviving keys cannot keep ever more chunks alive.
+const ARENA_MIN_LIVE_PERCENT: usize = 50;
+
+/// A fixed-size block of key buffers. A range of bytes is written once, before any key referring
+/// to it is created, and is immutable afterwards.
+enum ArenaChunk {
+    Memory(Box<[UnsafeCell<u8>]>),
+    Mapped {
+        mmap: MmapRaw,
+        /// Offset of the chunk in the file it is mapped from.
+        offset: u64,
+    },
+}
+
+// SAFETY: the bytes are only written through `&mut KeyArena`, in the range not yet handed out to
+// any key, so the ranges read by other threads are never written concurrently.
+unsafe impl Sync for ArenaChunk {}
+
+impl ArenaChunk {
+    fn memory() -> Self {
+        Self::Memory((0..ARENA_CHUNK_SIZE).map(|_| UnsafeCell::new(0)).collect())
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Self::Memory(bytes) => bytes.len(),
+            Self::Mapped { mmap, .. } => mmap.len(),
+        }
+    }
+
+    fn as_mut_ptr(&self) -> *mut u8 {
+        match self {
+            // `UnsafeCell<u8>` has the same layout as `u8`.
+            Self::Memory(bytes) => UnsafeCell::raw_get(bytes.as_ptr()),
+            Self::Mapped { mmap, .. } => mmap.as_mut_ptr(),
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The range must not be written while the returned slice is alive.
+    unsafe fn slice(&self, offset: usize, len: usize) -> &[u8] {
+        assert!(offset + len <= self.len());
+        // SAFETY: the range lies within the chunk and the caller guarantees there are no
+        // concurrent writes.
+        unsafe { std::slice::from_raw_parts(self.as_mut_ptr().add(offset), len) }
+    }
+
+    /// # Safety
+    ///
+    /// The range must not be read or written concurrently.
+    unsafe fn write(&self, offset: usize, src: &[u8]) {
+        assert!(offset + src.len() <= self.len());
+        // SAFETY: the destination lies within the chunk and the caller guarantees exclusive access.
+        unsafe {
+            std::ptr::copy_nonoverlapping(src.as_ptr(), self.as_mut_ptr().add(offset), src.len())
+        };
+    }
+}
+
+/// A bump allocator for the buffers of long-lived keys.
+///
+/// Keys too long to be stored inline are copied into shared chunks instead of getting a heap
+/// allocation each, which saves the per-allocation overhead and avoids fragmenting the allocator
+/// with millions of small buffers. A chunk is freed as a whole when all keys stored in it are
+/// dropped, so the space of dropped keys is not reused. When most keys of the earlier chunks are
+/// dropped, new keys get their own allocations until enough of those chunks are freed, which
+/// keeps the memory of the chunks within twice the memory of the keys alive in them.
+///
+/// A [`mapped`](Self::mapped) arena maps its chunks from a file instead, so that the kernel can
+/// evict the key bytes of a very large index from memory. Only the keys themselves, which refer to
+/// the chunks, stay in anonymous memory. The space of a chunk is allocated on the disk before it is
+/// mapped, and the arena falls back to a chunk in memory when that fails, e.g. on a full disk. The
+/// space of freed chunks is reused by later chunks but not returned to the file system, so the
+/// file grows in steps of 64 MiB up to the most chunks alive at once and never shrinks. It is
+/// removed when the arena and its keys are dropped.
+///
+/// An arena [`with_interning`](Self::with_interning) keeps a single copy of the leading columns
+/// shared by many keys, e.g. a low-cardinality partition key, and stores the rest of such a key
+/// inline. The prefixes no key refers to anymore are forgotten, checked once per as many interned
+/// keys as there are prefixes.
+#[derive(Default)]
+pub(crate) struct KeyArena {
+    /// The unlinked file the chunks are mapped from, if any.
+    file: Option<File>,
+    chunk: Option<Arc<ArenaChunk>>,
+    used: usize,
+    /// Number of keys stored in the current chunk.
+    stored: usize,
+    retired: Vec<RetiredChunk>,
+    /// Offsets in the file of the freed chunks, to be mapped again.
+    free_offsets: Vec<u64>,
+    /// Number of keys left on the heap since the chunks were last checked.
+    unchecked: usize,
+    interner: Option<Interner>,
+    /// Bytes of memory taken by interned prefixes and keys left on the heap.
+    memory_bytes: usize,
+    /// Bytes of memory taken by the chunks not mapped from the file, as of the last check.
+    chunk_bytes: usize,
+}
+
+/// A chunk the arena does not write to anymore, alive as long as any key refers to it.
+struct RetiredChunk {
+    chunk: Weak<ArenaChunk>,
+    /// Number of keys stored in the chunk.
+    stored: usize,
+    /// Offset in the file of a mapped chunk.
+    offset: Option<u64>,
+}
+
+/// The interned prefixes of an arena, kept as long as any key refers to them.
+struct Interner {
+    prefixes: HashMap<Arc<[u8]>, Arc<InternedPrefix>>,
+    /// Bytes of the prefixes the keys would store each, less a single copy of every prefix.
+    saved_bytes: Gauge,
+    /// Number of keys interned since the prefixes were last checked.
+    unchecked: usize,
+}
+
+impl Interner {
+    /// Forgets the prefixes no key refers to anymore and recounts the bytes saved by the others.
+    /// Returns the bytes of the forgotten prefixes. Clones of a key count as keys referring to its
+    /// prefix.
+    fn sweep(&mut self) -> usize {
+        let mut freed = 0;
+        let mut saved = 0;
+        self.prefixes.retain(|head, prefix| {
+            // One reference is held by the interner itself.
+            let keys = Arc::strong_count(prefix) - 1;
+            if keys == 0 {
+                freed += head.len();
+                return false;
+            }
+            saved += (keys - 1) * head.len();
+            true
+        });
+        self.unchecked = 0;
+        self.saved_bytes.set(saved as f64);
+        freed
+    }
+}
+
+impl fmt::Debug for KeyArena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyArena")
+            .field("mapped", &self.file.is_some())
+            .field("used", &self.used)
+            .field("retired", &self.retired.len())
+            .field("memory_bytes", &self.memory_bytes())
+            .field(
+                "interned",
+                &self
+                    .interner
+                    .as_ref()
+                    .map(|interner| interner.prefixes.len()),
+            )
+            .finish()
+    }
+}
+
+impl KeyArena {
+    /// Creates an arena with chunks mapped from a file in `dir`. The file is removed right away,
+    /// so its space is released once the arena and its keys are dropped, even after a crash.
+    pub(crate) fn mapped(dir: &Path) -> io::Result<Self> {
+        Ok(Self {
+            file: Some(tempfile::tempfile_in(dir)?),
+            ..Self::default()
+        })
+    }
+
+    /// Enables interning of the leading columns of keys. The bytes saved by the prefixes shared
+    /// by the keys alive are reported by `saved_bytes`.
+    pub(crate) fn with_interning(mut self, saved_bytes: Gauge) -> Self {
+        self.interner = Some(Interner {
+            prefixes: HashMap::new(),
+            saved_bytes,
+            unchecked: 0,
+        });
+        self
+    }
+
+    pub(crate) fn memory_bytes(&self) -> usize {
+        self.memory_bytes + self.chunk_bytes
+    }
+
+    /// Retires the current chunk and forgets the chunks no key refers to anymore. Returns whether
+    /// enough keys of the retired chunks are alive to start a new chunk.
+    fn retire_chunk(&mut self) -> bool {
+        if let Some(chunk) = self.chunk.take() {
+            self.retired.push(RetiredChunk {
+                chunk: Arc::downgrade(&chunk),
+                stored: self.stored,
+                offset: match *chunk {
+                    ArenaChunk::Memory(_) => None,
+                    ArenaChunk::Mapped { offset, .. } => Some(offset),
+                },
+            });
+        }
+        let free_offsets = &mut self.free_offsets;
+        self.retired.retain(|retired| {
+            let alive = retired.chunk.strong_count() > 0;
+            if !alive {
+                free_offsets.extend(retired.offset);
+            }
+            alive
+        });
+        self.unchecked = 0;
+        self.chunk_bytes = self
+            .retired
+            .iter()
+            .filter(|retired| retired.offset.is_none())
+            .count()
+            * ARENA_CHUNK_SIZE;
+        // Clones of a key count as live keys, which only delays the fallback.
+        let live: usize = self
+            .retired
+            .iter()
+            .map(|retired| retired.chunk.strong_count())
+            .sum();
+        let stored: usize = self.retired.iter().map(|retired| retired.stored).sum();
+        live * 100 >= stored * ARENA_MIN_LIVE_PERCENT
+    }
+
+    /// Maps a new chunk in place of a freed one or at the end of the file. The space at the end is
+    /// allocated first, as a write to a mapped hole of a sparse file on a full disk raises
+    /// `SIGBUS` instead of returning an error.
+    fn new_chunk(&mut self) -> io::Result<ArenaChunk> {
+        let Some(file) = &self.file else {
+            return Ok(ArenaChunk::memory());
+        };
+        let offset = match self.free_offsets.pop() {
+            Some(offset) => offset,
+            None => {
+                let offset = file.metadata()?.len();
+                if let Err(err) = allocate(file, offset, ARENA_MAPPED_CHUNK_SIZE) {
+                    // Releases the blocks allocated before the failure, if any.
+                    _ = file.set_len(offset);
+                    return Err(err);
+                }
+                offset
+            }
+        };
+        match MmapOptions::new()
+            .offset(offset)
+            .len(ARENA_MAPPED_CHUNK_SIZE)
+            .map_raw(file)
+        {
+            Ok(mmap) => Ok(ArenaChunk::Mapped { mmap, offset }),
+            Err(err) => {
+                self.free_offsets.push(offset);
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns a key equal to `key` with its buffer stored in the arena. Keys stored inline or
+    /// longer than a quarter of a chunk are returned as they are. With interning enabled, the
+    /// first `prefix_columns` columns are interned if the rest of the key fits inline.
+    pub(crate) fn store(&mut self, key: &InvariantKey, prefix_columns: usize) -> InvariantKey {
+        let Data::Heap(bytes) = &key.data else {
+            return key.clone();
+        };
+        if let Some(interned) = self.intern(bytes, prefix_columns) {
+            return interned;
+        }
+        if bytes.len() > ARENA_MAX_KEY_SIZE {
+            self.memory_bytes += bytes.len();
+            return key.clone();
+        }
+        if self
+            .chunk
+            .as_ref()
+            .is_none_or(|chunk| self.used + bytes.len() > chunk.len())
+        {
+            // Checking the retired chunks once per as many keys as there are chunks keeps the
+            // cost of the fallback constant per key.
+            if (self.chunk.is_none() && self.unchecked < self.retired.len()) || !self.retire_chunk()
+            {
+                self.unchecked += 1;
+                self.memory_bytes += bytes.len();
+                return key.clone();
+            }
+            let chunk = self.new_chunk().unwrap_or_else(|err| {
+                warn!("unable to map a chunk of the primary keys, keeping it in memory: {err}");
+                ArenaChunk::memory()
+            });
+            if let ArenaChunk::Memory(bytes) = &chunk {
+                self.chunk_bytes += bytes.len();
+            }
+            self.chunk = Some(Arc::new(chunk));
+            self.used = 0;
+            self.stored = 0;
+        }
+        let chunk = self.chunk.as_ref().unwrap();
+        // SAFETY: the range past `used` has not been handed out to any key yet and `&mut self`
+        // guarantees no other writer.
+        unsafe { chunk.write(self.used, bytes) };
+        let data = Data::Arena {
+            chunk: Arc::clone(chunk),
+            offset: self.used as u32,
+            len: bytes.len() as u32,
+        };
+        self.used += bytes.len();
+        self.stored += 1;
+        InvariantKey { data }
+    }
+
+    fn intern(&mut self, bytes: &[u8], prefix_columns: usize) -> Option<InvariantKey> {
+        let interner = self.interner.as_mut()?;
+        if prefix_columns == 0 || prefix_columns >= bytes[0] as usize {
+            return None;
+        }
+        let mut split = COUNT_SIZE;
+        for _ in 0..prefix_columns {
+            split += skip_value(&bytes[split..]);
+        }
+        let (head, tail) = bytes.split_at(split);
+        if tail.len() > INTERNED_SUFFIX_CAPACITY {
+            return None;
+        }
+        // Checking the prefixes once per as many keys as there are prefixes keeps the cost of the
+        // eviction constant per key.
+        interner.unchecked += 1;
+        if interner.unchecked > interner.prefixes.len() {
+            self.memory_bytes -= interner.sweep();
+        }
+        let prefix = match interner.prefixes.get(head) {
+            Some(prefix) => {
+                interner.saved_bytes.add(head.len() as f64);
+                Arc::clone(prefix)
+            }
+            None => {
+                let head: Arc<[u8]> = head.into();
+                let prefix = Arc::new(InternedPrefix(Arc::clone(&head)));
+                self.memory_bytes += head.len();
+                interner.prefixes.insert(head, Arc::clone(&prefix));
+                prefix
+            }
+        };
+        let mut suffix = [0; INTERNED_SUFFIX_CAPACITY];
+        suffix[..tail.len()].copy_from_slice(tail);
+        Some(InvariantKey {
+            data: Data::Interned {
+                prefix,
+                len: tail.len() as u8,
+                suffix,
+            },
+        })
+    }
+}
+
+/// Allocates the disk blocks of `len` bytes of `file` at `offset`, extending the file if needed.
+fn allocate(file: &File, offset: u64, len: usize) -> io::Result<()> {
+    // SAFETY: the descriptor stays open for the duration of the call.
+    match unsafe {
+        libc::posix_fallocate(file.as_raw_fd(), offset as libc::off_t, len as libc::off_t)
+    } {
+        0 => Ok(()),
+        err => Err(io::Error::from_raw_os_error(err)),
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Encoding
 // ---------------------------------------------------------------------------
@@ -497,58 +1115,67 @@ fn skip_value(data: &[u8]) -> usize {
 }
 
 /// Decode one value from the buffer, returning `(value, bytes_consumed)`.
-fn decode_value(data: &[u8]) -> (CqlValue, usize) {
+fn decode_value(data: &[u8]) -> (CqlValueRef<'_>, usize) {
     match data[0] {
-        TAG_EMPTY => (CqlValue::Empty, TAG_SIZE),
+        TAG_EMPTY => (CqlValueRef::Empty, TAG_SIZE),
 
         TAG_BOOLEAN => (
-            CqlValue::Boolean(data[DATA_OFFSET] != 0),
+            CqlValueRef::Boolean(data[DATA_OFFSET] != 0),
             TAG_SIZE + BYTE_SIZE,
         ),
 
         TAG_TINY_INT => {
             let v = i8::from_le_bytes(read_fixed::<1>(data));
-            (CqlValue::TinyInt(v), TAG_SIZE + BYTE_SIZE)
+            (CqlValueRef::TinyInt(v), TAG_SIZE + BYTE_SIZE)
         }
 
         TAG_SMALL_INT => {
             let v = i16::from_le_bytes(read_fixed::<2>(data));
-            (CqlValue::SmallInt(v), TAG_SIZE + std::mem::size_of::<i16>())
+            (
+                CqlValueRef::SmallInt(v),
+                TAG_SIZE + std::mem::size_of::<i16>(),
+            )
         }
         TAG_INT => {
             let v = i32::from_le_bytes(read_fixed::<4>(data));
-            (CqlValue::Int(v), TAG_SIZE + std::mem::size_of::<i32>())
+            (CqlValueRef::Int(v), TAG_SIZE + std::mem::size_of::<i32>())
         }
         TAG_BIG_INT => {
             let v = i64::from_le_bytes(read_fixed::<8>(data));
-            (CqlValue::BigInt(v), TAG_SIZE + std::mem::size_of::<i64>())
+            (
+                CqlValueRef::BigInt(v),
+                TAG_SIZE + std::mem::size_of::<i64>(),
+            )
         }
         TAG_FLOAT => {
             let v = f32::from_le_bytes(read_fixed::<4>(data));
-            (CqlValue::Float(v), TAG_SIZE + std::mem::size_of::<f32>())
+            (CqlValueRef::Float(v), TAG_SIZE + std::mem::size_of::<f32>())
         }
         TAG_DOUBLE => {
             let v = f64::from_le_bytes(read_fixed::<8>(data));
-            (CqlValue::Double(v), TAG_SIZE + std::mem::size_of::<f64>())
+            (
+                CqlValueRef::Double(v),
+                TAG_SIZE + std::mem::size_of::<f64>(),
+            )
         }
 
         TAG_TEXT => {
             let len = read_var_len(data);
-            let s = String::from_utf8(data[VAR_DATA_OFFSET..VAR_DATA_OFFSET + len].to_vec())
+            let s = std::str::from_utf8(&data[VAR_DATA_OFFSET..VAR_DATA_OFFSET + len])
                 .expect("invalid UTF-8 in InvariantKey Text value");
-            (CqlValue::Text(s), VAR_DATA_OFFSET + len)
+            (CqlValueRef::Text(s), VAR_DATA_OFFSET + len)
         }
         TAG_ASCII => {
             let len = read_var_len(data);
             // ASCII is valid UTF-8
-            let s = String::from_utf8(data[VAR_DATA_OFFSET..VAR_DATA_OFFSET + len].to_vec())
+            let s = std::str::from_utf8(&data[VAR_DATA_OFFSET..VAR_DATA_OFFSET + len])
                 .expect("invalid UTF-8 in InvariantKey Ascii value");
-            (CqlValue::Ascii(s), VAR_DATA_OFFSET + len)
+            (CqlValueRef::Ascii(s), VAR_DATA_OFFSET + len)
         }
         TAG_BLOB => {
             let len = read_var_len(data);
             (
-                CqlValue::Blob(data[VAR_DATA_OFFSET..VAR_DATA_OFFSET + len].to_vec()),
+                CqlValueRef::Blob(&data[VAR_DATA_OFFSET..VAR_DATA_OFFSET + len]),
                 VAR_DATA_OFFSET + len,
             )
         }
@@ -556,9 +1183,7 @@ fn decode_value(data: &[u8]) -> (CqlValue, usize) {
         TAG_VARINT => {
             let len = read_var_len(data);
             (
-                CqlValue::Varint(CqlVarint::from_signed_bytes_be(
-                    data[VAR_DATA_OFFSET..VAR_DATA_OFFSET + len].to_vec(),
-                )),
+                CqlValueRef::Varint(&data[VAR_DATA_OFFSET..VAR_DATA_OFFSET + len]),
                 VAR_DATA_OFFSET + len,
             )
         }
@@ -568,12 +1193,11 @@ fn decode_value(data: &[u8]) -> (CqlValue, usize) {
             let payload = &data[VAR_DATA_OFFSET..VAR_DATA_OFFSET + len];
             let scale =
                 i32::from_be_bytes(payload[..std::mem::size_of::<i32>()].try_into().unwrap());
-            let varint_bytes = payload[std::mem::size_of::<i32>()..].to_vec();
             (
-                CqlValue::Decimal(CqlDecimal::from_signed_be_bytes_and_exponent(
-                    varint_bytes,
+                CqlValueRef::Decimal {
+                    unscaled: &payload[std::mem::size_of::<i32>()..],
                     scale,
-                )),
+                },
                 VAR_DATA_OFFSET + len,
             )
         }
@@ -581,14 +1205,14 @@ fn decode_value(data: &[u8]) -> (CqlValue, usize) {
         TAG_UUID => {
             let bytes: [u8; 16] = read_fixed::<16>(data);
             (
-                CqlValue::Uuid(Uuid::from_bytes(bytes)),
+                CqlValueRef::Uuid(Uuid::from_bytes(bytes)),
                 TAG_SIZE + UUID_SIZE,
             )
         }
         TAG_TIMEUUID => {
             let bytes: [u8; 16] = read_fixed::<16>(data);
             (
-                CqlValue::Timeuuid(CqlTimeuuid::from_bytes(bytes)),
+                CqlValueRef::Timeuuid(CqlTimeuuid::from_bytes(bytes)),
                 TAG_SIZE + UUID_SIZE,
             )
         }
@@ -596,21 +1220,21 @@ fn decode_value(data: &[u8]) -> (CqlValue, usize) {
         TAG_DATE => {
             let v = u32::from_le_bytes(read_fixed::<4>(data));
             (
-                CqlValue::Date(CqlDate(v)),
+                CqlValueRef::Date(CqlDate(v)),
                 TAG_SIZE + std::mem::size_of::<u32>(),
             )
         }
         TAG_TIME => {
             let v = i64::from_le_bytes(read_fixed::<8>(data));
             (
-                CqlValue::Time(CqlTime(v)),
+                CqlValueRef::Time(CqlTime(v)),
                 TAG_SIZE + std::mem::size_of::<i64>(),
             )
         }
         TAG_TIMESTAMP => {
             let v = i64::from_le_bytes(read_fixed::<8>(data));
             (
-                CqlValue::Timestamp(CqlTimestamp(v)),
+                CqlValueRef::Timestamp(CqlTimestamp(v)),
                 TAG_SIZE + std::mem::size_of::<i64>(),
             )
         }
@@ -618,14 +1242,14 @@ fn decode_value(data: &[u8]) -> (CqlValue, usize) {
         TAG_INET_V4 => {
             let octets: [u8; 4] = read_fixed::<4>(data);
             (
-                CqlValue::Inet(IpAddr::V4(Ipv4Addr::from(octets))),
+                CqlValueRef::Inet(IpAddr::V4(Ipv4Addr::from(octets))),
                 TAG_SIZE + IPV4_SIZE,
             )
         }
         TAG_INET_V6 => {
             let octets: [u8; 16] = read_fixed::<16>(data);
             (
-                CqlValue::Inet(IpAddr::V6(Ipv6Addr::from(octets))),
+                CqlValueRef::Inet(IpAddr::V6(Ipv6Addr::from(octets))),
                 TAG_SIZE + UUID_SIZE,
             )
         }
@@ -633,7 +1257,7 @@ fn decode_value(data: &[u8]) -> (CqlValue, usize) {
         TAG_COUNTER => {
             let v = i64::from_le_bytes(read_fixed::<8>(data));
             (
-                CqlValue::Counter(Counter(v)),
+                CqlValueRef::Counter(Counter(v)),
                 TAG_SIZE + std::mem::size_of::<i64>(),
             )
         }
@@ -645,15 +1269,35 @@ fn decode_value(data: &[u8]) -> (CqlValue, usize) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::hash::BuildHasher;
+    use std::hash::RandomState;
 
-    // Static assertion: InvariantKey must be exactly 16 bytes (Arc<[u8]> = ptr + len).
-    const _: () = assert!(std::mem::size_of::<InvariantKey>() == 16);
+    // Static assertion: InvariantKey must be exactly 24 bytes (tag + inline length + buffer).
+    const _: () = assert!(std::mem::size_of::<InvariantKey>() == 24);
 
     #[test]
     fn single_int_overhead() {
         let ik = InvariantKey::new(vec![CqlValue::Int(42)]);
-        // 1 byte count + 1 byte tag + 4 bytes i32 = 6 bytes on heap
-        assert_eq!(ik.data.len(), 6);
+        // 1 byte count + 1 byte tag + 4 bytes i32 = 6 bytes, stored inline
+        assert_eq!(ik.parts(), (&[1, TAG_INT, 42, 0, 0, 0][..], &[][..]));
+        assert!(matches!(ik.data, Data::Inline { .. }));
+    }
+
+    #[test]
+    fn keys_are_inline_up_to_capacity() {
+        // 1 byte count + 1 byte tag + 16 bytes uuid = 18 bytes
+        let uuid: InvariantKey = vec![CqlValue::Uuid(Uuid::new_v4())].into();
+        assert!(matches!(uuid.data, Data::Inline { .. }));
+
+        // 1 byte count + 1 byte tag + 4 bytes length + 16 bytes text = 22 bytes
+        let text = |len| InvariantKey::new(vec![CqlValue::Text("x".repeat(len))]);
+        let inline = text(16);
+        assert!(matches!(inline.data, Data::Inline { .. }));
+        let heap = text(17);
+        assert!(matches!(heap.data, Data::Heap(_)));
+        assert_eq!(inline.get(0), Some(CqlValue::Text("x".repeat(16))));
+        assert_eq!(heap.get(0), Some(CqlValue::Text("x".repeat(17))));
+        assert!(inline < heap);
     }
 
     #[test]
@@ -763,6 +1407,31 @@ mod tests {
         assert_ne!(hash_prefix(&ik1, 2), hash_prefix(&ik2, 2));
     }
 
+    #[test]
+    fn borrowed_values_point_into_the_buffer() {
+        let ik: InvariantKey = vec![
+            CqlValue::Text("hello".to_string()),
+            CqlValue::Blob(vec![0xDE, 0xAD]),
+            CqlValue::Int(7),
+        ]
+        .into();
+
+        let Some(CqlValueRef::Text(text)) = ik.get_ref(0) else {
+            panic!("expected a text value");
+        };
+        assert_eq!(text, "hello");
+        assert!(ik.parts().0.as_ptr_range().contains(&text.as_ptr()));
+        assert_eq!(
+            ik.iter_ref().collect::<Vec<_>>(),
+            vec![
+                CqlValueRef::Text("hello"),
+                CqlValueRef::Blob(&[0xDE, 0xAD]),
+                CqlValueRef::Int(7),
+            ]
+        );
+        assert_eq!(ik.get_ref(3), None);
+    }
+
     #[test]
     fn iter_yields_all_values() {
         let ik: InvariantKey = vec![CqlValue::Int(1), CqlValue::Int(2), CqlValue::Int(3)].into();
@@ -789,10 +1458,210 @@ mod tests {
 
     #[test]
     fn clone_is_cheap_arc() {
-        let ik1: InvariantKey = vec![CqlValue::Int(42)].into();
+        let ik1: InvariantKey =
+            vec![CqlValue::Text("a text longer than inline".to_string())].into();
         let ik2 = ik1.clone();
         // Arc clone shares the same allocation
-        assert!(Arc::ptr_eq(&ik1.data, &ik2.data));
+        let (Data::Heap(data1), Data::Heap(data2)) = (&ik1.data, &ik2.data) else {
+            panic!("long keys should be stored on the heap");
+        };
+        assert!(Arc::ptr_eq(data1, data2));
+    }
+
+    #[test]
+    fn arena_keys_share_chunks() {
+        let text = |text: &str| InvariantKey::new(vec![CqlValue::Text(text.repeat(32))]);
+        let mut arena = KeyArena::default();
+
+        let inline = arena.store(&InvariantKey::new(vec![CqlValue::Int(1)]), 0);
+        assert!(matches!(inline.data, Data::Inline { .. }));
+
+        let key1 = arena.store(&text("a"), 0);
+        let key2 = arena.store(&text("b"), 0);
+        let (Data::Arena { chunk: chunk1, .. }, Data::Arena { chunk: chunk2, .. }) =
+            (&key1.data, &key2.data)
+        else {
+            panic!("long keys should be stored in the arena");
+        };
+        assert!(Arc::ptr_eq(chunk1, chunk2));
+        assert_eq!(key1, text("a"));
+        assert_eq!(key2, text("b"));
+        assert_eq!(key1.get(0), Some(CqlValue::Text("a".repeat(32))));
+
+        drop(arena);
+        assert_eq!(key2.clone(), text("b"));
+    }
+
+    #[test]
+    fn arena_starts_new_chunk_when_full() {
+        let key = InvariantKey::new(vec![CqlValue::Blob(vec![7; ARENA_MAX_KEY_SIZE - 8])]);
+        let mut arena = KeyArena::default();
+
+        let keys: Vec<_> = (0..5).map(|_| arena.store(&key, 0)).collect();
+
+        let chunks: Vec<_> = keys
+            .iter()
+            .map(|stored| {
+                assert_eq!(*stored, key);
+                let Data::Arena { chunk, .. } = &stored.data else {
+                    panic!("key should be stored in the arena");
+                };
+                Arc::as_ptr(chunk)
+            })
+            .collect();
+        assert!(chunks[..4].iter().all(|chunk| *chunk == chunks[0]));
+        assert_ne!(chunks[4], chunks[0]);
+        assert_eq!(arena.memory_bytes(), 2 * ARENA_CHUNK_SIZE);
+
+        let huge = InvariantKey::new(vec![CqlValue::Blob(vec![7; ARENA_MAX_KEY_SIZE])]);
+        assert!(matches!(arena.store(&huge, 0).data, Data::Heap(_)));
+        assert_eq!(
+            arena.memory_bytes(),
+            2 * ARENA_CHUNK_SIZE + huge.parts().0.len()
+        );
+    }
+
+    #[test]
+    fn arena_memory_stays_bounded_under_churn() {
+        let key = |round: usize, i: usize| {
+            InvariantKey::new(vec![CqlValue::Text(format!("{round:08}{i:08}").repeat(4))])
+        };
+        let mut arena = KeyArena::default();
+
+        let mut survivors = Vec::new();
+        for round in 0..100 {
+            let keys: Vec<_> = (0..1000).map(|i| arena.store(&key(round, i), 0)).collect();
+            // One key in ten outlives the others, which would keep every chunk alive.
+            survivors.extend(keys.into_iter().step_by(10));
+        }
+        let live_bytes: usize = survivors.iter().map(|key| key.parts().0.len()).sum();
+        assert!(arena.chunk_bytes <= 2 * live_bytes + ARENA_CHUNK_SIZE);
+        assert_eq!(survivors[9999], key(99, 990));
+
+        drop(survivors);
+        let keys: Vec<_> = (0..100).map(|i| arena.store(&key(100, i), 0)).collect();
+        assert!(matches!(keys[99].data, Data::Arena { .. }));
+        assert_eq!(arena.chunk_bytes, ARENA_CHUNK_SIZE);
+    }
+
+    #[test]
+    fn interned_keys_share_prefix() {
+        let key = |pk: &str, ck: i64| {
+            InvariantKey::new(vec![CqlValue::Text(pk.repeat(32)), CqlValue::BigInt(ck)])
+        };
+        let saved_bytes = Gauge::new("saved_bytes", "saved bytes").unwrap();
+        let mut arena = KeyArena::default().with_interning(saved_bytes.clone());
+        let hasher = RandomState::new();
+
+        let keys: Vec<_> = [("a", 1), ("a", 2), ("b", 1)]
+            .into_iter()
+            .map(|(pk, ck)| (key(pk, ck), arena.store(&key(pk, ck), 1)))
+            .collect();
+
+        let prefix = |key: &InvariantKey| {
+            let Data::Interned { prefix, .. } = &key.data else {
+                panic!("key should be interned");
+            };
+            Arc::clone(prefix)
+        };
+        assert!(Arc::ptr_eq(&prefix(&keys[0].1), &prefix(&keys[1].1)));
+        assert!(!Arc::ptr_eq(&prefix(&keys[0].1), &prefix(&keys[2].1)));
+        for (original, interned) in &keys {
+            assert_eq!(interned, original);
+            assert_eq!(interned.cmp(original), std::cmp::Ordering::Equal);
+            assert_eq!(
+                BuildHasher::hash_one(&hasher, interned),
+                BuildHasher::hash_one(&hasher, original)
+            );
+            assert_eq!(
+                interned.iter().collect::<Vec<_>>(),
+                original.iter().collect::<Vec<_>>()
+            );
+            assert_eq!(interned.get(1), original.get(1));
+        }
+        assert!(keys[0].1 < keys[1].1 && keys[1].1 < keys[2].1);
+        // 2 keys sharing a prefix of 38 bytes
+        assert_eq!(saved_bytes.get(), 38.0);
+        assert_eq!(arena.memory_bytes(), 2 * 38);
+
+        // a long rest of a key, or a key without the rest, is not interned
+        let long = InvariantKey::new(vec![
+            CqlValue::Text("a".repeat(32)),
+            CqlValue::Uuid(Uuid::new_v4()),
+        ]);
+        assert!(matches!(arena.store(&long, 1).data, Data::Arena { .. }));
+        assert!(matches!(
+            arena.store(&key("a", 1), 2).data,
+            Data::Arena { .. }
+        ));
+    }
+
+    #[test]
+    fn interned_prefixes_of_dropped_keys_are_forgotten() {
+        let key = |pk: &str, ck: i64| {
+            InvariantKey::new(vec![CqlValue::Text(pk.repeat(32)), CqlValue::BigInt(ck)])
+        };
+        let saved_bytes = Gauge::new("saved_bytes", "saved bytes").unwrap();
+        let mut arena = KeyArena::default().with_interning(saved_bytes.clone());
+        let a: Vec<_> = (0..10).map(|ck| arena.store(&key("a", ck), 1)).collect();
+        let _b: Vec<_> = (0..10).map(|ck| arena.store(&key("b", ck), 1)).collect();
+        assert_eq!(saved_bytes.get(), (2 * 9 * 38) as f64);
+        assert_eq!(arena.memory_bytes(), 2 * 38);
+
+        drop(a);
+        let _c: Vec<_> = (0..10).map(|ck| arena.store(&key("c", ck), 1)).collect();
+        let prefixes = &arena.interner.as_ref().unwrap().prefixes;
+        assert!(!prefixes.contains_key(&key("a", 0).parts().0[..38]));
+        assert_eq!(prefixes.len(), 2);
+        assert_eq!(arena.memory_bytes(), 2 * 38);
+        assert_eq!(saved_bytes.get(), (2 * 9 * 38) as f64);
+    }
+
+    #[test]
+    fn mapped_arena_stores_keys_in_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = |idx: i32| InvariantKey::new(vec![CqlValue::Text(format!("{idx:032}"))]);
+        let mut arena = KeyArena::mapped(dir.path()).unwrap();
+
+        let keys: Vec<_> = (0..1000).map(|idx| arena.store(&key(idx), 0)).collect();
+
+        let Data::Arena { chunk, .. } = &keys[0].data else {
+            panic!("key should be stored in the arena");
+        };
+        assert!(matches!(**chunk, ArenaChunk::Mapped { .. }));
+        assert_eq!(
+            arena.file.as_ref().unwrap().metadata().unwrap().len(),
+            ARENA_MAPPED_CHUNK_SIZE as u64
+        );
+        assert_eq!(arena.memory_bytes(), 0);
+        drop(arena);
+        for (idx, stored) in keys.iter().enumerate() {
+            assert_eq!(*stored, key(idx as i32));
+        }
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn mapped_arena_allocates_chunks_and_reuses_freed_ones() {
+        use std::os::unix::fs::MetadataExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let key = InvariantKey::new(vec![CqlValue::Text("a".repeat(32))]);
+        let mut arena = KeyArena::mapped(dir.path()).unwrap();
+        let metadata = |arena: &KeyArena| arena.file.as_ref().unwrap().metadata().unwrap();
+
+        let stored = arena.store(&key, 0);
+        assert!(metadata(&arena).blocks() * 512 >= ARENA_MAPPED_CHUNK_SIZE as u64);
+
+        drop(stored);
+        assert!(arena.retire_chunk());
+        let stored = arena.store(&key, 0);
+        let Data::Arena { chunk, .. } = &stored.data else {
+            panic!("key should be stored in the arena");
+        };
+        assert!(matches!(**chunk, ArenaChunk::Mapped { offset: 0, .. }));
+        assert_eq!(metadata(&arena).len(), ARENA_MAPPED_CHUNK_SIZE as u64);
+        assert_eq!(stored, key);
     }
 
     #[test]
@@ -3,19 +3,32 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
+pub mod actor_trace;
 mod async_in_progress;
+mod builder;
+mod cluster;
 mod config_manager;
+mod consul;
 pub mod db;
 mod db_cdc;
 pub mod db_index;
 mod db_index_backend;
 mod distance;
+mod drift;
+mod embedding;
 mod engine;
 mod file_monitor;
+mod forward;
 mod fts_index;
+#[cfg(feature = "grpc")]
+mod grpcserver;
+mod http_auth;
+mod http_limits;
 mod httproutes;
 mod httpserver;
+mod index_handle;
 mod index_key;
+mod index_versions;
 mod indexes;
 mod info;
 mod internals;
@@ -29,25 +42,48 @@ mod nonempty;
 mod partition_key;
 mod perf;
 mod primary_key;
+mod rerank;
+mod rescore;
+mod serde_duration;
+mod settings;
 mod similarity;
+mod systemd;
 mod table;
 mod timestamp;
 pub mod tls;
+mod token_ranges;
 mod vector;
 mod vs_index;
 mod worker;
 
+pub use crate::builder::VectorStore;
+pub use crate::builder::VectorStoreBuilder;
+pub use crate::cluster::ClusterConfig;
+pub use crate::cluster::ClusterNode;
+pub use crate::cluster::Membership;
+pub use crate::cluster::NodeId;
 pub use crate::config_manager::ConfigManager;
 pub use crate::config_manager::ConfigReceivers;
 pub use crate::config_manager::HttpServerConfig;
 pub use crate::config_manager::load_config;
+pub use crate::consul::ConsulConfig;
 pub use crate::distance::Distance;
+use crate::embedding::Embedder;
+pub use crate::embedding::EmbeddingConfig;
+pub use crate::embedding::EmbeddingProvider;
+pub use crate::engine::EngineHandle;
+pub use crate::http_auth::AuthConfig;
 pub use crate::httpserver::HttpServer;
 pub use crate::httpserver::HttpServerExt;
+pub use crate::index_handle::IndexError;
+pub use crate::index_handle::IndexHandle;
 pub use crate::index_key::IndexKey;
 use crate::indexes::Indexes;
 pub use crate::info::Info;
 use crate::internals::Internals;
+pub use crate::memory::Allocate;
+pub use crate::memory::Memory;
+pub use crate::memory::MemoryUsage;
 use crate::metrics::Metrics;
 use crate::node_state::NodeState;
 pub use crate::nonempty::NonemptyArc;
@@ -55,11 +91,22 @@ pub use crate::nonempty::NonemptyBox;
 pub use crate::nonempty::NonemptyIteratorExt;
 pub use crate::partition_key::PartitionKey;
 pub use crate::primary_key::PrimaryKey;
+pub use crate::rerank::RerankConfig;
+pub use crate::rerank::RerankProvider;
+use crate::rerank::Reranker;
+pub use crate::settings::SETTINGS;
+pub use crate::settings::Setting;
 pub use crate::similarity::SimilarityScore;
+pub use crate::table::IndexId;
 pub use crate::table::PartitionId;
 pub use crate::table::PrimaryId;
+pub use crate::table::Table;
+pub use crate::table::TableSearch;
 pub use crate::timestamp::Timestamp;
 pub use crate::timestamp::Timestamped;
+pub use crate::token_ranges::TokenOwnership;
+pub use crate::token_ranges::TokenRanges;
+use anyhow::bail;
 use db::Db;
 use scylla::cluster::metadata::ColumnType;
 use scylla::serialize::SerializationError;
@@ -70,7 +117,9 @@ use scylla::value::CqlValue;
 use scylla_cdc::CqlIdentifier;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::iter;
 use std::net::SocketAddr;
+use std::num::NonZeroU32;
 use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -83,6 +132,15 @@ use tokio::sync::watch;
 use utoipa::openapi::OpenApi;
 use uuid::Uuid;
 pub use vector::Vector;
+pub use vs_index::actor::AnnR;
+pub use vs_index::actor::Centroid;
+pub use vs_index::actor::CountR;
+pub use vs_index::actor::DuplicatesR;
+pub use vs_index::actor::KMeansR;
+pub use vs_index::actor::VsIndex;
+pub use vs_index::actor::VsStats;
+pub use vs_index::actor::VsStatsR;
+pub use vs_index::factory::VsIndexConfiguration;
 pub use vs_index::factory::VsIndexFactory;
 
 /// A CQL string literal that is always properly single-quoted when formatted
@@ -158,7 +216,8 @@ impl std::fmt::Display for TableIdentifier {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "f32", into = "f32")]
 pub struct DiskannAlpha(f32);
 
 impl DiskannAlpha {
@@ -177,58 +236,164 @@ impl DiskannAlpha {
     }
 }
 
-#[derive(Clone, Debug)]
+impl TryFrom<f32> for DiskannAlpha {
+    type Error = String;
+
+    fn try_from(value: f32) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl From<DiskannAlpha> for f32 {
+    fn from(value: DiskannAlpha) -> Self {
+        value.get()
+    }
+}
+
+/// All settings of the service. Durations are (de)serialized in the human readable format,
+/// e.g. `100ms`. Credentials are never (de)serialized.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     pub vector_store_addr: std::net::SocketAddr,
+    /// Addresses the HTTP server listens on in addition to `vector_store_addr`.
+    pub vector_store_extra_addrs: Vec<SocketAddr>,
     pub scylladb_uri: String,
     pub threads: Option<usize>,
     pub memory_limit: Option<u64>,
+    #[serde(with = "serde_duration::option")]
     pub memory_usage_check_interval: Option<Duration>,
+    /// Directory of the files the primary keys of indexes are mapped from. Keys are held in
+    /// memory when not set.
+    pub key_storage_dir: Option<std::path::PathBuf>,
+    /// Whether partition key columns shared by primary keys are stored once per index.
+    pub key_interning: bool,
+    /// Directory of the files the full precision vectors of quantized indexes are spilled to, to
+    /// rescore their search results. Quantized indexes are not rescored when not set.
+    pub vector_spill_dir: Option<std::path::PathBuf>,
     pub opensearch_addr: Option<String>,
+    /// k-NN engine of the indexes created in OpenSearch, like `faiss` or `lucene`. The default
+    /// engine of the OpenSearch cluster is used when not set.
+    pub opensearch_engine: Option<String>,
+    /// Description of the FAISS indexes, like `HNSW32` or `IVF1024,Flat`, selecting the FAISS
+    /// index backend.
+    pub faiss_index: Option<String>,
+    /// Indexes up to this number of rows are searched exactly by the flat index backend, and are
+    /// moved to the selected backend when they grow over it.
+    pub flat_index_max_rows: Option<NonZeroUsize>,
+    #[serde(skip)]
     pub credentials: Option<Credentials>,
+    #[serde(with = "serde_duration::option_vec")]
     pub usearch_simulator: Option<Vec<Duration>>,
     pub diskann_alpha: Option<DiskannAlpha>,
     pub use_diskann: bool,
     pub alter_index_simulator: bool,
     pub fulltext_indexes: bool,
+    pub actor_tracing: bool,
+    pub keyspace_partitions: HashMap<KeyspaceName, KeyspacePartition>,
+    pub index_limits: IndexLimits,
+    /// Clustering mode, where each index is built only by its owner node.
+    pub cluster: Option<ClusterConfig>,
+    /// Registration of the node in the Consul service catalog.
+    pub consul: Option<ConsulConfig>,
+    /// External provider embedding the texts of ANN queries.
+    pub embedding: Option<EmbeddingConfig>,
+    /// Cross-encoder model reranking the results of ANN queries.
+    pub rerank: Option<RerankConfig>,
+    /// Capacity of actor channels per runtime worker thread.
+    pub channel_size_per_worker: NonZeroUsize,
+    /// How often full-text indexes commit written documents.
+    #[serde(with = "serde_duration")]
+    pub fts_commit_interval: Duration,
+    /// Number of uncommitted documents after which a full-text index commits immediately.
+    pub fts_max_uncommitted_docs: NonZeroUsize,
+    /// Node status at which systemd is notified that the service is ready.
+    pub systemd_ready_status: node_state::NodeStatus,
+    #[serde(with = "serde_duration::option")]
     pub cql_connection_timeout: Option<Duration>,
+    #[serde(with = "serde_duration::option")]
     pub cql_keepalive_interval: Option<Duration>,
+    #[serde(with = "serde_duration::option")]
     pub cql_keepalive_timeout: Option<Duration>,
+    #[serde(with = "serde_duration::option")]
     pub cql_tcp_keepalive_interval: Option<Duration>,
     pub cql_uri_translation_map: Option<HashMap<SocketAddr, SocketAddr>>,
+    #[serde(with = "serde_duration::option")]
     pub cdc_safety_interval: Option<Duration>,
+    #[serde(with = "serde_duration::option")]
     pub cdc_sleep_interval: Option<Duration>,
+    #[serde(with = "serde_duration::option")]
     pub cdc_fine_safety_interval: Option<Duration>,
+    #[serde(with = "serde_duration::option")]
     pub cdc_fine_sleep_interval: Option<Duration>,
+    /// CDC readers of indexes not reading the log by both of them.
+    pub cdc_modes: HashMap<IndexKey, CdcMode>,
+    /// Token ranges of the rows indexed by the node, which indexes whole tables when not set.
+    pub token_ownership: Option<TokenOwnership>,
+    #[serde(with = "serde_duration::option")]
     pub monitor_indexes_interval: Option<Duration>,
+    #[serde(with = "serde_duration::option")]
     pub engine_status_update_interval: Option<Duration>,
+    /// How long the node keeps serving after a shutdown signal while reported not ready.
+    #[serde(with = "serde_duration::option")]
+    pub shutdown_delay: Option<Duration>,
     pub disable_colors: bool,
     pub tls_cert_path: Option<std::path::PathBuf>,
     pub tls_key_path: Option<std::path::PathBuf>,
     pub mtls_addr: SocketAddr,
+    /// Addresses the mTLS HTTP server listens on in addition to `mtls_addr`.
+    pub mtls_extra_addrs: Vec<SocketAddr>,
     pub mtls_ca_cert_path: Option<std::path::PathBuf>,
+    pub http_tuning: HttpTuning,
+    /// Authentication of the requests of the HTTP API, which is open when not set.
+    #[serde(skip)]
+    pub auth: Option<AuthConfig>,
+    /// Address of the gRPC API, which is not served when not set.
+    pub grpc_addr: Option<SocketAddr>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
             vector_store_addr: "127.0.0.1:6080".parse().unwrap(),
+            vector_store_extra_addrs: Vec::new(),
             scylladb_uri: "127.0.0.1:9042".to_string(),
             threads: None,
             memory_limit: None,
             memory_usage_check_interval: None,
+            key_storage_dir: None,
+            key_interning: false,
+            vector_spill_dir: None,
             opensearch_addr: None,
+            opensearch_engine: None,
+            faiss_index: None,
+            flat_index_max_rows: None,
             credentials: None,
             usearch_simulator: None,
             diskann_alpha: None,
             use_diskann: false,
             alter_index_simulator: false,
             fulltext_indexes: true,
+            actor_tracing: false,
+            keyspace_partitions: HashMap::new(),
+            index_limits: IndexLimits::default(),
+            cluster: None,
+            consul: None,
+            embedding: None,
+            rerank: None,
+            channel_size_per_worker: NonZeroUsize::new(3).unwrap(),
+            fts_commit_interval: Duration::from_secs(3),
+            fts_max_uncommitted_docs: NonZeroUsize::new(10_000).unwrap(),
+            systemd_ready_status: node_state::NodeStatus::Serving,
             disable_colors: false,
             tls_cert_path: None,
             tls_key_path: None,
             mtls_addr: "127.0.0.1:6081".parse().unwrap(),
+            mtls_extra_addrs: Vec::new(),
+            http_tuning: HttpTuning::default(),
+            auth: None,
             mtls_ca_cert_path: None,
+            grpc_addr: None,
             cql_connection_timeout: None,
             cql_keepalive_interval: None,
             cql_keepalive_timeout: None,
@@ -238,17 +403,259 @@ impl Default for Config {
             cdc_sleep_interval: None,
             cdc_fine_safety_interval: None,
             cdc_fine_sleep_interval: None,
+            cdc_modes: HashMap::new(),
+            token_ownership: None,
             monitor_indexes_interval: None,
             engine_status_update_interval: None,
+            shutdown_delay: None,
+        }
+    }
+}
+
+/// Rejects listening twice on the same address. Port 0 binds a random port, so it may repeat.
+fn check_unique_addrs(server: &str, addrs: &[SocketAddr]) -> anyhow::Result<()> {
+    for (i, addr) in addrs.iter().enumerate() {
+        if addr.port() != 0 && addrs[..i].contains(addr) {
+            bail!("{server} address {addr} is listed more than once");
+        }
+    }
+    Ok(())
+}
+
+impl Config {
+    /// Checks that the settings are within their valid ranges and consistent with each other.
+    pub fn validate(&self) -> anyhow::Result<()> {
+        match (&self.tls_cert_path, &self.tls_key_path) {
+            (Some(_), None) => bail!("TLS cert path is set but key path is missing"),
+            (None, Some(_)) => bail!("TLS key path is set but cert path is missing"),
+            _ => {}
+        }
+        let http_addrs: Vec<_> = iter::once(self.vector_store_addr)
+            .chain(self.vector_store_extra_addrs.iter().copied())
+            .collect();
+        check_unique_addrs("HTTP server", &http_addrs)?;
+        if self.mtls_ca_cert_path.is_some() {
+            if self.tls_cert_path.is_none() {
+                bail!("mTLS CA certificate path is set, but TLS is not configured");
+            }
+            let mtls_addrs: Vec<_> = iter::once(self.mtls_addr)
+                .chain(self.mtls_extra_addrs.iter().copied())
+                .collect();
+            check_unique_addrs("mTLS server", &mtls_addrs)?;
+            if let Some(addr) = mtls_addrs
+                .iter()
+                .find(|addr| addr.port() != 0 && http_addrs.contains(addr))
+            {
+                bail!("mTLS address {addr} must differ from the HTTP server addresses");
+            }
+        }
+        if self.memory_limit == Some(0) {
+            bail!("memory limit must be greater than 0");
+        }
+        if let Some(cluster) = &self.cluster {
+            cluster.validate()?;
+        }
+        if let Some(consul) = &self.consul {
+            consul.validate()?;
         }
+        if let Some(embedding) = &self.embedding {
+            embedding.validate()?;
+        }
+        if let Some(rerank) = &self.rerank {
+            rerank.validate()?;
+        }
+        for index in self.cdc_modes.keys() {
+            if !index.as_ref().contains('.') {
+                bail!("CDC mode of index {index} must be set for `keyspace.index`");
+            }
+        }
+        for (keyspace, partition) in &self.keyspace_partitions {
+            if partition.memory_limit == Some(0) {
+                bail!("memory limit of keyspace {keyspace} must be greater than 0");
+            }
+        }
+        if let Some(delays) = &self.usearch_simulator
+            && delays.len() > 3
+        {
+            bail!(
+                "usearch simulator accepts at most 3 delays, got {}",
+                delays.len()
+            );
+        }
+        // Intervals drive periodic timers, which do not accept a zero period.
+        [
+            (
+                "memory usage check interval",
+                self.memory_usage_check_interval,
+            ),
+            ("CQL connection timeout", self.cql_connection_timeout),
+            ("CQL keepalive interval", self.cql_keepalive_interval),
+            ("CQL keepalive timeout", self.cql_keepalive_timeout),
+            (
+                "CQL TCP keepalive interval",
+                self.cql_tcp_keepalive_interval,
+            ),
+            ("CDC sleep interval", self.cdc_sleep_interval),
+            ("CDC fine sleep interval", self.cdc_fine_sleep_interval),
+            ("monitor indexes interval", self.monitor_indexes_interval),
+            (
+                "index status update interval",
+                self.engine_status_update_interval,
+            ),
+            ("full-text commit interval", Some(self.fts_commit_interval)),
+            (
+                "HTTP header read timeout",
+                self.http_tuning.header_read_timeout,
+            ),
+            (
+                "HTTP/2 keep-alive interval",
+                self.http_tuning.http2_keep_alive_interval,
+            ),
+            (
+                "HTTP/2 keep-alive timeout",
+                self.http_tuning.http2_keep_alive_timeout,
+            ),
+        ]
+        .into_iter()
+        .try_for_each(|(name, interval)| {
+            if interval == Some(Duration::ZERO) {
+                bail!("{name} must be greater than 0");
+            }
+            Ok(())
+        })
     }
 }
 
+/// Connection handling of the HTTP servers. Unset values use the hyper defaults.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HttpTuning {
+    /// Accept HTTP/2 connections next to HTTP/1.
+    pub http2: bool,
+    /// Keep HTTP/1 connections open between requests.
+    pub keep_alive: bool,
+    /// Time to receive request headers, also bounding idle HTTP/1 keep-alive connections.
+    #[serde(with = "serde_duration::option")]
+    pub header_read_timeout: Option<Duration>,
+    /// Interval of HTTP/2 pings on idle connections.
+    #[serde(with = "serde_duration::option")]
+    pub http2_keep_alive_interval: Option<Duration>,
+    /// Time to wait for an HTTP/2 ping acknowledgement before closing the connection.
+    #[serde(with = "serde_duration::option")]
+    pub http2_keep_alive_timeout: Option<Duration>,
+    pub http2_max_concurrent_streams: Option<NonZeroU32>,
+    /// Open connections per server; connections over the limit are closed when accepted.
+    pub max_connections: Option<NonZeroUsize>,
+    /// Requests processed at once per server; requests over the limit get `503`.
+    pub max_concurrent_requests: Option<NonZeroUsize>,
+}
+
+impl Default for HttpTuning {
+    fn default() -> Self {
+        Self {
+            http2: true,
+            keep_alive: true,
+            header_read_timeout: None,
+            http2_keep_alive_interval: None,
+            http2_keep_alive_timeout: None,
+            http2_max_concurrent_streams: None,
+            max_connections: None,
+            max_concurrent_requests: None,
+        }
+    }
+}
+
+/// Upper bounds of vector index parameters, checked when an index is discovered. An index over
+/// the limits is reported as failed instead of being built.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IndexLimits {
+    pub max_dimensions: Option<NonZeroUsize>,
+    pub max_connectivity: Option<NonZeroUsize>,
+    pub max_expansion_add: Option<NonZeroUsize>,
+    pub max_expansion_search: Option<NonZeroUsize>,
+}
+
+impl IndexLimits {
+    /// Fails with the first parameter of the index which exceeds its limit.
+    pub(crate) fn check(&self, options: &IndexOptionsVs) -> anyhow::Result<()> {
+        let params = [
+            (
+                "dimensions",
+                options.dimensions.0.get(),
+                self.max_dimensions,
+            ),
+            (
+                "connectivity",
+                options.connectivity.0,
+                self.max_connectivity,
+            ),
+            (
+                "expansion_add",
+                options.expansion_add.0,
+                self.max_expansion_add,
+            ),
+            (
+                "expansion_search",
+                options.expansion_search.0,
+                self.max_expansion_search,
+            ),
+        ];
+        for (name, value, limit) in params {
+            if let Some(limit) = limit
+                && value > limit.get()
+            {
+                bail!("index {name} {value} exceeds the limit of {limit}");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// CDC readers applying the changes of the indexed table to an index after its full scan.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CdcMode {
+    /// The wide-framed reader for consistency and the fine-grained reader for low latency.
+    #[default]
+    Both,
+    /// The wide-framed reader only, applying the changes after its safety interval.
+    Wide,
+    /// The fine-grained reader only, which may miss changes applied by the cluster late.
+    Fine,
+}
+
+impl CdcMode {
+    pub(crate) fn reads_wide(self) -> bool {
+        matches!(self, Self::Both | Self::Wide)
+    }
+
+    pub(crate) fn reads_fine(self) -> bool {
+        matches!(self, Self::Both | Self::Fine)
+    }
+}
+
+/// Resources dedicated to the indexes of a single keyspace, isolating them from other
+/// keyspaces on the same node.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyspacePartition {
+    /// Number of workers building and querying the keyspace indexes. The keyspace uses the
+    /// shared workers if not set.
+    pub workers: Option<NonZeroUsize>,
+    /// Estimated memory in bytes the keyspace indexes may reserve, checked in addition to the
+    /// node memory limit.
+    pub memory_limit: Option<u64>,
+}
+
 #[derive(Clone, Debug)]
 pub struct Credentials {
     pub username: Option<String>,
     pub password: Option<secrecy::SecretString>,
     pub certificate_path: Option<std::path::PathBuf>,
+    /// Certificate authenticating the connections to ScyllaDB, set together with its key.
+    pub client_certificate_path: Option<std::path::PathBuf>,
+    pub client_key_path: Option<std::path::PathBuf>,
 }
 
 #[derive(
@@ -261,9 +668,12 @@ pub struct Credentials {
     derive_more::Display,
     derive_more::From,
     derive_more::Into,
+    serde::Serialize,
+    serde::Deserialize,
 )]
 #[from(String, &String, &str)]
 #[as_ref(str)]
+#[serde(transparent)]
 /// A keyspace name in a db.
 pub struct KeyspaceName(String);
 
@@ -562,7 +972,7 @@ pub struct Filter {
     pub allow_filtering: bool,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, derive_more::From)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, derive_more::From, derive_more::Display)]
 pub struct IndexVersion(Uuid);
 
 impl IndexVersion {
@@ -595,11 +1005,18 @@ pub struct IndexOptionsVs {
     pub expansion_search: ExpansionSearch,
     pub space_type: SpaceType,
     pub quantization: Quantization,
+    /// Model embedding the texts of a text target column.
+    pub embedding_model: Option<String>,
+    /// Time after the write of a row when its entry is removed from the index.
+    pub ttl: Option<Duration>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 /// Full-text-search-specific index configuration.
-pub struct IndexOptionsFts {}
+pub struct IndexOptionsFts {
+    /// Time after the write of a row when its entry is removed from the index.
+    pub ttl: Option<Duration>,
+}
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 /// Discriminates between vector-search and full-text-search index.
@@ -615,6 +1032,13 @@ impl IndexKind {
             IndexKind::Fts(_) => None,
         }
     }
+
+    pub fn ttl(&self) -> Option<Duration> {
+        match self {
+            IndexKind::Vs(vs) => vs.ttl,
+            IndexKind::Fts(fts) => fts.ttl,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -668,6 +1092,8 @@ pub struct DbCustomIndex {
     pub partitioning: DbIndexPartitioning,
     pub filtering_columns: Arc<[ColumnName]>,
     pub kind: DbIndexKind,
+    pub embedding_model: Option<String>,
+    pub ttl: Option<Duration>,
 }
 
 impl DbCustomIndex {
@@ -717,6 +1143,8 @@ pub fn block_on<Output>(threads: Option<usize>, f: impl AsyncFnOnce() -> Output)
         .block_on(async move { f().await })
 }
 
+/// Starts the engine and the HTTP servers using already created actors. Prefer
+/// [`VectorStoreBuilder`] which creates missing components and validates the configuration.
 pub async fn run(
     node_state: Sender<NodeState>,
     db_actor: Sender<Db>,
@@ -725,49 +1153,119 @@ pub async fn run(
     receivers: ConfigReceivers,
     metrics: Arc<Metrics>,
 ) -> anyhow::Result<(Sender<HttpServer>, Sender<HttpServer>)> {
-    let index_engine_version = index_factory.index_engine_version();
-    let indexes = Arc::new(RwLock::new(Indexes::new()));
-    let fts_index_factory: Box<dyn fts_index::FtsIndexFactory + Send + Sync> =
-        Box::new(fts_index::TantivyIndexFactory::new());
-    let engine = engine::new(
+    let grpc_addr = receivers.config.borrow().grpc_addr;
+    let engine = new_engine(
+        node_state,
         db_actor,
-        engine::IndexFactories {
-            vs: index_factory,
-            fts: fts_index_factory,
-        },
-        node_state.clone(),
-        metrics.clone(),
-        Arc::clone(&indexes),
         internals.clone(),
+        index_factory,
         receivers.config,
-    )
-    .await?;
-
-    let main = httpserver::new(
-        node_state.clone(),
-        Arc::clone(&indexes),
-        engine.clone(),
         metrics.clone(),
-        internals.clone(),
-        index_engine_version.clone(),
-        receivers.http,
     )
     .await?;
-
-    let mtls = httpserver::new(
-        node_state,
-        indexes,
-        engine,
-        metrics,
+    run_grpc_server(&engine, metrics.clone(), grpc_addr).await?;
+    run_http_servers(
+        &engine,
         internals,
-        index_engine_version,
+        metrics,
+        receivers.http,
         receivers.mtls_http,
     )
+    .await
+}
+
+/// Starts only the engine together with the db and monitor actors, without binding any listener.
+/// The returned handle is used for in-process access to the indexes.
+pub async fn new_engine(
+    node_state: Sender<NodeState>,
+    db_actor: Sender<Db>,
+    internals: Sender<Internals>,
+    index_factory: Box<dyn VsIndexFactory + Send + Sync>,
+    config_rx: watch::Receiver<Arc<Config>>,
+    metrics: Arc<Metrics>,
+) -> anyhow::Result<EngineHandle> {
+    let index_engine_version = index_factory.index_engine_version();
+    let indexes = Arc::new(RwLock::new(Indexes::new()));
+    let fts_index_factory: Box<dyn fts_index::FtsIndexFactory + Send + Sync> = {
+        let config = config_rx.borrow();
+        perf::set_channel_size_per_worker(config.channel_size_per_worker);
+        Box::new(fts_index::TantivyIndexFactory::new(
+            config.fts_commit_interval,
+            config.fts_max_uncommitted_docs.get(),
+        ))
+    };
+    let (membership, gossip) = cluster::new(config_rx.clone());
+    let embedder = config_rx
+        .borrow()
+        .embedding
+        .clone()
+        .map(|config| Embedder::new(config, Arc::clone(&metrics)));
+    let reranker = config_rx
+        .borrow()
+        .rerank
+        .clone()
+        .map(|config| Reranker::new(config, db_actor.clone()));
+    let engine = engine::new(
+        engine::EngineDeps {
+            db: db_actor,
+            index_factories: engine::IndexFactories {
+                vs: index_factory,
+                fts: fts_index_factory,
+            },
+            embedder: embedder.clone(),
+            node_state: node_state.clone(),
+            metrics,
+            indexes: Arc::clone(&indexes),
+            internals,
+        },
+        config_rx.clone(),
+        membership.clone(),
+    )
     .await?;
+    Ok(EngineHandle {
+        engine,
+        indexes,
+        node_state,
+        index_engine_version,
+        membership,
+        gossip,
+        embedder,
+        reranker,
+        config_rx,
+    })
+}
+
+async fn run_http_servers(
+    engine: &EngineHandle,
+    internals: Sender<Internals>,
+    metrics: Arc<Metrics>,
+    http_rx: watch::Receiver<Option<Arc<HttpServerConfig>>>,
+    mtls_http_rx: watch::Receiver<Option<Arc<HttpServerConfig>>>,
+) -> anyhow::Result<(Sender<HttpServer>, Sender<HttpServer>)> {
+    let main = httpserver::new(engine.clone(), metrics.clone(), internals.clone(), http_rx).await?;
+    let mtls = httpserver::new(engine.clone(), metrics, internals, mtls_http_rx).await?;
 
     Ok((main, mtls))
 }
 
+/// Starts the gRPC server if its address is configured, returning the address it listens on.
+async fn run_grpc_server(
+    engine: &EngineHandle,
+    metrics: Arc<Metrics>,
+    addr: Option<SocketAddr>,
+) -> anyhow::Result<Option<SocketAddr>> {
+    let Some(addr) = addr else {
+        return Ok(None);
+    };
+    #[cfg(feature = "grpc")]
+    return grpcserver::start(engine, metrics, addr).await.map(Some);
+    #[cfg(not(feature = "grpc"))]
+    {
+        let _ = (engine, metrics);
+        bail!("gRPC API is not compiled in, enable the `grpc` feature to listen on {addr}");
+    }
+}
+
 pub async fn new_db(
     node_state: Sender<NodeState>,
     internals: Sender<Internals>,
@@ -795,6 +1293,7 @@ pub fn new_index_factory_usearch(
     Ok(Box::new(vs_index::usearch::new_usearch(config_tx)?))
 }
 
+#[cfg(feature = "opensearch")]
 pub fn new_index_factory_opensearch(
     addr: String,
     config_rx: watch::Receiver<Arc<Config>>,
@@ -804,6 +1303,21 @@ pub fn new_index_factory_opensearch(
     )?))
 }
 
+#[cfg(feature = "faiss")]
+pub fn new_index_factory_faiss(
+    description: String,
+) -> anyhow::Result<Box<dyn VsIndexFactory + Send + Sync>> {
+    Ok(Box::new(vs_index::faiss::new_faiss(description)?))
+}
+
+pub fn new_index_factory_flat(
+    inner: Box<dyn VsIndexFactory + Send + Sync>,
+    max_rows: NonZeroUsize,
+    config_rx: watch::Receiver<Arc<Config>>,
+) -> Box<dyn VsIndexFactory + Send + Sync> {
+    Box::new(vs_index::flat::new_flat(inner, max_rows, config_rx))
+}
+
 pub fn new_index_factory_diskann(
     config_rx: watch::Receiver<Arc<Config>>,
 ) -> anyhow::Result<Box<dyn VsIndexFactory + Send + Sync>> {
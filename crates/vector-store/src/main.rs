@@ -7,30 +7,75 @@
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 use anyhow::anyhow;
-use clap::Parser;
+use anyhow::bail;
+use clap::Arg;
+use clap::ArgMatches;
+use clap::Command;
+use clap::parser::ValueSource;
 use rustls::crypto::aws_lc_rs;
+use std::collections::HashMap;
 use std::io::IsTerminal;
+use std::sync::Arc;
 use tracing_subscriber::EnvFilter;
 use tracing_subscriber::fmt;
 use tracing_subscriber::prelude::*;
-use vector_store::ConfigManager;
-use vector_store::HttpServerExt;
 use vector_store::Info;
+use vector_store::SETTINGS;
+use vector_store::VectorStoreBuilder;
+
+/// Builds the command line from [`SETTINGS`]. Values of the flags are passed to
+/// [`vector_store::load_config`] under their environment variable names and validated there.
+fn command() -> Command {
+    Command::new(env!("CARGO_PKG_NAME"))
+        .version(env!("CARGO_PKG_VERSION"))
+        .about(
+            "Every setting can be given as a command line flag or as an environment variable \
+            (also from a `.env` file). Flags take precedence over environment variables and are \
+            kept when the configuration is reloaded on SIGHUP.",
+        )
+        .args(SETTINGS.iter().map(|setting| {
+            Arg::new(setting.env)
+                .long(setting.flag)
+                .env(setting.env)
+                .value_name(setting.value_name)
+                .help(setting.help)
+        }))
+}
 
-#[derive(Parser)]
-#[clap(version)]
-struct Args {}
+/// Returns values of flags given on the command line keyed by their environment variable names.
+fn command_line_values(matches: &ArgMatches) -> HashMap<String, String> {
+    SETTINGS
+        .iter()
+        .filter(|setting| matches.value_source(setting.env) == Some(ValueSource::CommandLine))
+        .filter_map(|setting| {
+            let value = matches.get_raw(setting.env)?.next()?.to_str()?.to_string();
+            Some((setting.env.to_string(), value))
+        })
+        .collect()
+}
 
-fn dotenvy_to_std_var(key: &str) -> anyhow::Result<String> {
-    Ok(dotenvy::var(key)?)
+/// Looks up a setting in command line flags first and then in environment variables.
+fn setting(
+    command_line: Arc<HashMap<String, String>>,
+) -> impl Fn(&str) -> anyhow::Result<String> + Clone + Send + Sync + 'static {
+    move |key| match command_line.get(key) {
+        Some(value) => Ok(value.clone()),
+        None => Ok(dotenvy::var(key)?),
+    }
 }
 
 // Index creating/querying is CPU bound task, so that vector-store uses rayon ThreadPool for them.
 // From the start there was no need (network traffic seems to be not so high) to support more than
 // one thread per network IO bound tasks.
 fn main() -> anyhow::Result<()> {
+    // Load .env before parsing, so that --help shows its values next to the flags
+    _ = dotenvy::dotenv();
+
+    let matches = command().get_matches();
+    let setting = setting(Arc::new(command_line_values(&matches)));
+
     // Initialize logging early, before loading configuration, disable colors will be read twice
-    let disable_colors: bool = dotenvy::var("VECTOR_STORE_DISABLE_COLORS")
+    let disable_colors: bool = setting("VECTOR_STORE_DISABLE_COLORS")
         .unwrap_or("false".to_string())
         .trim()
         .parse()
@@ -51,70 +96,37 @@ fn main() -> anyhow::Result<()> {
         .install_default()
         .expect("install aws-lc-rs crypto provider");
 
-    _ = dotenvy::dotenv();
-
-    // Load configuration early to get disable_colors for logging setup
-    let config_future = vector_store::load_config(dotenvy_to_std_var);
+    let config_future = vector_store::load_config(setting.clone());
     let loaded_config = tokio::runtime::Builder::new_current_thread()
         .enable_all()
         .build()
         .unwrap()
         .block_on(config_future)?;
 
-    _ = Args::parse();
-
     tracing::info!("Starting {} version {}", Info::name(), Info::version());
 
-    // Create ConfigManager with initial configuration
     let threads = loaded_config.threads;
+    let shutdown_delay = loaded_config.shutdown_delay;
 
     vector_store::block_on(threads, async move || {
-        let (config_manager, config_receivers) = ConfigManager::new(loaded_config).await?;
-        // Start SIGHUP handler now that we're in the Tokio runtime
-        config_manager.start(dotenvy_to_std_var);
-
-        let node_state = vector_store::new_node_state().await;
-
-        let config_rx = config_receivers.config.clone();
-        let opensearch_addr = config_rx.borrow().opensearch_addr.clone();
-        let use_diskann = config_rx.borrow().use_diskann;
-
-        let index_factory = if let Some(addr) = opensearch_addr {
-            tracing::info!("Using OpenSearch index factory at {addr}");
-            vector_store::new_index_factory_opensearch(addr, config_rx.clone())?
-        } else if use_diskann {
-            tracing::info!("Using DiskANN index factory");
-            vector_store::new_index_factory_diskann(config_rx.clone())?
-        } else {
-            tracing::info!("Using Usearch index factory");
-            vector_store::new_index_factory_usearch(config_rx.clone())?
-        };
-
-        let internals = vector_store::new_internals();
-        let metrics = vector_store::new_metrics();
-        let db_actor = vector_store::new_db(
-            node_state.clone(),
-            internals.clone(),
-            config_rx,
-            metrics.clone(),
-        )
-        .await?;
-
-        let (server, _mtls) = vector_store::run(
-            node_state,
-            db_actor,
-            internals,
-            index_factory,
-            config_receivers,
-            metrics,
-        )
-        .await?;
-        let addr = (*server.address().await.borrow())
-            .ok_or_else(|| anyhow!("failed to get server address"))?;
-        tracing::info!("listening on {addr}");
+        let store = VectorStoreBuilder::new(loaded_config)
+            .reload_from_env(setting)
+            .build()
+            .await?;
+        let addrs = store.addrs().await;
+        if addrs.is_empty() {
+            bail!("failed to get server address");
+        }
+        tracing::info!("listening on {addrs:?}");
 
         vector_store::wait_for_shutdown().await;
 
+        store.start_shutdown().await;
+        if let Some(delay) = shutdown_delay {
+            tracing::info!("shutting down in {}", humantime::format_duration(delay));
+            tokio::time::sleep(delay).await;
+        }
+
         anyhow::Ok(())
     })?;
 
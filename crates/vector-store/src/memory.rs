@@ -4,10 +4,15 @@
  */
 
 use crate::Config;
+use crate::IndexKey;
+use crate::KeyspaceName;
 use crate::internals::Internals;
 use crate::internals::InternalsExt;
 use crate::perf;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 use sysinfo::System;
 use tokio::select;
@@ -32,12 +37,83 @@ pub enum Allocate {
 
 pub(crate) type AllocateR = watch::Receiver<Allocate>;
 
+/// Estimated memory in bytes used by the parts of an index.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Primary keys of the indexed rows and the maps of them to the internal ids and, for an index
+    /// with a TTL, to their write timestamps.
+    pub key_maps: u64,
+    /// Vectors and the graph of their neighbours.
+    pub graph: u64,
+    /// Rows read from the db and not applied to the index yet.
+    pub pending_queues: u64,
+}
+
+impl MemoryUsage {
+    pub fn total(&self) -> u64 {
+        self.key_maps
+            .saturating_add(self.graph)
+            .saturating_add(self.pending_queues)
+    }
+}
+
+/// Accounts the memory used by an index. It is shared by the actors of the index, each of them
+/// updating the part it owns.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct IndexMemory(Arc<IndexMemoryParts>);
+
+#[derive(Debug, Default)]
+struct IndexMemoryParts {
+    key_maps: AtomicU64,
+    expirations: AtomicU64,
+    graph: AtomicU64,
+    pending_queues: AtomicU64,
+}
+
+impl IndexMemory {
+    pub(crate) fn set_key_maps(&self, bytes: u64) {
+        self.0.key_maps.store(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_expirations(&self, bytes: u64) {
+        self.0.expirations.store(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_graph(&self, bytes: u64) {
+        self.0.graph.store(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_pending_queues(&self, bytes: u64) {
+        self.0.pending_queues.store(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) fn usage(&self) -> MemoryUsage {
+        MemoryUsage {
+            key_maps: self
+                .0
+                .key_maps
+                .load(Ordering::Relaxed)
+                .saturating_add(self.0.expirations.load(Ordering::Relaxed)),
+            graph: self.0.graph.load(Ordering::Relaxed),
+            pending_queues: self.0.pending_queues.load(Ordering::Relaxed),
+        }
+    }
+}
+
 pub enum Memory {
-    SubscribeAllocate { tx: oneshot::Sender<AllocateR> },
+    SubscribeAllocate {
+        tx: oneshot::Sender<AllocateR>,
+    },
+    /// Reports the total memory accounted for an index, zero when the index is dropped.
+    UpdateUsage {
+        key: IndexKey,
+        bytes: u64,
+    },
 }
 
 pub(crate) trait MemoryExt {
     async fn subscribe_allocate(&self) -> AllocateR;
+    async fn update_usage(&self, key: IndexKey, bytes: u64);
 }
 
 impl MemoryExt for mpsc::Sender<Memory> {
@@ -49,6 +125,12 @@ impl MemoryExt for mpsc::Sender<Memory> {
         rx.await
             .expect("MemoryExt::can_allocate: internal actor should send response")
     }
+
+    async fn update_usage(&self, key: IndexKey, bytes: u64) {
+        self.send(Memory::UpdateUsage { key, bytes })
+            .await
+            .expect("MemoryExt::update_usage: internal actor should receive request");
+    }
 }
 
 pub(crate) fn new(
@@ -74,6 +156,8 @@ pub(crate) fn new(
         );
         info!("Memory usage check interval set to {:?}", interval.period());
 
+        let mut usage: HashMap<IndexKey, u64> = HashMap::new();
+
         let (allocate_tx, allocate_rx) = watch::channel(
             can_allocate(&internals, used_memory(&system_info), memory_limit, Allocate::Can).await);
 
@@ -100,8 +184,11 @@ pub(crate) fn new(
                 _ = interval.tick() => {
                     system_info.refresh_memory();
                     let allocate = *allocate_tx.borrow();
+                    // The process memory lags behind the usage accounted by the indexes for
+                    // pages which are reserved but not touched yet.
+                    let used = used_memory(&system_info).max(usage.values().sum());
                     _ = allocate_tx.send(
-                        can_allocate(&internals, used_memory(&system_info), memory_limit, allocate).await);
+                        can_allocate(&internals, used, memory_limit, allocate).await);
                 }
 
                 msg = rx.recv() => {
@@ -115,6 +202,7 @@ pub(crate) fn new(
                                 .unwrap_or_else(|_|
                                     trace!("can_allocate: unable to send response"));
                         }
+                        Memory::UpdateUsage { key, bytes } => update_usage(&mut usage, key, bytes),
                     }
                 }
             }
@@ -126,6 +214,115 @@ pub(crate) fn new(
     tx
 }
 
+/// Creates a memory actor for a keyspace with a dedicated memory limit. The keyspace can
+/// allocate when the node can allocate and the usage reported by the keyspace indexes is below
+/// the keyspace limit.
+pub(crate) fn new_keyspace(
+    keyspace: KeyspaceName,
+    node: mpsc::Sender<Memory>,
+    mut config_rx: watch::Receiver<Arc<Config>>,
+) -> mpsc::Sender<Memory> {
+    let (tx, mut rx) = mpsc::channel(perf::channel_size().into());
+
+    tokio::spawn(
+        {
+            let keyspace = keyspace.clone();
+            async move {
+                debug!("starting");
+
+                let mut node_allocate = node.subscribe_allocate().await;
+                let mut memory_limit =
+                    keyspace_memory_limit(&config_rx.borrow_and_update(), &keyspace);
+                info!("Memory limit for keyspace {keyspace} set to {memory_limit:?}");
+                let mut usage: HashMap<IndexKey, u64> = HashMap::new();
+
+                let (allocate_tx, allocate_rx) = watch::channel(keyspace_allocate(
+                    *node_allocate.borrow_and_update(),
+                    &usage,
+                    memory_limit,
+                ));
+
+                loop {
+                    select! {
+                        Ok(()) = config_rx.changed() => {
+                            let config = config_rx.borrow_and_update().clone();
+                            let memory_limit_new = keyspace_memory_limit(&config, &keyspace);
+                            if memory_limit != memory_limit_new {
+                                memory_limit = memory_limit_new;
+                                info!("Memory limit for keyspace {keyspace} updated to {memory_limit:?}");
+                            }
+                        }
+
+                        Ok(()) = node_allocate.changed() => {}
+
+                        msg = rx.recv() => {
+                            let Some(msg) = msg else {
+                                break;
+                            };
+                            match msg {
+                                Memory::SubscribeAllocate { tx } => {
+                                    tx.send(allocate_rx.clone()).unwrap_or_else(|_| {
+                                        trace!("can_allocate: unable to send response")
+                                    });
+                                }
+                                Memory::UpdateUsage { key, bytes } => {
+                                    update_usage(&mut usage, key.clone(), bytes);
+                                    node.update_usage(key, bytes).await;
+                                }
+                            }
+                        }
+                    }
+
+                    let allocate =
+                        keyspace_allocate(*node_allocate.borrow(), &usage, memory_limit);
+                    allocate_tx.send_if_modified(|current| {
+                        if *current == allocate {
+                            return false;
+                        }
+                        info!("Keyspace {keyspace} memory allocation changed to {allocate:?}");
+                        *current = allocate;
+                        true
+                    });
+                }
+
+                debug!("finished");
+            }
+        }
+        .instrument(debug_span!("memory", "{keyspace}")),
+    );
+
+    tx
+}
+
+fn update_usage(usage: &mut HashMap<IndexKey, u64>, key: IndexKey, bytes: u64) {
+    if bytes == 0 {
+        usage.remove(&key);
+    } else {
+        usage.insert(key, bytes);
+    }
+}
+
+fn keyspace_memory_limit(config: &Config, keyspace: &KeyspaceName) -> Option<u64> {
+    config
+        .keyspace_partitions
+        .get(keyspace)
+        .and_then(|partition| partition.memory_limit)
+}
+
+fn keyspace_allocate(
+    node: Allocate,
+    usage: &HashMap<IndexKey, u64>,
+    memory_limit: Option<u64>,
+) -> Allocate {
+    if node == Allocate::Cannot {
+        return Allocate::Cannot;
+    }
+    match memory_limit {
+        Some(memory_limit) if usage.values().sum::<u64>() >= memory_limit => Allocate::Cannot,
+        _ => Allocate::Can,
+    }
+}
+
 fn available_memory(system_info: &System) -> u64 {
     let cgroup = system_info.cgroup_limits();
 
@@ -186,6 +383,7 @@ async fn can_allocate(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::KeyspacePartition;
 
     #[test]
     fn check_calculate_memory_limit() {
@@ -260,6 +458,79 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn keyspace_memory_limit_is_checked_with_node_limit() {
+        let (node_tx, mut node_rx) = mpsc::channel(10);
+        let (node_allocate_tx, node_allocate_rx) = watch::channel(Allocate::Can);
+        tokio::spawn(async move {
+            while let Some(msg) = node_rx.recv().await {
+                if let Memory::SubscribeAllocate { tx } = msg {
+                    _ = tx.send(node_allocate_rx.clone());
+                }
+            }
+        });
+        let keyspace: KeyspaceName = "tenant".into();
+        let (_config_tx, config_rx) = watch::channel(Arc::new(Config {
+            keyspace_partitions: HashMap::from([(
+                keyspace.clone(),
+                KeyspacePartition {
+                    workers: None,
+                    memory_limit: Some(100),
+                },
+            )]),
+            ..Config::default()
+        }));
+        let memory_actor = new_keyspace(keyspace.clone(), node_tx, config_rx);
+        let mut allocate_rx = memory_actor.subscribe_allocate().await;
+        assert_eq!(*allocate_rx.borrow(), Allocate::Can);
+
+        let key_a = IndexKey::new(&keyspace, &"a".into());
+        let key_b = IndexKey::new(&keyspace, &"b".into());
+        memory_actor.update_usage(key_a, 60).await;
+        memory_actor.update_usage(key_b.clone(), 60).await;
+        allocate_rx
+            .wait_for(|allocate| *allocate == Allocate::Cannot)
+            .await
+            .unwrap();
+
+        memory_actor.update_usage(key_b, 0).await;
+        allocate_rx
+            .wait_for(|allocate| *allocate == Allocate::Can)
+            .await
+            .unwrap();
+
+        node_allocate_tx.send(Allocate::Cannot).unwrap();
+        allocate_rx
+            .wait_for(|allocate| *allocate == Allocate::Cannot)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn node_memory_limit_is_checked_with_accounted_usage() {
+        let (_config_tx, config_rx) = watch::channel(Arc::new(Config {
+            memory_usage_check_interval: Some(Duration::from_millis(1)),
+            ..Config::default()
+        }));
+        let (tx, _rx) = mpsc::channel(100);
+        let memory_actor = new(tx, config_rx);
+        let mut allocate_rx = memory_actor.subscribe_allocate().await;
+        assert_eq!(*allocate_rx.borrow(), Allocate::Can);
+
+        let key = IndexKey::new(&"ks".into(), &"idx".into());
+        memory_actor.update_usage(key.clone(), u64::MAX / 2).await;
+        allocate_rx
+            .wait_for(|allocate| *allocate == Allocate::Cannot)
+            .await
+            .unwrap();
+
+        memory_actor.update_usage(key, 0).await;
+        allocate_rx
+            .wait_for(|allocate| *allocate == Allocate::Can)
+            .await
+            .unwrap();
+    }
+
     #[tokio::test]
     #[tracing_test::traced_test]
     async fn check_configuration_change() {
@@ -2,6 +2,7 @@
  * Copyright 2025-present ScyllaDB
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
+use crate::MemoryUsage;
 use dashmap::DashSet;
 use prometheus::CounterVec;
 use prometheus::GaugeVec;
@@ -14,6 +15,19 @@ pub const OP_UPDATE: &str = "update";
 pub const OP_REMOVE: &str = "remove";
 pub const OPERATIONS: &[&str] = &[OP_INSERT, OP_UPDATE, OP_REMOVE];
 
+pub const PART_KEY_MAPS: &str = "key_maps";
+pub const PART_GRAPH: &str = "graph";
+pub const PART_PENDING_QUEUES: &str = "pending_queues";
+pub const MEMORY_PARTS: &[&str] = &[PART_KEY_MAPS, PART_GRAPH, PART_PENDING_QUEUES];
+
+pub const VECTORS_INGESTED: &str = "ingested";
+pub const VECTORS_QUERIED: &str = "queried";
+pub const DRIFT_VECTORS: &[&str] = &[VECTORS_INGESTED, VECTORS_QUERIED];
+
+pub const DRIFT_NORM: &str = "norm";
+pub const DRIFT_DIRECTION: &str = "direction";
+pub const DRIFT_STATISTICS: &[&str] = &[DRIFT_NORM, DRIFT_DIRECTION];
+
 #[derive(Clone)]
 pub struct Metrics {
     pub registry: Registry,
@@ -27,6 +41,16 @@ pub struct Metrics {
     pub cdc_last_processed_timestamp_seconds: GaugeVec,
     pub fts_index_size_bytes: GaugeVec,
     pub fts_segment_count: GaugeVec,
+    pub key_interning_saved_bytes: GaugeVec,
+    pub index_memory_bytes: GaugeVec,
+    pub embedding_drift: GaugeVec,
+    pub http_connections: GaugeVec,
+    pub http_connections_rejected_total: CounterVec,
+    pub http_requests_in_flight: GaugeVec,
+    pub http_requests_rejected_total: CounterVec,
+    pub cluster_index_versions: GaugeVec,
+    pub embedding_cache_hits_total: CounterVec,
+    pub embedding_cache_misses_total: CounterVec,
     dirty_indexes: Arc<DashSet<(String, String)>>,
 }
 
@@ -159,6 +183,96 @@ impl Metrics {
         )
         .unwrap();
 
+        let key_interning_saved_bytes = GaugeVec::new(
+            prometheus::Opts::new(
+                "key_interning_saved_bytes",
+                "Bytes of primary keys saved by interning their partition key columns",
+            ),
+            &["keyspace", "index_name"],
+        )
+        .unwrap();
+
+        let index_memory_bytes = GaugeVec::new(
+            prometheus::Opts::new(
+                "index_memory_bytes",
+                "Estimated memory used by a part of an index (bytes)",
+            ),
+            &["keyspace", "index_name", "part"],
+        )
+        .unwrap();
+
+        let embedding_drift = GaugeVec::new(
+            prometheus::Opts::new(
+                "embedding_drift",
+                "Drift of recent vectors of an index from the first vectors seen by the node, \
+                 per kind of vectors and statistic: the shift of the mean norm in standard \
+                 deviations, or the cosine distance between the mean vectors",
+            ),
+            &["keyspace", "index_name", "vectors", "statistic"],
+        )
+        .unwrap();
+
+        let http_connections = GaugeVec::new(
+            prometheus::Opts::new("http_connections", "Number of open HTTP connections"),
+            &["server"],
+        )
+        .unwrap();
+
+        let http_connections_rejected_total = CounterVec::new(
+            prometheus::Opts::new(
+                "http_connections_rejected_total",
+                "Number of HTTP connections closed because of the connection limit",
+            ),
+            &["server"],
+        )
+        .unwrap();
+
+        let http_requests_in_flight = GaugeVec::new(
+            prometheus::Opts::new(
+                "http_requests_in_flight",
+                "Number of HTTP requests being processed",
+            ),
+            &["server"],
+        )
+        .unwrap();
+
+        let http_requests_rejected_total = CounterVec::new(
+            prometheus::Opts::new(
+                "http_requests_rejected_total",
+                "Number of HTTP requests rejected because of the concurrent requests limit",
+            ),
+            &["server"],
+        )
+        .unwrap();
+
+        let cluster_index_versions = GaugeVec::new(
+            prometheus::Opts::new(
+                "cluster_index_versions",
+                "Number of distinct versions of an index served by the nodes of the cluster. \
+                 More than one means some nodes serve a stale index definition.",
+            ),
+            &["keyspace", "index_name"],
+        )
+        .unwrap();
+
+        let embedding_cache_hits_total = CounterVec::new(
+            prometheus::Opts::new(
+                "embedding_cache_hits_total",
+                "Number of texts of queries whose embedding was found in the cache, per model",
+            ),
+            &["model"],
+        )
+        .unwrap();
+
+        let embedding_cache_misses_total = CounterVec::new(
+            prometheus::Opts::new(
+                "embedding_cache_misses_total",
+                "Number of texts of queries embedded by the model, as they were not in the cache",
+            ),
+            &["model"],
+        )
+        .unwrap();
+
         registry.register(Box::new(latency.clone())).unwrap();
         registry.register(Box::new(size.clone())).unwrap();
         registry.register(Box::new(modified.clone())).unwrap();
@@ -179,6 +293,36 @@ impl Metrics {
         registry
             .register(Box::new(fts_segment_count.clone()))
             .unwrap();
+        registry
+            .register(Box::new(key_interning_saved_bytes.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(index_memory_bytes.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(embedding_drift.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(http_connections.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(http_connections_rejected_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(http_requests_in_flight.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(http_requests_rejected_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(cluster_index_versions.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(embedding_cache_hits_total.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(embedding_cache_misses_total.clone()))
+            .unwrap();
 
         Self {
             registry,
@@ -192,6 +336,16 @@ impl Metrics {
             cdc_last_processed_timestamp_seconds,
             fts_index_size_bytes,
             fts_segment_count,
+            key_interning_saved_bytes,
+            index_memory_bytes,
+            embedding_drift,
+            http_connections,
+            http_connections_rejected_total,
+            http_requests_in_flight,
+            http_requests_rejected_total,
+            cluster_index_versions,
+            embedding_cache_hits_total,
+            embedding_cache_misses_total,
             dirty_indexes: Arc::new(DashSet::new()),
         }
     }
@@ -225,15 +379,58 @@ impl Metrics {
         let _ = self
             .fts_segment_count
             .remove_label_values(&[keyspace, index_name]);
+        let _ = self
+            .key_interning_saved_bytes
+            .remove_label_values(&[keyspace, index_name]);
         for op in OPERATIONS {
             let _ = self
                 .modified
                 .remove_label_values(&[keyspace, index_name, op]);
         }
+        for part in MEMORY_PARTS {
+            let _ = self
+                .index_memory_bytes
+                .remove_label_values(&[keyspace, index_name, part]);
+        }
+        for vectors in DRIFT_VECTORS {
+            for statistic in DRIFT_STATISTICS {
+                let _ = self
+                    .embedding_drift
+                    .remove_label_values(&[keyspace, index_name, vectors, statistic]);
+            }
+        }
         self.dirty_indexes
             .remove(&(keyspace.to_owned(), index_name.to_owned()));
     }
 
+    pub fn set_index_memory(&self, keyspace: &str, index_name: &str, usage: &MemoryUsage) {
+        for (part, bytes) in [
+            (PART_KEY_MAPS, usage.key_maps),
+            (PART_GRAPH, usage.graph),
+            (PART_PENDING_QUEUES, usage.pending_queues),
+        ] {
+            self.index_memory_bytes
+                .with_label_values(&[keyspace, index_name, part])
+                .set(bytes as f64);
+        }
+    }
+
+    /// Sets the drift of a kind of vectors of an index, see [`DRIFT_VECTORS`].
+    pub fn set_index_drift(
+        &self,
+        keyspace: &str,
+        index_name: &str,
+        vectors: &str,
+        norm: f64,
+        direction: f64,
+    ) {
+        for (statistic, value) in [(DRIFT_NORM, norm), (DRIFT_DIRECTION, direction)] {
+            self.embedding_drift
+                .with_label_values(&[keyspace, index_name, vectors, statistic])
+                .set(value);
+        }
+    }
+
     pub fn remove_reader_labels(&self, keyspace: &str, index_name: &str, reader: &str) {
         let _ = self
             .cdc_reader_up
@@ -283,6 +480,11 @@ mod tests {
             .indexing_lag
             .with_label_values(&["ks", "idx"])
             .observe(0.001);
+        metrics
+            .index_memory_bytes
+            .with_label_values(&["ks", "idx", PART_GRAPH])
+            .set(1024.0);
+        metrics.set_index_drift("ks", "idx", VECTORS_QUERIED, 1.5, 0.2);
 
         metrics.remove_index_labels("ks", "idx");
 
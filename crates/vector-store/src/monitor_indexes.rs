@@ -9,10 +9,13 @@ use crate::DbCustomIndex;
 use crate::DbIndexKind;
 use crate::ExpansionAdd;
 use crate::ExpansionSearch;
+use crate::IndexKey;
 use crate::IndexKind;
+use crate::IndexLimits;
 use crate::IndexMetadata;
 use crate::IndexOptionsFts;
 use crate::IndexOptionsVs;
+use crate::Membership;
 use crate::Quantization;
 use crate::SpaceType;
 use crate::db::Db;
@@ -27,6 +30,7 @@ use anyhow::bail;
 use futures::StreamExt;
 use futures::stream;
 use scylla::value::CqlTimeuuid;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -50,11 +54,17 @@ pub(crate) async fn new(
     engine: WeakSender<Engine>,
     node_state: Sender<NodeState>,
     mut config_rx: watch::Receiver<Arc<Config>>,
+    mut membership_rx: watch::Receiver<Option<Arc<Membership>>>,
 ) -> anyhow::Result<Sender<MonitorIndexes>> {
     let (tx, mut rx) = mpsc::channel(perf::channel_size().into());
     tokio::spawn(
         async move {
-            let (interval_duration, mut alter_index_simulator, mut fulltext_indexes) = {
+            let (
+                interval_duration,
+                mut alter_index_simulator,
+                mut fulltext_indexes,
+                mut index_limits,
+            ) = {
                 let config = config_rx.borrow_and_update();
                 (
                     config
@@ -62,12 +72,14 @@ pub(crate) async fn new(
                         .unwrap_or(Duration::from_secs(1)),
                     config.alter_index_simulator,
                     config.fulltext_indexes,
+                    config.index_limits.clone(),
                 )
             };
             let mut interval = time::interval(interval_duration);
 
             let mut schema_version = SchemaVersion::new();
             let mut indexes = HashSet::new();
+            let mut rejected_indexes = HashMap::new();
             if alter_index_simulator {
                 info!("monitor_indexes: alter index simulator is enabled");
             }
@@ -98,6 +110,15 @@ pub(crate) async fn new(
                         };
 
                         let new_indexes = filter_disabled_index_kinds(new_indexes, fulltext_indexes);
+                        let new_indexes = filter_not_owned(new_indexes, membership_rx.borrow_and_update().as_deref());
+                        let (new_indexes, rejected) = reject_over_limits(new_indexes, &indexes, &index_limits);
+                        if rejected != rejected_indexes {
+                            for (key, reason) in &rejected {
+                                warn!("monitor_indexes: index {key} is not built: {reason}");
+                            }
+                            node_state.send_event(Event::IndexesRejected(rejected.clone())).await;
+                            rejected_indexes = rejected;
+                        }
 
                         if alter_index_simulator {
                             node_state.send_event(Event::IndexesDiscovered(
@@ -141,6 +162,17 @@ pub(crate) async fn new(
                         let config = config_rx.borrow_and_update();
                         update_flag(&mut alter_index_simulator, config.alter_index_simulator, "alter index simulator");
                         update_flag(&mut fulltext_indexes, config.fulltext_indexes, "fulltext indexes");
+                        if index_limits != config.index_limits {
+                            info!("monitor_indexes: index limits changed to {:?}", config.index_limits);
+                            index_limits = config.index_limits.clone();
+                            // recheck discovered indexes against the new limits
+                            schema_version.reset();
+                        }
+                    }
+
+                    Ok(()) = membership_rx.changed() => {
+                        // rebalance: build newly owned indexes and drop the ones owned by others
+                        schema_version.reset();
                     }
 
                     _ = rx.recv() => { }
@@ -196,7 +228,7 @@ async fn get_indexes(db: &Sender<Db>) -> anyhow::Result<HashSet<IndexMetadata>>
                 };
                 kind
             }
-            DbIndexKind::FullTextSearch => IndexKind::Fts(IndexOptionsFts {}),
+            DbIndexKind::FullTextSearch => IndexKind::Fts(IndexOptionsFts { ttl: idx.ttl }),
         };
 
         let metadata = IndexMetadata {
@@ -264,6 +296,8 @@ async fn build_vs_index_kind(
         expansion_search,
         space_type,
         quantization,
+        embedding_model: idx.embedding_model.clone(),
+        ttl: idx.ttl,
     })))
 }
 
@@ -325,6 +359,48 @@ fn filter_disabled_index_kinds(
         .collect()
 }
 
+/// Keeps only indexes owned by the local node when clustering is enabled.
+fn filter_not_owned(
+    indexes: HashSet<IndexMetadata>,
+    membership: Option<&Membership>,
+) -> HashSet<IndexMetadata> {
+    let Some(membership) = membership else {
+        return indexes;
+    };
+    indexes
+        .into_iter()
+        .filter(|idx| membership.owns(&idx.key()))
+        .collect()
+}
+
+/// Moves new indexes exceeding the limits out of the discovered ones. Indexes which are already
+/// built are kept even if the limits were lowered.
+fn reject_over_limits(
+    new_indexes: HashSet<IndexMetadata>,
+    indexes: &HashSet<IndexMetadata>,
+    limits: &IndexLimits,
+) -> (HashSet<IndexMetadata>, HashMap<IndexKey, String>) {
+    let mut rejected = HashMap::new();
+    let new_indexes = new_indexes
+        .into_iter()
+        .filter(|idx| {
+            let Some(options) = idx.vs() else {
+                return true;
+            };
+            if indexes.contains(idx) {
+                return true;
+            }
+            limits
+                .check(options)
+                .inspect_err(|err| {
+                    rejected.insert(idx.key(), err.to_string());
+                })
+                .is_ok()
+        })
+        .collect();
+    (new_indexes, rejected)
+}
+
 /// delete the index if it doesn't appear in the new_indexes
 fn should_delete(curr_idx: &IndexMetadata, new_indexes: &HashSet<IndexMetadata>) -> bool {
     !new_indexes.contains(curr_idx)
@@ -408,6 +484,8 @@ mod tests {
                 expansion_search: Default::default(),
                 space_type: Default::default(),
                 quantization: Default::default(),
+                embedding_model: None,
+                ttl: None,
             }),
         }
     }
@@ -421,7 +499,7 @@ mod tests {
             partitioning: DbIndexPartitioning::Global,
             filtering_columns: Arc::new([]),
             version: Uuid::new_v4().into(),
-            kind: IndexKind::Fts(IndexOptionsFts {}),
+            kind: IndexKind::Fts(IndexOptionsFts { ttl: None }),
         }
     }
 
@@ -509,6 +587,8 @@ mod tests {
                 partitioning: DbIndexPartitioning::Global,
                 filtering_columns: Arc::new([]),
                 kind: DbIndexKind::VectorSearch,
+                embedding_model: None,
+                ttl: None,
             }
         }
 
@@ -568,6 +648,8 @@ mod tests {
                         partitioning: DbIndexPartitioning::Global,
                         filtering_columns: Arc::new([]),
                         kind: idx.kind,
+                        embedding_model: None,
+                        ttl: None,
                     })
                     .collect()
             }
@@ -690,10 +772,18 @@ mod tests {
 
         let (_config_tx, config_rx) = watch::channel(Arc::new(Config::default()));
 
+        let (_membership_tx, membership_rx) = watch::channel(None);
+
         // Start the monitor
-        let _monitor = new(tx_db.clone(), tx_eng.downgrade(), tx_ns.clone(), config_rx)
-            .await
-            .unwrap();
+        let _monitor = new(
+            tx_db.clone(),
+            tx_eng.downgrade(),
+            tx_ns.clone(),
+            config_rx,
+            membership_rx,
+        )
+        .await
+        .unwrap();
 
         // Add two indexes
         let index1 = sample_db_index("index1");
@@ -763,6 +853,8 @@ mod tests {
                         partitioning: DbIndexPartitioning::Global,
                         filtering_columns: Arc::new([]),
                         kind: DbIndexKind::VectorSearch,
+                        embedding_model: None,
+                        ttl: None,
                     };
                     tx.send(Ok(vec![index(), index(), index()])).unwrap();
                 }
@@ -842,6 +934,8 @@ mod tests {
                         partitioning: DbIndexPartitioning::Global,
                         filtering_columns: Arc::new([]),
                         kind: DbIndexKind::FullTextSearch,
+                        embedding_model: None,
+                        ttl: None,
                     }]))
                     .unwrap();
                 }
@@ -871,7 +965,7 @@ mod tests {
         assert_eq!(result.len(), 1);
         let idx = result.into_iter().next().unwrap();
         assert_eq!(idx.index_name.as_ref(), "fts_idx");
-        assert_eq!(idx.kind, IndexKind::Fts(IndexOptionsFts {}));
+        assert_eq!(idx.kind, IndexKind::Fts(IndexOptionsFts { ttl: None }));
     }
 
     #[test]
@@ -1067,4 +1161,64 @@ mod tests {
                 .all(|idx| matches!(idx.kind, IndexKind::Vs(_)))
         );
     }
+
+    #[test]
+    fn new_indexes_over_limits_are_rejected() {
+        let with_dimensions = |name, dimensions| {
+            let mut idx = sample_vs_index_metadata(name);
+            if let IndexKind::Vs(options) = &mut idx.kind {
+                options.dimensions = NonZeroUsize::new(dimensions).unwrap().into();
+            }
+            idx
+        };
+        let built_large = with_dimensions("built", 4);
+        let small = with_dimensions("small", 3);
+        let large = with_dimensions("large", 4);
+        let fts = sample_fts_index_metadata("fts");
+        let limits = IndexLimits {
+            max_dimensions: NonZeroUsize::new(3),
+            ..IndexLimits::default()
+        };
+
+        let (accepted, rejected) = reject_over_limits(
+            HashSet::from([
+                built_large.clone(),
+                small.clone(),
+                large.clone(),
+                fts.clone(),
+            ]),
+            &HashSet::from([built_large.clone()]),
+            &limits,
+        );
+
+        assert_eq!(accepted, HashSet::from([built_large, small, fts]));
+        assert_eq!(rejected.len(), 1);
+        assert_eq!(
+            rejected[&large.key()],
+            "index dimensions 4 exceeds the limit of 3"
+        );
+    }
+
+    #[test]
+    fn only_owned_indexes_are_kept_in_cluster() {
+        let indexes: HashSet<_> = (0..20)
+            .map(|i| sample_vs_index_metadata(&format!("idx{i}")))
+            .collect();
+        let nodes: Vec<_> = ["vs1", "vs2"]
+            .into_iter()
+            .map(|id| crate::ClusterNode {
+                id: id.into(),
+                url: format!("http://{id}:6080"),
+                zone: None,
+            })
+            .collect();
+        let vs1 = Membership::new("vs1".into(), nodes.clone(), NonZeroUsize::MIN);
+        let vs2 = Membership::new("vs2".into(), nodes, NonZeroUsize::MIN);
+
+        assert_eq!(filter_not_owned(indexes.clone(), None), indexes);
+        let owned1 = filter_not_owned(indexes.clone(), Some(&vs1));
+        let owned2 = filter_not_owned(indexes.clone(), Some(&vs2));
+        assert!(owned1.is_disjoint(&owned2));
+        assert_eq!(owned1.len() + owned2.len(), indexes.len());
+    }
 }
@@ -11,14 +11,21 @@ use crate::IndexKey;
 use crate::Metrics;
 use crate::NonemptyBox;
 use crate::PrimaryKey;
+use crate::Progress;
 use crate::Timestamp;
 use crate::Timestamped;
 use crate::Vector;
+use crate::actor_trace;
+use crate::drift::VectorDrift;
+use crate::embedding;
+use crate::embedding::IndexEmbedder;
 use crate::fts_index::FtsIndex;
 use crate::fts_index::FtsIndexExt;
+use crate::memory::IndexMemory;
 use crate::metrics::OP_INSERT;
 use crate::metrics::OP_REMOVE;
 use crate::metrics::OP_UPDATE;
+use crate::node_state::ScanRows;
 use crate::perf;
 use crate::table::Operation;
 use crate::table::PartitionId;
@@ -26,12 +33,18 @@ use crate::table::PrimaryId;
 use crate::table::TableModify;
 use crate::vs_index::VsIndex;
 use crate::vs_index::VsIndexExt;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
 use std::sync::RwLock;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::Receiver;
 use tokio::sync::mpsc::Sender;
+use tokio::sync::oneshot;
+use tokio::time;
+use tokio::time::MissedTickBehavior;
 use tracing::Instrument;
 use tracing::debug;
 use tracing::error;
@@ -121,7 +134,99 @@ impl IndexDispatch for mpsc::Sender<FtsIndex> {
     async fn remove_partition(&self, _partition_id: PartitionId) {}
 }
 
-pub(crate) enum MonitorItems {}
+pub(crate) enum MonitorItems {
+    /// Applies a row which doesn't come from the db, e.g. written through an `IndexHandle`.
+    ApplyRow {
+        row: DbIndexedRow,
+        tx: oneshot::Sender<()>,
+    },
+    /// Rows of the full scan processed so far, with the rows of the whole scan estimated by the
+    /// progress of the scan.
+    ScanRows {
+        progress: Progress,
+        tx: oneshot::Sender<ScanRows>,
+    },
+}
+
+pub(crate) trait MonitorItemsExt {
+    async fn apply_row(&self, row: DbIndexedRow) -> anyhow::Result<()>;
+    async fn scan_rows(&self, progress: Progress) -> anyhow::Result<ScanRows>;
+}
+
+impl MonitorItemsExt for mpsc::Sender<MonitorItems> {
+    async fn apply_row(&self, row: DbIndexedRow) -> anyhow::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MonitorItems::ApplyRow { row, tx }).await?;
+        Ok(rx.await?)
+    }
+
+    async fn scan_rows(&self, progress: Progress) -> anyhow::Result<ScanRows> {
+        let (tx, rx) = oneshot::channel();
+        self.send(MonitorItems::ScanRows { progress, tx }).await?;
+        Ok(rx.await?)
+    }
+}
+
+/// How often the rows of an index with a TTL are checked for expiration.
+const EXPIRATION_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The latest write timestamp of each row of an index with a TTL, also ordered by the timestamps to
+/// find the rows to expire first.
+#[derive(Default)]
+struct Expirations {
+    timestamps: HashMap<PrimaryKey, Timestamp>,
+    ordered: BTreeSet<(Timestamp, PrimaryKey)>,
+}
+
+impl Expirations {
+    /// Remembers the write of the row, unless a later one is already known.
+    fn upsert(&mut self, primary_key: &PrimaryKey, timestamp: Timestamp) {
+        if let Some(current) = self.timestamps.get(primary_key) {
+            if *current >= timestamp {
+                return;
+            }
+            self.ordered.remove(&(*current, primary_key.clone()));
+        }
+        self.timestamps.insert(primary_key.clone(), timestamp);
+        self.ordered.insert((timestamp, primary_key.clone()));
+    }
+
+    /// Forgets the row deleted at the timestamp, unless it was written again later.
+    fn delete(&mut self, primary_key: &PrimaryKey, timestamp: Timestamp) {
+        if let Some(current) = self.timestamps.get(primary_key)
+            && *current <= timestamp
+        {
+            self.ordered.remove(&(*current, primary_key.clone()));
+            self.timestamps.remove(primary_key);
+        }
+    }
+
+    /// Removes and returns the row written the longest ago, if it was written `ttl` ago or more.
+    fn pop_expired(&mut self, ttl: Duration) -> Option<(Timestamp, PrimaryKey)> {
+        let (timestamp, _) = self.ordered.first()?;
+        if timestamp.elapsed() < ttl {
+            return None;
+        }
+        let (timestamp, primary_key) = self.ordered.pop_first()?;
+        self.timestamps.remove(&primary_key);
+        Some((timestamp, primary_key))
+    }
+
+    /// Estimates the memory of both maps. Primary keys share their buffers with the table.
+    fn memory_bytes(&self) -> usize {
+        self.timestamps.capacity() * size_of::<(PrimaryKey, Timestamp)>()
+            + self.ordered.len() * size_of::<(Timestamp, PrimaryKey)>()
+    }
+}
+
+/// Parts of the monitoring of items which depend on the kind and the options of the index.
+#[derive(Default)]
+pub(crate) struct ItemsOptions {
+    pub(crate) memory: IndexMemory,
+    pub(crate) drift: VectorDrift,
+    pub(crate) embedder: Option<IndexEmbedder>,
+    pub(crate) ttl: Option<Duration>,
+}
 
 pub(crate) async fn new<T>(
     key: IndexKey,
@@ -129,11 +234,18 @@ pub(crate) async fn new<T>(
     mut db_rows: Receiver<(DbIndexedRow, AsyncInProgress)>,
     index: mpsc::Sender<T>,
     metrics: Arc<Metrics>,
+    options: ItemsOptions,
 ) -> anyhow::Result<Sender<MonitorItems>>
 where
     T: Send + 'static,
     mpsc::Sender<T>: IndexDispatch,
 {
+    let ItemsOptions {
+        memory,
+        drift,
+        embedder,
+        ttl,
+    } = options;
     let (tx, mut rx) = mpsc::channel(perf::channel_size().into());
     let key_for_span = key.clone();
 
@@ -141,23 +253,84 @@ where
         async move {
             debug!("starting");
 
+            let mut expirations = Expirations::new();
+            let mut scanned_rows = 0_u64;
+            let mut expiration = time::interval(EXPIRATION_INTERVAL);
+            expiration.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
             while !rx.is_closed() {
                 tokio::select! {
                     db_row = db_rows.recv() => {
                         let Some((db_row, in_progress)) = db_row else {
                             break;
                         };
-                        let primary_key = db_row.primary_key;
-                        match db_row.operation {
-                            DbIndexedOperation::Upsert(values) => {
-                                upsert(&table, &index, primary_key, values, in_progress, &metrics, &key).await;
-                            }
-                            DbIndexedOperation::Delete(timestamp) => {
-                                delete(&table, &index, primary_key, timestamp, in_progress, &metrics, &key).await;
+                        let _trace = actor_trace::start(
+                            "monitor_items",
+                            operation_name(&db_row.operation),
+                            Some(&key),
+                            db_rows.len(),
+                        );
+                        memory.set_pending_queues(
+                            db_rows.len() as u64 * estimated_row_bytes(&db_row),
+                        );
+                        if matches!(in_progress, AsyncInProgress::Fullscan(_)) {
+                            scanned_rows += 1;
+                        }
+                        if ttl.is_some() {
+                            track_expiration(&mut expirations, &db_row, &memory);
+                        }
+                        observe_vectors(&drift, &db_row);
+                        apply_row(
+                            &table,
+                            &index,
+                            embedder.as_ref(),
+                            db_row,
+                            in_progress,
+                            &metrics,
+                            &key,
+                        )
+                        .await;
+                    }
+                    msg = rx.recv() => match msg {
+                        Some(MonitorItems::ApplyRow { row, tx }) => {
+                            let _trace = actor_trace::start(
+                                "monitor_items",
+                                "ApplyRow",
+                                Some(&key),
+                                rx.len(),
+                            );
+                            if ttl.is_some() {
+                                track_expiration(&mut expirations, &row, &memory);
                             }
+                            observe_vectors(&drift, &row);
+                            apply_row(
+                                &table,
+                                &index,
+                                embedder.as_ref(),
+                                row,
+                                AsyncInProgress::None,
+                                &metrics,
+                                &key,
+                            )
+                            .await;
+                            tx.send(()).unwrap_or_else(|_| {
+                                debug!("MonitorItems::ApplyRow: unable to send response")
+                            });
                         }
+                        Some(MonitorItems::ScanRows { progress, tx }) => {
+                            tx.send(scan_rows(scanned_rows, progress)).unwrap_or_else(|_| {
+                                debug!("MonitorItems::ScanRows: unable to send response")
+                            });
+                        }
+                        None => {}
+                    },
+                    _ = expiration.tick(), if ttl.is_some() => {
+                        let Some(ttl) = ttl else {
+                            continue;
+                        };
+                        expire(&table, &index, &mut expirations, ttl, &metrics, &key).await;
+                        memory.set_expirations(expirations.memory_bytes() as u64);
                     }
-                    _ = rx.recv() => { }
                 }
             }
 
@@ -168,6 +341,144 @@ where
     Ok(tx)
 }
 
+/// Extrapolates the processed rows of the full scan over the scanned part of the token ring.
+fn scan_rows(processed: u64, progress: Progress) -> ScanRows {
+    let total = match progress {
+        Progress::Done => Some(processed),
+        Progress::InProgress(percent) if percent.get() > 0.0 => {
+            Some((processed as f64 * 100.0 / percent.get()).round() as u64)
+        }
+        Progress::InProgress(_) => None,
+    };
+    ScanRows { processed, total }
+}
+
+/// Remembers the write of an upserted row, to remove the row when the TTL of the index passes, or
+/// forgets a deleted row.
+fn track_expiration(expirations: &mut Expirations, row: &DbIndexedRow, memory: &IndexMemory) {
+    match &row.operation {
+        DbIndexedOperation::Upsert(values) => {
+            if let Some(timestamp) = values.iter().map(|value| value.timestamp()).max() {
+                expirations.upsert(&row.primary_key, timestamp);
+            }
+        }
+        DbIndexedOperation::Delete(timestamp) => {
+            expirations.delete(&row.primary_key, *timestamp);
+        }
+    }
+    memory.set_expirations(expirations.memory_bytes() as u64);
+}
+
+/// Adds the vectors of an upserted row to the drift of ingested vectors.
+fn observe_vectors(drift: &VectorDrift, row: &DbIndexedRow) {
+    if let DbIndexedOperation::Upsert(values) = &row.operation {
+        values
+            .iter()
+            .filter_map(|value| value.value())
+            .for_each(|value| {
+                if let DbIndexedValue::Vector(vector) = value {
+                    drift.observe_ingested(vector.as_slice());
+                }
+            });
+    }
+}
+
+/// Removes the rows written longer than `ttl` ago. A row is removed by a tombstone right after its
+/// write, so a later write of the row is kept.
+async fn expire<I: IndexDispatch>(
+    table: &Arc<RwLock<impl TableModify>>,
+    index: &I,
+    expirations: &mut Expirations,
+    ttl: Duration,
+    metrics: &Metrics,
+    index_key: &IndexKey,
+) {
+    while let Some((timestamp, primary_key)) = expirations.pop_expired(ttl) {
+        delete(
+            table,
+            index,
+            primary_key,
+            timestamp.next(),
+            AsyncInProgress::None,
+            metrics,
+            index_key,
+        )
+        .await;
+    }
+}
+
+/// Estimates memory taken by a row waiting in a queue.
+fn estimated_row_bytes(row: &DbIndexedRow) -> u64 {
+    let values = match &row.operation {
+        DbIndexedOperation::Upsert(values) => values
+            .iter()
+            .filter_map(|value| value.value())
+            .map(|value| match value {
+                DbIndexedValue::Vector(vector) => vector.len() * size_of::<f32>(),
+                DbIndexedValue::Document(document) => document.len(),
+                DbIndexedValue::Filtering(_) => 0,
+            })
+            .sum(),
+        DbIndexedOperation::Delete(_) => 0,
+    };
+    (size_of::<(DbIndexedRow, AsyncInProgress)>() + values) as u64
+}
+
+fn operation_name(operation: &DbIndexedOperation) -> &'static str {
+    match operation {
+        DbIndexedOperation::Upsert(_) => "Upsert",
+        DbIndexedOperation::Delete(_) => "Delete",
+    }
+}
+
+async fn apply_row<I: IndexDispatch>(
+    table: &Arc<RwLock<impl TableModify>>,
+    index: &I,
+    embedder: Option<&IndexEmbedder>,
+    row: DbIndexedRow,
+    in_progress: AsyncInProgress,
+    metrics: &Metrics,
+    index_key: &IndexKey,
+) {
+    let primary_key = row.primary_key;
+    match row.operation {
+        DbIndexedOperation::Upsert(values) => {
+            let values = match embedder {
+                Some(embedder) => match embed_documents(embedder, values).await {
+                    Ok(values) => values,
+                    Err(err) => {
+                        error!("failed to embed a text of a row: {err}");
+                        return;
+                    }
+                },
+                None => values,
+            };
+            upsert(
+                table,
+                index,
+                primary_key,
+                values,
+                in_progress,
+                metrics,
+                index_key,
+            )
+            .await;
+        }
+        DbIndexedOperation::Delete(timestamp) => {
+            delete(
+                table,
+                index,
+                primary_key,
+                timestamp,
+                in_progress,
+                metrics,
+                index_key,
+            )
+            .await;
+        }
+    }
+}
+
 async fn upsert<I: IndexDispatch>(
     table: &Arc<RwLock<impl TableModify>>,
     index: &I,
@@ -190,6 +501,26 @@ async fn upsert<I: IndexDispatch>(
     process_operations(operations, index, in_progress, metrics, index_key).await;
 }
 
+/// Replaces the texts of a row by their embeddings.
+async fn embed_documents(
+    embedder: &IndexEmbedder,
+    values: NonemptyBox<Timestamped<DbIndexedValue>>,
+) -> Result<NonemptyBox<Timestamped<DbIndexedValue>>, embedding::Error> {
+    let mut embedded = Vec::with_capacity(values.len().get());
+    for value in values {
+        let timestamp = value.timestamp();
+        let value = match value.into_value() {
+            Some(DbIndexedValue::Document(text)) => {
+                Some(DbIndexedValue::Vector(embedder.embed(&text).await?.into()))
+            }
+            value => value,
+        };
+        embedded.push(Timestamped::new(timestamp, value));
+    }
+    Ok(NonemptyBox::try_from(embedded.into_boxed_slice())
+        .expect("embedded values should not be empty"))
+}
+
 async fn delete<I: IndexDispatch>(
     table: &Arc<RwLock<impl TableModify>>,
     index: &I,
@@ -308,6 +639,32 @@ mod tests {
     use mockall::predicate::*;
     use scylla::value::CqlValue;
 
+    #[test]
+    fn scan_rows_are_extrapolated_over_the_scanned_ring() {
+        let progress = |percent: f64| Progress::InProgress(percent.try_into().unwrap());
+        assert_eq!(
+            scan_rows(0, progress(0.0)),
+            ScanRows {
+                processed: 0,
+                total: None
+            }
+        );
+        assert_eq!(
+            scan_rows(100, progress(25.0)),
+            ScanRows {
+                processed: 100,
+                total: Some(400)
+            }
+        );
+        assert_eq!(
+            scan_rows(1000, Progress::Done),
+            ScanRows {
+                processed: 1000,
+                total: Some(1000)
+            }
+        );
+    }
+
     // prometheus counter returns f64, so we need to compare with f64 values
     fn assert_modified_metric_counts(metrics: &Metrics, insert: f64, update: f64, remove: f64) {
         assert_eq!(
@@ -353,6 +710,7 @@ mod tests {
             rx_db_rows,
             tx_index,
             metrics,
+            ItemsOptions::default(),
         )
         .await
         .unwrap();
@@ -398,6 +756,7 @@ mod tests {
             rx_db_rows,
             tx_index,
             Arc::clone(&metrics),
+            ItemsOptions::default(),
         )
         .await
         .unwrap();
@@ -470,6 +829,7 @@ mod tests {
             rx_db_rows,
             tx_index,
             Arc::clone(&metrics),
+            ItemsOptions::default(),
         )
         .await
         .unwrap();
@@ -544,6 +904,7 @@ mod tests {
             rx_db_rows,
             tx_index,
             Arc::clone(&metrics),
+            ItemsOptions::default(),
         )
         .await
         .unwrap();
@@ -627,6 +988,7 @@ mod tests {
             rx_db_rows,
             tx_index,
             Arc::clone(&metrics),
+            ItemsOptions::default(),
         )
         .await
         .unwrap();
@@ -734,6 +1096,7 @@ mod tests {
             rx_db_rows,
             tx_index,
             Arc::clone(&metrics),
+            ItemsOptions::default(),
         )
         .await
         .unwrap();
@@ -793,6 +1156,7 @@ mod tests {
             rx_db_rows,
             tx_index,
             Arc::clone(&metrics),
+            ItemsOptions::default(),
         )
         .await
         .unwrap();
@@ -841,6 +1205,142 @@ mod tests {
         assert_modified_metric_counts(&metrics, 0., 0., 1.);
     }
 
+    #[tokio::test]
+    async fn remove_expired_rows() {
+        let (tx_db_rows, rx_db_rows) = mpsc::channel(10);
+        let (tx_index, mut rx_index) = mpsc::channel::<VsIndex>(10);
+        let metrics: Arc<Metrics> = Arc::new(Metrics::new());
+        let table = Arc::new(RwLock::new(MockTableModify::new()));
+        let index_key = IndexKey::new(&"vector".to_string().into(), &"store".to_string().into());
+        let _actor = new(
+            index_key.clone(),
+            Arc::clone(&table),
+            rx_db_rows,
+            tx_index,
+            Arc::clone(&metrics),
+            ItemsOptions {
+                ttl: Some(Duration::from_secs(60)),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let primary_key: PrimaryKey = [CqlValue::Int(1)].into();
+        let values = NonemptyBox::new([Timestamped::new(
+            Timestamp::from_millis(10),
+            Some(DbIndexedValue::Vector(vec![1.].into())),
+        )])
+        .unwrap();
+        table
+            .write()
+            .unwrap()
+            .expect_upsert()
+            .once()
+            .returning(|_, _, _| {
+                Ok(vec![Operation::AddVector {
+                    primary_id: 2.into(),
+                    partition_id: 3.into(),
+                    vector: vec![1.].into(),
+                    is_update: false,
+                }])
+            });
+        table
+            .write()
+            .unwrap()
+            .expect_delete()
+            .with(
+                eq(index_key),
+                eq(primary_key.clone()),
+                eq(Timestamp::from_millis(10).next()),
+            )
+            .once()
+            .returning(|_, _, _| {
+                Ok(vec![Operation::RemoveValue {
+                    primary_id: 2.into(),
+                    partition_id: 3.into(),
+                }])
+            });
+        tx_db_rows
+            .send((
+                DbIndexedRow {
+                    primary_key,
+                    operation: DbIndexedOperation::Upsert(values),
+                },
+                AsyncInProgress::None,
+            ))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            rx_index.recv().await,
+            Some(VsIndex::AddVector { .. })
+        ));
+        let Some(VsIndex::RemoveVector { primary_id, .. }) = rx_index.recv().await else {
+            unreachable!();
+        };
+        assert_eq!(primary_id, 2.into());
+
+        drop(tx_db_rows);
+        assert!(rx_index.recv().await.is_none());
+        assert_modified_metric_counts(&metrics, 1., 0., 1.);
+    }
+
+    fn expirations(entries: &[(i32, u64)]) -> Expirations {
+        let mut expirations = Expirations::default();
+        for (key, millis) in entries {
+            expirations.upsert(
+                &[CqlValue::Int(*key)].into(),
+                Timestamp::from_millis(*millis),
+            );
+        }
+        expirations
+    }
+
+    #[test]
+    fn expiration_is_tracked_once_per_row() {
+        let mut expirations = expirations(&[(1, 10), (2, 20), (1, 30), (1, 5)]);
+        assert_eq!(expirations.timestamps.len(), 2);
+
+        let ttl = Duration::ZERO;
+        let key = |key: i32| -> PrimaryKey { [CqlValue::Int(key)].into() };
+        assert_eq!(
+            expirations.pop_expired(ttl),
+            Some((Timestamp::from_millis(20), key(2)))
+        );
+        assert_eq!(
+            expirations.pop_expired(ttl),
+            Some((Timestamp::from_millis(30), key(1)))
+        );
+        assert_eq!(expirations.pop_expired(ttl), None);
+        assert_eq!(expirations.timestamps.len(), 0);
+    }
+
+    #[test]
+    fn deleted_rows_are_not_expired() {
+        let mut expirations = expirations(&[(1, 10), (2, 20)]);
+        let before = expirations.memory_bytes();
+        expirations.delete(&[CqlValue::Int(1)].into(), Timestamp::from_millis(15));
+        // The row was written again after the delete.
+        expirations.delete(&[CqlValue::Int(2)].into(), Timestamp::from_millis(15));
+
+        assert_eq!(expirations.timestamps.len(), 1);
+        assert!(expirations.memory_bytes() < before);
+        assert_eq!(
+            expirations.pop_expired(Duration::ZERO),
+            Some((Timestamp::from_millis(20), [CqlValue::Int(2)].into()))
+        );
+        assert!(expirations.pop_expired(Duration::ZERO).is_none());
+    }
+
+    #[test]
+    fn rows_are_not_expired_before_the_ttl() {
+        let mut expirations = Expirations::default();
+        expirations.upsert(&[CqlValue::Int(1)].into(), Timestamp::now());
+        assert!(expirations.pop_expired(Duration::from_secs(60)).is_none());
+        assert_eq!(expirations.timestamps.len(), 1);
+    }
+
     #[tokio::test]
     async fn remove_partition() {
         let (tx_db_rows, rx_db_rows) = mpsc::channel(10);
@@ -854,6 +1354,7 @@ mod tests {
             rx_db_rows,
             tx_index,
             Arc::clone(&metrics),
+            ItemsOptions::default(),
         )
         .await
         .unwrap();
@@ -895,4 +1396,27 @@ mod tests {
         assert!(rx_index.recv().await.is_none());
         assert_modified_metric_counts(&metrics, 0., 0., 0.);
     }
+
+    #[test]
+    fn row_bytes_include_values() {
+        let row = |operation| DbIndexedRow {
+            primary_key: [CqlValue::Int(1)].into(),
+            operation,
+        };
+        let base = size_of::<(DbIndexedRow, AsyncInProgress)>() as u64;
+        let values = NonemptyBox::new([Timestamped::new(
+            Timestamp::from_millis(1),
+            Some(DbIndexedValue::Vector(vec![1.0; 4].into())),
+        )])
+        .unwrap();
+
+        assert_eq!(
+            estimated_row_bytes(&row(DbIndexedOperation::Upsert(values))),
+            base + 16
+        );
+        assert_eq!(
+            estimated_row_bytes(&row(DbIndexedOperation::Delete(Timestamp::from_millis(1)))),
+            base
+        );
+    }
 }
@@ -5,10 +5,14 @@
 
 use crate::IndexKey;
 use crate::IndexMetadata;
+use crate::Percentage;
 use crate::perf;
+use anyhow::bail;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::hash_map::Entry::Vacant;
+use std::str::FromStr;
+use tokio::sync::broadcast;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tracing::Instrument;
@@ -17,7 +21,11 @@ use tracing::debug_span;
 use tracing::error;
 use tracing::info;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Statuses are ordered by the progress of the node startup.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
 pub enum NodeStatus {
     Initializing,
     ConnectingToDb,
@@ -26,6 +34,21 @@ pub enum NodeStatus {
     Serving,
 }
 
+impl FromStr for NodeStatus {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "initializing" => Ok(Self::Initializing),
+            "connecting-to-db" => Ok(Self::ConnectingToDb),
+            "discovering-indexes" => Ok(Self::DiscoveringIndexes),
+            "indexing-embeddings" => Ok(Self::IndexingEmbeddings),
+            "serving" => Ok(Self::Serving),
+            _ => bail!("unknown node status: {s}"),
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum IndexStatus {
     Initializing,
@@ -33,25 +56,96 @@ pub enum IndexStatus {
     Serving,
 }
 
+/// Rows read by the full scan of an index.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScanRows {
+    pub processed: u64,
+    /// Rows of the whole scan extrapolated from the scanned part of the token ring, unknown until
+    /// a part of it is scanned.
+    pub total: Option<u64>,
+}
+
+/// State of a discovered index.
+#[derive(Clone, Debug, PartialEq)]
+pub enum IndexState {
+    Discovered,
+    FullScanInProgress {
+        percent: Percentage,
+        rows: ScanRows,
+    },
+    Serving,
+    /// The index is not built, with the last error.
+    Failed {
+        error: String,
+    },
+}
+
 pub enum Event {
     ConnectingToDb,
     ConnectedToDb,
     DiscoveringIndexes,
     IndexesDiscovered(HashSet<IndexMetadata>),
     FullScanStarted(IndexMetadata),
+    FullScanProgress {
+        key: IndexKey,
+        percent: Percentage,
+        rows: ScanRows,
+    },
     FullScanFinished(IndexMetadata),
+    IndexAdded(IndexKey),
+    IndexRemoved(IndexKey),
+    IndexFailed {
+        key: IndexKey,
+        error: String,
+    },
+    /// Discovered indexes which are not built, with the reasons. Replaces the previous set.
+    IndexesRejected(HashMap<IndexKey, String>),
+    /// The node is shutting down and should not receive new traffic.
+    ShutdownStarted,
 }
 
+/// A lifecycle event of the node or of an index, published to subscribers of the node state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    NodeStatusChanged(NodeStatus),
+    /// The engine started serving the index.
+    IndexAdded(IndexKey),
+    /// The engine dropped the index.
+    IndexRemoved(IndexKey),
+    IndexStatusChanged {
+        key: IndexKey,
+        status: IndexStatus,
+    },
+    /// The initial full scan of the index table finished.
+    FullScanFinished(IndexKey),
+    /// The engine was unable to create the index, or the index was rejected when discovered.
+    IndexFailed {
+        key: IndexKey,
+        error: String,
+    },
+}
+
+/// Number of events buffered for each subscriber. A subscriber which falls behind receives
+/// [`broadcast::error::RecvError::Lagged`].
+const EVENTS_CAPACITY: usize = 1024;
+
 pub enum NodeState {
     SendEvent(Event),
     GetStatus(oneshot::Sender<NodeStatus>),
+    IsReady(oneshot::Sender<bool>),
     GetIndexStatus(oneshot::Sender<Option<IndexStatus>>, String, String),
+    GetIndexState(oneshot::Sender<Option<IndexState>>, IndexKey),
+    Subscribe(oneshot::Sender<broadcast::Receiver<LifecycleEvent>>),
 }
 
 pub(crate) trait NodeStateExt {
     async fn send_event(&self, event: Event);
     async fn get_status(&self) -> NodeStatus;
+    /// Whether the node is serving and not shutting down.
+    async fn is_ready(&self) -> bool;
     async fn get_index_status(&self, keyspace: &str, index: &str) -> Option<IndexStatus>;
+    async fn get_index_state(&self, key: &IndexKey) -> Option<IndexState>;
+    async fn subscribe(&self) -> broadcast::Receiver<LifecycleEvent>;
 }
 
 impl NodeStateExt for mpsc::Sender<NodeState> {
@@ -71,6 +165,15 @@ impl NodeStateExt for mpsc::Sender<NodeState> {
             .expect("NodeStateExt::get_status: failed to receive status")
     }
 
+    async fn is_ready(&self) -> bool {
+        let (tx, rx) = oneshot::channel();
+        self.send(NodeState::IsReady(tx))
+            .await
+            .expect("NodeStateExt::is_ready: internal actor should receive request");
+        rx.await
+            .expect("NodeStateExt::is_ready: failed to receive readiness")
+    }
+
     async fn get_index_status(&self, keyspace: &str, index: &str) -> Option<IndexStatus> {
         let (tx, rx) = oneshot::channel();
         self.send(NodeState::GetIndexStatus(
@@ -83,20 +186,95 @@ impl NodeStateExt for mpsc::Sender<NodeState> {
         rx.await
             .expect("NodeStateExt::get_index_status: failed to receive index status")
     }
+
+    async fn get_index_state(&self, key: &IndexKey) -> Option<IndexState> {
+        let (tx, rx) = oneshot::channel();
+        self.send(NodeState::GetIndexState(tx, key.clone()))
+            .await
+            .expect("NodeStateExt::get_index_state: internal actor should receive request");
+        rx.await
+            .expect("NodeStateExt::get_index_state: failed to receive index state")
+    }
+
+    async fn subscribe(&self) -> broadcast::Receiver<LifecycleEvent> {
+        let (tx, rx) = oneshot::channel();
+        self.send(NodeState::Subscribe(tx))
+            .await
+            .expect("NodeStateExt::subscribe: internal actor should receive request");
+        rx.await
+            .expect("NodeStateExt::subscribe: failed to receive subscription")
+    }
 }
 
-fn update_indexes(idxs: &mut HashMap<IndexKey, IndexStatus>, keys: HashSet<IndexKey>) {
+fn update_indexes(
+    idxs: &mut HashMap<IndexKey, IndexStatus>,
+    keys: HashSet<IndexKey>,
+    events: &broadcast::Sender<LifecycleEvent>,
+) {
     // Remove indexes that are no longer present
     idxs.retain(|idx, _| keys.contains(idx));
 
     for key in keys.into_iter() {
         // Add index only if not already present
         if let Vacant(e) = idxs.entry(key) {
+            publish(
+                events,
+                LifecycleEvent::IndexStatusChanged {
+                    key: e.key().clone(),
+                    status: IndexStatus::Initializing,
+                },
+            );
             e.insert(IndexStatus::Initializing);
         }
     }
 }
 
+fn set_index_status(
+    idxs: &mut HashMap<IndexKey, IndexStatus>,
+    key: IndexKey,
+    status: IndexStatus,
+    events: &broadcast::Sender<LifecycleEvent>,
+) {
+    if let Some(index_status) = idxs.get_mut(&key)
+        && *index_status != status
+    {
+        *index_status = status;
+        publish(events, LifecycleEvent::IndexStatusChanged { key, status });
+    }
+}
+
+/// Rejections and failures take precedence over the status of the index.
+fn index_state(
+    key: &IndexKey,
+    idxs: &HashMap<IndexKey, IndexStatus>,
+    scans: &HashMap<IndexKey, (Percentage, ScanRows)>,
+    rejected: &HashMap<IndexKey, String>,
+    failed: &HashMap<IndexKey, String>,
+) -> Option<IndexState> {
+    if let Some(error) = rejected.get(key).or_else(|| failed.get(key)) {
+        return Some(IndexState::Failed {
+            error: error.clone(),
+        });
+    }
+    idxs.get(key).map(|status| match status {
+        IndexStatus::Initializing => IndexState::Discovered,
+        IndexStatus::FullScanning => {
+            // No progress is reported right after the full scan starts.
+            let (percent, rows) = scans
+                .get(key)
+                .copied()
+                .unwrap_or((Percentage::from(0_u64), ScanRows::default()));
+            IndexState::FullScanInProgress { percent, rows }
+        }
+        IndexStatus::Serving => IndexState::Serving,
+    })
+}
+
+fn publish(events: &broadcast::Sender<LifecycleEvent>, event: LifecycleEvent) {
+    // An error means there are no subscribers.
+    _ = events.send(event);
+}
+
 pub(crate) async fn new() -> mpsc::Sender<NodeState> {
     let (tx, mut rx) = mpsc::channel(perf::channel_size().into());
 
@@ -107,7 +285,13 @@ pub(crate) async fn new() -> mpsc::Sender<NodeState> {
             let mut status = NodeStatus::Initializing;
             let mut initial_idxs: Option<HashSet<_>> = None;
             let mut idxs = HashMap::<IndexKey, IndexStatus>::new();
+            let mut rejected = HashMap::<IndexKey, String>::new();
+            let mut failed = HashMap::<IndexKey, String>::new();
+            let mut scans = HashMap::<IndexKey, (Percentage, ScanRows)>::new();
+            let mut shutting_down = false;
+            let (events, _) = broadcast::channel(EVENTS_CAPACITY);
             while let Some(msg) = rx.recv().await {
+                let status_prev = status;
                 match msg {
                     NodeState::SendEvent(event) => match event {
                         Event::ConnectingToDb => {
@@ -144,7 +328,10 @@ pub(crate) async fn new() -> mpsc::Sender<NodeState> {
                             update_indexes(
                                 &mut idxs,
                                 indexes.iter().map(|meta| meta.key()).collect(),
+                                &events,
                             );
+                            failed.retain(|key, _| idxs.contains_key(key));
+                            scans.retain(|key, _| idxs.contains_key(key));
 
                             let initial_idxs = initial_idxs
                                 .as_mut()
@@ -163,37 +350,87 @@ pub(crate) async fn new() -> mpsc::Sender<NodeState> {
                                         more = if initial { "" } else { "more " }
                                     );
                                 }
-                                continue;
+                            } else {
+                                // we have initial indexes to build, so we need to be in the
+                                // IndexingEmbeddings state
+                                status = NodeStatus::IndexingEmbeddings;
                             }
-
-                            // we have initial indexes to build, so we need to be in the
-                            // IndexingEmbeddings state
-                            status = NodeStatus::IndexingEmbeddings;
                         }
 
                         Event::FullScanStarted(metadata) => {
-                            if let Some(index_status) = idxs.get_mut(&metadata.key()) {
-                                *index_status = IndexStatus::FullScanning;
+                            scans.remove(&metadata.key());
+                            set_index_status(
+                                &mut idxs,
+                                metadata.key(),
+                                IndexStatus::FullScanning,
+                                &events,
+                            );
+                        }
+
+                        Event::FullScanProgress { key, percent, rows } => {
+                            if idxs.get(&key) == Some(&IndexStatus::FullScanning) {
+                                scans.insert(key, (percent, rows));
                             }
                         }
 
                         Event::FullScanFinished(metadata) => {
-                            if let Some(index_status) = idxs.get_mut(&metadata.key()) {
-                                *index_status = IndexStatus::Serving;
-                            }
+                            scans.remove(&metadata.key());
+                            set_index_status(
+                                &mut idxs,
+                                metadata.key(),
+                                IndexStatus::Serving,
+                                &events,
+                            );
+                            publish(&events, LifecycleEvent::FullScanFinished(metadata.key()));
 
-                            let Some(initial_idxs) = &mut initial_idxs else {
+                            if let Some(initial_idxs) = &mut initial_idxs {
+                                initial_idxs.remove(&metadata);
+                                if initial_idxs.is_empty() && status != NodeStatus::Serving {
+                                    status = NodeStatus::Serving;
+                                    info!("Service is running, finished building initial indexes");
+                                }
+                            } else {
                                 error!(
                                     "Received FullScanFinished for index {index:?} \
                                     but initial_idxs is None",
                                     index = metadata.key()
                                 );
-                                continue;
-                            };
-                            initial_idxs.remove(&metadata);
-                            if initial_idxs.is_empty() && status != NodeStatus::Serving {
-                                status = NodeStatus::Serving;
-                                info!("Service is running, finished building initial indexes");
+                            }
+                        }
+
+                        Event::IndexAdded(key) => {
+                            failed.remove(&key);
+                            publish(&events, LifecycleEvent::IndexAdded(key));
+                        }
+
+                        Event::IndexRemoved(key) => {
+                            publish(&events, LifecycleEvent::IndexRemoved(key));
+                        }
+
+                        Event::IndexFailed { key, error } => {
+                            failed.insert(key.clone(), error.clone());
+                            publish(&events, LifecycleEvent::IndexFailed { key, error });
+                        }
+
+                        Event::IndexesRejected(indexes) => {
+                            for (key, error) in &indexes {
+                                if rejected.get(key) != Some(error) {
+                                    publish(
+                                        &events,
+                                        LifecycleEvent::IndexFailed {
+                                            key: key.clone(),
+                                            error: error.clone(),
+                                        },
+                                    );
+                                }
+                            }
+                            rejected = indexes;
+                        }
+
+                        Event::ShutdownStarted => {
+                            if !shutting_down {
+                                shutting_down = true;
+                                info!("Shutdown started, the node is no longer ready");
                             }
                         }
                     },
@@ -202,6 +439,12 @@ pub(crate) async fn new() -> mpsc::Sender<NodeState> {
                             tracing::debug!("Failed to send current state");
                         });
                     }
+                    NodeState::IsReady(tx) => {
+                        tx.send(status == NodeStatus::Serving && !shutting_down)
+                            .unwrap_or_else(|_| {
+                                tracing::debug!("Failed to send readiness");
+                            });
+                    }
                     NodeState::GetIndexStatus(tx, keyspace, index) => {
                         if let Some(index_status) = idxs.get(&IndexKey::new(
                             &crate::KeyspaceName(keyspace.clone()),
@@ -216,6 +459,20 @@ pub(crate) async fn new() -> mpsc::Sender<NodeState> {
                             });
                         }
                     }
+                    NodeState::GetIndexState(tx, key) => {
+                        tx.send(index_state(&key, &idxs, &scans, &rejected, &failed))
+                            .unwrap_or_else(|_| {
+                                tracing::debug!("Failed to send index state");
+                            });
+                    }
+                    NodeState::Subscribe(tx) => {
+                        tx.send(events.subscribe()).unwrap_or_else(|_| {
+                            tracing::debug!("Failed to send events subscription");
+                        });
+                    }
+                }
+                if status != status_prev {
+                    publish(&events, LifecycleEvent::NodeStatusChanged(status));
                 }
             }
             debug!("finished");
@@ -257,6 +514,8 @@ mod tests {
                 expansion_search: Default::default(),
                 space_type: Default::default(),
                 quantization: Default::default(),
+                embedding_model: None,
+                ttl: None,
             }),
         }
     }
@@ -581,6 +840,8 @@ mod tests {
                 expansion_search: Default::default(),
                 space_type: Default::default(),
                 quantization: Default::default(),
+                embedding_model: None,
+                ttl: None,
             }),
         };
         let idxs = HashSet::from([idx.clone()]);
@@ -619,6 +880,55 @@ mod tests {
         assert_eq!(idx_status, None); // Index should be missing
     }
 
+    #[tokio::test]
+    async fn subscribers_receive_lifecycle_events() {
+        let node_state = new().await;
+        let mut events = node_state.subscribe().await;
+        let idx = index_metadata("idx");
+
+        node_state.send_event(Event::ConnectingToDb).await;
+        node_state.send_event(Event::DiscoveringIndexes).await;
+        node_state
+            .send_event(Event::IndexesDiscovered(HashSet::from([idx.clone()])))
+            .await;
+        node_state.send_event(Event::IndexAdded(idx.key())).await;
+        node_state
+            .send_event(Event::FullScanFinished(idx.clone()))
+            .await;
+        node_state
+            .send_event(Event::IndexFailed {
+                key: idx.key(),
+                error: "failure".to_string(),
+            })
+            .await;
+        node_state.send_event(Event::IndexRemoved(idx.key())).await;
+
+        let expected = [
+            LifecycleEvent::NodeStatusChanged(NodeStatus::ConnectingToDb),
+            LifecycleEvent::NodeStatusChanged(NodeStatus::DiscoveringIndexes),
+            LifecycleEvent::IndexStatusChanged {
+                key: idx.key(),
+                status: IndexStatus::Initializing,
+            },
+            LifecycleEvent::NodeStatusChanged(NodeStatus::IndexingEmbeddings),
+            LifecycleEvent::IndexAdded(idx.key()),
+            LifecycleEvent::IndexStatusChanged {
+                key: idx.key(),
+                status: IndexStatus::Serving,
+            },
+            LifecycleEvent::FullScanFinished(idx.key()),
+            LifecycleEvent::NodeStatusChanged(NodeStatus::Serving),
+            LifecycleEvent::IndexFailed {
+                key: idx.key(),
+                error: "failure".to_string(),
+            },
+            LifecycleEvent::IndexRemoved(idx.key()),
+        ];
+        for event in expected {
+            assert_eq!(events.recv().await.unwrap(), event);
+        }
+    }
+
     #[tokio::test]
     async fn no_indexes_discovered() {
         let node_state = new().await;
@@ -640,6 +950,25 @@ mod tests {
         assert_eq!(node_state.get_status().await, NodeStatus::Serving);
     }
 
+    #[tokio::test]
+    async fn ready_only_when_serving_and_not_shutting_down() {
+        let node_state = new().await;
+        assert!(!node_state.is_ready().await);
+
+        node_state.send_event(Event::ConnectingToDb).await;
+        node_state.send_event(Event::DiscoveringIndexes).await;
+        assert!(!node_state.is_ready().await);
+
+        node_state
+            .send_event(Event::IndexesDiscovered(HashSet::new()))
+            .await;
+        assert!(node_state.is_ready().await);
+
+        node_state.send_event(Event::ShutdownStarted).await;
+        assert!(!node_state.is_ready().await);
+        assert_eq!(node_state.get_status().await, NodeStatus::Serving);
+    }
+
     #[tokio::test]
     async fn status_remains_serving_when_discovering_indexes() {
         let node_state = new().await;
@@ -672,6 +1001,8 @@ mod tests {
                 expansion_search: Default::default(),
                 space_type: Default::default(),
                 quantization: Default::default(),
+                embedding_model: None,
+                ttl: None,
             }),
         };
 
@@ -689,4 +1020,113 @@ mod tests {
             .await;
         assert_eq!(idx_status, Some(IndexStatus::Initializing));
     }
+
+    #[tokio::test]
+    async fn rejected_indexes_are_reported_once_and_replaced() {
+        let node_state = new().await;
+        let mut events = node_state.subscribe().await;
+        let key = index_metadata("rejected").key();
+        let rejected = HashMap::from([(key.clone(), "too many dimensions".to_string())]);
+
+        node_state
+            .send_event(Event::IndexesRejected(rejected.clone()))
+            .await;
+        node_state
+            .send_event(Event::IndexesRejected(rejected))
+            .await;
+        assert_eq!(
+            node_state.get_index_state(&key).await,
+            Some(IndexState::Failed {
+                error: "too many dimensions".to_string()
+            })
+        );
+        assert_eq!(
+            events.try_recv().unwrap(),
+            LifecycleEvent::IndexFailed {
+                key: key.clone(),
+                error: "too many dimensions".to_string(),
+            }
+        );
+        assert!(events.try_recv().is_err());
+
+        node_state
+            .send_event(Event::IndexesRejected(HashMap::new()))
+            .await;
+        assert_eq!(node_state.get_index_state(&key).await, None);
+    }
+
+    #[tokio::test]
+    async fn index_state_follows_full_scan_and_failures() {
+        let node_state = new().await;
+        let idx = index_metadata("idx");
+        let key = idx.key();
+        let percent = Percentage::try_from(25.0).unwrap();
+        let rows = ScanRows {
+            processed: 100,
+            total: Some(400),
+        };
+
+        node_state
+            .send_event(Event::IndexesDiscovered(HashSet::from([idx.clone()])))
+            .await;
+        assert_eq!(
+            node_state.get_index_state(&key).await,
+            Some(IndexState::Discovered)
+        );
+
+        node_state
+            .send_event(Event::FullScanStarted(idx.clone()))
+            .await;
+        assert_eq!(
+            node_state.get_index_state(&key).await,
+            Some(IndexState::FullScanInProgress {
+                percent: Percentage::try_from(0.0).unwrap(),
+                rows: ScanRows::default(),
+            })
+        );
+
+        node_state
+            .send_event(Event::FullScanProgress {
+                key: key.clone(),
+                percent,
+                rows,
+            })
+            .await;
+        assert_eq!(
+            node_state.get_index_state(&key).await,
+            Some(IndexState::FullScanInProgress { percent, rows })
+        );
+
+        node_state
+            .send_event(Event::FullScanFinished(idx.clone()))
+            .await;
+        assert_eq!(
+            node_state.get_index_state(&key).await,
+            Some(IndexState::Serving)
+        );
+
+        node_state
+            .send_event(Event::IndexFailed {
+                key: key.clone(),
+                error: "failure".to_string(),
+            })
+            .await;
+        assert_eq!(
+            node_state.get_index_state(&key).await,
+            Some(IndexState::Failed {
+                error: "failure".to_string()
+            })
+        );
+
+        node_state.send_event(Event::IndexAdded(key.clone())).await;
+        assert_eq!(
+            node_state.get_index_state(&key).await,
+            Some(IndexState::Serving)
+        );
+
+        node_state
+            .send_event(Event::IndexesDiscovered(HashSet::new()))
+            .await;
+        assert_eq!(node_state.get_index_state(&key).await, None);
+    }
 }
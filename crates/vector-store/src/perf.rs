@@ -4,6 +4,8 @@
  */
 
 use std::num::NonZeroUsize;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use tokio::runtime::Handle;
 use tokio::task::Unconstrained;
 use tokio::task::coop;
@@ -17,9 +19,15 @@ pub(crate) fn num_workers() -> NonZeroUsize {
         .unwrap_or_else(|| NonZeroUsize::new(1).unwrap())
 }
 
+static CHANNEL_SIZE_PER_WORKER: AtomicUsize = AtomicUsize::new(3);
+
+/// Sets the channel capacity per worker for actors created afterwards.
+pub(crate) fn set_channel_size_per_worker(size: NonZeroUsize) {
+    CHANNEL_SIZE_PER_WORKER.store(size.get(), Ordering::Relaxed);
+}
+
 pub(crate) fn channel_size() -> NonZeroUsize {
-    const CHANNEL_SIZE_PER_WORKER: NonZeroUsize = NonZeroUsize::new(3).unwrap();
-    num_workers()
-        .checked_mul(CHANNEL_SIZE_PER_WORKER)
+    NonZeroUsize::new(CHANNEL_SIZE_PER_WORKER.load(Ordering::Relaxed))
+        .and_then(|size| num_workers().checked_mul(size))
         .unwrap_or_else(|| NonZeroUsize::new(1).unwrap())
 }
@@ -3,8 +3,14 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
+use crate::invariant_key::CqlValueRef;
 use crate::invariant_key::InvariantKey;
+use crate::invariant_key::KeyArena;
 use bigdecimal::BigDecimal;
+use scylla::routing::Token;
+use scylla::routing::partitioner::Murmur3Partitioner;
+use scylla::routing::partitioner::Partitioner;
+use scylla::routing::partitioner::PartitionerHasher;
 use scylla::value::CqlDecimal;
 use scylla::value::CqlValue;
 
@@ -25,6 +31,52 @@ impl PrimaryKey {
     pub fn get(&self, idx: usize) -> Option<CqlValue> {
         self.0.get(idx)
     }
+
+    pub(crate) fn get_ref(&self, idx: usize) -> Option<CqlValueRef<'_>> {
+        self.0.get_ref(idx)
+    }
+
+    /// Returns an equal key with its buffer stored in `arena`, which may intern the partition key
+    /// made of the first `partition_key_len` columns.
+    pub(crate) fn stored_in(&self, arena: &mut KeyArena, partition_key_len: usize) -> Self {
+        Self(arena.store(&self.0, partition_key_len))
+    }
+
+    /// Returns the token of the partition the row belongs to, computed by the Murmur3 partitioner
+    /// the same way as ScyllaDB does. The partition key is made of the first `partition_key_len`
+    /// columns.
+    ///
+    /// Decimal columns must not be [`normalize`]d, as the token depends on their exact bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `partition_key_len` is zero or exceeds the number of columns.
+    pub fn token(&self, partition_key_len: usize) -> Token {
+        assert!(
+            (1..=self.len()).contains(&partition_key_len),
+            "partition key length {partition_key_len} out of range for a key of {} columns",
+            self.len()
+        );
+        let mut hasher = Murmur3Partitioner.build_hasher();
+        if partition_key_len == 1 {
+            let mut buf = Vec::new();
+            self.0.iter_ref().next().unwrap().write_cql(&mut buf);
+            hasher.write(&buf);
+            return hasher.finish();
+        }
+        // A composite partition key is serialized as a sequence of components, each prefixed with
+        // its 16-bit length and followed by an end-of-component byte.
+        let mut buf = Vec::new();
+        for value in self.0.iter_ref().take(partition_key_len) {
+            buf.clear();
+            value.write_cql(&mut buf);
+            let len = u16::try_from(buf.len()).expect("partition key component exceeds 64 KiB");
+            hasher.write(&len.to_be_bytes());
+            hasher.write(&buf);
+            hasher.write(&[0]);
+        }
+        hasher.finish()
+    }
 }
 
 impl FromIterator<CqlValue> for PrimaryKey {
@@ -46,7 +98,7 @@ impl<I: IntoIterator<Item = CqlValue>> From<I> for PrimaryKey {
 /// This is needed because Scylla clustering keys use value-based comparison,
 /// but the raw bytes differ for different decimal representations.
 ///
-/// Used exclusively for BTreeMap lookup keys in [`Table`]. The original
+/// Used exclusively for key lookups in [`Table`]. The original
 /// (unnormalized) representation is stored separately and returned in ANN responses.
 pub(crate) fn normalize(value: CqlValue) -> CqlValue {
     match value {
@@ -61,3 +113,68 @@ pub(crate) fn normalize(value: CqlValue) -> CqlValue {
         other => other,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scylla::cluster::metadata::ColumnType;
+    use scylla::cluster::metadata::NativeType;
+    use scylla::routing::partitioner::PartitionerName;
+    use scylla::routing::partitioner::calculate_token_for_partition_key;
+    use scylla::serialize::row::SerializedValues;
+    use scylla::value::CqlTimestamp;
+    use uuid::Uuid;
+
+    fn driver_token(values: &[(CqlValue, NativeType)]) -> Token {
+        let mut serialized = SerializedValues::new();
+        for (value, typ) in values {
+            serialized
+                .add_value(value, &ColumnType::Native(typ.clone()))
+                .unwrap();
+        }
+        calculate_token_for_partition_key(&serialized, &PartitionerName::Murmur3).unwrap()
+    }
+
+    #[test]
+    fn token_matches_driver_for_single_column() {
+        for (value, typ) in [
+            (CqlValue::Int(42), NativeType::Int),
+            (CqlValue::BigInt(-7), NativeType::BigInt),
+            (CqlValue::Text("partition".to_string()), NativeType::Text),
+            (CqlValue::Blob(vec![1, 2, 3]), NativeType::Blob),
+            (CqlValue::Uuid(Uuid::new_v4()), NativeType::Uuid),
+            (
+                CqlValue::Timestamp(CqlTimestamp(1_700_000_000_000)),
+                NativeType::Timestamp,
+            ),
+        ] {
+            let key = PrimaryKey::from([value.clone(), CqlValue::Int(1)]);
+            assert_eq!(key.token(1), driver_token(&[(value, typ)]));
+        }
+    }
+
+    #[test]
+    fn token_matches_driver_for_composite_key() {
+        let key = PrimaryKey::from([
+            CqlValue::Int(42),
+            CqlValue::Text("partition".to_string()),
+            CqlValue::BigInt(1),
+        ]);
+
+        assert_eq!(
+            key.token(2),
+            driver_token(&[
+                (CqlValue::Int(42), NativeType::Int),
+                (CqlValue::Text("partition".to_string()), NativeType::Text),
+            ])
+        );
+        assert_ne!(key.token(2), key.token(1));
+        assert_ne!(key.token(2), key.token(3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn token_requires_partition_key() {
+        PrimaryKey::from([CqlValue::Int(42)]).token(0);
+    }
+}
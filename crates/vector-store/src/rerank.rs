@@ -0,0 +1,433 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Reranking of the candidates of an ANN search by a cross-encoder model.
+//!
+//! The search returns more candidates than requested, the text of each candidate is read from a
+//! column of the table, and a cross-encoder scores the texts against the query of the client. The
+//! candidates are returned in the order of their scores, which is more precise than the distance
+//! of their embeddings, for retrieval-augmented generation. The model is served by the rerank API
+//! of Cohere, or of a service compatible with it, or run on the node by ONNX Runtime.
+
+use crate::ColumnName;
+use crate::KeyspaceName;
+use crate::NonemptyArc;
+use crate::PrimaryKey;
+use crate::TableName;
+use crate::db::Db;
+use crate::db::DbExt;
+#[cfg(feature = "onnx")]
+use crate::embedding;
+use crate::serde_duration;
+use anyhow::bail;
+use secrecy::ExposeSecret;
+use serde_json::Value;
+use serde_json::json;
+use std::num::NonZeroUsize;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RerankProvider {
+    /// `POST {uri}/rerank` of the Cohere API, also served by Jina AI and vLLM.
+    Cohere,
+    /// A cross-encoder model run on the node, where the model is a directory with its
+    /// `model.onnx` and `tokenizer.json`.
+    Onnx,
+}
+
+impl RerankProvider {
+    /// Base URL of the public API of the provider, if there is a single one.
+    pub(crate) fn default_uri(self) -> Option<&'static str> {
+        match self {
+            Self::Cohere => Some("https://api.cohere.com/v2"),
+            Self::Onnx => None,
+        }
+    }
+}
+
+impl FromStr for RerankProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        match value {
+            "cohere" => Ok(Self::Cohere),
+            "onnx" => Ok(Self::Onnx),
+            _ => bail!("unknown rerank provider {value} (cohere, onnx)"),
+        }
+    }
+}
+
+/// Settings of the reranking model. The API key is never (de)serialized.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RerankConfig {
+    pub provider: RerankProvider,
+    /// Base URL of the provider API, e.g. `https://api.cohere.com/v2`. Not used by local models.
+    #[serde(default)]
+    pub uri: Option<String>,
+    pub model: String,
+    /// Sent as a bearer token.
+    #[serde(skip)]
+    pub api_key: Option<secrecy::SecretString>,
+    /// How many times more candidates than requested are reranked, unless the query says
+    /// otherwise.
+    #[serde(default = "default_oversampling")]
+    pub oversampling: NonZeroUsize,
+    #[serde(default = "default_timeout", with = "serde_duration")]
+    pub timeout: Duration,
+}
+
+pub(crate) fn default_oversampling() -> NonZeroUsize {
+    NonZeroUsize::new(4).unwrap()
+}
+
+pub(crate) fn default_timeout() -> Duration {
+    Duration::from_secs(10)
+}
+
+impl RerankConfig {
+    pub(crate) fn validate(&self) -> anyhow::Result<()> {
+        match self.provider {
+            RerankProvider::Onnx => {
+                if cfg!(not(feature = "onnx")) {
+                    bail!("ONNX rerank models are not compiled in, enable the `onnx` feature");
+                }
+            }
+            RerankProvider::Cohere => {
+                let Some(uri) = &self.uri else {
+                    bail!(
+                        "rerank provider URL is required for the {:?} provider",
+                        self.provider
+                    );
+                };
+                if !uri.starts_with("http://") && !uri.starts_with("https://") {
+                    bail!("rerank provider URL {uri} is not an HTTP URL");
+                }
+            }
+        }
+        if self.model.is_empty() {
+            bail!("rerank model is empty");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum Error {
+    #[error("rate limit of the rerank provider exceeded")]
+    RateLimited,
+
+    #[error("rerank provider error: {0}")]
+    Provider(String),
+
+    #[error("rerank model error: {0}")]
+    Model(String),
+
+    #[error("unable to read the texts of the candidates: {0}")]
+    Db(String),
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Provider(err.to_string())
+    }
+}
+
+enum Backend {
+    Http(reqwest::Client),
+    #[cfg(feature = "onnx")]
+    Onnx(embedding::onnx::Models),
+}
+
+/// Column of a table with the texts of the candidates.
+pub(crate) struct TextColumn {
+    pub(crate) keyspace: KeyspaceName,
+    pub(crate) table: TableName,
+    pub(crate) column: ColumnName,
+    pub(crate) primary_key_columns: NonemptyArc<ColumnName>,
+}
+
+#[derive(Clone)]
+pub(crate) struct Reranker {
+    config: Arc<RerankConfig>,
+    backend: Arc<Backend>,
+    db: mpsc::Sender<Db>,
+}
+
+impl Reranker {
+    pub(crate) fn new(config: RerankConfig, db: mpsc::Sender<Db>) -> Self {
+        let backend = match config.provider {
+            #[cfg(feature = "onnx")]
+            RerankProvider::Onnx => Backend::Onnx(embedding::onnx::Models::default()),
+            _ => Backend::Http(
+                reqwest::Client::builder()
+                    .timeout(config.timeout)
+                    .build()
+                    .expect("Reranker::new: HTTP client should be created"),
+            ),
+        };
+        Self {
+            config: Arc::new(config),
+            backend: Arc::new(backend),
+            db,
+        }
+    }
+
+    pub(crate) fn oversampling(&self) -> NonZeroUsize {
+        self.config.oversampling
+    }
+
+    /// Returns the positions of the candidates ordered by the relevance of their texts to the
+    /// query. Candidates without a text follow in their original order.
+    pub(crate) async fn rerank(
+        &self,
+        query: &str,
+        texts: TextColumn,
+        primary_keys: &[PrimaryKey],
+    ) -> Result<Vec<usize>, Error> {
+        let TextColumn {
+            keyspace,
+            table,
+            column,
+            primary_key_columns,
+        } = texts;
+        let texts = self
+            .db
+            .get_texts(
+                keyspace,
+                table,
+                column,
+                primary_key_columns,
+                primary_keys.to_vec(),
+            )
+            .await
+            .map_err(|err| Error::Db(err.to_string()))?;
+        let (positions, documents): (Vec<_>, Vec<_>) = texts
+            .into_iter()
+            .enumerate()
+            .filter_map(|(position, text)| text.map(|text| (position, text)))
+            .unzip();
+        let scores = if documents.is_empty() {
+            Vec::new()
+        } else {
+            self.score(query, documents).await?
+        };
+        Ok(order(primary_keys.len(), &positions, &scores))
+    }
+
+    async fn score(&self, query: &str, documents: Vec<String>) -> Result<Vec<f32>, Error> {
+        match self.backend.as_ref() {
+            Backend::Http(client) => self.request(client, query, documents).await,
+            #[cfg(feature = "onnx")]
+            Backend::Onnx(models) => models
+                .score(&self.config.model, query, documents)
+                .await
+                .map_err(|err| match err {
+                    embedding::Error::Model(msg) => Error::Model(msg),
+                    err => Error::Model(err.to_string()),
+                }),
+        }
+    }
+
+    async fn request(
+        &self,
+        client: &reqwest::Client,
+        query: &str,
+        documents: Vec<String>,
+    ) -> Result<Vec<f32>, Error> {
+        let RerankConfig {
+            uri,
+            model,
+            api_key,
+            ..
+        } = self.config.as_ref();
+        let count = documents.len();
+        let mut request = client
+            .post(format!("{}/rerank", uri.as_deref().unwrap_or_default()))
+            .json(&json!({
+                "model": model,
+                "query": query,
+                "documents": documents,
+                "top_n": count,
+            }));
+        if let Some(api_key) = api_key {
+            request = request.bearer_auth(api_key.expose_secret());
+        }
+        let response = request.send().await?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(Error::RateLimited);
+        }
+        let response: Value = response.error_for_status()?.json().await?;
+        parse_scores(&response, count)
+            .ok_or_else(|| Error::Provider("response without relevance scores".to_string()))
+    }
+}
+
+/// Puts the candidates in the order of their positions and keeps the first `limit` of them.
+pub(crate) fn reorder<T>(candidates: Vec<T>, order: &[usize], limit: usize) -> Vec<T> {
+    let mut candidates: Vec<_> = candidates.into_iter().map(Some).collect();
+    order
+        .iter()
+        .filter_map(|position| candidates.get_mut(*position)?.take())
+        .take(limit)
+        .collect()
+}
+
+/// Scores of the documents from the results of the rerank API, which refer to the documents by
+/// their index. Documents without a result get the lowest score.
+fn parse_scores(response: &Value, count: usize) -> Option<Vec<f32>> {
+    let mut scores = vec![f32::NEG_INFINITY; count];
+    for result in response["results"].as_array()? {
+        let index = result["index"].as_u64()? as usize;
+        *scores.get_mut(index)? = result["relevance_score"].as_f64()? as f32;
+    }
+    Some(scores)
+}
+
+/// Orders the scored positions by descending score, and then the remaining positions up to
+/// `count`. Ties keep the original order.
+fn order(count: usize, positions: &[usize], scores: &[f32]) -> Vec<usize> {
+    let mut scored: Vec<_> = positions.iter().copied().zip(scores).collect();
+    scored.sort_by(|(_, lhs), (_, rhs)| rhs.total_cmp(lhs));
+    let mut unscored = vec![true; count];
+    scored
+        .iter()
+        .for_each(|(position, _)| unscored[*position] = false);
+    scored
+        .into_iter()
+        .map(|(position, _)| position)
+        .chain((0..count).filter(|position| unscored[*position]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::tests::MockSimDb;
+    use axum::Json;
+    use axum::Router;
+    use axum::http::HeaderMap;
+    use axum::routing::post;
+    use futures::FutureExt;
+    use scylla::value::CqlValue;
+    use tokio::net::TcpListener;
+
+    /// Spawns a fake Cohere API scoring a document by its length.
+    async fn spawn_provider() -> String {
+        let router = Router::new().route(
+            "/rerank",
+            post(|headers: HeaderMap, Json(body): Json<Value>| async move {
+                assert_eq!(headers["authorization"], "Bearer secret");
+                assert_eq!(body["model"], "reranker");
+                assert_eq!(body["query"], "query");
+                let results: Vec<_> = body["documents"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .enumerate()
+                    .map(|(index, document)| {
+                        json!({
+                            "index": index,
+                            "relevance_score": document.as_str().unwrap().len(),
+                        })
+                    })
+                    .collect();
+                Json(json!({ "results": results }))
+            }),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        format!("http://{addr}")
+    }
+
+    #[tokio::test]
+    async fn candidates_are_ordered_by_scores_of_their_texts() {
+        let mut db = MockSimDb::new();
+        db.expect_get_texts()
+            .returning(|_, _, column, _, primary_keys, tx| {
+                assert_eq!(column.as_ref(), "text");
+                assert_eq!(primary_keys.len(), 4);
+                async move {
+                    tx.send(Ok(vec![
+                        Some("a".to_string()),
+                        None,
+                        Some("abc".to_string()),
+                        Some("ab".to_string()),
+                    ]))
+                    .unwrap();
+                }
+                .boxed()
+            });
+        let reranker = Reranker::new(
+            RerankConfig {
+                provider: RerankProvider::Cohere,
+                uri: Some(spawn_provider().await),
+                model: "reranker".to_string(),
+                api_key: Some(secrecy::SecretString::new("secret".into())),
+                oversampling: default_oversampling(),
+                timeout: default_timeout(),
+            },
+            crate::db::tests::new(db),
+        );
+        let primary_keys: Vec<_> = (0..4)
+            .map(|id| PrimaryKey::from([CqlValue::Int(id)]))
+            .collect();
+
+        let order = reranker
+            .rerank(
+                "query",
+                TextColumn {
+                    keyspace: "ks".into(),
+                    table: "tbl".into(),
+                    column: "text".into(),
+                    primary_key_columns: NonemptyArc::new(["id"]).unwrap(),
+                },
+                &primary_keys,
+            )
+            .await
+            .unwrap();
+        assert_eq!(order, vec![2, 3, 0, 1]);
+    }
+
+    #[test]
+    fn scores_are_parsed_from_provider_responses() {
+        assert_eq!(
+            parse_scores(
+                &json!({ "results": [
+                    { "index": 1, "relevance_score": 0.5 },
+                    { "index": 0, "relevance_score": 0.25 },
+                ] }),
+                3
+            ),
+            Some(vec![0.25, 0.5, f32::NEG_INFINITY])
+        );
+        assert_eq!(
+            parse_scores(
+                &json!({ "results": [{ "index": 3, "relevance_score": 0.5 }] }),
+                3
+            ),
+            None
+        );
+        assert_eq!(parse_scores(&json!({}), 3), None);
+    }
+
+    #[test]
+    fn candidates_are_reordered_and_truncated() {
+        assert_eq!(reorder(vec!['a', 'b', 'c'], &[2, 0, 1], 2), vec!['c', 'a']);
+        assert_eq!(reorder(vec!['a', 'b'], &[1, 5, 1, 0], 3), vec!['b', 'a']);
+    }
+
+    #[test]
+    fn ties_and_unscored_candidates_keep_their_order() {
+        assert_eq!(order(4, &[1, 2, 3], &[0.5, 1.0, 0.5]), vec![2, 1, 3, 0]);
+        assert_eq!(order(2, &[], &[]), vec![0, 1]);
+    }
+}
@@ -0,0 +1,102 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Rescoring of the candidates of an ANN search in a quantized index.
+//!
+//! The distances of a quantized index are approximate, so some of the nearest neighbors may be
+//! ranked below the limit of the search. The search returns more candidates than requested, their
+//! full precision vectors are read from the table, and the candidates are ordered by their exact
+//! distances to the query.
+
+use crate::Distance;
+use crate::PrimaryKey;
+use crate::SpaceType;
+use crate::Vector;
+use crate::db_index::DbIndex;
+use crate::db_index::DbIndexExt;
+use crate::vs_index::spill;
+use anyhow::ensure;
+use tokio::sync::mpsc;
+
+/// Returns the nearest `limit` candidates by the exact distances of their vectors read by
+/// `db_index`.
+pub(crate) async fn rescore(
+    db_index: &mpsc::Sender<DbIndex>,
+    query: &Vector,
+    space_type: SpaceType,
+    primary_keys: Vec<PrimaryKey>,
+    limit: usize,
+) -> anyhow::Result<(Vec<PrimaryKey>, Vec<Distance>)> {
+    let vectors = db_index.get_vectors(primary_keys.clone()).await?;
+    ensure!(
+        vectors.len() == primary_keys.len(),
+        "read {} vectors of {} candidates",
+        vectors.len(),
+        primary_keys.len()
+    );
+    rescored(
+        query,
+        space_type,
+        primary_keys.into_iter().zip(vectors),
+        limit,
+    )
+}
+
+/// Orders the candidates by the exact distances of their vectors to the query. Candidates without a
+/// vector, removed since the search, are dropped.
+fn rescored(
+    query: &Vector,
+    space_type: SpaceType,
+    candidates: impl IntoIterator<Item = (PrimaryKey, Option<Vector>)>,
+    limit: usize,
+) -> anyhow::Result<(Vec<PrimaryKey>, Vec<Distance>)> {
+    let mut candidates: Vec<_> = candidates
+        .into_iter()
+        .filter_map(|(primary_key, vector)| {
+            let vector = vector.filter(|vector| vector.len() == query.len())?;
+            let distance = spill::exact_distance(space_type, query.as_slice(), vector.as_slice());
+            Some((primary_key, distance))
+        })
+        .collect();
+    candidates.sort_by(|(_, lhs), (_, rhs)| lhs.total_cmp(rhs));
+    candidates.truncate(limit);
+    candidates
+        .into_iter()
+        .map(|(primary_key, distance)| {
+            Distance::try_from((distance, space_type, query.dim()))
+                .map(|distance| (primary_key, distance))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map(|candidates| candidates.into_iter().unzip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scylla::value::CqlValue;
+
+    fn key(idx: i32) -> PrimaryKey {
+        [CqlValue::Int(idx)].into()
+    }
+
+    #[test]
+    fn candidates_are_ordered_by_exact_distances() {
+        let query = Vector::from(vec![1.0, 0.0]);
+        let candidates = [
+            (key(1), Some(vec![0.0, 1.0].into())),
+            (key(2), None),
+            (key(3), Some(vec![1.0, 0.1].into())),
+            (key(4), Some(vec![1.0, 0.5].into())),
+            (key(5), Some(vec![1.0].into())),
+        ];
+
+        let (primary_keys, distances) =
+            rescored(&query, SpaceType::Euclidean, candidates, 2).unwrap();
+
+        assert_eq!(primary_keys, [key(3), key(4)]);
+        assert!((f32::from(distances[0]) - 0.01).abs() < 1e-6);
+        assert!((f32::from(distances[1]) - 0.25).abs() < 1e-6);
+    }
+}
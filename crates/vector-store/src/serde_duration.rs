@@ -0,0 +1,79 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Serde support for durations in the human readable format used by env variables, e.g. `100ms`.
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serializer;
+use serde::de::Error;
+use std::time::Duration;
+
+fn format(value: Duration) -> String {
+    humantime::format_duration(value).to_string()
+}
+
+fn parse<E: Error>(value: &str) -> Result<Duration, E> {
+    value
+        .parse::<humantime::Duration>()
+        .map(Into::into)
+        .map_err(E::custom)
+}
+
+pub(crate) fn serialize<S: Serializer>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&format(*value))
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Duration, D::Error> {
+    parse(&String::deserialize(deserializer)?)
+}
+
+pub(crate) mod option {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Option<Duration>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => serializer.serialize_some(&format(*value)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Duration>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|value| parse(&value))
+            .transpose()
+    }
+}
+
+pub(crate) mod option_vec {
+    use super::*;
+
+    pub(crate) fn serialize<S: Serializer>(
+        value: &Option<Vec<Duration>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match value {
+            Some(value) => {
+                serializer.serialize_some(&value.iter().map(|v| format(*v)).collect::<Vec<_>>())
+            }
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<Duration>>, D::Error> {
+        Option::<Vec<String>>::deserialize(deserializer)?
+            .map(|values| values.iter().map(|value| parse(value)).collect())
+            .transpose()
+    }
+}
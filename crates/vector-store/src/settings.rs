@@ -0,0 +1,592 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+/// A setting read by [`crate::load_config`]. Every setting can be given as an environment
+/// variable or as a command line flag named after it without the `VECTOR_STORE_` prefix.
+pub struct Setting {
+    pub env: &'static str,
+    pub flag: &'static str,
+    pub value_name: &'static str,
+    pub help: &'static str,
+}
+
+/// All settings read by [`crate::load_config`], in the order they are listed by `--help`.
+pub const SETTINGS: &[Setting] = &[
+    Setting {
+        env: "VECTOR_STORE_URI",
+        flag: "uri",
+        value_name: "HOST:PORT",
+        help: "Comma separated bind addresses and listening ports of HTTP(S) API (host:port)",
+    },
+    Setting {
+        env: "VECTOR_STORE_TLS_CERT_PATH",
+        flag: "tls-cert-path",
+        value_name: "PATH",
+        help: "Path to the TLS certificate file to enable HTTPS",
+    },
+    Setting {
+        env: "VECTOR_STORE_TLS_KEY_PATH",
+        flag: "tls-key-path",
+        value_name: "PATH",
+        help: "Path to the TLS private key file to enable HTTPS",
+    },
+    Setting {
+        env: "VECTOR_STORE_TLS_FILE_CHECK_INTERVAL",
+        flag: "tls-file-check-interval",
+        value_name: "DURATION",
+        help: "How often TLS certificate files are checked for changes",
+    },
+    Setting {
+        env: "VECTOR_STORE_MTLS_URI",
+        flag: "mtls-uri",
+        value_name: "HOST:PORT",
+        help: "Comma separated bind addresses and listening ports of the mTLS API endpoint (host:port)",
+    },
+    Setting {
+        env: "VECTOR_STORE_GRPC_URI",
+        flag: "grpc-uri",
+        value_name: "HOST:PORT",
+        help: "Bind address and listening port of the gRPC API, served only when the `grpc` feature is compiled in (host:port)",
+    },
+    Setting {
+        env: "VECTOR_STORE_MTLS_CA_CERT_PATH",
+        flag: "mtls-ca-cert-path",
+        value_name: "PATH",
+        help: "Path to the CA certificate bundle used to verify mTLS client certificates",
+    },
+    Setting {
+        env: "VECTOR_STORE_HTTP2",
+        flag: "http2",
+        value_name: "BOOL",
+        help: "Accept HTTP/2 connections next to HTTP/1",
+    },
+    Setting {
+        env: "VECTOR_STORE_HTTP_KEEP_ALIVE",
+        flag: "http-keep-alive",
+        value_name: "BOOL",
+        help: "Keep HTTP/1 connections open between requests",
+    },
+    Setting {
+        env: "VECTOR_STORE_HTTP_HEADER_READ_TIMEOUT",
+        flag: "http-header-read-timeout",
+        value_name: "DURATION",
+        help: "Time to receive request headers, also bounding idle HTTP/1 keep-alive connections",
+    },
+    Setting {
+        env: "VECTOR_STORE_HTTP2_KEEP_ALIVE_INTERVAL",
+        flag: "http2-keep-alive-interval",
+        value_name: "DURATION",
+        help: "Interval of HTTP/2 pings on idle connections",
+    },
+    Setting {
+        env: "VECTOR_STORE_HTTP2_KEEP_ALIVE_TIMEOUT",
+        flag: "http2-keep-alive-timeout",
+        value_name: "DURATION",
+        help: "Time to wait for an HTTP/2 ping acknowledgement",
+    },
+    Setting {
+        env: "VECTOR_STORE_HTTP2_MAX_CONCURRENT_STREAMS",
+        flag: "http2-max-concurrent-streams",
+        value_name: "COUNT",
+        help: "Maximum number of concurrent HTTP/2 streams per connection",
+    },
+    Setting {
+        env: "VECTOR_STORE_HTTP_MAX_CONNECTIONS",
+        flag: "http-max-connections",
+        value_name: "COUNT",
+        help: "Maximum number of open connections per HTTP server",
+    },
+    Setting {
+        env: "VECTOR_STORE_HTTP_MAX_CONCURRENT_REQUESTS",
+        flag: "http-max-concurrent-requests",
+        value_name: "COUNT",
+        help: "Maximum number of requests processed at once per HTTP server",
+    },
+    Setting {
+        env: "VECTOR_STORE_SCYLLADB_URI",
+        flag: "scylladb-uri",
+        value_name: "HOST:PORT",
+        help: "The connection endpoint to ScyllaDB server",
+    },
+    Setting {
+        env: "VECTOR_STORE_SCYLLADB_USERNAME",
+        flag: "scylladb-username",
+        value_name: "USERNAME",
+        help: "The username for authenticating with ScyllaDB",
+    },
+    Setting {
+        env: "VECTOR_STORE_SCYLLADB_PASSWORD_FILE",
+        flag: "scylladb-password-file",
+        value_name: "PATH",
+        help: "Path to a file containing the password for ScyllaDB authentication",
+    },
+    Setting {
+        env: "VECTOR_STORE_SCYLLADB_CERTIFICATE_FILE",
+        flag: "scylladb-certificate-file",
+        value_name: "PATH",
+        help: "Path to a TLS certificate file for ScyllaDB authentication",
+    },
+    Setting {
+        env: "VECTOR_STORE_SCYLLADB_CLIENT_CERTIFICATE_FILE",
+        flag: "scylladb-client-certificate-file",
+        value_name: "PATH",
+        help: "Path to a TLS client certificate file authenticating the connections to ScyllaDB",
+    },
+    Setting {
+        env: "VECTOR_STORE_SCYLLADB_CLIENT_KEY_FILE",
+        flag: "scylladb-client-key-file",
+        value_name: "PATH",
+        help: "Path to the private key file of the TLS client certificate",
+    },
+    Setting {
+        env: "VECTOR_STORE_DISABLE_COLORS",
+        flag: "disable-colors",
+        value_name: "BOOL",
+        help: "Disable ANSI colors in log output",
+    },
+    Setting {
+        env: "VECTOR_STORE_OPENSEARCH_URI",
+        flag: "opensearch-uri",
+        value_name: "URL",
+        help: "A connection endpoint to an OpenSearch instance HTTP API, replaces USearch",
+    },
+    Setting {
+        env: "VECTOR_STORE_OPENSEARCH_ENGINE",
+        flag: "opensearch-engine",
+        value_name: "ENGINE",
+        help: "k-NN engine of the indexes created in OpenSearch (faiss, lucene or nmslib)",
+    },
+    Setting {
+        env: "VECTOR_STORE_FAISS_INDEX",
+        flag: "faiss-index",
+        value_name: "DESCRIPTION",
+        help: "A FAISS index factory description, like `HNSW32` or `IVF1024,Flat`, replaces USearch",
+    },
+    Setting {
+        env: "VECTOR_STORE_FLAT_INDEX_MAX_ROWS",
+        flag: "flat-index-max-rows",
+        value_name: "COUNT",
+        help: "Indexes up to this number of rows are searched exactly, before moving to the index backend",
+    },
+    Setting {
+        env: "VECTOR_STORE_THREADS",
+        flag: "threads",
+        value_name: "COUNT",
+        help: "How many threads should be used for indexing",
+    },
+    Setting {
+        env: "VECTOR_STORE_MEMORY_LIMIT",
+        flag: "memory-limit",
+        value_name: "BYTES",
+        help: "How much memory (in bytes) could be in use to allow allocation more memory for the index",
+    },
+    Setting {
+        env: "VECTOR_STORE_MEMORY_USAGE_CHECK_INTERVAL",
+        flag: "memory-usage-check-interval",
+        value_name: "DURATION",
+        help: "How frequently available memory should be checked",
+    },
+    Setting {
+        env: "VECTOR_STORE_CDC_MODES",
+        flag: "cdc-modes",
+        value_name: "JSON",
+        help: "CDC readers of indexes as JSON (`both`, `wide` or `fine` by `keyspace.index`)",
+    },
+    Setting {
+        env: "VECTOR_STORE_TOKEN_RANGES",
+        flag: "token-ranges",
+        value_name: "RANGES",
+        help: "Token ranges of the rows indexed by the node (`local` or `begin:end,...`)",
+    },
+    Setting {
+        env: "VECTOR_STORE_KEYSPACE_PARTITIONS",
+        flag: "keyspace-partitions",
+        value_name: "JSON",
+        help: "Dedicated resources for vector indexes of keyspaces as JSON",
+    },
+    Setting {
+        env: "VECTOR_STORE_KEY_STORAGE_DIR",
+        flag: "key-storage-dir",
+        value_name: "PATH",
+        help: "Directory of the files the primary keys of indexes are mapped from",
+    },
+    Setting {
+        env: "VECTOR_STORE_KEY_INTERNING",
+        flag: "key-interning",
+        value_name: "BOOL",
+        help: "Store partition key columns shared by primary keys once per index",
+    },
+    Setting {
+        env: "VECTOR_STORE_VECTOR_SPILL_DIR",
+        flag: "vector-spill-dir",
+        value_name: "PATH",
+        help: "Directory of the files full precision vectors of quantized indexes are spilled to",
+    },
+    Setting {
+        env: "VECTOR_STORE_INDEX_MAX_DIMENSIONS",
+        flag: "index-max-dimensions",
+        value_name: "COUNT",
+        help: "Maximum dimensions of a new vector index",
+    },
+    Setting {
+        env: "VECTOR_STORE_INDEX_MAX_CONNECTIVITY",
+        flag: "index-max-connectivity",
+        value_name: "COUNT",
+        help: "Maximum connectivity of a new vector index",
+    },
+    Setting {
+        env: "VECTOR_STORE_INDEX_MAX_EXPANSION_ADD",
+        flag: "index-max-expansion-add",
+        value_name: "COUNT",
+        help: "Maximum expansion_add of a new vector index",
+    },
+    Setting {
+        env: "VECTOR_STORE_INDEX_MAX_EXPANSION_SEARCH",
+        flag: "index-max-expansion-search",
+        value_name: "COUNT",
+        help: "Maximum expansion_search of a new vector index",
+    },
+    Setting {
+        env: "VECTOR_STORE_CLUSTER_NODE_ID",
+        flag: "cluster-node-id",
+        value_name: "ID",
+        help: "Identifier of this node in clustering mode",
+    },
+    Setting {
+        env: "VECTOR_STORE_CLUSTER_NODES",
+        flag: "cluster-nodes",
+        value_name: "ID=URL[,ID=URL...]",
+        help: "Seed nodes of the cluster, other nodes are learned from them by gossip",
+    },
+    Setting {
+        env: "VECTOR_STORE_CLUSTER_ZONES",
+        flag: "cluster-zones",
+        value_name: "ID=ZONE[,ID=ZONE...]",
+        help: "Availability zones of the cluster nodes, queries are forwarded to the local zone first",
+    },
+    Setting {
+        env: "VECTOR_STORE_CLUSTER_REPLICATION_FACTOR",
+        flag: "cluster-replication-factor",
+        value_name: "COUNT",
+        help: "Number of cluster nodes serving each index, each of them scanning ScyllaDB on its own",
+    },
+    Setting {
+        env: "VECTOR_STORE_CLUSTER_PROBE_INTERVAL",
+        flag: "cluster-probe-interval",
+        value_name: "DURATION",
+        help: "How often the peers of the cluster are probed and gossiped with",
+    },
+    Setting {
+        env: "VECTOR_STORE_CLUSTER_FAILURE_THRESHOLD",
+        flag: "cluster-failure-threshold",
+        value_name: "COUNT",
+        help: "Number of failed probes in a row after which a peer is considered down",
+    },
+    Setting {
+        env: "VECTOR_STORE_CLUSTER_FORWARD_TIMEOUT",
+        flag: "cluster-forward-timeout",
+        value_name: "DURATION",
+        help: "How long a replica may take to answer a forwarded query before the next one is tried",
+    },
+    Setting {
+        env: "VECTOR_STORE_CONSUL_URI",
+        flag: "consul-uri",
+        value_name: "URL",
+        help: "URL of the Consul agent to register the node at",
+    },
+    Setting {
+        env: "VECTOR_STORE_CONSUL_SERVICE_NAME",
+        flag: "consul-service-name",
+        value_name: "NAME",
+        help: "Name of the service registered in Consul",
+    },
+    Setting {
+        env: "VECTOR_STORE_CONSUL_SERVICE_ADDRESS",
+        flag: "consul-service-address",
+        value_name: "ADDRESS",
+        help: "Address of the service registered in Consul",
+    },
+    Setting {
+        env: "VECTOR_STORE_EMBEDDING_PROVIDER",
+        flag: "embedding-provider",
+        value_name: "PROVIDER",
+        help: "Provider embedding the texts of ANN queries (openai, cohere, bedrock, onnx)",
+    },
+    Setting {
+        env: "VECTOR_STORE_EMBEDDING_URI",
+        flag: "embedding-uri",
+        value_name: "URL",
+        help: "Base URL of the embedding provider API",
+    },
+    Setting {
+        env: "VECTOR_STORE_EMBEDDING_MODEL",
+        flag: "embedding-model",
+        value_name: "MODEL",
+        help: "Model of the embedding provider, or the directory of a local ONNX model",
+    },
+    Setting {
+        env: "VECTOR_STORE_EMBEDDING_INDEX_MODELS",
+        flag: "embedding-index-models",
+        value_name: "INDEX=MODEL,...",
+        help: "Models of indexes as keyspace.index=model pairs separated by commas",
+    },
+    Setting {
+        env: "VECTOR_STORE_EMBEDDING_API_KEY_FILE",
+        flag: "embedding-api-key-file",
+        value_name: "PATH",
+        help: "Path to a file containing the API key of the embedding provider",
+    },
+    Setting {
+        env: "VECTOR_STORE_EMBEDDING_CACHE_SIZE",
+        flag: "embedding-cache-size",
+        value_name: "COUNT",
+        help: "Number of cached embeddings of query texts",
+    },
+    Setting {
+        env: "VECTOR_STORE_EMBEDDING_CACHE_TTL",
+        flag: "embedding-cache-ttl",
+        value_name: "DURATION",
+        help: "Time after which a cached embedding of a query text is computed again",
+    },
+    Setting {
+        env: "VECTOR_STORE_EMBEDDING_RATE_LIMIT",
+        flag: "embedding-rate-limit",
+        value_name: "COUNT",
+        help: "Requests per second sent to the embedding provider",
+    },
+    Setting {
+        env: "VECTOR_STORE_EMBEDDING_TIMEOUT",
+        flag: "embedding-timeout",
+        value_name: "DURATION",
+        help: "Timeout of requests to the embedding provider",
+    },
+    Setting {
+        env: "VECTOR_STORE_RERANK_PROVIDER",
+        flag: "rerank-provider",
+        value_name: "PROVIDER",
+        help: "Provider of the cross-encoder model reranking the results of ANN queries (cohere, onnx)",
+    },
+    Setting {
+        env: "VECTOR_STORE_RERANK_URI",
+        flag: "rerank-uri",
+        value_name: "URL",
+        help: "Base URL of the rerank provider API",
+    },
+    Setting {
+        env: "VECTOR_STORE_RERANK_MODEL",
+        flag: "rerank-model",
+        value_name: "MODEL",
+        help: "Model of the rerank provider, or the directory of a local ONNX cross-encoder",
+    },
+    Setting {
+        env: "VECTOR_STORE_RERANK_API_KEY_FILE",
+        flag: "rerank-api-key-file",
+        value_name: "PATH",
+        help: "Path to a file containing the API key of the rerank provider",
+    },
+    Setting {
+        env: "VECTOR_STORE_RERANK_OVERSAMPLING",
+        flag: "rerank-oversampling",
+        value_name: "FACTOR",
+        help: "How many times more candidates than requested are reranked by default",
+    },
+    Setting {
+        env: "VECTOR_STORE_RERANK_TIMEOUT",
+        flag: "rerank-timeout",
+        value_name: "DURATION",
+        help: "Timeout of requests to the rerank provider",
+    },
+    Setting {
+        env: "VECTOR_STORE_AUTH_TOKENS_FILE",
+        flag: "auth-tokens-file",
+        value_name: "PATH",
+        help: "Path to a file of the bearer tokens accepted by the HTTP API, one per line",
+    },
+    Setting {
+        env: "VECTOR_STORE_AUTH_JWT_KEY_FILE",
+        flag: "auth-jwt-key-file",
+        value_name: "PATH",
+        help: "Path to a file of the key validating JSON Web Tokens accepted by the HTTP API",
+    },
+    Setting {
+        env: "VECTOR_STORE_AUTH_JWT_ALGORITHM",
+        flag: "auth-jwt-algorithm",
+        value_name: "ALGORITHM",
+        help: "Algorithm of the signatures of JSON Web Tokens",
+    },
+    Setting {
+        env: "VECTOR_STORE_AUTH_PEER_TOKEN_FILE",
+        flag: "auth-peer-token-file",
+        value_name: "PATH",
+        help: "Path to a file of the bearer token the nodes of a cluster send to each other",
+    },
+    Setting {
+        env: "VECTOR_STORE_CHANNEL_SIZE_PER_WORKER",
+        flag: "channel-size-per-worker",
+        value_name: "COUNT",
+        help: "Capacity of actor channels per runtime worker thread",
+    },
+    Setting {
+        env: "VECTOR_STORE_FTS_COMMIT_INTERVAL",
+        flag: "fts-commit-interval",
+        value_name: "DURATION",
+        help: "How often full-text indexes commit written documents",
+    },
+    Setting {
+        env: "VECTOR_STORE_FTS_MAX_UNCOMMITTED_DOCS",
+        flag: "fts-max-uncommitted-docs",
+        value_name: "COUNT",
+        help: "Number of uncommitted documents after which a full-text index commits immediately",
+    },
+    Setting {
+        env: "VECTOR_STORE_SYSTEMD_READY_STATUS",
+        flag: "systemd-ready-status",
+        value_name: "STATUS",
+        help: "Node status at which systemd is notified that the service is ready (initializing, connecting-to-db, discovering-indexes, indexing-embeddings, serving)",
+    },
+    Setting {
+        env: "VECTOR_STORE_CQL_CONNECTION_TIMEOUT",
+        flag: "cql-connection-timeout",
+        value_name: "DURATION",
+        help: "CQL driver's connection timeout",
+    },
+    Setting {
+        env: "VECTOR_STORE_CQL_KEEPALIVE_INTERVAL",
+        flag: "cql-keepalive-interval",
+        value_name: "DURATION",
+        help: "CQL driver's keepalive interval",
+    },
+    Setting {
+        env: "VECTOR_STORE_CQL_KEEPALIVE_TIMEOUT",
+        flag: "cql-keepalive-timeout",
+        value_name: "DURATION",
+        help: "CQL driver's keepalive timeout",
+    },
+    Setting {
+        env: "VECTOR_STORE_CQL_TCP_KEEPALIVE_INTERVAL",
+        flag: "cql-tcp-keepalive-interval",
+        value_name: "DURATION",
+        help: "CQL driver's TCP keepalive interval",
+    },
+    Setting {
+        env: "VECTOR_STORE_CQL_URI_TRANSLATION_MAP",
+        flag: "cql-uri-translation-map",
+        value_name: "JSON",
+        help: "For testing. Translation map for cql cluster addresses as JSON",
+    },
+    Setting {
+        env: "VECTOR_STORE_CDC_SAFETY_INTERVAL",
+        flag: "cdc-safety-interval",
+        value_name: "DURATION",
+        help: "Wide-framed CDC reader's safety interval",
+    },
+    Setting {
+        env: "VECTOR_STORE_CDC_SLEEP_INTERVAL",
+        flag: "cdc-sleep-interval",
+        value_name: "DURATION",
+        help: "Wide-framed CDC reader's sleep interval",
+    },
+    Setting {
+        env: "VECTOR_STORE_CDC_FINE_SAFETY_INTERVAL",
+        flag: "cdc-fine-safety-interval",
+        value_name: "DURATION",
+        help: "Fine-grained CDC reader's safety interval",
+    },
+    Setting {
+        env: "VECTOR_STORE_CDC_FINE_SLEEP_INTERVAL",
+        flag: "cdc-fine-sleep-interval",
+        value_name: "DURATION",
+        help: "Fine-grained CDC reader's sleep interval",
+    },
+    Setting {
+        env: "VECTOR_STORE_MONITOR_INDEXES_INTERVAL",
+        flag: "monitor-indexes-interval",
+        value_name: "DURATION",
+        help: "How often to poll ScyllaDB for schema changes",
+    },
+    Setting {
+        env: "VECTOR_STORE_INDEX_STATUS_UPDATE_INTERVAL",
+        flag: "index-status-update-interval",
+        value_name: "DURATION",
+        help: "How often to sync index status into the engine's cached state",
+    },
+    Setting {
+        env: "VECTOR_STORE_SHUTDOWN_DELAY",
+        flag: "shutdown-delay",
+        value_name: "DURATION",
+        help: "How long to keep serving after a shutdown signal while `/readyz` reports not ready",
+    },
+    Setting {
+        env: "VECTOR_STORE_USEARCH_SIMULATOR",
+        flag: "usearch-simulator",
+        value_name: "DELAYS",
+        help: "Enable simulator for USearch with delays of simulated operations (search:add-remove:reserve)",
+    },
+    Setting {
+        env: "VECTOR_STORE_USE_DISKANN",
+        flag: "use-diskann",
+        value_name: "BOOL",
+        help: "Use DiskANN as the indexing engine instead of USearch",
+    },
+    Setting {
+        env: "VECTOR_STORE_DISKANN_ALPHA",
+        flag: "diskann-alpha",
+        value_name: "ALPHA",
+        help: "DiskANN trade-off between index quality and build time",
+    },
+    Setting {
+        env: "VECTOR_STORE_FULLTEXT_INDEXES",
+        flag: "fulltext-indexes",
+        value_name: "BOOL",
+        help: "Enable full-text indexes",
+    },
+    Setting {
+        env: "VECTOR_STORE_ALTER_INDEX_SIMULATOR",
+        flag: "alter-index-simulator",
+        value_name: "BOOL",
+        help: "Enable simulator for missing ALTER INDEX",
+    },
+    Setting {
+        env: "VECTOR_STORE_ACTOR_TRACING",
+        flag: "actor-tracing",
+        value_name: "BOOL",
+        help: "Log every message processed by internal actors at the trace level",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn flags_are_named_after_variables() {
+        for setting in SETTINGS {
+            let name = setting.env.strip_prefix("VECTOR_STORE_").unwrap();
+            assert_eq!(setting.flag, name.to_lowercase().replace('_', "-"));
+        }
+        let envs: HashSet<_> = SETTINGS.iter().map(|setting| setting.env).collect();
+        assert_eq!(envs.len(), SETTINGS.len());
+    }
+
+    #[test]
+    fn every_variable_read_by_config_manager_is_a_setting() {
+        let source = include_str!("config_manager.rs");
+        let source = &source[..source.find("mod tests {").unwrap()];
+        let envs: HashSet<_> = SETTINGS.iter().map(|setting| setting.env).collect();
+        source
+            .match_indices("\"VECTOR_STORE_")
+            .map(|(start, _)| {
+                let literal = &source[start + 1..];
+                &literal[..literal.find('"').unwrap()]
+            })
+            .filter(|literal| {
+                literal
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            })
+            .for_each(|env| assert!(envs.contains(env), "{env} is not listed in SETTINGS"));
+    }
+}
@@ -0,0 +1,177 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Notifications for systemd services with `Type=notify`.
+//!
+//! When systemd runs the service it sets `NOTIFY_SOCKET`. `READY=1` is sent once the node
+//! reaches the configured status and `WATCHDOG=1` keepalives are sent while the node state
+//! actor responds, if `WatchdogSec=` is configured for the unit.
+
+use crate::node_state::LifecycleEvent;
+use crate::node_state::NodeState;
+use crate::node_state::NodeStateExt;
+use crate::node_state::NodeStatus;
+use std::future;
+use std::io;
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::net::SocketAddr;
+use std::os::unix::net::UnixDatagram;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio::time;
+use tokio::time::Interval;
+use tracing::Instrument;
+use tracing::debug_span;
+use tracing::info;
+use tracing::warn;
+
+const NOTIFY_SOCKET_ENV: &str = "NOTIFY_SOCKET";
+const WATCHDOG_USEC_ENV: &str = "WATCHDOG_USEC";
+const WATCHDOG_PID_ENV: &str = "WATCHDOG_PID";
+
+struct Notifier {
+    socket: UnixDatagram,
+    addr: SocketAddr,
+}
+
+impl Notifier {
+    fn new(path: &str) -> io::Result<Self> {
+        let addr = match path.strip_prefix('@') {
+            Some(name) => SocketAddr::from_abstract_name(name)?,
+            None => SocketAddr::from_pathname(path)?,
+        };
+        let socket = UnixDatagram::unbound()?;
+        socket.set_nonblocking(true)?;
+        Ok(Self { socket, addr })
+    }
+
+    fn notify(&self, state: &str) {
+        if let Err(err) = self.socket.send_to_addr(state.as_bytes(), &self.addr) {
+            warn!("unable to send {state:?} to systemd: {err}");
+        }
+    }
+}
+
+/// Returns the watchdog timeout requested by systemd for this process.
+fn watchdog_timeout() -> Option<Duration> {
+    if let Ok(pid) = std::env::var(WATCHDOG_PID_ENV)
+        && pid.parse::<u32>().ok() != Some(std::process::id())
+    {
+        return None;
+    }
+    let usec = std::env::var(WATCHDOG_USEC_ENV).ok()?.parse().ok()?;
+    (usec > 0).then(|| Duration::from_micros(usec))
+}
+
+/// Starts sending notifications if the service runs under systemd with `Type=notify`.
+pub(crate) fn start(node_state: mpsc::Sender<NodeState>, ready_status: NodeStatus) {
+    let Ok(path) = std::env::var(NOTIFY_SOCKET_ENV) else {
+        return;
+    };
+    let notifier = match Notifier::new(&path) {
+        Ok(notifier) => notifier,
+        Err(err) => {
+            warn!("unable to use systemd notify socket {path}: {err}");
+            return;
+        }
+    };
+    tokio::spawn(
+        run(notifier, node_state, ready_status, watchdog_timeout())
+            .instrument(debug_span!("systemd")),
+    );
+}
+
+async fn run(
+    notifier: Notifier,
+    node_state: mpsc::Sender<NodeState>,
+    ready_status: NodeStatus,
+    watchdog_timeout: Option<Duration>,
+) {
+    let mut events = node_state.subscribe().await;
+    let mut status = node_state.get_status().await;
+    let mut ready = false;
+    // systemd recommends sending keepalives at half of the timeout.
+    let mut watchdog = watchdog_timeout.map(|timeout| time::interval(timeout / 2));
+
+    loop {
+        if !ready && status >= ready_status {
+            notifier.notify("READY=1");
+            ready = true;
+            info!("notified systemd that the service is ready ({status:?})");
+        }
+
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(LifecycleEvent::NodeStatusChanged(new_status)) => {
+                    status = new_status;
+                    notifier.notify(&format!("STATUS={status:?}"));
+                }
+                Ok(_) => {}
+                Err(RecvError::Lagged(_)) => {
+                    status = node_state.get_status().await;
+                }
+                Err(RecvError::Closed) => break,
+            },
+
+            _ = tick(&mut watchdog) => {
+                let timeout = watchdog_timeout.unwrap_or_default() / 2;
+                match time::timeout(timeout, node_state.get_status()).await {
+                    Ok(_) => notifier.notify("WATCHDOG=1"),
+                    Err(_) => warn!("node state is not responding, skipping systemd keepalive"),
+                }
+            }
+        }
+    }
+}
+
+async fn tick(interval: &mut Option<Interval>) {
+    match interval {
+        Some(interval) => {
+            interval.tick().await;
+        }
+        None => future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node_state::Event;
+    use tempfile::TempDir;
+
+    async fn recv(socket: &tokio::net::UnixDatagram) -> String {
+        let mut buf = [0; 64];
+        let len = time::timeout(Duration::from_secs(5), socket.recv(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        String::from_utf8_lossy(&buf[..len]).into_owned()
+    }
+
+    #[tokio::test]
+    async fn notifies_ready_at_configured_status_and_sends_keepalives() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("notify.sock");
+        let socket = tokio::net::UnixDatagram::bind(&path).unwrap();
+        let node_state = crate::new_node_state().await;
+
+        tokio::spawn(run(
+            Notifier::new(path.to_str().unwrap()).unwrap(),
+            node_state.clone(),
+            NodeStatus::ConnectingToDb,
+            Some(Duration::from_millis(100)),
+        ));
+
+        assert_eq!(recv(&socket).await, "WATCHDOG=1");
+
+        node_state.send_event(Event::ConnectingToDb).await;
+        let mut messages = Vec::new();
+        while !messages.iter().any(|msg| msg == "READY=1") {
+            messages.push(recv(&socket).await);
+        }
+        assert!(messages.contains(&"STATUS=ConnectingToDb".to_string()));
+    }
+}
@@ -0,0 +1,105 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::PrimaryKey;
+use crate::table::primary_id::PrimaryId;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::hash::BuildHasher;
+use std::hash::RandomState;
+
+/// Maps primary keys to their [`PrimaryId`]s without storing the keys.
+///
+/// The table already keeps every key in a dense vector indexed by [`PrimaryId`], so only the hash
+/// of a key is stored here. Candidates sharing the hash are told apart by comparing the key stored
+/// for them, which the caller provides on lookup.
+#[derive(Debug, Default)]
+pub(super) struct KeyIndex {
+    hasher: RandomState,
+    ids: HashMap<u64, PrimaryId>,
+    /// Further ids of keys whose hash is already taken in `ids`.
+    collisions: HashMap<u64, Vec<PrimaryId>>,
+}
+
+impl KeyIndex {
+    pub(super) fn len(&self) -> usize {
+        self.ids.len() + self.collisions.values().map(Vec::len).sum::<usize>()
+    }
+
+    /// Estimated bytes of memory taken by the index.
+    pub(super) fn memory_bytes(&self) -> usize {
+        self.ids.capacity() * (size_of::<u64>() + size_of::<PrimaryId>())
+            + self.collisions.capacity() * (size_of::<u64>() + size_of::<Vec<PrimaryId>>())
+            + self
+                .collisions
+                .values()
+                .map(|ids| ids.capacity() * size_of::<PrimaryId>())
+                .sum::<usize>()
+    }
+
+    /// Returns the id of `key`. `is_key_of` tells whether `key` is the key of a candidate id.
+    pub(super) fn get(
+        &self,
+        key: &PrimaryKey,
+        is_key_of: impl Fn(PrimaryId) -> bool,
+    ) -> Option<PrimaryId> {
+        let hash = self.hasher.hash_one(key);
+        let first = *self.ids.get(&hash)?;
+        if is_key_of(first) {
+            return Some(first);
+        }
+        self.collisions
+            .get(&hash)?
+            .iter()
+            .copied()
+            .find(|id| is_key_of(*id))
+    }
+
+    /// Adds the id of a key which is not in the index yet.
+    pub(super) fn insert(&mut self, key: &PrimaryKey, id: PrimaryId) {
+        let hash = self.hasher.hash_one(key);
+        match self.ids.entry(hash) {
+            Entry::Vacant(entry) => {
+                entry.insert(id);
+            }
+            Entry::Occupied(_) => self.collisions.entry(hash).or_default().push(id),
+        }
+    }
+
+    pub(super) fn ids(&self) -> impl Iterator<Item = PrimaryId> + '_ {
+        self.ids
+            .values()
+            .chain(self.collisions.values().flatten())
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::Idx;
+    use crate::table::primary_id::Epoch;
+    use scylla::value::CqlValue;
+
+    #[test]
+    fn ids_are_found_by_key() {
+        let keys: Vec<PrimaryKey> = (0..100).map(|idx| [CqlValue::Int(idx)].into()).collect();
+        let id = |idx| PrimaryId::try_new(idx, Epoch::new()).unwrap();
+        let mut index = KeyIndex::default();
+        keys.iter()
+            .enumerate()
+            .for_each(|(idx, key)| index.insert(key, id(idx)));
+        let get = |key: &PrimaryKey| index.get(key, |id: PrimaryId| keys[id.idx()] == *key);
+
+        assert_eq!(index.len(), 100);
+        assert_eq!(index.ids().count(), 100);
+        assert!(index.memory_bytes() >= 100 * (size_of::<u64>() + size_of::<PrimaryId>()));
+        for (idx, key) in keys.iter().enumerate() {
+            assert_eq!(get(key), Some(id(idx)));
+        }
+        assert_eq!(get(&[CqlValue::Int(100)].into()), None);
+        assert_eq!(index.get(&keys[0], |_| false), None);
+    }
+}
@@ -7,6 +7,7 @@ mod chunk_timestamps;
 mod column;
 mod column_vec;
 mod column_vec_chunks;
+mod key_index;
 mod partition_id;
 mod primary_id;
 mod vec_chunks;
@@ -21,6 +22,8 @@ use crate::PrimaryKey;
 use crate::Restriction;
 use crate::Timestamp;
 use crate::Vector;
+use crate::invariant_key::KeyArena;
+use crate::memory::IndexMemory;
 use crate::primary_key::normalize;
 use crate::table::chunk_timestamps::ChunkTimestampsExclusive;
 use crate::timestamp::Timestamped;
@@ -32,8 +35,9 @@ use column::Column;
 use column_vec::ColumnVec;
 use column_vec_chunks::ColumnVecChunks;
 use itertools::Itertools;
+use key_index::KeyIndex;
 use num_bigint::BigInt;
-pub(crate) use partition_id::IndexId;
+pub use partition_id::IndexId;
 pub(crate) use partition_id::IndexIdGenerator;
 pub use partition_id::PartitionId;
 use primary_id::Epoch;
@@ -55,7 +59,7 @@ use vec_chunks::Chunk;
 use vec_chunks::VecChunks;
 
 /// Idx is a trait for types that can be used as an index in the column vectors.
-trait Idx {
+pub(crate) trait Idx {
     fn idx(&self) -> usize;
 }
 
@@ -336,9 +340,13 @@ pub struct Table {
     primary_key_columns: NonemptyArc<ColumnName>,
     partition_primary_key_count: usize,
     needs_ck_normalization: bool,
-    primary_ids: BTreeMap<PrimaryKey, PrimaryId>,
+    primary_ids: KeyIndex,
     free_primary_ids: FreePrimaryIds,
     primary_keys: ColumnVec<PrimaryId, Option<PrimaryKey>>,
+    /// Normalized keys of the rows whose key is changed by normalization.
+    normalized_keys: HashMap<PrimaryId, PrimaryKey>,
+    key_arena: KeyArena,
+    memory: IndexMemory,
 
     columns: BTreeMap<ColumnName, Column>,
 
@@ -407,9 +415,12 @@ impl Table {
             .iter()
             .any(|col| matches!(table_columns.get(col), Some(NativeType::Decimal)));
         let mut table = Self {
-            primary_ids: BTreeMap::new(),
+            primary_ids: KeyIndex::default(),
             free_primary_ids: FreePrimaryIds(VecDeque::new()),
             primary_keys: ColumnVec::new(),
+            normalized_keys: HashMap::new(),
+            key_arena: KeyArena::default(),
+            memory: IndexMemory::default(),
             primary_key_columns,
             partition_primary_key_count,
             needs_ck_normalization,
@@ -423,7 +434,28 @@ impl Table {
         Ok(table)
     }
 
-    /// Normalize clustering key columns of a primary key for BTreeMap lookups.
+    /// Stores the primary keys of the table in the given arena. It must be called before any row
+    /// is added to the table.
+    pub(crate) fn with_key_arena(mut self, key_arena: KeyArena) -> Self {
+        self.key_arena = key_arena;
+        self.update_key_maps_memory();
+        self
+    }
+
+    /// Returns the memory accounting of the index the table belongs to.
+    pub(crate) fn memory(&self) -> &IndexMemory {
+        &self.memory
+    }
+
+    fn update_key_maps_memory(&self) {
+        let bytes = self.primary_keys.len() * size_of::<Option<PrimaryKey>>()
+            + self.primary_ids.memory_bytes()
+            + self.normalized_keys.capacity() * (size_of::<PrimaryId>() + size_of::<PrimaryKey>())
+            + self.key_arena.memory_bytes();
+        self.memory.set_key_maps(bytes as u64);
+    }
+
+    /// Normalize clustering key columns of a primary key for key lookups.
     /// Partition key columns are kept as-is; clustering key Decimals are normalized
     /// so that semantically equal values (e.g. 1.0 vs 1.00) produce identical keys.
     ///
@@ -477,6 +509,7 @@ impl Table {
         self.indexes
             .iter_mut()
             .for_each(|(_, index)| index.resize_primary_ids_with(new_size));
+        self.update_key_maps_memory();
         Ok(())
     }
 
@@ -494,21 +527,74 @@ impl Table {
             .is_some_and(|epoch| epoch == primary_id.epoch())
     }
 
+    fn is_in_partition(&self, partition_id: PartitionId, primary_id: PrimaryId) -> bool {
+        if !self.is_valid_primary_id(partition_id, primary_id) {
+            return false;
+        }
+        self.indexes
+            .get(&partition_id.index_id())
+            .is_some_and(|index| match &index.data {
+                IndexData::Global => true,
+                IndexData::Local { ids, .. } => ids
+                    .get(primary_id)
+                    .is_some_and(|id| *id == Some(partition_id)),
+            })
+    }
+
+    fn normalized_primary_id(&self, normalized_key: &PrimaryKey) -> Option<PrimaryId> {
+        let is_key_of = |primary_id| {
+            self.normalized_keys
+                .get(&primary_id)
+                .or_else(|| self.primary_keys.get(primary_id).and_then(Option::as_ref))
+                .is_some_and(|key| key == normalized_key)
+        };
+        self.primary_ids.get(normalized_key, is_key_of)
+    }
+
     fn add_primary_key(&mut self, primary_key: &PrimaryKey) -> anyhow::Result<PrimaryId> {
         let normalized_key = self.normalize_primary_key(primary_key);
 
-        match self.primary_ids.entry(normalized_key) {
-            Entry::Occupied(entry) => Ok(*entry.get()),
-            Entry::Vacant(entry) => {
-                let primary_id = self.free_primary_ids.take_id()?;
-                entry.insert(primary_id);
-                self.primary_keys
-                    .get_mut(primary_id)
-                    .ok_or_else(|| anyhow!("PrimaryId index out of primary keys bounds"))?
-                    .replace(primary_key.clone());
-                Ok(primary_id)
-            }
+        if let Some(primary_id) = self.normalized_primary_id(&normalized_key) {
+            return Ok(primary_id);
+        }
+        let primary_id = self.free_primary_ids.take_id()?;
+        self.primary_ids.insert(&normalized_key, primary_id);
+        // Keys kept for the lifetime of the row are moved to the arena.
+        if normalized_key != *primary_key {
+            self.normalized_keys.insert(
+                primary_id,
+                normalized_key.stored_in(&mut self.key_arena, self.partition_primary_key_count),
+            );
         }
+        let primary_key =
+            primary_key.stored_in(&mut self.key_arena, self.partition_primary_key_count);
+        self.primary_keys
+            .get_mut(primary_id)
+            .ok_or_else(|| anyhow!("PrimaryId index out of primary keys bounds"))?
+            .replace(primary_key);
+        self.update_key_maps_memory();
+        Ok(primary_id)
+    }
+
+    /// Returns the values of the columns of a row stored by the table. Columns without a value are
+    /// empty.
+    pub(crate) fn stored_values(
+        &self,
+        primary_key: &PrimaryKey,
+        columns: &[ColumnName],
+    ) -> Option<Vec<CqlValue>> {
+        let primary_id = self.normalized_primary_id(&self.normalize_primary_key(primary_key))?;
+        Some(
+            columns
+                .iter()
+                .map(|column| {
+                    self.columns
+                        .get(column)
+                        .and_then(|column| column.get(primary_id, &self.primary_keys))
+                        .unwrap_or(CqlValue::Empty)
+                })
+                .collect(),
+        )
     }
 }
 
@@ -841,7 +927,7 @@ impl TableModify for Table {
 
 /// A trait that defines the search operations for the table.
 #[cfg_attr(test, mockall::automock)]
-pub(crate) trait TableSearch {
+pub trait TableSearch {
     fn index_id(&self, index_key: &IndexKey) -> Option<IndexId>;
 
     fn partition_id(
@@ -852,12 +938,25 @@ pub(crate) trait TableSearch {
 
     fn primary_key(&self, partition_id: PartitionId, primary_id: PrimaryId) -> Option<PrimaryKey>;
 
+    /// Returns the values of the columns restrictions can refer to, for backends filtering rows by
+    /// their own copies of the values. Columns without a value are left out.
+    fn values(&self, primary_id: PrimaryId) -> Vec<(ColumnName, CqlValue)>;
+
     fn is_valid_for(
         &self,
         partition_id: PartitionId,
         primary_id: PrimaryId,
         restriction: &Restriction,
     ) -> bool;
+
+    /// Returns primary ids of all rows in the partition which satisfy all given restrictions.
+    /// It is used by backends which cannot call back into the table during a search and need
+    /// the set of allowed rows upfront.
+    fn primary_ids(
+        &self,
+        partition_id: PartitionId,
+        restrictions: &[Restriction],
+    ) -> Vec<PrimaryId>;
 }
 
 impl TableSearch for Table {
@@ -898,6 +997,18 @@ impl TableSearch for Table {
         self.primary_keys.get(primary_id).cloned().flatten()
     }
 
+    #[hotpath::measure]
+    fn values(&self, primary_id: PrimaryId) -> Vec<(ColumnName, CqlValue)> {
+        self.columns
+            .iter()
+            .filter_map(|(name, column)| {
+                column
+                    .get(primary_id, &self.primary_keys)
+                    .map(|value| (name.clone(), value))
+            })
+            .collect()
+    }
+
     #[hotpath::measure]
     fn is_valid_for(
         &self,
@@ -993,6 +1104,23 @@ impl TableSearch for Table {
             }
         }
     }
+
+    #[hotpath::measure]
+    fn primary_ids(
+        &self,
+        partition_id: PartitionId,
+        restrictions: &[Restriction],
+    ) -> Vec<PrimaryId> {
+        self.primary_ids
+            .ids()
+            .filter(|primary_id| self.is_in_partition(partition_id, *primary_id))
+            .filter(|primary_id| {
+                restrictions
+                    .iter()
+                    .all(|restriction| self.is_valid_for(partition_id, *primary_id, restriction))
+            })
+            .collect()
+    }
 }
 
 /// Construct a partition key from the given restrictions.
@@ -1671,6 +1799,131 @@ mod tests {
         }
     }
 
+    #[test]
+    fn stored_values_of_rows() {
+        let index_key = IndexKey::new(&"ks".into(), &"idx".into());
+        let mut table = Table::new(
+            index_key.clone(),
+            NonemptyArc::new([ColumnName::from("p")]).unwrap(),
+            1,
+            None,
+            NonZeroUsize::new(1).unwrap(),
+            Arc::new(["f".into(), "g".into()]),
+            Arc::new(
+                [
+                    ("p".into(), NativeType::Int),
+                    ("f".into(), NativeType::Int),
+                    ("g".into(), NativeType::Text),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        )
+        .unwrap();
+        let pk = PrimaryKey::from([CqlValue::Int(1)]);
+        _ = table
+            .upsert(
+                &index_key,
+                pk.clone(),
+                NonemptyBox::new([
+                    Timestamped::new(
+                        Timestamp::from_millis(10),
+                        Some(DbIndexedValue::Vector(vec![1.0].into())),
+                    ),
+                    Timestamped::new(
+                        Timestamp::from_millis(10),
+                        Some(DbIndexedValue::Filtering(CqlValue::Int(2))),
+                    ),
+                    Timestamped::new(Timestamp::from_millis(10), None),
+                ])
+                .unwrap(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            table.stored_values(&pk, &["g".into(), "f".into(), "p".into()]),
+            Some(vec![CqlValue::Empty, CqlValue::Int(2), CqlValue::Int(1)])
+        );
+        assert_eq!(
+            table.stored_values(&PrimaryKey::from([CqlValue::Int(2)]), &["f".into()]),
+            None
+        );
+        let primary_id = table.normalized_primary_id(&pk).unwrap();
+        assert_eq!(
+            table.values(primary_id),
+            vec![
+                ("f".into(), CqlValue::Int(2)),
+                ("p".into(), CqlValue::Int(1))
+            ]
+        );
+    }
+
+    #[test]
+    fn primary_ids_in_partition_with_restrictions() {
+        let index_key = IndexKey::new(&"ks".into(), &"idx".into());
+        let mut table = Table::new(
+            index_key.clone(),
+            NonemptyArc::new([ColumnName::from("p"), ColumnName::from("c")]).unwrap(),
+            1,
+            Some(NonemptyArc::new([ColumnName::from("p")]).unwrap()),
+            NonZeroUsize::new(1).unwrap(),
+            Arc::new([]),
+            Arc::new(
+                [("p".into(), NativeType::Int), ("c".into(), NativeType::Int)]
+                    .into_iter()
+                    .collect(),
+            ),
+        )
+        .unwrap();
+        let values = || {
+            NonemptyBox::new([Timestamped::new(
+                Timestamp::from_millis(10),
+                Some(DbIndexedValue::Vector(vec![1.0].into())),
+            )])
+            .unwrap()
+        };
+        for (p, c) in [(1, 1), (1, 2), (2, 1)] {
+            table
+                .upsert(
+                    &index_key,
+                    PrimaryKey::from([CqlValue::Int(p), CqlValue::Int(c)]),
+                    values(),
+                )
+                .unwrap();
+        }
+
+        let (partition_id, restrictions) = table
+            .partition_id(
+                &index_key,
+                Some(vec![Restriction::Eq {
+                    lhs: "p".into(),
+                    rhs: CqlValue::Int(1),
+                }]),
+            )
+            .unwrap();
+        assert!(restrictions.is_none());
+        let primary_ids = table.primary_ids(partition_id, &[]);
+        assert_eq!(primary_ids.len(), 2);
+        assert!(primary_ids.iter().all(|primary_id| {
+            table
+                .primary_key(partition_id, *primary_id)
+                .is_some_and(|key| key.get(0) == Some(CqlValue::Int(1)))
+        }));
+
+        let primary_ids = table.primary_ids(
+            partition_id,
+            &[Restriction::Eq {
+                lhs: "c".into(),
+                rhs: CqlValue::Int(2),
+            }],
+        );
+        assert_eq!(primary_ids.len(), 1);
+        assert_eq!(
+            table.primary_key(partition_id, primary_ids[0]).unwrap(),
+            PrimaryKey::from([CqlValue::Int(1), CqlValue::Int(2)])
+        );
+    }
+
     #[test]
     fn upsert_and_delete_unordered() {
         let vector_orig: Vector = vec![1.0].into();
@@ -2250,4 +2503,34 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn key_maps_memory_is_accounted() {
+        let values = NonemptyBox::new([Timestamped::new(
+            Timestamp::from_millis(1),
+            Some(DbIndexedValue::Vector(vec![1.0].into())),
+        )])
+        .unwrap();
+        let index_key = IndexKey::new(&"ks".into(), &"idx".into());
+        let mut table = Table::new(
+            index_key.clone(),
+            NonemptyArc::new(["p"]).unwrap(),
+            1,
+            None,
+            NonZeroUsize::new(1).unwrap(),
+            Arc::new([]),
+            Arc::new([("p".into(), NativeType::Text)].into_iter().collect()),
+        )
+        .unwrap();
+        let reserved = table.memory().usage().key_maps;
+        assert!(reserved >= (Table::INCREMENT_SIZE * size_of::<Option<PrimaryKey>>()) as u64);
+
+        let pk = PrimaryKey::from([CqlValue::Text("p".repeat(64))]);
+        table.upsert(&index_key, pk, values).unwrap();
+
+        let usage = table.memory().usage();
+        assert!(usage.key_maps > reserved);
+        assert_eq!(usage.graph, 0);
+        assert_eq!(usage.pending_queues, 0);
+    }
 }
@@ -50,7 +50,7 @@ impl Idx for PartitionId {
 #[derive(
     Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, derive_more::AsRef, derive_more::From,
 )]
-pub(crate) struct IndexId(u16);
+pub struct IndexId(u16);
 
 impl IndexId {
     const MASK: u16 = u16::MAX;
@@ -70,6 +70,11 @@ impl Timestamp {
         Timestamp(timestamp * 10_000_000)
     }
 
+    /// Returns the timestamp right after this one.
+    pub const fn next(self) -> Self {
+        Self::from_100_nanos(self.0 + 1)
+    }
+
     /// Returns the current time in UTC.
     pub fn now() -> Self {
         let offset_100_ns =
@@ -0,0 +1,216 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Token ranges of the rows indexed by a node, so that several nodes can share the indexing of
+//! the same tables. Each node indexes the rows of its ranges only, both by the full scan and by
+//! the CDC readers, and serves searches over these rows.
+
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::ensure;
+use serde::Deserialize;
+use serde::Serialize;
+use std::str::FromStr;
+
+/// The lowest token of a row in ScyllaDB.
+pub(crate) const TOKEN_MIN: i64 = -i64::MAX;
+/// The highest token of a row in ScyllaDB.
+pub(crate) const TOKEN_MAX: i64 = i64::MAX;
+
+/// Rows of the tables indexed by the node.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenOwnership {
+    /// The primary token ranges of the ScyllaDB node at `scylladb_uri`.
+    Local,
+    /// The configured token ranges.
+    Ranges(TokenRanges),
+}
+
+impl FromStr for TokenOwnership {
+    type Err = anyhow::Error;
+
+    /// Parses `local` or the token ranges.
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        if value.trim() == "local" {
+            return Ok(Self::Local);
+        }
+        value.parse().map(Self::Ranges)
+    }
+}
+
+/// Sorted and disjoint inclusive ranges of tokens.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "Vec<(i64, i64)>", into = "Vec<(i64, i64)>")]
+pub struct TokenRanges(Vec<(i64, i64)>);
+
+impl TryFrom<Vec<(i64, i64)>> for TokenRanges {
+    type Error = anyhow::Error;
+
+    fn try_from(mut ranges: Vec<(i64, i64)>) -> anyhow::Result<Self> {
+        ensure!(!ranges.is_empty(), "no token ranges");
+        for &(begin, end) in &ranges {
+            ensure!(
+                begin >= TOKEN_MIN,
+                "token {begin} is lower than the lowest token {TOKEN_MIN}"
+            );
+            ensure!(begin <= end, "token range {begin}:{end} is empty");
+        }
+        ranges.sort_unstable();
+        let mut merged: Vec<(i64, i64)> = Vec::with_capacity(ranges.len());
+        for (begin, end) in ranges {
+            match merged.last_mut() {
+                Some((_, last_end)) if begin <= last_end.saturating_add(1) => {
+                    *last_end = end.max(*last_end);
+                }
+                _ => merged.push((begin, end)),
+            }
+        }
+        Ok(Self(merged))
+    }
+}
+
+impl From<TokenRanges> for Vec<(i64, i64)> {
+    fn from(ranges: TokenRanges) -> Self {
+        ranges.0
+    }
+}
+
+impl FromStr for TokenRanges {
+    type Err = anyhow::Error;
+
+    /// Parses comma separated inclusive ranges like `-9223372036854775807:0,1000:2000`.
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        value
+            .split(',')
+            .map(|range| {
+                let Some((begin, end)) = range.trim().split_once(':') else {
+                    bail!("token range {range:?} is not formatted as begin:end");
+                };
+                let token = |token: &str| {
+                    token
+                        .trim()
+                        .parse::<i64>()
+                        .map_err(|err| anyhow!("invalid token {token:?}: {err}"))
+                };
+                Ok((token(begin)?, token(end)?))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?
+            .try_into()
+    }
+}
+
+impl TokenRanges {
+    /// Primary token ranges of the nodes marked in the token ring: a node owns the tokens after
+    /// the previous token of the ring up to its own token. Returns none when no node is marked.
+    pub(crate) fn primary(ring: impl IntoIterator<Item = (i64, bool)>) -> Option<Self> {
+        let ring: Vec<_> = ring.into_iter().collect();
+        let (last, _) = *ring.last()?;
+        let ranges = ring
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, owned))| *owned)
+            .flat_map(|(idx, &(token, _))| match idx {
+                0 => [
+                    Some((TOKEN_MIN, token)),
+                    last.checked_add(1).map(|begin| (begin, TOKEN_MAX)),
+                ],
+                _ => [Some((ring[idx - 1].0 + 1, token)), None],
+            })
+            .flatten()
+            .filter(|(begin, end)| begin <= end)
+            .collect();
+        Self::try_from(ranges).ok()
+    }
+
+    /// Whether the token is within the ranges.
+    pub(crate) fn contains(&self, token: i64) -> bool {
+        let idx = self.0.partition_point(|&(_, end)| end < token);
+        self.0.get(idx).is_some_and(|&(begin, _)| begin <= token)
+    }
+
+    /// Number of tokens within the ranges, which is `u64::MAX` for the whole token ring.
+    pub(crate) fn tokens(&self) -> u64 {
+        self.0
+            .iter()
+            .map(|&(begin, end)| end.abs_diff(begin) + 1)
+            .sum()
+    }
+
+    /// Parts of the inclusive range within the ranges.
+    pub(crate) fn intersect(&self, begin: i64, end: i64) -> impl Iterator<Item = (i64, i64)> {
+        self.0
+            .iter()
+            .map(move |&(range_begin, range_end)| (range_begin.max(begin), range_end.min(end)))
+            .filter(|(begin, end)| begin <= end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(ranges: &[(i64, i64)]) -> TokenRanges {
+        TokenRanges::try_from(ranges.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn ranges_are_parsed_and_merged() {
+        assert_eq!(
+            "local".parse::<TokenOwnership>().unwrap(),
+            TokenOwnership::Local
+        );
+        assert_eq!(
+            "10:20, -5:0,1:4,15:30".parse::<TokenOwnership>().unwrap(),
+            TokenOwnership::Ranges(ranges(&[(-5, 4), (10, 30)]))
+        );
+        assert_eq!(
+            format!("{TOKEN_MIN}:{TOKEN_MAX}")
+                .parse::<TokenRanges>()
+                .unwrap()
+                .tokens(),
+            u64::MAX
+        );
+        assert!("".parse::<TokenRanges>().is_err());
+        assert!("5".parse::<TokenRanges>().is_err());
+        assert!("5:1".parse::<TokenRanges>().is_err());
+        assert!("a:1".parse::<TokenRanges>().is_err());
+        assert!(format!("{}:0", i64::MIN).parse::<TokenRanges>().is_err());
+    }
+
+    #[test]
+    fn tokens_are_looked_up_in_ranges() {
+        let ranges = ranges(&[(-5, 4), (10, 30)]);
+        assert!(!ranges.contains(-6));
+        assert!(ranges.contains(-5));
+        assert!(ranges.contains(4));
+        assert!(!ranges.contains(5));
+        assert!(ranges.contains(30));
+        assert!(!ranges.contains(31));
+        assert_eq!(ranges.tokens(), 10 + 21);
+        assert_eq!(
+            ranges.intersect(0, 20).collect::<Vec<_>>(),
+            [(0, 4), (10, 20)]
+        );
+        assert_eq!(ranges.intersect(5, 9).count(), 0);
+    }
+
+    #[test]
+    fn primary_ranges_are_taken_from_ring() {
+        let ring = [(-100, true), (0, false), (100, true)];
+        assert_eq!(
+            TokenRanges::primary(ring),
+            Some(ranges(&[(TOKEN_MIN, -100), (1, 100), (101, TOKEN_MAX)]))
+        );
+        let ring = [(-100, false), (0, true), (100, false)];
+        assert_eq!(TokenRanges::primary(ring), Some(ranges(&[(-99, 0)])));
+        assert_eq!(
+            TokenRanges::primary([(0, true)]).unwrap().tokens(),
+            u64::MAX
+        );
+        assert_eq!(TokenRanges::primary([(0, false)]), None);
+        assert_eq!(TokenRanges::primary([]), None);
+    }
+}
@@ -11,12 +11,45 @@ use crate::PrimaryKey;
 use crate::Vector;
 use crate::table::PartitionId;
 use crate::table::PrimaryId;
+use std::num::NonZeroUsize;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 
-pub(crate) type AnnR = anyhow::Result<(Vec<PrimaryKey>, Vec<Distance>)>;
-pub(crate) type CountR = anyhow::Result<usize>;
+/// Response to [`VsIndex::Ann`], [`VsIndex::FilteredAnn`] and [`VsIndex::Range`].
+pub type AnnR = anyhow::Result<(Vec<PrimaryKey>, Vec<Distance>)>;
+/// Response to [`VsIndex::AnnBatch`], the results of the queries in their order.
+pub type AnnBatchR = anyhow::Result<Vec<(Vec<PrimaryKey>, Vec<Distance>)>>;
+/// Response to [`VsIndex::Count`].
+pub type CountR = anyhow::Result<usize>;
+/// Response to [`VsIndex::Stats`].
+pub type VsStatsR = anyhow::Result<VsStats>;
+/// Response to [`VsIndex::Duplicates`], the primary keys of each cluster of near-duplicates.
+pub type DuplicatesR = anyhow::Result<Vec<Vec<PrimaryKey>>>;
+/// Response to [`VsIndex::KMeans`].
+pub type KMeansR = anyhow::Result<Vec<Centroid>>;
 
+/// Internals of the graphs of a vector index.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct VsStats {
+    /// Vectors in the index.
+    pub size: usize,
+    /// Slots reserved for vectors.
+    pub capacity: usize,
+    /// Slots of removed vectors which are not reused by added vectors yet.
+    pub deleted: usize,
+    /// Partitions of the index, each with its own graph.
+    pub partitions: usize,
+}
+
+/// A centroid of a cluster of vectors of an index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Centroid {
+    pub vector: Vector,
+    /// Vectors nearer to the centroid than to any other.
+    pub size: usize,
+}
+
+/// Messages handled by a vector index actor.
 pub enum VsIndex {
     AddVector {
         partition_id: PartitionId,
@@ -45,10 +78,57 @@ pub enum VsIndex {
         limit: Limit,
         tx: oneshot::Sender<AnnR>,
     },
+    /// Searches of several embeddings with their limits, run in parallel.
+    AnnBatch {
+        index_key: IndexKey,
+        queries: Vec<(Vector, Limit)>,
+        tx: oneshot::Sender<AnnBatchR>,
+    },
+    /// Search of all vectors within `max_distance` of the embedding.
+    Range {
+        index_key: IndexKey,
+        embedding: Vector,
+        max_distance: f32,
+        tx: oneshot::Sender<AnnR>,
+    },
     Count {
         index_key: IndexKey,
         tx: oneshot::Sender<CountR>,
     },
+    Stats {
+        index_key: IndexKey,
+        tx: oneshot::Sender<VsStatsR>,
+    },
+    /// Clusters of vectors linked by distances not greater than `max_distance`.
+    Duplicates {
+        index_key: IndexKey,
+        max_distance: f32,
+        tx: oneshot::Sender<DuplicatesR>,
+    },
+    /// Up to `k` centroids of the vectors, by mini-batch k-means.
+    KMeans {
+        index_key: IndexKey,
+        k: NonZeroUsize,
+        tx: oneshot::Sender<KMeansR>,
+    },
+}
+
+impl VsIndex {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            VsIndex::AddVector { .. } => "AddVector",
+            VsIndex::RemoveVector { .. } => "RemoveVector",
+            VsIndex::RemovePartition { .. } => "RemovePartition",
+            VsIndex::Ann { .. } => "Ann",
+            VsIndex::FilteredAnn { .. } => "FilteredAnn",
+            VsIndex::AnnBatch { .. } => "AnnBatch",
+            VsIndex::Range { .. } => "Range",
+            VsIndex::Count { .. } => "Count",
+            VsIndex::Stats { .. } => "Stats",
+            VsIndex::Duplicates { .. } => "Duplicates",
+            VsIndex::KMeans { .. } => "KMeans",
+        }
+    }
 }
 
 pub(crate) trait VsIndexExt {
@@ -74,7 +154,12 @@ pub(crate) trait VsIndexExt {
         filter: Filter,
         limit: Limit,
     ) -> AnnR;
+    async fn ann_batch(&self, index_key: IndexKey, queries: Vec<(Vector, Limit)>) -> AnnBatchR;
+    async fn range(&self, index_key: IndexKey, embedding: Vector, max_distance: f32) -> AnnR;
     async fn count(&self, index_key: IndexKey) -> CountR;
+    async fn stats(&self, index_key: IndexKey) -> VsStatsR;
+    async fn duplicates(&self, index_key: IndexKey, max_distance: f32) -> DuplicatesR;
+    async fn kmeans(&self, index_key: IndexKey, k: NonZeroUsize) -> KMeansR;
 }
 
 impl VsIndexExt for mpsc::Sender<VsIndex> {
@@ -152,10 +237,61 @@ impl VsIndexExt for mpsc::Sender<VsIndex> {
         rx.await?
     }
 
+    #[hotpath::measure]
+    async fn ann_batch(&self, index_key: IndexKey, queries: Vec<(Vector, Limit)>) -> AnnBatchR {
+        let (tx, rx) = oneshot::channel();
+        self.send(VsIndex::AnnBatch {
+            index_key,
+            queries,
+            tx,
+        })
+        .await?;
+        rx.await?
+    }
+
+    #[hotpath::measure]
+    async fn range(&self, index_key: IndexKey, embedding: Vector, max_distance: f32) -> AnnR {
+        let (tx, rx) = oneshot::channel();
+        self.send(VsIndex::Range {
+            index_key,
+            embedding,
+            max_distance,
+            tx,
+        })
+        .await?;
+        rx.await?
+    }
+
     #[hotpath::measure]
     async fn count(&self, index_key: IndexKey) -> CountR {
         let (tx, rx) = oneshot::channel();
         self.send(VsIndex::Count { index_key, tx }).await?;
         rx.await?
     }
+
+    #[hotpath::measure]
+    async fn stats(&self, index_key: IndexKey) -> VsStatsR {
+        let (tx, rx) = oneshot::channel();
+        self.send(VsIndex::Stats { index_key, tx }).await?;
+        rx.await?
+    }
+
+    #[hotpath::measure]
+    async fn duplicates(&self, index_key: IndexKey, max_distance: f32) -> DuplicatesR {
+        let (tx, rx) = oneshot::channel();
+        self.send(VsIndex::Duplicates {
+            index_key,
+            max_distance,
+            tx,
+        })
+        .await?;
+        rx.await?
+    }
+
+    #[hotpath::measure]
+    async fn kmeans(&self, index_key: IndexKey, k: NonZeroUsize) -> KMeansR {
+        let (tx, rx) = oneshot::channel();
+        self.send(VsIndex::KMeans { index_key, k, tx }).await?;
+        rx.await?
+    }
 }
@@ -8,12 +8,14 @@ use crate::Dimensions;
 use crate::DiskannAlpha;
 use crate::SpaceType;
 use crate::VsIndexFactory;
+use crate::actor_trace;
 use crate::memory::Memory;
 use crate::perf;
 use crate::table::Table;
 use crate::vs_index::actor::VsIndex;
 use crate::vs_index::factory::VsIndexConfiguration;
 use anyhow::Context;
+use async_trait::async_trait;
 use diskann::graph::Config as DiskannConfig;
 use diskann::graph::DiskANNIndex;
 use diskann::graph::config::Builder;
@@ -46,8 +48,9 @@ pub struct DiskannIndexFactory {
     alpha: DiskannAlpha,
 }
 
+#[async_trait]
 impl VsIndexFactory for DiskannIndexFactory {
-    fn create_index(
+    async fn create_index(
         &self,
         index: VsIndexConfiguration,
         _table: Arc<RwLock<Table>>,
@@ -99,17 +102,26 @@ fn new(
 
     tokio::spawn(perf::hotpath_async(
         {
+            let index_key = index_key.clone();
             async move {
                 debug!("starting");
 
                 while let Some(msg) = rx.recv().await {
+                    let _trace =
+                        actor_trace::start("diskann", msg.name(), Some(&index_key), rx.len());
                     match msg {
                         VsIndex::AddVector { .. }
                         | VsIndex::RemoveVector { .. }
                         | VsIndex::RemovePartition { .. } => {
                             warn!("not implemented yet");
                         }
-                        VsIndex::Ann { tx, .. } | VsIndex::FilteredAnn { tx, .. } => {
+                        VsIndex::Ann { tx, .. }
+                        | VsIndex::FilteredAnn { tx, .. }
+                        | VsIndex::Range { tx, .. } => {
+                            _ = tx
+                                .send(Err(anyhow::anyhow!("DiskANN index is not implemented yet")));
+                        }
+                        VsIndex::AnnBatch { tx, .. } => {
                             _ = tx
                                 .send(Err(anyhow::anyhow!("DiskANN index is not implemented yet")));
                         }
@@ -117,6 +129,18 @@ fn new(
                             _ = tx
                                 .send(Err(anyhow::anyhow!("DiskANN index is not implemented yet")));
                         }
+                        VsIndex::Stats { tx, .. } => {
+                            _ = tx
+                                .send(Err(anyhow::anyhow!("DiskANN index is not implemented yet")));
+                        }
+                        VsIndex::Duplicates { tx, .. } => {
+                            _ = tx
+                                .send(Err(anyhow::anyhow!("DiskANN index is not implemented yet")));
+                        }
+                        VsIndex::KMeans { tx, .. } => {
+                            _ = tx
+                                .send(Err(anyhow::anyhow!("DiskANN index is not implemented yet")));
+                        }
                     }
                 }
                 drop(index);
@@ -13,10 +13,13 @@ use crate::SpaceType;
 use crate::memory::Memory;
 use crate::table::Table;
 use crate::vs_index::actor::VsIndex;
+use async_trait::async_trait;
 use std::sync::Arc;
 use std::sync::RwLock;
 use tokio::sync::mpsc;
 
+/// Parameters of a vector index passed to a [`VsIndexFactory`] when the index is created.
+#[derive(Clone)]
 pub struct VsIndexConfiguration {
     pub key: IndexKey,
     pub dimensions: Dimensions,
@@ -27,12 +30,20 @@ pub struct VsIndexConfiguration {
     pub quantization: Quantization,
 }
 
+/// Creates vector index actors for a single ANN engine.
+///
+/// The returned sender is the index actor: it must handle every [`VsIndex`] message until the
+/// sender is dropped. Custom engines can be plugged into the service by implementing this trait
+/// and passing the factory to [`crate::VectorStoreBuilder::index_factory`].
+#[async_trait]
 pub trait VsIndexFactory {
-    fn create_index(
+    async fn create_index(
         &self,
         index: VsIndexConfiguration,
         table: Arc<RwLock<Table>>,
         memory: mpsc::Sender<Memory>,
     ) -> anyhow::Result<mpsc::Sender<VsIndex>>;
+
+    /// Engine name and version reported by the `/api/v1/info` endpoint.
     fn index_engine_version(&self) -> String;
 }
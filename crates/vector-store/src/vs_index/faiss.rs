@@ -0,0 +1,518 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! An index backend over FAISS, selected by [`Config::faiss_index`](crate::Config::faiss_index).
+//! Each partition of an index is a FAISS index built by the index factory from its description,
+//! like `HNSW32` or `IVF1024,Flat`, with the primary ids of the rows as labels.
+//!
+//! Indexes which need training, like IVF ones, hold the vectors back until [`TRAINING_SIZE`] of
+//! them are collected and are searched exactly until then. Indexes which cannot remove vectors,
+//! like HNSW ones, keep them and search for as many more neighbors instead, the removed rows
+//! being left out by the table.
+
+use crate::Dimensions;
+use crate::Distance;
+use crate::IndexKey;
+use crate::Limit;
+use crate::PartitionId;
+use crate::SpaceType;
+use crate::Vector;
+use crate::VsIndexFactory;
+use crate::actor_trace;
+use crate::memory::Memory;
+use crate::perf;
+use crate::table::PrimaryId;
+use crate::table::Table;
+use crate::table::TableSearch;
+use crate::vs_index::actor::AnnR;
+use crate::vs_index::actor::VsIndex;
+use crate::vs_index::factory::VsIndexConfiguration;
+use crate::vs_index::spill;
+use crate::vs_index::validator;
+use crate::worker;
+use crate::worker::Worker;
+use crate::worker::WorkerExt;
+use anyhow::Context;
+use anyhow::anyhow;
+use anyhow::bail;
+use anyhow::ensure;
+use async_trait::async_trait;
+use faiss::IdMap;
+use faiss::Idx;
+use faiss::Index;
+use faiss::IndexImpl;
+use faiss::MetricType;
+use faiss::selector::IdSelector;
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::RwLock;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tracing::Instrument;
+use tracing::debug;
+use tracing::debug_span;
+use tracing::warn;
+
+/// Vectors collected to train an index, retried at each multiple when the training fails.
+const TRAINING_SIZE: usize = 10_000;
+
+pub struct FaissIndexFactory {
+    description: String,
+    worker: async_channel::Sender<Worker>,
+}
+
+#[async_trait]
+impl VsIndexFactory for FaissIndexFactory {
+    async fn create_index(
+        &self,
+        index: VsIndexConfiguration,
+        table: Arc<RwLock<Table>>,
+        _: mpsc::Sender<Memory>,
+    ) -> anyhow::Result<mpsc::Sender<VsIndex>> {
+        let params = Params {
+            dimensions: index.dimensions,
+            space_type: index.space_type,
+            description: self.description.clone(),
+        };
+        // A wrong description fails the index instead of each of its partitions.
+        params.new_partition()?;
+        new(index.key, params, table, self.worker.clone())
+    }
+
+    fn index_engine_version(&self) -> String {
+        "faiss".into()
+    }
+}
+
+pub fn new_faiss(description: String) -> anyhow::Result<FaissIndexFactory> {
+    ensure!(
+        !description.trim().is_empty(),
+        "FAISS index description must not be empty"
+    );
+    Ok(FaissIndexFactory {
+        description,
+        worker: worker::new(),
+    })
+}
+
+struct Params {
+    dimensions: Dimensions,
+    space_type: SpaceType,
+    description: String,
+}
+
+impl Params {
+    fn metric(&self) -> anyhow::Result<MetricType> {
+        match self.space_type {
+            SpaceType::Euclidean => Ok(MetricType::L2),
+            SpaceType::Cosine | SpaceType::DotProduct => Ok(MetricType::InnerProduct),
+            SpaceType::Hamming => bail!("Hamming distance is not supported by FAISS indexes"),
+        }
+    }
+
+    fn new_partition(&self) -> anyhow::Result<Partition> {
+        let index = faiss::index_factory(
+            self.dimensions.0.get() as u32,
+            &self.description,
+            self.metric()?,
+        )
+        .with_context(|| format!("unable to create FAISS index {:?}", self.description))?;
+        Ok(Partition {
+            index: IdMap::new(index)?,
+            pending: BTreeMap::new(),
+            unremoved: 0,
+        })
+    }
+
+    /// Returns the vector as indexed, normalized for the cosine distance searched by the inner
+    /// product.
+    fn indexed(&self, vector: &[f32]) -> Vec<f32> {
+        if self.space_type != SpaceType::Cosine {
+            return vector.to_vec();
+        }
+        let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+        if norm == 0.0 {
+            return vector.to_vec();
+        }
+        vector.iter().map(|value| value / norm).collect()
+    }
+
+    /// Converts a distance of FAISS, the squared L2 distance or the inner product, to the
+    /// distance of the space type as returned by the other backends.
+    fn distance(&self, value: f32) -> f32 {
+        match self.space_type {
+            SpaceType::Cosine => (1.0 - value).clamp(0.0, 2.0),
+            SpaceType::DotProduct => 1.0 - value,
+            SpaceType::Euclidean | SpaceType::Hamming => value.max(0.0),
+        }
+    }
+}
+
+/// Returns the label of the primary id. Labels are signed in FAISS, so the epoch in the highest
+/// bits may make them negative.
+fn label(primary_id: PrimaryId) -> Idx {
+    Idx::from(u64::from(primary_id) as i64)
+}
+
+struct Partition {
+    index: IdMap<IndexImpl>,
+    /// Vectors held back until the index is trained.
+    pending: BTreeMap<PrimaryId, Vec<f32>>,
+    /// Vectors kept by the index after their removal, as it does not support removing them.
+    unremoved: usize,
+}
+
+impl Partition {
+    fn add(
+        &mut self,
+        params: &Params,
+        primary_id: PrimaryId,
+        vector: &[f32],
+    ) -> anyhow::Result<()> {
+        if self.index.is_trained() {
+            self.index
+                .add_with_ids(&params.indexed(vector), &[label(primary_id)])?;
+            return Ok(());
+        }
+        self.pending.insert(primary_id, vector.to_vec());
+        if !self.pending.len().is_multiple_of(TRAINING_SIZE) {
+            return Ok(());
+        }
+        self.train(params)
+    }
+
+    /// Trains the index by the vectors held back and adds them.
+    fn train(&mut self, params: &Params) -> anyhow::Result<()> {
+        let (labels, vectors): (Vec<_>, Vec<_>) = self
+            .pending
+            .iter()
+            .map(|(&primary_id, vector)| (label(primary_id), params.indexed(vector)))
+            .unzip();
+        let vectors = vectors.concat();
+        self.index
+            .train(&vectors)
+            .with_context(|| format!("unable to train by {} vectors", labels.len()))?;
+        self.pending.clear();
+        self.index.add_with_ids(&vectors, &labels)?;
+        Ok(())
+    }
+
+    fn remove(&mut self, primary_id: PrimaryId) -> anyhow::Result<()> {
+        if self.pending.remove(&primary_id).is_some() {
+            return Ok(());
+        }
+        let selector = IdSelector::batch(&[label(primary_id)])?;
+        if self.index.remove_ids(&selector).is_err() {
+            self.unremoved += 1;
+        }
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        (self.index.ntotal() as usize).saturating_sub(self.unremoved) + self.pending.len()
+    }
+
+    /// Returns the nearest vectors with their distances, including the removed vectors kept by
+    /// the index.
+    fn search(
+        &mut self,
+        params: &Params,
+        query: &[f32],
+        limit: usize,
+    ) -> anyhow::Result<Vec<(PrimaryId, f32)>> {
+        if !self.index.is_trained() {
+            let mut neighbors: Vec<_> = self
+                .pending
+                .iter()
+                .map(|(&primary_id, vector)| {
+                    let distance = spill::exact_distance(params.space_type, query, vector);
+                    (primary_id, distance)
+                })
+                .collect();
+            neighbors.sort_by(|(_, lhs), (_, rhs)| lhs.total_cmp(rhs));
+            neighbors.truncate(limit);
+            return Ok(neighbors);
+        }
+        let k = usize::min(limit + self.unremoved, self.index.ntotal() as usize);
+        if k == 0 {
+            return Ok(Vec::new());
+        }
+        let result = self.index.search(&params.indexed(query), k)?;
+        Ok(result
+            .labels
+            .into_iter()
+            .zip(result.distances)
+            .filter_map(|(label, distance)| {
+                let primary_id = PrimaryId::from(label.get()?);
+                Some((primary_id, params.distance(distance)))
+            })
+            .collect())
+    }
+}
+
+type Partitions = BTreeMap<PartitionId, Arc<Mutex<Partition>>>;
+
+fn new(
+    key: IndexKey,
+    params: Params,
+    table: Arc<RwLock<impl TableSearch + Send + Sync + 'static>>,
+    worker: async_channel::Sender<Worker>,
+) -> anyhow::Result<mpsc::Sender<VsIndex>> {
+    let (tx, mut rx) = mpsc::channel(perf::channel_size().into());
+    let params = Arc::new(params);
+
+    tokio::spawn(perf::hotpath_async(
+        {
+            let index_key = key.clone();
+            async move {
+                debug!("starting");
+
+                let mut partitions = Partitions::new();
+                while let Some(msg) = rx.recv().await {
+                    let _trace =
+                        actor_trace::start("faiss", msg.name(), Some(&index_key), rx.len());
+                    process(msg, &params, &mut partitions, &table, &worker).await;
+                }
+
+                debug!("finished");
+            }
+        }
+        .instrument(debug_span!("faiss", "{key}")),
+    ));
+
+    Ok(tx)
+}
+
+/// Runs `f` on the worker and waits for it, so that the writes are applied in order.
+async fn run(worker: &async_channel::Sender<Worker>, f: impl FnOnce() + Send + 'static) {
+    let (tx, rx) = oneshot::channel();
+    worker
+        .spawn_blocking(move || {
+            f();
+            _ = tx.send(());
+        })
+        .await;
+    _ = rx.await;
+}
+
+async fn process(
+    msg: VsIndex,
+    params: &Arc<Params>,
+    partitions: &mut Partitions,
+    table: &Arc<RwLock<impl TableSearch + Send + Sync + 'static>>,
+    worker: &async_channel::Sender<Worker>,
+) {
+    match msg {
+        VsIndex::AddVector {
+            partition_id,
+            primary_id,
+            embedding,
+            in_progress,
+        } => {
+            let partition = match partitions.entry(partition_id) {
+                Entry::Occupied(entry) => Arc::clone(entry.get()),
+                Entry::Vacant(entry) => match params.new_partition() {
+                    Ok(partition) => Arc::clone(entry.insert(Arc::new(Mutex::new(partition)))),
+                    Err(err) => {
+                        warn!("add: unable to create a partition: {err}");
+                        return;
+                    }
+                },
+            };
+            let params = Arc::clone(params);
+            run(worker, move || {
+                if let Err(err) =
+                    partition
+                        .lock()
+                        .unwrap()
+                        .add(&params, primary_id, embedding.as_slice())
+                {
+                    warn!("add: unable to add embedding for primary_id {primary_id:?}: {err}");
+                }
+                drop(in_progress);
+            })
+            .await;
+        }
+        VsIndex::RemoveVector {
+            partition_id,
+            primary_id,
+            in_progress,
+        } => {
+            let Some(partition) = partitions.get(&partition_id).cloned() else {
+                return;
+            };
+            run(worker, move || {
+                if let Err(err) = partition.lock().unwrap().remove(primary_id) {
+                    warn!(
+                        "remove: unable to remove embedding for primary_id {primary_id:?}: {err}"
+                    );
+                }
+                drop(in_progress);
+            })
+            .await;
+        }
+        VsIndex::RemovePartition { partition_id } => {
+            partitions.remove(&partition_id);
+        }
+        VsIndex::Ann {
+            index_key,
+            embedding,
+            limit,
+            tx,
+        } => {
+            if let Err(err) = validator::embedding_dimensions(&embedding, params.dimensions) {
+                _ = tx.send(Err(err));
+                return;
+            }
+            let partition_id = table
+                .read()
+                .unwrap()
+                .partition_id(&index_key, None)
+                .map(|(partition_id, _)| partition_id);
+            let Some((partition_id, partition)) = partition_id.and_then(|partition_id| {
+                let partition = partitions.get(&partition_id).cloned()?;
+                Some((partition_id, partition))
+            }) else {
+                _ = tx.send(Ok((vec![], vec![])));
+                return;
+            };
+            let params = Arc::clone(params);
+            let table = Arc::clone(table);
+            worker
+                .spawn_blocking(move || {
+                    _ = tx.send(ann(
+                        &params,
+                        &partition,
+                        &table,
+                        partition_id,
+                        &embedding,
+                        limit,
+                    ));
+                })
+                .await;
+        }
+        VsIndex::FilteredAnn { tx, .. } => {
+            _ = tx.send(Err(anyhow!(
+                "filtered ANN of FAISS indexes is not supported"
+            )));
+        }
+        VsIndex::AnnBatch { tx, .. } => {
+            _ = tx.send(Err(anyhow!("batch ANN of FAISS indexes is not supported")));
+        }
+        VsIndex::Range { tx, .. } => {
+            _ = tx.send(Err(anyhow!(
+                "range search of FAISS indexes is not supported"
+            )));
+        }
+        VsIndex::Count { tx, .. } => {
+            let partitions: Vec<_> = partitions.values().cloned().collect();
+            worker
+                .spawn_blocking(move || {
+                    let count = partitions
+                        .iter()
+                        .map(|partition| partition.lock().unwrap().len())
+                        .sum();
+                    _ = tx.send(Ok(count));
+                })
+                .await;
+        }
+        VsIndex::Stats { tx, .. } => {
+            _ = tx.send(Err(anyhow!(
+                "statistics of FAISS indexes are not supported"
+            )));
+        }
+        VsIndex::Duplicates { tx, .. } => {
+            _ = tx.send(Err(anyhow!(
+                "duplicates of FAISS indexes are not supported"
+            )));
+        }
+        VsIndex::KMeans { tx, .. } => {
+            _ = tx.send(Err(anyhow!("k-means of FAISS indexes is not supported")));
+        }
+    }
+}
+
+fn ann(
+    params: &Params,
+    partition: &Mutex<Partition>,
+    table: &RwLock<impl TableSearch>,
+    partition_id: PartitionId,
+    embedding: &Vector,
+    limit: Limit,
+) -> AnnR {
+    let limit = limit.0.get();
+    let neighbors = partition
+        .lock()
+        .unwrap()
+        .search(params, embedding.as_slice(), limit)?;
+    let table = table.read().unwrap();
+    let neighbors = neighbors
+        .into_iter()
+        .filter_map(|(primary_id, distance)| {
+            Some((table.primary_key(partition_id, primary_id)?, distance))
+        })
+        .take(limit)
+        .map(|(primary_key, distance)| {
+            let distance =
+                Distance::try_from((distance, params.space_type, Some(params.dimensions)))?;
+            Ok((primary_key, distance))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    Ok(neighbors.into_iter().unzip())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::num::NonZeroUsize;
+
+    fn params(space_type: SpaceType) -> Params {
+        Params {
+            dimensions: Dimensions(NonZeroUsize::new(2).unwrap()),
+            space_type,
+            description: "Flat".into(),
+        }
+    }
+
+    #[test]
+    fn distances_match_other_backends() {
+        let lhs = [3.0, 4.0];
+        let rhs = [1.0, 0.0];
+        let inner_product = |params: &Params| {
+            let (lhs, rhs) = (params.indexed(&lhs), params.indexed(&rhs));
+            lhs.iter().zip(&rhs).map(|(l, r)| l * r).sum::<f32>()
+        };
+
+        let cosine = params(SpaceType::Cosine);
+        assert_eq!(cosine.indexed(&lhs), vec![0.6, 0.8]);
+        assert_eq!(cosine.indexed(&[0.0, 0.0]), vec![0.0, 0.0]);
+        assert!(
+            (cosine.distance(inner_product(&cosine))
+                - spill::exact_distance(SpaceType::Cosine, &lhs, &rhs))
+            .abs()
+                < 1e-6
+        );
+
+        let dot_product = params(SpaceType::DotProduct);
+        assert_eq!(
+            dot_product.distance(inner_product(&dot_product)),
+            spill::exact_distance(SpaceType::DotProduct, &lhs, &rhs)
+        );
+
+        let euclidean = params(SpaceType::Euclidean);
+        assert_eq!(euclidean.distance(20.0), 20.0);
+        assert_eq!(euclidean.distance(-1e-6), 0.0);
+        assert!(params(SpaceType::Hamming).metric().is_err());
+    }
+
+    #[test]
+    fn labels_keep_epochs() {
+        let primary_id = PrimaryId::from(u64::MAX - 1);
+        assert_eq!(label(primary_id).get(), Some(u64::MAX - 1));
+        assert_eq!(label(PrimaryId::from(7)).get(), Some(7));
+    }
+}
@@ -0,0 +1,781 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! An exact index backend for small tables, selected by
+//! [`Config::flat_index_max_rows`](crate::Config::flat_index_max_rows). The vectors of a partition
+//! are stored one after another and every search scans all of them on a worker of the keyspace, so
+//! the results are exact. When an index grows over the limit, its vectors are moved to an index of
+//! the wrapped factory, like usearch, and the messages are forwarded to it from then on.
+
+use crate::Config;
+use crate::Dimensions;
+use crate::Distance;
+use crate::Limit;
+use crate::PartitionId;
+use crate::PrimaryKey;
+use crate::SpaceType;
+use crate::Vector;
+use crate::VsIndexFactory;
+use crate::actor_trace;
+use crate::async_in_progress::AsyncInProgress;
+use crate::memory::Memory;
+use crate::perf;
+use crate::table::PrimaryId;
+use crate::table::Table;
+use crate::table::TableSearch;
+use crate::vs_index::actor::AnnR;
+use crate::vs_index::actor::VsIndex;
+use crate::vs_index::actor::VsStats;
+use crate::vs_index::factory::VsIndexConfiguration;
+use crate::vs_index::kmeans;
+use crate::vs_index::spill;
+use crate::vs_index::validator;
+use crate::worker::KeyspaceWorkers;
+use crate::worker::Worker;
+use crate::worker::WorkerExt;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::RwLock;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tokio::sync::watch;
+use tracing::Instrument;
+use tracing::debug;
+use tracing::debug_span;
+use tracing::info;
+use tracing::warn;
+
+/// Independent sums of the distance loops, so that the compiler vectorizes them.
+const LANES: usize = 16;
+
+pub struct FlatIndexFactory {
+    inner: Arc<dyn VsIndexFactory + Send + Sync>,
+    max_rows: NonZeroUsize,
+    workers: KeyspaceWorkers,
+}
+
+#[async_trait]
+impl VsIndexFactory for FlatIndexFactory {
+    async fn create_index(
+        &self,
+        index: VsIndexConfiguration,
+        table: Arc<RwLock<Table>>,
+        memory: mpsc::Sender<Memory>,
+    ) -> anyhow::Result<mpsc::Sender<VsIndex>> {
+        let (worker, _) = self.workers.worker(index.key.keyspace());
+        Ok(new(
+            index,
+            table,
+            memory,
+            Arc::clone(&self.inner),
+            self.max_rows,
+            worker,
+        ))
+    }
+
+    fn index_engine_version(&self) -> String {
+        format!("flat, {}", self.inner.index_engine_version())
+    }
+}
+
+/// Wraps the factory, serving its indexes exactly until they have more than `max_rows` rows.
+pub fn new_flat(
+    inner: Box<dyn VsIndexFactory + Send + Sync>,
+    max_rows: NonZeroUsize,
+    config_rx: watch::Receiver<Arc<Config>>,
+) -> FlatIndexFactory {
+    FlatIndexFactory {
+        inner: Arc::from(inner),
+        max_rows,
+        workers: KeyspaceWorkers::new(config_rx),
+    }
+}
+
+struct Params {
+    dimensions: Dimensions,
+    space_type: SpaceType,
+}
+
+impl Params {
+    /// Returns the distance as returned by usearch. `query_norm` and `norm` are the squared norms
+    /// of the vectors.
+    fn distance(&self, query: &[f32], query_norm: f32, vector: &[f32], norm: f32) -> f32 {
+        match self.space_type {
+            SpaceType::Euclidean => sum_of(query, vector, |lhs, rhs| (lhs - rhs) * (lhs - rhs)),
+            SpaceType::DotProduct => 1.0 - sum_of(query, vector, |lhs, rhs| lhs * rhs),
+            SpaceType::Cosine => {
+                let norms = (query_norm * norm).sqrt();
+                if norms == 0.0 {
+                    return 1.0;
+                }
+                (1.0 - sum_of(query, vector, |lhs, rhs| lhs * rhs) / norms).clamp(0.0, 2.0)
+            }
+            SpaceType::Hamming => spill::exact_distance(SpaceType::Hamming, query, vector),
+        }
+    }
+}
+
+/// Returns the sum of the terms of the values of the vectors at the same positions.
+fn sum_of(lhs: &[f32], rhs: &[f32], term: impl Fn(f32, f32) -> f32) -> f32 {
+    let (lhs_chunks, rhs_chunks) = (lhs.chunks_exact(LANES), rhs.chunks_exact(LANES));
+    let tail = lhs_chunks
+        .remainder()
+        .iter()
+        .zip(rhs_chunks.remainder())
+        .map(|(&lhs, &rhs)| term(lhs, rhs))
+        .sum::<f32>();
+    let mut sums = [0.0; LANES];
+    for (lhs, rhs) in lhs_chunks.zip(rhs_chunks) {
+        sums.iter_mut()
+            .zip(lhs)
+            .zip(rhs)
+            .for_each(|((sum, &lhs), &rhs)| *sum += term(lhs, rhs));
+    }
+    sums.iter().sum::<f32>() + tail
+}
+
+fn squared_norm(vector: &[f32]) -> f32 {
+    sum_of(vector, vector, |lhs, rhs| lhs * rhs)
+}
+
+#[derive(Default)]
+struct Partition {
+    primary_ids: Vec<PrimaryId>,
+    /// Vectors of the rows in the order of `primary_ids`, one after another.
+    vectors: Vec<f32>,
+    /// Squared norms of the vectors, for the cosine distance.
+    norms: Vec<f32>,
+    positions: HashMap<PrimaryId, usize>,
+}
+
+impl Partition {
+    fn len(&self) -> usize {
+        self.primary_ids.len()
+    }
+
+    fn vector(&self, position: usize, dimensions: usize) -> &[f32] {
+        &self.vectors[position * dimensions..(position + 1) * dimensions]
+    }
+
+    /// Adds or replaces the vector of the row, returning whether the row is new.
+    fn add(&mut self, primary_id: PrimaryId, vector: &[f32]) -> bool {
+        let norm = squared_norm(vector);
+        if let Some(&position) = self.positions.get(&primary_id) {
+            let dimensions = vector.len();
+            self.vectors[position * dimensions..(position + 1) * dimensions]
+                .copy_from_slice(vector);
+            self.norms[position] = norm;
+            return false;
+        }
+        self.positions.insert(primary_id, self.len());
+        self.primary_ids.push(primary_id);
+        self.vectors.extend_from_slice(vector);
+        self.norms.push(norm);
+        true
+    }
+
+    /// Removes the vector of the row by moving the last vector in its place, returning whether
+    /// the row was there.
+    fn remove(&mut self, primary_id: PrimaryId, dimensions: usize) -> bool {
+        let Some(position) = self.positions.remove(&primary_id) else {
+            return false;
+        };
+        let last = self.len() - 1;
+        self.primary_ids.swap_remove(position);
+        self.norms.swap_remove(position);
+        self.vectors
+            .copy_within(last * dimensions.., position * dimensions);
+        self.vectors.truncate(last * dimensions);
+        if position < last {
+            self.positions.insert(self.primary_ids[position], position);
+        }
+        true
+    }
+
+    /// Returns the distances of the query to the vectors of the rows accepted by `valid`.
+    fn scan(
+        &self,
+        params: &Params,
+        query: &[f32],
+        valid: impl Fn(PrimaryId) -> bool + Sync,
+    ) -> Vec<(PrimaryId, f32)> {
+        let query_norm = squared_norm(query);
+        self.vectors
+            .chunks_exact(params.dimensions.0.get())
+            .zip(&self.primary_ids)
+            .zip(&self.norms)
+            .filter(|((_, primary_id), _)| valid(**primary_id))
+            .map(|((vector, &primary_id), &norm)| {
+                let distance = params.distance(query, query_norm, vector, norm);
+                (primary_id, distance)
+            })
+            .collect()
+    }
+
+    /// Returns clusters of rows whose vectors are linked by distances not greater than
+    /// `max_distance`.
+    fn duplicates(&self, params: &Params, max_distance: f32) -> Vec<Vec<PrimaryId>> {
+        fn root(parents: &mut [usize], position: usize) -> usize {
+            let mut root = position;
+            while parents[root] != root {
+                root = parents[root];
+            }
+            parents[position] = root;
+            root
+        }
+
+        let dimensions = params.dimensions.0.get();
+        let mut parents: Vec<_> = (0..self.len()).collect();
+        for position in 0..self.len() {
+            let (vector, norm) = (self.vector(position, dimensions), self.norms[position]);
+            let linked: Vec<_> = (position + 1..self.len())
+                .filter(|&other| {
+                    let distance = params.distance(
+                        vector,
+                        norm,
+                        self.vector(other, dimensions),
+                        self.norms[other],
+                    );
+                    distance <= max_distance
+                })
+                .collect();
+            for other in linked {
+                let (lhs, rhs) = (root(&mut parents, position), root(&mut parents, other));
+                parents[lhs.max(rhs)] = lhs.min(rhs);
+            }
+        }
+
+        let mut clusters: BTreeMap<usize, Vec<PrimaryId>> = BTreeMap::new();
+        for position in 0..self.len() {
+            let root = root(&mut parents, position);
+            clusters
+                .entry(root)
+                .or_default()
+                .push(self.primary_ids[position]);
+        }
+        clusters
+            .into_values()
+            .filter(|cluster| cluster.len() > 1)
+            .collect()
+    }
+}
+
+/// Returns the `limit` nearest neighbors ordered by their distances.
+fn nearest(mut neighbors: Vec<(PrimaryId, f32)>, limit: usize) -> Vec<(PrimaryId, f32)> {
+    let by_distance =
+        |lhs: &(PrimaryId, f32), rhs: &(PrimaryId, f32)| -> Ordering { lhs.1.total_cmp(&rhs.1) };
+    if neighbors.len() > limit {
+        neighbors.select_nth_unstable_by(limit, by_distance);
+        neighbors.truncate(limit);
+    }
+    neighbors.sort_by(by_distance);
+    neighbors
+}
+
+fn with_primary_keys(
+    params: &Params,
+    table: &RwLock<Table>,
+    partition_id: PartitionId,
+    neighbors: Vec<(PrimaryId, f32)>,
+) -> anyhow::Result<(Vec<PrimaryKey>, Vec<Distance>)> {
+    let table = table.read().unwrap();
+    itertools::process_results(
+        neighbors
+            .into_iter()
+            .filter_map(|(primary_id, distance)| {
+                table
+                    .primary_key(partition_id, primary_id)
+                    .map(|primary_key| (primary_key, distance))
+            })
+            .map(|(primary_key, distance)| {
+                let distance =
+                    Distance::try_from((distance, params.space_type, Some(params.dimensions)))?;
+                anyhow::Ok((primary_key, distance))
+            }),
+        |neighbors| neighbors.unzip(),
+    )
+}
+
+type Partitions = BTreeMap<PartitionId, Arc<RwLock<Partition>>>;
+
+struct Flat {
+    params: Arc<Params>,
+    table: Arc<RwLock<Table>>,
+    partitions: Partitions,
+    rows: usize,
+    worker: async_channel::Sender<Worker>,
+}
+
+fn new(
+    index: VsIndexConfiguration,
+    table: Arc<RwLock<Table>>,
+    memory: mpsc::Sender<Memory>,
+    inner: Arc<dyn VsIndexFactory + Send + Sync>,
+    max_rows: NonZeroUsize,
+    worker: async_channel::Sender<Worker>,
+) -> mpsc::Sender<VsIndex> {
+    let (tx, mut rx) = mpsc::channel(perf::channel_size().into());
+    let key = index.key.clone();
+
+    tokio::spawn(perf::hotpath_async(
+        {
+            let index_key = key.clone();
+            async move {
+                debug!("starting");
+
+                let mut flat = Flat {
+                    params: Arc::new(Params {
+                        dimensions: index.dimensions,
+                        space_type: index.space_type,
+                    }),
+                    table: Arc::clone(&table),
+                    partitions: Partitions::new(),
+                    rows: 0,
+                    worker,
+                };
+                let mut delegate: Option<mpsc::Sender<VsIndex>> = None;
+                let mut promote = true;
+                while let Some(msg) = rx.recv().await {
+                    if let Some(delegate) = &delegate {
+                        if delegate.send(msg).await.is_err() {
+                            warn!("the index the flat index was moved to is stopped");
+                            break;
+                        }
+                        continue;
+                    }
+
+                    let _trace = actor_trace::start("flat", msg.name(), Some(&index_key), rx.len());
+                    flat.process(msg).await;
+                    if !promote || flat.rows <= max_rows.get() {
+                        continue;
+                    }
+                    match flat.promote(&index, &table, &memory, inner.as_ref()).await {
+                        Ok(tx) => {
+                            info!(
+                                "moved {} rows over the limit of the flat index to {}",
+                                flat.rows,
+                                inner.index_engine_version()
+                            );
+                            flat.partitions.clear();
+                            delegate = Some(tx);
+                        }
+                        Err(err) => {
+                            warn!("unable to move the flat index over its limit: {err}");
+                            promote = false;
+                        }
+                    }
+                }
+
+                debug!("finished");
+            }
+        }
+        .instrument(debug_span!("flat", "{key}")),
+    ));
+
+    tx
+}
+
+impl Flat {
+    fn dimensions(&self) -> usize {
+        self.params.dimensions.0.get()
+    }
+
+    /// Creates the index of the wrapped factory with all vectors of the flat index.
+    async fn promote(
+        &self,
+        index: &VsIndexConfiguration,
+        table: &Arc<RwLock<Table>>,
+        memory: &mpsc::Sender<Memory>,
+        inner: &(dyn VsIndexFactory + Send + Sync),
+    ) -> anyhow::Result<mpsc::Sender<VsIndex>> {
+        let tx = inner
+            .create_index(index.clone(), Arc::clone(table), memory.clone())
+            .await?;
+        for (&partition_id, partition) in &self.partitions {
+            let rows: Vec<_> = {
+                let partition = partition.read().unwrap();
+                (0..partition.len())
+                    .map(|position| {
+                        let vector = partition.vector(position, self.dimensions());
+                        (
+                            partition.primary_ids[position],
+                            Vector::from(vector.to_vec()),
+                        )
+                    })
+                    .collect()
+            };
+            for (primary_id, embedding) in rows {
+                tx.send(VsIndex::AddVector {
+                    partition_id,
+                    primary_id,
+                    embedding,
+                    in_progress: AsyncInProgress::None,
+                })
+                .await
+                .map_err(|_| anyhow!("the index actor is stopped"))?;
+            }
+        }
+        Ok(tx)
+    }
+
+    /// Runs the search of the partition on the worker and sends the rows it found.
+    async fn search(
+        &self,
+        partition_id: Option<PartitionId>,
+        tx: oneshot::Sender<AnnR>,
+        search: impl FnOnce(&Partition, &Params, &RwLock<Table>) -> Vec<(PrimaryId, f32)>
+        + Send
+        + 'static,
+    ) {
+        let Some((partition_id, partition)) = partition_id.and_then(|partition_id| {
+            let partition = self.partitions.get(&partition_id)?;
+            Some((partition_id, Arc::clone(partition)))
+        }) else {
+            _ = tx.send(Ok((vec![], vec![])));
+            return;
+        };
+        let params = Arc::clone(&self.params);
+        let table = Arc::clone(&self.table);
+        self.worker
+            .spawn_non_blocking(move || {
+                let neighbors = search(&partition.read().unwrap(), &params, &table);
+                _ = tx.send(with_primary_keys(&params, &table, partition_id, neighbors));
+            })
+            .await;
+    }
+
+    fn global_partition_id(&self, index_key: &crate::IndexKey) -> Option<PartitionId> {
+        self.table
+            .read()
+            .unwrap()
+            .partition_id(index_key, None)
+            .map(|(partition_id, _)| partition_id)
+    }
+
+    async fn process(&mut self, msg: VsIndex) {
+        match msg {
+            VsIndex::AddVector {
+                partition_id,
+                primary_id,
+                embedding,
+                in_progress: _in_progress,
+            } => {
+                if embedding.len() != self.dimensions() {
+                    warn!("add: wrong dimensions of embedding for primary_id {primary_id:?}");
+                    return;
+                }
+                let partition = self.partitions.entry(partition_id).or_default();
+                if partition
+                    .write()
+                    .unwrap()
+                    .add(primary_id, embedding.as_slice())
+                {
+                    self.rows += 1;
+                }
+            }
+            VsIndex::RemoveVector {
+                partition_id,
+                primary_id,
+                in_progress: _in_progress,
+            } => {
+                let dimensions = self.dimensions();
+                if let Some(partition) = self.partitions.get(&partition_id)
+                    && partition.write().unwrap().remove(primary_id, dimensions)
+                {
+                    self.rows -= 1;
+                }
+            }
+            VsIndex::RemovePartition { partition_id } => {
+                if let Some(partition) = self.partitions.remove(&partition_id) {
+                    self.rows -= partition.read().unwrap().len();
+                }
+            }
+            VsIndex::Ann {
+                index_key,
+                embedding,
+                limit,
+                tx,
+            } => {
+                if let Err(err) =
+                    validator::embedding_dimensions(&embedding, self.params.dimensions)
+                {
+                    _ = tx.send(Err(err));
+                    return;
+                }
+                let limit = limit.0.get();
+                self.search(
+                    self.global_partition_id(&index_key),
+                    tx,
+                    move |partition, params, _| {
+                        nearest(
+                            partition.scan(params, embedding.as_slice(), |_| true),
+                            limit,
+                        )
+                    },
+                )
+                .await;
+            }
+            VsIndex::FilteredAnn {
+                index_key,
+                embedding,
+                filter,
+                limit,
+                tx,
+            } => {
+                if let Err(err) =
+                    validator::embedding_dimensions(&embedding, self.params.dimensions)
+                {
+                    _ = tx.send(Err(err));
+                    return;
+                }
+                let Some((partition_id, restrictions)) = self
+                    .table
+                    .read()
+                    .unwrap()
+                    .partition_id(&index_key, Some(filter.restrictions))
+                else {
+                    _ = tx.send(Ok((vec![], vec![])));
+                    return;
+                };
+                let restrictions = restrictions.unwrap_or_default();
+                let limit = limit.0.get();
+                self.search(Some(partition_id), tx, move |partition, params, table| {
+                    let table = table.read().unwrap();
+                    let valid = |primary_id| {
+                        restrictions.iter().all(|restriction| {
+                            table.is_valid_for(partition_id, primary_id, restriction)
+                        })
+                    };
+                    nearest(partition.scan(params, embedding.as_slice(), valid), limit)
+                })
+                .await;
+            }
+            VsIndex::AnnBatch {
+                index_key,
+                queries,
+                tx,
+            } => {
+                if let Some(err) = queries.iter().find_map(|(embedding, _)| {
+                    validator::embedding_dimensions(embedding, self.params.dimensions).err()
+                }) {
+                    _ = tx.send(Err(err));
+                    return;
+                }
+                let partition_id = self.global_partition_id(&index_key);
+                let partition =
+                    partition_id.and_then(|partition_id| self.partitions.get(&partition_id));
+                let (Some(partition_id), Some(partition)) = (partition_id, partition) else {
+                    _ = tx.send(Ok(queries.iter().map(|_| (vec![], vec![])).collect()));
+                    return;
+                };
+                let partition = Arc::clone(partition);
+                let params = Arc::clone(&self.params);
+                let table = Arc::clone(&self.table);
+                self.worker
+                    .spawn_blocking(move || {
+                        let results = queries
+                            .into_iter()
+                            .map(|(embedding, limit): (Vector, Limit)| {
+                                let neighbors = nearest(
+                                    partition.read().unwrap().scan(
+                                        &params,
+                                        embedding.as_slice(),
+                                        |_| true,
+                                    ),
+                                    limit.0.get(),
+                                );
+                                with_primary_keys(&params, &table, partition_id, neighbors)
+                            })
+                            .collect();
+                        _ = tx.send(results);
+                    })
+                    .await;
+            }
+            VsIndex::Range {
+                index_key,
+                embedding,
+                max_distance,
+                tx,
+            } => {
+                if let Err(err) =
+                    validator::embedding_dimensions(&embedding, self.params.dimensions)
+                {
+                    _ = tx.send(Err(err));
+                    return;
+                }
+                self.search(
+                    self.global_partition_id(&index_key),
+                    tx,
+                    move |partition, params, _| {
+                        let mut neighbors = partition.scan(params, embedding.as_slice(), |_| true);
+                        neighbors.retain(|(_, distance)| *distance <= max_distance);
+                        nearest(neighbors, usize::MAX)
+                    },
+                )
+                .await;
+            }
+            VsIndex::Count { tx, .. } => {
+                _ = tx.send(Ok(self.rows));
+            }
+            VsIndex::Stats { tx, .. } => {
+                let capacity = self
+                    .partitions
+                    .values()
+                    .map(|partition| partition.read().unwrap().primary_ids.capacity())
+                    .sum();
+                _ = tx.send(Ok(VsStats {
+                    size: self.rows,
+                    capacity,
+                    deleted: 0,
+                    partitions: self.partitions.len(),
+                }));
+            }
+            VsIndex::Duplicates {
+                max_distance, tx, ..
+            } => {
+                let partitions: Vec<_> = self
+                    .partitions
+                    .iter()
+                    .map(|(&partition_id, partition)| (partition_id, Arc::clone(partition)))
+                    .collect();
+                let params = Arc::clone(&self.params);
+                let table = Arc::clone(&self.table);
+                self.worker
+                    .spawn_blocking(move || {
+                        let clusters: Vec<_> = partitions
+                            .iter()
+                            .flat_map(|(partition_id, partition)| {
+                                partition
+                                    .read()
+                                    .unwrap()
+                                    .duplicates(&params, max_distance)
+                                    .into_iter()
+                                    .map(move |cluster| (*partition_id, cluster))
+                            })
+                            .collect();
+                        let table = table.read().unwrap();
+                        _ = tx.send(Ok(clusters
+                            .into_iter()
+                            .map(|(partition_id, cluster)| {
+                                cluster
+                                    .into_iter()
+                                    .filter_map(|primary_id| {
+                                        table.primary_key(partition_id, primary_id)
+                                    })
+                                    .collect::<Vec<_>>()
+                            })
+                            .filter(|cluster| cluster.len() > 1)
+                            .collect()));
+                    })
+                    .await;
+            }
+            VsIndex::KMeans { k, tx, .. } => {
+                let partitions: Vec<_> = self.partitions.values().cloned().collect();
+                let dimensions = self.dimensions();
+                self.worker
+                    .spawn_blocking(move || {
+                        let partitions: Vec<_> = partitions
+                            .iter()
+                            .map(|partition| partition.read().unwrap())
+                            .collect();
+                        let vectors: Vec<_> = partitions
+                            .iter()
+                            .flat_map(|partition| partition.vectors.chunks_exact(dimensions))
+                            .collect();
+                        _ = tx.send(kmeans::mini_batch(vectors.len(), k, |position| {
+                            Ok(Some(vectors[position].to_vec()))
+                        }));
+                    })
+                    .await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(space_type: SpaceType) -> Params {
+        Params {
+            dimensions: Dimensions(NonZeroUsize::new(3).unwrap()),
+            space_type,
+        }
+    }
+
+    #[test]
+    fn distances_match_exact_distances() {
+        let lhs: Vec<_> = (0..37).map(|idx| (idx as f32 * 0.7).sin()).collect();
+        let rhs: Vec<_> = (0..37).map(|idx| (idx as f32 * 0.3).cos()).collect();
+        for space_type in [
+            SpaceType::Euclidean,
+            SpaceType::Cosine,
+            SpaceType::DotProduct,
+            SpaceType::Hamming,
+        ] {
+            let distance =
+                params(space_type).distance(&lhs, squared_norm(&lhs), &rhs, squared_norm(&rhs));
+            let exact = spill::exact_distance(space_type, &lhs, &rhs);
+            assert!((distance - exact).abs() < 1e-4, "{space_type:?}");
+        }
+    }
+
+    #[test]
+    fn partition_keeps_vectors_of_rows() {
+        let params = params(SpaceType::Euclidean);
+        let mut partition = Partition::default();
+        assert!(partition.add(1.into(), &[0.0, 0.0, 0.0]));
+        assert!(partition.add(2.into(), &[1.0, 0.0, 0.0]));
+        assert!(partition.add(3.into(), &[5.0, 0.0, 0.0]));
+        assert!(!partition.add(1.into(), &[0.0, 2.0, 0.0]));
+        assert_eq!(partition.len(), 3);
+
+        let query = [0.0, 0.0, 0.0];
+        assert_eq!(
+            nearest(partition.scan(&params, &query, |_| true), 2),
+            vec![(2.into(), 1.0), (1.into(), 4.0)]
+        );
+
+        assert!(partition.remove(2.into(), 3));
+        assert!(!partition.remove(2.into(), 3));
+        assert_eq!(
+            nearest(partition.scan(&params, &query, |_| true), 5),
+            vec![(1.into(), 4.0), (3.into(), 25.0)]
+        );
+        assert_eq!(
+            nearest(
+                partition.scan(&params, &query, |primary_id| primary_id != 1.into()),
+                5
+            ),
+            vec![(3.into(), 25.0)]
+        );
+        assert_eq!(partition.vector(1, 3), [5.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn duplicates_are_clustered() {
+        let params = params(SpaceType::Euclidean);
+        let mut partition = Partition::default();
+        partition.add(1.into(), &[0.0, 0.0, 0.0]);
+        partition.add(2.into(), &[10.0, 0.0, 0.0]);
+        partition.add(3.into(), &[0.5, 0.0, 0.0]);
+        partition.add(4.into(), &[1.0, 0.0, 0.0]);
+        partition.add(5.into(), &[10.0, 0.0, 0.5]);
+        partition.add(6.into(), &[20.0, 0.0, 0.0]);
+
+        let mut clusters = partition.duplicates(&params, 0.25);
+        clusters.iter_mut().for_each(|cluster| cluster.sort());
+        clusters.sort();
+        assert_eq!(
+            clusters,
+            vec![vec![1.into(), 3.into(), 4.into()], vec![2.into(), 5.into()],]
+        );
+    }
+}
@@ -0,0 +1,251 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Scaling of the vectors of indexes quantized to 8-bit integers.
+//!
+//! Usearch quantizes a value by multiplying it by 127 and clamping it, so only values within
+//! [-1, 1] keep their precision. The vectors are scaled into this range by the largest magnitude
+//! found by the min/max statistics of the first [`TRAINING_SIZE`] vectors of the index, which
+//! mostly come from the initial full scan of the table. Until the scale is trained, the vectors
+//! are held back at full precision and added to the index when the training completes. A search
+//! trains the scale earlier if at least [`MIN_TRAINING_SIZE`] vectors are held back, otherwise the
+//! few vectors held back are searched by their exact distances and the scale stays untrained.
+//! A vector stays held back until it is added, so that a failed addition is retried later.
+
+use crate::table::PrimaryId;
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+/// Vectors collected to train the scale.
+const TRAINING_SIZE: usize = 1000;
+
+/// Vectors needed to train the scale at a search, before the training set is full.
+const MIN_TRAINING_SIZE: usize = 100;
+
+#[derive(Default)]
+pub(crate) struct I8Scale {
+    scale: OnceLock<f32>,
+    pending: Mutex<BTreeMap<PrimaryId, Vec<f32>>>,
+    /// Whether the scale is trained and all vectors held back were added.
+    flushed: AtomicBool,
+}
+
+impl I8Scale {
+    /// Returns the trained scale.
+    pub(crate) fn get(&self) -> Option<f32> {
+        self.scale.get().copied()
+    }
+
+    fn flushed(&self) -> Option<f32> {
+        self.flushed
+            .load(Ordering::Acquire)
+            .then(|| self.get())
+            .flatten()
+    }
+
+    /// Adds the scaled vector with `add`, or holds it back until the scale is trained.
+    pub(crate) fn add(
+        &self,
+        primary_id: PrimaryId,
+        vector: &[f32],
+        add: impl Fn(PrimaryId, &[f32]) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        if let Some(scale) = self.flushed() {
+            return add(primary_id, &scaled(vector, scale));
+        }
+        let mut pending = self.pending.lock().unwrap();
+        if let Some(scale) = self.flushed() {
+            return add(primary_id, &scaled(vector, scale));
+        }
+        pending.insert(primary_id, vector.to_vec());
+        if self.get().is_none() && pending.len() < TRAINING_SIZE {
+            return Ok(());
+        }
+        self.flush(&mut pending, add)
+    }
+
+    /// Removes a vector held back, returning whether it was held.
+    pub(crate) fn remove_pending(&self, primary_id: PrimaryId) -> bool {
+        self.flushed().is_none() && self.pending.lock().unwrap().remove(&primary_id).is_some()
+    }
+
+    /// Returns a vector held back.
+    pub(crate) fn pending(&self, primary_id: PrimaryId) -> Option<Vec<f32>> {
+        if self.flushed().is_some() {
+            return None;
+        }
+        self.pending.lock().unwrap().get(&primary_id).cloned()
+    }
+
+    /// Returns the vectors held back.
+    pub(crate) fn pending_ids(&self) -> Vec<PrimaryId> {
+        if self.flushed().is_some() {
+            return Vec::new();
+        }
+        self.pending.lock().unwrap().keys().copied().collect()
+    }
+
+    /// Returns the scaled query vector, training the scale by the vectors held back so far if it
+    /// is not trained yet. Returns `None` while too few vectors are held back to train the scale,
+    /// then they should be searched by [`Self::pending_ids`].
+    pub(crate) fn query(
+        &self,
+        vector: &[f32],
+        add: impl Fn(PrimaryId, &[f32]) -> anyhow::Result<()>,
+    ) -> anyhow::Result<Option<Vec<f32>>> {
+        if let Some(scale) = self.flushed() {
+            return Ok(Some(scaled(vector, scale)));
+        }
+        let mut pending = self.pending.lock().unwrap();
+        if self.get().is_none() && pending.len() < MIN_TRAINING_SIZE {
+            return Ok(None);
+        }
+        self.flush(&mut pending, add)?;
+        Ok(self.get().map(|scale| scaled(vector, scale)))
+    }
+
+    /// Trains the scale by the vectors held back unless it is trained already, and adds them with
+    /// `add`. A vector is released only when it is added.
+    fn flush(
+        &self,
+        pending: &mut BTreeMap<PrimaryId, Vec<f32>>,
+        add: impl Fn(PrimaryId, &[f32]) -> anyhow::Result<()>,
+    ) -> anyhow::Result<()> {
+        let scale = *self.scale.get_or_init(|| train(pending));
+        while let Some(entry) = pending.first_entry() {
+            add(*entry.key(), &scaled(entry.get(), scale))?;
+            entry.remove();
+        }
+        self.flushed.store(true, Ordering::Release);
+        Ok(())
+    }
+}
+
+/// Returns the scale of the vectors by their largest magnitude.
+fn train(vectors: &BTreeMap<PrimaryId, Vec<f32>>) -> f32 {
+    let (min, max) = vectors
+        .values()
+        .flatten()
+        .fold((0.0_f32, 0.0_f32), |(min, max), &value| {
+            (min.min(value), max.max(value))
+        });
+    let magnitude = f32::max(-min, max);
+    if magnitude.is_normal() {
+        1.0 / magnitude
+    } else {
+        1.0
+    }
+}
+
+fn scaled(vector: &[f32], scale: f32) -> Vec<f32> {
+    vector.iter().map(|value| value * scale).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors_are_held_until_scale_is_trained() {
+        let scale = I8Scale::default();
+        let added = Mutex::new(Vec::new());
+        let add = |primary_id: PrimaryId, vector: &[f32]| {
+            added.lock().unwrap().push((primary_id, vector.to_vec()));
+            Ok(())
+        };
+
+        scale.add(1.into(), &[2.0, -4.0], add).unwrap();
+        scale.add(2.into(), &[1.0, 0.0], add).unwrap();
+        scale.add(3.into(), &[0.0, 1.0], add).unwrap();
+        assert!(scale.remove_pending(3.into()));
+        assert_eq!(scale.pending(2.into()), Some(vec![1.0, 0.0]));
+        assert_eq!(scale.pending_ids(), vec![1.into(), 2.into()]);
+
+        // Too few vectors to train the scale at a search.
+        assert_eq!(scale.query(&[8.0, 0.0], add).unwrap(), None);
+        assert!(added.lock().unwrap().is_empty());
+        assert_eq!(scale.get(), None);
+
+        for idx in 10..10 + MIN_TRAINING_SIZE as u64 - 2 {
+            scale.add(idx.into(), &[0.0, 0.0], add).unwrap();
+        }
+        assert_eq!(scale.query(&[8.0, 0.0], add).unwrap(), Some(vec![2.0, 0.0]));
+        assert_eq!(scale.get(), Some(0.25));
+        assert_eq!(added.lock().unwrap().len(), MIN_TRAINING_SIZE);
+        assert_eq!(
+            added.lock().unwrap()[..2],
+            [(1.into(), vec![0.5, -1.0]), (2.into(), vec![0.25, 0.0])]
+        );
+        assert!(scale.pending_ids().is_empty());
+
+        scale.add(4.into(), &[4.0, 4.0], add).unwrap();
+        assert_eq!(
+            added.lock().unwrap().last(),
+            Some(&(4.into(), vec![1.0, 1.0]))
+        );
+        assert_eq!(scale.pending(4.into()), None);
+        assert!(!scale.remove_pending(4.into()));
+    }
+
+    #[test]
+    fn scale_is_trained_by_full_training_set() {
+        let scale = I8Scale::default();
+        let added = Mutex::new(0);
+        let add = |_: PrimaryId, _: &[f32]| {
+            *added.lock().unwrap() += 1;
+            Ok(())
+        };
+
+        for idx in 0..TRAINING_SIZE {
+            assert_eq!(scale.get(), None);
+            scale
+                .add((idx as u64).into(), &[idx as f32 / 10.0], add)
+                .unwrap();
+        }
+        let magnitude = (TRAINING_SIZE - 1) as f32 / 10.0;
+        assert!((scale.get().unwrap() - 1.0 / magnitude).abs() < 1e-6);
+        assert_eq!(*added.lock().unwrap(), TRAINING_SIZE);
+
+        // An empty index does not train the scale.
+        let empty = I8Scale::default();
+        assert_eq!(empty.query(&[0.0, 2.0], add).unwrap(), None);
+        assert_eq!(empty.get(), None);
+    }
+
+    #[test]
+    fn vectors_are_held_until_added() {
+        let scale = I8Scale::default();
+        let fail = |primary_id: PrimaryId, _: &[f32]| {
+            anyhow::ensure!(primary_id != 7.into(), "failed");
+            Ok(())
+        };
+        for idx in 0..TRAINING_SIZE as u64 - 1 {
+            scale.add(idx.into(), &[2.0], fail).unwrap();
+        }
+        assert!(
+            scale
+                .add((TRAINING_SIZE as u64).into(), &[2.0], fail)
+                .is_err()
+        );
+        assert_eq!(scale.get(), Some(0.5));
+        assert_eq!(scale.pending(6.into()), None);
+        assert_eq!(scale.pending(7.into()), Some(vec![2.0]));
+        assert_eq!(scale.pending_ids().len(), TRAINING_SIZE - 7);
+
+        // The next addition retries the vectors held back.
+        let added = Mutex::new(Vec::new());
+        let add = |primary_id: PrimaryId, vector: &[f32]| {
+            added.lock().unwrap().push((primary_id, vector.to_vec()));
+            Ok(())
+        };
+        scale.add(2000.into(), &[4.0], add).unwrap();
+        assert_eq!(added.lock().unwrap().len(), TRAINING_SIZE - 7 + 1);
+        assert_eq!(added.lock().unwrap()[0], (7.into(), vec![1.0]));
+        assert!(scale.pending_ids().is_empty());
+    }
+}
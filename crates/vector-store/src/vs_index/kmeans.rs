@@ -0,0 +1,176 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Mini-batch k-means over the vectors of an index, as in Sculley, "Web-scale k-means clustering".
+//!
+//! The centroids are seeded by k-means++ from a sample of the vectors. Each iteration assigns a
+//! random batch of vectors to their nearest centroids by Euclidean distance and moves every
+//! centroid towards its vectors by a rate falling with the number of vectors it was given. The
+//! sizes of the clusters are counted by a last pass over all vectors.
+
+use crate::vs_index::actor::Centroid;
+use std::num::NonZeroUsize;
+
+/// Vectors in the seeding sample and in a batch of an iteration.
+const BATCH_SIZE: usize = 1024;
+/// Batches applied to the centroids.
+const ITERATIONS: usize = 100;
+
+/// Returns up to `k` centroids of `count` vectors, read by their positions with `vector`, with the
+/// number of vectors nearest to each. Positions without a vector are skipped.
+pub(crate) fn mini_batch(
+    count: usize,
+    k: NonZeroUsize,
+    vector: impl Fn(usize) -> anyhow::Result<Option<Vec<f32>>>,
+) -> anyhow::Result<Vec<Centroid>> {
+    let mut positions: Vec<_> = (0..count).collect();
+    let sample_size = BATCH_SIZE.max(k.get()).min(count);
+    for idx in 0..sample_size {
+        positions.swap(idx, rand::random_range(idx..count));
+    }
+    let sample: Vec<_> = positions[..sample_size]
+        .iter()
+        .filter_map(|&position| vector(position).transpose())
+        .collect::<anyhow::Result<_>>()?;
+    let mut centroids = seed(&sample, k.get().min(sample.len()));
+    if centroids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut counts = vec![0_usize; centroids.len()];
+    for _ in 0..ITERATIONS {
+        let batch: Vec<_> = (0..BATCH_SIZE.min(count))
+            .filter_map(|_| vector(rand::random_range(0..count)).transpose())
+            .collect::<anyhow::Result<_>>()?;
+        let nearest: Vec<_> = batch
+            .iter()
+            .map(|vector| nearest(&centroids, vector))
+            .collect();
+        for (vector, idx) in batch.iter().zip(nearest) {
+            counts[idx] += 1;
+            let rate = 1.0 / counts[idx] as f32;
+            centroids[idx]
+                .iter_mut()
+                .zip(vector)
+                .for_each(|(centroid, value)| *centroid += rate * (value - *centroid));
+        }
+    }
+
+    let mut sizes = vec![0; centroids.len()];
+    for position in 0..count {
+        if let Some(vector) = vector(position)? {
+            sizes[nearest(&centroids, &vector)] += 1;
+        }
+    }
+    Ok(centroids
+        .into_iter()
+        .zip(sizes)
+        .map(|(centroid, size)| Centroid {
+            vector: centroid.into(),
+            size,
+        })
+        .collect())
+}
+
+/// Picks `k` vectors of the sample by k-means++: each next one with the probability proportional to
+/// its squared distance from the nearest picked one.
+fn seed(sample: &[Vec<f32>], k: usize) -> Vec<Vec<f32>> {
+    if k == 0 {
+        return Vec::new();
+    }
+    let mut centroids = vec![sample[rand::random_range(0..sample.len())].clone()];
+    let mut distances: Vec<_> = sample
+        .iter()
+        .map(|vector| distance(vector, &centroids[0]))
+        .collect();
+    while centroids.len() < k {
+        let total: f32 = distances.iter().sum();
+        let next = if total > 0.0 {
+            let mut point = rand::random_range(0.0..total);
+            distances
+                .iter()
+                .position(|distance| {
+                    point -= distance;
+                    point < 0.0
+                })
+                .unwrap_or(sample.len() - 1)
+        } else {
+            rand::random_range(0..sample.len())
+        };
+        distances
+            .iter_mut()
+            .zip(sample)
+            .for_each(|(nearest, vector)| {
+                *nearest = nearest.min(distance(vector, &sample[next]));
+            });
+        centroids.push(sample[next].clone());
+    }
+    centroids
+}
+
+fn nearest(centroids: &[Vec<f32>], vector: &[f32]) -> usize {
+    centroids
+        .iter()
+        .map(|centroid| distance(centroid, vector))
+        .enumerate()
+        .min_by(|(_, lhs), (_, rhs)| lhs.total_cmp(rhs))
+        .map(|(idx, _)| idx)
+        .unwrap()
+}
+
+/// Squared Euclidean distance.
+fn distance(lhs: &[f32], rhs: &[f32]) -> f32 {
+    lhs.iter().zip(rhs).map(|(l, r)| (l - r) * (l - r)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn centroids_of_separate_groups() {
+        let vectors = [
+            vec![0.0, 0.0],
+            vec![0.0, 0.2],
+            vec![0.2, 0.0],
+            vec![10.0, 10.0],
+            vec![10.0, 10.2],
+            vec![10.2, 10.0],
+            // A position without a vector.
+            vec![],
+        ];
+        let vector = |position: usize| {
+            Ok(Some(vectors[position].clone()).filter(|vector| !vector.is_empty()))
+        };
+
+        let mut centroids =
+            mini_batch(vectors.len(), NonZeroUsize::new(2).unwrap(), vector).unwrap();
+        centroids.sort_by(|lhs, rhs| {
+            let first = |centroid: &Centroid| centroid.vector.as_slice()[0];
+            first(lhs).total_cmp(&first(rhs))
+        });
+
+        assert_eq!(centroids.len(), 2);
+        assert_eq!(centroids[0].size, 3);
+        assert_eq!(centroids[1].size, 3);
+        assert!(distance(centroids[0].vector.as_slice(), &[0.067, 0.067]) < 0.01);
+        assert!(distance(centroids[1].vector.as_slice(), &[10.067, 10.067]) < 0.01);
+    }
+
+    #[test]
+    fn at_most_one_centroid_per_vector() {
+        let vector = |position: usize| Ok(Some(vec![position as f32]));
+
+        let centroids = mini_batch(2, NonZeroUsize::new(5).unwrap(), vector).unwrap();
+        assert_eq!(centroids.len(), 2);
+        assert!(centroids.iter().all(|centroid| centroid.size == 1));
+
+        assert!(
+            mini_batch(0, NonZeroUsize::new(5).unwrap(), vector)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
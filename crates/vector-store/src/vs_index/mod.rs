@@ -12,5 +12,12 @@ pub(crate) use actor::VsIndexExt;
 pub(crate) use validator::Error;
 
 pub(crate) mod diskann;
+#[cfg(feature = "faiss")]
+pub(crate) mod faiss;
+pub(crate) mod flat;
+pub(crate) mod i8_scale;
+pub(crate) mod kmeans;
+#[cfg(feature = "opensearch")]
 pub(crate) mod opensearch;
+pub(crate) mod spill;
 pub(crate) mod usearch;
@@ -3,17 +3,21 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
+use crate::ColumnName;
 use crate::Connectivity;
 use crate::Dimensions;
 use crate::Distance;
 use crate::ExpansionAdd;
 use crate::ExpansionSearch;
+use crate::Filter;
 use crate::IndexKey;
 use crate::Limit;
 use crate::PartitionId;
+use crate::Restriction;
 use crate::SpaceType;
 use crate::Vector;
 use crate::VsIndexFactory;
+use crate::actor_trace;
 use crate::memory::Memory;
 use crate::perf;
 use crate::table::IndexIdGenerator;
@@ -24,6 +28,8 @@ use crate::vs_index::actor::VsIndex;
 use crate::vs_index::factory::VsIndexConfiguration;
 use crate::vs_index::validator;
 use anyhow::anyhow;
+use anyhow::bail;
+use async_trait::async_trait;
 use opensearch::DeleteParts;
 use opensearch::IndexParts;
 use opensearch::OpenSearch;
@@ -31,11 +37,13 @@ use opensearch::http::Url;
 use opensearch::http::transport::SingleNodeConnectionPool;
 use opensearch::http::transport::TransportBuilder;
 use opensearch::indices::IndicesCreateParts;
+use scylla::value::CqlValue;
 use serde_json::Value;
 use serde_json::json;
 use std::fmt::Display;
 use std::sync::Arc;
 use std::sync::RwLock;
+use std::time::Instant;
 use tokio::sync::Notify;
 use tokio::sync::Semaphore;
 use tokio::sync::mpsc;
@@ -54,6 +62,7 @@ use super::actor::CountR;
 
 pub struct OpenSearchIndexFactory {
     client: Arc<OpenSearch>,
+    config_rx: watch::Receiver<Arc<crate::Config>>,
     shutdown_notify: Arc<Notify>,
 }
 
@@ -84,8 +93,9 @@ impl Display for SpaceType {
     }
 }
 
+#[async_trait]
 impl VsIndexFactory for OpenSearchIndexFactory {
-    fn create_index(
+    async fn create_index(
         &self,
         index: VsIndexConfiguration,
         table: Arc<RwLock<Table>>,
@@ -98,6 +108,7 @@ impl VsIndexFactory for OpenSearchIndexFactory {
             index.expansion_add,
             index.expansion_search,
             index.space_type,
+            self.config_rx.borrow().opensearch_engine.clone(),
             table,
             self.client.clone(),
         )
@@ -116,6 +127,7 @@ pub fn new_opensearch(
     let shutdown_notify = Arc::new(Notify::new());
     let factory = OpenSearchIndexFactory {
         client: Arc::new(OpenSearchIndexFactory::create_opensearch_client(addr)?),
+        config_rx: config_rx.clone(),
         shutdown_notify: shutdown_notify.clone(),
     };
 
@@ -155,8 +167,34 @@ async fn create_index(
     expansion_add: ExpansionAdd,
     expansion_search: ExpansionSearch,
     space_type: SpaceType,
+    engine: Option<&str>,
     client: Arc<OpenSearch>,
 ) -> Result<opensearch::http::response::Response, ()> {
+    let mut method = json!({
+        "name": "hnsw",
+        "space_type": space_type.to_string(),
+        "parameters": {
+            "ef_search": if expansion_search.0 > 0 {
+                expansion_search.0
+            } else {
+                100
+            },
+            "ef_construction": if expansion_add.0 > 0 {
+                expansion_add.0
+            } else {
+                100
+            },
+            "m": if connectivity.0 > 0 {
+                connectivity.0
+            } else {
+                16
+            },
+        }
+    });
+    if let Some(engine) = engine {
+        method["engine"] = json!(engine);
+    }
+
     let response: Result<opensearch::http::response::Response, ()> = client
         .indices()
         .create(IndicesCreateParts::Index(key.as_ref()))
@@ -165,31 +203,30 @@ async fn create_index(
                 "index.knn": true
             },
             "mappings": {
+                // Values of the filtering columns are stored under `columns` and compared as a
+                // whole, so strings are keywords and never detected as dates.
+                "date_detection": false,
+                "dynamic_templates": [
+                    {
+                        "column_strings": {
+                            "path_match": "columns.*",
+                            "match_mapping_type": "string",
+                            "mapping": { "type": "keyword" }
+                        }
+                    },
+                    {
+                        "column_doubles": {
+                            "path_match": "columns.*",
+                            "match_mapping_type": "double",
+                            "mapping": { "type": "double" }
+                        }
+                    }
+                ],
                 "properties": {
                     "vector": {
                         "type": "knn_vector",
                         "dimension": dimensions.0.get(),
-                        "method": {
-                            "name": "hnsw",
-                            "space_type": space_type.to_string(),
-                            "parameters": {
-                                "ef_search": if expansion_search.0 > 0 {
-                                    expansion_search.0
-                                } else {
-                                    100
-                                },
-                                "ef_construction": if expansion_add.0 > 0 {
-                                    expansion_add.0
-                                } else {
-                                    100
-                                },
-                                "m": if connectivity.0 > 0 {
-                                    connectivity.0
-                                } else {
-                                    16
-                                },
-                            }
-                        }
+                        "method": method
                     },
                 }
             }
@@ -216,6 +253,7 @@ pub fn new(
     expansion_add: ExpansionAdd,
     expansion_search: ExpansionSearch,
     space_type: SpaceType,
+    engine: Option<String>,
     table: Arc<RwLock<impl TableSearch + Send + Sync + 'static>>,
     client: Arc<OpenSearch>,
 ) -> anyhow::Result<mpsc::Sender<VsIndex>> {
@@ -232,6 +270,7 @@ pub fn new(
                 expansion_add,
                 expansion_search,
                 space_type,
+                engine.as_deref(),
                 client.clone(),
             )
             .await;
@@ -252,12 +291,17 @@ pub fn new(
             let key = Arc::new(key);
 
             while let Some(msg) = rx.recv().await {
+                let received = Instant::now();
+                let queue_len = rx.len();
                 let permit = Arc::clone(&semaphore).acquire_owned().await.unwrap();
                 tokio::spawn({
                     let key = Arc::clone(&key);
                     let table = Arc::clone(&table);
                     let client = Arc::clone(&client);
                     async move {
+                        let _trace =
+                            actor_trace::start("opensearch", msg.name(), Some(&*key), queue_len)
+                                .map(|trace| trace.queue_wait(received));
                         process(msg, dimensions, space_type, key, table, client).await;
                         drop(permit);
                     }
@@ -286,7 +330,7 @@ async fn process(
             embedding,
             in_progress: _in_progress,
             ..
-        } => add(index_key, primary_id, &embedding, client).await,
+        } => add(index_key, primary_id, &embedding, table, client).await,
         VsIndex::RemoveVector {
             primary_id,
             in_progress: _in_progress,
@@ -303,8 +347,62 @@ async fn process(
             )
             .await
         }
-        VsIndex::FilteredAnn { tx, .. } => filtered_ann(tx).await,
+        VsIndex::FilteredAnn {
+            embedding,
+            filter,
+            limit,
+            tx,
+            ..
+        } => {
+            filtered_ann(
+                index_key, tx, embedding, dimensions, filter, limit, space_type, table, client,
+            )
+            .await
+        }
+        VsIndex::AnnBatch { queries, tx, .. } => {
+            let results =
+                futures::future::try_join_all(queries.into_iter().map(|(embedding, limit)| {
+                    let (tx_ann, rx_ann) = oneshot::channel();
+                    let search = ann(
+                        Arc::clone(&index_key),
+                        tx_ann,
+                        embedding,
+                        dimensions,
+                        limit,
+                        space_type,
+                        Arc::clone(&table),
+                        Arc::clone(&client),
+                    );
+                    async move {
+                        search.await;
+                        rx_ann.await?
+                    }
+                }))
+                .await;
+            tx.send(results)
+                .unwrap_or_else(|_| trace!("ann batch: unable to send response"));
+        }
+        VsIndex::Range { tx, .. } => {
+            _ = tx.send(Err(anyhow!(
+                "range search of OpenSearch indexes is not supported"
+            )));
+        }
         VsIndex::Count { tx, .. } => count(index_key, tx, client).await,
+        VsIndex::Stats { tx, .. } => {
+            _ = tx.send(Err(anyhow!(
+                "statistics of OpenSearch indexes are not supported"
+            )));
+        }
+        VsIndex::Duplicates { tx, .. } => {
+            _ = tx.send(Err(anyhow!(
+                "duplicates of OpenSearch indexes are not supported"
+            )));
+        }
+        VsIndex::KMeans { tx, .. } => {
+            _ = tx.send(Err(anyhow!(
+                "k-means of OpenSearch indexes is not supported"
+            )));
+        }
 
         _ => todo!(),
     }
@@ -314,8 +412,17 @@ async fn add(
     index_key: Arc<IndexKey>,
     primary_id: PrimaryId,
     embeddings: &Vector,
+    table: Arc<RwLock<impl TableSearch>>,
     client: Arc<OpenSearch>,
 ) {
+    let columns: serde_json::Map<_, _> = table
+        .read()
+        .unwrap()
+        .values(primary_id)
+        .iter()
+        .filter_map(|(name, value)| Some((name.to_string(), field_value(value)?.0)))
+        .collect();
+
     _ = client
         .index(IndexParts::IndexId(
             index_key.as_ref().as_ref(),
@@ -323,6 +430,7 @@ async fn add(
         ))
         .body(json!({
             "vector": embeddings.as_slice(),
+            "columns": columns,
         }))
         .send()
         .await
@@ -369,15 +477,117 @@ async fn ann(
             .unwrap_or_else(|_| trace!("ann: unable to send response"));
     }
 
+    // We need to setup global index for opensearch, so we can run tests
+    let index_id = IndexIdGenerator::new().next(true).unwrap();
+    let partition_id = PartitionId::global(index_id);
+
+    let query = json!({
+        "vector": embedding.as_slice(),
+        "k": limit.0,
+    });
+    search(
+        key,
+        tx_ann,
+        query,
+        partition_id,
+        &[],
+        dimensions,
+        space_type,
+        table,
+        client,
+    )
+    .await;
+}
+
+/// Filtered ANN uses the OpenSearch efficient k-NN filtering: the filter is put into the `knn`
+/// query itself, so the engine applies it during the graph traversal and returns up to `k`
+/// matching results. The restrictions are translated into queries over the column values copied
+/// into the documents, which need an engine supporting efficient filtering (`faiss` or `lucene`).
+///
+/// Documents keep the values of the time their vector was written, so the hits are checked
+/// against the table once more and rows which don't match anymore are dropped.
+#[allow(clippy::too_many_arguments)]
+async fn filtered_ann(
+    key: Arc<IndexKey>,
+    tx_ann: oneshot::Sender<AnnR>,
+    embedding: Vector,
+    dimensions: Dimensions,
+    filter: Filter,
+    limit: Limit,
+    space_type: SpaceType,
+    table: Arc<RwLock<impl TableSearch>>,
+    client: Arc<OpenSearch>,
+) {
+    if let Err(err) = validator::embedding_dimensions(&embedding, dimensions) {
+        return tx_ann
+            .send(Err(err))
+            .unwrap_or_else(|_| trace!("filtered_ann: unable to send response"));
+    }
+
+    // Restrictions on the partition key are consumed by the partition lookup, but documents
+    // don't know their partitions, so the whole set of restrictions is pushed down.
+    let clauses = match filter_clauses(&filter.restrictions) {
+        Ok(clauses) => clauses,
+        Err(err) => {
+            _ = tx_ann.send(Err(err));
+            return;
+        }
+    };
+
+    let Some((partition_id, restrictions)) = table
+        .read()
+        .unwrap()
+        .partition_id(&key, Some(filter.restrictions))
+    else {
+        warn!("partition id not found for index key {key:?} during filtered ann");
+        _ = tx_ann.send(Ok((vec![], vec![])));
+        return;
+    };
+
+    let mut query = json!({
+        "vector": embedding.as_slice(),
+        "k": limit.0,
+    });
+    if !clauses.is_empty() {
+        query["filter"] = json!({
+            "bool": {
+                "filter": clauses,
+            }
+        });
+    }
+
+    search(
+        key,
+        tx_ann,
+        query,
+        partition_id,
+        restrictions.as_deref().unwrap_or_default(),
+        dimensions,
+        space_type,
+        table,
+        client,
+    )
+    .await;
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn search(
+    key: Arc<IndexKey>,
+    tx_ann: oneshot::Sender<AnnR>,
+    query: Value,
+    partition_id: PartitionId,
+    restrictions: &[Restriction],
+    dimensions: Dimensions,
+    space_type: SpaceType,
+    table: Arc<RwLock<impl TableSearch>>,
+    client: Arc<OpenSearch>,
+) {
     let response = client
         .search(opensearch::SearchParts::Index(&[key.as_ref().as_ref()]))
         .body(json!({
             "query": {
                 "knn": {
-                    "vector": {
-                        "vector": embedding.as_slice(),
-                        "k": limit.0,
-                    }
+                    "vector": query
                 }
             }
         }))
@@ -413,20 +623,20 @@ async fn ann(
         _ = tx_ann.send(Err(anyhow!("ann: unable to search for embedding")));
         return;
     }
-    // We need to setup global index for opensearch, so we can run tests
-    let index_id = IndexIdGenerator::new().next(true).unwrap();
-    let partition_id = PartitionId::global(index_id);
 
     let hits = {
         let table = table.read().unwrap();
         hits.unwrap()
             .iter()
-            .map(|hit| {
-                let id = hit["_id"].as_str().unwrap();
-                let score = hit["_score"].as_f64().unwrap();
-                let primary_id = PrimaryId::from(id.parse::<u64>().unwrap());
-                let primary_key = table.primary_key(partition_id, primary_id).unwrap();
-                (primary_key, score)
+            .filter_map(|hit| {
+                let primary_id = PrimaryId::from(hit["_id"].as_str()?.parse::<u64>().ok()?);
+                let score = hit["_score"].as_f64()?;
+                restrictions
+                    .iter()
+                    .all(|restriction| table.is_valid_for(partition_id, primary_id, restriction))
+                    .then_some(())?;
+                let primary_key = table.primary_key(partition_id, primary_id)?;
+                Some((primary_key, score))
             })
             .collect::<Vec<_>>()
     };
@@ -449,8 +659,126 @@ async fn ann(
         .unwrap_or_else(|_| trace!("ann: unable to send response"));
 }
 
-async fn filtered_ann(tx_ann: oneshot::Sender<AnnR>) {
-    _ = tx_ann.send(Err(anyhow!("Filtering not supported")));
+/// Returns the value of a column as stored in documents and whether the stored values keep the
+/// order of the CQL values, so that they can be compared by ranges. Values without a single
+/// representation, like decimals and varints, are not stored.
+fn field_value(value: &CqlValue) -> Option<(Value, bool)> {
+    let (value, ordered) = match value {
+        CqlValue::Ascii(value) | CqlValue::Text(value) => (json!(value), true),
+        CqlValue::TinyInt(value) => (json!(value), true),
+        CqlValue::SmallInt(value) => (json!(value), true),
+        CqlValue::Int(value) => (json!(value), true),
+        CqlValue::BigInt(value) => (json!(value), true),
+        CqlValue::Counter(value) => (json!(value.0), true),
+        CqlValue::Float(value) => (json!(f64::from(*value)), true),
+        CqlValue::Double(value) => (json!(value), true),
+        CqlValue::Timestamp(value) => (json!(value.0), true),
+        CqlValue::Date(value) => (json!(value.0), true),
+        CqlValue::Time(value) => (json!(value.0), true),
+        CqlValue::Boolean(value) => (json!(value), false),
+        CqlValue::Uuid(value) => (json!(value.to_string()), false),
+        CqlValue::Timeuuid(value) => (json!(value.as_ref().to_string()), false),
+        CqlValue::Inet(value) => (json!(value.to_string()), false),
+        CqlValue::Blob(value) => (json!(const_hex::encode(value)), false),
+        _ => return None,
+    };
+    // NaNs are serialized as nulls, which are not indexed.
+    (!value.is_null()).then_some((value, ordered))
+}
+
+fn field(column: &ColumnName) -> String {
+    format!("columns.{column}")
+}
+
+/// Translates restrictions of a filtered ANN query into OpenSearch queries over the column values
+/// stored in documents.
+fn filter_clauses(restrictions: &[Restriction]) -> anyhow::Result<Vec<Value>> {
+    restrictions
+        .iter()
+        .map(|restriction| match restriction {
+            Restriction::Eq { lhs, rhs } => term(lhs, rhs),
+            Restriction::In { lhs, rhs } => Ok(json!({
+                "terms": {
+                    field(lhs): rhs
+                        .iter()
+                        .map(|rhs| value_of(lhs, rhs))
+                        .collect::<anyhow::Result<Vec<_>>>()?,
+                }
+            })),
+            Restriction::Lt { lhs, rhs } => range(lhs, "lt", rhs),
+            Restriction::Lte { lhs, rhs } => range(lhs, "lte", rhs),
+            Restriction::Gt { lhs, rhs } => range(lhs, "gt", rhs),
+            Restriction::Gte { lhs, rhs } => range(lhs, "gte", rhs),
+            Restriction::EqTuple { lhs, rhs } => terms(lhs, rhs),
+            Restriction::InTuple { lhs, rhs } => Ok(json!({
+                "bool": {
+                    "should": rhs
+                        .iter()
+                        .map(|rhs| terms(lhs, rhs))
+                        .collect::<anyhow::Result<Vec<_>>>()?,
+                    "minimum_should_match": 1,
+                }
+            })),
+            Restriction::LtTuple { lhs, rhs } => tuple_range(lhs, "lt", rhs),
+            Restriction::LteTuple { lhs, rhs } => tuple_range(lhs, "lte", rhs),
+            Restriction::GtTuple { lhs, rhs } => tuple_range(lhs, "gt", rhs),
+            Restriction::GteTuple { lhs, rhs } => tuple_range(lhs, "gte", rhs),
+        })
+        .collect()
+}
+
+fn value_of(column: &ColumnName, value: &CqlValue) -> anyhow::Result<Value> {
+    let Some((value, _)) = field_value(value) else {
+        bail!("filtering {column} by {value:?} is not supported by OpenSearch indexes");
+    };
+    Ok(value)
+}
+
+fn term(column: &ColumnName, value: &CqlValue) -> anyhow::Result<Value> {
+    Ok(json!({ "term": { field(column): value_of(column, value)? } }))
+}
+
+/// Matches rows equal to the tuple.
+fn terms(columns: &[ColumnName], values: &[CqlValue]) -> anyhow::Result<Value> {
+    Ok(json!({
+        "bool": {
+            "filter": columns
+                .iter()
+                .zip(values)
+                .map(|(column, value)| term(column, value))
+                .collect::<anyhow::Result<Vec<_>>>()?,
+        }
+    }))
+}
+
+fn range(column: &ColumnName, op: &str, value: &CqlValue) -> anyhow::Result<Value> {
+    let Some((value, true)) = field_value(value) else {
+        bail!("range filtering {column} by {value:?} is not supported by OpenSearch indexes");
+    };
+    Ok(json!({ "range": { field(column): { op: value } } }))
+}
+
+/// Compares tuples lexicographically: `(a, b) < (x, y)` matches `a < x` or `a = x and b < y`.
+fn tuple_range(columns: &[ColumnName], op: &str, values: &[CqlValue]) -> anyhow::Result<Value> {
+    let len = columns.len().min(values.len());
+    let strict = &op[..2];
+    Ok(json!({
+        "bool": {
+            "should": (0..len)
+                .map(|idx| {
+                    let mut filter = columns[..idx]
+                        .iter()
+                        .zip(&values[..idx])
+                        .map(|(column, value)| term(column, value))
+                        .collect::<anyhow::Result<Vec<_>>>()?;
+                    let op = if idx + 1 == len { op } else { strict };
+                    filter.push(range(&columns[idx], op, &values[idx])?);
+                    Ok(json!({ "bool": { "filter": filter } }))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?,
+            "minimum_should_match": 1,
+        }
+    }))
 }
 
 async fn count(key: Arc<IndexKey>, tx: oneshot::Sender<CountR>, client: Arc<OpenSearch>) {
@@ -0,0 +1,109 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+//! Full precision vectors of quantized indexes kept on local disk.
+//!
+//! A quantized index holds only the quantized vectors in memory. The full precision vectors are
+//! written to an unlinked file, one fixed size record per row of the table, and read back only to
+//! rescore the candidates of a search by their exact distances.
+
+use crate::SpaceType;
+use crate::table::Idx;
+use crate::table::PrimaryId;
+use std::fs::File;
+use std::io;
+use std::os::unix::fs::FileExt;
+use std::path::Path;
+
+pub(crate) struct SpilledVectors {
+    file: File,
+    dimensions: usize,
+}
+
+impl SpilledVectors {
+    pub(crate) fn new(dir: &Path, dimensions: usize) -> io::Result<Self> {
+        Ok(Self {
+            file: tempfile::tempfile_in(dir)?,
+            dimensions,
+        })
+    }
+
+    fn offset(&self, primary_id: PrimaryId) -> u64 {
+        (primary_id.idx() * self.dimensions * size_of::<f32>()) as u64
+    }
+
+    pub(crate) fn write(&self, primary_id: PrimaryId, vector: &[f32]) -> io::Result<()> {
+        let bytes: Vec<u8> = vector
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        self.file.write_all_at(&bytes, self.offset(primary_id))
+    }
+
+    pub(crate) fn read(&self, primary_id: PrimaryId) -> io::Result<Vec<f32>> {
+        let mut bytes = vec![0; self.dimensions * size_of::<f32>()];
+        self.file
+            .read_exact_at(&mut bytes, self.offset(primary_id))?;
+        Ok(bytes
+            .chunks_exact(size_of::<f32>())
+            .map(|value| f32::from_le_bytes(value.try_into().unwrap()))
+            .collect())
+    }
+}
+
+/// Returns the distance between full precision vectors as computed by usearch for the space type.
+pub(crate) fn exact_distance(space_type: SpaceType, lhs: &[f32], rhs: &[f32]) -> f32 {
+    let dot = |lhs: &[f32], rhs: &[f32]| lhs.iter().zip(rhs).map(|(l, r)| l * r).sum::<f32>();
+    match space_type {
+        SpaceType::Cosine => {
+            let norms = (dot(lhs, lhs) * dot(rhs, rhs)).sqrt();
+            if norms == 0.0 {
+                return 1.0;
+            }
+            (1.0 - dot(lhs, rhs) / norms).clamp(0.0, 2.0)
+        }
+        SpaceType::Euclidean => lhs.iter().zip(rhs).map(|(l, r)| (l - r) * (l - r)).sum(),
+        SpaceType::DotProduct => 1.0 - dot(lhs, rhs),
+        SpaceType::Hamming => lhs
+            .iter()
+            .zip(rhs)
+            .filter(|(l, r)| (**l > 0.0) != (**r > 0.0))
+            .count() as f32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vectors_are_read_by_primary_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let vectors = SpilledVectors::new(dir.path(), 3).unwrap();
+
+        vectors.write(5.into(), &[1.0, 2.0, 3.0]).unwrap();
+        vectors.write(1.into(), &[4.0, 5.0, 6.0]).unwrap();
+        vectors.write(5.into(), &[7.0, 8.0, 9.0]).unwrap();
+
+        assert_eq!(vectors.read(1.into()).unwrap(), vec![4.0, 5.0, 6.0]);
+        assert_eq!(vectors.read(5.into()).unwrap(), vec![7.0, 8.0, 9.0]);
+        assert!(vectors.read(6.into()).is_err());
+    }
+
+    #[test]
+    fn exact_distances_match_space_types() {
+        let lhs = [1.0, 0.0];
+        let rhs = [0.0, 2.0];
+        assert_eq!(exact_distance(SpaceType::Cosine, &lhs, &rhs), 1.0);
+        assert_eq!(exact_distance(SpaceType::Cosine, &lhs, &[3.0, 0.0]), 0.0);
+        assert_eq!(exact_distance(SpaceType::Cosine, &lhs, &[0.0, 0.0]), 1.0);
+        assert_eq!(exact_distance(SpaceType::Euclidean, &lhs, &rhs), 5.0);
+        assert_eq!(
+            exact_distance(SpaceType::DotProduct, &lhs, &[0.5, 1.0]),
+            0.5
+        );
+        assert_eq!(exact_distance(SpaceType::Hamming, &lhs, &rhs), 2.0);
+    }
+}
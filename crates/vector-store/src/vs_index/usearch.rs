@@ -9,11 +9,14 @@ use crate::Distance;
 use crate::Filter;
 use crate::IndexKey;
 use crate::Limit;
+use crate::PrimaryKey;
 use crate::Quantization;
 use crate::SpaceType;
 use crate::Vector;
 use crate::VsIndexFactory;
+use crate::actor_trace;
 use crate::memory::Allocate;
+use crate::memory::IndexMemory;
 use crate::memory::Memory;
 use crate::memory::MemoryExt;
 use crate::perf;
@@ -23,16 +26,28 @@ use crate::table::PrimaryId;
 use crate::table::Table;
 use crate::table::TableSearch;
 use crate::vs_index::actor::AnnR;
+use crate::vs_index::actor::DuplicatesR;
+use crate::vs_index::actor::KMeansR;
 use crate::vs_index::actor::VsIndex;
+use crate::vs_index::actor::VsStats;
 use crate::vs_index::factory::VsIndexConfiguration;
+use crate::vs_index::i8_scale::I8Scale;
+use crate::vs_index::kmeans;
+use crate::vs_index::spill;
+use crate::vs_index::spill::SpilledVectors;
 use crate::vs_index::validator;
 use crate::worker;
+use crate::worker::KeyspaceWorkers;
 use crate::worker::Worker;
 use crate::worker::WorkerExt;
 use anyhow::anyhow;
+use async_trait::async_trait;
 use itertools::Itertools;
+use std::cmp::Reverse;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::RwLock;
 use std::sync::atomic::AtomicUsize;
@@ -56,17 +71,25 @@ use usearch::ScalarKind;
 use usearch::b1x8;
 
 pub struct UsearchIndexFactory {
-    worker: async_channel::Sender<Worker>,
+    workers: KeyspaceWorkers,
+    config_rx: watch::Receiver<Arc<Config>>,
     mode: Mode,
 }
 
+#[async_trait]
 impl VsIndexFactory for UsearchIndexFactory {
-    fn create_index(
+    async fn create_index(
         &self,
         index: VsIndexConfiguration,
         table: Arc<RwLock<Table>>,
         memory: mpsc::Sender<Memory>,
     ) -> anyhow::Result<mpsc::Sender<VsIndex>> {
+        let (worker, threads) = self.workers.worker(index.key.keyspace());
+        let graph_memory = GraphMemory {
+            memory,
+            index: table.read().unwrap().memory().clone(),
+            vector_bytes: estimated_vector_bytes(&index),
+        };
         match &self.mode {
             Mode::Usearch => {
                 let options = IndexOptions {
@@ -78,14 +101,32 @@ impl VsIndexFactory for UsearchIndexFactory {
                     quantization: index.quantization.into(),
                     ..Default::default()
                 };
-                let threads = perf::num_workers().into();
+                let threads = threads.get();
+                if let Some(dir) = spill_dir(&self.config_rx.borrow(), index.quantization) {
+                    let dimensions = index.dimensions.0.get();
+                    let space_type = index.space_type;
+                    return new(
+                        move || {
+                            Ok(Arc::new(SpilledUsearchIndex {
+                                inner: ThreadedUsearchIndex::new(options, threads)?,
+                                vectors: SpilledVectors::new(&dir, dimensions)?,
+                                space_type,
+                            }))
+                        },
+                        index.key,
+                        index.dimensions,
+                        table,
+                        worker,
+                        graph_memory,
+                    );
+                }
                 new(
                     move || Ok(Arc::new(ThreadedUsearchIndex::new(options, threads)?)),
                     index.key,
                     index.dimensions,
                     table,
-                    self.worker.clone(),
-                    memory,
+                    worker,
+                    graph_memory,
                 )
             }
             Mode::Simulator { config, config_rx } => new(
@@ -98,8 +139,8 @@ impl VsIndexFactory for UsearchIndexFactory {
                 index.key,
                 index.dimensions,
                 table,
-                self.worker.clone(),
-                memory,
+                worker,
+                graph_memory,
             ),
         }
     }
@@ -117,7 +158,8 @@ pub fn new_usearch(
 ) -> anyhow::Result<UsearchIndexFactory> {
     let config = config_rx.borrow_and_update().clone();
     Ok(UsearchIndexFactory {
-        worker: worker::new(),
+        workers: KeyspaceWorkers::new(config_rx.clone()),
+        config_rx: config_rx.clone(),
         mode: if config.usearch_simulator.is_none() {
             Mode::Usearch
         } else {
@@ -126,6 +168,31 @@ pub fn new_usearch(
     })
 }
 
+/// Returns the directory full precision vectors of an index are spilled to. Only indexes
+/// quantized to floats or integers are rescored, as binary ones are searched by Hamming distance.
+fn spill_dir(config: &Config, quantization: Quantization) -> Option<PathBuf> {
+    match quantization {
+        Quantization::F16 | Quantization::BF16 | Quantization::I8 => {
+            config.vector_spill_dir.clone()
+        }
+        Quantization::F32 | Quantization::B1 => None,
+    }
+}
+
+/// Estimates memory reserved for a single vector: the vector itself and its neighbors in the
+/// base layer of the graph.
+fn estimated_vector_bytes(index: &VsIndexConfiguration) -> u64 {
+    let dimensions = index.dimensions.0.get() as u64;
+    let vector = match index.quantization {
+        Quantization::F32 => dimensions * 4,
+        Quantization::F16 | Quantization::BF16 => dimensions * 2,
+        Quantization::I8 => dimensions,
+        Quantization::B1 => dimensions.div_ceil(8),
+    };
+    let neighbors = 2 * index.connectivity.0 as u64 * size_of::<u32>() as u64;
+    vector + neighbors + size_of::<u64>() as u64
+}
+
 enum Mode {
     Usearch,
     Simulator {
@@ -139,6 +206,8 @@ trait UsearchIndex {
     fn capacity(&self) -> usize;
     fn add(&self, primary_id: PrimaryId, vector: &Vector) -> anyhow::Result<()>;
     fn remove(&self, primary_id: PrimaryId) -> anyhow::Result<bool>;
+    /// Returns the vector of a row, or `None` if the index has no vector of it.
+    fn get(&self, primary_id: PrimaryId) -> anyhow::Result<Option<Vector>>;
     fn search(
         &self,
         vector: &Vector,
@@ -151,6 +220,11 @@ trait UsearchIndex {
         filter: impl Fn(PrimaryId) -> bool,
     ) -> anyhow::Result<impl Iterator<Item = anyhow::Result<(PrimaryId, Distance)>>>;
 
+    /// Whether searches read from disk, so they must not run on the non-blocking path.
+    fn reads_disk(&self) -> bool {
+        false
+    }
+
     fn stop(&self);
 }
 
@@ -159,6 +233,8 @@ struct ThreadedUsearchIndex {
     threads: usize,
     quantization: usearch::ScalarKind,
     space_type: usearch::MetricKind,
+    /// The scale of the vectors of an index quantized to 8-bit integers.
+    scale: Option<I8Scale>,
 }
 
 impl ThreadedUsearchIndex {
@@ -168,8 +244,70 @@ impl ThreadedUsearchIndex {
             threads,
             quantization: options.quantization,
             space_type: options.metric,
+            scale: (options.quantization == ScalarKind::I8).then(I8Scale::default),
         })
     }
+
+    fn add_scaled(&self, primary_id: PrimaryId, vector: &[f32]) -> anyhow::Result<()> {
+        Ok(self.inner.add(primary_id.into(), vector)?)
+    }
+
+    fn contains(&self, primary_id: PrimaryId) -> bool {
+        self.inner.contains(primary_id.into())
+            || self
+                .scale
+                .as_ref()
+                .is_some_and(|scale| scale.pending(primary_id).is_some())
+    }
+
+    /// Returns the matches with their distances, recomputed from the dequantized vectors of an
+    /// index quantized to 8-bit integers, so that they do not depend on the scale.
+    fn matches(
+        &self,
+        vector: &Vector,
+        limit: Limit,
+        keys: Vec<u64>,
+        distances: Vec<f32>,
+    ) -> anyhow::Result<impl Iterator<Item = anyhow::Result<(PrimaryId, Distance)>>> {
+        let space_type: SpaceType = self.space_type.try_into()?;
+        let mut matches: Vec<_> = keys
+            .into_iter()
+            .map(PrimaryId::from)
+            .zip(distances)
+            .collect();
+        if self.scale.is_some() {
+            matches = matches
+                .into_iter()
+                .filter_map(|(primary_id, _)| {
+                    let stored = self.get(primary_id).transpose()?;
+                    Some(stored.map(|stored| {
+                        let distance =
+                            spill::exact_distance(space_type, vector.as_slice(), stored.as_slice());
+                        (primary_id, distance)
+                    }))
+                })
+                .collect::<anyhow::Result<_>>()?;
+            matches.sort_by(|(_, lhs), (_, rhs)| lhs.total_cmp(rhs));
+            matches.truncate(limit.0.get());
+        }
+        let dimensions = vector.dim();
+        Ok(matches.into_iter().map(move |(primary_id, distance)| {
+            Distance::try_from((distance, space_type, dimensions)).map(|dist| (primary_id, dist))
+        }))
+    }
+}
+
+/// Returns all vectors held back before the scale of an index quantized to 8-bit integers is
+/// trained, as matches to be ranked by their exact distances.
+fn pending_matches(scale: &I8Scale, filter: impl Fn(PrimaryId) -> bool) -> usearch::ffi::Matches {
+    let keys: Vec<_> = scale
+        .pending_ids()
+        .into_iter()
+        .filter(|&primary_id| filter(primary_id))
+        .map(u64::from)
+        .collect();
+    let distances = vec![0.0; keys.len()];
+    usearch::ffi::Matches { keys, distances }
 }
 
 impl UsearchIndex for ThreadedUsearchIndex {
@@ -188,13 +326,49 @@ impl UsearchIndex for ThreadedUsearchIndex {
             let vector = f32_to_b1x8(vector.as_slice());
             return Ok(self.inner.add(primary_id.into(), &vector)?);
         }
-        Ok(self.inner.add(primary_id.into(), vector.as_slice())?)
+        if let Some(scale) = &self.scale {
+            return scale.add(primary_id, vector.as_slice(), |primary_id, vector| {
+                self.add_scaled(primary_id, vector)
+            });
+        }
+        self.add_scaled(primary_id, vector.as_slice())
     }
 
     fn remove(&self, primary_id: PrimaryId) -> anyhow::Result<bool> {
+        if let Some(scale) = &self.scale
+            && scale.remove_pending(primary_id)
+        {
+            return Ok(true);
+        }
         Ok(self.inner.remove(primary_id.into())? != 0)
     }
 
+    fn get(&self, primary_id: PrimaryId) -> anyhow::Result<Option<Vector>> {
+        let dimensions = self.inner.dimensions();
+        if self.quantization == ScalarKind::B1 {
+            let mut bytes = vec![b1x8(0); dimensions.div_ceil(8)];
+            if self.inner.get(primary_id.into(), &mut bytes)? == 0 {
+                return Ok(None);
+            }
+            return Ok(Some(b1x8_to_f32(&bytes, dimensions).into()));
+        }
+        if let Some(vector) = self
+            .scale
+            .as_ref()
+            .and_then(|scale| scale.pending(primary_id))
+        {
+            return Ok(Some(vector.into()));
+        }
+        let mut vector = vec![0.0; dimensions];
+        if self.inner.get(primary_id.into(), &mut vector)? == 0 {
+            return Ok(None);
+        }
+        if let Some(scale) = self.scale.as_ref().and_then(I8Scale::get) {
+            vector.iter_mut().for_each(|value| *value /= scale);
+        }
+        Ok(Some(vector.into()))
+    }
+
     fn search(
         &self,
         vector: &Vector,
@@ -203,17 +377,17 @@ impl UsearchIndex for ThreadedUsearchIndex {
         let matches = if self.quantization == ScalarKind::B1 {
             let vector = f32_to_b1x8(vector.as_slice());
             self.inner.search(&vector, limit.0.get())?
+        } else if let Some(scale) = &self.scale {
+            match scale.query(vector.as_slice(), |primary_id, vector| {
+                self.add_scaled(primary_id, vector)
+            })? {
+                Some(query) => self.inner.search(&query, limit.0.get())?,
+                None => pending_matches(scale, |_| true),
+            }
         } else {
             self.inner.search(vector.as_slice(), limit.0.get())?
         };
-        Ok(matches
-            .keys
-            .into_iter()
-            .zip(matches.distances)
-            .map(|(primary_id, distance)| {
-                Distance::try_from((distance, self.space_type.try_into()?, vector.dim()))
-                    .map(|dist| (primary_id.into(), dist))
-            }))
+        self.matches(vector, limit, matches.keys, matches.distances)
     }
 
     fn filtered_search(
@@ -226,20 +400,118 @@ impl UsearchIndex for ThreadedUsearchIndex {
             let vector = f32_to_b1x8(vector.as_slice());
             self.inner
                 .filtered_search(&vector, limit.0.get(), |row_id| filter(row_id.into()))?
+        } else if let Some(scale) = &self.scale {
+            match scale.query(vector.as_slice(), |primary_id, vector| {
+                self.add_scaled(primary_id, vector)
+            })? {
+                Some(query) => self
+                    .inner
+                    .filtered_search(&query, limit.0.get(), |row_id| filter(row_id.into()))?,
+                None => pending_matches(scale, filter),
+            }
         } else {
             self.inner
                 .filtered_search(vector.as_slice(), limit.0.get(), |row_id| {
                     filter(row_id.into())
                 })?
         };
-        Ok(matches
-            .keys
-            .into_iter()
-            .zip(matches.distances)
-            .map(|(primary_id, distance)| {
-                Distance::try_from((distance, self.space_type.try_into()?, vector.dim()))
-                    .map(|dist| (primary_id.into(), dist))
-            }))
+        self.matches(vector, limit, matches.keys, matches.distances)
+    }
+
+    fn stop(&self) {}
+}
+
+/// A quantized index with its full precision vectors spilled to disk. Searches take
+/// [`RESCORE_OVERSAMPLING`] times more candidates from the quantized graph and return the nearest
+/// ones by their exact distances.
+struct SpilledUsearchIndex {
+    inner: ThreadedUsearchIndex,
+    vectors: SpilledVectors,
+    space_type: SpaceType,
+}
+
+impl SpilledUsearchIndex {
+    fn rescore(
+        &self,
+        vector: &Vector,
+        limit: Limit,
+        candidates: impl Iterator<Item = anyhow::Result<(PrimaryId, Distance)>>,
+    ) -> anyhow::Result<impl Iterator<Item = anyhow::Result<(PrimaryId, Distance)>>> {
+        let mut rescored = itertools::process_results(candidates, |candidates| {
+            candidates
+                .map(|(primary_id, _)| {
+                    let spilled = self.vectors.read(primary_id)?;
+                    let distance =
+                        spill::exact_distance(self.space_type, vector.as_slice(), &spilled);
+                    Ok((primary_id, distance))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        })??;
+        rescored.sort_by(|(_, lhs), (_, rhs)| lhs.total_cmp(rhs));
+        rescored.truncate(limit.0.get());
+        let space_type = self.space_type;
+        let dimensions = vector.dim();
+        Ok(rescored.into_iter().map(move |(primary_id, distance)| {
+            Distance::try_from((distance, space_type, dimensions)).map(|dist| (primary_id, dist))
+        }))
+    }
+}
+
+fn oversampled(limit: Limit) -> Limit {
+    limit
+        .0
+        .saturating_mul(NonZeroUsize::new(RESCORE_OVERSAMPLING).unwrap())
+        .into()
+}
+
+impl UsearchIndex for SpilledUsearchIndex {
+    fn reserve(&self, size: usize) -> anyhow::Result<()> {
+        self.inner.reserve(size)
+    }
+
+    fn capacity(&self) -> usize {
+        self.inner.capacity()
+    }
+
+    fn add(&self, primary_id: PrimaryId, vector: &Vector) -> anyhow::Result<()> {
+        self.vectors.write(primary_id, vector.as_slice())?;
+        self.inner.add(primary_id, vector)
+    }
+
+    fn remove(&self, primary_id: PrimaryId) -> anyhow::Result<bool> {
+        self.inner.remove(primary_id)
+    }
+
+    fn get(&self, primary_id: PrimaryId) -> anyhow::Result<Option<Vector>> {
+        if !self.inner.contains(primary_id) {
+            return Ok(None);
+        }
+        Ok(Some(self.vectors.read(primary_id)?.into()))
+    }
+
+    fn search(
+        &self,
+        vector: &Vector,
+        limit: Limit,
+    ) -> anyhow::Result<impl Iterator<Item = anyhow::Result<(PrimaryId, Distance)>>> {
+        let candidates = self.inner.search(vector, oversampled(limit))?;
+        self.rescore(vector, limit, candidates)
+    }
+
+    fn filtered_search(
+        &self,
+        vector: &Vector,
+        limit: Limit,
+        filter: impl Fn(PrimaryId) -> bool,
+    ) -> anyhow::Result<impl Iterator<Item = anyhow::Result<(PrimaryId, Distance)>>> {
+        let candidates = self
+            .inner
+            .filtered_search(vector, oversampled(limit), filter)?;
+        self.rescore(vector, limit, candidates)
+    }
+
+    fn reads_disk(&self) -> bool {
+        true
     }
 
     fn stop(&self) {}
@@ -387,6 +659,11 @@ impl UsearchIndex for RwLock<Simulator> {
         Ok(removed)
     }
 
+    /// The simulator keeps no vectors.
+    fn get(&self, _: PrimaryId) -> anyhow::Result<Option<Vector>> {
+        Ok(None)
+    }
+
     #[hotpath::measure]
     fn search(
         &self,
@@ -437,6 +714,16 @@ impl UsearchIndex for RwLock<Simulator> {
 const RESERVE_INCREMENT_GLOBAL: usize = 1000000;
 const RESERVE_INCREMENT_LOCAL: usize = 1000;
 
+/// Candidates taken from the quantized graph per result when rescoring by spilled vectors.
+const RESCORE_OVERSAMPLING: usize = 4;
+
+/// Nearest neighbours searched per vector when looking for near-duplicates.
+const DUPLICATE_CANDIDATES: usize = 10;
+
+/// Nearest neighbours searched first by a range search, doubled until the search reaches beyond
+/// its distance.
+const RANGE_INITIAL_LIMIT: usize = 64;
+
 struct MetricConfig {
     quantization: Quantization,
     space_type: SpaceType,
@@ -527,9 +814,14 @@ mod operation {
             match msg {
                 VsIndex::AddVector { .. } => Mode::Insert,
                 VsIndex::RemoveVector { .. } => Mode::Remove,
-                VsIndex::Ann { .. } | VsIndex::FilteredAnn { .. } => Mode::Search,
+                VsIndex::Ann { .. }
+                | VsIndex::FilteredAnn { .. }
+                | VsIndex::AnnBatch { .. }
+                | VsIndex::Range { .. }
+                | VsIndex::Duplicates { .. }
+                | VsIndex::KMeans { .. } => Mode::Search,
                 VsIndex::RemovePartition { .. } => todo!(),
-                VsIndex::Count { .. } => unreachable!(),
+                VsIndex::Count { .. } | VsIndex::Stats { .. } => unreachable!(),
             }
         }
     }
@@ -619,6 +911,10 @@ struct PartitionState<I: UsearchIndex + Send + Sync + 'static> {
     partition_id: PartitionId,
     size: Arc<AtomicUsize>,
     capacity: Arc<AtomicUsize>,
+    /// Slots of removed vectors, which usearch reuses for added vectors.
+    deleted: AtomicUsize,
+    /// Capacity reserved by all partitions of the index actor.
+    reserved: Arc<AtomicUsize>,
     capacity_increment: usize,
     free_threshold: usize,
     idx: Arc<I>,
@@ -628,7 +924,7 @@ impl<I> PartitionState<I>
 where
     I: UsearchIndex + Send + Sync + 'static,
 {
-    fn new(partition_id: PartitionId, idx: Arc<I>) -> Self {
+    fn new(partition_id: PartitionId, idx: Arc<I>, reserved: Arc<AtomicUsize>) -> Self {
         let capacity_increment = if partition_id.index_id().is_global() {
             RESERVE_INCREMENT_GLOBAL
         } else {
@@ -638,6 +934,8 @@ where
             partition_id,
             size: Arc::new(AtomicUsize::new(0)),
             capacity: Arc::new(AtomicUsize::new(0)),
+            deleted: AtomicUsize::new(0),
+            reserved,
             capacity_increment,
             free_threshold: perf::channel_size().into(),
             idx,
@@ -661,6 +959,16 @@ where
     }
 }
 
+impl<I> Drop for PartitionState<I>
+where
+    I: UsearchIndex + Send + Sync + 'static,
+{
+    fn drop(&mut self) {
+        self.reserved
+            .fetch_sub(self.capacity.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+}
+
 struct IndexState {
     dimensions: Dimensions,
     size: Arc<AtomicUsize>,
@@ -677,14 +985,27 @@ impl IndexState {
     }
 }
 
+/// The memory actor of an index together with the accounting of the memory of its graph.
+struct GraphMemory {
+    memory: mpsc::Sender<Memory>,
+    index: IndexMemory,
+    /// Estimated bytes taken by a single vector in the graph.
+    vector_bytes: u64,
+}
+
 fn new<I: UsearchIndex + Send + Sync + 'static>(
     index_fn: impl FnOnce() -> anyhow::Result<Arc<I>> + Clone + Send + Sync + 'static,
     index_key: IndexKey,
     dimensions: Dimensions,
     table: Arc<RwLock<impl TableSearch + Send + Sync + 'static>>,
     worker: async_channel::Sender<Worker>,
-    memory: mpsc::Sender<Memory>,
+    graph_memory: GraphMemory,
 ) -> anyhow::Result<mpsc::Sender<VsIndex>> {
+    let GraphMemory {
+        memory,
+        index: index_memory,
+        vector_bytes,
+    } = graph_memory;
     let (tx, mut rx) = mpsc::channel(perf::channel_size().into());
 
     tokio::spawn(perf::hotpath_async(
@@ -697,17 +1018,35 @@ fn new<I: UsearchIndex + Send + Sync + 'static>(
 
                 let mut allocate_prev = Allocate::Can;
                 let allocate_rx = memory.subscribe_allocate().await;
+                let reserved = Arc::new(AtomicUsize::new(0));
+                let mut reported_usage = 0;
 
                 while let Some(msg) = rx.recv().await {
+                    let _trace =
+                        actor_trace::start("usearch", msg.name(), Some(&index_key), rx.len());
+                    index_memory.set_graph(
+                        (reserved.load(Ordering::Relaxed) as u64).saturating_mul(vector_bytes),
+                    );
+                    let usage = index_memory.usage().total();
+                    if usage != reported_usage {
+                        memory.update_usage(index_key.clone(), usage).await;
+                        reported_usage = usage;
+                    }
                     if !check_memory_allocation(&msg, &allocate_rx, &mut allocate_prev, &index_key)
                     {
                         continue;
                     }
 
+                    if let VsIndex::Duplicates { .. } | VsIndex::KMeans { .. } = msg {
+                        dispatch_scan(&mut states, &partitions, &table, &worker, msg).await;
+                        continue;
+                    }
+
                     let Some((state, partition, msg)) = preprocess(
                         index_fn.clone(),
                         &mut states,
                         &mut partitions,
+                        &reserved,
                         table.as_ref(),
                         dimensions,
                         msg,
@@ -715,13 +1054,20 @@ fn new<I: UsearchIndex + Send + Sync + 'static>(
                         continue;
                     };
 
-                    dispatch_task(state, partition, &table, &worker, msg).await;
+                    if let VsIndex::AnnBatch { .. } = msg {
+                        dispatch_batch(state, partition, &table, &worker, msg).await;
+                    } else {
+                        dispatch_task(state, partition, &table, &worker, msg).await;
+                    }
                     hotpath::val!("usearch-rx.len-end").set(&rx.len());
                 }
 
                 partitions
                     .into_values()
                     .for_each(|partition| partition.stop());
+                if reported_usage != 0 {
+                    memory.update_usage(index_key.clone(), 0).await;
+                }
 
                 debug!("finished");
             }
@@ -737,6 +1083,7 @@ fn preprocess<'a, I, T>(
     index_fn: impl FnOnce() -> anyhow::Result<Arc<I>>,
     states: &'a mut BTreeMap<IndexId, IndexState>,
     partitions: &mut BTreeMap<PartitionId, Arc<PartitionState<I>>>,
+    reserved: &Arc<AtomicUsize>,
     table: &RwLock<T>,
     dimensions: Dimensions,
     msg: VsIndex,
@@ -762,6 +1109,7 @@ where
                         error!("failed to create index for partition {partition_id:?}: {err}")
                     })
                     .ok()?,
+                Arc::clone(reserved),
             ));
             let state = states
                 .entry(index_id)
@@ -855,6 +1203,74 @@ where
             Some((state, partition, msg))
         }
 
+        VsIndex::AnnBatch {
+            index_key,
+            queries,
+            tx,
+        } => {
+            let Some((partition_id, _)) = table.read().unwrap().partition_id(&index_key, None)
+            else {
+                warn!("partition id not found for index key {index_key:?} during ann batch");
+                _ = tx.send(Ok(vec![(vec![], vec![]); queries.len()]));
+                return None;
+            };
+            let index_id = partition_id.index_id();
+            let Some((state, partition)) = states
+                .get_mut(&index_id)
+                .zip(partitions.get(&partition_id))
+                .map(|(state, partition)| (state, Arc::clone(partition)))
+            else {
+                warn!("state or partition not found for index key {index_key:?} during ann batch");
+                _ = tx.send(Ok(vec![(vec![], vec![]); queries.len()]));
+                return None;
+            };
+            Some((
+                state,
+                partition,
+                VsIndex::AnnBatch {
+                    index_key,
+                    queries,
+                    tx,
+                },
+            ))
+        }
+
+        VsIndex::Range {
+            index_key,
+            embedding,
+            max_distance,
+            tx,
+        } => {
+            let Some((partition_id, _)) = table.read().unwrap().partition_id(&index_key, None)
+            else {
+                warn!("partition id not found for index key {index_key:?} during range search");
+                _ = tx.send(Ok((vec![], vec![])));
+                return None;
+            };
+            let index_id = partition_id.index_id();
+            let Some((state, partition)) = states
+                .get_mut(&index_id)
+                .zip(partitions.get(&partition_id))
+                .map(|(state, partition)| (state, Arc::clone(partition)))
+            else {
+                warn!(
+                    "state or partition not found for index key {index_key:?} during range search"
+                );
+                _ = tx.send(Ok((vec![], vec![])));
+                return None;
+            };
+            Some((
+                state,
+                partition,
+                VsIndex::Range {
+                    index_key,
+                    embedding,
+                    max_distance,
+                    tx,
+                },
+            ))
+        }
+
         VsIndex::Count { index_key, tx } => {
             let Some(index_id) = table.read().unwrap().index_id(&index_key) else {
                 let err = anyhow!("index id not found for index key {index_key:?}");
@@ -869,6 +1285,25 @@ where
             None
         }
 
+        VsIndex::Stats { index_key, tx } => {
+            let Some(index_id) = table.read().unwrap().index_id(&index_key) else {
+                let err = anyhow!("index id not found for index key {index_key:?}");
+                warn!("index stats: {err}");
+                _ = tx.send(Err(err));
+                return None;
+            };
+            _ = tx.send(Ok(partitions
+                .iter()
+                .filter(|(partition_id, _)| partition_id.index_id() == index_id)
+                .fold(VsStats::default(), |stats, (_, partition)| VsStats {
+                    size: stats.size + partition.size.load(Ordering::Relaxed),
+                    capacity: stats.capacity + partition.capacity.load(Ordering::Relaxed),
+                    deleted: stats.deleted + partition.deleted.load(Ordering::Relaxed),
+                    partitions: stats.partitions + 1,
+                })));
+            None
+        }
+
         VsIndex::RemoveVector { partition_id, .. } => {
             let index_id = partition_id.index_id();
             states
@@ -883,9 +1318,72 @@ where
             };
             None
         }
+
+        VsIndex::Duplicates { .. } | VsIndex::KMeans { .. } => unreachable!(),
     }
 }
 
+/// Runs a message reading the vectors of all rows of an index on a worker, holding a search permit
+/// until it is done.
+async fn dispatch_scan<I, T>(
+    states: &mut BTreeMap<IndexId, IndexState>,
+    partitions: &BTreeMap<PartitionId, Arc<PartitionState<I>>>,
+    table: &Arc<RwLock<T>>,
+    worker: &async_channel::Sender<Worker>,
+    msg: VsIndex,
+) where
+    I: UsearchIndex + Send + Sync + 'static,
+    T: TableSearch + Send + Sync + 'static,
+{
+    let (VsIndex::Duplicates { index_key, .. } | VsIndex::KMeans { index_key, .. }) = &msg else {
+        unreachable!();
+    };
+    let Some(index_id) = table.read().unwrap().index_id(index_key) else {
+        let err = anyhow!("index id not found for index key {index_key:?}");
+        warn!("index {}: {err}", msg.name());
+        match msg {
+            VsIndex::Duplicates { tx, .. } => _ = tx.send(Err(err)),
+            VsIndex::KMeans { tx, .. } => _ = tx.send(Err(err)),
+            _ => unreachable!(),
+        }
+        return;
+    };
+    let partitions: Vec<_> = partitions
+        .iter()
+        .filter(|(partition_id, _)| partition_id.index_id() == index_id)
+        .map(|(_, partition)| Arc::clone(partition))
+        .collect();
+    let operation_permit = match states.get_mut(&index_id) {
+        Some(state) => Some(state.operation.permit_for_message(&msg).await),
+        None => None,
+    };
+    let table = Arc::clone(table);
+    worker
+        .spawn_blocking(move || {
+            match msg {
+                VsIndex::Duplicates {
+                    max_distance, tx, ..
+                } => {
+                    let clusters: DuplicatesR = partitions
+                        .iter()
+                        .map(|partition| duplicates(partition, &*table, max_distance))
+                        .flatten_ok()
+                        .collect();
+                    _ = tx.send(clusters.map(|mut clusters| {
+                        clusters.sort_by_key(|cluster| Reverse(cluster.len()));
+                        clusters
+                    }));
+                }
+                VsIndex::KMeans { k, tx, .. } => {
+                    _ = tx.send(centroids(&partitions, &*table, k));
+                }
+                _ => unreachable!(),
+            }
+            drop(operation_permit);
+        })
+        .await;
+}
+
 #[hotpath::measure]
 async fn dispatch_task<I, T>(
     state: &mut IndexState,
@@ -917,7 +1415,7 @@ async fn dispatch_task<I, T>(
     let table = Arc::clone(table);
     let dimensions = state.dimensions;
     let size = Arc::clone(&state.size);
-    if is_non_blocking(&msg) {
+    if is_non_blocking(&msg) && !partition.idx.reads_disk() {
         worker
             .spawn_non_blocking(move || {
                 process(&partition, table, dimensions, size, msg);
@@ -934,6 +1432,50 @@ async fn dispatch_task<I, T>(
         .await;
 }
 
+/// Dispatches each query of a batch as a separate search, so that the workers run them in parallel,
+/// and answers when all of them are done.
+async fn dispatch_batch<I, T>(
+    state: &mut IndexState,
+    partition: Arc<PartitionState<I>>,
+    table: &Arc<RwLock<T>>,
+    worker: &async_channel::Sender<Worker>,
+    msg: VsIndex,
+) where
+    I: UsearchIndex + Send + Sync + 'static,
+    T: TableSearch + Send + Sync + 'static,
+{
+    let VsIndex::AnnBatch {
+        index_key,
+        queries,
+        tx,
+    } = msg
+    else {
+        unreachable!();
+    };
+    let mut results = Vec::with_capacity(queries.len());
+    for (embedding, limit) in queries {
+        let (tx_ann, rx_ann) = oneshot::channel();
+        results.push(rx_ann);
+        let msg = VsIndex::Ann {
+            index_key: index_key.clone(),
+            embedding,
+            limit,
+            tx: tx_ann,
+        };
+        dispatch_task(state, Arc::clone(&partition), table, worker, msg).await;
+    }
+    tokio::spawn(async move {
+        let results = futures::future::try_join_all(
+            results
+                .into_iter()
+                .map(|rx_ann| async move { rx_ann.await? }),
+        )
+        .await;
+        tx.send(results)
+            .unwrap_or_else(|_| trace!("dispatch_batch: unable to send response"));
+    });
+}
+
 #[hotpath::measure]
 fn is_non_blocking(msg: &VsIndex) -> bool {
     matches!(msg, VsIndex::Ann { .. })
@@ -981,7 +1523,22 @@ fn process<I, T>(
             }
         }
 
-        VsIndex::Count { .. } => unreachable!(),
+        VsIndex::Range {
+            embedding,
+            max_distance,
+            tx,
+            ..
+        } => {
+            if let Some(tx) = validate_dimensions(tx, &embedding, dimensions) {
+                range(partition, tx, &table, embedding, max_distance);
+            }
+        }
+
+        VsIndex::AnnBatch { .. }
+        | VsIndex::Count { .. }
+        | VsIndex::Stats { .. }
+        | VsIndex::Duplicates { .. }
+        | VsIndex::KMeans { .. } => unreachable!(),
 
         VsIndex::RemoveVector {
             primary_id,
@@ -1004,7 +1561,16 @@ where
     } else {
         let actual_capacity = partition.idx.capacity();
         debug!("reserve: reserved index capacity for {actual_capacity}");
-        partition.capacity.store(actual_capacity, Ordering::Relaxed);
+        let previous_capacity = partition.capacity.swap(actual_capacity, Ordering::Relaxed);
+        if actual_capacity >= previous_capacity {
+            partition
+                .reserved
+                .fetch_add(actual_capacity - previous_capacity, Ordering::Relaxed);
+        } else {
+            partition
+                .reserved
+                .fetch_sub(previous_capacity - actual_capacity, Ordering::Relaxed);
+        }
     }
 }
 
@@ -1022,6 +1588,11 @@ fn add<I>(
     } else {
         partition.size.fetch_add(1, Ordering::Relaxed);
         size.fetch_add(1, Ordering::Relaxed);
+        _ = partition
+            .deleted
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |deleted| {
+                deleted.checked_sub(1)
+            });
     }
 }
 
@@ -1036,10 +1607,103 @@ where
         Ok(true) => {
             size.fetch_sub(1, Ordering::Relaxed);
             partition.size.fetch_sub(1, Ordering::Relaxed);
+            partition.deleted.fetch_add(1, Ordering::Relaxed);
         }
     }
 }
 
+/// Returns clusters of rows of a partition whose vectors are linked by distances not greater than
+/// `max_distance`. Each vector is searched for its [`DUPLICATE_CANDIDATES`] nearest neighbours,
+/// and a cluster joins all vectors reachable through them.
+#[hotpath::measure]
+fn duplicates<I>(
+    partition: &PartitionState<I>,
+    table: &RwLock<impl TableSearch>,
+    max_distance: f32,
+) -> anyhow::Result<Vec<Vec<PrimaryKey>>>
+where
+    I: UsearchIndex + Send + Sync + 'static,
+{
+    /// Returns the root of the tree of a row in the union-find forest, making it the parent.
+    fn root(parents: &mut BTreeMap<PrimaryId, PrimaryId>, primary_id: PrimaryId) -> PrimaryId {
+        let parent = *parents.entry(primary_id).or_insert(primary_id);
+        if parent == primary_id {
+            return primary_id;
+        }
+        let root = root(parents, parent);
+        parents.insert(primary_id, root);
+        root
+    }
+
+    let primary_ids = table
+        .read()
+        .unwrap()
+        .primary_ids(partition.partition_id, &[]);
+    let mut parents: BTreeMap<PrimaryId, PrimaryId> = BTreeMap::new();
+    let limit = NonZeroUsize::new(DUPLICATE_CANDIDATES).unwrap().into();
+    for primary_id in primary_ids {
+        let Some(vector) = partition.idx.get(primary_id)? else {
+            continue;
+        };
+        for found in partition.idx.search(&vector, limit)? {
+            let (other, distance) = found?;
+            if other != primary_id && f32::from(distance) <= max_distance {
+                let (lhs, rhs) = (root(&mut parents, primary_id), root(&mut parents, other));
+                parents.insert(lhs.max(rhs), lhs.min(rhs));
+            }
+        }
+    }
+
+    let mut clusters: BTreeMap<PrimaryId, Vec<PrimaryId>> = BTreeMap::new();
+    for primary_id in parents.keys().copied().collect_vec() {
+        let root = root(&mut parents, primary_id);
+        clusters.entry(root).or_default().push(primary_id);
+    }
+    let table = table.read().unwrap();
+    Ok(clusters
+        .into_values()
+        .filter(|cluster| cluster.len() > 1)
+        .map(|cluster| {
+            cluster
+                .into_iter()
+                .filter_map(|primary_id| table.primary_key(partition.partition_id, primary_id))
+                .collect_vec()
+        })
+        .filter(|cluster| cluster.len() > 1)
+        .collect())
+}
+
+/// Returns up to `k` centroids of the vectors of all partitions of an index.
+#[hotpath::measure]
+fn centroids<I>(
+    partitions: &[Arc<PartitionState<I>>],
+    table: &RwLock<impl TableSearch>,
+    k: NonZeroUsize,
+) -> KMeansR
+where
+    I: UsearchIndex + Send + Sync + 'static,
+{
+    let rows: Vec<_> = {
+        let table = table.read().unwrap();
+        partitions
+            .iter()
+            .flat_map(|partition| {
+                table
+                    .primary_ids(partition.partition_id, &[])
+                    .into_iter()
+                    .map(move |primary_id| (partition, primary_id))
+            })
+            .collect()
+    };
+    kmeans::mini_batch(rows.len(), k, |position| {
+        let (partition, primary_id) = rows[position];
+        Ok(partition
+            .idx
+            .get(primary_id)?
+            .map(|vector| vector.as_slice().to_vec()))
+    })
+}
+
 #[hotpath::measure]
 fn validate_dimensions(
     tx_ann: oneshot::Sender<AnnR>,
@@ -1096,6 +1760,56 @@ fn ann<I>(
         .unwrap_or_else(|_| trace!("ann: unable to send response"));
 }
 
+/// Searches the vectors within `max_distance`, expanding the search until its farthest result is
+/// beyond the distance or it finds every vector of the partition.
+#[hotpath::measure]
+fn range<I>(
+    partition: &PartitionState<I>,
+    tx_ann: oneshot::Sender<AnnR>,
+    table: &Arc<RwLock<impl TableSearch>>,
+    embedding: Vector,
+    max_distance: f32,
+) where
+    I: UsearchIndex + Send + Sync + 'static,
+{
+    let search = || -> anyhow::Result<Vec<(PrimaryId, Distance)>> {
+        let mut limit = RANGE_INITIAL_LIMIT;
+        loop {
+            let matches: Vec<_> = partition
+                .idx
+                .search(&embedding, NonZeroUsize::new(limit).unwrap().into())
+                .map_err(|err| anyhow!("range: search failed: {err}"))?
+                .try_collect()?;
+            let exhausted =
+                matches.len() < limit || limit >= partition.size.load(Ordering::Relaxed);
+            let beyond = matches
+                .last()
+                .is_some_and(|(_, distance)| f32::from(*distance) > max_distance);
+            if exhausted || beyond {
+                return Ok(matches
+                    .into_iter()
+                    .filter(|(_, distance)| f32::from(*distance) <= max_distance)
+                    .collect());
+            }
+            limit = limit.saturating_mul(2);
+        }
+    };
+
+    tx_ann
+        .send(search().map(|matches| {
+            let table = table.read().unwrap();
+            matches
+                .into_iter()
+                .filter_map(|(primary_id, distance)| {
+                    table
+                        .primary_key(partition.partition_id, primary_id)
+                        .map(|primary_key| (primary_key, distance))
+                })
+                .unzip()
+        }))
+        .unwrap_or_else(|_| trace!("range: unable to send response"));
+}
+
 #[hotpath::measure]
 fn filtered_ann<I>(
     partition: &PartitionState<I>,
@@ -1196,6 +1910,19 @@ fn f32_to_b1x8(f32_vec: &[f32]) -> Vec<b1x8> {
     bytes
 }
 
+/// Unpacks bits packed by [`f32_to_b1x8`] as 1.0 and 0.0.
+fn b1x8_to_f32(bytes: &[b1x8], dimensions: usize) -> Vec<f32> {
+    (0..dimensions)
+        .map(|i| {
+            if bytes[i / 8].0 & (1 << (i % 8)) != 0 {
+                1.0
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1203,6 +1930,7 @@ mod tests {
     use crate::Config;
     use crate::IndexKey;
     use crate::memory;
+    use crate::table::Idx;
     use crate::table::IndexIdGenerator;
     use crate::table::MockTableSearch;
     use crate::vs_index::VsIndexExt;
@@ -1289,7 +2017,11 @@ mod tests {
             NonZeroUsize::new(3).unwrap().into(),
             Arc::clone(&table),
             worker::new(),
-            memory::new(internals_tx, config_rx),
+            GraphMemory {
+                memory: memory::new(internals_tx, config_rx),
+                index: IndexMemory::default(),
+                vector_bytes: 0,
+            },
         )
         .unwrap();
 
@@ -1436,6 +2168,163 @@ mod tests {
         assert_eq!(primary_keys.first().unwrap(), &[CqlValue::Int(2)].into());
     }
 
+    #[tokio::test]
+    async fn stats_count_deleted_slots() {
+        let (_, config_rx) = watch::channel(Arc::new(Config::default()));
+        let (internals_tx, _rx) = mpsc::channel(100);
+
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::L2sq,
+            ..Default::default()
+        };
+        let threads = perf::num_workers().into();
+        let table = Arc::new(RwLock::new(MockTableSearch::new()));
+        let index_key = IndexKey::new(&"vector".into(), &"store".into());
+        let actor = new(
+            move || Ok(Arc::new(ThreadedUsearchIndex::new(options, threads)?)),
+            index_key.clone(),
+            NonZeroUsize::new(3).unwrap().into(),
+            Arc::clone(&table),
+            worker::new(),
+            GraphMemory {
+                memory: memory::new(internals_tx, config_rx),
+                index: IndexMemory::default(),
+                vector_bytes: 0,
+            },
+        )
+        .unwrap();
+
+        let index_id = IndexIdGenerator::new().next(true).unwrap();
+        let partition_id = PartitionId::global(index_id);
+        table
+            .write()
+            .unwrap()
+            .expect_index_id()
+            .with(eq(index_key.clone()))
+            .returning(move |_| Some(index_id));
+
+        for primary_id in 1..=3 {
+            actor
+                .add_vector(
+                    partition_id,
+                    primary_id.into(),
+                    vec![primary_id as f32; 3].into(),
+                    AsyncInProgress::None,
+                )
+                .await;
+        }
+        actor
+            .remove_vector(partition_id, 2.into(), AsyncInProgress::None)
+            .await;
+
+        time::timeout(Duration::from_secs(10), async {
+            while actor.stats(index_key.clone()).await.unwrap().size != 2 {
+                task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        let stats = actor.stats(index_key.clone()).await.unwrap();
+        assert_eq!(stats.deleted, 1);
+        assert_eq!(stats.partitions, 1);
+        assert!(stats.capacity >= 3);
+
+        actor
+            .add_vector(
+                partition_id,
+                4.into(),
+                vec![4.0; 3].into(),
+                AsyncInProgress::None,
+            )
+            .await;
+        time::timeout(Duration::from_secs(10), async {
+            while actor.stats(index_key.clone()).await.unwrap().size != 3 {
+                task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(actor.stats(index_key).await.unwrap().deleted, 0);
+    }
+
+    #[tokio::test]
+    async fn duplicates_cluster_close_vectors() {
+        let (_, config_rx) = watch::channel(Arc::new(Config::default()));
+        let (internals_tx, _rx) = mpsc::channel(100);
+
+        let options = IndexOptions {
+            dimensions: 3,
+            metric: MetricKind::L2sq,
+            ..Default::default()
+        };
+        let threads = perf::num_workers().into();
+        let table = Arc::new(RwLock::new(MockTableSearch::new()));
+        let index_key = IndexKey::new(&"vector".into(), &"store".into());
+        let actor = new(
+            move || Ok(Arc::new(ThreadedUsearchIndex::new(options, threads)?)),
+            index_key.clone(),
+            NonZeroUsize::new(3).unwrap().into(),
+            Arc::clone(&table),
+            worker::new(),
+            GraphMemory {
+                memory: memory::new(internals_tx, config_rx),
+                index: IndexMemory::default(),
+                vector_bytes: 0,
+            },
+        )
+        .unwrap();
+
+        let index_id = IndexIdGenerator::new().next(true).unwrap();
+        let partition_id = PartitionId::global(index_id);
+        {
+            let mut table = table.write().unwrap();
+            table
+                .expect_index_id()
+                .with(eq(index_key.clone()))
+                .returning(move |_| Some(index_id));
+            table
+                .expect_primary_ids()
+                .returning(|_, _| (1..=4).map(Into::into).collect());
+            table.expect_primary_key().returning(|_, primary_id| {
+                Some([CqlValue::BigInt(primary_id.idx() as i64)].into())
+            });
+        }
+
+        let vectors = [
+            vec![1.0, 1.0, 1.0],
+            vec![1.0, 1.0, 1.01],
+            vec![5.0, 5.0, 5.0],
+            vec![9.0, 9.0, 9.0],
+        ];
+        for (primary_id, vector) in (1..).zip(vectors) {
+            actor
+                .add_vector(
+                    partition_id,
+                    primary_id.into(),
+                    vector.into(),
+                    AsyncInProgress::None,
+                )
+                .await;
+        }
+        time::timeout(Duration::from_secs(10), async {
+            while actor.stats(index_key.clone()).await.unwrap().size != 4 {
+                task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            actor.duplicates(index_key.clone(), 0.01).await.unwrap(),
+            vec![vec![
+                [CqlValue::BigInt(1)].into(),
+                [CqlValue::BigInt(2)].into()
+            ]]
+        );
+        assert!(actor.duplicates(index_key, 0.0).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn allocate_parameter_works() {
         let (memory_tx, mut memory_rx) = mpsc::channel(1);
@@ -1460,7 +2349,11 @@ mod tests {
             NonZeroUsize::new(3).unwrap().into(),
             Arc::clone(&table),
             worker::new(),
-            memory_tx,
+            GraphMemory {
+                memory: memory_tx,
+                index: IndexMemory::default(),
+                vector_bytes: 0,
+            },
         )
         .unwrap();
         memory_respond.await.unwrap();
@@ -1530,7 +2423,11 @@ mod tests {
             dimensions.into(),
             Arc::clone(&table),
             worker::new(),
-            memory::new(internals_tx, config_rx),
+            GraphMemory {
+                memory: memory::new(internals_tx, config_rx),
+                index: IndexMemory::default(),
+                vector_bytes: 0,
+            },
         )
         .unwrap();
 
@@ -1592,6 +2489,82 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn spilled_index_rescores_by_exact_distances() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::I8,
+            ..Default::default()
+        };
+        let index = SpilledUsearchIndex {
+            inner: ThreadedUsearchIndex::new(options, 1).unwrap(),
+            vectors: SpilledVectors::new(dir.path(), 2).unwrap(),
+            space_type: SpaceType::Euclidean,
+        };
+        index.reserve(10).unwrap();
+        index.add(1.into(), &vec![0.5, 0.25].into()).unwrap();
+        index.add(2.into(), &vec![0.0, 0.0].into()).unwrap();
+        assert!(index.reads_disk());
+
+        let matches: Vec<_> = index
+            .search(&vec![0.5, 0.0].into(), NonZeroUsize::MIN.into())
+            .unwrap()
+            .map(|result| result.map(|(primary_id, distance)| (primary_id, f32::from(distance))))
+            .try_collect()
+            .unwrap();
+
+        assert_eq!(matches, vec![(1.into(), 0.0625)]);
+    }
+
+    #[test]
+    fn i8_index_rescales_vectors_and_distances() {
+        let options = IndexOptions {
+            dimensions: 2,
+            metric: MetricKind::L2sq,
+            quantization: ScalarKind::I8,
+            ..Default::default()
+        };
+        let index = ThreadedUsearchIndex::new(options, 1).unwrap();
+        index.reserve(10).unwrap();
+        // Values far outside of [-1, 1], which would be clamped without scaling.
+        index.add(1.into(), &vec![10.0, 5.0].into()).unwrap();
+        index.add(2.into(), &vec![-20.0, 0.0].into()).unwrap();
+        index.add(3.into(), &vec![0.0, 0.0].into()).unwrap();
+        assert!(index.remove(3.into()).unwrap());
+
+        let matches: Vec<_> = index
+            .search(
+                &vec![10.0, 0.0].into(),
+                NonZeroUsize::new(3).unwrap().into(),
+            )
+            .unwrap()
+            .map(|result| result.map(|(primary_id, distance)| (primary_id, f32::from(distance))))
+            .try_collect()
+            .unwrap();
+
+        assert_eq!(matches.len(), 2);
+        assert_eq!(matches[0].0, 1.into());
+        assert!((matches[0].1 - 25.0).abs() < 1.0);
+        assert_eq!(matches[1].0, 2.into());
+        assert!((matches[1].1 - 900.0).abs() < 10.0);
+        let stored = index.get(2.into()).unwrap().unwrap();
+        assert!((stored.as_slice()[0] + 20.0).abs() < 0.2);
+    }
+
+    #[test]
+    fn only_indexes_quantized_to_numbers_are_spilled() {
+        let config = Config {
+            vector_spill_dir: Some("/tmp".into()),
+            ..Default::default()
+        };
+        assert_eq!(spill_dir(&config, Quantization::F32), None);
+        assert_eq!(spill_dir(&config, Quantization::B1), None);
+        assert_eq!(spill_dir(&config, Quantization::I8), Some("/tmp".into()));
+        assert_eq!(spill_dir(&Config::default(), Quantization::I8), None);
+    }
+
     #[tokio::test]
     async fn quantization_to_kind_conversion() {
         assert_eq!(ScalarKind::from(Quantization::F32), ScalarKind::F32);
@@ -3,14 +3,20 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.1
  */
 
+use crate::Config;
+use crate::KeyspaceName;
 use crate::perf;
 use async_channel::Sender;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::thread;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
+use tokio::sync::watch;
 use tracing::Instrument;
 use tracing::debug;
 use tracing::error_span;
@@ -41,7 +47,68 @@ impl WorkerExt for Sender<Worker> {
     }
 }
 
+/// The worker pools of indexes: a shared pool and dedicated pools of keyspaces with a configured
+/// number of workers.
+pub(crate) struct KeyspaceWorkers {
+    worker: Sender<Worker>,
+    keyspace_workers: Mutex<HashMap<KeyspaceName, KeyspaceWorker>>,
+    config_rx: watch::Receiver<Arc<Config>>,
+}
+
+struct KeyspaceWorker {
+    workers: NonZeroUsize,
+    worker: Sender<Worker>,
+}
+
+impl KeyspaceWorkers {
+    pub(crate) fn new(config_rx: watch::Receiver<Arc<Config>>) -> Self {
+        Self {
+            worker: new(),
+            keyspace_workers: Mutex::new(HashMap::new()),
+            config_rx,
+        }
+    }
+
+    /// Returns the worker pool for indexes of the keyspace and its number of workers. Keyspaces
+    /// with a configured number of workers get a dedicated pool.
+    pub(crate) fn worker(&self, keyspace: KeyspaceName) -> (Sender<Worker>, NonZeroUsize) {
+        let workers = self
+            .config_rx
+            .borrow()
+            .keyspace_partitions
+            .get(&keyspace)
+            .and_then(|partition| partition.workers);
+        let Some(workers) = workers else {
+            return (self.worker.clone(), perf::num_workers());
+        };
+        let mut keyspace_workers = self.keyspace_workers.lock().unwrap();
+        let keyspace_worker = keyspace_workers
+            .entry(keyspace)
+            .and_modify(|keyspace_worker| {
+                if keyspace_worker.workers != workers {
+                    *keyspace_worker = KeyspaceWorker::new(workers);
+                }
+            })
+            .or_insert_with(|| KeyspaceWorker::new(workers));
+        (keyspace_worker.worker.clone(), workers)
+    }
+}
+
+impl KeyspaceWorker {
+    fn new(workers: NonZeroUsize) -> Self {
+        Self {
+            workers,
+            worker: new_with_workers(workers),
+        }
+    }
+}
+
 pub(crate) fn new() -> Sender<Worker> {
+    new_with_workers(perf::num_workers())
+}
+
+/// Creates a pool with a given number of workers, used to isolate keyspaces from each other.
+pub(crate) fn new_with_workers(workers: NonZeroUsize) -> Sender<Worker> {
     let (tx_worker, rx_worker) = async_channel::bounded(perf::channel_size().into());
     let (tx_thread, mut rx_thread) =
         mpsc::channel::<(Box<dyn FnOnce() + Send>, oneshot::Sender<()>)>(1);
@@ -56,7 +123,7 @@ pub(crate) fn new() -> Sender<Worker> {
 
     let operations_in_flow = Arc::new(AtomicUsize::new(0));
     let thread_operations_in_flow = Arc::new(AtomicUsize::new(0));
-    let workers = perf::num_workers().into();
+    let workers = workers.get();
     (0..workers).for_each(|id| {
         let rx_worker = rx_worker.clone();
         let tx_thread = tx_thread.clone();
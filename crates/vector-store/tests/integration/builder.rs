@@ -0,0 +1,309 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::db_basic;
+use crate::db_basic::Table;
+use crate::usearch::test_config;
+use crate::wait_for;
+use httpclient::HttpClient;
+use scylla::cluster::metadata::NativeType;
+use scylla::value::CqlValue;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+use testsupport::TestVectorStore;
+use tokio::sync::broadcast;
+use tokio::time;
+use uuid::Uuid;
+use vector_store::DbIndexPartitioning;
+use vector_store::IndexKind;
+use vector_store::IndexMetadata;
+use vector_store::IndexOptionsVs;
+use vector_store::NonemptyArc;
+use vector_store::Timestamp;
+use vector_store::VectorStoreBuilder;
+use vector_store::node_state::IndexStatus;
+use vector_store::node_state::LifecycleEvent;
+
+#[tokio::test]
+async fn builder_starts_service_with_custom_db() {
+    crate::enable_tracing();
+    let node_state = vector_store::new_node_state().await;
+    let (db_actor, _db) = db_basic::new(node_state.clone());
+
+    let store = VectorStoreBuilder::new(test_config())
+        .node_state(node_state)
+        .db(db_actor)
+        .build()
+        .await
+        .unwrap();
+
+    let addr = store.addr().await.unwrap();
+    assert_ne!(addr.port(), 0);
+    assert!(store.mtls_addr().await.is_none());
+
+    let client = HttpClient::new(addr);
+    assert!(client.indexes().await.is_empty());
+    assert_eq!(client.info().await.service, env!("CARGO_PKG_NAME"));
+}
+
+#[tokio::test]
+async fn builder_listens_on_extra_addresses() {
+    crate::enable_tracing();
+    let node_state = vector_store::new_node_state().await;
+    let (db_actor, _db) = db_basic::new(node_state.clone());
+
+    let store = VectorStoreBuilder::new(test_config())
+        .extra_addr("127.0.0.1:0".parse().unwrap())
+        .node_state(node_state)
+        .db(db_actor)
+        .build()
+        .await
+        .unwrap();
+
+    let addrs = store.addrs().await;
+    assert_eq!(addrs.len(), 2);
+    assert_eq!(store.addr().await, Some(addrs[0]));
+    for addr in addrs {
+        let client = HttpClient::new(addr);
+        assert_eq!(client.info().await.service, env!("CARGO_PKG_NAME"));
+    }
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_config() {
+    let err = VectorStoreBuilder::new(test_config())
+        .mtls_ca_cert("ca.pem")
+        .build()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("mTLS"));
+}
+
+#[tokio::test]
+async fn engine_runs_without_http_servers() {
+    crate::enable_tracing();
+    let node_state = vector_store::new_node_state().await;
+    let (db_actor, db) = db_basic::new(node_state.clone());
+
+    let engine = VectorStoreBuilder::new(test_config())
+        .node_state(node_state)
+        .db(db_actor)
+        .build_engine()
+        .await
+        .unwrap();
+    assert!(engine.index_keys().is_empty());
+
+    let index = IndexMetadata {
+        keyspace_name: "vector".into(),
+        table_name: "items".into(),
+        index_name: "ann".into(),
+        target_columns: NonemptyArc::new(["embedding"]).unwrap(),
+        partitioning: DbIndexPartitioning::Global,
+        filtering_columns: Arc::new([]),
+        version: Uuid::new_v4().into(),
+        kind: IndexKind::Vs(IndexOptionsVs {
+            dimensions: NonZeroUsize::new(3).unwrap().into(),
+            connectivity: Default::default(),
+            expansion_add: Default::default(),
+            expansion_search: Default::default(),
+            space_type: Default::default(),
+            quantization: Default::default(),
+            embedding_model: None,
+            ttl: None,
+        }),
+    };
+    db.add_table(
+        index.keyspace_name.clone(),
+        index.table_name.clone(),
+        Table {
+            primary_keys: NonemptyArc::new(["pk"]).unwrap(),
+            partition_key_count: 1,
+            columns: Arc::new([("pk".into(), NativeType::Int)].into_iter().collect()),
+            dimensions: [(
+                index.target_columns.first().clone(),
+                index.vs().unwrap().dimensions,
+            )]
+            .into_iter()
+            .collect(),
+        },
+    )
+    .unwrap();
+    db.add_index(
+        index.clone(),
+        Some(db_basic::scan_fn_vectors([(
+            [CqlValue::Int(1)].into(),
+            Some(vec![1., 1., 1.].into()),
+            [].into(),
+            Timestamp::from_millis(10),
+        )])),
+        None,
+    )
+    .unwrap();
+
+    wait_for(
+        || async { engine.index_status(&index.key()) == Some(IndexStatus::Serving) },
+        "Waiting for index to be serving",
+    )
+    .await;
+    assert_eq!(engine.index_keys(), vec![index.key()]);
+}
+
+#[tokio::test]
+async fn test_vector_store_serves_fed_rows() {
+    crate::enable_tracing();
+    let store = TestVectorStore::start().await.unwrap();
+
+    let index = IndexMetadata {
+        keyspace_name: "vector".into(),
+        table_name: "items".into(),
+        index_name: "ann".into(),
+        target_columns: NonemptyArc::new(["embedding"]).unwrap(),
+        partitioning: DbIndexPartitioning::Global,
+        filtering_columns: Arc::new([]),
+        version: Uuid::new_v4().into(),
+        kind: IndexKind::Vs(IndexOptionsVs {
+            dimensions: NonZeroUsize::new(3).unwrap().into(),
+            connectivity: Default::default(),
+            expansion_add: Default::default(),
+            expansion_search: Default::default(),
+            space_type: Default::default(),
+            quantization: Default::default(),
+            embedding_model: None,
+            ttl: None,
+        }),
+    };
+    store
+        .add_index(
+            index.clone(),
+            Table {
+                primary_keys: NonemptyArc::new(["pk"]).unwrap(),
+                partition_key_count: 1,
+                columns: Arc::new([("pk".into(), NativeType::Int)].into_iter().collect()),
+                dimensions: [(
+                    index.target_columns.first().clone(),
+                    index.vs().unwrap().dimensions,
+                )]
+                .into_iter()
+                .collect(),
+            },
+            Some(db_basic::scan_fn_vectors([
+                (
+                    [CqlValue::Int(1)].into(),
+                    Some(vec![1., 1., 1.].into()),
+                    [].into(),
+                    Timestamp::from_millis(10),
+                ),
+                (
+                    [CqlValue::Int(2)].into(),
+                    Some(vec![-1., 1., -1.].into()),
+                    [].into(),
+                    Timestamp::from_millis(10),
+                ),
+            ])),
+            None,
+        )
+        .unwrap();
+    store.wait_for_serving(&index.key()).await.unwrap();
+
+    let keyspace_name = index.keyspace_name.clone().into();
+    let index_name = index.index_name.clone().into();
+    wait_for(
+        || async {
+            store
+                .client()
+                .index_status(&keyspace_name, &index_name)
+                .await
+                .is_ok_and(|status| status.count == 2)
+        },
+        "Waiting for rows to be indexed",
+    )
+    .await;
+
+    let (primary_keys, distances, _) = store
+        .client()
+        .ann(
+            &keyspace_name,
+            &index_name,
+            vec![-1., 1., -1.].into(),
+            None,
+            NonZeroUsize::new(1).unwrap().into(),
+        )
+        .await;
+    assert_eq!(distances.len(), 1);
+    let primary_keys_pk = primary_keys.get(&"pk".into()).unwrap();
+    assert_eq!(primary_keys_pk.first().unwrap().as_i64().unwrap(), 2);
+}
+
+#[tokio::test]
+async fn engine_publishes_index_lifecycle_events() {
+    crate::enable_tracing();
+    let store = TestVectorStore::start().await.unwrap();
+    let mut events = store.engine().subscribe().await;
+
+    let index = IndexMetadata {
+        keyspace_name: "vector".into(),
+        table_name: "items".into(),
+        index_name: "ann".into(),
+        target_columns: NonemptyArc::new(["embedding"]).unwrap(),
+        partitioning: DbIndexPartitioning::Global,
+        filtering_columns: Arc::new([]),
+        version: Uuid::new_v4().into(),
+        kind: IndexKind::Vs(IndexOptionsVs {
+            dimensions: NonZeroUsize::new(3).unwrap().into(),
+            connectivity: Default::default(),
+            expansion_add: Default::default(),
+            expansion_search: Default::default(),
+            space_type: Default::default(),
+            quantization: Default::default(),
+            embedding_model: None,
+            ttl: None,
+        }),
+    };
+    store
+        .add_index(
+            index.clone(),
+            Table {
+                primary_keys: NonemptyArc::new(["pk"]).unwrap(),
+                partition_key_count: 1,
+                columns: Arc::new([("pk".into(), NativeType::Int)].into_iter().collect()),
+                dimensions: [(
+                    index.target_columns.first().clone(),
+                    index.vs().unwrap().dimensions,
+                )]
+                .into_iter()
+                .collect(),
+            },
+            Some(db_basic::scan_fn_vectors([(
+                [CqlValue::Int(1)].into(),
+                Some(vec![1., 1., 1.].into()),
+                [].into(),
+                Timestamp::from_millis(10),
+            )])),
+            None,
+        )
+        .unwrap();
+
+    let key = index.key();
+    wait_for_event(&mut events, LifecycleEvent::IndexAdded(key.clone())).await;
+    wait_for_event(&mut events, LifecycleEvent::FullScanFinished(key.clone())).await;
+
+    store
+        .db()
+        .del_index(&index.keyspace_name, &index.index_name)
+        .unwrap();
+    wait_for_event(&mut events, LifecycleEvent::IndexRemoved(key)).await;
+}
+
+async fn wait_for_event(
+    events: &mut broadcast::Receiver<LifecycleEvent>,
+    expected: LifecycleEvent,
+) {
+    time::timeout(Duration::from_secs(5), async {
+        while events.recv().await.unwrap() != expected {}
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timeout waiting for {expected:?}"));
+}
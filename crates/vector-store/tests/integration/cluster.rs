@@ -0,0 +1,149 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::db_basic;
+use crate::usearch::test_config;
+use crate::wait_for;
+use httpclient::HttpClient;
+use reqwest::header::AUTHORIZATION;
+use reqwest::header::HeaderValue;
+use std::net::SocketAddr;
+use std::net::TcpListener;
+use std::num::NonZeroUsize;
+use std::time::Duration;
+use tokio::time;
+use vector_store::AuthConfig;
+use vector_store::ClusterConfig;
+use vector_store::ClusterNode;
+use vector_store::Config;
+use vector_store::NodeId;
+use vector_store::VectorStore;
+use vector_store::VectorStoreBuilder;
+
+const PROBE_INTERVAL: Duration = Duration::from_millis(100);
+
+fn cluster_node(id: &str, addr: SocketAddr) -> ClusterNode {
+    ClusterNode {
+        id: id.into(),
+        url: format!("http://{addr}"),
+        zone: None,
+    }
+}
+
+/// Starts a node accepting the `client` token from clients and the `peer` token from its peers.
+async fn start_node(
+    addr: SocketAddr,
+    node_id: &str,
+    nodes: Vec<ClusterNode>,
+) -> (VectorStore, impl Sized) {
+    let node_state = vector_store::new_node_state().await;
+    let (db_actor, db) = db_basic::new(node_state.clone());
+    let config = Config {
+        auth: Some(AuthConfig {
+            tokens: vec!["client".into()],
+            peer_token: Some("peer".into()),
+            ..Default::default()
+        }),
+        cluster: Some(ClusterConfig {
+            node_id: node_id.into(),
+            nodes,
+            replication_factor: NonZeroUsize::MIN,
+            probe_interval: PROBE_INTERVAL,
+            failure_threshold: NonZeroUsize::new(3).unwrap(),
+            forward_timeout: Duration::from_secs(5),
+        }),
+        ..test_config()
+    };
+    let store = VectorStoreBuilder::new(config)
+        .addr(addr)
+        .node_state(node_state)
+        .db(db_actor)
+        .build()
+        .await
+        .unwrap();
+    (store, db)
+}
+
+fn members(store: &VectorStore) -> Vec<NodeId> {
+    let mut members: Vec<_> = store
+        .engine()
+        .membership()
+        .borrow()
+        .as_ref()
+        .unwrap()
+        .nodes()
+        .iter()
+        .map(|node| node.id.clone())
+        .collect();
+    members.sort();
+    members
+}
+
+#[tokio::test]
+async fn nodes_with_authentication_enabled_talk_to_each_other_with_the_peer_token() {
+    crate::enable_tracing();
+    let listeners = [
+        TcpListener::bind("127.0.0.1:0").unwrap(),
+        TcpListener::bind("127.0.0.1:0").unwrap(),
+    ];
+    let addrs = listeners.map(|listener| listener.local_addr().unwrap());
+    let nodes = [
+        cluster_node("node0", addrs[0]),
+        cluster_node("node1", addrs[1]),
+    ];
+
+    // The first node learns about the second one only from its gossip.
+    let (node0, _db0) = start_node(addrs[0], "node0", vec![nodes[0].clone()]).await;
+    let (node1, _db1) = start_node(addrs[1], "node1", nodes.to_vec()).await;
+    wait_for(
+        || async { members(&node0).len() == 2 },
+        "node0 should learn node1 by gossip",
+    )
+    .await;
+
+    // Rejected probes would leave the peer out of the membership.
+    time::sleep(10 * PROBE_INTERVAL).await;
+    let both = [NodeId::from("node0"), NodeId::from("node1")];
+    assert_eq!(members(&node0), both);
+    assert_eq!(members(&node1), both);
+
+    let client = HttpClient::builder(addrs[0])
+        .header(AUTHORIZATION, HeaderValue::from_static("Bearer client"))
+        .build()
+        .unwrap();
+    assert_eq!(client.cluster_index_versions().await.unwrap(), Some(vec![]));
+
+    node1.start_shutdown().await;
+    wait_for(
+        || async { members(&node0) == [NodeId::from("node0")] },
+        "node0 should learn that node1 left",
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn clients_cannot_gossip_fake_nodes() {
+    crate::enable_tracing();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (node0, _db0) = start_node(addr, "node0", vec![cluster_node("node0", addr)]).await;
+
+    let fake = serde_json::json!([{
+        "node": { "id": "fake", "url": "http://127.0.0.1:1" },
+        "generation": 1,
+        "heartbeat": 1,
+    }]);
+    let response = reqwest::Client::new()
+        .post(format!("http://{addr}/api/v1/cluster/gossip"))
+        .bearer_auth("client")
+        .json(&fake)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    time::sleep(2 * PROBE_INTERVAL).await;
+    assert_eq!(members(&node0), [NodeId::from("node0")]);
+}
@@ -0,0 +1,273 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::db_basic;
+use crate::db_basic::Table as DbTable;
+use crate::usearch::test_config;
+use crate::wait_for;
+use async_trait::async_trait;
+use httpclient::HttpClient;
+use scylla::cluster::metadata::NativeType;
+use scylla::value::CqlValue;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::sync::RwLock;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+use vector_store::AnnR;
+use vector_store::DbIndexPartitioning;
+use vector_store::Distance;
+use vector_store::Filter;
+use vector_store::IndexKind;
+use vector_store::IndexMetadata;
+use vector_store::IndexOptionsVs;
+use vector_store::Limit;
+use vector_store::Memory;
+use vector_store::NonemptyArc;
+use vector_store::PartitionId;
+use vector_store::PrimaryId;
+use vector_store::SpaceType;
+use vector_store::Table;
+use vector_store::TableSearch;
+use vector_store::Timestamp;
+use vector_store::Vector;
+use vector_store::VectorStoreBuilder;
+use vector_store::VsIndex;
+use vector_store::VsIndexConfiguration;
+use vector_store::VsIndexFactory;
+use vector_store::VsStats;
+
+/// A brute-force ANN engine implemented outside of the crate.
+struct BruteForceIndexFactory;
+
+#[async_trait]
+impl VsIndexFactory for BruteForceIndexFactory {
+    async fn create_index(
+        &self,
+        _index: VsIndexConfiguration,
+        table: Arc<RwLock<Table>>,
+        _memory: mpsc::Sender<Memory>,
+    ) -> anyhow::Result<mpsc::Sender<VsIndex>> {
+        let (tx, mut rx) = mpsc::channel(10);
+        tokio::spawn(async move {
+            let mut vectors: BTreeMap<PrimaryId, (PartitionId, Vector)> = BTreeMap::new();
+            while let Some(msg) = rx.recv().await {
+                match msg {
+                    VsIndex::AddVector {
+                        partition_id,
+                        primary_id,
+                        embedding,
+                        ..
+                    } => {
+                        vectors.insert(primary_id, (partition_id, embedding));
+                    }
+                    VsIndex::RemoveVector { primary_id, .. } => {
+                        vectors.remove(&primary_id);
+                    }
+                    VsIndex::RemovePartition { partition_id } => {
+                        vectors.retain(|_, (id, _)| *id != partition_id);
+                    }
+                    VsIndex::Ann {
+                        embedding,
+                        limit,
+                        tx,
+                        ..
+                    } => {
+                        _ = tx.send(search(&table, &vectors, &embedding, None, limit));
+                    }
+                    VsIndex::FilteredAnn {
+                        embedding,
+                        filter,
+                        limit,
+                        tx,
+                        ..
+                    } => {
+                        _ = tx.send(search(&table, &vectors, &embedding, Some(&filter), limit));
+                    }
+                    VsIndex::AnnBatch { queries, tx, .. } => {
+                        _ = tx.send(
+                            queries
+                                .iter()
+                                .map(|(embedding, limit)| {
+                                    search(&table, &vectors, embedding, None, *limit)
+                                })
+                                .collect(),
+                        );
+                    }
+                    VsIndex::Range { tx, .. } => {
+                        _ = tx.send(Err(anyhow::anyhow!(
+                            "the brute-force engine does not search by range"
+                        )));
+                    }
+                    VsIndex::Count { tx, .. } => {
+                        _ = tx.send(Ok(vectors.len()));
+                    }
+                    VsIndex::Stats { tx, .. } => {
+                        _ = tx.send(Ok(VsStats {
+                            size: vectors.len(),
+                            capacity: vectors.len(),
+                            deleted: 0,
+                            partitions: 1,
+                        }));
+                    }
+                    VsIndex::Duplicates { tx, .. } => {
+                        _ = tx.send(Err(anyhow::anyhow!(
+                            "the brute-force engine does not find duplicates"
+                        )));
+                    }
+                    VsIndex::KMeans { tx, .. } => {
+                        _ = tx.send(Err(anyhow::anyhow!(
+                            "the brute-force engine does not cluster vectors"
+                        )));
+                    }
+                }
+            }
+        });
+        Ok(tx)
+    }
+
+    fn index_engine_version(&self) -> String {
+        "brute-force-1.0".to_string()
+    }
+}
+
+fn search(
+    table: &RwLock<Table>,
+    vectors: &BTreeMap<PrimaryId, (PartitionId, Vector)>,
+    embedding: &Vector,
+    filter: Option<&Filter>,
+    limit: Limit,
+) -> AnnR {
+    let table = table.read().unwrap();
+    let mut found: Vec<_> = vectors
+        .iter()
+        .filter(|(primary_id, (partition_id, _))| {
+            filter.is_none_or(|filter| {
+                filter
+                    .restrictions
+                    .iter()
+                    .all(|restriction| table.is_valid_for(*partition_id, **primary_id, restriction))
+            })
+        })
+        .map(|(primary_id, (partition_id, vector))| {
+            let distance = vector
+                .as_slice()
+                .iter()
+                .zip(embedding.as_slice())
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum::<f32>();
+            (distance, *partition_id, *primary_id)
+        })
+        .collect();
+    found.sort_by(|a, b| a.0.total_cmp(&b.0));
+    found
+        .into_iter()
+        .take(limit.as_ref().get())
+        .filter_map(|(distance, partition_id, primary_id)| {
+            let primary_key = table.primary_key(partition_id, primary_id)?;
+            Some(Distance::new_euclidean(distance).map(|distance| (primary_key, distance)))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()
+        .map(|found| found.into_iter().unzip())
+}
+
+#[tokio::test]
+async fn custom_index_factory_serves_ann() {
+    crate::enable_tracing();
+    let node_state = vector_store::new_node_state().await;
+    let (db_actor, db) = db_basic::new(node_state.clone());
+
+    let store = VectorStoreBuilder::new(test_config())
+        .node_state(node_state)
+        .db(db_actor)
+        .index_factory(Box::new(BruteForceIndexFactory))
+        .build()
+        .await
+        .unwrap();
+    let client = HttpClient::new(store.addr().await.unwrap());
+    assert_eq!(client.info().await.engine, "brute-force-1.0");
+
+    let index = IndexMetadata {
+        keyspace_name: "vector".into(),
+        table_name: "items".into(),
+        index_name: "ann".into(),
+        target_columns: NonemptyArc::new(["embedding"]).unwrap(),
+        partitioning: DbIndexPartitioning::Global,
+        filtering_columns: Arc::new([]),
+        version: Uuid::new_v4().into(),
+        kind: IndexKind::Vs(IndexOptionsVs {
+            dimensions: NonZeroUsize::new(3).unwrap().into(),
+            connectivity: Default::default(),
+            expansion_add: Default::default(),
+            expansion_search: Default::default(),
+            space_type: SpaceType::Euclidean,
+            quantization: Default::default(),
+            embedding_model: None,
+            ttl: None,
+        }),
+    };
+    db.add_table(
+        index.keyspace_name.clone(),
+        index.table_name.clone(),
+        DbTable {
+            primary_keys: NonemptyArc::new(["pk"]).unwrap(),
+            partition_key_count: 1,
+            columns: Arc::new([("pk".into(), NativeType::Int)].into_iter().collect()),
+            dimensions: [(
+                index.target_columns.first().clone(),
+                index.vs().unwrap().dimensions,
+            )]
+            .into_iter()
+            .collect(),
+        },
+    )
+    .unwrap();
+    db.add_index(
+        index.clone(),
+        Some(db_basic::scan_fn_vectors([
+            (
+                [CqlValue::Int(1)].into(),
+                Some(vec![1., 1., 1.].into()),
+                [].into(),
+                Timestamp::from_millis(10),
+            ),
+            (
+                [CqlValue::Int(2)].into(),
+                Some(vec![5., 5., 5.].into()),
+                [].into(),
+                Timestamp::from_millis(10),
+            ),
+        ])),
+        None,
+    )
+    .unwrap();
+
+    let keyspace_name = index.keyspace_name.clone().into();
+    let index_name = index.index_name.clone().into();
+    wait_for(
+        || async {
+            client
+                .index_status(&keyspace_name, &index_name)
+                .await
+                .is_ok_and(|status| status.count == 2)
+        },
+        "Waiting for rows to be indexed by the custom engine",
+    )
+    .await;
+
+    let (primary_keys, distances, _) = client
+        .ann(
+            &keyspace_name,
+            &index_name,
+            vec![4., 4., 4.].into(),
+            None,
+            NonZeroUsize::new(1).unwrap().into(),
+        )
+        .await;
+    assert_eq!(distances.len(), 1);
+    let primary_keys_pk = primary_keys.get(&"pk".into()).unwrap();
+    assert_eq!(primary_keys_pk.first().unwrap().as_i64().unwrap(), 2);
+}
@@ -43,7 +43,7 @@ fn fts_index_metadata(filtering_columns: impl IntoIterator<Item = ColumnName>) -
         partitioning: DbIndexPartitioning::Global,
         filtering_columns: filtering_columns.into_iter().collect(),
         version: Uuid::new_v4().into(),
-        kind: IndexKind::Fts(IndexOptionsFts {}),
+        kind: IndexKind::Fts(IndexOptionsFts { ttl: None }),
     }
 }
 
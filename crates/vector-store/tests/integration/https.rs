@@ -112,6 +112,11 @@ async fn test_https_server_responds() {
             vector: vec![1.0].into(),
             filter: None,
             limit: NonZeroUsize::new(1).unwrap().into(),
+            min_write_timestamp: None,
+            write_wait_timeout_ms: None,
+            oversampling: None,
+            rerank: None,
+            payload: None,
         })
         .send()
         .await
@@ -124,6 +129,11 @@ async fn test_https_server_responds() {
             vector: vec![1.0].into(),
             filter: None,
             limit: NonZeroUsize::new(1).unwrap().into(),
+            min_write_timestamp: None,
+            write_wait_timeout_ms: None,
+            oversampling: None,
+            rerank: None,
+            payload: None,
         })
         .send()
         .await
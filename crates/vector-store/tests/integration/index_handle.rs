@@ -0,0 +1,152 @@
+/*
+ * Copyright 2026-present ScyllaDB
+ * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
+ */
+
+use crate::db_basic;
+use crate::db_basic::Table;
+use crate::usearch::test_config;
+use crate::wait_for;
+use scylla::cluster::metadata::NativeType;
+use scylla::value::CqlValue;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use uuid::Uuid;
+use vector_store::DbIndexPartitioning;
+use vector_store::IndexError;
+use vector_store::IndexKind;
+use vector_store::IndexMetadata;
+use vector_store::IndexOptionsVs;
+use vector_store::NonemptyArc;
+use vector_store::PrimaryKey;
+use vector_store::Timestamp;
+use vector_store::VectorStoreBuilder;
+use vector_store::node_state::IndexStatus;
+
+#[tokio::test]
+async fn index_handle_queries_and_writes_in_process() {
+    crate::enable_tracing();
+    let node_state = vector_store::new_node_state().await;
+    let (db_actor, db) = db_basic::new(node_state.clone());
+
+    let engine = VectorStoreBuilder::new(test_config())
+        .node_state(node_state)
+        .db(db_actor)
+        .build_engine()
+        .await
+        .unwrap();
+
+    let index = IndexMetadata {
+        keyspace_name: "vector".into(),
+        table_name: "items".into(),
+        index_name: "ann".into(),
+        target_columns: NonemptyArc::new(["embedding"]).unwrap(),
+        partitioning: DbIndexPartitioning::Global,
+        filtering_columns: Arc::new([]),
+        version: Uuid::new_v4().into(),
+        kind: IndexKind::Vs(IndexOptionsVs {
+            dimensions: NonZeroUsize::new(3).unwrap().into(),
+            connectivity: Default::default(),
+            expansion_add: Default::default(),
+            expansion_search: Default::default(),
+            space_type: Default::default(),
+            quantization: Default::default(),
+            embedding_model: None,
+            ttl: None,
+        }),
+    };
+    assert!(matches!(
+        engine.index(&index.key()),
+        Err(IndexError::NotFound(_))
+    ));
+
+    db.add_table(
+        index.keyspace_name.clone(),
+        index.table_name.clone(),
+        Table {
+            primary_keys: NonemptyArc::new(["pk"]).unwrap(),
+            partition_key_count: 1,
+            columns: Arc::new([("pk".into(), NativeType::Int)].into_iter().collect()),
+            dimensions: [(
+                index.target_columns.first().clone(),
+                index.vs().unwrap().dimensions,
+            )]
+            .into_iter()
+            .collect(),
+        },
+    )
+    .unwrap();
+    db.add_index(
+        index.clone(),
+        Some(db_basic::scan_fn_vectors([(
+            [CqlValue::Int(1)].into(),
+            Some(vec![1., 1., 1.].into()),
+            [].into(),
+            Timestamp::from_millis(10),
+        )])),
+        None,
+    )
+    .unwrap();
+
+    wait_for(
+        || async { engine.index_status(&index.key()) == Some(IndexStatus::Serving) },
+        "Waiting for index to be serving",
+    )
+    .await;
+    let handle = engine.index(&index.key()).unwrap();
+    assert_eq!(handle.status().unwrap(), IndexStatus::Serving);
+    wait_for(
+        || async { handle.count().await.unwrap() == 1 },
+        "Waiting for the scanned row",
+    )
+    .await;
+
+    let added: PrimaryKey = [CqlValue::Int(2)].into();
+    handle
+        .add(added.clone(), vec![-1., 1., -1.].into())
+        .await
+        .unwrap();
+    wait_for(
+        || async { handle.count().await.unwrap() == 2 },
+        "Waiting for the added row",
+    )
+    .await;
+    let memory = handle.memory_usage().unwrap();
+    assert!(memory.key_maps > 0);
+    assert!(memory.total() >= memory.key_maps);
+
+    let found = handle
+        .ann(
+            vec![-1., 1., -1.].into(),
+            NonZeroUsize::new(1).unwrap().into(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].0, added);
+
+    assert!(matches!(
+        handle
+            .ann(vec![1., 1.].into(), NonZeroUsize::new(1).unwrap().into())
+            .await,
+        Err(IndexError::WrongEmbeddingDimension {
+            expected: 3,
+            actual: 2
+        })
+    ));
+
+    handle.remove(added).await.unwrap();
+    wait_for(
+        || async { handle.count().await.unwrap() == 1 },
+        "Waiting for the removed row",
+    )
+    .await;
+
+    db.del_index(&index.keyspace_name, &index.index_name)
+        .unwrap();
+    wait_for(
+        || async { matches!(handle.count().await, Err(IndexError::NotFound(_))) },
+        "Waiting for index to be removed",
+    )
+    .await;
+}
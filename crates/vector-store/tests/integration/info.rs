@@ -78,3 +78,13 @@ async fn get_application_info_diskann() {
     assert_eq!(info.service, env!("CARGO_PKG_NAME"));
     assert_eq!(info.engine, format!("diskann-{}", diskann::version()));
 }
+
+#[tokio::test]
+async fn get_index_versions_without_clustering() {
+    let (_, rx) = watch::channel(Arc::new(Config::default()));
+    let (client, _server, _config_senders) =
+        run_vs(vector_store::new_index_factory_usearch(rx).unwrap()).await;
+
+    assert!(client.index_versions().await.unwrap().is_empty());
+    assert!(client.cluster_index_versions().await.unwrap().is_none());
+}
@@ -3,13 +3,15 @@
  * SPDX-License-Identifier: LicenseRef-ScyllaDB-Source-Available-1.0
  */
 
-mod db_basic;
+mod builder;
+mod cluster;
+mod custom_index_factory;
 mod fts;
 mod https;
+mod index_handle;
 mod info;
 mod memory_limit;
 mod metrics;
-mod mock_opensearch;
 mod mtls;
 mod openapi;
 mod opensearch;
@@ -22,6 +24,8 @@ mod usearch;
 use std::sync::Arc;
 use std::sync::Once;
 use std::time::Duration;
+use testsupport::db_basic;
+use testsupport::mock_opensearch;
 use tokio::sync::watch;
 use tokio::task;
 use tokio::time;
@@ -99,7 +103,10 @@ async fn derive_http_configs(config: &Config) -> (HttpServerConfig, Option<Arc<H
         .map(|id| tls::TlsServerConfig::new(id).unwrap());
     let http = HttpServerConfig {
         addr: config.vector_store_addr,
+        extra_addrs: config.vector_store_extra_addrs.clone(),
         tls: http_tls,
+        tuning: config.http_tuning.clone(),
+        auth: None,
     };
     let mtls_http = match (&identity, &config.mtls_ca_cert_path) {
         (Some(id), Some(ca_path)) => {
@@ -107,7 +114,10 @@ async fn derive_http_configs(config: &Config) -> (HttpServerConfig, Option<Arc<H
             let mtls_tls = tls::TlsServerConfig::new_mtls(id, &ca_bundle).unwrap();
             Some(Arc::new(HttpServerConfig {
                 addr: config.mtls_addr,
+                extra_addrs: config.mtls_extra_addrs.clone(),
                 tls: Some(mtls_tls),
+                tuning: config.http_tuning.clone(),
+                auth: None,
             }))
         }
         _ => None,
@@ -70,6 +70,8 @@ async fn memory_limit_during_index_build() {
             expansion_search: ExpansionSearch::default(),
             space_type: SpaceType::default(),
             quantization: Quantization::default(),
+            embedding_model: None,
+            ttl: None,
         }),
     };
 
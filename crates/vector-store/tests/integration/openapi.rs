@@ -16,3 +16,35 @@ fn openapi_json_is_synced() {
         "api/openapi.json is not in sync with the server's OpenAPI spec. Run `cargo openapi` to update it."
     );
 }
+
+#[cfg(feature = "swagger-ui")]
+#[tokio::test]
+async fn swagger_ui_requires_authentication() {
+    crate::enable_tracing();
+    let node_state = vector_store::new_node_state().await;
+    let (db_actor, _db) = crate::db_basic::new(node_state.clone());
+    let config = vector_store::Config {
+        auth: Some(vector_store::AuthConfig {
+            tokens: vec!["client".into()],
+            ..Default::default()
+        }),
+        ..crate::usearch::test_config()
+    };
+    let store = vector_store::VectorStoreBuilder::new(config)
+        .node_state(node_state)
+        .db(db_actor)
+        .build()
+        .await
+        .unwrap();
+    let addr = store.addr().await.unwrap();
+
+    let client = reqwest::Client::new();
+    for path in ["/api-docs/openapi.json", "/swagger-ui/"] {
+        let url = format!("http://{addr}{path}");
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+        let response = client.get(&url).bearer_auth("client").send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+    }
+}
@@ -5,11 +5,14 @@
 
 use crate::create_config_channels;
 use crate::db_basic;
+use crate::db_basic::DbBasic;
 use crate::db_basic::Table;
 use crate::mock_opensearch;
 use crate::usearch::test_config;
 use crate::wait_for;
 use httpapi::IndexStatus;
+use httpapi::PostIndexAnnFilter;
+use httpapi::PostIndexAnnRestriction;
 use httpclient::HttpClient;
 use scylla::cluster::metadata::NativeType;
 use scylla::value::CqlValue;
@@ -25,9 +28,7 @@ use vector_store::IndexOptionsVs;
 use vector_store::NonemptyArc;
 use vector_store::Timestamp;
 
-#[tokio::test]
-async fn simple_create_search_delete_index() {
-    crate::enable_tracing();
+async fn setup_store() -> (IndexMetadata, HttpClient, DbBasic, impl Sized) {
     let node_state = vector_store::new_node_state().await;
     let internals = vector_store::new_internals();
     let (db_actor, db) = db_basic::new(node_state.clone());
@@ -47,15 +48,18 @@ async fn simple_create_search_delete_index() {
             expansion_search: Default::default(),
             space_type: Default::default(),
             quantization: Default::default(),
+            embedding_model: None,
+            ttl: None,
         }),
     };
-    let server = mock_opensearch::TestOpenSearchServer::start().await;
+    let opensearch = mock_opensearch::TestOpenSearchServer::start().await;
 
     let (_, config_rx_factory) = watch::channel(Arc::new(vector_store::Config::default()));
     let index_factory =
-        vector_store::new_index_factory_opensearch(server.base_url(), config_rx_factory).unwrap();
+        vector_store::new_index_factory_opensearch(opensearch.base_url(), config_rx_factory)
+            .unwrap();
 
-    let (receivers, _senders) = create_config_channels(test_config()).await;
+    let (receivers, senders) = create_config_channels(test_config()).await;
     let (server, _mtls) = vector_store::run(
         node_state,
         db_actor,
@@ -132,6 +136,17 @@ async fn simple_create_search_delete_index() {
     )
     .await;
 
+    (index, client, db, (opensearch, server, senders))
+}
+
+#[tokio::test]
+async fn simple_create_search_delete_index() {
+    crate::enable_tracing();
+    let (index, client, db, _server) = setup_store().await;
+
+    let keyspace_name = index.keyspace_name.clone().into();
+    let index_name = index.index_name.clone().into();
+
     let indexes = client.indexes().await;
     assert_eq!(indexes.len(), 1);
     assert_eq!(indexes[0], httpapi::IndexInfo::new("vector", "ann"));
@@ -164,3 +179,79 @@ async fn simple_create_search_delete_index() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn filtered_ann_returns_only_matching_rows() {
+    crate::enable_tracing();
+    let (index, client, _db, _server) = setup_store().await;
+
+    let keyspace_name = index.keyspace_name.clone().into();
+    let index_name = index.index_name.clone().into();
+
+    let (primary_keys, distances, _) = client
+        .ann(
+            &keyspace_name,
+            &index_name,
+            vec![1., 1., 1.].into(),
+            Some(PostIndexAnnFilter {
+                restrictions: vec![PostIndexAnnRestriction::Eq {
+                    lhs: "ck".into(),
+                    rhs: "two".into(),
+                }],
+                allow_filtering: true,
+            }),
+            NonZeroUsize::new(3).unwrap().into(),
+        )
+        .await;
+    assert_eq!(distances.len(), 1);
+    let primary_keys_pk = primary_keys.get(&"pk".into()).unwrap();
+    assert_eq!(primary_keys_pk.len(), 1);
+    assert_eq!(primary_keys_pk.first().unwrap().as_i64().unwrap(), 2);
+
+    let (primary_keys, distances, _) = client
+        .ann(
+            &keyspace_name,
+            &index_name,
+            vec![1., 1., 1.].into(),
+            Some(PostIndexAnnFilter {
+                restrictions: vec![PostIndexAnnRestriction::Gte {
+                    lhs: "pk".into(),
+                    rhs: 2.into(),
+                }],
+                allow_filtering: true,
+            }),
+            NonZeroUsize::new(3).unwrap().into(),
+        )
+        .await;
+    assert_eq!(distances.len(), 2);
+    let mut primary_keys_pk: Vec<_> = primary_keys
+        .get(&"pk".into())
+        .unwrap()
+        .iter()
+        .map(|pk| pk.as_i64().unwrap())
+        .collect();
+    primary_keys_pk.sort();
+    assert_eq!(primary_keys_pk, vec![2, 3]);
+
+    let (primary_keys, distances, _) = client
+        .ann(
+            &keyspace_name,
+            &index_name,
+            vec![1., 1., 1.].into(),
+            Some(PostIndexAnnFilter {
+                restrictions: vec![PostIndexAnnRestriction::Eq {
+                    lhs: "pk".into(),
+                    rhs: 4.into(),
+                }],
+                allow_filtering: true,
+            }),
+            NonZeroUsize::new(3).unwrap().into(),
+        )
+        .await;
+    assert!(distances.is_empty());
+    assert!(
+        primary_keys
+            .get(&"pk".into())
+            .is_none_or(|pk| pk.is_empty())
+    );
+}
@@ -9,17 +9,18 @@ use crate::db_basic::DbBasic;
 use crate::db_basic::ScanFn;
 use crate::db_basic::Table;
 use crate::usearch::test_config;
-use crate::wait_for;
 use futures::FutureExt;
 use httpapi::IndexStatus;
 use httpapi::PostIndexAnnFilter;
 use httpapi::PostIndexAnnRestriction;
 use httpclient::HttpClient;
+use httpclient::WaitOptions;
 use reqwest::StatusCode;
 use scylla::cluster::metadata::NativeType;
 use scylla::value::CqlValue;
 use std::num::NonZeroUsize;
 use std::sync::Arc;
+use std::time::Duration;
 use uuid::Uuid;
 use vector_store::ColumnName;
 use vector_store::DbIndexPartitioning;
@@ -80,6 +81,8 @@ fn make_index(
             expansion_search: Default::default(),
             space_type: Default::default(),
             quantization: Default::default(),
+            embedding_model: None,
+            ttl: None,
         }),
     }
 }
@@ -127,34 +130,22 @@ fn add_table(
     .unwrap();
 }
 
-async fn wait_for_serving(client: &HttpClient, index: &IndexMetadata) {
+async fn wait_for_status(client: &HttpClient, index: &IndexMetadata, status: IndexStatus) {
     let ks = index.keyspace_name.as_ref().into();
     let idx = index.index_name.as_ref().into();
-    wait_for(
-        || async {
-            client
-                .index_status(&ks, &idx)
-                .await
-                .is_ok_and(|s| s.status == IndexStatus::Serving)
-        },
-        &format!("index {} to be serving", index.index_name),
-    )
-    .await;
+    let wait = WaitOptions::new(Duration::from_secs(5)).with_interval(Duration::from_millis(10));
+    client
+        .wait_for_status(&ks, &idx, status, wait)
+        .await
+        .unwrap();
+}
+
+async fn wait_for_serving(client: &HttpClient, index: &IndexMetadata) {
+    wait_for_status(client, index, IndexStatus::Serving).await;
 }
 
 async fn wait_for_bootstrapping(client: &HttpClient, index: &IndexMetadata) {
-    let ks = index.keyspace_name.as_ref().into();
-    let idx = index.index_name.as_ref().into();
-    wait_for(
-        || async {
-            client
-                .index_status(&ks, &idx)
-                .await
-                .is_ok_and(|s| s.status == IndexStatus::Bootstrapping)
-        },
-        &format!("index {} to be bootstrapping", index.index_name),
-    )
-    .await;
+    wait_for_status(client, index, IndexStatus::Bootstrapping).await;
 }
 
 async fn post_ann(client: &HttpClient, index: &IndexMetadata) -> reqwest::Response {
@@ -7,8 +7,11 @@ use crate::usearch;
 use crate::usearch::test_config;
 use crate::wait_for;
 use httpapi::NodeStatus;
+use reqwest::StatusCode;
 use scylla::cluster::metadata::NativeType;
 use vector_store::DbIndexPartitioning;
+use vector_store::node_state::Event;
+use vector_store::node_state::NodeState;
 
 #[tokio::test]
 async fn status_is_serving_after_creation() {
@@ -57,3 +60,64 @@ async fn status_is_bootstrapping_while_discovering_indexes() {
     )
     .await;
 }
+
+#[tokio::test]
+async fn node_is_ready_while_serving_until_shutdown() {
+    crate::enable_tracing();
+    let (_index, client, _db, _server, node_state) = usearch::setup_store_and_wait_for_index(
+        DbIndexPartitioning::Global,
+        ["pk".into(), "ck".into()],
+        1,
+        [
+            ("pk".to_string().into(), NativeType::Int),
+            ("ck".to_string().into(), NativeType::Text),
+        ],
+        None,
+        None,
+        None,
+    )
+    .await;
+    assert_eq!(client.healthz().await.unwrap(), StatusCode::OK);
+    assert_eq!(client.readyz().await.unwrap(), StatusCode::OK);
+
+    node_state
+        .send(NodeState::SendEvent(Event::ShutdownStarted))
+        .await
+        .unwrap();
+    assert_eq!(
+        client.readyz().await.unwrap(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+    assert_eq!(client.healthz().await.unwrap(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn node_is_alive_but_not_ready_while_discovering_indexes() {
+    crate::enable_tracing();
+    let (run, _index, db, _node_state) = usearch::setup_store(
+        test_config(),
+        DbIndexPartitioning::Global,
+        ["pk".into(), "ck".into()],
+        1,
+        [
+            ("pk".to_string().into(), NativeType::Int),
+            ("ck".to_string().into(), NativeType::Text),
+        ],
+        None,
+        None,
+    )
+    .await;
+    db.simulate_endless_get_indexes_processing();
+    let (client, _server, _config_tx) = run.await;
+    wait_for(
+        || async { client.status().await.unwrap() == NodeStatus::Bootstrapping },
+        "Waiting for status to be Bootstrapping",
+    )
+    .await;
+
+    assert_eq!(client.healthz().await.unwrap(), StatusCode::OK);
+    assert_eq!(
+        client.readyz().await.unwrap(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+}
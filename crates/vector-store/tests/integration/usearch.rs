@@ -11,12 +11,14 @@ use crate::db_basic::ScanFn;
 use crate::db_basic::Table;
 use crate::wait_for;
 use crate::wait_for_value;
+use httpapi::IndexBuildProgress;
 use httpapi::IndexNotReadyReason;
 use httpapi::IndexStatus;
 use httpapi::PostIndexAnnFilter;
 use httpapi::PostIndexAnnResponse;
 use httpapi::PostIndexAnnRestriction;
 use httpclient::HttpClient;
+use itertools::Itertools;
 use reqwest::StatusCode;
 use scylla::cluster::metadata::NativeType;
 use scylla::value::CqlValue;
@@ -126,6 +128,8 @@ pub(crate) async fn setup_store_with_quantization(
             expansion_search: ExpansionSearch::default(),
             space_type: SpaceType::Euclidean,
             quantization,
+            embedding_model: None,
+            ttl: None,
         }),
     };
 
@@ -294,6 +298,26 @@ async fn simple_create_search_delete_index() {
     assert_eq!(primary_keys_pk.first().unwrap().as_i64().unwrap(), 2);
     assert_eq!(primary_keys_ck.first().unwrap().as_str().unwrap(), "two");
 
+    let stats = client
+        .index_stats(&keyspace_name, &index_name)
+        .await
+        .unwrap();
+    let memory = stats.memory;
+    assert!(memory.key_maps > 0);
+    assert_eq!(
+        memory.total,
+        memory.key_maps + memory.graph + memory.pending_queues
+    );
+    let graph = stats.graph.unwrap();
+    assert_eq!(graph.vectors, 3);
+    assert_eq!(graph.deleted_slots, 0);
+    assert_eq!(graph.partitions, 1);
+    assert_eq!(stats.searches.count, 1);
+    // Too few vectors for a reference of their distribution.
+    let drift = stats.drift.unwrap();
+    assert!(drift.ingested.is_none());
+    assert!(drift.queried.is_none());
+
     db.del_index(&index.keyspace_name, &index.index_name)
         .unwrap();
 
@@ -327,6 +351,8 @@ async fn failed_db_index_create() {
             expansion_search: Default::default(),
             space_type: Default::default(),
             quantization: Default::default(),
+            embedding_model: None,
+            ttl: None,
         }),
     };
 
@@ -568,6 +594,51 @@ async fn ann_fail_while_building_when_node_is_bootstrapping() {
     assert_eq!(reason, IndexNotReadyReason::NodeBootstrapping);
 }
 
+#[tokio::test]
+async fn index_status_reports_full_scan_progress() {
+    crate::enable_tracing();
+    let (run, index, db, _node_state) = setup_store(
+        test_config(),
+        DbIndexPartitioning::Global,
+        ["pk".into(), "ck".into()],
+        1,
+        [
+            ("pk".to_string().into(), NativeType::Int),
+            ("ck".to_string().into(), NativeType::Text),
+        ],
+        Some(db_basic::pending_scan_fn()),
+        None,
+    )
+    .await;
+    db.set_next_full_scan_progress(vector_store::Progress::InProgress(
+        Percentage::try_from(25.0).unwrap(),
+    ));
+    let (client, _server, _config_tx) = run.await;
+
+    let keyspace_name = index.keyspace_name.into();
+    let index_name = index.index_name.into();
+
+    let status = wait_for_value(
+        || async {
+            client
+                .index_status(&keyspace_name, &index_name)
+                .await
+                .ok()
+                .filter(|status| status.status == IndexStatus::Bootstrapping)
+        },
+        "Waiting for index to be bootstrapping",
+    )
+    .await;
+    assert_eq!(
+        status.progress,
+        Some(IndexBuildProgress {
+            percent: 25.0,
+            processed_rows: 0,
+            total_rows: Some(0),
+        })
+    );
+}
+
 #[tokio::test]
 async fn ann_fail_while_building_when_node_is_serving() {
     crate::enable_tracing();
@@ -1718,6 +1789,8 @@ async fn similarity_scores_are_decreasing_and_correctly_converted() {
             expansion_search: ExpansionSearch::default(),
             space_type: SpaceType::Euclidean,
             quantization: Quantization::default(),
+            embedding_model: None,
+            ttl: None,
         }),
     };
 
@@ -1896,3 +1969,136 @@ async fn empty_index_returns_empty_ann_results() {
     assert!(distances.is_empty());
     assert!(similarity_scores.is_empty());
 }
+
+#[tokio::test]
+async fn ann_batch_returns_results_in_order_of_queries() {
+    crate::enable_tracing();
+
+    let (index, client, _db, _server, _node_state) = setup_store_and_wait_for_index(
+        DbIndexPartitioning::Global,
+        ["pk".into()],
+        1,
+        [("pk".to_string().into(), NativeType::Int)],
+        Some(db_basic::scan_fn_vectors((1..=3).map(|pk| {
+            (
+                [CqlValue::Int(pk)].into(),
+                Some(vec![pk as f32, 0., 0.].into()),
+                [].into(),
+                Timestamp::from_millis(10),
+            )
+        }))),
+        None,
+        Some(3),
+    )
+    .await;
+    let keyspace_name = index.keyspace_name.clone().into();
+    let index_name = index.index_name.clone().into();
+
+    let response = client
+        .ann_batch(
+            &keyspace_name,
+            &index_name,
+            vec![
+                (vec![3., 0., 0.].into(), None),
+                (
+                    vec![1., 0., 0.].into(),
+                    Some(NonZeroUsize::new(2).unwrap().into()),
+                ),
+            ],
+            NonZeroUsize::new(1).unwrap().into(),
+        )
+        .await
+        .unwrap();
+    let pks: Vec<Vec<i64>> = response
+        .results
+        .iter()
+        .map(|result| {
+            result.primary_keys[&"pk".into()]
+                .iter()
+                .map(|pk| pk.as_i64().unwrap())
+                .collect()
+        })
+        .collect();
+    assert_eq!(pks, [vec![3], vec![1, 2]]);
+    assert_eq!(response.results[1].distances.len(), 2);
+
+    let err = client
+        .ann_batch(
+            &keyspace_name,
+            &index_name,
+            vec![(vec![1., 0., 0.].into(), None), (vec![1.].into(), None)],
+            NonZeroUsize::new(1).unwrap().into(),
+        )
+        .await;
+    assert!(err.is_err());
+}
+
+#[tokio::test]
+async fn range_returns_vectors_within_distance() {
+    crate::enable_tracing();
+
+    let (index, client, _db, _server, _node_state) = setup_store_and_wait_for_index(
+        DbIndexPartitioning::Global,
+        ["pk".into()],
+        1,
+        [("pk".to_string().into(), NativeType::Int)],
+        Some(db_basic::scan_fn_vectors((1..=100).map(|pk| {
+            (
+                [CqlValue::Int(pk)].into(),
+                Some(vec![pk as f32, 0., 0.].into()),
+                [].into(),
+                Timestamp::from_millis(10),
+            )
+        }))),
+        None,
+        Some(100),
+    )
+    .await;
+    let keyspace_name = index.keyspace_name.clone().into();
+    let index_name = index.index_name.clone().into();
+    let pks = |response: &PostIndexAnnResponse| -> Vec<i64> {
+        response.primary_keys[&"pk".into()]
+            .iter()
+            .map(|pk| pk.as_i64().unwrap())
+            .collect()
+    };
+
+    // Squared Euclidean distances of 1 and 4.
+    let response = client
+        .range(&keyspace_name, &index_name, vec![3., 0., 0.].into(), 4.)
+        .await
+        .unwrap();
+    let found = pks(&response);
+    assert_eq!(found[0], 3);
+    assert_eq!(
+        found.iter().copied().sorted().collect_vec(),
+        [1, 2, 3, 4, 5]
+    );
+    assert_eq!(response.distances.len(), 5);
+
+    // More vectors than the first search of a range.
+    let response = client
+        .range(
+            &keyspace_name,
+            &index_name,
+            vec![0., 0., 0.].into(),
+            10_000.,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        pks(&response).into_iter().sorted().collect_vec(),
+        (1..=100).collect_vec()
+    );
+
+    let response = client
+        .range(&keyspace_name, &index_name, vec![-10., 0., 0.].into(), 1.)
+        .await
+        .unwrap();
+    assert!(pks(&response).is_empty());
+
+    let err = client
+        .range(&keyspace_name, &index_name, vec![1.].into(), 1.)
+        .await;
+    assert!(err.is_err());
+}